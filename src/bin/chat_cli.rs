@@ -102,6 +102,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             content: vec![ContentPart::Text {
                 text: user_text.to_string(),
             }],
+            cache_hint: None,
         });
 
         let mut turn_failed = false;
@@ -110,16 +111,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 model: ModelRef {
                     provider_hint: Some(config.provider.provider_id()),
                     model_id: config.model.clone(),
+                    requirements: None,
                 },
                 messages: history.clone(),
                 tools: built_in_tools(),
+                hosted_tools: Vec::new(),
                 tool_choice: ToolChoice::Auto,
+                parallel_tool_calls: None,
                 response_format: ResponseFormat::Text,
                 temperature: None,
                 top_p: None,
                 max_output_tokens: config.max_output_tokens,
                 stop: Vec::new(),
                 metadata: BTreeMap::new(),
+                n: None,
+                seed: None,
+                provider_options: BTreeMap::new(),
             };
 
             let response = match runtime.run(req).await {
@@ -135,17 +142,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             history.push(Message {
                 role: MessageRole::Assistant,
                 content: assistant_content.clone(),
+                cache_hint: None,
             });
 
             let mut printed_any = false;
             let mut tool_calls = Vec::new();
             for part in assistant_content {
                 match part {
-                    ContentPart::Text { text } => {
-                        if !text.trim().is_empty() {
-                            println!("{text}");
-                            printed_any = true;
-                        }
+                    ContentPart::Text { text } if !text.trim().is_empty() => {
+                        println!("{text}");
+                        printed_any = true;
                     }
                     ContentPart::ToolCall { tool_call } => {
                         println!(
@@ -162,6 +168,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         );
                         printed_any = true;
                     }
+                    ContentPart::Thinking { text, .. } if !text.trim().is_empty() => {
+                        println!("[thinking: {text}]");
+                    }
+                    _ => {}
                 }
             }
 
@@ -185,6 +195,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 history.push(Message {
                     role: MessageRole::Tool,
                     content: vec![ContentPart::ToolResult { tool_result }],
+                    cache_hint: None,
                 });
             }
         }
@@ -211,10 +222,12 @@ fn built_in_tools() -> Vec<ToolDefinition> {
             "required": [],
             "additionalProperties": false
         }),
+        cache_hint: None,
     }]
 }
 
 fn execute_tool(tool_call: &ToolCall) -> ToolResult {
+    let is_error = tool_call.name != "time_now";
     let output_value = match tool_call.name.as_str() {
         "time_now" => {
             let unix_seconds = SystemTime::now()
@@ -239,6 +252,7 @@ fn execute_tool(tool_call: &ToolCall) -> ToolResult {
             value: output_value,
         },
         raw_provider_content: None,
+        is_error,
     }
 }
 
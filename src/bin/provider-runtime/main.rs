@@ -0,0 +1,348 @@
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+use std::sync::Arc;
+
+use provider_runtime::core::types::{
+    ContentPart, DiscoveryOptions, Message, MessageRole, ModelCatalog, ModelRef, ProviderId,
+    ProviderRequest, ProviderResponse, ResponseFormat, ToolChoice,
+};
+use provider_runtime::pricing::PricingTable;
+use provider_runtime::providers::anthropic::AnthropicAdapter;
+use provider_runtime::providers::openai::OpenAiAdapter;
+use provider_runtime::providers::openrouter::OpenRouterAdapter;
+use provider_runtime::{ProviderRuntime, ProviderRuntimeBuilder};
+
+type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+enum Command {
+    Run(RunArgs),
+    Catalog(CatalogArgs),
+    Validate(ValidateTarget),
+}
+
+enum ValidateTarget {
+    Config(String),
+    Catalog(String),
+}
+
+struct RunArgs {
+    model: String,
+    provider: Option<ProviderId>,
+    prompt_file: Option<String>,
+    system: Option<String>,
+    max_output_tokens: Option<u32>,
+}
+
+struct CatalogArgs {
+    remote: bool,
+    providers: Vec<ProviderId>,
+    output: Option<String>,
+}
+
+#[tokio::main]
+async fn main() {
+    let _ = dotenvy::dotenv();
+
+    let result = match parse_command(std::env::args().skip(1).collect()) {
+        Ok(Command::Run(args)) => run(args).await,
+        Ok(Command::Catalog(args)) => catalog(args).await,
+        Ok(Command::Validate(target)) => validate(target),
+        Err(error) => Err(error),
+    };
+
+    if let Err(error) = result {
+        eprintln!("provider-runtime: {error}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(args: RunArgs) -> CliResult<()> {
+    let prompt = match &args.prompt_file {
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|error| format!("failed to read prompt file {path}: {error}"))?,
+        None => {
+            let mut prompt = String::new();
+            io::stdin().read_to_string(&mut prompt)?;
+            prompt
+        }
+    };
+    if prompt.trim().is_empty() {
+        return Err("prompt is empty".into());
+    }
+
+    let mut messages = Vec::new();
+    if let Some(system) = args.system {
+        messages.push(text_message(MessageRole::System, system));
+    }
+    messages.push(text_message(MessageRole::User, prompt));
+
+    let request = ProviderRequest {
+        model: ModelRef {
+            provider_hint: args.provider,
+            model_id: args.model,
+            requirements: None,
+        },
+        messages,
+        tools: Vec::new(),
+        hosted_tools: Vec::new(),
+        tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
+        response_format: ResponseFormat::Text,
+        temperature: None,
+        top_p: None,
+        max_output_tokens: args.max_output_tokens,
+        stop: Vec::new(),
+        metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
+    };
+
+    let response = build_runtime()?.run(request).await?;
+    println!("{}", response_text(&response));
+    print_summary(&response);
+    Ok(())
+}
+
+async fn catalog(args: CatalogArgs) -> CliResult<()> {
+    let runtime = build_runtime()?;
+    let catalog = if args.remote {
+        let report = runtime
+            .discover_models_report(DiscoveryOptions {
+                remote: true,
+                include_provider: args.providers,
+                refresh_cache: true,
+            })
+            .await;
+        for warning in report.warnings() {
+            eprintln!("warning: {}", warning.message);
+        }
+        report.catalog
+    } else {
+        let mut catalog = runtime
+            .discover_models(DiscoveryOptions {
+                remote: false,
+                include_provider: Vec::new(),
+                refresh_cache: false,
+            })
+            .await?;
+        if !args.providers.is_empty() {
+            catalog
+                .models
+                .retain(|model| args.providers.contains(&model.provider));
+        }
+        catalog
+    };
+
+    let json = runtime.export_catalog_json(&catalog)?;
+    match args.output {
+        Some(path) => {
+            std::fs::write(&path, json)
+                .map_err(|error| format!("failed to write {path}: {error}"))?;
+            eprintln!("wrote {} models to {path}", catalog.models.len());
+        }
+        None => println!("{json}"),
+    }
+    Ok(())
+}
+
+/// Loads a runtime config TOML file or a model catalog JSON file through the same library
+/// entry points the runtime uses, so both accept and reject the same files.
+fn validate(target: ValidateTarget) -> CliResult<()> {
+    match target {
+        ValidateTarget::Config(path) => {
+            ProviderRuntimeBuilder::from_config_path(&path)
+                .and_then(ProviderRuntimeBuilder::try_build)
+                .map_err(|error| format!("{path}: {error}"))?;
+            println!("{path}: ok");
+        }
+        ValidateTarget::Catalog(path) => {
+            let catalog =
+                ModelCatalog::from_path(&path).map_err(|error| format!("{path}: {error}"))?;
+            println!("{path}: ok ({} models)", catalog.models.len());
+        }
+    }
+    Ok(())
+}
+
+fn build_runtime() -> CliResult<ProviderRuntime> {
+    Ok(ProviderRuntime::builder()
+        .with_pricing_table(PricingTable::builtin())
+        .with_adapter(Arc::new(
+            OpenAiAdapter::new(None).map_err(|e| format!("failed to build OpenAI adapter: {e}"))?,
+        ))
+        .with_adapter(Arc::new(
+            AnthropicAdapter::new(None)
+                .map_err(|e| format!("failed to build Anthropic adapter: {e}"))?,
+        ))
+        .with_adapter(Arc::new(
+            OpenRouterAdapter::new(None)
+                .map_err(|e| format!("failed to build OpenRouter adapter: {e}"))?,
+        ))
+        .build())
+}
+
+fn text_message(role: MessageRole, text: String) -> Message {
+    Message {
+        role,
+        content: vec![ContentPart::Text { text }],
+        cache_hint: None,
+    }
+}
+
+fn response_text(response: &ProviderResponse) -> String {
+    response
+        .output
+        .content
+        .iter()
+        .filter_map(|part| match part {
+            ContentPart::Text { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn print_summary(response: &ProviderResponse) {
+    let usage = &response.usage;
+    let tokens = |value: Option<u64>| value.map_or_else(|| "-".to_string(), |v| v.to_string());
+    eprintln!(
+        "provider={:?} model={} finish_reason={:?}",
+        response.provider, response.model, response.finish_reason
+    );
+    eprintln!(
+        "usage: input={} output={} cached_input={} reasoning={} total={}",
+        tokens(usage.input_tokens),
+        tokens(usage.output_tokens),
+        tokens(usage.cached_input_tokens),
+        tokens(usage.reasoning_tokens),
+        tokens(usage.total_tokens),
+    );
+    match &response.cost {
+        Some(cost) => eprintln!(
+            "cost: {:.6} {} (input {:.6}, output {:.6})",
+            cost.total_cost, cost.currency, cost.input_cost, cost.output_cost
+        ),
+        None => eprintln!("cost: unavailable"),
+    }
+    for warning in &response.warnings {
+        eprintln!("warning: [{}] {}", warning.code, warning.message);
+    }
+}
+
+fn parse_command(args: Vec<String>) -> CliResult<Command> {
+    let Some((command, rest)) = args.split_first() else {
+        print_help();
+        std::process::exit(2);
+    };
+
+    match command.as_str() {
+        "run" => parse_run_args(rest).map(Command::Run),
+        "catalog" => parse_catalog_args(rest).map(Command::Catalog),
+        "validate" => match rest {
+            [flag, path] if flag == "--catalog" => {
+                Ok(Command::Validate(ValidateTarget::Catalog(path.clone())))
+            }
+            [path] if !path.starts_with("--") => {
+                Ok(Command::Validate(ValidateTarget::Config(path.clone())))
+            }
+            _ => {
+                Err("usage: provider-runtime validate CONFIG_TOML | --catalog CATALOG_JSON".into())
+            }
+        },
+        "--help" | "-h" | "help" => {
+            print_help();
+            std::process::exit(0);
+        }
+        other => Err(format!("unknown command: {other}").into()),
+    }
+}
+
+fn parse_run_args(args: &[String]) -> CliResult<RunArgs> {
+    let mut run = RunArgs {
+        model: String::new(),
+        provider: None,
+        prompt_file: None,
+        system: None,
+        max_output_tokens: None,
+    };
+
+    let mut i = 0usize;
+    while i < args.len() {
+        let value = || flag_value(args, i);
+        match args[i].as_str() {
+            "--model" => run.model = value()?.trim().to_string(),
+            "--provider" => run.provider = Some(parse_provider(value()?)?),
+            "--prompt-file" => run.prompt_file = Some(value()?.to_string()),
+            "--system" => run.system = Some(value()?.to_string()),
+            "--max-output-tokens" => {
+                run.max_output_tokens = Some(
+                    value()?
+                        .parse::<u32>()
+                        .map_err(|_| "--max-output-tokens must be a positive integer")?,
+                );
+            }
+            other => return Err(format!("unknown argument for run: {other}").into()),
+        }
+        i += 2;
+    }
+
+    if run.model.is_empty() {
+        return Err("run requires --model".into());
+    }
+    Ok(run)
+}
+
+fn parse_catalog_args(args: &[String]) -> CliResult<CatalogArgs> {
+    let mut catalog = CatalogArgs {
+        remote: false,
+        providers: Vec::new(),
+        output: None,
+    };
+
+    let mut i = 0usize;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--remote" => {
+                catalog.remote = true;
+                i += 1;
+            }
+            "--provider" => {
+                catalog
+                    .providers
+                    .push(parse_provider(flag_value(args, i)?)?);
+                i += 2;
+            }
+            "--output" => {
+                catalog.output = Some(flag_value(args, i)?.to_string());
+                i += 2;
+            }
+            other => return Err(format!("unknown argument for catalog: {other}").into()),
+        }
+    }
+
+    Ok(catalog)
+}
+
+fn flag_value(args: &[String], index: usize) -> CliResult<&str> {
+    args.get(index + 1)
+        .map(String::as_str)
+        .ok_or_else(|| format!("missing value for {}", args[index]).into())
+}
+
+fn parse_provider(value: &str) -> CliResult<ProviderId> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "openai" => Ok(ProviderId::Openai),
+        "anthropic" => Ok(ProviderId::Anthropic),
+        "openrouter" => Ok(ProviderId::Openrouter),
+        _ => {
+            Err(format!("invalid provider '{value}' (expected openai|anthropic|openrouter)").into())
+        }
+    }
+}
+
+fn print_help() {
+    println!(
+        "Usage:\n  provider-runtime run --model MODEL [--provider openai|anthropic|openrouter] [--prompt-file FILE] [--system TEXT] [--max-output-tokens N]\n  provider-runtime catalog [--remote] [--provider NAME]... [--output FILE]\n  provider-runtime validate CONFIG_TOML\n  provider-runtime validate --catalog CATALOG_JSON\n\nThe prompt is read from stdin when --prompt-file is omitted. Usage, cost (from the bundled\nprice list), and warnings are printed to stderr. `validate` checks a runtime config TOML file,\nor with --catalog a model catalog JSON file such as `catalog --output` writes.\n\nEnv:\n  OPENAI_API_KEY / ANTHROPIC_API_KEY / OPENROUTER_API_KEY"
+    );
+}
@@ -1,5 +1,173 @@
-use crate::core::error::{RoutingError, RuntimeError};
-use crate::core::types::{ModelCatalog, ModelInfo, ProviderId};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::core::error::{ConfigError, ErrorSource, RoutingError, RuntimeError};
+use crate::core::types::{
+    ModelAlias, ModelCatalog, ModelInfo, ModelPreference, ModelRequirements, ProviderId,
+};
+
+/// Schema version written by [`export_catalog_json`]. Catalog files without a `version` field
+/// are read as version 1.
+pub const CATALOG_SCHEMA_VERSION: u32 = 1;
+
+const KNOWN_PROVIDER_TYPES: &[&str] = &["openai", "anthropic", "openrouter"];
+
+/// Criteria for [`ModelCatalog::query`]. Every set field must match; the default matches all
+/// models.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CatalogFilter {
+    /// Only these providers; empty allows any.
+    pub providers: Vec<ProviderId>,
+    pub requires_tools: bool,
+    pub requires_structured_output: bool,
+    /// Models without a known context window are excluded when set.
+    pub min_context_window: Option<u32>,
+    /// Model id pattern where `*` matches any run of characters, such as `claude-*` or `*mini*`.
+    pub model_id_glob: Option<String>,
+    /// Models without published pricing are excluded when either ceiling is set.
+    pub max_input_cost_per_token: Option<f64>,
+    pub max_output_cost_per_token: Option<f64>,
+}
+
+impl CatalogFilter {
+    pub fn matches(&self, model: &ModelInfo) -> bool {
+        if !self.providers.is_empty() && !self.providers.contains(&model.provider) {
+            return false;
+        }
+        if (self.requires_tools && !model.supports_tools)
+            || (self.requires_structured_output && !model.supports_structured_output)
+        {
+            return false;
+        }
+        if let Some(min_context_window) = self.min_context_window
+            && model
+                .context_window
+                .is_none_or(|context_window| context_window < min_context_window)
+        {
+            return false;
+        }
+        if let Some(glob) = &self.model_id_glob
+            && !glob_matches(glob, &model.model_id)
+        {
+            return false;
+        }
+        if self.max_input_cost_per_token.is_some() || self.max_output_cost_per_token.is_some() {
+            let Some(pricing) = &model.pricing else {
+                return false;
+            };
+            let within = |ceiling: Option<f64>, rate: f64| ceiling.is_none_or(|max| rate <= max);
+            if !within(self.max_input_cost_per_token, pricing.input_cost_per_token)
+                || !within(
+                    self.max_output_cost_per_token,
+                    pricing.output_cost_per_token,
+                )
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl ModelCatalog {
+    /// The models matching `filter`, in catalog order.
+    pub fn query(&self, filter: &CatalogFilter) -> Vec<&ModelInfo> {
+        self.models
+            .iter()
+            .filter(|model| filter.matches(model))
+            .collect()
+    }
+
+    /// Parses a catalog in the format written by [`export_catalog_json`]. Model ids must be
+    /// non-empty and unique per provider, and providers must be ones this crate knows.
+    pub fn from_json_str(contents: &str) -> Result<Self, ConfigError> {
+        let document: serde_json::Value = serde_json::from_str(contents)
+            .map_err(|error| invalid_catalog(format!("malformed catalog json: {error}")))?;
+        let Some(fields) = document.as_object() else {
+            return Err(invalid_catalog("catalog must be a json object"));
+        };
+        if let Some(field) = fields
+            .keys()
+            .find(|field| !matches!(field.as_str(), "version" | "models"))
+        {
+            return Err(invalid_catalog(format!("unknown field `{field}`")));
+        }
+
+        let version = match fields.get("version") {
+            None => CATALOG_SCHEMA_VERSION,
+            Some(value) => value
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or_else(|| invalid_catalog("version must be a positive integer"))?,
+        };
+        if version != CATALOG_SCHEMA_VERSION {
+            return Err(invalid_catalog(format!(
+                "unsupported catalog version {version}; expected {CATALOG_SCHEMA_VERSION}"
+            )));
+        }
+
+        let entries = match fields.get("models") {
+            None => &Vec::new(),
+            Some(serde_json::Value::Array(entries)) => entries,
+            Some(_) => return Err(invalid_catalog("models must be an array")),
+        };
+
+        let mut seen = BTreeSet::new();
+        let mut models = Vec::with_capacity(entries.len());
+        for (index, entry) in entries.iter().enumerate() {
+            let provider_type = entry
+                .pointer("/provider/type")
+                .and_then(serde_json::Value::as_str);
+            if let Some(provider_type) = provider_type
+                && !KNOWN_PROVIDER_TYPES.contains(&provider_type)
+            {
+                return Err(invalid_catalog(format!(
+                    "models[{index}].provider: unknown provider `{provider_type}`"
+                )));
+            }
+
+            let model: ModelInfo = serde_json::from_value(entry.clone())
+                .map_err(|error| invalid_catalog(format!("models[{index}]: {error}")))?;
+            if model.model_id.trim().is_empty() {
+                return Err(invalid_catalog(format!(
+                    "models[{index}].model_id must not be empty"
+                )));
+            }
+            if !seen.insert((model.provider.clone(), model.model_id.clone())) {
+                return Err(invalid_catalog(format!(
+                    "models[{index}].model_id: duplicate model `{}` for provider {:?}",
+                    model.model_id, model.provider
+                )));
+            }
+            models.push(model);
+        }
+
+        Ok(Self { models })
+    }
+
+    /// Reads a catalog file written by [`export_catalog_json`].
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|error| {
+            invalid_catalog(format!("failed to read {}: {error}", path.display()))
+        })?;
+        Self::from_json_str(&contents)
+    }
+}
+
+fn invalid_catalog(reason: impl Into<String>) -> ConfigError {
+    ConfigError::InvalidCatalog {
+        reason: reason.into(),
+    }
+}
+
+#[derive(Serialize)]
+struct CatalogFile<'a> {
+    version: u32,
+    models: &'a [ModelInfo],
+}
 
 pub fn merge_static_and_remote_catalog(
     static_catalog: &ModelCatalog,
@@ -82,15 +250,86 @@ pub fn resolve_model_provider(
     })
 }
 
+/// Finds the alias entry for `model_id`. With a `provider_hint` only that provider's entry
+/// matches; otherwise the first entry whose provider is `available` wins, so aliases listed
+/// earlier take priority.
+pub fn resolve_model_alias<'a>(
+    aliases: &'a [ModelAlias],
+    model_id: &str,
+    provider_hint: Option<&ProviderId>,
+    available: impl Fn(&ProviderId) -> bool,
+) -> Option<&'a ModelAlias> {
+    aliases.iter().find(|alias| {
+        alias.alias == model_id
+            && match provider_hint {
+                Some(hint) => alias.provider == *hint,
+                None => available(&alias.provider),
+            }
+    })
+}
+
+/// Picks the model that best meets `requirements` among those whose provider is `available`,
+/// limited to `provider_hint` when given. `latency` reports a model's observed latency in
+/// milliseconds for [`ModelPreference::Fastest`]. Ties keep catalog order.
+pub fn select_model<'a>(
+    catalog: &'a ModelCatalog,
+    requirements: &ModelRequirements,
+    provider_hint: Option<&ProviderId>,
+    available: impl Fn(&ProviderId) -> bool,
+    latency: impl Fn(&ModelInfo) -> Option<f64>,
+) -> Option<&'a ModelInfo> {
+    let per_token = |per_million: Option<f64>| per_million.map(|cost| cost / 1_000_000.0);
+    let filter = CatalogFilter {
+        providers: provider_hint.into_iter().cloned().collect(),
+        requires_tools: requirements.requires_tools,
+        requires_structured_output: requirements.requires_structured_output,
+        min_context_window: requirements.min_context_window,
+        model_id_glob: None,
+        max_input_cost_per_token: per_token(requirements.max_input_cost_per_million),
+        max_output_cost_per_token: per_token(requirements.max_output_cost_per_million),
+    };
+
+    let price = |model: &ModelInfo| {
+        model.pricing.as_ref().map_or(f64::INFINITY, |pricing| {
+            pricing.input_cost_per_token + pricing.output_cost_per_token
+        })
+    };
+    let rank = |model: &ModelInfo| match requirements.prefer {
+        ModelPreference::Cheapest => (price(model), 0.0),
+        ModelPreference::Fastest => (latency(model).unwrap_or(f64::INFINITY), price(model)),
+    };
+
+    catalog
+        .query(&filter)
+        .into_iter()
+        .filter(|model| available(&model.provider))
+        .map(|model| (rank(model), model))
+        .reduce(|best, candidate| {
+            if candidate.0 < best.0 {
+                candidate
+            } else {
+                best
+            }
+        })
+        .map(|(_, model)| model)
+}
+
+/// Writes `catalog` as pretty JSON with models in a stable order, tagged with
+/// [`CATALOG_SCHEMA_VERSION`]. [`ModelCatalog::from_json_str`] reads it back.
 pub fn export_catalog_json(catalog: &ModelCatalog) -> Result<String, RuntimeError> {
     let mut normalized = catalog.clone();
     sort_models(&mut normalized.models);
 
-    serde_json::to_string_pretty(&normalized).map_err(|error| RuntimeError::SerializationError {
+    let file = CatalogFile {
+        version: CATALOG_SCHEMA_VERSION,
+        models: &normalized.models,
+    };
+    serde_json::to_string_pretty(&file).map_err(|error| RuntimeError::SerializationError {
         provider: None,
         model: None,
         request_id: None,
         message: error.to_string(),
+        source: Some(ErrorSource::new(error)),
     })
 }
 
@@ -105,6 +344,11 @@ pub(crate) fn builtin_static_catalog() -> ModelCatalog {
                 max_output_tokens: None,
                 supports_tools: true,
                 supports_structured_output: true,
+                pricing: None,
+                input_modalities: Vec::new(),
+                output_modalities: Vec::new(),
+                deprecated: false,
+                knowledge_cutoff: None,
             },
             ModelInfo {
                 provider: ProviderId::Anthropic,
@@ -114,6 +358,11 @@ pub(crate) fn builtin_static_catalog() -> ModelCatalog {
                 max_output_tokens: None,
                 supports_tools: true,
                 supports_structured_output: true,
+                pricing: None,
+                input_modalities: Vec::new(),
+                output_modalities: Vec::new(),
+                deprecated: false,
+                knowledge_cutoff: None,
             },
             ModelInfo {
                 provider: ProviderId::Openrouter,
@@ -123,11 +372,36 @@ pub(crate) fn builtin_static_catalog() -> ModelCatalog {
                 max_output_tokens: None,
                 supports_tools: true,
                 supports_structured_output: true,
+                pricing: None,
+                input_modalities: Vec::new(),
+                output_modalities: Vec::new(),
+                deprecated: false,
+                knowledge_cutoff: None,
             },
         ],
     }
 }
 
+fn glob_matches(glob: &str, value: &str) -> bool {
+    let mut parts = glob.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = value.strip_prefix(first) else {
+        return false;
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
 fn model_key(model: &ModelInfo) -> (ProviderId, &str) {
     (model.provider.clone(), model.model_id.as_str())
 }
@@ -154,6 +428,24 @@ fn fill_missing_optional_metadata(target: &mut ModelInfo, source: &ModelInfo) {
     if target.max_output_tokens.is_none() {
         target.max_output_tokens = source.max_output_tokens;
     }
+
+    if target.pricing.is_none() {
+        target.pricing = source.pricing.clone();
+    }
+
+    if target.input_modalities.is_empty() {
+        target.input_modalities = source.input_modalities.clone();
+    }
+
+    if target.output_modalities.is_empty() {
+        target.output_modalities = source.output_modalities.clone();
+    }
+
+    if target.knowledge_cutoff.is_none() {
+        target.knowledge_cutoff = source.knowledge_cutoff.clone();
+    }
+
+    target.deprecated |= source.deprecated;
 }
 
 fn unique_providers_for_model(catalog: &ModelCatalog, model_id: &str) -> Vec<ProviderId> {
@@ -1,4 +1,5 @@
 use super::*;
+use crate::core::types::{ModelPreference, ModelPricing, ModelRequirements};
 
 fn model(
     provider: ProviderId,
@@ -17,6 +18,11 @@ fn model(
         max_output_tokens,
         supports_tools,
         supports_structured_output,
+        pricing: None,
+        input_modalities: Vec::new(),
+        output_modalities: Vec::new(),
+        deprecated: false,
+        knowledge_cutoff: None,
     }
 }
 
@@ -300,3 +306,442 @@ fn test_builtin_static_catalog_contains_minimal_seed() {
         model.provider == ProviderId::Openrouter && model.model_id == "openrouter/auto"
     }));
 }
+
+#[test]
+fn test_catalog_query_filters() {
+    let mut priced = model(
+        ProviderId::Openrouter,
+        "openai/gpt-5-mini",
+        None,
+        Some(400_000),
+        None,
+        true,
+        true,
+    );
+    priced.pricing = Some(ModelPricing {
+        input_cost_per_token: 0.00000025,
+        output_cost_per_token: 0.000002,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+    });
+    let catalog = ModelCatalog {
+        models: vec![
+            model(
+                ProviderId::Openai,
+                "gpt-5-mini",
+                None,
+                Some(400_000),
+                None,
+                true,
+                true,
+            ),
+            model(
+                ProviderId::Anthropic,
+                "claude-haiku-4-5",
+                None,
+                Some(200_000),
+                None,
+                true,
+                false,
+            ),
+            model(
+                ProviderId::Openai,
+                "text-embedding-3-small",
+                None,
+                None,
+                None,
+                false,
+                false,
+            ),
+            priced,
+        ],
+    };
+    let ids = |filter: CatalogFilter| {
+        catalog
+            .query(&filter)
+            .into_iter()
+            .map(|model| model.model_id.as_str())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(ids(CatalogFilter::default()).len(), 4);
+    assert_eq!(
+        ids(CatalogFilter {
+            providers: vec![ProviderId::Openai],
+            requires_tools: true,
+            ..CatalogFilter::default()
+        }),
+        vec!["gpt-5-mini"]
+    );
+    assert_eq!(
+        ids(CatalogFilter {
+            requires_structured_output: true,
+            min_context_window: Some(300_000),
+            ..CatalogFilter::default()
+        }),
+        vec!["gpt-5-mini", "openai/gpt-5-mini"]
+    );
+    assert_eq!(
+        ids(CatalogFilter {
+            model_id_glob: Some("*gpt-5*".to_string()),
+            ..CatalogFilter::default()
+        }),
+        vec!["gpt-5-mini", "openai/gpt-5-mini"]
+    );
+    assert_eq!(
+        ids(CatalogFilter {
+            model_id_glob: Some("claude-*-4-5".to_string()),
+            ..CatalogFilter::default()
+        }),
+        vec!["claude-haiku-4-5"]
+    );
+    assert_eq!(
+        ids(CatalogFilter {
+            max_output_cost_per_token: Some(0.00001),
+            ..CatalogFilter::default()
+        }),
+        vec!["openai/gpt-5-mini"]
+    );
+    assert!(
+        ids(CatalogFilter {
+            max_input_cost_per_token: Some(0.0000001),
+            ..CatalogFilter::default()
+        })
+        .is_empty()
+    );
+}
+
+#[test]
+fn test_glob_matches() {
+    assert!(glob_matches("gpt-5", "gpt-5"));
+    assert!(!glob_matches("gpt-5", "gpt-5-mini"));
+    assert!(glob_matches("*", ""));
+    assert!(glob_matches("a*a", "aa"));
+    assert!(!glob_matches("a*a", "a"));
+    assert!(glob_matches("*mini", "gpt-5-mini"));
+    assert!(!glob_matches("*mini*x", "gpt-5-mini"));
+}
+
+#[test]
+fn test_merge_fills_modalities_cutoff_and_deprecation() {
+    let static_catalog = ModelCatalog {
+        models: vec![model(
+            ProviderId::Openrouter,
+            "openai/gpt-4o",
+            Some("Static"),
+            None,
+            None,
+            true,
+            true,
+        )],
+    };
+    let mut remote = model(
+        ProviderId::Openrouter,
+        "openai/gpt-4o",
+        Some("Remote"),
+        None,
+        None,
+        true,
+        true,
+    );
+    remote.input_modalities = vec!["text".to_string(), "image".to_string()];
+    remote.output_modalities = vec!["text".to_string()];
+    remote.knowledge_cutoff = Some("2023-10".to_string());
+    remote.deprecated = true;
+
+    let merged = merge_static_and_remote_catalog(
+        &static_catalog,
+        &ModelCatalog {
+            models: vec![remote],
+        },
+    );
+
+    let merged = &merged.models[0];
+    assert_eq!(merged.display_name.as_deref(), Some("Static"));
+    assert_eq!(merged.input_modalities, vec!["text", "image"]);
+    assert_eq!(merged.output_modalities, vec!["text"]);
+    assert_eq!(merged.knowledge_cutoff.as_deref(), Some("2023-10"));
+    assert!(merged.deprecated);
+}
+
+fn invalid_catalog_reason(contents: &str) -> String {
+    match ModelCatalog::from_json_str(contents) {
+        Err(ConfigError::InvalidCatalog { reason }) => reason,
+        other => panic!("expected invalid catalog error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_catalog_json_round_trips_through_export() {
+    let mut priced = model(
+        ProviderId::Openrouter,
+        "openai/gpt-5-mini",
+        Some("GPT-5 Mini"),
+        Some(400_000),
+        Some(128_000),
+        true,
+        true,
+    );
+    priced.pricing = Some(ModelPricing {
+        input_cost_per_token: 0.000_000_25,
+        output_cost_per_token: 0.000_002,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+    });
+    priced.input_modalities = vec!["text".to_string(), "image".to_string()];
+    let catalog = ModelCatalog {
+        models: vec![
+            model(
+                ProviderId::Openai,
+                "gpt-5-mini",
+                None,
+                None,
+                None,
+                true,
+                true,
+            ),
+            model(
+                ProviderId::Anthropic,
+                "claude-sonnet-4-5",
+                None,
+                Some(200_000),
+                None,
+                true,
+                false,
+            ),
+            priced,
+        ],
+    };
+
+    let exported = export_catalog_json(&catalog).expect("export should succeed");
+    let parsed: serde_json::Value =
+        serde_json::from_str(&exported).expect("export should be valid json");
+    assert_eq!(parsed["version"], CATALOG_SCHEMA_VERSION);
+
+    let imported = ModelCatalog::from_json_str(&exported).expect("import should succeed");
+    assert_eq!(imported, catalog);
+    assert_eq!(
+        export_catalog_json(&imported).expect("export should succeed"),
+        exported
+    );
+}
+
+#[test]
+fn test_catalog_from_json_str_defaults_missing_version() {
+    let catalog = ModelCatalog::from_json_str(
+        r#"{"models":[{"provider":{"type":"openai"},"model_id":"gpt-5-mini","supports_tools":true,"supports_structured_output":true}]}"#,
+    )
+    .expect("catalog without version should parse");
+    assert_eq!(catalog.models.len(), 1);
+    assert_eq!(catalog.models[0].model_id, "gpt-5-mini");
+
+    let empty = ModelCatalog::from_json_str(r#"{"version":1}"#).expect("empty catalog parses");
+    assert!(empty.models.is_empty());
+}
+
+#[test]
+fn test_catalog_from_json_str_rejects_unsupported_version() {
+    let reason = invalid_catalog_reason(r#"{"version":2,"models":[]}"#);
+    assert!(reason.contains("unsupported catalog version 2"), "{reason}");
+}
+
+#[test]
+fn test_catalog_from_json_str_rejects_invalid_models() {
+    let reason = invalid_catalog_reason(
+        r#"{"models":[
+            {"provider":{"type":"openai"},"model_id":"a","supports_tools":true,"supports_structured_output":true},
+            {"provider":{"type":"openai"},"model_id":" ","supports_tools":true,"supports_structured_output":true}
+        ]}"#,
+    );
+    assert_eq!(reason, "models[1].model_id must not be empty");
+
+    let reason = invalid_catalog_reason(
+        r#"{"models":[
+            {"provider":{"type":"openai"},"model_id":"a","supports_tools":true,"supports_structured_output":true},
+            {"provider":{"type":"anthropic"},"model_id":"a","supports_tools":true,"supports_structured_output":true},
+            {"provider":{"type":"openai"},"model_id":"a","supports_tools":false,"supports_structured_output":false}
+        ]}"#,
+    );
+    assert!(
+        reason.starts_with("models[2].model_id: duplicate model `a`"),
+        "{reason}"
+    );
+
+    let reason = invalid_catalog_reason(
+        r#"{"models":[{"provider":{"type":"mistral"},"model_id":"a","supports_tools":true,"supports_structured_output":true}]}"#,
+    );
+    assert_eq!(reason, "models[0].provider: unknown provider `mistral`");
+
+    let reason = invalid_catalog_reason(r#"{"models":[{"provider":{"type":"openai"}}]}"#);
+    assert!(reason.starts_with("models[0]: missing field"), "{reason}");
+
+    let reason = invalid_catalog_reason(r#"{"version":1,"entries":[]}"#);
+    assert_eq!(reason, "unknown field `entries`");
+}
+
+#[test]
+fn test_catalog_from_path_reports_missing_file() {
+    let reason = match ModelCatalog::from_path("/nonexistent/catalog.json") {
+        Err(ConfigError::InvalidCatalog { reason }) => reason,
+        other => panic!("expected invalid catalog error, got {other:?}"),
+    };
+    assert!(reason.starts_with("failed to read /nonexistent/catalog.json"));
+}
+
+fn priced(mut info: ModelInfo, input_per_million: f64, output_per_million: f64) -> ModelInfo {
+    info.pricing = Some(ModelPricing {
+        input_cost_per_token: input_per_million / 1_000_000.0,
+        output_cost_per_token: output_per_million / 1_000_000.0,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+    });
+    info
+}
+
+fn selection_catalog() -> ModelCatalog {
+    ModelCatalog {
+        models: vec![
+            priced(
+                model(
+                    ProviderId::Openai,
+                    "large",
+                    None,
+                    Some(400_000),
+                    None,
+                    true,
+                    true,
+                ),
+                1.25,
+                10.0,
+            ),
+            priced(
+                model(
+                    ProviderId::Openai,
+                    "small",
+                    None,
+                    Some(128_000),
+                    None,
+                    true,
+                    true,
+                ),
+                0.25,
+                2.0,
+            ),
+            priced(
+                model(
+                    ProviderId::Anthropic,
+                    "tiny",
+                    None,
+                    Some(200_000),
+                    None,
+                    false,
+                    false,
+                ),
+                0.1,
+                0.4,
+            ),
+            model(
+                ProviderId::Openrouter,
+                "unpriced",
+                None,
+                Some(1_000_000),
+                None,
+                true,
+                true,
+            ),
+        ],
+    }
+}
+
+fn selected_id(
+    catalog: &ModelCatalog,
+    requirements: &ModelRequirements,
+    provider_hint: Option<&ProviderId>,
+    latency: impl Fn(&ModelInfo) -> Option<f64>,
+) -> Option<String> {
+    select_model(catalog, requirements, provider_hint, |_| true, latency)
+        .map(|model| model.model_id.clone())
+}
+
+#[test]
+fn test_select_model_prefers_cheapest_matching_model() {
+    let catalog = selection_catalog();
+
+    let any = ModelRequirements::default();
+    assert_eq!(
+        selected_id(&catalog, &any, None, |_| None).as_deref(),
+        Some("tiny")
+    );
+
+    let tools = ModelRequirements {
+        requires_tools: true,
+        ..ModelRequirements::default()
+    };
+    assert_eq!(
+        selected_id(&catalog, &tools, None, |_| None).as_deref(),
+        Some("small")
+    );
+
+    let long_context = ModelRequirements {
+        requires_tools: true,
+        min_context_window: Some(200_000),
+        ..ModelRequirements::default()
+    };
+    assert_eq!(
+        selected_id(&catalog, &long_context, None, |_| None).as_deref(),
+        Some("large")
+    );
+
+    let capped = ModelRequirements {
+        min_context_window: Some(200_000),
+        max_output_cost_per_million: Some(5.0),
+        requires_tools: true,
+        ..ModelRequirements::default()
+    };
+    assert_eq!(selected_id(&catalog, &capped, None, |_| None), None);
+
+    assert_eq!(
+        selected_id(&catalog, &any, Some(&ProviderId::Openrouter), |_| None).as_deref(),
+        Some("unpriced")
+    );
+    assert_eq!(
+        select_model(
+            &catalog,
+            &any,
+            None,
+            |provider| *provider == ProviderId::Openai,
+            |_| None
+        )
+        .map(|model| model.model_id.as_str()),
+        Some("small")
+    );
+}
+
+#[test]
+fn test_select_model_fastest_ranks_observed_latency_first() {
+    let catalog = selection_catalog();
+    let fastest = ModelRequirements {
+        requires_tools: true,
+        prefer: ModelPreference::Fastest,
+        ..ModelRequirements::default()
+    };
+
+    let latency = |model: &ModelInfo| match model.model_id.as_str() {
+        "large" => Some(300.0),
+        "small" => Some(900.0),
+        _ => None,
+    };
+    assert_eq!(
+        selected_id(&catalog, &fastest, None, latency).as_deref(),
+        Some("large")
+    );
+
+    // Without observations the cheapest model wins.
+    assert_eq!(
+        selected_id(&catalog, &fastest, None, |_| None).as_deref(),
+        Some("small")
+    );
+}
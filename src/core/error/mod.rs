@@ -1,6 +1,58 @@
-use crate::core::types::ProviderId;
+use std::error::Error as StdError;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::types::{ProviderId, RateLimitInfo, WarningCode};
 use thiserror::Error;
 
+/// Shared handle to an underlying error (for example a `reqwest` or `serde_json` failure).
+///
+/// Keeps the original error reachable through `Error::source()` while letting the enclosing
+/// error types stay `Clone`. Equality compares rendered messages.
+#[derive(Clone)]
+pub struct ErrorSource(Arc<dyn StdError + Send + Sync + 'static>);
+
+impl ErrorSource {
+    pub fn new(error: impl StdError + Send + Sync + 'static) -> Self {
+        Self(Arc::new(error))
+    }
+
+    pub fn get_ref(&self) -> &(dyn StdError + Send + Sync + 'static) {
+        self.0.as_ref()
+    }
+
+    pub fn downcast_ref<T: StdError + 'static>(&self) -> Option<&T> {
+        self.0.downcast_ref::<T>()
+    }
+}
+
+impl fmt::Debug for ErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for ErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl StdError for ErrorSource {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.0.source()
+    }
+}
+
+impl PartialEq for ErrorSource {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl Eq for ErrorSource {}
+
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
 pub enum ConfigError {
     #[error("missing default provider configuration")]
@@ -16,12 +68,46 @@ pub enum ConfigError {
     InvalidRetryPolicy { reason: String },
     #[error("invalid pricing config: {reason}")]
     InvalidPricingConfig { reason: String },
+    #[error("invalid model catalog: {reason}")]
+    InvalidCatalog { reason: String },
+    #[error("invalid credential store: {reason}")]
+    InvalidCredentialStore { reason: String },
+    #[error("invalid OAuth config: {reason}")]
+    InvalidOAuthConfig { reason: String },
+    #[error("invalid runtime config: {reason}")]
+    InvalidRuntimeConfig { reason: String },
+    #[error("default provider {provider:?} has no registered adapter")]
+    DefaultProviderNotRegistered { provider: ProviderId },
+    #[error(
+        "catalog model {model} references provider {provider:?}, which has no registered adapter"
+    )]
+    CatalogProviderNotRegistered { provider: ProviderId, model: String },
+    #[error(
+        "pricing rule '{pattern}' references provider {provider:?}, which has no registered adapter"
+    )]
+    PricingProviderNotRegistered {
+        provider: ProviderId,
+        pattern: String,
+    },
+    #[error("{rule} routes to {model}, which cannot be resolved: {error}")]
+    UnresolvableModelRoute {
+        rule: String,
+        model: String,
+        #[source]
+        error: RoutingError,
+    },
+    #[error("invalid transport config: {reason}")]
+    InvalidTransportConfig { reason: String },
+    #[error("invalid prompt template '{name}': {reason}")]
+    InvalidPromptTemplate { name: String, reason: String },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
 pub enum RoutingError {
     #[error("provider not registered: {provider:?}")]
     ProviderNotRegistered { provider: ProviderId },
+    #[error("provider unhealthy: {provider:?}")]
+    ProviderUnhealthy { provider: ProviderId },
     #[error("model route not found: {model}")]
     ModelNotFound { model: String },
     #[error(
@@ -32,6 +118,8 @@ pub enum RoutingError {
         model: String,
         candidates: Vec<ProviderId>,
     },
+    #[error("no model in the catalog meets the requirements: {requirements}")]
+    NoModelMatchesRequirements { requirements: String },
     #[error(
         "provider hint mismatch for model {model}: hint={provider_hint:?} resolved={resolved:?}"
     )]
@@ -43,7 +131,17 @@ pub enum RoutingError {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
 pub enum ProviderError {
+    /// No credential could be obtained for the call, such as a failed token refresh.
+    #[error(
+        "provider credential missing{context}: {message}",
+        context = format_context(Some(.provider), None, None, None)
+    )]
+    CredentialMissing {
+        provider: ProviderId,
+        message: String,
+    },
     #[error(
         "provider credentials rejected{context}: {message}",
         context = format_context(Some(.provider), None, .request_id.as_deref(), None)
@@ -61,6 +159,8 @@ pub enum ProviderError {
         provider: ProviderId,
         request_id: Option<String>,
         message: String,
+        #[source]
+        source: Option<ErrorSource>,
     },
     #[error(
         "provider status error{context}: {message}",
@@ -77,6 +177,36 @@ pub enum ProviderError {
         status_code: u16,
         request_id: Option<String>,
         message: String,
+        rate_limit: Option<Box<RateLimitInfo>>,
+    },
+    /// HTTP 429. `retry_after` comes from the `retry-after` headers when the provider sent one.
+    #[error(
+        "provider rate limited{context}: {message}",
+        context = format_context(
+            Some(.provider),
+            .model.as_deref(),
+            .request_id.as_deref(),
+            Some(429)
+        )
+    )]
+    RateLimited {
+        provider: ProviderId,
+        model: Option<String>,
+        request_id: Option<String>,
+        retry_after: Option<Duration>,
+        message: String,
+        rate_limit: Option<Box<RateLimitInfo>>,
+    },
+    #[error(
+        "provider request timed out{context}: {message}",
+        context = format_context(Some(.provider), .model.as_deref(), None, None)
+    )]
+    Timeout {
+        provider: ProviderId,
+        model: Option<String>,
+        message: String,
+        #[source]
+        source: Option<ErrorSource>,
     },
     #[error(
         "provider protocol error{context}: {message}",
@@ -107,10 +237,13 @@ pub enum ProviderError {
         model: Option<String>,
         request_id: Option<String>,
         message: String,
+        #[source]
+        source: Option<ErrorSource>,
     },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
 pub enum RuntimeError {
     #[error(transparent)]
     ConfigError(#[from] ConfigError),
@@ -121,6 +254,9 @@ pub enum RuntimeError {
     CredentialMissing {
         provider: ProviderId,
         env_candidates: Vec<String>,
+        /// Why a credential source came up empty, such as a failing `TokenProvider`.
+        #[source]
+        source: Option<ErrorSource>,
     },
     #[error(transparent)]
     RoutingError(#[from] RoutingError),
@@ -139,6 +275,8 @@ pub enum RuntimeError {
         model: Option<String>,
         request_id: Option<String>,
         message: String,
+        #[source]
+        source: Option<ErrorSource>,
     },
     #[error(
         "provider protocol error{context}: {message}",
@@ -155,6 +293,32 @@ pub enum RuntimeError {
         request_id: Option<String>,
         status_code: Option<u16>,
         message: String,
+        /// Rate-limit headers from the failed response, when the provider sent them.
+        rate_limit: Option<Box<RateLimitInfo>>,
+    },
+    #[error(
+        "rate limited{context}: {message}",
+        context = format_context(.provider.as_ref(), .model.as_deref(), .request_id.as_deref(), Some(429))
+    )]
+    RateLimited {
+        provider: Option<ProviderId>,
+        model: Option<String>,
+        request_id: Option<String>,
+        retry_after: Option<Duration>,
+        message: String,
+        /// Rate-limit headers from the 429 response, for client-side pacing.
+        rate_limit: Option<Box<RateLimitInfo>>,
+    },
+    #[error(
+        "request timed out{context}: {message}",
+        context = format_context(.provider.as_ref(), .model.as_deref(), None, None)
+    )]
+    Timeout {
+        provider: Option<ProviderId>,
+        model: Option<String>,
+        message: String,
+        #[source]
+        source: Option<ErrorSource>,
     },
     #[error(
         "serialization error{context}: {message}",
@@ -165,6 +329,17 @@ pub enum RuntimeError {
         model: Option<String>,
         request_id: Option<String>,
         message: String,
+        #[source]
+        source: Option<ErrorSource>,
+    },
+    #[error(
+        "encode warning rejected [provider={provider:?}, model={model}, code={code}]: {message}"
+    )]
+    EncodeWarningRejected {
+        provider: ProviderId,
+        model: String,
+        code: WarningCode,
+        message: String,
     },
     #[error(
         "cost calculation error{context}: {message}",
@@ -175,6 +350,27 @@ pub enum RuntimeError {
         model: Option<String>,
         message: String,
     },
+    /// A `RuntimeMiddleware` hook refused the request or response, e.g. a guardrail.
+    #[error("rejected by middleware: {message}")]
+    MiddlewareRejected { message: String },
+    /// The structured output does not match the requested JSON Schema.
+    #[error(
+        "structured output invalid{context}: {message}",
+        context = format_context(.provider.as_ref(), .model.as_deref(), .request_id.as_deref(), None)
+    )]
+    StructuredOutputInvalid {
+        provider: Option<ProviderId>,
+        model: Option<String>,
+        request_id: Option<String>,
+        message: String,
+    },
+    /// The accounting key has spent its `BudgetTracker` cap.
+    #[error("budget exceeded [key={key}]: {message}")]
+    BudgetExceeded { key: String, message: String },
+    /// The request still overflows its model's context window after the configured
+    /// `TruncationStrategy` ran.
+    #[error("context window exceeded [model={model}]: {message}")]
+    ContextWindowExceeded { model: String, message: String },
 }
 
 impl RuntimeError {
@@ -186,6 +382,64 @@ impl RuntimeError {
         Self::CredentialMissing {
             provider,
             env_candidates,
+            source: None,
+        }
+    }
+
+    /// The free-text message, which may quote a provider body; `None` for variants built only
+    /// from runtime state.
+    pub(crate) fn message_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Self::TransportError { message, .. }
+            | Self::ProviderProtocolError { message, .. }
+            | Self::RateLimited { message, .. }
+            | Self::Timeout { message, .. }
+            | Self::SerializationError { message, .. }
+            | Self::EncodeWarningRejected { message, .. }
+            | Self::CostCalculationError { message, .. }
+            | Self::MiddlewareRejected { message }
+            | Self::StructuredOutputInvalid { message, .. }
+            | Self::BudgetExceeded { message, .. }
+            | Self::ContextWindowExceeded { message, .. } => Some(message),
+            Self::ConfigError(_)
+            | Self::CredentialMissing { .. }
+            | Self::RoutingError(_)
+            | Self::CapabilityMismatch { .. } => None,
+        }
+    }
+}
+
+impl ProviderError {
+    /// Builds the error for a non-success HTTP status, using `RateLimited` for 429.
+    pub(crate) fn from_status(
+        provider: ProviderId,
+        model: Option<String>,
+        status_code: u16,
+        request_id: Option<String>,
+        message: String,
+        rate_limit: Option<Box<RateLimitInfo>>,
+    ) -> Self {
+        if status_code == 429 {
+            return Self::RateLimited {
+                provider,
+                model,
+                request_id,
+                retry_after: rate_limit
+                    .as_ref()
+                    .and_then(|rate_limit| rate_limit.retry_after_ms)
+                    .map(Duration::from_millis),
+                message,
+                rate_limit,
+            };
+        }
+
+        Self::Status {
+            provider,
+            model,
+            status_code,
+            request_id,
+            message,
+            rate_limit,
         }
     }
 }
@@ -193,26 +447,38 @@ impl RuntimeError {
 impl From<ProviderError> for RuntimeError {
     fn from(error: ProviderError) -> Self {
         match error {
+            ProviderError::CredentialMissing { provider, message } => Self::CredentialMissing {
+                provider: provider.clone(),
+                env_candidates: Vec::new(),
+                source: Some(ErrorSource::new(ProviderError::CredentialMissing {
+                    provider,
+                    message,
+                })),
+            },
             ProviderError::Transport {
                 provider,
                 request_id,
                 message,
+                source,
             } => Self::TransportError {
                 provider: Some(provider),
                 model: None,
                 request_id,
                 message,
+                source,
             },
             ProviderError::Serialization {
                 provider,
                 model,
                 request_id,
                 message,
+                source,
             } => Self::SerializationError {
                 provider: Some(provider),
                 model,
                 request_id,
                 message,
+                source,
             },
             ProviderError::CredentialsRejected {
                 provider,
@@ -224,6 +490,7 @@ impl From<ProviderError> for RuntimeError {
                 request_id,
                 status_code: None,
                 message,
+                rate_limit: None,
             },
             ProviderError::Status {
                 provider,
@@ -231,12 +498,40 @@ impl From<ProviderError> for RuntimeError {
                 status_code,
                 request_id,
                 message,
+                rate_limit,
             } => Self::ProviderProtocolError {
                 provider: Some(provider),
                 model,
                 request_id,
                 status_code: Some(status_code),
                 message,
+                rate_limit,
+            },
+            ProviderError::RateLimited {
+                provider,
+                model,
+                request_id,
+                retry_after,
+                message,
+                rate_limit,
+            } => Self::RateLimited {
+                provider: Some(provider),
+                model,
+                request_id,
+                retry_after,
+                message,
+                rate_limit,
+            },
+            ProviderError::Timeout {
+                provider,
+                model,
+                message,
+                source,
+            } => Self::Timeout {
+                provider: Some(provider),
+                model,
+                message,
+                source,
             },
             ProviderError::Protocol {
                 provider,
@@ -249,6 +544,7 @@ impl From<ProviderError> for RuntimeError {
                 request_id,
                 status_code: None,
                 message,
+                rate_limit: None,
             },
         }
     }
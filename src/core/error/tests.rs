@@ -1,5 +1,7 @@
+use std::time::Duration;
+
 use super::*;
-use crate::core::types::ProviderId;
+use crate::core::types::{ProviderId, RateLimitInfo};
 
 #[test]
 fn test_runtime_error_display_messages() {
@@ -38,6 +40,7 @@ fn test_runtime_error_display_messages() {
         model: Some("openrouter/auto".to_string()),
         request_id: Some("req_123".to_string()),
         message: "timeout".to_string(),
+        source: None,
     };
     assert_eq!(
         transport_error.to_string(),
@@ -50,6 +53,7 @@ fn test_runtime_error_display_messages() {
         request_id: Some("req_abc".to_string()),
         status_code: Some(429),
         message: "rate limited".to_string(),
+        rate_limit: None,
     };
     assert_eq!(
         protocol_with_status.to_string(),
@@ -62,6 +66,7 @@ fn test_runtime_error_display_messages() {
         request_id: None,
         status_code: None,
         message: "invalid payload".to_string(),
+        rate_limit: None,
     };
     assert_eq!(
         protocol_without_status.to_string(),
@@ -95,6 +100,7 @@ fn test_missing_credential_error_contains_env_hints() {
         RuntimeError::CredentialMissing {
             provider,
             env_candidates,
+            ..
         } => {
             assert_eq!(*provider, ProviderId::Anthropic);
             assert_eq!(
@@ -116,6 +122,7 @@ fn test_provider_error_conversion_preserves_context() {
         provider: ProviderId::Openrouter,
         request_id: Some("req_transport".to_string()),
         message: "connection reset".to_string(),
+        source: None,
     }
     .into();
     assert_eq!(
@@ -125,6 +132,7 @@ fn test_provider_error_conversion_preserves_context() {
             model: None,
             request_id: Some("req_transport".to_string()),
             message: "connection reset".to_string(),
+            source: None,
         }
     );
 
@@ -133,6 +141,7 @@ fn test_provider_error_conversion_preserves_context() {
         model: Some("gpt-5-mini".to_string()),
         request_id: Some("req_serialization".to_string()),
         message: "decode failure".to_string(),
+        source: None,
     }
     .into();
     assert_eq!(
@@ -142,6 +151,7 @@ fn test_provider_error_conversion_preserves_context() {
             model: Some("gpt-5-mini".to_string()),
             request_id: Some("req_serialization".to_string()),
             message: "decode failure".to_string(),
+            source: None,
         }
     );
 
@@ -159,6 +169,7 @@ fn test_provider_error_conversion_preserves_context() {
             request_id: Some("req_credentials".to_string()),
             status_code: None,
             message: "invalid key".to_string(),
+            rate_limit: None,
         }
     );
 
@@ -168,6 +179,7 @@ fn test_provider_error_conversion_preserves_context() {
         status_code: 429,
         request_id: Some("req_status".to_string()),
         message: "too many requests".to_string(),
+        rate_limit: None,
     }
     .into();
     assert_eq!(
@@ -178,6 +190,7 @@ fn test_provider_error_conversion_preserves_context() {
             request_id: Some("req_status".to_string()),
             status_code: Some(429),
             message: "too many requests".to_string(),
+            rate_limit: None,
         }
     );
 
@@ -196,6 +209,131 @@ fn test_provider_error_conversion_preserves_context() {
             request_id: Some("req_protocol".to_string()),
             status_code: None,
             message: "unexpected response shape".to_string(),
+            rate_limit: None,
+        }
+    );
+}
+
+#[test]
+fn test_error_source_chain_survives_conversion_and_clone() {
+    use std::error::Error as _;
+
+    let parse_error = serde_json::from_str::<serde_json::Value>("{").expect_err("invalid json");
+    let provider_error = ProviderError::Serialization {
+        provider: ProviderId::Openai,
+        model: None,
+        request_id: None,
+        message: parse_error.to_string(),
+        source: Some(ErrorSource::new(parse_error)),
+    };
+
+    let runtime_error = RuntimeError::from(provider_error.clone());
+    let cloned = runtime_error.clone();
+    assert_eq!(runtime_error, cloned);
+
+    let source = cloned.source().expect("source should be preserved");
+    let error_source = source
+        .downcast_ref::<ErrorSource>()
+        .expect("source should be an ErrorSource");
+    assert!(error_source.downcast_ref::<serde_json::Error>().is_some());
+    assert_eq!(
+        source.to_string(),
+        provider_error
+            .source()
+            .expect("provider source")
+            .to_string()
+    );
+
+    let without_source = RuntimeError::TransportError {
+        provider: None,
+        model: None,
+        request_id: None,
+        message: "timeout".to_string(),
+        source: None,
+    };
+    assert!(without_source.source().is_none());
+}
+
+#[test]
+fn test_credential_missing_conversion_keeps_message_as_source() {
+    use std::error::Error as _;
+
+    let runtime_error = RuntimeError::from(ProviderError::CredentialMissing {
+        provider: ProviderId::Openai,
+        message: "token provider failed: sts unavailable".to_string(),
+    });
+
+    assert!(matches!(
+        runtime_error,
+        RuntimeError::CredentialMissing {
+            provider: ProviderId::Openai,
+            ref env_candidates,
+            ..
+        } if env_candidates.is_empty()
+    ));
+    let source = runtime_error
+        .source()
+        .expect("provider message should be the source");
+    assert!(source.to_string().contains("sts unavailable"));
+}
+
+#[test]
+fn test_from_status_maps_429_to_rate_limited() {
+    let rate_limit = RateLimitInfo {
+        retry_after_ms: Some(2_500),
+        ..Default::default()
+    };
+
+    let limited = ProviderError::from_status(
+        ProviderId::Openai,
+        Some("gpt-5-mini".to_string()),
+        429,
+        Some("req-1".to_string()),
+        "slow down".to_string(),
+        Some(Box::new(rate_limit)),
+    );
+    match &limited {
+        ProviderError::RateLimited { retry_after, .. } => {
+            assert_eq!(*retry_after, Some(Duration::from_millis(2_500)));
         }
+        other => panic!("expected rate limited error, got {other:?}"),
+    }
+    assert_eq!(
+        limited.to_string(),
+        "provider rate limited [provider=Openai, model=gpt-5-mini, request_id=req-1, status_code=429]: slow down"
+    );
+    assert!(matches!(
+        RuntimeError::from(limited),
+        RuntimeError::RateLimited {
+            retry_after: Some(_),
+            ..
+        }
+    ));
+
+    let status = ProviderError::from_status(
+        ProviderId::Openai,
+        None,
+        503,
+        None,
+        "busy".to_string(),
+        None,
+    );
+    assert!(matches!(
+        status,
+        ProviderError::Status {
+            status_code: 503,
+            ..
+        }
+    ));
+
+    let timeout = RuntimeError::from(ProviderError::Timeout {
+        provider: ProviderId::Anthropic,
+        model: Some("claude".to_string()),
+        message: "operation timed out".to_string(),
+        source: None,
+    });
+    assert_eq!(
+        timeout.to_string(),
+        "request timed out [provider=Anthropic, model=claude]: operation timed out"
     );
 }
@@ -1,9 +1,15 @@
+use std::fmt;
+
 use async_trait::async_trait;
+use serde_json::Value;
 
-use crate::core::error::{ProviderError, RuntimeError};
+use crate::core::error::{ErrorSource, ProviderError, RuntimeError};
 use crate::core::types::{
-    AdapterContext, DiscoveryOptions, ModelInfo, ProviderCapabilities, ProviderId, ProviderRequest,
-    ProviderResponse,
+    AccessToken, AdapterContext, AudioSpeechRequest, AudioSpeechResponse,
+    AudioTranscriptionRequest, AudioTranscriptionResponse, DiscoveryOptions, EmbeddingRequest,
+    EmbeddingResponse, ImageGenerationRequest, ImageGenerationResponse, ModelInfo,
+    ModerationRequest, ModerationResponse, ProviderCapabilities, ProviderId, ProviderRequest,
+    ProviderResponse, RuntimeWarning,
 };
 
 /// Provider adapter contract for translating canonical runtime requests to a
@@ -32,22 +38,183 @@ pub trait ProviderAdapter: Send + Sync {
         ctx: &AdapterContext,
     ) -> Result<ProviderResponse, ProviderError>;
 
+    /// Returns the warnings encoding `req` would produce, without sending anything.
+    ///
+    /// The runtime calls this before dispatch only when an encode-warning policy is configured.
+    /// Adapters without a separate encode step report no warnings.
+    fn encode_warnings(&self, req: &ProviderRequest) -> Result<Vec<RuntimeWarning>, ProviderError> {
+        let _ = req;
+        Ok(Vec::new())
+    }
+
+    /// Counts the input tokens `req` would consume, as reported by the provider.
+    ///
+    /// Returns `Ok(None)` when the provider has no counting endpoint; the runtime then falls
+    /// back to its local `TokenCounter`.
+    async fn count_tokens(
+        &self,
+        req: &ProviderRequest,
+        ctx: &AdapterContext,
+    ) -> Result<Option<u64>, ProviderError> {
+        let _ = (req, ctx);
+        Ok(None)
+    }
+
+    /// Embeds `req.inputs` with the provider's embeddings endpoint.
+    ///
+    /// Returns `Ok(None)` when the provider has no embeddings endpoint; the runtime reports that
+    /// as a capability mismatch.
+    async fn embed(
+        &self,
+        req: &EmbeddingRequest,
+        ctx: &AdapterContext,
+    ) -> Result<Option<EmbeddingResponse>, ProviderError> {
+        let _ = (req, ctx);
+        Ok(None)
+    }
+
+    /// Classifies `req.inputs` with the provider's moderation endpoint.
+    ///
+    /// Returns `Ok(None)` when the provider has no moderation endpoint; the runtime reports that
+    /// as a capability mismatch.
+    async fn moderate(
+        &self,
+        req: &ModerationRequest,
+        ctx: &AdapterContext,
+    ) -> Result<Option<ModerationResponse>, ProviderError> {
+        let _ = (req, ctx);
+        Ok(None)
+    }
+
+    /// Generates images for `req.prompt` with the provider's image endpoint or image-capable
+    /// models.
+    ///
+    /// Returns `Ok(None)` when the provider cannot generate images; the runtime reports that as a
+    /// capability mismatch.
+    async fn generate_images(
+        &self,
+        req: &ImageGenerationRequest,
+        ctx: &AdapterContext,
+    ) -> Result<Option<ImageGenerationResponse>, ProviderError> {
+        let _ = (req, ctx);
+        Ok(None)
+    }
+
+    /// Synthesizes speech for `req.input`.
+    ///
+    /// Returns `Ok(None)` when the provider has no text-to-speech endpoint; the runtime reports
+    /// that as a capability mismatch.
+    async fn synthesize_speech(
+        &self,
+        req: &AudioSpeechRequest,
+        ctx: &AdapterContext,
+    ) -> Result<Option<AudioSpeechResponse>, ProviderError> {
+        let _ = (req, ctx);
+        Ok(None)
+    }
+
+    /// Transcribes `req.audio` to text.
+    ///
+    /// Returns `Ok(None)` when the provider has no transcription endpoint; the runtime reports
+    /// that as a capability mismatch.
+    async fn transcribe(
+        &self,
+        req: &AudioTranscriptionRequest,
+        ctx: &AdapterContext,
+    ) -> Result<Option<AudioTranscriptionResponse>, ProviderError> {
+        let _ = (req, ctx);
+        Ok(None)
+    }
+
     /// Discovers provider models and maps results into canonical model records.
     async fn discover_models(
         &self,
         opts: &DiscoveryOptions,
         ctx: &AdapterContext,
     ) -> Result<Vec<ModelInfo>, ProviderError>;
+
+    /// Checks that the provider is reachable and accepts the configured credentials.
+    ///
+    /// The default lists the provider's models, a cheap call for every built-in provider.
+    /// Adapters without remote discovery report healthy.
+    async fn health_check(&self, ctx: &AdapterContext) -> Result<(), ProviderError> {
+        if !self.capabilities().supports_remote_discovery {
+            return Ok(());
+        }
+        let opts = DiscoveryOptions {
+            remote: true,
+            include_provider: vec![self.id()],
+            refresh_cache: true,
+        };
+        self.discover_models(&opts, ctx).await.map(|_| ())
+    }
+}
+
+/// Source of provider API keys, consulted before every provider call so stores that re-read
+/// their backing secret pick up rotated keys without a restart.
+///
+/// Built-in stores live in `registry`: environment variables, static keys, a key file, the OS
+/// keychain, adapter context metadata, and a chain trying several in order.
+pub trait CredentialStore: Send + Sync {
+    /// Returns the API key for `provider`, or `Ok(None)` when this store has none.
+    fn api_key(
+        &self,
+        provider: &ProviderId,
+        ctx: &AdapterContext,
+    ) -> Result<Option<String>, RuntimeError>;
 }
 
-/// Optional auth extension point for externally managed bearer token retrieval.
+/// Source of short-lived bearer tokens, such as OAuth or STS credentials, for providers
+/// registered with `ProviderRuntimeBuilder::with_token_provider`.
 ///
-/// This is a hook only for future OAuth-compatible integrations. v0 does not
-/// implement OAuth flows or token lifecycle management.
+/// The runtime asks for a token before each call to the provider and sends it in place of an
+/// API key. Tokens from [`TokenProvider::access_token`] are reused until they near expiry, or
+/// until the provider rejects them when they have no expiry; a failure surfaces as
+/// `RuntimeError::CredentialMissing`.
 #[async_trait]
 pub trait TokenProvider: Send + Sync {
     /// Returns an access token for the requested provider.
     async fn get_token(&self, provider: ProviderId) -> Result<String, RuntimeError>;
+
+    /// Returns an access token with its expiry. Defaults to [`TokenProvider::get_token`] with no
+    /// expiry, which the runtime reuses until the provider rejects it; override it to have
+    /// tokens refreshed ahead of a known expiry instead.
+    async fn access_token(&self, provider: ProviderId) -> Result<AccessToken, RuntimeError> {
+        Ok(AccessToken::new(self.get_token(provider).await?))
+    }
+}
+
+/// Scrubs provider bodies before they appear in error messages, warnings, run traces, or
+/// `tracing` events.
+///
+/// `transport::redaction::DefaultRedactor` masks bearer tokens and API keys, and can also mask
+/// message content.
+pub trait Redactor: Send + Sync + fmt::Debug {
+    /// Returns `text` with anything sensitive masked.
+    fn redact(&self, text: &str) -> String;
+
+    /// Masks a captured JSON body in place. Defaults to redacting every string value.
+    fn redact_json(&self, value: &mut Value) {
+        match value {
+            Value::String(text) => *text = self.redact(text),
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_json(item)),
+            Value::Object(fields) => fields
+                .values_mut()
+                .for_each(|field| self.redact_json(field)),
+            _ => {}
+        }
+    }
+}
+
+/// Encrypts data the crate persists, such as saved `Conversation`s and, with the `redis`
+/// feature, cached responses, so transcripts are not stored in plaintext.
+///
+/// Implementations own key handling. For envelope encryption, encrypt each payload with a fresh
+/// data key and prepend the data key as wrapped by a key-management service, so `decrypt` needs
+/// nothing but the ciphertext.
+pub trait Encryptor: Send + Sync {
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, ErrorSource>;
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, ErrorSource>;
 }
 
 #[cfg(test)]
@@ -56,12 +56,16 @@ impl ProviderAdapter for MockAdapter {
                     text: "ok".to_string(),
                 }],
                 structured_output: None,
+                annotations: Vec::new(),
             },
             usage: Usage {
                 input_tokens: Some(1),
                 output_tokens: Some(1),
                 cached_input_tokens: None,
                 total_tokens: None,
+                reasoning_tokens: None,
+                cache_creation_input_tokens: None,
+                web_search_requests: None,
             },
             cost: None,
             provider: self.provider.clone(),
@@ -69,6 +73,10 @@ impl ProviderAdapter for MockAdapter {
             raw_provider_response: None,
             finish_reason: FinishReason::Stop,
             warnings: Vec::new(),
+            alternatives: Vec::new(),
+            response_id: None,
+            request_id: None,
+            rate_limit: None,
         })
     }
 
@@ -90,6 +98,11 @@ impl ProviderAdapter for MockAdapter {
             max_output_tokens: Some(1_024),
             supports_tools: self.capabilities.supports_tools,
             supports_structured_output: self.capabilities.supports_structured_output,
+            pricing: None,
+            input_modalities: Vec::new(),
+            output_modalities: Vec::new(),
+            deprecated: false,
+            knowledge_cutoff: None,
         }])
     }
 }
@@ -131,21 +144,28 @@ fn sample_request() -> ProviderRequest {
         model: ModelRef {
             provider_hint: Some(ProviderId::Openai),
             model_id: "gpt-5-mini".to_string(),
+            requirements: None,
         },
         messages: vec![Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "hello".to_string(),
             }],
+            cache_hint: None,
         }],
         tools: Vec::new(),
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::Text,
         temperature: None,
         top_p: None,
         max_output_tokens: None,
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     }
 }
 
@@ -156,6 +176,7 @@ async fn test_provider_adapter_object_safety() {
         supports_structured_output: true,
         supports_thinking: false,
         supports_remote_discovery: true,
+        supports_parallel_tool_calls: false,
     };
     let adapter: Box<dyn ProviderAdapter> =
         Box::new(MockAdapter::new(ProviderId::Openai, capabilities.clone()));
@@ -198,6 +219,7 @@ fn test_provider_capabilities_contract() {
         supports_structured_output: true,
         supports_thinking: true,
         supports_remote_discovery: false,
+        supports_parallel_tool_calls: false,
     };
     let adapter = MockAdapter::new(ProviderId::Anthropic, expected.clone());
 
@@ -213,6 +235,7 @@ async fn test_token_provider_provider_scoped_contract() {
         model: None,
         request_id: Some("req_token_1".to_string()),
         message: "token endpoint unavailable".to_string(),
+        source: None,
     };
     let token_provider = MockTokenProvider::new(Some(ProviderId::Anthropic), failure.clone());
 
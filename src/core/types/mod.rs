@@ -1,4 +1,5 @@
 use std::collections::BTreeMap;
+use std::time::SystemTime;
 
 use serde::{Deserialize, Serialize};
 
@@ -9,8 +10,16 @@ pub struct ProviderRequest {
     pub messages: Vec<Message>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tools: Vec<ToolDefinition>,
+    /// Provider built-in tools the model may call; the provider runs them and returns their
+    /// calls as `ContentPart::HostedToolCall`. Providers without a given tool drop it with a
+    /// warning.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hosted_tools: Vec<HostedTool>,
     #[serde(default)]
     pub tool_choice: ToolChoice,
+    /// Whether the model may call several tools in one turn. `None` keeps the provider default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
     #[serde(default)]
     pub response_format: ResponseFormat,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -23,6 +32,46 @@ pub struct ProviderRequest {
     pub stop: Vec<String>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub metadata: BTreeMap<String, String>,
+    /// Number of completions to generate. Providers that cannot return several reject values
+    /// other than 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<u8>,
+    /// Best-effort deterministic sampling seed. Providers without seed support drop it with a
+    /// warning.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<i64>,
+    /// Provider-specific body fields merged over adapter-level options, keyed by the provider
+    /// they target. Entries for other providers are ignored.
+    #[serde(
+        default,
+        skip_serializing_if = "BTreeMap::is_empty",
+        with = "provider_options_serde"
+    )]
+    pub provider_options: BTreeMap<ProviderId, serde_json::Value>,
+}
+
+impl ProviderRequest {
+    /// A request for `model` with `messages` and every other setting at its default: no tools,
+    /// automatic tool choice, plain text output, and the provider's own sampling defaults.
+    pub fn new(model: ModelRef, messages: Vec<Message>) -> Self {
+        Self {
+            model,
+            messages,
+            tools: Vec::new(),
+            hosted_tools: Vec::new(),
+            tool_choice: ToolChoice::Auto,
+            parallel_tool_calls: None,
+            response_format: ResponseFormat::Text,
+            temperature: None,
+            top_p: None,
+            max_output_tokens: None,
+            stop: Vec::new(),
+            metadata: BTreeMap::new(),
+            n: None,
+            seed: None,
+            provider_options: BTreeMap::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -36,17 +85,274 @@ pub struct ProviderResponse {
     pub model: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub raw_provider_response: Option<serde_json::Value>,
+    /// Provider-assigned id of the response body, such as OpenAI's `resp_...` id that a later
+    /// request can continue from with `previous_response_id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_id: Option<String>,
+    /// Provider request id from the response headers, for correlating with provider-side logs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimitInfo>,
     pub finish_reason: FinishReason,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub warnings: Vec<RuntimeWarning>,
+    /// Additional completions when more than one was requested via `ProviderRequest::n`;
+    /// `output` and `finish_reason` always describe the first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternatives: Vec<ResponseAlternative>,
+}
+
+/// A request to embed `inputs` as vectors with an embedding model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EmbeddingRequest {
+    pub model: ModelRef,
+    pub inputs: Vec<String>,
+    /// Output vector size, for models that can shorten their embeddings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EmbeddingResponse {
+    /// One vector per input, in input order.
+    pub embeddings: Vec<Vec<f32>>,
+    /// Embedding calls only consume input tokens; `output_tokens` is reported as 0.
+    pub usage: Usage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost: Option<CostBreakdown>,
+    pub provider: ProviderId,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<RuntimeWarning>,
+}
+
+/// A request to classify `inputs` against the provider's safety policy categories.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModerationRequest {
+    pub model: ModelRef,
+    pub inputs: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModerationResponse {
+    /// One result per input, in input order.
+    pub results: Vec<ModerationResult>,
+    pub provider: ProviderId,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<RuntimeWarning>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModerationResult {
+    /// Whether any category was flagged.
+    pub flagged: bool,
+    /// Per-category verdicts, keyed by the provider's category name (e.g. `harassment`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub categories: BTreeMap<String, bool>,
+    /// Per-category confidence between 0 and 1.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub category_scores: BTreeMap<String, f64>,
+}
+
+/// A request to generate images from a text prompt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImageGenerationRequest {
+    pub model: ModelRef,
+    pub prompt: String,
+    /// Number of images to generate. Providers that return one image per call warn on other
+    /// values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<u8>,
+    /// Provider size string, such as `1024x1024`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+    /// Provider quality string, such as `high` or `hd`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImageGenerationResponse {
+    pub images: Vec<GeneratedImage>,
+    pub usage: Usage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost: Option<CostBreakdown>,
+    pub provider: ProviderId,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<RuntimeWarning>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
+pub struct GeneratedImage {
+    pub source: ImageSource,
+    /// The prompt the provider actually used, when it rewrote the request prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revised_prompt: Option<String>,
+}
+
+impl GeneratedImage {
+    /// Decoded image bytes for inline images; `None` for URLs or malformed base64.
+    pub fn bytes(&self) -> Option<Vec<u8>> {
+        use base64::Engine as _;
+
+        match &self.source {
+            ImageSource::Base64 { data, .. } => {
+                base64::engine::general_purpose::STANDARD.decode(data).ok()
+            }
+            ImageSource::Url { .. } => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ImageSource {
+    /// A provider-hosted URL, usually short-lived.
+    Url { url: String },
+    /// Inline base64 image data.
+    Base64 {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        media_type: Option<String>,
+        data: String,
+    },
+}
+
+/// A request to synthesize speech from text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AudioSpeechRequest {
+    pub model: ModelRef,
+    pub input: String,
+    /// Provider voice name, such as `alloy`.
+    pub voice: String,
+    /// Output container, such as `mp3`, `opus`, or `wav`. Providers pick their default when
+    /// unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Playback speed multiplier, where 1.0 is normal speed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f32>,
+    /// Delivery guidance (tone, accent, pacing) for models that accept it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AudioSpeechResponse {
+    /// The encoded audio file.
+    pub audio: Vec<u8>,
+    /// MIME type reported by the provider, such as `audio/mpeg`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    pub provider: ProviderId,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<RuntimeWarning>,
+}
+
+/// A request to transcribe an audio file to text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AudioTranscriptionRequest {
+    pub model: ModelRef,
+    /// The encoded audio file.
+    pub audio: Vec<u8>,
+    /// File name sent with the upload. Providers use its extension to detect the format.
+    pub filename: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    /// ISO-639-1 language of the audio, which improves accuracy and latency when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Text to guide the transcript's style or spell domain terms.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AudioTranscriptionResponse {
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<f64>,
+    /// Token usage, for models billed per token. Models billed per second leave it empty.
+    pub usage: Usage,
+    pub provider: ProviderId,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<RuntimeWarning>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ModelRef {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub provider_hint: Option<ProviderId>,
+    #[serde(default)]
     pub model_id: String,
+    /// Lets the runtime pick the model from the active catalog. When set, `model_id` is
+    /// replaced with the selected model and `provider_hint`, if any, limits the candidates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requirements: Option<ModelRequirements>,
+}
+
+/// What a model must support to be selected automatically. Unset fields accept any model.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelRequirements {
+    #[serde(default)]
+    pub requires_tools: bool,
+    #[serde(default)]
+    pub requires_structured_output: bool,
+    /// Models without a known context window are excluded when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_context_window: Option<u32>,
+    /// Price ceilings per million tokens. Models without published pricing are excluded when
+    /// either is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_input_cost_per_million: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_cost_per_million: Option<f64>,
+    #[serde(default)]
+    pub prefer: ModelPreference,
+}
+
+/// How to choose among the models that meet [`ModelRequirements`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelPreference {
+    /// Lowest combined input and output price; unpriced models rank last.
+    #[default]
+    Cheapest,
+    /// Lowest latency observed by the registry; models not yet called rank last, cheapest first.
+    Fastest,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -54,12 +360,24 @@ pub struct ModelRef {
 pub struct Message {
     pub role: MessageRole,
     pub content: Vec<ContentPart>,
+    /// Marks the end of a cacheable prompt prefix. Providers without explicit cache control ignore it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_hint: Option<CacheHint>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CacheHint {
+    Ephemeral,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum MessageRole {
     System,
+    /// Instructions from the application developer. Mapped to OpenAI's `developer` role and folded
+    /// into system instructions for providers without a distinct role.
+    Developer,
     User,
     Assistant,
     Tool,
@@ -67,10 +385,32 @@ pub enum MessageRole {
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum ContentPart {
-    Text { text: String },
-    ToolCall { tool_call: ToolCall },
-    ToolResult { tool_result: ToolResult },
+    Text {
+        text: String,
+    },
+    ToolCall {
+        tool_call: ToolCall,
+    },
+    ToolResult {
+        tool_result: ToolResult,
+    },
+    Thinking {
+        text: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signature: Option<String>,
+    },
+    HostedToolCall {
+        hosted_tool_call: HostedToolCall,
+    },
+    /// A provider content block of a type this crate does not model, kept as received. Adapters
+    /// drop it with a warning when it is sent back.
+    Other {
+        /// The provider's block type, such as `computer_call`.
+        kind: String,
+        raw: serde_json::Value,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -80,6 +420,27 @@ pub struct ToolDefinition {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     pub parameters_schema: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_hint: Option<CacheHint>,
+}
+
+/// A tool that runs on the provider's side instead of the caller's.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+#[non_exhaustive]
+pub enum HostedTool {
+    WebSearch {
+        /// Restrict results to these domains.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        allowed_domains: Vec<String>,
+    },
+    FileSearch {
+        /// Provider-side vector stores to search.
+        vector_store_ids: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_num_results: Option<u32>,
+    },
+    CodeInterpreter,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -90,41 +451,73 @@ pub struct ToolCall {
     pub arguments_json: serde_json::Value,
 }
 
+/// A call the provider made to one of its hosted tools, already executed. It needs no tool
+/// result from the caller.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HostedToolCall {
+    pub id: String,
+    /// Provider item type, such as `web_search_call`.
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Provider that made the call. The raw item is only replayed to this provider.
+    pub provider: ProviderId,
+    /// The provider's item as received, replayed verbatim in later turns.
+    pub raw_provider_content: serde_json::Value,
+}
+
+/// The caller's answer to a [`ToolCall`], sent back in a [`MessageRole::Tool`] message.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ToolResult {
+    /// The [`ToolCall::id`] this result answers.
     pub tool_call_id: String,
     pub content: ToolResultContent,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// A provider-native output that takes precedence over `content` when it fits the wire
+    /// format. OpenAI and OpenRouter accept a string; anything else is ignored with a warning.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub raw_provider_content: Option<serde_json::Value>,
+    /// The tool failed and `content` describes the failure. Sent as Anthropic's `is_error`; the
+    /// chat-style providers receive the output wrapped as `{"is_error": true, "content": ...}`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_error: bool,
 }
 
+/// The output of a tool. Providers whose tool outputs are plain text receive `Json` as
+/// canonical JSON text and `Parts` joined line by line, with a `tool_result_coerced` warning.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ToolResultContent {
-    Text { text: String },
-    Json { value: serde_json::Value },
-    Parts { parts: Vec<ContentPart> },
+    Text {
+        text: String,
+    },
+    /// Structured output, serialized with sorted keys.
+    Json {
+        value: serde_json::Value,
+    },
+    /// Multiple blocks of output. Only [`ContentPart::Text`] parts are accepted.
+    Parts {
+        parts: Vec<ContentPart>,
+    },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ToolChoice {
     None,
+    #[default]
     Auto,
     Required,
-    Specific { name: String },
-}
-
-impl Default for ToolChoice {
-    fn default() -> Self {
-        Self::Auto
-    }
+    Specific {
+        name: String,
+    },
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ResponseFormat {
+    #[default]
     Text,
     JsonObject,
     JsonSchema {
@@ -133,18 +526,62 @@ pub enum ResponseFormat {
     },
 }
 
-impl Default for ResponseFormat {
-    fn default() -> Self {
-        Self::Text
-    }
-}
-
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct AssistantOutput {
     pub content: Vec<ContentPart>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub structured_output: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<Annotation>,
+}
+
+/// One of the additional completions of a response, with the reason that completion stopped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResponseAlternative {
+    pub output: AssistantOutput,
+    pub finish_reason: FinishReason,
+}
+
+/// Provider-attached metadata about the assistant output, such as web search citations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Annotation {
+    UrlCitation {
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        content: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        start_index: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        end_index: Option<u64>,
+        /// Index in `AssistantOutput::content` of the text part the character range refers to.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        part_index: Option<usize>,
+    },
+    /// A passage cited from a provider-side file, such as an OpenAI file search result.
+    FileCitation {
+        file_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        filename: Option<String>,
+        /// Character offset in the text part where the citation applies.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        index: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        part_index: Option<usize>,
+    },
+    /// A parsed file returned by the provider; sending it back avoids re-parsing the file.
+    File {
+        hash: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        content: Option<serde_json::Value>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -158,12 +595,65 @@ pub struct Usage {
     pub cached_input_tokens: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub total_tokens: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_tokens: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_creation_input_tokens: Option<u64>,
+    /// Hosted web searches the provider ran, billed per search rather than per token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub web_search_requests: Option<u64>,
+}
+
+/// Provider rate-limit headers from the most recent HTTP response.
+///
+/// Reset values are kept as sent because providers disagree on the format (OpenAI sends
+/// durations such as `6m0s`, Anthropic sends RFC 3339 timestamps).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requests_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requests_remaining: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requests_reset: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens_remaining: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens_reset: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_ms: Option<u64>,
 }
 
 impl Usage {
+    /// Adds `other`'s token counts to these, e.g. to report usage across retried calls. A
+    /// count stays `None` only when it is missing on both sides.
+    pub fn accumulate(&mut self, other: &Usage) {
+        let add = |total: &mut Option<u64>, more: Option<u64>| {
+            if let Some(more) = more {
+                *total = Some(total.unwrap_or(0) + more);
+            }
+        };
+        add(&mut self.input_tokens, other.input_tokens);
+        add(&mut self.output_tokens, other.output_tokens);
+        add(&mut self.cached_input_tokens, other.cached_input_tokens);
+        add(&mut self.total_tokens, other.total_tokens);
+        add(&mut self.reasoning_tokens, other.reasoning_tokens);
+        add(
+            &mut self.cache_creation_input_tokens,
+            other.cache_creation_input_tokens,
+        );
+        add(&mut self.web_search_requests, other.web_search_requests);
+    }
+
     pub fn derived_total_tokens(&self) -> u64 {
-        self.total_tokens
-            .unwrap_or(self.input_tokens.unwrap_or(0) + self.output_tokens.unwrap_or(0))
+        self.total_tokens.unwrap_or(
+            self.input_tokens.unwrap_or(0)
+                + self.cache_creation_input_tokens.unwrap_or(0)
+                + self.output_tokens.unwrap_or(0),
+        )
     }
 }
 
@@ -172,7 +662,13 @@ impl Usage {
 pub struct CostBreakdown {
     pub currency: String,
     pub input_cost: f64,
+    /// The part of `input_cost` spent on tokens read from the provider's prompt cache.
+    #[serde(default)]
+    pub cached_input_cost: f64,
     pub output_cost: f64,
+    /// Flat fees not tied to token counts, such as per-request or per-search charges.
+    #[serde(default)]
+    pub fixed_cost: f64,
     pub total_cost: f64,
     pub pricing_source: PricingSource,
 }
@@ -187,6 +683,7 @@ pub enum PricingSource {
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum FinishReason {
     Stop,
     Length,
@@ -199,11 +696,142 @@ pub enum FinishReason {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct RuntimeWarning {
-    pub code: String,
+    pub code: WarningCode,
     pub message: String,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+impl RuntimeWarning {
+    pub fn new(code: WarningCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+macro_rules! warning_codes {
+    ($($(#[$meta:meta])* $variant:ident => $code:literal,)+) => {
+        /// Machine-readable code of a [`RuntimeWarning`].
+        ///
+        /// Serializes as the code string (e.g. `usage_missing` or `runtime.cache_hit`). Codes this
+        /// version does not know, such as ones produced by custom adapters, round-trip through
+        /// [`WarningCode::Other`].
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+        #[serde(from = "String", into = "String")]
+        #[non_exhaustive]
+        pub enum WarningCode {
+            $($(#[$meta])* $variant,)+
+            Other(String),
+        }
+
+        impl WarningCode {
+            /// The serialized code string.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(WarningCode::$variant => $code,)+
+                    WarningCode::Other(code) => code,
+                }
+            }
+        }
+
+        impl From<String> for WarningCode {
+            fn from(code: String) -> Self {
+                match code.as_str() {
+                    $($code => WarningCode::$variant,)+
+                    _ => WarningCode::Other(code),
+                }
+            }
+        }
+    };
+}
+
+warning_codes! {
+    BothTemperatureAndTopPSet => "both_temperature_and_top_p_set",
+    DebugEchoEnabled => "debug_echo_enabled",
+    DefaultMaxTokensApplied => "default_max_tokens_applied",
+    DroppedUnsupportedMetadataKeys => "dropped_unsupported_metadata_keys",
+    EmptyOutput => "empty_output",
+    FallbackModelServed => "fallback_model_served",
+    HostedToolCallDropped => "hosted_tool_call_dropped",
+    HostedToolUnsupported => "hosted_tool_unsupported",
+    ImageOptionUnsupported => "image_option_unsupported",
+    JsonInstructionInjected => "json_instruction_injected",
+    ModelRefusal => "model_refusal",
+    OpenaiIncompleteContentFilter => "openai_incomplete_content_filter",
+    OpenaiIncompleteMaxOutputTokens => "openai_incomplete_max_output_tokens",
+    OpenaiIncompleteMissingReason => "openai_incomplete_missing_reason",
+    OpenaiIncompleteUnknownReason => "openai_incomplete_unknown_reason",
+    SeedUnsupported => "seed_unsupported",
+    StopSequenceEmulated => "stop_sequence_emulated",
+    StructuredOutputParseFailed => "structured_output_parse_failed",
+    ThinkingWithoutSignatureDropped => "thinking_without_signature_dropped",
+    ToolArgumentsInvalidJson => "tool_arguments_invalid_json",
+    ToolResultCoerced => "tool_result_coerced",
+    ToolResultRawProviderContentIgnored => "tool_result_raw_provider_content_ignored",
+    ToolSchemaNotStrictCompatibleStrictDisabled =>
+        "tool_schema_not_strict_compatible_strict_disabled",
+    UnknownAnnotationDropped => "unknown_annotation_dropped",
+    UnknownContentBlockKept => "unknown_content_block_kept",
+    UnknownContentPartDropped => "unknown_content_part_dropped",
+    UnknownFinishReason => "unknown_finish_reason",
+    UnknownStopReason => "unknown_stop_reason",
+    UsageMissing => "usage_missing",
+    UsagePartial => "usage_partial",
+    ContextTruncated => "runtime.context_truncated",
+    ContextSummaryUnpriced => "runtime.context_summary_unpriced",
+    StructuredOutputSchemaViolation => "runtime.structured_output_schema_violation",
+    StructuredOutputSchemaInvalid => "runtime.structured_output_schema_invalid",
+    StructuredOutputRepaired => "runtime.structured_output_repaired",
+    ProviderFailover => "runtime.provider_failover",
+    ParallelToolCallsUnsupported => "runtime.parallel_tool_calls_unsupported",
+    ModelSelected => "runtime.model_selected",
+    MaxOutputTokensDefaulted => "runtime.max_output_tokens_defaulted",
+    MaxOutputTokensClamped => "runtime.max_output_tokens_clamped",
+    HedgeWon => "runtime.hedge_won",
+    ConcurrencyQueued => "runtime.concurrency_queued",
+    CacheHit => "runtime.cache_hit",
+    PricingMissingRule => "pricing.missing_rule",
+    PricingInvalidRule => "pricing.invalid_rule",
+    PricingCurrencyMismatch => "pricing.currency_mismatch",
+    PricingPartialUsage => "pricing.partial_usage",
+    PricingMissingUsage => "pricing.missing_usage",
+    DiscoveryProviderFailed => "discovery.provider_failed",
+}
+
+impl From<&str> for WarningCode {
+    fn from(code: &str) -> Self {
+        WarningCode::from(code.to_string())
+    }
+}
+
+impl From<WarningCode> for String {
+    fn from(code: WarningCode) -> Self {
+        match code {
+            WarningCode::Other(code) => code,
+            code => code.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for WarningCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq<str> for WarningCode {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for WarningCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ModelInfo {
     pub provider: ProviderId,
@@ -216,15 +844,55 @@ pub struct ModelInfo {
     pub max_output_tokens: Option<u32>,
     pub supports_tools: bool,
     pub supports_structured_output: bool,
+    /// List prices published by the provider's model listing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<ModelPricing>,
+    /// Lowercase modality names the model accepts, such as `text` or `image`. Empty when unknown.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub input_modalities: Vec<String>,
+    /// Lowercase modality names the model produces. Empty when unknown.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub output_modalities: Vec<String>,
+    /// The provider has scheduled the model for removal.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    /// Training data cutoff as the provider reports it, such as `2025-01`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub knowledge_cutoff: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+/// Per-token prices in USD, as reported by a provider.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelPricing {
+    pub input_cost_per_token: f64,
+    pub output_cost_per_token: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_input_cost_per_token: Option<f64>,
+    /// Rate for input tokens written to the prompt cache. Falls back to the input rate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write_input_cost_per_token: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_cost_per_token: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(deny_unknown_fields)]
 pub struct ModelCatalog {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub models: Vec<ModelInfo>,
 }
 
+/// A stable name that routes to a provider-specific model id, such as `gpt-5-mini` served as
+/// `openai/gpt-5-mini` on OpenRouter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelAlias {
+    pub alias: String,
+    pub provider: ProviderId,
+    pub model_id: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct DiscoveryOptions {
@@ -234,8 +902,9 @@ pub struct DiscoveryOptions {
     pub refresh_cache: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum ProviderId {
     Openai,
     Anthropic,
@@ -243,6 +912,29 @@ pub enum ProviderId {
     Other(String),
 }
 
+impl ProviderId {
+    /// Maps a lowercase provider name, as written in config files, to its id. Unknown names
+    /// become [`ProviderId::Other`].
+    pub(crate) fn from_name(name: String) -> Self {
+        match name.as_str() {
+            "openai" => ProviderId::Openai,
+            "anthropic" => ProviderId::Anthropic,
+            "openrouter" => ProviderId::Openrouter,
+            _ => ProviderId::Other(name),
+        }
+    }
+
+    /// The lowercase name `from_name` maps back to this id.
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            ProviderId::Openai => "openai",
+            ProviderId::Anthropic => "anthropic",
+            ProviderId::Openrouter => "openrouter",
+            ProviderId::Other(name) => name,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ProviderCapabilities {
@@ -250,6 +942,10 @@ pub struct ProviderCapabilities {
     pub supports_structured_output: bool,
     pub supports_thinking: bool,
     pub supports_remote_discovery: bool,
+    /// Whether the provider honors `ProviderRequest::parallel_tool_calls`. When it does not, the
+    /// runtime drops the setting with a warning.
+    #[serde(default)]
+    pub supports_parallel_tool_calls: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -259,5 +955,71 @@ pub struct AdapterContext {
     pub metadata: BTreeMap<String, String>,
 }
 
+/// Bearer token issued by a `TokenProvider`. The runtime reuses it until `expires_at`; a token
+/// without an expiry is reused until the provider rejects it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AccessToken {
+    pub token: String,
+    pub expires_at: Option<SystemTime>,
+}
+
+impl AccessToken {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            expires_at: None,
+        }
+    }
+
+    pub fn with_expires_at(mut self, expires_at: SystemTime) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+}
+
+impl std::fmt::Debug for AccessToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccessToken")
+            .field("token", &"<redacted>")
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+/// `ProviderId` is an internally tagged enum, which JSON cannot use as a map key, so
+/// `provider_options` is keyed by the provider name instead.
+mod provider_options_serde {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::ProviderId;
+
+    pub(super) fn serialize<S: Serializer>(
+        options: &BTreeMap<ProviderId, serde_json::Value>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        options
+            .iter()
+            .map(|(provider, value)| (provider.name(), value))
+            .collect::<BTreeMap<_, _>>()
+            .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<ProviderId, serde_json::Value>, D::Error> {
+        let raw = BTreeMap::<String, serde_json::Value>::deserialize(deserializer)?;
+        Ok(raw
+            .into_iter()
+            .map(|(name, value)| (provider(name), value))
+            .collect())
+    }
+
+    fn provider(name: String) -> ProviderId {
+        ProviderId::from_name(name)
+    }
+}
+
 #[cfg(test)]
 mod tests;
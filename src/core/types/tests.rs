@@ -12,19 +12,29 @@ fn test_provider_request_serde_roundtrip() {
         model: ModelRef {
             provider_hint: Some(ProviderId::Openai),
             model_id: "gpt-5-mini".to_string(),
+            requirements: None,
         },
         messages: vec![Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "hello".to_string(),
             }],
+            cache_hint: None,
         }],
         tools: vec![ToolDefinition {
             name: "lookup".to_string(),
             description: None,
             parameters_schema: json!({"type": "object"}),
+            cache_hint: None,
         }],
+        hosted_tools: vec![
+            HostedTool::WebSearch {
+                allowed_domains: vec!["example.com".to_string()],
+            },
+            HostedTool::CodeInterpreter,
+        ],
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::JsonSchema {
             name: "answer".to_string(),
             schema: json!({"type": "object", "properties": {"ok": {"type": "boolean"}}}),
@@ -34,6 +44,18 @@ fn test_provider_request_serde_roundtrip() {
         max_output_tokens: None,
         stop: Vec::new(),
         metadata,
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::from([
+            (
+                ProviderId::Openrouter,
+                json!({"provider": {"sort": "price"}}),
+            ),
+            (
+                ProviderId::Other("local".to_string()),
+                json!({"keep_alive": 30}),
+            ),
+        ]),
     };
 
     let value = serde_json::to_value(&req).expect("request should serialize");
@@ -47,12 +69,44 @@ fn test_provider_request_serde_roundtrip() {
         .get("response_format")
         .expect("response_format should exist");
     assert_eq!(response_format.get("type"), Some(&json!("json_schema")));
+    assert_eq!(
+        value.pointer("/provider_options/openrouter/provider/sort"),
+        Some(&json!("price"))
+    );
+    assert_eq!(
+        value.pointer("/provider_options/local/keep_alive"),
+        Some(&json!(30))
+    );
+    assert_eq!(
+        value.get("hosted_tools"),
+        Some(&json!([
+            { "type": "web_search", "allowed_domains": ["example.com"] },
+            { "type": "code_interpreter" }
+        ]))
+    );
 
     let roundtrip: ProviderRequest =
         serde_json::from_value(value).expect("request should deserialize");
     assert_eq!(roundtrip, req);
 }
 
+#[test]
+fn test_provider_request_new_matches_serde_defaults() {
+    let model = ModelRef {
+        provider_hint: None,
+        model_id: "claude-sonnet-4-5".to_string(),
+        requirements: None,
+    };
+
+    let decoded: ProviderRequest = serde_json::from_value(json!({
+        "model": { "model_id": "claude-sonnet-4-5" },
+        "messages": []
+    }))
+    .expect("minimal request should deserialize");
+
+    assert_eq!(ProviderRequest::new(model, Vec::new()), decoded);
+}
+
 #[test]
 fn test_usage_total_tokens_derivation() {
     let explicit = Usage {
@@ -60,6 +114,9 @@ fn test_usage_total_tokens_derivation() {
         output_tokens: Some(3),
         cached_input_tokens: Some(7),
         total_tokens: Some(99),
+        reasoning_tokens: None,
+        cache_creation_input_tokens: None,
+        web_search_requests: None,
     };
     assert_eq!(explicit.derived_total_tokens(), 99);
 
@@ -68,6 +125,9 @@ fn test_usage_total_tokens_derivation() {
         output_tokens: Some(3),
         cached_input_tokens: Some(100),
         total_tokens: None,
+        reasoning_tokens: None,
+        cache_creation_input_tokens: None,
+        web_search_requests: None,
     };
     assert_eq!(derived.derived_total_tokens(), 5);
 
@@ -76,6 +136,9 @@ fn test_usage_total_tokens_derivation() {
         output_tokens: Some(4),
         cached_input_tokens: None,
         total_tokens: None,
+        reasoning_tokens: None,
+        cache_creation_input_tokens: None,
+        web_search_requests: None,
     };
     assert_eq!(zero_based.derived_total_tokens(), 4);
 }
@@ -89,6 +152,7 @@ fn test_content_part_invariants() {
                 text: "done".to_string(),
             },
             raw_provider_content: None,
+            is_error: false,
         },
     };
 
@@ -109,6 +173,77 @@ fn test_content_part_invariants() {
     assert!(err.to_string().contains("unknown field"));
 }
 
+#[test]
+fn test_content_part_other_roundtrip() {
+    let part = ContentPart::Other {
+        kind: "computer_call".to_string(),
+        raw: json!({ "type": "computer_call", "id": "cu_1" }),
+    };
+
+    let value = serde_json::to_value(&part).expect("content part should serialize");
+    assert_eq!(
+        value,
+        json!({
+            "type": "other",
+            "kind": "computer_call",
+            "raw": { "type": "computer_call", "id": "cu_1" }
+        })
+    );
+    let decoded: ContentPart =
+        serde_json::from_value(value).expect("content part should deserialize");
+    assert_eq!(decoded, part);
+}
+
+#[test]
+fn test_tool_result_content_variants_roundtrip() {
+    let cases = [
+        (
+            ToolResultContent::Text {
+                text: "done".to_string(),
+            },
+            json!({ "type": "text", "text": "done" }),
+        ),
+        (
+            ToolResultContent::Json {
+                value: json!({ "temperature_c": 21 }),
+            },
+            json!({ "type": "json", "value": { "temperature_c": 21 } }),
+        ),
+        (
+            ToolResultContent::Parts {
+                parts: vec![ContentPart::Text {
+                    text: "line one".to_string(),
+                }],
+            },
+            json!({ "type": "parts", "parts": [{ "type": "text", "text": "line one" }] }),
+        ),
+    ];
+
+    for (content, expected) in cases {
+        let value = serde_json::to_value(&content).expect("tool result content should serialize");
+        assert_eq!(value, expected);
+        let decoded: ToolResultContent =
+            serde_json::from_value(value).expect("tool result content should deserialize");
+        assert_eq!(decoded, content);
+    }
+}
+
+#[test]
+fn test_tool_result_optional_fields_default() {
+    let decoded: ToolResult = serde_json::from_value(json!({
+        "tool_call_id": "call_1",
+        "content": { "type": "json", "value": [1, 2] }
+    }))
+    .expect("tool result should deserialize");
+
+    assert_eq!(decoded.raw_provider_content, None);
+    assert!(!decoded.is_error);
+
+    let value = serde_json::to_value(&decoded).expect("tool result should serialize");
+    assert_eq!(value.get("raw_provider_content"), None);
+    assert_eq!(value.get("is_error"), None);
+}
+
 #[test]
 fn test_tool_choice_specific_roundtrip() {
     let choice = ToolChoice::Specific {
@@ -123,3 +258,28 @@ fn test_tool_choice_specific_roundtrip() {
         serde_json::from_value(value).expect("tool choice should deserialize");
     assert_eq!(decoded, choice);
 }
+
+#[test]
+fn test_warning_code_serializes_as_code_string() {
+    let warning = RuntimeWarning::new(WarningCode::UsageMissing, "usage missing");
+    let value = serde_json::to_value(&warning).expect("warning should serialize");
+    assert_eq!(value.get("code"), Some(&json!("usage_missing")));
+
+    let decoded: RuntimeWarning =
+        serde_json::from_value(json!({ "code": "runtime.cache_hit", "message": "hit" }))
+            .expect("warning should deserialize");
+    assert_eq!(decoded.code, WarningCode::CacheHit);
+    assert_eq!(decoded.code, "runtime.cache_hit");
+}
+
+#[test]
+fn test_warning_code_unknown_roundtrips_through_other() {
+    let code: WarningCode =
+        serde_json::from_value(json!("custom.adapter_quirk")).expect("code should deserialize");
+    assert_eq!(code, WarningCode::Other("custom.adapter_quirk".to_string()));
+    assert_eq!(code.to_string(), "custom.adapter_quirk");
+    assert_eq!(
+        serde_json::to_value(&code).expect("code should serialize"),
+        json!("custom.adapter_quirk")
+    );
+}
@@ -8,6 +8,7 @@ fn assistant(content: Vec<ContentPart>) -> Message {
     Message {
         role: MessageRole::Assistant,
         content,
+        cache_hint: None,
     }
 }
 
@@ -15,6 +16,7 @@ fn user(content: Vec<ContentPart>) -> Message {
     Message {
         role: MessageRole::User,
         content,
+        cache_hint: None,
     }
 }
 
@@ -31,6 +33,7 @@ fn test_handoff_normalization_is_identity_for_assistant_content() {
             text: "result payload".to_string(),
         },
         raw_provider_content: None,
+        is_error: false,
     };
     let messages = vec![assistant(vec![
         ContentPart::Text {
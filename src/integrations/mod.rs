@@ -0,0 +1,9 @@
+//! Optional adapters that plug `ProviderRuntime` into other ecosystems. Each integration is
+//! compiled only with its cargo feature.
+
+#[cfg(feature = "openai-server")]
+pub mod openai_server;
+#[cfg(feature = "redis")]
+pub mod redis;
+#[cfg(feature = "tower")]
+pub mod tower;
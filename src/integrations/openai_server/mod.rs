@@ -0,0 +1,630 @@
+//! OpenAI-compatible HTTP facade over `ProviderRuntime`.
+//!
+//! Accepts Chat Completions requests, routes them through the runtime like any other canonical
+//! request, and renders the result back in the Chat Completions wire format, so tools that only
+//! speak the OpenAI API can reach every registered provider.
+//!
+//! Every request is served with the runtime's own provider credentials, so anyone who can reach
+//! the endpoint spends on those accounts. [`router`] does no authentication and must sit behind
+//! a gateway or middleware that does; [`router_with_api_keys`] checks a bearer key itself.
+
+use std::collections::BTreeMap;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Json, Response};
+use axum::routing::{get, post};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::core::error::RuntimeError;
+use crate::core::types::{
+    AssistantOutput, ContentPart, DiscoveryOptions, FinishReason, Message, MessageRole, ModelRef,
+    ProviderRequest, ProviderResponse, ResponseFormat, ToolCall, ToolChoice, ToolDefinition,
+    ToolResult, ToolResultContent, Usage,
+};
+use crate::runtime::ProviderRuntime;
+
+#[derive(Clone)]
+struct ServerState {
+    runtime: Arc<ProviderRuntime>,
+    /// Bearer keys accepted in the `Authorization` header; empty disables the check.
+    api_keys: Arc<[String]>,
+}
+
+impl ServerState {
+    fn authorize(&self, headers: &HeaderMap) -> Result<(), ApiError> {
+        if self.api_keys.is_empty() {
+            return Ok(());
+        }
+        let presented = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .unwrap_or_default();
+        if !presented.is_empty()
+            && self
+                .api_keys
+                .iter()
+                .any(|key| constant_time_eq(key.as_bytes(), presented.as_bytes()))
+        {
+            Ok(())
+        } else {
+            Err(ApiError {
+                status: StatusCode::UNAUTHORIZED,
+                kind: "invalid_request_error",
+                message: "missing or invalid API key".to_string(),
+            })
+        }
+    }
+}
+
+/// Builds a router serving `POST /v1/chat/completions` and `GET /v1/models` without
+/// authentication; put it behind something that authenticates callers.
+pub fn router(runtime: Arc<ProviderRuntime>) -> Router {
+    router_with_api_keys(runtime, Vec::<String>::new())
+}
+
+/// Like [`router`], but answers 401 unless the request carries one of `api_keys` as
+/// `Authorization: Bearer <key>`. An empty `api_keys` accepts every request.
+pub fn router_with_api_keys(
+    runtime: Arc<ProviderRuntime>,
+    api_keys: impl IntoIterator<Item = impl Into<String>>,
+) -> Router {
+    let state = ServerState {
+        runtime,
+        api_keys: api_keys.into_iter().map(Into::into).collect(),
+    };
+    Router::new()
+        .route("/v1/chat/completions", post(chat_completions))
+        .route("/v1/models", get(list_models))
+        .with_state(state)
+}
+
+/// Serves [`router`] on `listener` until the server fails. See the module docs on
+/// authentication.
+pub async fn serve(
+    listener: tokio::net::TcpListener,
+    runtime: Arc<ProviderRuntime>,
+) -> std::io::Result<()> {
+    axum::serve(listener, router(runtime)).await
+}
+
+async fn chat_completions(
+    State(state): State<ServerState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    if let Err(error) = state.authorize(&headers) {
+        return error.into_response();
+    }
+    let request = match serde_json::from_slice::<ChatCompletionRequest>(&body) {
+        Ok(request) => request,
+        Err(error) => {
+            return ApiError::invalid_request(format!("invalid request body: {error}"))
+                .into_response();
+        }
+    };
+    let provider_request = match request.into_provider_request() {
+        Ok(provider_request) => provider_request,
+        Err(error) => return error.into_response(),
+    };
+
+    match state.runtime.run(provider_request).await {
+        Ok(response) => Json(chat_completion_body(&response)).into_response(),
+        Err(error) => ApiError::from(error).into_response(),
+    }
+}
+
+async fn list_models(State(state): State<ServerState>, headers: HeaderMap) -> Response {
+    if let Err(error) = state.authorize(&headers) {
+        return error.into_response();
+    }
+    let opts = DiscoveryOptions {
+        remote: false,
+        include_provider: Vec::new(),
+        refresh_cache: false,
+    };
+    match state.runtime.discover_models(opts).await {
+        Ok(catalog) => {
+            let data = catalog
+                .models
+                .iter()
+                .map(|model| {
+                    json!({
+                        "id": model.model_id,
+                        "object": "model",
+                        "owned_by": model.provider.name(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            Json(json!({ "object": "list", "data": data })).into_response()
+        }
+        Err(error) => ApiError::from(error).into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    #[serde(default)]
+    tools: Vec<ChatTool>,
+    #[serde(default)]
+    tool_choice: Option<Value>,
+    #[serde(default)]
+    parallel_tool_calls: Option<bool>,
+    #[serde(default)]
+    response_format: Option<Value>,
+    #[serde(default)]
+    temperature: Option<f32>,
+    #[serde(default)]
+    top_p: Option<f32>,
+    #[serde(default)]
+    max_tokens: Option<u32>,
+    #[serde(default)]
+    max_completion_tokens: Option<u32>,
+    #[serde(default)]
+    stop: Option<StopField>,
+    #[serde(default)]
+    n: Option<u8>,
+    #[serde(default)]
+    seed: Option<i64>,
+    #[serde(default)]
+    stream: bool,
+    #[serde(default)]
+    metadata: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessage {
+    role: String,
+    #[serde(default)]
+    content: Option<ChatContent>,
+    #[serde(default)]
+    tool_calls: Vec<ChatToolCall>,
+    #[serde(default)]
+    tool_call_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ChatContent {
+    Text(String),
+    Parts(Vec<ChatContentPart>),
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatContentPart {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatToolCall {
+    id: String,
+    function: ChatFunctionCall,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatFunctionCall {
+    name: String,
+    arguments: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatTool {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    function: Option<ChatFunction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatFunction {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    parameters: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StopField {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl ChatCompletionRequest {
+    fn into_provider_request(self) -> Result<ProviderRequest, ApiError> {
+        if self.stream {
+            return Err(ApiError::invalid_request(
+                "streaming chat completions are not supported",
+            ));
+        }
+
+        let messages = self
+            .messages
+            .into_iter()
+            .map(ChatMessage::into_message)
+            .collect::<Result<Vec<_>, _>>()?;
+        let tools = self
+            .tools
+            .into_iter()
+            .map(ChatTool::into_tool_definition)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ProviderRequest {
+            model: ModelRef {
+                provider_hint: None,
+                model_id: self.model,
+                requirements: None,
+            },
+            messages,
+            tools,
+            hosted_tools: Vec::new(),
+            tool_choice: self
+                .tool_choice
+                .map(parse_tool_choice)
+                .transpose()?
+                .unwrap_or_default(),
+            parallel_tool_calls: self.parallel_tool_calls,
+            response_format: self
+                .response_format
+                .map(parse_response_format)
+                .transpose()?
+                .unwrap_or_default(),
+            temperature: self.temperature,
+            top_p: self.top_p,
+            max_output_tokens: self.max_completion_tokens.or(self.max_tokens),
+            stop: match self.stop {
+                Some(StopField::One(stop)) => vec![stop],
+                Some(StopField::Many(stop)) => stop,
+                None => Vec::new(),
+            },
+            metadata: self.metadata,
+            n: self.n,
+            seed: self.seed,
+            provider_options: BTreeMap::new(),
+        })
+    }
+}
+
+impl ChatTool {
+    fn into_tool_definition(self) -> Result<ToolDefinition, ApiError> {
+        let function = match (self.kind.as_str(), self.function) {
+            ("function", Some(function)) => function,
+            ("function", None) => {
+                return Err(ApiError::invalid_request(
+                    "function tools require 'function'",
+                ));
+            }
+            (kind, _) => {
+                return Err(ApiError::invalid_request(format!(
+                    "unsupported tool type '{kind}'; only function tools are supported"
+                )));
+            }
+        };
+        Ok(ToolDefinition {
+            name: function.name,
+            description: function.description,
+            parameters_schema: function
+                .parameters
+                .unwrap_or_else(|| json!({ "type": "object", "properties": {} })),
+            cache_hint: None,
+        })
+    }
+}
+
+impl ChatMessage {
+    fn into_message(self) -> Result<Message, ApiError> {
+        let role = match self.role.as_str() {
+            "system" => MessageRole::System,
+            "developer" => MessageRole::Developer,
+            "user" => MessageRole::User,
+            "assistant" => MessageRole::Assistant,
+            "tool" => MessageRole::Tool,
+            other => {
+                return Err(ApiError::invalid_request(format!(
+                    "unsupported message role '{other}'"
+                )));
+            }
+        };
+        let text = self.content.map(ChatContent::into_text).transpose()?;
+
+        let content = if matches!(role, MessageRole::Tool) {
+            let tool_call_id = self
+                .tool_call_id
+                .ok_or_else(|| ApiError::invalid_request("tool messages require 'tool_call_id'"))?;
+            vec![ContentPart::ToolResult {
+                tool_result: ToolResult {
+                    tool_call_id,
+                    content: ToolResultContent::Text {
+                        text: text.unwrap_or_default(),
+                    },
+                    raw_provider_content: None,
+                    is_error: false,
+                },
+            }]
+        } else {
+            let mut content = text
+                .filter(|text| !text.is_empty())
+                .map(|text| vec![ContentPart::Text { text }])
+                .unwrap_or_default();
+            for call in self.tool_calls {
+                let arguments_json =
+                    serde_json::from_str(&call.function.arguments).map_err(|error| {
+                        ApiError::invalid_request(format!(
+                            "tool call '{}' arguments are not valid JSON: {error}",
+                            call.id
+                        ))
+                    })?;
+                content.push(ContentPart::ToolCall {
+                    tool_call: ToolCall {
+                        id: call.id,
+                        name: call.function.name,
+                        arguments_json,
+                    },
+                });
+            }
+            content
+        };
+
+        Ok(Message {
+            role,
+            content,
+            cache_hint: None,
+        })
+    }
+}
+
+impl ChatContent {
+    fn into_text(self) -> Result<String, ApiError> {
+        match self {
+            Self::Text(text) => Ok(text),
+            Self::Parts(parts) => parts
+                .into_iter()
+                .map(|part| match (part.kind.as_str(), part.text) {
+                    ("text", Some(text)) => Ok(text),
+                    (kind, _) => Err(ApiError::invalid_request(format!(
+                        "unsupported content part type '{kind}'"
+                    ))),
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(|texts| texts.concat()),
+        }
+    }
+}
+
+fn parse_tool_choice(value: Value) -> Result<ToolChoice, ApiError> {
+    match &value {
+        Value::String(choice) => match choice.as_str() {
+            "none" => Ok(ToolChoice::None),
+            "auto" => Ok(ToolChoice::Auto),
+            "required" => Ok(ToolChoice::Required),
+            other => Err(ApiError::invalid_request(format!(
+                "unsupported tool_choice '{other}'"
+            ))),
+        },
+        _ => value
+            .pointer("/function/name")
+            .and_then(Value::as_str)
+            .map(|name| ToolChoice::Specific {
+                name: name.to_string(),
+            })
+            .ok_or_else(|| ApiError::invalid_request("tool_choice object requires function.name")),
+    }
+}
+
+fn parse_response_format(value: Value) -> Result<ResponseFormat, ApiError> {
+    match value.get("type").and_then(Value::as_str) {
+        Some("text") => Ok(ResponseFormat::Text),
+        Some("json_object") => Ok(ResponseFormat::JsonObject),
+        Some("json_schema") => {
+            let json_schema = value.get("json_schema");
+            let name = json_schema
+                .and_then(|schema| schema.get("name"))
+                .and_then(Value::as_str)
+                .unwrap_or("response")
+                .to_string();
+            let schema = json_schema
+                .and_then(|schema| schema.get("schema"))
+                .cloned()
+                .ok_or_else(|| {
+                    ApiError::invalid_request("response_format json_schema requires a schema")
+                })?;
+            Ok(ResponseFormat::JsonSchema { name, schema })
+        }
+        _ => Err(ApiError::invalid_request(
+            "response_format.type must be text, json_object, or json_schema",
+        )),
+    }
+}
+
+fn chat_completion_body(response: &ProviderResponse) -> Value {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let created = now.as_secs();
+    // Without a provider request id, a random suffix keeps ids unique within the same second.
+    let id = response
+        .request_id
+        .as_deref()
+        .map(|request_id| format!("chatcmpl-{request_id}"))
+        .unwrap_or_else(|| {
+            format!(
+                "chatcmpl-{:016x}",
+                RandomState::new().hash_one(now.as_nanos())
+            )
+        });
+    let alternatives = response
+        .alternatives
+        .iter()
+        .map(|alternative| (&alternative.output, &alternative.finish_reason));
+    let choices = std::iter::once((&response.output, &response.finish_reason))
+        .chain(alternatives)
+        .enumerate()
+        .map(|(index, (output, finish_reason))| choice_body(index, output, finish_reason))
+        .collect::<Vec<_>>();
+
+    json!({
+        "id": id,
+        "object": "chat.completion",
+        "created": created,
+        "model": response.model,
+        "choices": choices,
+        "usage": usage_body(&response.usage),
+    })
+}
+
+fn choice_body(index: usize, output: &AssistantOutput, finish_reason: &FinishReason) -> Value {
+    let text = output
+        .content
+        .iter()
+        .filter_map(|part| match part {
+            ContentPart::Text { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<String>();
+    let tool_calls = output
+        .content
+        .iter()
+        .filter_map(|part| match part {
+            ContentPart::ToolCall { tool_call } => Some(json!({
+                "id": tool_call.id,
+                "type": "function",
+                "function": {
+                    "name": tool_call.name,
+                    "arguments": tool_call.arguments_json.to_string(),
+                },
+            })),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut message = json!({
+        "role": "assistant",
+        "content": if text.is_empty() && !tool_calls.is_empty() { Value::Null } else { Value::String(text) },
+    });
+    if !tool_calls.is_empty() {
+        message["tool_calls"] = Value::Array(tool_calls);
+    }
+
+    json!({
+        "index": index,
+        "message": message,
+        "finish_reason": match finish_reason {
+            FinishReason::Length => "length",
+            FinishReason::ToolCalls => "tool_calls",
+            FinishReason::ContentFilter => "content_filter",
+            _ => "stop",
+        },
+    })
+}
+
+fn usage_body(usage: &Usage) -> Value {
+    let prompt_tokens = usage.input_tokens.unwrap_or_default();
+    let completion_tokens = usage.output_tokens.unwrap_or_default();
+    json!({
+        "prompt_tokens": prompt_tokens,
+        "completion_tokens": completion_tokens,
+        "total_tokens": usage
+            .total_tokens
+            .unwrap_or(prompt_tokens + completion_tokens),
+    })
+}
+
+/// An error rendered in the OpenAI error envelope.
+#[derive(Debug)]
+struct ApiError {
+    status: StatusCode,
+    kind: &'static str,
+    message: String,
+}
+
+impl ApiError {
+    fn invalid_request(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            kind: "invalid_request_error",
+            message: message.into(),
+        }
+    }
+}
+
+impl From<RuntimeError> for ApiError {
+    fn from(error: RuntimeError) -> Self {
+        let (status, kind) = match &error {
+            RuntimeError::RoutingError(_) => (StatusCode::NOT_FOUND, "invalid_request_error"),
+            RuntimeError::CapabilityMismatch { .. }
+            | RuntimeError::EncodeWarningRejected { .. }
+            | RuntimeError::MiddlewareRejected { .. }
+            | RuntimeError::ContextWindowExceeded { .. } => {
+                (StatusCode::BAD_REQUEST, "invalid_request_error")
+            }
+            RuntimeError::RateLimited { .. } => (StatusCode::TOO_MANY_REQUESTS, "rate_limit_error"),
+            RuntimeError::BudgetExceeded { .. } => {
+                (StatusCode::TOO_MANY_REQUESTS, "insufficient_quota")
+            }
+            RuntimeError::Timeout { .. } => (StatusCode::GATEWAY_TIMEOUT, "timeout_error"),
+            RuntimeError::TransportError { .. }
+            | RuntimeError::SerializationError { .. }
+            | RuntimeError::StructuredOutputInvalid { .. } => {
+                (StatusCode::BAD_GATEWAY, "upstream_error")
+            }
+            // An upstream 401 or 403 is the server's credential failing, not the client's, so it
+            // is not passed through.
+            RuntimeError::ProviderProtocolError { status_code, .. } => (
+                status_code
+                    .filter(|code| !matches!(code, 401 | 403))
+                    .and_then(|code| StatusCode::from_u16(code).ok())
+                    .filter(|status| status.is_client_error() || status.is_server_error())
+                    .unwrap_or(StatusCode::BAD_GATEWAY),
+                "upstream_error",
+            ),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "server_error"),
+        };
+
+        Self {
+            status,
+            kind,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Compares without returning early on the first differing byte, so response timing does not
+/// reveal how much of a key was guessed.
+fn constant_time_eq(expected: &[u8], presented: &[u8]) -> bool {
+    expected.len() == presented.len()
+        && expected
+            .iter()
+            .zip(presented)
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = json!({
+            "error": {
+                "message": self.message,
+                "type": self.kind,
+            }
+        });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests;
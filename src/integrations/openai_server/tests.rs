@@ -0,0 +1,391 @@
+use std::future::poll_fn;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serde_json::{Value, json};
+use tower_service::Service;
+
+use super::{router, router_with_api_keys};
+use crate::core::error::ProviderError;
+use crate::core::traits::ProviderAdapter;
+use crate::core::types::{
+    AdapterContext, AssistantOutput, ContentPart, DiscoveryOptions, FinishReason, MessageRole,
+    ModelCatalog, ModelInfo, ProviderCapabilities, ProviderId, ProviderRequest, ProviderResponse,
+    ResponseAlternative, ToolCall, ToolChoice, ToolResultContent, Usage,
+};
+use crate::runtime::ProviderRuntime;
+
+#[derive(Default)]
+struct RecordingAdapter {
+    last_request: Mutex<Option<ProviderRequest>>,
+    /// Fails every call with this upstream HTTP status instead of answering.
+    error_status: Option<u16>,
+    /// Answers without a provider request id.
+    without_request_id: bool,
+}
+
+#[async_trait]
+impl ProviderAdapter for RecordingAdapter {
+    fn id(&self) -> ProviderId {
+        ProviderId::Anthropic
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            supports_tools: true,
+            supports_structured_output: true,
+            supports_thinking: false,
+            supports_remote_discovery: false,
+            supports_parallel_tool_calls: false,
+        }
+    }
+
+    async fn run(
+        &self,
+        req: &ProviderRequest,
+        _ctx: &AdapterContext,
+    ) -> Result<ProviderResponse, ProviderError> {
+        *self.last_request.lock().expect("request lock") = Some(req.clone());
+        if let Some(status_code) = self.error_status {
+            return Err(ProviderError::Status {
+                provider: ProviderId::Anthropic,
+                model: Some(req.model.model_id.clone()),
+                status_code,
+                request_id: None,
+                message: "invalid x-api-key".to_string(),
+                rate_limit: None,
+            });
+        }
+        let alternatives = (1..req.n.unwrap_or(1))
+            .map(|_| ResponseAlternative {
+                output: AssistantOutput {
+                    content: vec![ContentPart::Text {
+                        text: "cut short".to_string(),
+                    }],
+                    structured_output: None,
+                    annotations: Vec::new(),
+                },
+                finish_reason: FinishReason::Length,
+            })
+            .collect();
+        Ok(ProviderResponse {
+            output: AssistantOutput {
+                content: vec![ContentPart::ToolCall {
+                    tool_call: ToolCall {
+                        id: "call_2".to_string(),
+                        name: "lookup".to_string(),
+                        arguments_json: json!({ "q": "rust" }),
+                    },
+                }],
+                structured_output: None,
+                annotations: Vec::new(),
+            },
+            usage: Usage {
+                input_tokens: Some(12),
+                output_tokens: Some(3),
+                ..Usage::default()
+            },
+            cost: None,
+            provider: ProviderId::Anthropic,
+            model: req.model.model_id.clone(),
+            raw_provider_response: None,
+            finish_reason: FinishReason::ToolCalls,
+            warnings: Vec::new(),
+            alternatives,
+            response_id: None,
+            request_id: (!self.without_request_id).then(|| "req_1".to_string()),
+            rate_limit: None,
+        })
+    }
+
+    async fn discover_models(
+        &self,
+        _opts: &DiscoveryOptions,
+        _ctx: &AdapterContext,
+    ) -> Result<Vec<ModelInfo>, ProviderError> {
+        Ok(Vec::new())
+    }
+}
+
+fn runtime(adapter: Arc<RecordingAdapter>) -> Arc<ProviderRuntime> {
+    Arc::new(
+        ProviderRuntime::builder()
+            .with_adapter(adapter)
+            .with_model_catalog(ModelCatalog {
+                models: vec![ModelInfo {
+                    provider: ProviderId::Anthropic,
+                    model_id: "claude-test".to_string(),
+                    display_name: None,
+                    context_window: None,
+                    max_output_tokens: None,
+                    supports_tools: true,
+                    supports_structured_output: true,
+                    pricing: None,
+                    input_modalities: Vec::new(),
+                    output_modalities: Vec::new(),
+                    deprecated: false,
+                    knowledge_cutoff: None,
+                }],
+            })
+            .build(),
+    )
+}
+
+async fn send(runtime: Arc<ProviderRuntime>, request: Request<Body>) -> (StatusCode, Value) {
+    send_to(router(runtime), request).await
+}
+
+async fn send_to(mut app: axum::Router, request: Request<Body>) -> (StatusCode, Value) {
+    poll_fn(|cx| Service::<Request<Body>>::poll_ready(&mut app, cx))
+        .await
+        .expect("router should be ready");
+    let response = app.call(request).await.expect("router is infallible");
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    (
+        status,
+        serde_json::from_slice(&body).expect("response body should be JSON"),
+    )
+}
+
+fn chat_request(body: Value) -> Request<Body> {
+    Request::post("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .expect("build request")
+}
+
+#[tokio::test]
+async fn test_chat_completions_routes_through_runtime() {
+    let adapter = Arc::new(RecordingAdapter::default());
+    let (status, body) = send(
+        runtime(Arc::clone(&adapter)),
+        chat_request(json!({
+            "model": "claude-test",
+            "messages": [
+                { "role": "system", "content": "be brief" },
+                { "role": "user", "content": [{ "type": "text", "text": "find rust" }] },
+                {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [{
+                        "id": "call_1",
+                        "type": "function",
+                        "function": { "name": "lookup", "arguments": "{\"q\":\"crab\"}" }
+                    }]
+                },
+                { "role": "tool", "tool_call_id": "call_1", "content": "no results" }
+            ],
+            "tools": [{
+                "type": "function",
+                "function": { "name": "lookup", "parameters": { "type": "object" } }
+            }],
+            "tool_choice": { "type": "function", "function": { "name": "lookup" } },
+            "max_tokens": 64,
+            "stop": "END",
+            "user": "ignored"
+        })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["id"], "chatcmpl-req_1");
+    assert_eq!(body["model"], "claude-test");
+    assert_eq!(body["choices"][0]["finish_reason"], "tool_calls");
+    assert_eq!(body["choices"][0]["message"]["content"], Value::Null);
+    assert_eq!(
+        body["choices"][0]["message"]["tool_calls"][0]["function"],
+        json!({ "name": "lookup", "arguments": "{\"q\":\"rust\"}" })
+    );
+    assert_eq!(
+        body["usage"],
+        json!({ "prompt_tokens": 12, "completion_tokens": 3, "total_tokens": 15 })
+    );
+
+    let sent = adapter
+        .last_request
+        .lock()
+        .expect("request lock")
+        .clone()
+        .expect("request should reach the adapter");
+    assert_eq!(sent.model.provider_hint, None);
+    assert_eq!(sent.max_output_tokens, Some(64));
+    assert_eq!(sent.stop, vec!["END".to_string()]);
+    assert_eq!(
+        sent.tool_choice,
+        ToolChoice::Specific {
+            name: "lookup".to_string()
+        }
+    );
+    assert_eq!(sent.messages.len(), 4);
+    assert_eq!(sent.messages[3].role, MessageRole::Tool);
+    assert!(matches!(
+        &sent.messages[3].content[0],
+        ContentPart::ToolResult { tool_result }
+            if tool_result.tool_call_id == "call_1"
+                && tool_result.content == ToolResultContent::Text { text: "no results".to_string() }
+    ));
+}
+
+#[tokio::test]
+async fn test_chat_completions_reports_errors_in_openai_envelope() {
+    let adapter = Arc::new(RecordingAdapter::default());
+
+    let (status, body) = send(
+        runtime(Arc::clone(&adapter)),
+        chat_request(json!({ "model": "claude-test", "messages": [], "stream": true })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["type"], "invalid_request_error");
+
+    let (status, body) = send(
+        runtime(Arc::clone(&adapter)),
+        chat_request(json!({
+            "model": "unknown-model",
+            "messages": [{ "role": "user", "content": "hi" }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(body["error"]["message"].is_string());
+    assert!(adapter.last_request.lock().expect("request lock").is_none());
+}
+
+#[tokio::test]
+async fn test_models_lists_catalog() {
+    let request = Request::get("/v1/models")
+        .body(Body::empty())
+        .expect("build request");
+    let (status, body) = send(runtime(Arc::default()), request).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["object"], "list");
+    assert!(
+        body["data"]
+            .as_array()
+            .expect("data array")
+            .contains(&json!({ "id": "claude-test", "object": "model", "owned_by": "anthropic" }))
+    );
+}
+
+#[tokio::test]
+async fn test_chat_completions_choices_keep_their_own_finish_reason() {
+    let (status, body) = send(
+        runtime(Arc::new(RecordingAdapter::default())),
+        chat_request(json!({
+            "model": "claude-test",
+            "messages": [{ "role": "user", "content": "hi" }],
+            "n": 2
+        })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["choices"][0]["finish_reason"], "tool_calls");
+    assert_eq!(body["choices"][1]["index"], 1);
+    assert_eq!(body["choices"][1]["finish_reason"], "length");
+    assert_eq!(body["choices"][1]["message"]["content"], "cut short");
+}
+
+#[tokio::test]
+async fn test_router_with_api_keys_requires_a_listed_bearer_key() {
+    let adapter = Arc::new(RecordingAdapter::default());
+    let app = || router_with_api_keys(runtime(Arc::clone(&adapter)), ["key-a", "key-b"]);
+    let body = json!({ "model": "claude-test", "messages": [{ "role": "user", "content": "hi" }] });
+    let with_key = |key: &str| {
+        let mut request = chat_request(body.clone());
+        request.headers_mut().insert(
+            "authorization",
+            format!("Bearer {key}").parse().expect("header value"),
+        );
+        request
+    };
+
+    let (status, error) = send_to(app(), chat_request(body.clone())).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(error["error"]["type"], "invalid_request_error");
+    let (status, _) = send_to(app(), with_key("key-c")).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let models = Request::get("/v1/models")
+        .body(Body::empty())
+        .expect("build request");
+    let (status, _) = send_to(app(), models).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(adapter.last_request.lock().expect("request lock").is_none());
+
+    let (status, _) = send_to(app(), with_key("key-b")).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_chat_completions_rejects_non_function_tools() {
+    let adapter = Arc::new(RecordingAdapter::default());
+
+    let (status, body) = send(
+        runtime(Arc::clone(&adapter)),
+        chat_request(json!({
+            "model": "claude-test",
+            "messages": [{ "role": "user", "content": "hi" }],
+            "tools": [{ "type": "web_search_preview" }]
+        })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["type"], "invalid_request_error");
+    assert!(
+        body["error"]["message"]
+            .as_str()
+            .expect("message")
+            .contains("unsupported tool type 'web_search_preview'")
+    );
+    assert!(adapter.last_request.lock().expect("request lock").is_none());
+}
+
+#[tokio::test]
+async fn test_upstream_auth_failures_are_reported_as_bad_gateway() {
+    for status_code in [401, 403] {
+        let adapter = Arc::new(RecordingAdapter {
+            error_status: Some(status_code),
+            ..RecordingAdapter::default()
+        });
+
+        let (status, body) = send(
+            runtime(adapter),
+            chat_request(json!({
+                "model": "claude-test",
+                "messages": [{ "role": "user", "content": "hi" }]
+            })),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+        assert_eq!(body["error"]["type"], "upstream_error");
+    }
+}
+
+#[tokio::test]
+async fn test_chat_completion_ids_without_request_id_are_unique() {
+    let adapter = Arc::new(RecordingAdapter {
+        without_request_id: true,
+        ..RecordingAdapter::default()
+    });
+    let request = || {
+        chat_request(json!({
+            "model": "claude-test",
+            "messages": [{ "role": "user", "content": "hi" }]
+        }))
+    };
+
+    let (_, first) = send(runtime(Arc::clone(&adapter)), request()).await;
+    let (_, second) = send(runtime(Arc::clone(&adapter)), request()).await;
+
+    let first = first["id"].as_str().expect("id");
+    assert!(first.starts_with("chatcmpl-"));
+    assert_ne!(first, second["id"].as_str().expect("id"));
+}
@@ -0,0 +1,354 @@
+//! Redis-backed rate limiting, response caching, and request dedup, so several runtime instances
+//! share one provider budget, one cache, and one view of which requests are in flight.
+//!
+//! Each type wraps a `redis::aio::ConnectionManager`, which reconnects on its own and is cheap to
+//! clone, and namespaces its keys under a prefix (`provider-runtime` by default). Redis errors
+//! never fail a provider call: the limiter lets the request through, the cache reports a miss, and
+//! dedup grants the claim, so losing Redis costs the sharing but not the requests.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use ::redis::aio::ConnectionManager;
+use ::redis::{RedisResult, Script};
+use async_trait::async_trait;
+
+use crate::core::traits::Encryptor;
+use crate::core::types::{ProviderId, ProviderResponse};
+use crate::runtime::cache::{InFlightDedup, ResponseCache, claim_token};
+use crate::transport::ratelimit::{RateLimit, RateLimitBackend};
+
+const DEFAULT_PREFIX: &str = "provider-runtime";
+
+/// Refills and takes from one provider's request and token buckets atomically. Capacities of 0
+/// disable a bucket. Returns 0 after reserving, otherwise the milliseconds to wait.
+const TOKEN_BUCKET_SCRIPT: &str = r"
+local clock = redis.call('TIME')
+local now = tonumber(clock[1]) * 1000 + math.floor(tonumber(clock[2]) / 1000)
+local request_capacity = tonumber(ARGV[1])
+local request_rate = tonumber(ARGV[2])
+local token_capacity = tonumber(ARGV[3])
+local token_rate = tonumber(ARGV[4])
+local tokens = tonumber(ARGV[5])
+local state = redis.call('HMGET', KEYS[1], 'requests', 'tokens', 'at')
+local elapsed = math.max(0, now - (tonumber(state[3]) or now))
+
+local function refill(available, capacity, rate)
+  if capacity <= 0 then return 0 end
+  return math.min(capacity, (tonumber(available) or capacity) + elapsed * rate)
+end
+
+local function wait(available, capacity, rate, amount)
+  if capacity <= 0 then return 0 end
+  local missing = math.min(amount, capacity) - available
+  if missing <= 0 then return 0 end
+  return math.ceil(missing / rate)
+end
+
+local requests = refill(state[1], request_capacity, request_rate)
+local available_tokens = refill(state[2], token_capacity, token_rate)
+local wait_ms = math.max(
+  wait(requests, request_capacity, request_rate, 1),
+  wait(available_tokens, token_capacity, token_rate, tokens))
+if wait_ms == 0 then
+  if request_capacity > 0 then requests = requests - 1 end
+  if token_capacity > 0 then
+    available_tokens = available_tokens - math.min(tokens, token_capacity)
+  end
+end
+
+redis.call('HSET', KEYS[1], 'requests', tostring(requests),
+  'tokens', tostring(available_tokens), 'at', tostring(now))
+redis.call('PEXPIRE', KEYS[1], ARGV[6])
+return wait_ms
+";
+
+/// Deletes a dedup claim only while it still holds the releasing caller's token, so a caller
+/// whose claim expired cannot release the claim another caller took since.
+const RELEASE_CLAIM_SCRIPT: &str = r"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+  return redis.call('DEL', KEYS[1])
+end
+return 0
+";
+
+/// Per-provider token buckets kept in Redis; the shared counterpart of `RateLimiter`. Attach it
+/// with `HttpTransportBuilder::with_rate_limiter`.
+#[derive(Clone)]
+pub struct RedisRateLimiter {
+    connection: ConnectionManager,
+    prefix: String,
+    limits: BTreeMap<ProviderId, RateLimit>,
+    script: Script,
+}
+
+impl RedisRateLimiter {
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self {
+            connection,
+            prefix: DEFAULT_PREFIX.to_string(),
+            limits: BTreeMap::new(),
+            script: Script::new(TOKEN_BUCKET_SCRIPT),
+        }
+    }
+
+    /// Sets the limits for `provider`, with the same burst rules as `RateLimiter::with_limit`.
+    /// Providers without limits are never delayed.
+    pub fn with_limit(mut self, provider: ProviderId, limit: RateLimit) -> Self {
+        self.limits.insert(provider, limit);
+        self
+    }
+
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Reserves capacity if it is available now; otherwise returns how long to wait.
+    async fn try_acquire(
+        &self,
+        provider: &ProviderId,
+        limit: &RateLimit,
+        estimated_tokens: u64,
+    ) -> RedisResult<Option<Duration>> {
+        let args = BucketArgs::new(limit);
+        let wait_ms: u64 = self
+            .script
+            .key(namespaced(&self.prefix, "ratelimit", provider.name()))
+            .arg(args.request_capacity)
+            .arg(args.request_rate_per_ms)
+            .arg(args.token_capacity)
+            .arg(args.token_rate_per_ms)
+            .arg(estimated_tokens)
+            .arg(args.idle_ttl_ms)
+            .invoke_async(&mut self.connection.clone())
+            .await?;
+        Ok((wait_ms > 0).then(|| Duration::from_millis(wait_ms)))
+    }
+}
+
+impl fmt::Debug for RedisRateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisRateLimiter")
+            .field("prefix", &self.prefix)
+            .field("limits", &self.limits)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl RateLimitBackend for RedisRateLimiter {
+    async fn acquire(&self, provider: &ProviderId, estimated_tokens: u64) {
+        let Some(limit) = self.limits.get(provider) else {
+            return;
+        };
+        while let Ok(Some(wait)) = self.try_acquire(provider, limit, estimated_tokens).await {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Script arguments for one provider's buckets. Rates are per millisecond, matching the
+/// script's clock.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BucketArgs {
+    request_capacity: f64,
+    request_rate_per_ms: f64,
+    token_capacity: f64,
+    token_rate_per_ms: f64,
+    /// How long an untouched bucket lives; long enough for both buckets to refill completely,
+    /// after which a missing key reads as full.
+    idle_ttl_ms: u64,
+}
+
+impl BucketArgs {
+    fn new(limit: &RateLimit) -> Self {
+        let (request_capacity, request_rate_per_ms) = limit
+            .requests_per_second
+            .filter(|rate| *rate > 0.0)
+            .map_or((0.0, 0.0), |rate| (rate.max(1.0), rate / 1000.0));
+        let (token_capacity, token_rate_per_ms) = limit
+            .tokens_per_minute
+            .filter(|tokens| *tokens > 0)
+            .map_or((0.0, 0.0), |tokens| {
+                (tokens as f64, tokens as f64 / 60_000.0)
+            });
+
+        let refill_ms = [
+            (request_capacity, request_rate_per_ms),
+            (token_capacity, token_rate_per_ms),
+        ]
+        .into_iter()
+        .filter(|(_, rate)| *rate > 0.0)
+        .map(|(capacity, rate)| capacity / rate)
+        .fold(0.0, f64::max);
+
+        Self {
+            request_capacity,
+            request_rate_per_ms,
+            token_capacity,
+            token_rate_per_ms,
+            idle_ttl_ms: refill_ms.ceil() as u64 + 1000,
+        }
+    }
+}
+
+/// `ResponseCache` storing responses as JSON, optionally expiring them. Responses hold full
+/// model output, so set `with_encryptor` unless the Redis deployment is trusted with
+/// transcripts in plaintext.
+#[derive(Clone)]
+pub struct RedisResponseCache {
+    connection: ConnectionManager,
+    prefix: String,
+    ttl: Option<Duration>,
+    encryptor: Option<Arc<dyn Encryptor>>,
+}
+
+impl RedisResponseCache {
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self {
+            connection,
+            prefix: DEFAULT_PREFIX.to_string(),
+            ttl: None,
+            encryptor: None,
+        }
+    }
+
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Lets Redis expire entries `ttl` after they are stored.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Encrypts entries before they are stored. Entries that fail to encrypt are not stored, and
+    /// entries that fail to decrypt, including plaintext ones written without an encryptor, read
+    /// as misses.
+    pub fn with_encryptor(mut self, encryptor: Arc<dyn Encryptor>) -> Self {
+        self.encryptor = Some(encryptor);
+        self
+    }
+}
+
+impl fmt::Debug for RedisResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisResponseCache")
+            .field("prefix", &self.prefix)
+            .field("ttl", &self.ttl)
+            .field("encrypted", &self.encryptor.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl ResponseCache for RedisResponseCache {
+    async fn get(&self, key: &str) -> Option<ProviderResponse> {
+        let stored: Option<Vec<u8>> = ::redis::cmd("GET")
+            .arg(namespaced(&self.prefix, "cache", key))
+            .query_async(&mut self.connection.clone())
+            .await
+            .ok()?;
+        let mut stored = stored?;
+        if let Some(encryptor) = &self.encryptor {
+            stored = encryptor.decrypt(&stored).ok()?;
+        }
+        serde_json::from_slice(&stored).ok()
+    }
+
+    async fn put(&self, key: String, response: ProviderResponse) {
+        let Ok(mut encoded) = serde_json::to_vec(&response) else {
+            return;
+        };
+        if let Some(encryptor) = &self.encryptor {
+            let Ok(encrypted) = encryptor.encrypt(&encoded) else {
+                return;
+            };
+            encoded = encrypted;
+        }
+        let mut command = ::redis::cmd("SET");
+        command
+            .arg(namespaced(&self.prefix, "cache", &key))
+            .arg(encoded);
+        if let Some(ttl) = self.ttl {
+            command.arg("PX").arg(ttl_millis(ttl));
+        }
+        let _: RedisResult<()> = command.query_async(&mut self.connection.clone()).await;
+    }
+}
+
+/// `InFlightDedup` holding claims as `SET NX` keys that expire with the claim. Each key stores
+/// its claim token, and release is a compare-and-delete on that token.
+#[derive(Clone)]
+pub struct RedisDedup {
+    connection: ConnectionManager,
+    prefix: String,
+    release_script: Script,
+}
+
+impl RedisDedup {
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self {
+            connection,
+            prefix: DEFAULT_PREFIX.to_string(),
+            release_script: Script::new(RELEASE_CLAIM_SCRIPT),
+        }
+    }
+
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+}
+
+impl fmt::Debug for RedisDedup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisDedup")
+            .field("prefix", &self.prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl InFlightDedup for RedisDedup {
+    async fn claim(&self, key: &str, ttl: Duration) -> Option<String> {
+        let token = claim_token(key);
+        let claimed: RedisResult<Option<String>> = ::redis::cmd("SET")
+            .arg(namespaced(&self.prefix, "inflight", key))
+            .arg(&token)
+            .arg("NX")
+            .arg("PX")
+            .arg(ttl_millis(ttl))
+            .query_async(&mut self.connection.clone())
+            .await;
+        claimed
+            .map_or(true, |reply| reply.is_some())
+            .then_some(token)
+    }
+
+    async fn release(&self, key: &str, token: &str) {
+        let _: RedisResult<i64> = self
+            .release_script
+            .key(namespaced(&self.prefix, "inflight", key))
+            .arg(token)
+            .invoke_async(&mut self.connection.clone())
+            .await;
+    }
+}
+
+fn namespaced(prefix: &str, kind: &str, key: &str) -> String {
+    format!("{prefix}:{kind}:{key}")
+}
+
+/// Redis rejects a `PX` of 0, so sub-millisecond durations round up.
+fn ttl_millis(ttl: Duration) -> u64 {
+    u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX).max(1)
+}
+
+#[cfg(test)]
+mod tests;
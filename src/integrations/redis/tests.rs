@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use super::{BucketArgs, namespaced, ttl_millis};
+use crate::transport::ratelimit::RateLimit;
+
+#[test]
+fn test_bucket_args_match_in_process_burst_rules() {
+    let args = BucketArgs::new(&RateLimit::requests_per_second(0.5).with_tokens_per_minute(6_000));
+
+    assert_eq!(args.request_capacity, 1.0);
+    assert_eq!(args.request_rate_per_ms, 0.0005);
+    assert_eq!(args.token_capacity, 6_000.0);
+    assert_eq!(args.token_rate_per_ms, 0.1);
+    assert_eq!(args.idle_ttl_ms, 61_000);
+}
+
+#[test]
+fn test_bucket_args_disable_unset_limits() {
+    let args = BucketArgs::new(&RateLimit {
+        requests_per_second: Some(0.0),
+        tokens_per_minute: None,
+    });
+
+    assert_eq!(args.request_capacity, 0.0);
+    assert_eq!(args.token_capacity, 0.0);
+    assert_eq!(args.idle_ttl_ms, 1000);
+}
+
+#[test]
+fn test_keys_are_namespaced_and_ttls_never_zero() {
+    assert_eq!(
+        namespaced("svc", "cache", "abc123"),
+        "svc:cache:abc123".to_string()
+    );
+    assert_eq!(ttl_millis(Duration::ZERO), 1);
+    assert_eq!(ttl_millis(Duration::from_secs(2)), 2000);
+}
+
+/// Round trips against the server at `REDIS_URL`; skipped when it is unset.
+#[cfg(feature = "live-tests")]
+mod live {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use redis::aio::ConnectionManager;
+
+    use super::super::{RedisDedup, RedisRateLimiter, RedisResponseCache};
+    use crate::core::error::ErrorSource;
+    use crate::core::traits::Encryptor;
+    use crate::core::types::{
+        AssistantOutput, ContentPart, FinishReason, ProviderId, ProviderResponse, Usage,
+    };
+    use crate::runtime::cache::{InFlightDedup, ResponseCache};
+    use crate::transport::ratelimit::{RateLimit, RateLimitBackend};
+
+    async fn connection() -> Option<ConnectionManager> {
+        let url = std::env::var("REDIS_URL").ok()?;
+        let client = redis::Client::open(url).expect("REDIS_URL should parse");
+        Some(
+            ConnectionManager::new(client)
+                .await
+                .expect("redis should accept connections"),
+        )
+    }
+
+    fn prefix(test: &str) -> String {
+        format!("provider-runtime-test:{test}:{}", std::process::id())
+    }
+
+    #[tokio::test]
+    async fn test_redis_cache_round_trips_responses() {
+        let Some(connection) = connection().await else {
+            return;
+        };
+        let cache = RedisResponseCache::new(connection)
+            .with_prefix(prefix("cache"))
+            .with_ttl(Duration::from_secs(30));
+        let response = ProviderResponse {
+            output: AssistantOutput {
+                content: vec![ContentPart::Text {
+                    text: "cached".to_string(),
+                }],
+                structured_output: None,
+                annotations: Vec::new(),
+            },
+            usage: Usage::default(),
+            cost: None,
+            provider: ProviderId::Openai,
+            model: "gpt-5-mini".to_string(),
+            raw_provider_response: None,
+            request_id: None,
+            rate_limit: None,
+            finish_reason: FinishReason::Stop,
+            warnings: Vec::new(),
+            alternatives: Vec::new(),
+            response_id: None,
+        };
+
+        assert!(cache.get("missing").await.is_none());
+        cache.put("key".to_string(), response.clone()).await;
+        assert_eq!(cache.get("key").await, Some(response.clone()));
+
+        let encrypted = cache.clone().with_encryptor(Arc::new(Reversed));
+        assert!(
+            encrypted.get("key").await.is_none(),
+            "plaintext entries read as misses"
+        );
+        encrypted.put("sealed".to_string(), response.clone()).await;
+        assert_eq!(encrypted.get("sealed").await, Some(response));
+        assert!(cache.get("sealed").await.is_none());
+    }
+
+    /// Stands in for a real cipher: the stored bytes are not the plaintext JSON.
+    struct Reversed;
+
+    impl Encryptor for Reversed {
+        fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, ErrorSource> {
+            Ok(plaintext.iter().rev().copied().collect())
+        }
+
+        fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, ErrorSource> {
+            Ok(ciphertext.iter().rev().copied().collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_redis_dedup_claims_are_exclusive_until_released() {
+        let Some(connection) = connection().await else {
+            return;
+        };
+        let dedup = RedisDedup::new(connection).with_prefix(prefix("dedup"));
+
+        let token = dedup
+            .claim("key", Duration::from_secs(30))
+            .await
+            .expect("first claim");
+        assert!(dedup.claim("key", Duration::from_secs(30)).await.is_none());
+        dedup.release("key", &token).await;
+        let token = dedup
+            .claim("key", Duration::from_secs(30))
+            .await
+            .expect("released key should be claimable");
+        dedup.release("key", &token).await;
+    }
+
+    #[tokio::test]
+    async fn test_redis_dedup_expired_claim_does_not_release_its_successor() {
+        let Some(connection) = connection().await else {
+            return;
+        };
+        let dedup = RedisDedup::new(connection).with_prefix(prefix("dedup-expired"));
+
+        let expired = dedup
+            .claim("key", Duration::from_millis(1))
+            .await
+            .expect("first claim");
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let current = dedup
+            .claim("key", Duration::from_secs(30))
+            .await
+            .expect("expired claim should not block");
+        dedup.release("key", &expired).await;
+        assert!(dedup.claim("key", Duration::from_secs(30)).await.is_none());
+        dedup.release("key", &current).await;
+    }
+
+    #[tokio::test]
+    async fn test_redis_rate_limiter_delays_burst_beyond_capacity() {
+        let Some(connection) = connection().await else {
+            return;
+        };
+        let limiter = RedisRateLimiter::new(connection)
+            .with_prefix(prefix("ratelimit"))
+            .with_limit(ProviderId::Openai, RateLimit::requests_per_second(10.0));
+
+        let started = tokio::time::Instant::now();
+        for _ in 0..11 {
+            limiter.acquire(&ProviderId::Openai, 0).await;
+        }
+
+        assert!(started.elapsed() >= Duration::from_millis(80));
+    }
+}
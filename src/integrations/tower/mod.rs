@@ -0,0 +1,98 @@
+//! `tower::Service` wrapper around `ProviderRuntime::run`, so gateways can layer standard tower
+//! middleware (timeouts, load shedding, concurrency limits, tracing) over provider calls.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use tower_service::Service;
+
+use crate::core::error::RuntimeError;
+use crate::core::types::{ProviderRequest, ProviderResponse};
+use crate::runtime::ProviderRuntime;
+
+/// A cloneable `Service<ProviderRequest>` backed by a shared runtime.
+///
+/// The runtime holds no per-call state, so the service is always ready; back-pressure comes from
+/// the middleware stacked on top of it.
+#[derive(Clone)]
+pub struct RuntimeService {
+    runtime: Arc<ProviderRuntime>,
+}
+
+impl RuntimeService {
+    pub fn new(runtime: Arc<ProviderRuntime>) -> Self {
+        Self { runtime }
+    }
+
+    pub fn runtime(&self) -> &Arc<ProviderRuntime> {
+        &self.runtime
+    }
+}
+
+impl From<ProviderRuntime> for RuntimeService {
+    fn from(runtime: ProviderRuntime) -> Self {
+        Self::new(Arc::new(runtime))
+    }
+}
+
+impl Service<ProviderRequest> for RuntimeService {
+    type Response = ProviderResponse;
+    type Error = RuntimeError;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<ProviderResponse, RuntimeError>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: ProviderRequest) -> Self::Future {
+        let runtime = Arc::clone(&self.runtime);
+        Box::pin(async move { runtime.run(request).await })
+    }
+}
+
+#[cfg(feature = "axum")]
+pub mod sse {
+    //! Server-sent-event responder for axum handlers.
+
+    use std::convert::Infallible;
+
+    use axum::response::sse::{Event, Sse};
+    use futures_util::stream::{self, Stream};
+    use serde_json::json;
+
+    use crate::core::error::RuntimeError;
+    use crate::core::types::ProviderResponse;
+
+    /// Renders a run result as an SSE stream: a `response` event carrying the canonical
+    /// `ProviderResponse` JSON or an `error` event with the error message, followed by a
+    /// terminating `done` event.
+    ///
+    /// Provider calls are not streamed incrementally yet, so the full response arrives as a single
+    /// event once the run completes.
+    pub fn sse_response(
+        result: Result<ProviderResponse, RuntimeError>,
+    ) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+        let first = match result {
+            Ok(response) => match serde_json::to_string(&response) {
+                Ok(data) => Event::default().event("response").data(data),
+                Err(error) => error_event(&format!("failed to serialize response: {error}")),
+            },
+            Err(error) => error_event(&error.to_string()),
+        };
+        let done = Event::default().event("done").data("[DONE]");
+
+        Sse::new(stream::iter([Ok(first), Ok(done)]))
+    }
+
+    fn error_event(message: &str) -> Event {
+        Event::default()
+            .event("error")
+            .data(json!({ "error": { "message": message } }).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests;
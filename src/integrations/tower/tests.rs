@@ -0,0 +1,122 @@
+use std::future::poll_fn;
+
+use async_trait::async_trait;
+use tower_service::Service;
+
+use super::RuntimeService;
+use crate::core::error::{ProviderError, RuntimeError};
+use crate::core::traits::ProviderAdapter;
+use crate::core::types::{
+    AdapterContext, AssistantOutput, ContentPart, DiscoveryOptions, FinishReason, ModelInfo,
+    ModelRef, ProviderCapabilities, ProviderId, ProviderRequest, ProviderResponse, Usage,
+};
+use crate::runtime::ProviderRuntime;
+
+struct EchoAdapter;
+
+#[async_trait]
+impl ProviderAdapter for EchoAdapter {
+    fn id(&self) -> ProviderId {
+        ProviderId::Openai
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            supports_tools: true,
+            supports_structured_output: true,
+            supports_thinking: false,
+            supports_remote_discovery: false,
+            supports_parallel_tool_calls: false,
+        }
+    }
+
+    async fn run(
+        &self,
+        req: &ProviderRequest,
+        _ctx: &AdapterContext,
+    ) -> Result<ProviderResponse, ProviderError> {
+        Ok(ProviderResponse {
+            output: AssistantOutput {
+                content: vec![ContentPart::Text {
+                    text: "ok".to_string(),
+                }],
+                structured_output: None,
+                annotations: Vec::new(),
+            },
+            usage: Usage::default(),
+            cost: None,
+            provider: ProviderId::Openai,
+            model: req.model.model_id.clone(),
+            raw_provider_response: None,
+            finish_reason: FinishReason::Stop,
+            warnings: Vec::new(),
+            alternatives: Vec::new(),
+            response_id: None,
+            request_id: None,
+            rate_limit: None,
+        })
+    }
+
+    async fn discover_models(
+        &self,
+        _opts: &DiscoveryOptions,
+        _ctx: &AdapterContext,
+    ) -> Result<Vec<ModelInfo>, ProviderError> {
+        Ok(Vec::new())
+    }
+}
+
+fn request(provider_hint: ProviderId) -> ProviderRequest {
+    ProviderRequest::new(
+        ModelRef {
+            provider_hint: Some(provider_hint),
+            model_id: "gpt-5-mini".to_string(),
+            requirements: None,
+        },
+        Vec::new(),
+    )
+}
+
+fn service() -> RuntimeService {
+    ProviderRuntime::builder()
+        .with_adapter(std::sync::Arc::new(EchoAdapter))
+        .build()
+        .into()
+}
+
+#[tokio::test]
+async fn test_runtime_service_forwards_to_runtime() {
+    let mut service = service();
+    poll_fn(|cx| service.poll_ready(cx))
+        .await
+        .expect("service should be ready");
+
+    let response = service
+        .call(request(ProviderId::Openai))
+        .await
+        .expect("call should succeed");
+    assert_eq!(response.model, "gpt-5-mini");
+
+    let error = service
+        .call(request(ProviderId::Anthropic))
+        .await
+        .expect_err("unregistered provider should fail");
+    assert!(matches!(error, RuntimeError::RoutingError(_)));
+}
+
+#[cfg(feature = "axum")]
+#[tokio::test]
+async fn test_sse_response_emits_response_then_done() {
+    use axum::response::IntoResponse;
+
+    let result = service().call(request(ProviderId::Openai)).await;
+    let response = super::sse::sse_response(result).into_response();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read sse body");
+    let body = String::from_utf8(body.to_vec()).expect("utf8 body");
+
+    assert!(body.starts_with("event: response\ndata: {"));
+    assert!(body.contains("\"model\":\"gpt-5-mini\""));
+    assert!(body.ends_with("event: done\ndata: [DONE]\n\n"));
+}
@@ -1,10 +1,14 @@
 pub mod catalog;
 pub mod core;
 pub mod handoff;
+pub mod integrations;
+pub mod prelude;
 pub mod pricing;
 pub mod providers;
 pub mod registry;
 pub mod runtime;
+#[cfg(feature = "tracing")]
+mod telemetry;
 pub mod transport;
 
 pub use core::types::*;
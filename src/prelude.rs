@@ -0,0 +1,55 @@
+//! The curated public surface in one import: `use provider_runtime::prelude::*;`.
+//!
+//! Covers what an application needs to build a runtime, send canonical requests, and handle the
+//! results. Provider-specific option types beyond each adapter's options struct stay under
+//! `providers::*` to avoid name clashes between providers.
+
+pub use crate::core::error::{ConfigError, ProviderError, RoutingError, RuntimeError};
+pub use crate::core::traits::{
+    CredentialStore, Encryptor, ProviderAdapter, Redactor, TokenProvider,
+};
+pub use crate::core::types::*;
+pub use crate::pricing::{ImagePriceRule, PriceRule, PriceTier, PricingTable};
+pub use crate::providers::anthropic::{AnthropicAdapter, AnthropicAdapterOptions};
+pub use crate::providers::mock::{MockAdapter, MockMatcher, MockOutcome};
+pub use crate::providers::openai::{OpenAiAdapter, OpenAiAdapterOptions};
+pub use crate::providers::openrouter::{OpenRouterAdapter, OpenRouterAdapterOptions};
+#[cfg(feature = "keychain")]
+pub use crate::registry::KeychainCredentialStore;
+pub use crate::registry::{
+    ChainCredentialStore, DiscoveryFailure, DiscoveryReport, EnvCredentialStore,
+    FileCredentialStore, KeyPool, KeyRotation, MetadataCredentialStore, ProviderHealth,
+    RoutingPolicy, StaticCredentialStore,
+};
+pub use crate::runtime::budget::{BudgetTracker, UsageTotals};
+pub use crate::runtime::cache::{
+    CachePolicy, InFlightDedup, InMemoryDedup, InMemoryResponseCache, ResponseCache,
+};
+pub use crate::runtime::context::RequestContext;
+pub use crate::runtime::eval::{EvalModelSummary, EvalReport, EvalResult, EvalSuite};
+pub use crate::runtime::metrics::{NoopMetrics, RuntimeMetrics};
+pub use crate::runtime::middleware::RuntimeMiddleware;
+pub use crate::runtime::prompt::{MessageTemplate, PromptTemplate};
+pub use crate::runtime::session::Conversation;
+#[cfg(feature = "jsonschema")]
+pub use crate::runtime::structured::StructuredOutputPolicy;
+pub use crate::runtime::structured::{StructuredOptions, StructuredResponse};
+pub use crate::runtime::tokens::{HeuristicTokenCounter, TokenCounter, TokenEstimate};
+#[cfg(feature = "schemars")]
+pub use crate::runtime::tool_loop::TypedTool;
+pub use crate::runtime::tool_loop::{
+    ToolExecutor, ToolLoopOptions, ToolLoopOutcome, ToolLoopStop, ToolSet,
+};
+pub use crate::runtime::trace::RunTrace;
+pub use crate::runtime::transform::{
+    DedupeWarnings, ExtractJsonObject, NormalizeFinishReason, ResponseTransform, StripJsonFences,
+    TrimWhitespace,
+};
+pub use crate::runtime::truncation::TruncationStrategy;
+pub use crate::runtime::{EncodeWarningHook, ProviderRuntime, ProviderRuntimeBuilder};
+pub use crate::transport::Transport;
+pub use crate::transport::http::{
+    HttpTransport, HttpTransportBuilder, JitterMode, RetryPolicy, TlsVersion,
+};
+pub use crate::transport::ratelimit::{RateLimit, RateLimitBackend, RateLimiter};
+pub use crate::transport::redaction::DefaultRedactor;
@@ -1,4 +1,57 @@
-use crate::core::types::{CostBreakdown, PricingSource, ProviderId, RuntimeWarning, Usage};
+//! Cost estimation from configured per-token prices.
+//!
+//! A [`PricingTable`] can be built in code or loaded from a TOML or JSON file with
+//! [`PricingTable::from_path`]. Rates in files are per million tokens, in the table's currency
+//! unless a rule names its own:
+//!
+//! ```toml
+//! currency = "EUR"                 # optional, defaults to USD
+//!
+//! [exchange_rates]                 # EUR per unit of each currency rules are priced in
+//! USD = 0.92
+//!
+//! [[rules]]
+//! provider = "openai"              # openai, anthropic, openrouter, or any other name
+//! pattern = "gpt-5*"               # exact model id, `prefix*`, or `*`
+//! input_per_million = 1.25
+//! output_per_million = 10.0
+//! cached_input_per_million = 0.125 # optional
+//! reasoning_per_million = 10.0     # optional, defaults to the output rate
+//! currency = "USD"                 # optional, defaults to the table currency
+//! per_request = 0.0                # optional flat fee per request
+//! per_web_search = 0.01            # optional fee per hosted web search
+//!
+//! [[rules.tiers]]                  # optional; replaces the rates above once a request's
+//! above_input_tokens = 200000      # input tokens exceed the threshold
+//! input_per_million = 2.5
+//! output_per_million = 15.0
+//!
+//! [[image_rules]]
+//! provider = "openai"
+//! pattern = "gpt-image-1"
+//! per_image = 0.04
+//! ```
+//!
+//! The JSON form has the same shape: `{"rules": [...], "image_rules": [...]}`.
+//!
+//! [`PricingTable::builtin`] loads the bundled `builtin.toml`, which is in the same format.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::core::error::{ConfigError, ProviderError};
+use crate::core::traits::ProviderAdapter;
+use crate::core::types::{
+    AdapterContext, CostBreakdown, DiscoveryOptions, ModelInfo, PricingSource, ProviderId,
+    RuntimeWarning, Usage, WarningCode,
+};
+
+const TOKENS_PER_MILLION: f64 = 1_000_000.0;
+const DEFAULT_CURRENCY: &str = "USD";
+const BUILTIN_PRICING: &str = include_str!("builtin.toml");
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct PriceRule {
@@ -6,52 +59,581 @@ pub struct PriceRule {
     pub model_pattern: String,
     pub input_cost_per_token: f64,
     pub output_cost_per_token: f64,
+    /// Rate for input tokens served from the provider's prompt cache.
+    pub cached_input_cost_per_token: Option<f64>,
+    /// Rate for input tokens written to the provider's prompt cache
+    /// ([`Usage::cache_creation_input_tokens`]), which Anthropic bills at a premium over the
+    /// input rate. Falls back to the input rate, including a long-context tier's.
+    pub cache_write_input_cost_per_token: Option<f64>,
+    /// Rate for reasoning tokens, which providers count within output tokens. Falls back to the
+    /// output rate.
+    pub reasoning_cost_per_token: Option<f64>,
+    /// Currency the rates are quoted in. Defaults to the table currency.
+    pub currency: Option<String>,
+    /// Long-context rates; the tier with the highest threshold below the request's input
+    /// tokens replaces the base token rates.
+    pub tiers: Vec<PriceTier>,
+    /// Flat fee charged once per request.
+    pub per_request_cost: Option<f64>,
+    /// Fee per hosted web search, counted from [`Usage::web_search_requests`].
+    pub per_web_search_cost: Option<f64>,
+}
+
+/// Token rates that apply once a request's input exceeds `above_input_tokens`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceTier {
+    pub above_input_tokens: u64,
+    pub input_cost_per_token: f64,
+    pub output_cost_per_token: f64,
+    pub cached_input_cost_per_token: Option<f64>,
+}
+
+impl PriceTier {
+    fn has_valid_rates(&self) -> bool {
+        is_valid_rate(self.input_cost_per_token)
+            && is_valid_rate(self.output_cost_per_token)
+            && self.cached_input_cost_per_token.is_none_or(is_valid_rate)
+    }
 }
 
 impl PriceRule {
+    /// A rule for embedding models, which bill input tokens only.
+    pub fn embedding(
+        provider: ProviderId,
+        model_pattern: impl Into<String>,
+        cost_per_token: f64,
+    ) -> Self {
+        Self {
+            provider,
+            model_pattern: model_pattern.into(),
+            input_cost_per_token: cost_per_token,
+            output_cost_per_token: 0.0,
+            cached_input_cost_per_token: None,
+            cache_write_input_cost_per_token: None,
+            reasoning_cost_per_token: None,
+            currency: None,
+            tiers: Vec::new(),
+            per_request_cost: None,
+            per_web_search_cost: None,
+        }
+    }
+
     fn has_valid_rates(&self) -> bool {
-        is_valid_rate(self.input_cost_per_token) && is_valid_rate(self.output_cost_per_token)
+        is_valid_rate(self.input_cost_per_token)
+            && is_valid_rate(self.output_cost_per_token)
+            && self.cached_input_cost_per_token.is_none_or(is_valid_rate)
+            && self
+                .cache_write_input_cost_per_token
+                .is_none_or(is_valid_rate)
+            && self.reasoning_cost_per_token.is_none_or(is_valid_rate)
+            && self.per_request_cost.is_none_or(is_valid_rate)
+            && self.per_web_search_cost.is_none_or(is_valid_rate)
+            && self.tiers.iter().all(PriceTier::has_valid_rates)
     }
+
+    /// Input, cached-input, and output rates for a request with `input_tokens` of input.
+    fn token_rates(&self, input_tokens: u64) -> (f64, Option<f64>, f64) {
+        let tier = self
+            .tiers
+            .iter()
+            .filter(|tier| input_tokens > tier.above_input_tokens)
+            .max_by_key(|tier| tier.above_input_tokens);
+        match tier {
+            Some(tier) => (
+                tier.input_cost_per_token,
+                tier.cached_input_cost_per_token,
+                tier.output_cost_per_token,
+            ),
+            None => (
+                self.input_cost_per_token,
+                self.cached_input_cost_per_token,
+                self.output_cost_per_token,
+            ),
+        }
+    }
+}
+
+/// Flat price per generated image for image generation models.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImagePriceRule {
+    pub provider: ProviderId,
+    pub model_pattern: String,
+    pub cost_per_image: f64,
+    /// Currency the price is quoted in. Defaults to the table currency.
+    pub currency: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct PricingTable {
     pub rules: Vec<PriceRule>,
+    pub image_rules: Vec<ImagePriceRule>,
+    /// Currency costs are reported in.
+    pub currency: String,
+    /// Units of `currency` per unit of each other currency that rules are quoted in.
+    pub exchange_rates: BTreeMap<String, f64>,
+}
+
+impl Default for PricingTable {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
 }
 
 impl PricingTable {
     pub fn new(rules: Vec<PriceRule>) -> Self {
-        Self { rules }
+        Self {
+            rules,
+            image_rules: Vec::new(),
+            currency: DEFAULT_CURRENCY.to_string(),
+            exchange_rates: BTreeMap::new(),
+        }
     }
 
-    pub fn find_rule(&self, provider: &ProviderId, model: &str) -> Option<&PriceRule> {
-        let mut best_index: Option<usize> = None;
-        let mut best_score: Option<RuleMatchScore> = None;
+    pub fn with_image_rule(mut self, rule: ImagePriceRule) -> Self {
+        self.image_rules.push(rule);
+        self
+    }
 
-        for (index, rule) in self.rules.iter().enumerate() {
-            if rule.provider != *provider {
-                continue;
-            }
+    /// Reports costs in `currency`; rules quoted in other currencies need an exchange rate.
+    pub fn with_currency(mut self, currency: impl Into<String>) -> Self {
+        self.currency = currency.into();
+        self
+    }
 
-            let Some(score) = match_pattern(&rule.model_pattern, model) else {
-                continue;
+    /// Sets how many units of the table currency one unit of `currency` is worth.
+    pub fn with_exchange_rate(mut self, currency: impl Into<String>, rate: f64) -> Self {
+        self.exchange_rates.insert(currency.into(), rate);
+        self
+    }
+
+    /// Converts `cost` into the table currency. Without an exchange rate the cost is returned
+    /// unchanged with a `pricing.currency_mismatch` warning.
+    pub fn convert_cost(&self, cost: CostBreakdown) -> (CostBreakdown, Option<RuntimeWarning>) {
+        if cost.currency == self.currency {
+            return (cost, None);
+        }
+
+        let Some(rate) = self
+            .exchange_rates
+            .get(&cost.currency)
+            .copied()
+            .filter(|rate| is_valid_rate(*rate))
+        else {
+            let warning = RuntimeWarning {
+                code: WarningCode::PricingCurrencyMismatch,
+                message: format!(
+                    "cost in {} was not converted to {}; no exchange rate configured",
+                    cost.currency, self.currency
+                ),
             };
+            return (cost, Some(warning));
+        };
 
-            let should_replace = match best_score {
-                Some(current) => score > current,
-                None => true,
+        let converted = CostBreakdown {
+            currency: self.currency.clone(),
+            input_cost: cost.input_cost * rate,
+            cached_input_cost: cost.cached_input_cost * rate,
+            output_cost: cost.output_cost * rate,
+            fixed_cost: cost.fixed_cost * rate,
+            total_cost: cost.total_cost * rate,
+            pricing_source: cost.pricing_source,
+        };
+        (converted, None)
+    }
+
+    fn rule_currency(&self, currency: &Option<String>) -> String {
+        currency.clone().unwrap_or_else(|| self.currency.clone())
+    }
+
+    /// Loads a table from a `.toml` or `.json` file in the format described in the
+    /// [module docs](self).
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|error| {
+            invalid_pricing(format!("failed to read {}: {error}", path.display()))
+        })?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => parse_toml(&contents),
+            Some("json") => parse_json(&contents),
+            _ => Err(invalid_pricing(format!(
+                "unsupported pricing file extension for {}; expected .toml or .json",
+                path.display()
+            ))),
+        }
+    }
+
+    /// List prices for common OpenAI, Anthropic, and OpenRouter models, bundled with the crate.
+    pub fn builtin() -> Self {
+        BUILTIN_PRICING
+            .parse()
+            .expect("builtin pricing table should be valid")
+    }
+
+    /// Replaces `adapter`'s rules with the prices its model listing reports, such as OpenRouter's
+    /// `/models` price list. Models without published prices keep their existing rules. Returns
+    /// the number of models priced.
+    pub async fn refresh_from_remote(
+        &mut self,
+        adapter: &dyn ProviderAdapter,
+        ctx: &AdapterContext,
+    ) -> Result<usize, ProviderError> {
+        let provider = adapter.id();
+        let models = adapter
+            .discover_models(
+                &DiscoveryOptions {
+                    remote: true,
+                    include_provider: vec![provider.clone()],
+                    refresh_cache: true,
+                },
+                ctx,
+            )
+            .await?;
+        Ok(self.apply_model_pricing(&provider, &models))
+    }
+
+    fn apply_model_pricing(&mut self, provider: &ProviderId, models: &[ModelInfo]) -> usize {
+        let mut priced = 0;
+        for model in models {
+            let Some(pricing) = &model.pricing else {
+                continue;
             };
+            if model.provider != *provider {
+                continue;
+            }
 
-            if should_replace {
-                best_index = Some(index);
-                best_score = Some(score);
+            let rule = PriceRule {
+                provider: model.provider.clone(),
+                model_pattern: model.model_id.clone(),
+                input_cost_per_token: pricing.input_cost_per_token,
+                output_cost_per_token: pricing.output_cost_per_token,
+                cached_input_cost_per_token: pricing.cached_input_cost_per_token,
+                cache_write_input_cost_per_token: pricing.cache_write_input_cost_per_token,
+                reasoning_cost_per_token: pricing.reasoning_cost_per_token,
+                currency: Some(DEFAULT_CURRENCY.to_string()),
+                tiers: Vec::new(),
+                per_request_cost: None,
+                per_web_search_cost: None,
+            };
+            match self.rules.iter_mut().find(|existing| {
+                existing.provider == rule.provider && existing.model_pattern == rule.model_pattern
+            }) {
+                Some(existing) => *existing = rule,
+                None => self.rules.push(rule),
             }
+            priced += 1;
         }
+        priced
+    }
 
-        best_index.map(|index| &self.rules[index])
+    pub fn find_rule(&self, provider: &ProviderId, model: &str) -> Option<&PriceRule> {
+        best_match(&self.rules, provider, model, |rule| {
+            (&rule.provider, &rule.model_pattern)
+        })
+    }
+
+    pub fn find_image_rule(&self, provider: &ProviderId, model: &str) -> Option<&ImagePriceRule> {
+        best_match(&self.image_rules, provider, model, |rule| {
+            (&rule.provider, &rule.model_pattern)
+        })
+    }
+}
+
+/// Parses a pricing table from TOML, or from JSON when the input starts with `{`.
+impl FromStr for PricingTable {
+    type Err = ConfigError;
+
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        if contents.trim_start().starts_with('{') {
+            parse_json(contents)
+        } else {
+            parse_toml(contents)
+        }
     }
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PricingFile {
+    #[serde(default)]
+    currency: Option<String>,
+    #[serde(default)]
+    exchange_rates: BTreeMap<String, f64>,
+    #[serde(default)]
+    rules: Vec<PricingFileRule>,
+    #[serde(default)]
+    image_rules: Vec<PricingFileImageRule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PricingFileRule {
+    provider: String,
+    pattern: String,
+    input_per_million: f64,
+    output_per_million: f64,
+    #[serde(default)]
+    cached_input_per_million: Option<f64>,
+    #[serde(default)]
+    cache_write_input_per_million: Option<f64>,
+    #[serde(default)]
+    reasoning_per_million: Option<f64>,
+    #[serde(default)]
+    currency: Option<String>,
+    #[serde(default)]
+    per_request: Option<f64>,
+    #[serde(default)]
+    per_web_search: Option<f64>,
+    #[serde(default)]
+    tiers: Vec<PricingFileTier>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PricingFileTier {
+    above_input_tokens: u64,
+    input_per_million: f64,
+    output_per_million: f64,
+    #[serde(default)]
+    cached_input_per_million: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PricingFileImageRule {
+    provider: String,
+    pattern: String,
+    per_image: f64,
+    #[serde(default)]
+    currency: Option<String>,
+}
+
+fn parse_toml(contents: &str) -> Result<PricingTable, ConfigError> {
+    let file: PricingFile = toml::from_str(contents)
+        .map_err(|error| invalid_pricing(format!("invalid TOML: {}", error.message())))?;
+    file.into_table()
+}
+
+fn parse_json(contents: &str) -> Result<PricingTable, ConfigError> {
+    let file: PricingFile = serde_json::from_str(contents)
+        .map_err(|error| invalid_pricing(format!("invalid JSON: {error}")))?;
+    file.into_table()
+}
+
+impl PricingFile {
+    fn into_table(self) -> Result<PricingTable, ConfigError> {
+        let currency = match self.currency {
+            Some(currency) => validate_currency("currency", currency)?,
+            None => DEFAULT_CURRENCY.to_string(),
+        };
+        let exchange_rates = self
+            .exchange_rates
+            .into_iter()
+            .map(|(from, rate)| {
+                let field = format!("exchange_rates.{from}");
+                if !is_valid_rate(rate) || rate == 0.0 {
+                    return Err(invalid_pricing(format!(
+                        "{field} must be a finite, positive number"
+                    )));
+                }
+                Ok((validate_currency(&field, from)?, rate))
+            })
+            .collect::<Result<BTreeMap<_, _>, ConfigError>>()?;
+
+        let rules = self
+            .rules
+            .into_iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                let field = |name: &str| format!("rules[{index}].{name}");
+                let (provider, model_pattern) =
+                    validate_target(&field, rule.provider, rule.pattern)?;
+                Ok(PriceRule {
+                    provider,
+                    model_pattern,
+                    input_cost_per_token: per_token(
+                        &field("input_per_million"),
+                        rule.input_per_million,
+                    )?,
+                    output_cost_per_token: per_token(
+                        &field("output_per_million"),
+                        rule.output_per_million,
+                    )?,
+                    cached_input_cost_per_token: rule
+                        .cached_input_per_million
+                        .map(|rate| per_token(&field("cached_input_per_million"), rate))
+                        .transpose()?,
+                    cache_write_input_cost_per_token: rule
+                        .cache_write_input_per_million
+                        .map(|rate| per_token(&field("cache_write_input_per_million"), rate))
+                        .transpose()?,
+                    reasoning_cost_per_token: rule
+                        .reasoning_per_million
+                        .map(|rate| per_token(&field("reasoning_per_million"), rate))
+                        .transpose()?,
+                    currency: rule
+                        .currency
+                        .map(|currency| validate_currency(&field("currency"), currency))
+                        .transpose()?,
+                    tiers: rule
+                        .tiers
+                        .into_iter()
+                        .enumerate()
+                        .map(|(tier_index, tier)| {
+                            let field = |name: &str| field(&format!("tiers[{tier_index}].{name}"));
+                            Ok(PriceTier {
+                                above_input_tokens: tier.above_input_tokens,
+                                input_cost_per_token: per_token(
+                                    &field("input_per_million"),
+                                    tier.input_per_million,
+                                )?,
+                                output_cost_per_token: per_token(
+                                    &field("output_per_million"),
+                                    tier.output_per_million,
+                                )?,
+                                cached_input_cost_per_token: tier
+                                    .cached_input_per_million
+                                    .map(|rate| per_token(&field("cached_input_per_million"), rate))
+                                    .transpose()?,
+                            })
+                        })
+                        .collect::<Result<Vec<_>, ConfigError>>()?,
+                    per_request_cost: rule
+                        .per_request
+                        .map(|fee| flat_fee(&field("per_request"), fee))
+                        .transpose()?,
+                    per_web_search_cost: rule
+                        .per_web_search
+                        .map(|fee| flat_fee(&field("per_web_search"), fee))
+                        .transpose()?,
+                })
+            })
+            .collect::<Result<Vec<_>, ConfigError>>()?;
+
+        let image_rules = self
+            .image_rules
+            .into_iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                let field = |name: &str| format!("image_rules[{index}].{name}");
+                let (provider, model_pattern) =
+                    validate_target(&field, rule.provider, rule.pattern)?;
+                if !is_valid_rate(rule.per_image) {
+                    return Err(invalid_rate(&field("per_image")));
+                }
+                Ok(ImagePriceRule {
+                    provider,
+                    model_pattern,
+                    cost_per_image: rule.per_image,
+                    currency: rule
+                        .currency
+                        .map(|currency| validate_currency(&field("currency"), currency))
+                        .transpose()?,
+                })
+            })
+            .collect::<Result<Vec<_>, ConfigError>>()?;
+
+        Ok(PricingTable {
+            rules,
+            image_rules,
+            currency,
+            exchange_rates,
+        })
+    }
+}
+
+fn validate_target(
+    field: &dyn Fn(&str) -> String,
+    provider: String,
+    pattern: String,
+) -> Result<(ProviderId, String), ConfigError> {
+    if provider.trim().is_empty() {
+        return Err(invalid_pricing(format!(
+            "{} must not be empty",
+            field("provider")
+        )));
+    }
+    let wildcard_misplaced = pattern
+        .find('*')
+        .is_some_and(|position| position != pattern.len() - 1);
+    if pattern.is_empty() || wildcard_misplaced {
+        return Err(invalid_pricing(format!(
+            "{} must be an exact model id, `prefix*`, or `*`",
+            field("pattern")
+        )));
+    }
+    Ok((ProviderId::from_name(provider), pattern))
+}
+
+/// Currencies are ISO 4217 codes such as `USD` or `EUR`.
+fn validate_currency(field: &str, currency: String) -> Result<String, ConfigError> {
+    if currency.len() != 3 || !currency.bytes().all(|byte| byte.is_ascii_uppercase()) {
+        return Err(invalid_pricing(format!(
+            "{field} must be a three-letter uppercase currency code, got {currency:?}"
+        )));
+    }
+    Ok(currency)
+}
+
+fn per_token(field: &str, per_million: f64) -> Result<f64, ConfigError> {
+    if !is_valid_rate(per_million) {
+        return Err(invalid_rate(field));
+    }
+    Ok(per_million / TOKENS_PER_MILLION)
+}
+
+fn flat_fee(field: &str, fee: f64) -> Result<f64, ConfigError> {
+    if !is_valid_rate(fee) {
+        return Err(invalid_rate(field));
+    }
+    Ok(fee)
+}
+
+fn invalid_rate(field: &str) -> ConfigError {
+    invalid_pricing(format!("{field} must be a finite, non-negative number"))
+}
+
+fn invalid_pricing(reason: impl Into<String>) -> ConfigError {
+    ConfigError::InvalidPricingConfig {
+        reason: reason.into(),
+    }
+}
+
+/// The rule for `provider` whose pattern matches `model` most specifically; exact ids win over
+/// longer prefixes, and earlier rules win ties.
+fn best_match<'a, R>(
+    rules: &'a [R],
+    provider: &ProviderId,
+    model: &str,
+    key: impl Fn(&R) -> (&ProviderId, &String),
+) -> Option<&'a R> {
+    let mut best_index: Option<usize> = None;
+    let mut best_score: Option<RuleMatchScore> = None;
+
+    for (index, rule) in rules.iter().enumerate() {
+        let (rule_provider, model_pattern) = key(rule);
+        if rule_provider != provider {
+            continue;
+        }
+
+        let Some(score) = match_pattern(model_pattern, model) else {
+            continue;
+        };
+
+        let should_replace = match best_score {
+            Some(current) => score > current,
+            None => true,
+        };
+
+        if should_replace {
+            best_index = Some(index);
+            best_score = Some(score);
+        }
+    }
+
+    best_index.map(|index| &rules[index])
+}
+
 pub fn estimate_cost(
     provider: &ProviderId,
     model: &str,
@@ -62,7 +644,7 @@ pub fn estimate_cost(
 
     let Some(rule) = table.find_rule(provider, model) else {
         warnings.push(RuntimeWarning {
-            code: "pricing.missing_rule".to_string(),
+            code: WarningCode::PricingMissingRule,
             message: format!("no pricing rule configured for provider={provider:?}, model={model}"),
         });
         return (None, warnings);
@@ -70,7 +652,7 @@ pub fn estimate_cost(
 
     if !rule.has_valid_rates() {
         warnings.push(RuntimeWarning {
-            code: "pricing.invalid_rule".to_string(),
+            code: WarningCode::PricingInvalidRule,
             message: format!(
                 "invalid pricing rule for provider={provider:?}, model_pattern={}",
                 rule.model_pattern
@@ -82,7 +664,7 @@ pub fn estimate_cost(
     let has_any_usage = usage.input_tokens.is_some() || usage.output_tokens.is_some();
     if !has_any_usage {
         warnings.push(RuntimeWarning {
-            code: "pricing.missing_usage".to_string(),
+            code: WarningCode::PricingMissingUsage,
             message: format!("usage tokens missing for provider={provider:?}, model={model}"),
         });
         return (None, warnings);
@@ -90,37 +672,130 @@ pub fn estimate_cost(
 
     if usage.input_tokens.is_none() || usage.output_tokens.is_none() {
         warnings.push(RuntimeWarning {
-            code: "pricing.partial_usage".to_string(),
+            code: WarningCode::PricingPartialUsage,
             message: format!(
                 "partial usage for provider={provider:?}, model={model}; missing input or output tokens"
             ),
         });
     }
 
-    let input_cost = usage.input_tokens.unwrap_or(0) as f64 * rule.input_cost_per_token;
-    let output_cost = usage.output_tokens.unwrap_or(0) as f64 * rule.output_cost_per_token;
+    // Providers report cache reads within `input_tokens`, so only the remainder is billed at
+    // the full input rate. Cache writes are reported separately and billed at their own rate.
+    let input_tokens = usage.input_tokens.unwrap_or(0);
+    let (input_rate, cached_input_rate, output_rate) = rule.token_rates(input_tokens);
+    let cached_input_tokens = usage.cached_input_tokens.unwrap_or(0).min(input_tokens);
+    let billed_input_tokens = input_tokens - cached_input_tokens;
+    let cache_write_tokens = usage.cache_creation_input_tokens.unwrap_or(0);
+    let cached_input_cost = cached_input_tokens as f64 * cached_input_rate.unwrap_or(input_rate);
+    let cache_write_cost =
+        cache_write_tokens as f64 * rule.cache_write_input_cost_per_token.unwrap_or(input_rate);
+    let input_cost = billed_input_tokens as f64 * input_rate + cache_write_cost + cached_input_cost;
+    let output_tokens = usage.output_tokens.unwrap_or(0);
+    let output_cost = match (rule.reasoning_cost_per_token, usage.reasoning_tokens) {
+        (Some(reasoning_rate), Some(reasoning_tokens)) => {
+            let reasoning_tokens = reasoning_tokens.min(output_tokens);
+            (output_tokens - reasoning_tokens) as f64 * output_rate
+                + reasoning_tokens as f64 * reasoning_rate
+        }
+        _ => output_tokens as f64 * output_rate,
+    };
+    let fixed_cost = rule.per_request_cost.unwrap_or(0.0)
+        + usage.web_search_requests.unwrap_or(0) as f64 * rule.per_web_search_cost.unwrap_or(0.0);
+
+    let total_cost = input_cost + output_cost + fixed_cost;
+
+    let (cost, warning) = table.convert_cost(CostBreakdown {
+        currency: table.rule_currency(&rule.currency),
+        input_cost,
+        cached_input_cost,
+        output_cost,
+        fixed_cost,
+        total_cost,
+        pricing_source: PricingSource::Configured,
+    });
+    warnings.extend(warning);
+    (Some(cost), warnings)
+}
+
+/// Completes a provider-reported cost that only carries a total, splitting it between input
+/// and output in the proportions of the configured `estimate`. The result is
+/// [`PricingSource::Mixed`]; a reported cost that already has a split is returned unchanged.
+pub fn merge_reported_cost(reported: CostBreakdown, estimate: &CostBreakdown) -> CostBreakdown {
+    let has_split =
+        reported.input_cost > 0.0 || reported.output_cost > 0.0 || reported.fixed_cost > 0.0;
+    if has_split
+        || reported.currency != estimate.currency
+        || estimate.total_cost <= 0.0
+        || reported.total_cost <= 0.0
+    {
+        return reported;
+    }
+
+    let scale = reported.total_cost / estimate.total_cost;
+    CostBreakdown {
+        currency: reported.currency,
+        input_cost: estimate.input_cost * scale,
+        cached_input_cost: estimate.cached_input_cost * scale,
+        output_cost: estimate.output_cost * scale,
+        fixed_cost: estimate.fixed_cost * scale,
+        total_cost: reported.total_cost,
+        pricing_source: PricingSource::Mixed,
+    }
+}
+
+/// Prices `image_count` generated images with the table's image rules.
+pub fn estimate_image_cost(
+    provider: &ProviderId,
+    model: &str,
+    image_count: usize,
+    table: &PricingTable,
+) -> (Option<CostBreakdown>, Vec<RuntimeWarning>) {
+    let Some(rule) = table.find_image_rule(provider, model) else {
+        return (
+            None,
+            vec![RuntimeWarning {
+                code: WarningCode::PricingMissingRule,
+                message: format!(
+                    "no image pricing rule configured for provider={provider:?}, model={model}"
+                ),
+            }],
+        );
+    };
 
-    let total_cost = input_cost + output_cost;
+    if !is_valid_rate(rule.cost_per_image) {
+        return (
+            None,
+            vec![RuntimeWarning {
+                code: WarningCode::PricingInvalidRule,
+                message: format!(
+                    "invalid image pricing rule for provider={provider:?}, model_pattern={}",
+                    rule.model_pattern
+                ),
+            }],
+        );
+    }
 
-    (
-        Some(CostBreakdown {
-            currency: "USD".to_string(),
-            input_cost,
-            output_cost,
-            total_cost,
-            pricing_source: PricingSource::Configured,
-        }),
-        warnings,
-    )
+    let output_cost = image_count as f64 * rule.cost_per_image;
+    let (cost, warning) = table.convert_cost(CostBreakdown {
+        currency: table.rule_currency(&rule.currency),
+        input_cost: 0.0,
+        cached_input_cost: 0.0,
+        output_cost,
+        fixed_cost: 0.0,
+        total_cost: output_cost,
+        pricing_source: PricingSource::Configured,
+    });
+    (Some(cost), warning.into_iter().collect())
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-struct RuleMatchScore {
+pub(crate) struct RuleMatchScore {
     exact: bool,
     prefix_len: usize,
 }
 
-fn match_pattern(pattern: &str, model: &str) -> Option<RuleMatchScore> {
+/// Matches `model` against an exact id, `*`, or a `prefix*` pattern.
+pub(crate) fn match_pattern(pattern: &str, model: &str) -> Option<RuleMatchScore> {
     if pattern == model {
         return Some(RuleMatchScore {
             exact: true,
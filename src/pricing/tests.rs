@@ -1,4 +1,6 @@
 use super::*;
+use crate::core::types::ModelPricing;
+use crate::providers::mock::MockAdapter;
 
 fn single_rule_table(rule: PriceRule) -> PricingTable {
     PricingTable::new(vec![rule])
@@ -11,12 +13,22 @@ fn test_estimate_cost_known_model() {
         model_pattern: "gpt-5-mini".to_string(),
         input_cost_per_token: 0.01,
         output_cost_per_token: 0.02,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: None,
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
     });
     let usage = Usage {
         input_tokens: Some(10),
         output_tokens: Some(20),
         cached_input_tokens: Some(7),
         total_tokens: Some(35),
+        reasoning_tokens: None,
+        cache_creation_input_tokens: None,
+        web_search_requests: None,
     };
 
     let (cost, warnings) = estimate_cost(&ProviderId::Openai, "gpt-5-mini", &usage, &table);
@@ -38,6 +50,9 @@ fn test_missing_price_returns_warning_not_error() {
         output_tokens: Some(2),
         cached_input_tokens: None,
         total_tokens: None,
+        reasoning_tokens: None,
+        cache_creation_input_tokens: None,
+        web_search_requests: None,
     };
 
     let (cost, warnings) =
@@ -55,12 +70,22 @@ fn test_partial_usage_handles_optional_fields() {
         model_pattern: "gpt-5-mini".to_string(),
         input_cost_per_token: 0.01,
         output_cost_per_token: 0.02,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: None,
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
     });
     let usage = Usage {
         input_tokens: Some(10),
         output_tokens: None,
         cached_input_tokens: None,
         total_tokens: None,
+        reasoning_tokens: None,
+        cache_creation_input_tokens: None,
+        web_search_requests: None,
     };
 
     let (cost, warnings) = estimate_cost(&ProviderId::Openai, "gpt-5-mini", &usage, &table);
@@ -81,12 +106,26 @@ fn test_rule_resolution_prefers_exact_over_wildcard() {
             model_pattern: "gpt-*".to_string(),
             input_cost_per_token: 1.0,
             output_cost_per_token: 1.0,
+            cached_input_cost_per_token: None,
+            cache_write_input_cost_per_token: None,
+            reasoning_cost_per_token: None,
+            currency: None,
+            tiers: Vec::new(),
+            per_request_cost: None,
+            per_web_search_cost: None,
         },
         PriceRule {
             provider: ProviderId::Openai,
             model_pattern: "gpt-5-mini".to_string(),
             input_cost_per_token: 2.0,
             output_cost_per_token: 2.0,
+            cached_input_cost_per_token: None,
+            cache_write_input_cost_per_token: None,
+            reasoning_cost_per_token: None,
+            currency: None,
+            tiers: Vec::new(),
+            per_request_cost: None,
+            per_web_search_cost: None,
         },
     ]);
 
@@ -106,18 +145,39 @@ fn test_rule_resolution_uses_longest_wildcard_prefix() {
             model_pattern: "*".to_string(),
             input_cost_per_token: 1.0,
             output_cost_per_token: 1.0,
+            cached_input_cost_per_token: None,
+            cache_write_input_cost_per_token: None,
+            reasoning_cost_per_token: None,
+            currency: None,
+            tiers: Vec::new(),
+            per_request_cost: None,
+            per_web_search_cost: None,
         },
         PriceRule {
             provider: ProviderId::Openai,
             model_pattern: "gpt-*".to_string(),
             input_cost_per_token: 2.0,
             output_cost_per_token: 2.0,
+            cached_input_cost_per_token: None,
+            cache_write_input_cost_per_token: None,
+            reasoning_cost_per_token: None,
+            currency: None,
+            tiers: Vec::new(),
+            per_request_cost: None,
+            per_web_search_cost: None,
         },
         PriceRule {
             provider: ProviderId::Openai,
             model_pattern: "gpt-5-*".to_string(),
             input_cost_per_token: 3.0,
             output_cost_per_token: 3.0,
+            cached_input_cost_per_token: None,
+            cache_write_input_cost_per_token: None,
+            reasoning_cost_per_token: None,
+            currency: None,
+            tiers: Vec::new(),
+            per_request_cost: None,
+            per_web_search_cost: None,
         },
     ]);
 
@@ -136,12 +196,22 @@ fn test_input_output_usage_without_optional_fields_estimates_cost() {
         model_pattern: "claude-*".to_string(),
         input_cost_per_token: 0.1,
         output_cost_per_token: 0.2,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: None,
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
     });
     let usage = Usage {
         input_tokens: Some(1),
         output_tokens: Some(2),
         cached_input_tokens: None,
         total_tokens: None,
+        reasoning_tokens: None,
+        cache_creation_input_tokens: None,
+        web_search_requests: None,
     };
 
     let (cost, warnings) =
@@ -159,12 +229,22 @@ fn test_invalid_rate_returns_warning_and_none_cost() {
         model_pattern: "openrouter/*".to_string(),
         input_cost_per_token: 0.1,
         output_cost_per_token: -0.2,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: None,
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
     });
     let usage = Usage {
         input_tokens: Some(2),
         output_tokens: Some(3),
         cached_input_tokens: None,
         total_tokens: None,
+        reasoning_tokens: None,
+        cache_creation_input_tokens: None,
+        web_search_requests: None,
     };
 
     let (cost, warnings) =
@@ -182,6 +262,13 @@ fn test_no_usage_tokens_returns_none_with_warning() {
         model_pattern: "gpt-*".to_string(),
         input_cost_per_token: 0.1,
         output_cost_per_token: 0.2,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: None,
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
     });
     let usage = Usage::default();
 
@@ -191,3 +278,676 @@ fn test_no_usage_tokens_returns_none_with_warning() {
     assert_eq!(warnings.len(), 1);
     assert_eq!(warnings[0].code, "pricing.missing_usage");
 }
+
+#[test]
+fn test_cache_creation_tokens_fall_back_to_input_rate() {
+    let table = single_rule_table(PriceRule {
+        provider: ProviderId::Anthropic,
+        model_pattern: "claude-*".to_string(),
+        input_cost_per_token: 0.5,
+        output_cost_per_token: 1.0,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: None,
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
+    });
+    let usage = Usage {
+        input_tokens: Some(4),
+        output_tokens: Some(2),
+        cached_input_tokens: None,
+        total_tokens: Some(10),
+        reasoning_tokens: None,
+        cache_creation_input_tokens: Some(4),
+        web_search_requests: None,
+    };
+
+    let (cost, warnings) =
+        estimate_cost(&ProviderId::Anthropic, "claude-sonnet-4-5", &usage, &table);
+
+    assert!(warnings.is_empty());
+    let cost = cost.expect("cost should be estimated");
+    assert_eq!(cost.input_cost, 4.0);
+    assert_eq!(cost.total_cost, 6.0);
+}
+
+#[test]
+fn test_cache_creation_tokens_are_billed_at_cache_write_rate() {
+    let table: PricingTable = r#"
+        [[rules]]
+        provider = "anthropic"
+        pattern = "claude-*"
+        input_per_million = 3.0
+        output_per_million = 15.0
+        cached_input_per_million = 0.3
+        cache_write_input_per_million = 3.75
+    "#
+    .parse()
+    .expect("pricing TOML should parse");
+    let usage = Usage {
+        input_tokens: Some(1_000_000),
+        output_tokens: Some(0),
+        cached_input_tokens: Some(400_000),
+        total_tokens: None,
+        reasoning_tokens: None,
+        cache_creation_input_tokens: Some(2_000_000),
+        web_search_requests: None,
+    };
+
+    let (cost, warnings) =
+        estimate_cost(&ProviderId::Anthropic, "claude-sonnet-4-5", &usage, &table);
+
+    assert!(warnings.is_empty());
+    let cost = cost.expect("cost should be estimated");
+    assert!((cost.cached_input_cost - 0.12).abs() < 1e-9);
+    // 600k uncached at 3.0, 2M writes at 3.75, 400k reads at 0.3.
+    assert!((cost.input_cost - (1.8 + 7.5 + 0.12)).abs() < 1e-9);
+
+    let (builtin, _) = estimate_cost(
+        &ProviderId::Anthropic,
+        "claude-sonnet-4-5",
+        &usage,
+        &PricingTable::builtin(),
+    );
+    let builtin = builtin.expect("builtin table prices claude-sonnet-4-5");
+    assert!((builtin.input_cost - cost.input_cost).abs() < 1e-9);
+}
+
+#[test]
+fn test_pricing_table_from_toml() {
+    let table: PricingTable = r#"
+        [[rules]]
+        provider = "openai"
+        pattern = "gpt-5*"
+        input_per_million = 1.25
+        output_per_million = 10.0
+        cached_input_per_million = 0.125
+
+        [[rules]]
+        provider = "together"
+        pattern = "*"
+        input_per_million = 0.5
+        output_per_million = 0.5
+
+        [[image_rules]]
+        provider = "openai"
+        pattern = "gpt-image-1"
+        per_image = 0.04
+    "#
+    .parse()
+    .expect("pricing TOML should parse");
+
+    let rule = table
+        .find_rule(&ProviderId::Openai, "gpt-5-mini")
+        .expect("prefix rule should match");
+    assert_eq!(rule.input_cost_per_token, 1.25 / 1_000_000.0);
+    assert_eq!(rule.output_cost_per_token, 10.0 / 1_000_000.0);
+    assert_eq!(rule.cached_input_cost_per_token, Some(0.125 / 1_000_000.0));
+    assert_eq!(rule.reasoning_cost_per_token, None);
+    assert!(
+        table
+            .find_rule(&ProviderId::Other("together".to_string()), "llama")
+            .is_some()
+    );
+    assert_eq!(
+        table
+            .find_image_rule(&ProviderId::Openai, "gpt-image-1")
+            .map(|rule| rule.cost_per_image),
+        Some(0.04)
+    );
+}
+
+#[test]
+fn test_pricing_table_from_json_matches_toml() {
+    let from_json: PricingTable = r#"{
+        "rules": [{
+            "provider": "anthropic",
+            "pattern": "claude-sonnet-4-5",
+            "input_per_million": 3.0,
+            "output_per_million": 15.0
+        }]
+    }"#
+    .parse()
+    .expect("pricing JSON should parse");
+    let from_toml: PricingTable = r#"
+        [[rules]]
+        provider = "anthropic"
+        pattern = "claude-sonnet-4-5"
+        input_per_million = 3.0
+        output_per_million = 15.0
+    "#
+    .parse()
+    .expect("pricing TOML should parse");
+
+    assert_eq!(from_json, from_toml);
+    assert_eq!(from_json.rules[0].provider, ProviderId::Anthropic);
+}
+
+#[test]
+fn test_pricing_table_rejects_invalid_files() {
+    let cases = [
+        (
+            r#"
+            [[rules]]
+            provider = "openai"
+            pattern = "gpt-5*"
+            input_per_million = -1.0
+            output_per_million = 10.0
+            "#,
+            "rules[0].input_per_million",
+        ),
+        (
+            r#"
+            [[rules]]
+            provider = "openai"
+            pattern = "gpt-*-mini"
+            input_per_million = 1.0
+            output_per_million = 1.0
+            "#,
+            "rules[0].pattern",
+        ),
+        (
+            r#"{"image_rules": [{"provider": "", "pattern": "*", "per_image": 0.1}]}"#,
+            "image_rules[0].provider",
+        ),
+        (
+            r#"
+            [[rules]]
+            provider = "openai"
+            pattern = "gpt-5"
+            input = 1.0
+            "#,
+            "invalid TOML",
+        ),
+    ];
+
+    for (contents, expected) in cases {
+        let err = contents
+            .parse::<PricingTable>()
+            .expect_err("invalid pricing config should fail");
+        let ConfigError::InvalidPricingConfig { reason } = err else {
+            panic!("expected InvalidPricingConfig, got {err:?}");
+        };
+        assert!(reason.contains(expected), "{reason}");
+    }
+}
+
+#[test]
+fn test_pricing_table_from_path_requires_known_extension() {
+    let dir = std::env::temp_dir().join(format!("pricing-table-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("temp dir should be created");
+    let json_path = dir.join("prices.json");
+    std::fs::write(
+        &json_path,
+        r#"{"rules": [{"provider": "openai", "pattern": "*", "input_per_million": 1.0, "output_per_million": 2.0}]}"#,
+    )
+    .expect("pricing file should be written");
+    let yaml_path = dir.join("prices.yaml");
+    std::fs::write(&yaml_path, "rules: []").expect("pricing file should be written");
+
+    let table = PricingTable::from_path(&json_path).expect("JSON file should load");
+    assert_eq!(table.rules.len(), 1);
+    let err = PricingTable::from_path(&yaml_path).expect_err("yaml should be rejected");
+    assert!(matches!(err, ConfigError::InvalidPricingConfig { .. }));
+    let missing = PricingTable::from_path(dir.join("missing.toml"))
+        .expect_err("missing file should be rejected");
+    assert!(matches!(missing, ConfigError::InvalidPricingConfig { .. }));
+
+    std::fs::remove_dir_all(&dir).expect("temp dir should be removed");
+}
+
+#[test]
+fn test_estimate_cost_bills_reasoning_tokens_at_reasoning_rate() {
+    let table = single_rule_table(PriceRule {
+        provider: ProviderId::Openai,
+        model_pattern: "o4-mini".to_string(),
+        input_cost_per_token: 0.01,
+        output_cost_per_token: 0.02,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: Some(0.05),
+        currency: None,
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
+    });
+    let usage = Usage {
+        input_tokens: Some(10),
+        output_tokens: Some(20),
+        cached_input_tokens: None,
+        total_tokens: Some(30),
+        reasoning_tokens: Some(8),
+        cache_creation_input_tokens: None,
+        web_search_requests: None,
+    };
+
+    let (cost, _) = estimate_cost(&ProviderId::Openai, "o4-mini", &usage, &table);
+
+    let cost = cost.expect("cost should be estimated");
+    assert!((cost.output_cost - (12.0 * 0.02 + 8.0 * 0.05)).abs() < 1e-12);
+}
+
+#[test]
+fn test_estimate_cost_discounts_cached_input_tokens() {
+    let table = single_rule_table(PriceRule {
+        provider: ProviderId::Anthropic,
+        model_pattern: "claude-*".to_string(),
+        input_cost_per_token: 0.01,
+        output_cost_per_token: 0.05,
+        cached_input_cost_per_token: Some(0.001),
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: None,
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
+    });
+    let usage = Usage {
+        input_tokens: Some(100),
+        output_tokens: Some(10),
+        cached_input_tokens: Some(80),
+        total_tokens: Some(110),
+        reasoning_tokens: None,
+        cache_creation_input_tokens: None,
+        web_search_requests: None,
+    };
+
+    let (cost, warnings) =
+        estimate_cost(&ProviderId::Anthropic, "claude-sonnet-4-5", &usage, &table);
+
+    assert!(warnings.is_empty());
+    let cost = cost.expect("cost should be estimated");
+    assert!((cost.cached_input_cost - 0.08).abs() < 1e-12);
+    assert!((cost.input_cost - (20.0 * 0.01 + 0.08)).abs() < 1e-12);
+    assert!((cost.total_cost - (cost.input_cost + 0.5)).abs() < 1e-12);
+}
+
+#[test]
+fn test_estimate_cost_cached_tokens_fall_back_to_input_rate() {
+    let table = single_rule_table(PriceRule {
+        provider: ProviderId::Openai,
+        model_pattern: "gpt-5".to_string(),
+        input_cost_per_token: 0.01,
+        output_cost_per_token: 0.02,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: None,
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
+    });
+    let usage = Usage {
+        input_tokens: Some(10),
+        output_tokens: Some(0),
+        cached_input_tokens: Some(4),
+        total_tokens: Some(10),
+        reasoning_tokens: None,
+        cache_creation_input_tokens: None,
+        web_search_requests: None,
+    };
+
+    let (cost, _) = estimate_cost(&ProviderId::Openai, "gpt-5", &usage, &table);
+
+    let cost = cost.expect("cost should be estimated");
+    assert!((cost.cached_input_cost - 0.04).abs() < 1e-12);
+    assert!((cost.input_cost - 0.1).abs() < 1e-12);
+}
+
+#[test]
+fn test_merge_reported_cost_splits_total_by_configured_rates() {
+    let reported = CostBreakdown {
+        currency: "USD".to_string(),
+        input_cost: 0.0,
+        cached_input_cost: 0.0,
+        output_cost: 0.0,
+        fixed_cost: 0.0,
+        total_cost: 0.02,
+        pricing_source: PricingSource::ProviderReported,
+    };
+    let estimate = CostBreakdown {
+        currency: "USD".to_string(),
+        input_cost: 0.001,
+        cached_input_cost: 0.0,
+        output_cost: 0.003,
+        fixed_cost: 0.0,
+        total_cost: 0.004,
+        pricing_source: PricingSource::Configured,
+    };
+
+    let merged = merge_reported_cost(reported.clone(), &estimate);
+    assert_eq!(merged.pricing_source, PricingSource::Mixed);
+    assert_eq!(merged.total_cost, 0.02);
+    assert!((merged.input_cost - 0.005).abs() < 1e-12);
+    assert!((merged.output_cost - 0.015).abs() < 1e-12);
+
+    let split = CostBreakdown {
+        input_cost: 0.01,
+        output_cost: 0.01,
+        ..reported
+    };
+    assert_eq!(merge_reported_cost(split.clone(), &estimate), split);
+}
+
+#[test]
+fn test_builtin_pricing_table_covers_default_providers() {
+    let table = PricingTable::builtin();
+
+    for (provider, model) in [
+        (ProviderId::Openai, "gpt-5-mini"),
+        (ProviderId::Openai, "gpt-4o-mini-2024-07-18"),
+        (ProviderId::Anthropic, "claude-sonnet-4-5-20250929"),
+        (ProviderId::Openrouter, "anthropic/claude-sonnet-4.5"),
+    ] {
+        let rule = table
+            .find_rule(&provider, model)
+            .unwrap_or_else(|| panic!("builtin table should price {model}"));
+        assert!(rule.has_valid_rates());
+    }
+    assert_eq!(
+        table
+            .find_rule(&ProviderId::Openai, "gpt-5-mini")
+            .map(|rule| rule.model_pattern.as_str()),
+        Some("gpt-5-mini*")
+    );
+}
+
+#[tokio::test]
+async fn test_refresh_from_remote_replaces_priced_models() {
+    let model = |model_id: &str, pricing: Option<ModelPricing>| ModelInfo {
+        provider: ProviderId::Openrouter,
+        model_id: model_id.to_string(),
+        display_name: None,
+        context_window: None,
+        max_output_tokens: None,
+        supports_tools: true,
+        supports_structured_output: true,
+        pricing,
+        input_modalities: Vec::new(),
+        output_modalities: Vec::new(),
+        deprecated: false,
+        knowledge_cutoff: None,
+    };
+    let adapter = MockAdapter::new(ProviderId::Openrouter).with_models(vec![
+        model(
+            "openai/gpt-5",
+            Some(ModelPricing {
+                input_cost_per_token: 0.000002,
+                output_cost_per_token: 0.000012,
+                cached_input_cost_per_token: None,
+                cache_write_input_cost_per_token: None,
+                reasoning_cost_per_token: None,
+            }),
+        ),
+        model(
+            "new/model",
+            Some(ModelPricing {
+                input_cost_per_token: 0.000001,
+                output_cost_per_token: 0.000001,
+                cached_input_cost_per_token: None,
+                cache_write_input_cost_per_token: None,
+                reasoning_cost_per_token: None,
+            }),
+        ),
+        model("openrouter/auto", None),
+    ]);
+    let mut table = PricingTable::builtin();
+    let rule_count = table.rules.len();
+
+    let priced = table
+        .refresh_from_remote(&adapter, &AdapterContext::default())
+        .await
+        .expect("refresh should succeed");
+
+    assert_eq!(priced, 2);
+    assert_eq!(table.rules.len(), rule_count + 1);
+    assert_eq!(
+        table
+            .find_rule(&ProviderId::Openrouter, "openai/gpt-5")
+            .map(|rule| rule.output_cost_per_token),
+        Some(0.000012)
+    );
+    assert!(
+        table
+            .find_rule(&ProviderId::Openrouter, "new/model")
+            .is_some()
+    );
+}
+
+#[test]
+fn test_estimate_cost_converts_rule_currency() {
+    let table = single_rule_table(PriceRule {
+        provider: ProviderId::Openai,
+        model_pattern: "gpt-5".to_string(),
+        input_cost_per_token: 0.01,
+        output_cost_per_token: 0.02,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: Some("USD".to_string()),
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
+    })
+    .with_currency("EUR")
+    .with_exchange_rate("USD", 0.5);
+    let usage = Usage {
+        input_tokens: Some(10),
+        output_tokens: Some(10),
+        ..Usage::default()
+    };
+
+    let (cost, warnings) = estimate_cost(&ProviderId::Openai, "gpt-5", &usage, &table);
+
+    assert!(warnings.is_empty());
+    let cost = cost.expect("cost should be estimated");
+    assert_eq!(cost.currency, "EUR");
+    assert!((cost.input_cost - 0.05).abs() < 1e-12);
+    assert!((cost.total_cost - 0.15).abs() < 1e-12);
+}
+
+#[test]
+fn test_estimate_cost_warns_when_exchange_rate_missing() {
+    let table = single_rule_table(PriceRule {
+        provider: ProviderId::Openai,
+        model_pattern: "gpt-5".to_string(),
+        input_cost_per_token: 0.01,
+        output_cost_per_token: 0.02,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: Some("GBP".to_string()),
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
+    })
+    .with_currency("EUR");
+    let usage = Usage {
+        input_tokens: Some(1),
+        output_tokens: Some(1),
+        ..Usage::default()
+    };
+
+    let (cost, warnings) = estimate_cost(&ProviderId::Openai, "gpt-5", &usage, &table);
+
+    assert_eq!(cost.map(|cost| cost.currency), Some("GBP".to_string()));
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].code, "pricing.currency_mismatch");
+}
+
+#[test]
+fn test_pricing_file_currency_and_exchange_rates() {
+    let table: PricingTable = r#"
+        currency = "EUR"
+
+        [exchange_rates]
+        USD = 0.92
+
+        [[rules]]
+        provider = "openai"
+        pattern = "gpt-5"
+        input_per_million = 1.0
+        output_per_million = 1.0
+        currency = "USD"
+
+        [[image_rules]]
+        provider = "openai"
+        pattern = "gpt-image-1"
+        per_image = 0.04
+    "#
+    .parse()
+    .expect("pricing TOML should parse");
+
+    assert_eq!(table.currency, "EUR");
+    assert_eq!(table.exchange_rates.get("USD"), Some(&0.92));
+    assert_eq!(table.rules[0].currency.as_deref(), Some("USD"));
+    assert_eq!(table.image_rules[0].currency, None);
+    assert_eq!(PricingTable::default().currency, "USD");
+
+    for (contents, expected) in [
+        (r#"currency = "euro""#, "currency"),
+        ("[exchange_rates]\nUSD = 0.0", "exchange_rates.USD"),
+    ] {
+        let err = contents
+            .parse::<PricingTable>()
+            .expect_err("invalid currency config should fail");
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
+
+#[test]
+fn test_estimate_cost_selects_tier_from_input_tokens() {
+    let table = single_rule_table(PriceRule {
+        provider: ProviderId::Other("gemini".to_string()),
+        model_pattern: "gemini-2.5-pro".to_string(),
+        input_cost_per_token: 0.001,
+        output_cost_per_token: 0.01,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: None,
+        tiers: vec![
+            PriceTier {
+                above_input_tokens: 200,
+                input_cost_per_token: 0.002,
+                output_cost_per_token: 0.02,
+                cached_input_cost_per_token: None,
+            },
+            PriceTier {
+                above_input_tokens: 100,
+                input_cost_per_token: 0.0015,
+                output_cost_per_token: 0.015,
+                cached_input_cost_per_token: None,
+            },
+        ],
+        per_request_cost: None,
+        per_web_search_cost: None,
+    });
+    let provider = ProviderId::Other("gemini".to_string());
+    let cost_for = |input_tokens: u64| {
+        let usage = Usage {
+            input_tokens: Some(input_tokens),
+            output_tokens: Some(10),
+            ..Usage::default()
+        };
+        estimate_cost(&provider, "gemini-2.5-pro", &usage, &table)
+            .0
+            .expect("cost should be estimated")
+    };
+
+    let base = cost_for(100);
+    assert!((base.input_cost - 0.1).abs() < 1e-12);
+    assert!((base.output_cost - 0.1).abs() < 1e-12);
+
+    let middle = cost_for(150);
+    assert!((middle.input_cost - 150.0 * 0.0015).abs() < 1e-12);
+
+    let long = cost_for(300);
+    assert!((long.input_cost - 0.6).abs() < 1e-12);
+    assert!((long.output_cost - 0.2).abs() < 1e-12);
+}
+
+#[test]
+fn test_estimate_cost_adds_request_and_web_search_fees() {
+    let table = single_rule_table(PriceRule {
+        provider: ProviderId::Anthropic,
+        model_pattern: "claude-*".to_string(),
+        input_cost_per_token: 0.0,
+        output_cost_per_token: 0.01,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: None,
+        tiers: Vec::new(),
+        per_request_cost: Some(0.5),
+        per_web_search_cost: Some(0.01),
+    });
+    let usage = Usage {
+        input_tokens: Some(10),
+        output_tokens: Some(10),
+        web_search_requests: Some(3),
+        ..Usage::default()
+    };
+
+    let (cost, _) = estimate_cost(&ProviderId::Anthropic, "claude-sonnet-4-5", &usage, &table);
+
+    let cost = cost.expect("cost should be estimated");
+    assert!((cost.fixed_cost - 0.53).abs() < 1e-12);
+    assert!((cost.total_cost - (0.1 + 0.53)).abs() < 1e-12);
+}
+
+#[test]
+fn test_pricing_file_tiers_and_fees() {
+    let table: PricingTable = r#"
+        [[rules]]
+        provider = "gemini"
+        pattern = "gemini-2.5-pro*"
+        input_per_million = 1.25
+        output_per_million = 10.0
+        per_request = 0.001
+        per_web_search = 0.035
+
+        [[rules.tiers]]
+        above_input_tokens = 200000
+        input_per_million = 2.5
+        output_per_million = 15.0
+        cached_input_per_million = 0.625
+    "#
+    .parse()
+    .expect("pricing TOML should parse");
+
+    let rule = &table.rules[0];
+    assert_eq!(rule.per_request_cost, Some(0.001));
+    assert_eq!(rule.per_web_search_cost, Some(0.035));
+    assert_eq!(
+        rule.tiers,
+        vec![PriceTier {
+            above_input_tokens: 200_000,
+            input_cost_per_token: 2.5 / 1_000_000.0,
+            output_cost_per_token: 15.0 / 1_000_000.0,
+            cached_input_cost_per_token: Some(0.625 / 1_000_000.0),
+        }]
+    );
+
+    let err = r#"
+        [[rules]]
+        provider = "gemini"
+        pattern = "*"
+        input_per_million = 1.0
+        output_per_million = 1.0
+
+        [[rules.tiers]]
+        above_input_tokens = 10
+        input_per_million = 1.0
+        output_per_million = -1.0
+    "#
+    .parse::<PricingTable>()
+    .expect_err("negative tier rate should fail");
+    assert!(
+        err.to_string()
+            .contains("rules[0].tiers[0].output_per_million"),
+        "{err}"
+    );
+}
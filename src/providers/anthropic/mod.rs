@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use serde_json::Value;
 
@@ -5,54 +7,184 @@ use crate::core::error::{ConfigError, ProviderError};
 use crate::core::traits::ProviderAdapter;
 use crate::core::types::{
     AdapterContext, DiscoveryOptions, ModelInfo, ProviderCapabilities, ProviderId, ProviderRequest,
-    ProviderResponse,
+    ProviderResponse, RuntimeWarning,
 };
 use crate::providers::anthropic_translate::{
-    AnthropicDecodeEnvelope, AnthropicTranslator, decode_anthropic_models_list,
-    format_anthropic_error_message, parse_anthropic_error_envelope,
+    AnthropicDecodeEnvelope, AnthropicTranslateOptions, AnthropicTranslator,
+    decode_anthropic_models_list, format_anthropic_error_message, parse_anthropic_error_envelope,
 };
 use crate::providers::translator_contract::ProviderTranslator;
-use crate::transport::http::{HttpTransport, RetryPolicy};
+use crate::registry::{ACCESS_TOKEN_METADATA_KEY, ChainCredentialStore, credentials};
+use crate::runtime::trace;
+use crate::transport::Transport;
+use crate::transport::http::{HttpTransport, JsonResponse, RetryPolicy};
 
 const ANTHROPIC_DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
 const ANTHROPIC_API_KEY_ENV: &str = "ANTHROPIC_API_KEY";
 const ANTHROPIC_API_KEY_METADATA: &str = "anthropic.api_key";
-const ANTHROPIC_VERSION: &str = "2023-06-01";
+pub(crate) const ANTHROPIC_VERSION: &str = "2023-06-01";
+const ANTHROPIC_DEFAULT_TIMEOUT_MS: u64 = 30_000;
 
 const TRANSPORT_HEADER_API_KEY: &str = "transport.header.x-api-key";
+const TRANSPORT_AUTH_BEARER_TOKEN_KEY: &str = "transport.auth.bearer_token";
 const TRANSPORT_HEADER_ANTHROPIC_VERSION: &str = "transport.header.anthropic-version";
+const TRANSPORT_HEADER_ANTHROPIC_BETA: &str = "transport.header.anthropic-beta";
 const TRANSPORT_REQUEST_ID_HEADER: &str = "transport.request_id_header";
 
+const MIN_THINKING_BUDGET_TOKENS: u32 = 1024;
+
+/// Encoded `/v1/messages` fields the `count_tokens` endpoint accepts; the rest only affect
+/// generation and are rejected there.
+const COUNT_TOKENS_FIELDS: &[&str] = &[
+    "model",
+    "messages",
+    "system",
+    "tools",
+    "tool_choice",
+    "thinking",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThinkingConfig {
+    pub budget_tokens: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AnthropicAdapterOptions {
+    pub thinking: Option<ThinkingConfig>,
+    /// `anthropic-version` header value; defaults to `2023-06-01`.
+    pub api_version: Option<String>,
+    /// Beta feature names sent in the `anthropic-beta` header, such as
+    /// `token-efficient-tools-2025-02-19`.
+    pub beta_features: Vec<String>,
+    /// Per-attempt HTTP timeout; defaults to 30 seconds. Ignored by `with_transport`.
+    pub timeout_ms: Option<u64>,
+    /// Ignored by `with_transport`, where the caller's transport owns retries.
+    pub retry_policy: Option<RetryPolicy>,
+    /// `max_tokens` sent when a request leaves `max_output_tokens` unset; defaults to 1024.
+    pub default_max_tokens: Option<u32>,
+}
+
+impl AnthropicAdapterOptions {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(thinking) = &self.thinking
+            && thinking.budget_tokens < MIN_THINKING_BUDGET_TOKENS
+        {
+            return Err(Self::invalid_config(format!(
+                "thinking.budget_tokens must be at least {MIN_THINKING_BUDGET_TOKENS}, got {}",
+                thinking.budget_tokens
+            )));
+        }
+
+        if let Some(api_version) = &self.api_version
+            && api_version.trim().is_empty()
+        {
+            return Err(Self::invalid_config(
+                "api_version must be non-empty when provided",
+            ));
+        }
+
+        for beta in &self.beta_features {
+            if beta.trim().is_empty() || beta.contains(',') {
+                return Err(Self::invalid_config(format!(
+                    "beta_features entries must be non-empty and comma-free, got '{beta}'"
+                )));
+            }
+        }
+
+        if self.default_max_tokens == Some(0) {
+            return Err(Self::invalid_config(
+                "default_max_tokens must be greater than 0",
+            ));
+        }
+
+        if let Some(retry_policy) = &self.retry_policy {
+            retry_policy.validate()?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn to_translate_options(&self) -> AnthropicTranslateOptions {
+        AnthropicTranslateOptions {
+            thinking_budget_tokens: self.thinking.map(|thinking| thinking.budget_tokens),
+            default_max_tokens: self.default_max_tokens,
+        }
+    }
+
+    fn invalid_config(reason: impl Into<String>) -> ConfigError {
+        ConfigError::InvalidProviderConfig {
+            provider: ProviderId::Anthropic,
+            reason: reason.into(),
+        }
+    }
+}
+
+enum AnthropicCredential {
+    ApiKey(String),
+    AccessToken(String),
+}
+
 pub struct AnthropicAdapter {
-    transport: HttpTransport,
+    transport: Arc<dyn Transport>,
     translator: AnthropicTranslator,
     base_url: String,
-    api_key: Option<String>,
+    credentials: ChainCredentialStore,
+    api_version: String,
+    beta_header: Option<String>,
 }
 
 impl AnthropicAdapter {
     pub fn new(api_key: Option<String>) -> Result<Self, ConfigError> {
-        Self::with_base_url(api_key, ANTHROPIC_DEFAULT_BASE_URL)
+        Self::with_base_url_and_options(api_key, ANTHROPIC_DEFAULT_BASE_URL, Default::default())
     }
 
     pub fn with_base_url(
         api_key: Option<String>,
         base_url: impl Into<String>,
     ) -> Result<Self, ConfigError> {
-        let transport = HttpTransport::new(30_000, RetryPolicy::default())?;
-        Ok(Self::with_transport(api_key, base_url, transport))
+        Self::with_base_url_and_options(api_key, base_url, Default::default())
+    }
+
+    pub fn with_base_url_and_options(
+        api_key: Option<String>,
+        base_url: impl Into<String>,
+        options: AnthropicAdapterOptions,
+    ) -> Result<Self, ConfigError> {
+        options.validate()?;
+        let transport = HttpTransport::new(
+            options.timeout_ms.unwrap_or(ANTHROPIC_DEFAULT_TIMEOUT_MS),
+            options.retry_policy.clone().unwrap_or_default(),
+        )?;
+        Ok(Self::with_transport(
+            api_key,
+            base_url,
+            options,
+            Arc::new(transport),
+        ))
     }
 
-    pub(crate) fn with_transport(
+    /// Builds the adapter over a caller-supplied transport, such as a test fake or a gateway
+    /// client.
+    pub fn with_transport(
         api_key: Option<String>,
         base_url: impl Into<String>,
-        transport: HttpTransport,
+        options: AnthropicAdapterOptions,
+        transport: Arc<dyn Transport>,
     ) -> Self {
         Self {
             transport,
-            translator: AnthropicTranslator,
+            translator: AnthropicTranslator::new(options.to_translate_options()),
             base_url: normalize_base_url(base_url),
-            api_key: sanitize_api_key(api_key),
+            credentials: ChainCredentialStore::adapter_default(
+                ProviderId::Anthropic,
+                sanitize_api_key(api_key),
+            ),
+            api_version: options
+                .api_version
+                .unwrap_or_else(|| ANTHROPIC_VERSION.to_string()),
+            beta_header: (!options.beta_features.is_empty())
+                .then(|| options.beta_features.join(",")),
         }
     }
 
@@ -60,31 +192,27 @@ impl AnthropicAdapter {
         format!("{}/v1/messages", self.base_url)
     }
 
+    fn count_tokens_url(&self) -> String {
+        format!("{}/v1/messages/count_tokens", self.base_url)
+    }
+
     fn models_url(&self) -> String {
         format!("{}/v1/models", self.base_url)
     }
 
-    fn resolve_api_key(&self, ctx: &AdapterContext) -> Option<String> {
-        let env_api_key = std::env::var(ANTHROPIC_API_KEY_ENV).ok();
-        self.resolve_api_key_with_env(ctx, env_api_key)
+    fn resolve_api_key(&self, ctx: &AdapterContext) -> Result<Option<String>, ProviderError> {
+        credentials::resolve_api_key(&ProviderId::Anthropic, &self.credentials, ctx)
     }
 
-    fn resolve_api_key_with_env(
+    /// A runtime-supplied access token goes out as a bearer token; otherwise the API key.
+    fn resolve_credential(
         &self,
         ctx: &AdapterContext,
-        env_api_key: Option<String>,
-    ) -> Option<String> {
-        if let Some(key) = self.api_key.as_ref().cloned() {
-            return Some(key);
+    ) -> Result<Option<AnthropicCredential>, ProviderError> {
+        if let Some(token) = ctx.metadata.get(ACCESS_TOKEN_METADATA_KEY) {
+            return Ok(Some(AnthropicCredential::AccessToken(token.clone())));
         }
-
-        if let Some(key) = ctx.metadata.get(ANTHROPIC_API_KEY_METADATA)
-            && !key.trim().is_empty()
-        {
-            return Some(key.clone());
-        }
-
-        env_api_key.and_then(|value| sanitize_api_key(Some(value)))
+        Ok(self.resolve_api_key(ctx)?.map(AnthropicCredential::ApiKey))
     }
 
     fn missing_api_key_error(model: Option<&str>) -> ProviderError {
@@ -98,15 +226,27 @@ impl AnthropicAdapter {
         }
     }
 
-    fn attach_transport_headers(ctx: &AdapterContext, api_key: String) -> AdapterContext {
+    fn attach_transport_headers(
+        &self,
+        ctx: &AdapterContext,
+        credential: AnthropicCredential,
+    ) -> AdapterContext {
         let mut request_ctx = ctx.clone();
-        request_ctx
-            .metadata
-            .insert(TRANSPORT_HEADER_API_KEY.to_string(), api_key);
+        let (key, value) = match credential {
+            AnthropicCredential::ApiKey(api_key) => (TRANSPORT_HEADER_API_KEY, api_key),
+            AnthropicCredential::AccessToken(token) => (TRANSPORT_AUTH_BEARER_TOKEN_KEY, token),
+        };
+        request_ctx.metadata.insert(key.to_string(), value);
         request_ctx.metadata.insert(
             TRANSPORT_HEADER_ANTHROPIC_VERSION.to_string(),
-            ANTHROPIC_VERSION.to_string(),
+            self.api_version.clone(),
         );
+        if let Some(beta_header) = &self.beta_header {
+            request_ctx.metadata.insert(
+                TRANSPORT_HEADER_ANTHROPIC_BETA.to_string(),
+                beta_header.clone(),
+            );
+        }
         request_ctx.metadata.insert(
             TRANSPORT_REQUEST_ID_HEADER.to_string(),
             "request-id".to_string(),
@@ -124,6 +264,7 @@ impl AnthropicAdapter {
                 request_id,
                 message,
                 model,
+                rate_limit,
                 ..
             } => {
                 let mut request_id = request_id;
@@ -143,13 +284,14 @@ impl AnthropicAdapter {
                         };
                     }
 
-                    return ProviderError::Status {
-                        provider: ProviderId::Anthropic,
+                    return ProviderError::from_status(
+                        ProviderId::Anthropic,
                         model,
                         status_code,
                         request_id,
                         message,
-                    };
+                        rate_limit,
+                    );
                 }
 
                 if status_code == 401 {
@@ -160,13 +302,14 @@ impl AnthropicAdapter {
                     };
                 }
 
-                ProviderError::Status {
-                    provider: ProviderId::Anthropic,
+                ProviderError::from_status(
+                    ProviderId::Anthropic,
                     model,
                     status_code,
                     request_id,
                     message,
-                }
+                    rate_limit,
+                )
             }
             other => other,
         }
@@ -185,22 +328,30 @@ impl ProviderAdapter for AnthropicAdapter {
             supports_structured_output: true,
             supports_thinking: true,
             supports_remote_discovery: true,
+            supports_parallel_tool_calls: true,
         }
     }
 
+    fn encode_warnings(&self, req: &ProviderRequest) -> Result<Vec<RuntimeWarning>, ProviderError> {
+        self.translator
+            .encode_request(req)
+            .map(|encoded| encoded.warnings)
+    }
+
     async fn run(
         &self,
         req: &ProviderRequest,
         ctx: &AdapterContext,
     ) -> Result<ProviderResponse, ProviderError> {
-        let api_key = self
-            .resolve_api_key(ctx)
+        let credential = self
+            .resolve_credential(ctx)?
             .ok_or_else(|| Self::missing_api_key_error(Some(&req.model.model_id)))?;
 
         let encoded = self.translator.encode_request(req)?;
-        let request_ctx = Self::attach_transport_headers(ctx, api_key);
+        let request_ctx = self.attach_transport_headers(ctx, credential);
 
-        let response_body: Value = self
+        trace::record_encoded_request(&encoded.body);
+        let response: JsonResponse<Value> = self
             .transport
             .post_json(
                 ProviderId::Anthropic,
@@ -212,6 +363,8 @@ impl ProviderAdapter for AnthropicAdapter {
             .await
             .map_err(|error| Self::normalize_transport_error(error, Some(&req.model.model_id)))?;
 
+        let response_body = response.body;
+        trace::record_raw_response(&response_body);
         let envelope = AnthropicDecodeEnvelope {
             body: response_body,
             requested_response_format: req.response_format.clone(),
@@ -224,21 +377,63 @@ impl ProviderAdapter for AnthropicAdapter {
             decoded.warnings = warnings;
         }
 
+        decoded.request_id = response.request_id;
+        decoded.rate_limit = response.rate_limit;
         Ok(decoded)
     }
 
+    async fn count_tokens(
+        &self,
+        req: &ProviderRequest,
+        ctx: &AdapterContext,
+    ) -> Result<Option<u64>, ProviderError> {
+        let credential = self
+            .resolve_credential(ctx)?
+            .ok_or_else(|| Self::missing_api_key_error(Some(&req.model.model_id)))?;
+
+        let mut body = self.translator.encode_request(req)?.body;
+        if let Value::Object(fields) = &mut body {
+            fields.retain(|key, _| COUNT_TOKENS_FIELDS.contains(&key.as_str()));
+        }
+        let request_ctx = self.attach_transport_headers(ctx, credential);
+
+        let payload = self
+            .transport
+            .post_json(
+                ProviderId::Anthropic,
+                Some(req.model.model_id.as_str()),
+                &self.count_tokens_url(),
+                &body,
+                &request_ctx,
+            )
+            .await
+            .map_err(|error| Self::normalize_transport_error(error, Some(&req.model.model_id)))?
+            .body;
+
+        payload
+            .get("input_tokens")
+            .and_then(Value::as_u64)
+            .map(Some)
+            .ok_or_else(|| ProviderError::Protocol {
+                provider: ProviderId::Anthropic,
+                model: Some(req.model.model_id.clone()),
+                request_id: None,
+                message: "count_tokens response is missing input_tokens".to_string(),
+            })
+    }
+
     async fn discover_models(
         &self,
         _opts: &DiscoveryOptions,
         ctx: &AdapterContext,
     ) -> Result<Vec<ModelInfo>, ProviderError> {
-        let api_key = self
-            .resolve_api_key(ctx)
+        let credential = self
+            .resolve_credential(ctx)?
             .ok_or_else(|| Self::missing_api_key_error(None))?;
 
-        let request_ctx = Self::attach_transport_headers(ctx, api_key);
+        let request_ctx = self.attach_transport_headers(ctx, credential);
 
-        let payload: Value = self
+        let payload = self
             .transport
             .get_json(
                 ProviderId::Anthropic,
@@ -247,7 +442,8 @@ impl ProviderAdapter for AnthropicAdapter {
                 &request_ctx,
             )
             .await
-            .map_err(|error| Self::normalize_transport_error(error, None))?;
+            .map_err(|error| Self::normalize_transport_error(error, None))?
+            .body;
 
         decode_anthropic_models_list(&payload, &self.capabilities())
     }
@@ -6,14 +6,15 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
-use crate::core::error::ProviderError;
+use crate::core::error::{ConfigError, ProviderError};
 use crate::core::traits::ProviderAdapter;
 use crate::core::types::{
     AdapterContext, ContentPart, DiscoveryOptions, Message, MessageRole, ModelRef, ProviderId,
     ProviderRequest, ResponseFormat, ToolChoice,
 };
-use crate::providers::anthropic::AnthropicAdapter;
-use crate::transport::http::{HttpTransport, RetryPolicy};
+use crate::providers::anthropic::{AnthropicAdapter, AnthropicAdapterOptions, ThinkingConfig};
+use crate::transport::http::{HttpTransport, JitterMode, JsonResponse, RetryPolicy};
+use crate::transport::{SseStream, Transport};
 
 #[derive(Debug, Clone)]
 struct MockResponse {
@@ -128,21 +129,28 @@ fn base_request() -> ProviderRequest {
         model: ModelRef {
             provider_hint: Some(ProviderId::Anthropic),
             model_id: "claude-sonnet-4-5".to_string(),
+            requirements: None,
         },
         messages: vec![Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "hello".to_string(),
             }],
+            cache_hint: None,
         }],
         tools: Vec::new(),
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::Text,
         temperature: None,
         top_p: None,
         max_output_tokens: Some(16),
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     }
 }
 
@@ -157,6 +165,47 @@ fn test_anthropic_adapter_capabilities() {
     assert!(capabilities.supports_remote_discovery);
 }
 
+#[test]
+fn test_anthropic_adapter_options_validate_thinking_budget() {
+    let options = AnthropicAdapterOptions {
+        thinking: Some(ThinkingConfig { budget_tokens: 512 }),
+        ..AnthropicAdapterOptions::default()
+    };
+
+    let err = match AnthropicAdapter::with_base_url_and_options(
+        Some("test-key".to_string()),
+        "http://example.com",
+        options,
+    ) {
+        Ok(_) => panic!("budget below minimum should be rejected"),
+        Err(err) => err,
+    };
+    match err {
+        ConfigError::InvalidProviderConfig { provider, reason } => {
+            assert_eq!(provider, ProviderId::Anthropic);
+            assert!(reason.contains("thinking.budget_tokens"));
+        }
+        other => panic!("expected invalid provider config, got {other:?}"),
+    }
+
+    let options = AnthropicAdapterOptions {
+        thinking: Some(ThinkingConfig {
+            budget_tokens: 2048,
+        }),
+        ..AnthropicAdapterOptions::default()
+    };
+    assert_eq!(
+        options.to_translate_options().thinking_budget_tokens,
+        Some(2048)
+    );
+    AnthropicAdapter::with_base_url_and_options(
+        Some("test-key".to_string()),
+        "http://example.com",
+        options,
+    )
+    .expect("valid thinking budget should be accepted");
+}
+
 #[tokio::test]
 async fn test_anthropic_adapter_missing_key_error() {
     let adapter = AnthropicAdapter::with_base_url(None, "http://127.0.0.1:1").expect("adapter");
@@ -192,6 +241,9 @@ async fn test_anthropic_adapter_uses_translator_boundary() {
             initial_backoff_ms: 0,
             max_backoff_ms: 0,
             retryable_status_codes: vec![429],
+            max_retry_after_ms: 0,
+            max_total_retry_duration_ms: None,
+            jitter: JitterMode::None,
         },
     )
     .expect("transport");
@@ -199,7 +251,8 @@ async fn test_anthropic_adapter_uses_translator_boundary() {
     let adapter = AnthropicAdapter::with_transport(
         Some("test-key".to_string()),
         "http://127.0.0.1:1",
-        transport,
+        AnthropicAdapterOptions::default(),
+        Arc::new(transport),
     );
 
     let mut req = base_request();
@@ -209,12 +262,14 @@ async fn test_anthropic_adapter_uses_translator_boundary() {
             content: vec![ContentPart::Text {
                 text: "hello".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::System,
             content: vec![ContentPart::Text {
                 text: "late system".to_string(),
             }],
+            cache_hint: None,
         },
     ];
 
@@ -244,12 +299,14 @@ async fn test_anthropic_adapter_uses_translator_boundary_before_transport_call()
             content: vec![ContentPart::Text {
                 text: "hello".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::System,
             content: vec![ContentPart::Text {
                 text: "late system".to_string(),
             }],
+            cache_hint: None,
         },
     ];
 
@@ -306,6 +363,135 @@ async fn test_anthropic_adapter_sets_required_headers() {
     assert_eq!(headers[0].get("authorization"), None);
 }
 
+#[tokio::test]
+async fn test_anthropic_adapter_options_set_version_and_beta_headers() {
+    let mut server = MockServer::start(vec![MockResponse::new(
+        200,
+        vec![],
+        r#"{
+            "id":"msg_1",
+            "type":"message",
+            "role":"assistant",
+            "model":"claude-sonnet-4-5",
+            "stop_reason":"end_turn",
+            "content":[{"type":"text","text":"ok"}],
+            "usage":{"input_tokens":1,"output_tokens":1}
+        }"#,
+    )]);
+
+    let adapter = AnthropicAdapter::with_base_url_and_options(
+        Some("test-key".to_string()),
+        server.url(),
+        AnthropicAdapterOptions {
+            api_version: Some("2024-01-01".to_string()),
+            beta_features: vec![
+                "token-efficient-tools-2025-02-19".to_string(),
+                "interleaved-thinking-2025-05-14".to_string(),
+            ],
+            timeout_ms: Some(5_000),
+            ..AnthropicAdapterOptions::default()
+        },
+    )
+    .expect("adapter");
+
+    adapter
+        .run(&base_request(), &AdapterContext::default())
+        .await
+        .expect("run should succeed");
+
+    server.shutdown();
+    let headers = server.captured_headers();
+    assert_eq!(
+        headers[0].get("anthropic-version"),
+        Some(&"2024-01-01".to_string())
+    );
+    assert_eq!(
+        headers[0].get("anthropic-beta"),
+        Some(&"token-efficient-tools-2025-02-19,interleaved-thinking-2025-05-14".to_string())
+    );
+}
+
+#[test]
+fn test_anthropic_adapter_options_reject_invalid_transport_settings() {
+    for (options, expected) in [
+        (
+            AnthropicAdapterOptions {
+                beta_features: vec!["a,b".to_string()],
+                ..AnthropicAdapterOptions::default()
+            },
+            "beta_features",
+        ),
+        (
+            AnthropicAdapterOptions {
+                default_max_tokens: Some(0),
+                ..AnthropicAdapterOptions::default()
+            },
+            "default_max_tokens",
+        ),
+        (
+            AnthropicAdapterOptions {
+                api_version: Some(" ".to_string()),
+                ..AnthropicAdapterOptions::default()
+            },
+            "api_version",
+        ),
+    ] {
+        match AnthropicAdapter::with_base_url_and_options(
+            Some("test-key".to_string()),
+            "http://example.com",
+            options,
+        ) {
+            Err(ConfigError::InvalidProviderConfig { reason, .. }) => {
+                assert!(reason.contains(expected), "unexpected reason: {reason}");
+            }
+            Err(other) => panic!("expected invalid provider config, got {other:?}"),
+            Ok(_) => panic!("options with invalid {expected} should be rejected"),
+        }
+    }
+
+    let timeout_error = AnthropicAdapter::with_base_url_and_options(
+        Some("test-key".to_string()),
+        "http://example.com",
+        AnthropicAdapterOptions {
+            timeout_ms: Some(0),
+            ..AnthropicAdapterOptions::default()
+        },
+    );
+    assert!(matches!(
+        timeout_error,
+        Err(ConfigError::InvalidTimeout { timeout_ms: 0 })
+    ));
+}
+
+#[tokio::test]
+async fn test_anthropic_adapter_populates_request_id_on_success() {
+    let mut server = MockServer::start(vec![MockResponse::new(
+        200,
+        vec![("request-id".to_string(), "req-ok-1".to_string())],
+        r#"{
+            "id":"msg_1",
+            "type":"message",
+            "role":"assistant",
+            "model":"claude-sonnet-4-5",
+            "stop_reason":"end_turn",
+            "content":[{"type":"text","text":"ok"}],
+            "usage":{"input_tokens":1,"output_tokens":1}
+        }"#,
+    )]);
+
+    let adapter = AnthropicAdapter::with_base_url(Some("test-key".to_string()), server.url())
+        .expect("adapter");
+
+    let response = adapter
+        .run(&base_request(), &AdapterContext::default())
+        .await
+        .expect("run should succeed");
+
+    assert_eq!(response.request_id.as_deref(), Some("req-ok-1"));
+
+    server.shutdown();
+}
+
 #[tokio::test]
 async fn test_anthropic_adapter_sets_required_headers_with_metadata_key() {
     let mut server = MockServer::start(vec![MockResponse::new(
@@ -344,6 +530,44 @@ async fn test_anthropic_adapter_sets_required_headers_with_metadata_key() {
     );
 }
 
+#[tokio::test]
+async fn test_anthropic_adapter_sends_access_token_as_bearer() {
+    let mut server = MockServer::start(vec![MockResponse::new(
+        200,
+        vec![],
+        r#"{
+            "id":"msg_1",
+            "type":"message",
+            "role":"assistant",
+            "model":"claude-sonnet-4-5",
+            "stop_reason":"end_turn",
+            "content":[{"type":"text","text":"ok"}],
+            "usage":{"input_tokens":1,"output_tokens":1}
+        }"#,
+    )]);
+
+    let adapter = AnthropicAdapter::with_base_url(Some("test-key".to_string()), server.url())
+        .expect("adapter");
+    let mut ctx = AdapterContext::default();
+    ctx.metadata.insert(
+        crate::registry::ACCESS_TOKEN_METADATA_KEY.to_string(),
+        "oauth-token".to_string(),
+    );
+
+    adapter
+        .run(&base_request(), &ctx)
+        .await
+        .expect("run should succeed");
+
+    server.shutdown();
+    let headers = server.captured_headers();
+    assert_eq!(
+        headers[0].get("authorization"),
+        Some(&"Bearer oauth-token".to_string())
+    );
+    assert_eq!(headers[0].get("x-api-key"), None);
+}
+
 #[tokio::test]
 async fn test_anthropic_adapter_maps_auth_status_to_credentials_rejected() {
     let mut server = MockServer::start(vec![MockResponse::new(
@@ -400,12 +624,19 @@ async fn test_anthropic_adapter_maps_non_auth_status_to_status_error() {
             initial_backoff_ms: 0,
             max_backoff_ms: 0,
             retryable_status_codes: vec![429],
+            max_retry_after_ms: 0,
+            max_total_retry_duration_ms: None,
+            jitter: JitterMode::None,
         },
     )
     .expect("transport");
 
-    let adapter =
-        AnthropicAdapter::with_transport(Some("test-key".to_string()), server.url(), transport);
+    let adapter = AnthropicAdapter::with_transport(
+        Some("test-key".to_string()),
+        server.url(),
+        AnthropicAdapterOptions::default(),
+        Arc::new(transport),
+    );
 
     let err = adapter
         .run(&base_request(), &AdapterContext::default())
@@ -419,6 +650,7 @@ async fn test_anthropic_adapter_maps_non_auth_status_to_status_error() {
             status_code,
             request_id,
             message,
+            ..
         } => {
             assert_eq!(provider, ProviderId::Anthropic);
             assert_eq!(model, Some("claude-sonnet-4-5".to_string()));
@@ -452,12 +684,19 @@ async fn test_anthropic_adapter_maps_status_request_id_from_body_when_header_mis
             initial_backoff_ms: 0,
             max_backoff_ms: 0,
             retryable_status_codes: vec![429],
+            max_retry_after_ms: 0,
+            max_total_retry_duration_ms: None,
+            jitter: JitterMode::None,
         },
     )
     .expect("transport");
 
-    let adapter =
-        AnthropicAdapter::with_transport(Some("test-key".to_string()), server.url(), transport);
+    let adapter = AnthropicAdapter::with_transport(
+        Some("test-key".to_string()),
+        server.url(),
+        AnthropicAdapterOptions::default(),
+        Arc::new(transport),
+    );
 
     let err = adapter
         .run(&base_request(), &AdapterContext::default())
@@ -471,6 +710,7 @@ async fn test_anthropic_adapter_maps_status_request_id_from_body_when_header_mis
             status_code,
             request_id,
             message,
+            ..
         } => {
             assert_eq!(provider, ProviderId::Anthropic);
             assert_eq!(model, Some("claude-sonnet-4-5".to_string()));
@@ -495,17 +735,20 @@ fn test_anthropic_adapter_api_key_resolution_precedence() {
     ctx.metadata
         .insert("anthropic.api_key".to_string(), "context-key".to_string());
 
-    let constructor = adapter.resolve_api_key_with_env(&ctx, Some("env-key".to_string()));
+    let constructor = adapter.resolve_api_key(&ctx).expect("resolve");
     assert_eq!(constructor.as_deref(), Some("constructor-key"));
 
     let metadata_adapter =
         AnthropicAdapter::with_base_url(None, "http://example.com").expect("metadata adapter");
-    let metadata = metadata_adapter.resolve_api_key_with_env(&ctx, Some("env-key".to_string()));
+    let metadata = metadata_adapter.resolve_api_key(&ctx).expect("resolve");
     assert_eq!(metadata.as_deref(), Some("context-key"));
 
-    let env = metadata_adapter
-        .resolve_api_key_with_env(&AdapterContext::default(), Some("env-key".to_string()));
-    assert_eq!(env.as_deref(), Some("env-key"));
+    ctx.metadata.insert(
+        crate::registry::API_KEY_METADATA_KEY.to_string(),
+        "pooled-key".to_string(),
+    );
+    let pooled = adapter.resolve_api_key(&ctx).expect("resolve");
+    assert_eq!(pooled.as_deref(), Some("pooled-key"));
 }
 
 #[tokio::test]
@@ -569,6 +812,117 @@ async fn test_anthropic_adapter_discover_models_invalid_payload_is_protocol_erro
     server.shutdown();
 }
 
+/// Answers every POST with a canned body and records the URL and body it was sent.
+struct CountTokensTransport {
+    body: serde_json::Value,
+    calls: Mutex<Vec<(String, serde_json::Value)>>,
+}
+
+#[async_trait::async_trait]
+impl Transport for CountTokensTransport {
+    async fn post_json(
+        &self,
+        _provider: ProviderId,
+        _model: Option<&str>,
+        url: &str,
+        body: &serde_json::Value,
+        _ctx: &AdapterContext,
+    ) -> Result<JsonResponse<serde_json::Value>, ProviderError> {
+        self.calls
+            .lock()
+            .expect("calls lock")
+            .push((url.to_string(), body.clone()));
+        Ok(JsonResponse {
+            body: self.body.clone(),
+            request_id: None,
+            rate_limit: None,
+        })
+    }
+
+    async fn get_json(
+        &self,
+        provider: ProviderId,
+        _model: Option<&str>,
+        _url: &str,
+        _ctx: &AdapterContext,
+    ) -> Result<JsonResponse<serde_json::Value>, ProviderError> {
+        Err(ProviderError::Transport {
+            provider,
+            request_id: None,
+            message: "GET is not faked".to_string(),
+            source: None,
+        })
+    }
+
+    async fn post_sse(
+        &self,
+        provider: ProviderId,
+        _model: Option<&str>,
+        _url: &str,
+        _body: &serde_json::Value,
+        _ctx: &AdapterContext,
+    ) -> Result<SseStream, ProviderError> {
+        Err(ProviderError::Transport {
+            provider,
+            request_id: None,
+            message: "streaming is not faked".to_string(),
+            source: None,
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_anthropic_adapter_count_tokens_posts_generation_free_body() {
+    let transport = Arc::new(CountTokensTransport {
+        body: serde_json::json!({ "input_tokens": 42 }),
+        calls: Mutex::new(Vec::new()),
+    });
+    let adapter = AnthropicAdapter::with_transport(
+        Some("test-key".to_string()),
+        "http://gateway.internal/",
+        AnthropicAdapterOptions::default(),
+        transport.clone(),
+    );
+    let mut request = base_request();
+    request.temperature = Some(0.2);
+
+    let count = adapter
+        .count_tokens(&request, &AdapterContext::default())
+        .await
+        .expect("count should succeed");
+
+    assert_eq!(count, Some(42));
+    let calls = transport.calls.lock().expect("calls lock");
+    assert_eq!(calls.len(), 1);
+    let (url, body) = &calls[0];
+    assert_eq!(url, "http://gateway.internal/v1/messages/count_tokens");
+    assert_eq!(body["model"], "claude-sonnet-4-5");
+    assert!(body.get("messages").is_some());
+    assert!(body.get("max_tokens").is_none());
+    assert!(body.get("temperature").is_none());
+}
+
+#[tokio::test]
+async fn test_anthropic_adapter_count_tokens_requires_input_tokens() {
+    let transport = Arc::new(CountTokensTransport {
+        body: serde_json::json!({}),
+        calls: Mutex::new(Vec::new()),
+    });
+    let adapter = AnthropicAdapter::with_transport(
+        Some("test-key".to_string()),
+        "http://gateway.internal",
+        AnthropicAdapterOptions::default(),
+        transport,
+    );
+
+    let err = adapter
+        .count_tokens(&base_request(), &AdapterContext::default())
+        .await
+        .expect_err("missing input_tokens should fail");
+
+    assert!(matches!(err, ProviderError::Protocol { .. }));
+}
+
 fn read_http_request(stream: &mut std::net::TcpStream) -> String {
     let mut request = Vec::new();
     let mut chunk = [0_u8; 1024];
@@ -4,39 +4,68 @@ use serde_json::{Map, Value, json};
 
 use crate::core::error::ProviderError;
 use crate::core::types::{
-    AssistantOutput, ContentPart, FinishReason, MessageRole, ModelInfo, ProviderCapabilities,
-    ProviderId, ProviderRequest, ProviderResponse, ResponseFormat, RuntimeWarning, ToolCall,
-    ToolChoice, ToolDefinition, ToolResult, ToolResultContent, Usage,
+    Annotation, AssistantOutput, CacheHint, ContentPart, FinishReason, HostedTool, HostedToolCall,
+    MessageRole, ModelInfo, ProviderCapabilities, ProviderId, ProviderRequest, ProviderResponse,
+    ResponseFormat, RuntimeWarning, ToolCall, ToolChoice, ToolDefinition, ToolResult,
+    ToolResultContent, Usage, WarningCode,
 };
+use crate::providers::json_extract::extract_first_json_object;
+use crate::providers::request_options::merge_provider_options;
 use crate::providers::translator_contract::ProviderTranslator;
 
 /*
 Anthropic Messages coverage policy (Stage 16/17 strict):
 - Mapped fields: model, max_tokens, messages/system, tools, tool_choice, output_config, stop,
-  temperature/top_p, metadata.user_id, content blocks, stop_reason, usage.
-- Warning-drop fields: unsupported metadata keys, unknown response content block types, parse
-  failures for structured output.
+  temperature/top_p, parallel_tool_calls (as tool_choice.disable_parallel_tool_use),
+  metadata.user_id, thinking (adapter option), cache_control (from message and tool cache
+  hints), content blocks, stop_reason, usage (cache creation tokens reported separately from
+  input tokens, server tool web search counts), hosted tools (web search and code execution
+  server tools).
+- Server tools: `server_tool_use` and `*_tool_result` blocks decode to canonical hosted tool calls
+  that keep the raw block; they encode back verbatim in assistant messages.
+- Thinking: response `thinking` blocks decode to canonical thinking parts (with signature);
+  signed assistant thinking parts encode back to `thinking` blocks. `redacted_thinking` blocks
+  are not represented canonically.
+- Citations: `web_search_result_location` citations on text blocks decode to URL citation
+  annotations; other citation types are dropped with a warning.
+- Unknown content: response content blocks of unknown types decode to canonical `Other` parts
+  that keep the raw block, with a warning.
+- Warning-drop fields: seed, unsupported metadata keys, parse failures for structured output,
+  unsigned assistant thinking parts, hosted tools without an Anthropic server tool, hosted tool
+  calls made by other providers, canonical `Other` parts.
 - Hard-error fields/states: provider_hint mismatch, empty/invalid model, invalid max_output_tokens,
-  invalid sampling/stop/tool schemas and tool ordering, non-object tool_use input, non-prefix
-  system messages, malformed payload types.
-- Known out-of-scope under frozen canonical model: tool strictness flag, cache-creation token
-  breakout, rich server-tool response block typing.
+  invalid sampling/stop/tool schemas and tool ordering, n other than 1, non-object tool_use input,
+  non-prefix system messages, malformed payload types.
+- Known out-of-scope under frozen canonical model: tool strictness flag.
 */
 
 const DEFAULT_MAX_TOKENS: u64 = 1024;
-
-const WARN_BOTH_TEMPERATURE_AND_TOP_P_SET: &str = "both_temperature_and_top_p_set";
-const WARN_DROPPED_UNSUPPORTED_METADATA_KEYS: &str = "dropped_unsupported_metadata_keys";
-const WARN_DEFAULT_MAX_TOKENS_APPLIED: &str = "default_max_tokens_applied";
-const WARN_UNKNOWN_CONTENT_BLOCK_MAPPED: &str = "unknown_content_block_mapped_to_text";
-const WARN_UNKNOWN_STOP_REASON: &str = "unknown_stop_reason";
-const WARN_USAGE_MISSING: &str = "usage_missing";
-const WARN_USAGE_PARTIAL: &str = "usage_partial";
-const WARN_STRUCTURED_OUTPUT_PARSE_FAILED: &str = "structured_output_parse_failed";
-const WARN_EMPTY_OUTPUT: &str = "empty_output";
-const WARN_TOOL_RESULT_COERCED: &str = "tool_result_coerced";
-const WARN_TOOL_RESULT_RAW_PROVIDER_CONTENT_IGNORED: &str =
-    "tool_result_raw_provider_content_ignored";
+const MAX_CACHE_BREAKPOINTS: usize = 4;
+
+const WEB_SEARCH_TOOL_TYPE: &str = "web_search_20250305";
+const CODE_EXECUTION_TOOL_TYPE: &str = "code_execution_20250522";
+
+/// Body fields owned by typed `ProviderRequest` fields; `provider_options` may not set them.
+const RESERVED_PROVIDER_OPTION_KEYS: &[&str] = &[
+    "model",
+    "messages",
+    "system",
+    "stream",
+    "tools",
+    "tool_choice",
+    "max_tokens",
+    "stop_sequences",
+    "temperature",
+    "top_p",
+    "metadata",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct AnthropicTranslateOptions {
+    pub thinking_budget_tokens: Option<u32>,
+    /// Replaces `DEFAULT_MAX_TOKENS` for requests without `max_output_tokens`.
+    pub default_max_tokens: Option<u32>,
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct AnthropicEncodedRequest {
@@ -57,15 +86,23 @@ pub(crate) struct AnthropicErrorEnvelope {
     pub request_id: Option<String>,
 }
 
-#[derive(Debug, Default, Clone, Copy)]
-pub(crate) struct AnthropicTranslator;
+#[derive(Debug, Default, Clone)]
+pub(crate) struct AnthropicTranslator {
+    options: AnthropicTranslateOptions,
+}
+
+impl AnthropicTranslator {
+    pub(crate) fn new(options: AnthropicTranslateOptions) -> Self {
+        Self { options }
+    }
+}
 
 impl ProviderTranslator for AnthropicTranslator {
     type RequestPayload = AnthropicEncodedRequest;
     type ResponsePayload = AnthropicDecodeEnvelope;
 
     fn encode_request(&self, req: &ProviderRequest) -> Result<Self::RequestPayload, ProviderError> {
-        encode_anthropic_request(req)
+        encode_anthropic_request(req, &self.options)
     }
 
     fn decode_response(
@@ -78,20 +115,29 @@ impl ProviderTranslator for AnthropicTranslator {
 
 pub(crate) fn encode_anthropic_request(
     req: &ProviderRequest,
+    options: &AnthropicTranslateOptions,
 ) -> Result<AnthropicEncodedRequest, ProviderError> {
     validate_provider_hint(req)?;
     validate_model_id(req)?;
     validate_max_output_tokens(req)?;
     validate_sampling_controls(req)?;
     validate_stop_sequences(req)?;
+    validate_thinking(req, options)?;
+    validate_cache_hints(req)?;
 
     let mut warnings = Vec::new();
     if req.temperature.is_some() && req.top_p.is_some() {
         warnings.push(RuntimeWarning {
-            code: WARN_BOTH_TEMPERATURE_AND_TOP_P_SET.to_string(),
+            code: WarningCode::BothTemperatureAndTopPSet,
             message: "Anthropic recommends setting temperature or top_p, but not both".to_string(),
         });
     }
+    if req.seed.is_some() {
+        warnings.push(RuntimeWarning {
+            code: WarningCode::SeedUnsupported,
+            message: "Anthropic does not support seed; dropped".to_string(),
+        });
+    }
 
     let (system, non_system_messages) = map_system_prefix(req)?;
     let mapped_messages = map_non_system_messages(req, &non_system_messages, &mut warnings)?;
@@ -103,7 +149,7 @@ pub(crate) fn encode_anthropic_request(
     }
 
     let output_config = map_response_format(req, &merged_messages)?;
-    let tools = map_tools(req)?;
+    let tools = map_tools(req, &mut warnings)?;
     let tool_choice = map_tool_choice(req)?;
 
     let mut body = Map::new();
@@ -116,17 +162,30 @@ pub(crate) fn encode_anthropic_request(
         match req.max_output_tokens {
             Some(value) => Value::Number(value.into()),
             None => {
+                // Thinking budget counts against max_tokens, so the default leaves room for
+                // the visible answer on top of the budget.
+                let default_max_tokens = options
+                    .default_max_tokens
+                    .map_or(DEFAULT_MAX_TOKENS, u64::from)
+                    + options.thinking_budget_tokens.map(u64::from).unwrap_or(0);
                 warnings.push(RuntimeWarning {
-                    code: WARN_DEFAULT_MAX_TOKENS_APPLIED.to_string(),
+                    code: WarningCode::DefaultMaxTokensApplied,
                     message: format!(
-                        "max_output_tokens not set; defaulting to {DEFAULT_MAX_TOKENS} for Anthropic"
+                        "max_output_tokens not set; defaulting to {default_max_tokens} for Anthropic"
                     ),
                 });
-                Value::Number(DEFAULT_MAX_TOKENS.into())
+                Value::Number(default_max_tokens.into())
             }
         },
     );
 
+    if let Some(budget_tokens) = options.thinking_budget_tokens {
+        body.insert(
+            "thinking".to_string(),
+            json!({ "type": "enabled", "budget_tokens": budget_tokens }),
+        );
+    }
+
     body.insert(
         "messages".to_string(),
         Value::Array(
@@ -167,6 +226,14 @@ pub(crate) fn encode_anthropic_request(
         body.insert("metadata".to_string(), metadata);
     }
 
+    merge_provider_options(
+        &mut body,
+        req,
+        &ProviderId::Anthropic,
+        RESERVED_PROVIDER_OPTION_KEYS,
+    )
+    .map_err(|message| protocol_error(Some(&req.model.model_id), message))?;
+
     Ok(AnthropicEncodedRequest {
         body: Value::Object(body),
         warnings,
@@ -211,6 +278,7 @@ pub(crate) fn decode_anthropic_response(
     let mut warnings = Vec::new();
     let mut content = Vec::new();
     let mut text_blocks = Vec::new();
+    let mut annotations = Vec::new();
 
     for block in content_blocks {
         let block_obj = block.as_object().ok_or_else(|| {
@@ -230,6 +298,12 @@ pub(crate) fn decode_anthropic_response(
                         protocol_error(Some(&model), "text content block missing text")
                     })?;
                 text_blocks.push(text.to_string());
+                decode_citations(
+                    block_obj.get("citations"),
+                    content.len(),
+                    &mut annotations,
+                    &mut warnings,
+                );
                 content.push(ContentPart::Text {
                     text: text.to_string(),
                 });
@@ -261,16 +335,57 @@ pub(crate) fn decode_anthropic_response(
                     },
                 });
             }
-            "thinking" | "redacted_thinking" => {}
+            "thinking" => {
+                let thinking = block_obj
+                    .get("thinking")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| {
+                        protocol_error(Some(&model), "thinking content block missing thinking")
+                    })?;
+                let signature = block_obj
+                    .get("signature")
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+                content.push(ContentPart::Thinking {
+                    text: thinking.to_string(),
+                    signature,
+                });
+            }
+            "redacted_thinking" => {}
+            "server_tool_use" => {
+                let id = block_obj.get("id").and_then(Value::as_str).ok_or_else(|| {
+                    protocol_error(Some(&model), "server_tool_use block missing id")
+                })?;
+                let name = block_obj
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| {
+                        protocol_error(Some(&model), "server_tool_use block missing name")
+                    })?;
+                content.push(hosted_tool_call(id, name, block_obj));
+            }
+            server_tool_result if server_tool_result.ends_with("_tool_result") => {
+                let tool_use_id = block_obj
+                    .get("tool_use_id")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| {
+                        protocol_error(
+                            Some(&model),
+                            format!("{server_tool_result} block missing tool_use_id"),
+                        )
+                    })?;
+                content.push(hosted_tool_call(tool_use_id, server_tool_result, block_obj));
+            }
             _ => {
                 warnings.push(RuntimeWarning {
-                    code: WARN_UNKNOWN_CONTENT_BLOCK_MAPPED.to_string(),
+                    code: WarningCode::UnknownContentBlockKept,
                     message: format!(
-                        "anthropic content block type '{block_type}' mapped to canonical text via JSON"
+                        "anthropic content block type '{block_type}' kept as unknown content"
                     ),
                 });
-                content.push(ContentPart::Text {
-                    text: stable_json_string(block),
+                content.push(ContentPart::Other {
+                    kind: block_type.to_string(),
+                    raw: block.clone(),
                 });
             }
         }
@@ -278,7 +393,7 @@ pub(crate) fn decode_anthropic_response(
 
     if content.is_empty() {
         warnings.push(RuntimeWarning {
-            code: WARN_EMPTY_OUTPUT.to_string(),
+            code: WarningCode::EmptyOutput,
             message: "anthropic response contained no content blocks".to_string(),
         });
     }
@@ -296,6 +411,7 @@ pub(crate) fn decode_anthropic_response(
         output: AssistantOutput {
             content,
             structured_output,
+            annotations,
         },
         usage,
         cost: None,
@@ -304,6 +420,10 @@ pub(crate) fn decode_anthropic_response(
         raw_provider_response: None,
         finish_reason,
         warnings,
+        alternatives: Vec::new(),
+        response_id: root.get("id").and_then(Value::as_str).map(str::to_string),
+        request_id: None,
+        rate_limit: None,
     })
 }
 
@@ -397,6 +517,11 @@ pub(crate) fn decode_anthropic_models_list(
             max_output_tokens: None,
             supports_tools: capabilities.supports_tools,
             supports_structured_output: capabilities.supports_structured_output,
+            pricing: None,
+            input_modalities: Vec::new(),
+            output_modalities: Vec::new(),
+            deprecated: false,
+            knowledge_cutoff: None,
         });
     }
 
@@ -419,13 +544,13 @@ impl WireMessage {
 }
 
 fn validate_provider_hint(req: &ProviderRequest) -> Result<(), ProviderError> {
-    if let Some(provider_hint) = &req.model.provider_hint {
-        if *provider_hint != ProviderId::Anthropic {
-            return Err(protocol_error(
-                Some(&req.model.model_id),
-                format!("provider_hint must be Anthropic, got {provider_hint:?}"),
-            ));
-        }
+    if let Some(provider_hint) = &req.model.provider_hint
+        && *provider_hint != ProviderId::Anthropic
+    {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            format!("provider_hint must be Anthropic, got {provider_hint:?}"),
+        ));
     }
 
     Ok(())
@@ -451,22 +576,73 @@ fn validate_max_output_tokens(req: &ProviderRequest) -> Result<(), ProviderError
 }
 
 fn validate_sampling_controls(req: &ProviderRequest) -> Result<(), ProviderError> {
-    if let Some(temperature) = req.temperature {
-        if !(0.0..=1.0).contains(&temperature) {
-            return Err(protocol_error(
-                Some(&req.model.model_id),
-                format!("temperature must be in [0.0, 1.0], got {temperature}"),
-            ));
-        }
+    if let Some(temperature) = req.temperature
+        && !(0.0..=1.0).contains(&temperature)
+    {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            format!("temperature must be in [0.0, 1.0], got {temperature}"),
+        ));
     }
 
-    if let Some(top_p) = req.top_p {
-        if !(0.0..=1.0).contains(&top_p) {
-            return Err(protocol_error(
-                Some(&req.model.model_id),
-                format!("top_p must be in [0.0, 1.0], got {top_p}"),
-            ));
-        }
+    if let Some(top_p) = req.top_p
+        && !(0.0..=1.0).contains(&top_p)
+    {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            format!("top_p must be in [0.0, 1.0], got {top_p}"),
+        ));
+    }
+
+    if let Some(n) = req.n
+        && n != 1
+    {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            format!("anthropic does not support multiple completions; n must be 1, got {n}"),
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_thinking(
+    req: &ProviderRequest,
+    options: &AnthropicTranslateOptions,
+) -> Result<(), ProviderError> {
+    let Some(budget_tokens) = options.thinking_budget_tokens else {
+        return Ok(());
+    };
+
+    if let Some(max_output_tokens) = req.max_output_tokens
+        && max_output_tokens <= budget_tokens
+    {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            format!(
+                "max_output_tokens ({max_output_tokens}) must exceed thinking budget_tokens ({budget_tokens})"
+            ),
+        ));
+    }
+
+    if matches!(
+        req.tool_choice,
+        ToolChoice::Required | ToolChoice::Specific { .. }
+    ) {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            "extended thinking only supports auto or none tool_choice",
+        ));
+    }
+
+    if req
+        .temperature
+        .is_some_and(|temperature| temperature != 1.0)
+    {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            "extended thinking requires temperature to be unset or 1.0",
+        ));
     }
 
     Ok(())
@@ -485,17 +661,41 @@ fn validate_stop_sequences(req: &ProviderRequest) -> Result<(), ProviderError> {
     Ok(())
 }
 
+fn validate_cache_hints(req: &ProviderRequest) -> Result<(), ProviderError> {
+    let breakpoints = req
+        .messages
+        .iter()
+        .filter(|message| message.cache_hint.is_some())
+        .count()
+        + req
+            .tools
+            .iter()
+            .filter(|tool| tool.cache_hint.is_some())
+            .count();
+
+    if breakpoints > MAX_CACHE_BREAKPOINTS {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            format!(
+                "anthropic supports at most {MAX_CACHE_BREAKPOINTS} cache breakpoints; request has {breakpoints}"
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
 fn map_system_prefix(
     req: &ProviderRequest,
 ) -> Result<(Option<Vec<Value>>, Vec<&crate::core::types::Message>), ProviderError> {
     let mut index = 0;
-    while index < req.messages.len() && req.messages[index].role == MessageRole::System {
+    while index < req.messages.len() && is_system_like(&req.messages[index].role) {
         index += 1;
     }
 
     if req.messages[index..]
         .iter()
-        .any(|message| message.role == MessageRole::System)
+        .any(|message| is_system_like(&message.role))
     {
         return Err(protocol_error(
             Some(&req.model.model_id),
@@ -505,6 +705,7 @@ fn map_system_prefix(
 
     let mut system_blocks = Vec::new();
     for message in &req.messages[..index] {
+        let first_block = system_blocks.len();
         for part in &message.content {
             match part {
                 ContentPart::Text { text } => system_blocks.push(json!({
@@ -519,6 +720,7 @@ fn map_system_prefix(
                 }
             }
         }
+        apply_cache_hint(&mut system_blocks[first_block..], message.cache_hint);
     }
 
     let rest = req.messages[index..].iter().collect::<Vec<_>>();
@@ -531,6 +733,11 @@ fn map_system_prefix(
     Ok((system, rest))
 }
 
+/// Anthropic has a single `system` field, so developer instructions fold into it.
+fn is_system_like(role: &MessageRole) -> bool {
+    matches!(role, MessageRole::System | MessageRole::Developer)
+}
+
 fn map_non_system_messages(
     req: &ProviderRequest,
     messages: &[&crate::core::types::Message],
@@ -544,7 +751,7 @@ fn map_non_system_messages(
             MessageRole::User => "user",
             MessageRole::Assistant => "assistant",
             MessageRole::Tool => "user",
-            MessageRole::System => unreachable!(),
+            MessageRole::System | MessageRole::Developer => unreachable!(),
         };
 
         let mut blocks = Vec::new();
@@ -608,12 +815,66 @@ fn map_non_system_messages(
                         warnings,
                     )?;
 
-                    blocks.push(json!({
+                    let mut block = json!({
                         "type": "tool_result",
                         "tool_use_id": tool_result.tool_call_id,
                         "content": content,
+                    });
+                    if tool_result.is_error {
+                        block["is_error"] = Value::Bool(true);
+                    }
+                    blocks.push(block);
+                }
+                ContentPart::Thinking { text, signature } => {
+                    if message.role != MessageRole::Assistant {
+                        return Err(protocol_error(
+                            Some(&req.model.model_id),
+                            "thinking content is only valid in assistant messages",
+                        ));
+                    }
+
+                    let Some(signature) = signature else {
+                        warnings.push(RuntimeWarning {
+                            code: WarningCode::ThinkingWithoutSignatureDropped,
+                            message: "thinking part without signature dropped for Anthropic"
+                                .to_string(),
+                        });
+                        continue;
+                    };
+
+                    blocks.push(json!({
+                        "type": "thinking",
+                        "thinking": text,
+                        "signature": signature,
                     }));
                 }
+                ContentPart::HostedToolCall { hosted_tool_call } => {
+                    if message.role != MessageRole::Assistant {
+                        return Err(protocol_error(
+                            Some(&req.model.model_id),
+                            "hosted_tool_call content is only valid in assistant messages",
+                        ));
+                    }
+                    if hosted_tool_call.provider != ProviderId::Anthropic {
+                        warnings.push(RuntimeWarning {
+                            code: WarningCode::HostedToolCallDropped,
+                            message: format!(
+                                "{:?} hosted tool call '{}' cannot be replayed to Anthropic; dropped",
+                                hosted_tool_call.provider, hosted_tool_call.name
+                            ),
+                        });
+                        continue;
+                    }
+                    blocks.push(hosted_tool_call.raw_provider_content.clone());
+                }
+                ContentPart::Other { kind, .. } => {
+                    warnings.push(RuntimeWarning {
+                        code: WarningCode::UnknownContentPartDropped,
+                        message: format!(
+                            "unknown '{kind}' content cannot be replayed to Anthropic; dropped"
+                        ),
+                    });
+                }
             }
         }
 
@@ -623,6 +884,7 @@ fn map_non_system_messages(
                 "message content must contain at least one encodable part",
             ));
         }
+        apply_cache_hint(&mut blocks, message.cache_hint);
 
         mapped.push(WireMessage {
             role,
@@ -633,6 +895,28 @@ fn map_non_system_messages(
     Ok(mapped)
 }
 
+/// Anthropic caches the prompt prefix up to and including the marked block, so the marker goes on
+/// the message's last block. Thinking blocks cannot carry `cache_control` themselves.
+fn apply_cache_hint(blocks: &mut [Value], cache_hint: Option<CacheHint>) {
+    let Some(cache_hint) = cache_hint else {
+        return;
+    };
+
+    let target = blocks
+        .iter_mut()
+        .rev()
+        .find(|block| block.get("type").and_then(Value::as_str) != Some("thinking"));
+    if let Some(Value::Object(block)) = target {
+        block.insert("cache_control".to_string(), cache_control(cache_hint));
+    }
+}
+
+fn cache_control(cache_hint: CacheHint) -> Value {
+    match cache_hint {
+        CacheHint::Ephemeral => json!({ "type": "ephemeral" }),
+    }
+}
+
 fn tool_result_content_as_text_blocks(
     tool_result: &ToolResult,
     model: &str,
@@ -644,7 +928,7 @@ fn tool_result_content_as_text_blocks(
         }
 
         warnings.push(RuntimeWarning {
-            code: WARN_TOOL_RESULT_RAW_PROVIDER_CONTENT_IGNORED.to_string(),
+            code: WarningCode::ToolResultRawProviderContentIgnored,
             message:
                 "tool_result raw_provider_content ignored for Anthropic because it is not an array"
                     .to_string(),
@@ -658,7 +942,7 @@ fn tool_result_content_as_text_blocks(
         })]),
         ToolResultContent::Json { value } => {
             warnings.push(RuntimeWarning {
-                code: WARN_TOOL_RESULT_COERCED.to_string(),
+                code: WarningCode::ToolResultCoerced,
                 message: "tool_result JSON content coerced to Anthropic text block".to_string(),
             });
             Ok(vec![json!({
@@ -831,12 +1115,35 @@ fn validate_tool_ordering(
     Ok(())
 }
 
-fn map_tools(req: &ProviderRequest) -> Result<Vec<Value>, ProviderError> {
+fn map_tools(
+    req: &ProviderRequest,
+    warnings: &mut Vec<RuntimeWarning>,
+) -> Result<Vec<Value>, ProviderError> {
     let mut tools = Vec::new();
 
     for tool in &req.tools {
         tools.push(map_tool_definition(tool, &req.model.model_id)?);
     }
+    for tool in &req.hosted_tools {
+        match tool {
+            HostedTool::WebSearch { allowed_domains } => {
+                let mut mapped = json!({ "type": WEB_SEARCH_TOOL_TYPE, "name": "web_search" });
+                if !allowed_domains.is_empty() {
+                    mapped["allowed_domains"] = json!(allowed_domains);
+                }
+                tools.push(mapped);
+            }
+            // Code execution is in beta; callers enable it with the matching beta feature.
+            HostedTool::CodeInterpreter => tools.push(json!({
+                "type": CODE_EXECUTION_TOOL_TYPE,
+                "name": "code_execution",
+            })),
+            other => warnings.push(RuntimeWarning {
+                code: WarningCode::HostedToolUnsupported,
+                message: format!("Anthropic has no server tool for {other:?}; dropped"),
+            }),
+        }
+    }
 
     Ok(tools)
 }
@@ -875,6 +1182,9 @@ fn map_tool_definition(tool: &ToolDefinition, model: &str) -> Result<Value, Prov
         );
     }
     mapped.insert("input_schema".to_string(), tool.parameters_schema.clone());
+    if let Some(cache_hint) = tool.cache_hint {
+        mapped.insert("cache_control".to_string(), cache_control(cache_hint));
+    }
 
     Ok(Value::Object(mapped))
 }
@@ -892,8 +1202,8 @@ fn map_tool_choice(req: &ProviderRequest) -> Result<Value, ProviderError> {
         }
     }
 
-    let mapped = match &req.tool_choice {
-        ToolChoice::None => json!({ "type": "none" }),
+    let mut mapped = match &req.tool_choice {
+        ToolChoice::None => return Ok(json!({ "type": "none" })),
         ToolChoice::Auto => json!({ "type": "auto" }),
         ToolChoice::Required => json!({ "type": "any" }),
         ToolChoice::Specific { name } => {
@@ -914,6 +1224,9 @@ fn map_tool_choice(req: &ProviderRequest) -> Result<Value, ProviderError> {
             json!({ "type": "tool", "name": name, "disable_parallel_tool_use": true })
         }
     };
+    if let Some(parallel_tool_calls) = req.parallel_tool_calls {
+        mapped["disable_parallel_tool_use"] = json!(!parallel_tool_calls);
+    }
 
     Ok(mapped)
 }
@@ -980,7 +1293,7 @@ fn map_metadata(
 
     if req.metadata.keys().any(|key| key != "user_id") {
         warnings.push(RuntimeWarning {
-            code: WARN_DROPPED_UNSUPPORTED_METADATA_KEYS.to_string(),
+            code: WarningCode::DroppedUnsupportedMetadataKeys,
             message: "anthropic metadata only supports user_id; unsupported keys dropped"
                 .to_string(),
         });
@@ -993,6 +1306,63 @@ fn map_metadata(
     }
 }
 
+/// Decodes the `citations` of the text block that becomes `content[part_index]`. Web search
+/// citations cite the whole block, so they carry no character range.
+fn decode_citations(
+    value: Option<&Value>,
+    part_index: usize,
+    annotations: &mut Vec<Annotation>,
+    warnings: &mut Vec<RuntimeWarning>,
+) {
+    let Some(citations) = value.and_then(Value::as_array) else {
+        return;
+    };
+
+    for citation in citations {
+        let citation_type = citation.get("type").and_then(Value::as_str);
+        let url = citation.get("url").and_then(Value::as_str);
+        match (citation_type, url) {
+            (Some("web_search_result_location"), Some(url)) => {
+                annotations.push(Annotation::UrlCitation {
+                    url: url.to_string(),
+                    title: citation
+                        .get("title")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    content: citation
+                        .get("cited_text")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    start_index: None,
+                    end_index: None,
+                    part_index: Some(part_index),
+                });
+            }
+            _ => warnings.push(RuntimeWarning {
+                code: WarningCode::UnknownAnnotationDropped,
+                message: format!(
+                    "anthropic citation type {} dropped",
+                    citation_type.unwrap_or("<missing>")
+                ),
+            }),
+        }
+    }
+}
+
+/// Server tool blocks are kept whole so they can be sent back unchanged; the result block shares
+/// the id of the `server_tool_use` block it answers.
+fn hosted_tool_call(id: &str, name: &str, block_obj: &Map<String, Value>) -> ContentPart {
+    ContentPart::HostedToolCall {
+        hosted_tool_call: HostedToolCall {
+            id: id.to_string(),
+            name: name.to_string(),
+            status: None,
+            provider: ProviderId::Anthropic,
+            raw_provider_content: Value::Object(block_obj.clone()),
+        },
+    }
+}
+
 fn map_finish_reason(
     stop_reason: &str,
     model: &str,
@@ -1006,7 +1376,7 @@ fn map_finish_reason(
         "pause_turn" => FinishReason::Other,
         other => {
             warnings.push(RuntimeWarning {
-                code: WARN_UNKNOWN_STOP_REASON.to_string(),
+                code: WarningCode::UnknownStopReason,
                 message: format!("unknown anthropic stop_reason '{other}' mapped to Other"),
             });
             FinishReason::Other
@@ -1030,7 +1400,7 @@ fn decode_usage(
 ) -> Result<Usage, ProviderError> {
     let Some(usage_value) = usage_value else {
         warnings.push(RuntimeWarning {
-            code: WARN_USAGE_MISSING.to_string(),
+            code: WarningCode::UsageMissing,
             message: "anthropic response missing usage object".to_string(),
         });
         return Ok(Usage::default());
@@ -1052,27 +1422,41 @@ fn decode_usage(
         "cache_read_input_tokens",
     )?;
     let output_tokens = parse_usage_u64(usage_obj.get("output_tokens"), model, "output_tokens")?;
+    let web_search_requests = match usage_obj.get("server_tool_use") {
+        Some(server_tool_use) => parse_usage_u64(
+            server_tool_use.get("web_search_requests"),
+            model,
+            "server_tool_use.web_search_requests",
+        )?,
+        None => None,
+    };
 
     if input_tokens.is_none() || output_tokens.is_none() {
         warnings.push(RuntimeWarning {
-            code: WARN_USAGE_PARTIAL.to_string(),
+            code: WarningCode::UsagePartial,
             message: "anthropic usage object missing required token fields".to_string(),
         });
     }
 
-    let billed_input = input_tokens.map(|base| {
-        base + cache_creation_input_tokens.unwrap_or(0) + cache_read_input_tokens.unwrap_or(0)
-    });
-    let total_tokens = match (billed_input, output_tokens) {
-        (Some(input), Some(output)) => Some(input + output),
+    // Cache reads are folded into input like other providers' cached tokens; cache writes are
+    // billed at a different rate and reported on their own.
+    let input_with_cache_reads =
+        input_tokens.map(|base| base + cache_read_input_tokens.unwrap_or(0));
+    let total_tokens = match (input_with_cache_reads, output_tokens) {
+        (Some(input), Some(output)) => {
+            Some(input + cache_creation_input_tokens.unwrap_or(0) + output)
+        }
         _ => None,
     };
 
     Ok(Usage {
-        input_tokens: billed_input,
+        input_tokens: input_with_cache_reads,
         output_tokens,
         cached_input_tokens: cache_read_input_tokens,
         total_tokens,
+        reasoning_tokens: None,
+        cache_creation_input_tokens,
+        web_search_requests,
     })
 }
 
@@ -1115,7 +1499,7 @@ fn decode_structured_output(
         ResponseFormat::JsonObject => {
             if text_blocks.is_empty() {
                 warnings.push(RuntimeWarning {
-                    code: WARN_STRUCTURED_OUTPUT_PARSE_FAILED.to_string(),
+                    code: WarningCode::StructuredOutputParseFailed,
                     message: "json_object requested but response contained no text blocks"
                         .to_string(),
                 });
@@ -1132,14 +1516,14 @@ fn decode_structured_output(
 
             let combined = text_blocks.join("\n");
             if let Some(object_text) = extract_first_json_object(&combined)
-                && let Some(parsed) = parse_json_with_warning(&object_text, model, warnings)
+                && let Some(parsed) = parse_json_with_warning(object_text, model, warnings)
                 && parsed.is_object()
             {
                 return Some(parsed);
             }
 
             warnings.push(RuntimeWarning {
-                code: WARN_STRUCTURED_OUTPUT_PARSE_FAILED.to_string(),
+                code: WarningCode::StructuredOutputParseFailed,
                 message: "failed to parse json_object structured output from anthropic text blocks"
                     .to_string(),
             });
@@ -1157,7 +1541,7 @@ fn parse_json_with_warning(
         Ok(value) => Some(value),
         Err(error) => {
             warnings.push(RuntimeWarning {
-                code: WARN_STRUCTURED_OUTPUT_PARSE_FAILED.to_string(),
+                code: WarningCode::StructuredOutputParseFailed,
                 message: format!(
                     "failed to parse structured output JSON for model {model}: {error}"
                 ),
@@ -1167,57 +1551,6 @@ fn parse_json_with_warning(
     }
 }
 
-fn extract_first_json_object(text: &str) -> Option<String> {
-    let bytes = text.as_bytes();
-    let mut start = None;
-    let mut depth = 0_u64;
-    let mut in_string = false;
-    let mut escaped = false;
-
-    for (index, byte) in bytes.iter().enumerate() {
-        let ch = *byte as char;
-
-        if in_string {
-            if escaped {
-                escaped = false;
-                continue;
-            }
-            if ch == '\\' {
-                escaped = true;
-                continue;
-            }
-            if ch == '"' {
-                in_string = false;
-            }
-            continue;
-        }
-
-        if ch == '"' {
-            in_string = true;
-            continue;
-        }
-
-        if ch == '{' {
-            if start.is_none() {
-                start = Some(index);
-            }
-            depth += 1;
-            continue;
-        }
-
-        if ch == '}' && depth > 0 {
-            depth -= 1;
-            if depth == 0 {
-                if let Some(start_index) = start {
-                    return Some(text[start_index..=index].to_string());
-                }
-            }
-        }
-    }
-
-    None
-}
-
 fn stable_json_string(value: &Value) -> String {
     serde_json::to_string(value).unwrap_or_else(|_| "{}".to_string())
 }
@@ -3,14 +3,15 @@ use std::collections::BTreeMap;
 use serde_json::json;
 
 use super::{
-    AnthropicDecodeEnvelope, decode_anthropic_models_list, decode_anthropic_response,
-    encode_anthropic_request, format_anthropic_error_message, parse_anthropic_error_envelope,
+    AnthropicDecodeEnvelope, AnthropicTranslateOptions, decode_anthropic_models_list,
+    decode_anthropic_response, encode_anthropic_request, format_anthropic_error_message,
+    parse_anthropic_error_envelope,
 };
 use crate::core::error::ProviderError;
 use crate::core::types::{
-    ContentPart, FinishReason, Message, MessageRole, ModelRef, ProviderCapabilities, ProviderId,
-    ProviderRequest, ResponseFormat, ToolCall, ToolChoice, ToolDefinition, ToolResult,
-    ToolResultContent,
+    Annotation, CacheHint, ContentPart, FinishReason, HostedTool, Message, MessageRole, ModelRef,
+    ProviderCapabilities, ProviderId, ProviderRequest, ResponseFormat, ToolCall, ToolChoice,
+    ToolDefinition, ToolResult, ToolResultContent,
 };
 
 fn base_request() -> ProviderRequest {
@@ -18,21 +19,28 @@ fn base_request() -> ProviderRequest {
         model: ModelRef {
             provider_hint: Some(ProviderId::Anthropic),
             model_id: "claude-sonnet-4-5".to_string(),
+            requirements: None,
         },
         messages: vec![Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "hello".to_string(),
             }],
+            cache_hint: None,
         }],
         tools: Vec::new(),
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::Text,
         temperature: None,
         top_p: None,
         max_output_tokens: None,
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     }
 }
 
@@ -45,12 +53,14 @@ fn test_encode_anthropic_translator_category_contract() {
             content: vec![ContentPart::Text {
                 text: "You are a concise assistant.".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "Return weather as JSON".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Assistant,
@@ -61,6 +71,7 @@ fn test_encode_anthropic_translator_category_contract() {
                     arguments_json: json!({ "city": "SF" }),
                 },
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Tool,
@@ -71,14 +82,17 @@ fn test_encode_anthropic_translator_category_contract() {
                         text: "{\"temp\":55}".to_string(),
                     },
                     raw_provider_content: None,
+                    is_error: false,
                 },
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "Now summarize".to_string(),
             }],
+            cache_hint: None,
         },
     ];
     req.tools = vec![ToolDefinition {
@@ -89,6 +103,7 @@ fn test_encode_anthropic_translator_category_contract() {
             "properties": {"city": {"type":"string"}},
             "required": ["city"]
         }),
+        cache_hint: None,
     }];
     req.tool_choice = ToolChoice::Specific {
         name: "lookup_weather".to_string(),
@@ -109,7 +124,8 @@ fn test_encode_anthropic_translator_category_contract() {
         .insert("trace_id".to_string(), "trace-1".to_string());
     req.stop.push("DONE".to_string());
 
-    let encoded = encode_anthropic_request(&req).expect("encode should succeed");
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
 
     assert_eq!(
         encoded.body.pointer("/model"),
@@ -180,7 +196,8 @@ fn test_encode_anthropic_translator_category_contract() {
 
 #[test]
 fn test_encode_minimal_text_request() {
-    let encoded = encode_anthropic_request(&base_request()).expect("encode should succeed");
+    let encoded = encode_anthropic_request(&base_request(), &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
 
     assert_eq!(
         encoded.body.pointer("/model"),
@@ -208,24 +225,28 @@ fn test_encode_tool_choice_mode_matrix() {
         name: "lookup".to_string(),
         description: Some("Lookup".to_string()),
         parameters_schema: json!({"type":"object"}),
+        cache_hint: None,
     }];
 
     req.tool_choice = ToolChoice::None;
-    let none_choice = encode_anthropic_request(&req).expect("none should encode");
+    let none_choice = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("none should encode");
     assert_eq!(
         none_choice.body.pointer("/tool_choice/type"),
         Some(&json!("none"))
     );
 
     req.tool_choice = ToolChoice::Auto;
-    let auto_choice = encode_anthropic_request(&req).expect("auto should encode");
+    let auto_choice = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("auto should encode");
     assert_eq!(
         auto_choice.body.pointer("/tool_choice/type"),
         Some(&json!("auto"))
     );
 
     req.tool_choice = ToolChoice::Required;
-    let required_choice = encode_anthropic_request(&req).expect("required should encode");
+    let required_choice = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("required should encode");
     assert_eq!(
         required_choice.body.pointer("/tool_choice/type"),
         Some(&json!("any"))
@@ -234,7 +255,8 @@ fn test_encode_tool_choice_mode_matrix() {
     req.tool_choice = ToolChoice::Specific {
         name: "lookup".to_string(),
     };
-    let specific_choice = encode_anthropic_request(&req).expect("specific should encode");
+    let specific_choice = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("specific should encode");
     assert_eq!(
         specific_choice.body.pointer("/tool_choice/type"),
         Some(&json!("tool"))
@@ -247,13 +269,54 @@ fn test_encode_tool_choice_mode_matrix() {
     );
 }
 
+#[test]
+fn test_encode_parallel_tool_calls_maps_to_disable_parallel_tool_use() {
+    let mut req = base_request();
+    req.tools = vec![ToolDefinition {
+        name: "lookup".to_string(),
+        description: None,
+        parameters_schema: json!({"type":"object"}),
+        cache_hint: None,
+    }];
+    req.parallel_tool_calls = Some(false);
+
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(
+        encoded.body.get("tool_choice"),
+        Some(&json!({ "type": "auto", "disable_parallel_tool_use": true }))
+    );
+
+    req.tool_choice = ToolChoice::Specific {
+        name: "lookup".to_string(),
+    };
+    req.parallel_tool_calls = Some(true);
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(
+        encoded
+            .body
+            .pointer("/tool_choice/disable_parallel_tool_use"),
+        Some(&json!(false))
+    );
+
+    req.tool_choice = ToolChoice::None;
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(
+        encoded.body.get("tool_choice"),
+        Some(&json!({ "type": "none" }))
+    );
+}
+
 #[test]
 fn test_encode_tool_choice_requires_tools_for_required_and_specific() {
     let mut req = base_request();
     req.tools = Vec::new();
 
     req.tool_choice = ToolChoice::Required;
-    let required_err = encode_anthropic_request(&req).expect_err("required should fail");
+    let required_err = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect_err("required should fail");
     assert!(
         required_err
             .to_string()
@@ -263,7 +326,8 @@ fn test_encode_tool_choice_requires_tools_for_required_and_specific() {
     req.tool_choice = ToolChoice::Specific {
         name: "lookup".to_string(),
     };
-    let specific_err = encode_anthropic_request(&req).expect_err("specific should fail");
+    let specific_err = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect_err("specific should fail");
     assert!(
         specific_err
             .to_string()
@@ -275,7 +339,8 @@ fn test_encode_tool_choice_requires_tools_for_required_and_specific() {
 fn test_encode_response_format_matrix() {
     let mut req = base_request();
     req.response_format = ResponseFormat::JsonObject;
-    let json_object = encode_anthropic_request(&req).expect("json object encode");
+    let json_object = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("json object encode");
     assert_eq!(
         json_object.body.pointer("/output_config/format/type"),
         Some(&json!("json_schema"))
@@ -291,7 +356,8 @@ fn test_encode_response_format_matrix() {
         name: "shape".to_string(),
         schema: json!({"type":"object","properties":{"value":{"type":"number"}}}),
     };
-    let json_schema = encode_anthropic_request(&req).expect("json schema encode");
+    let json_schema = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("json schema encode");
     assert_eq!(
         json_schema.body.pointer("/output_config/format/type"),
         Some(&json!("json_schema"))
@@ -313,17 +379,20 @@ fn test_encode_json_prefill_is_rejected() {
             content: vec![ContentPart::Text {
                 text: "output json".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Assistant,
             content: vec![ContentPart::Text {
                 text: "{".to_string(),
             }],
+            cache_hint: None,
         },
     ];
     req.response_format = ResponseFormat::JsonObject;
 
-    let err = encode_anthropic_request(&req).expect_err("prefill should fail");
+    let err = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect_err("prefill should fail");
     assert!(matches!(err, ProviderError::Protocol { .. }));
     assert!(
         err.to_string()
@@ -331,6 +400,42 @@ fn test_encode_json_prefill_is_rejected() {
     );
 }
 
+#[test]
+fn test_encode_developer_messages_fold_into_system() {
+    let mut req = base_request();
+    req.messages.insert(
+        0,
+        Message {
+            role: MessageRole::Developer,
+            content: vec![ContentPart::Text {
+                text: "be terse".to_string(),
+            }],
+            cache_hint: None,
+        },
+    );
+    req.messages.insert(
+        0,
+        Message {
+            role: MessageRole::System,
+            content: vec![ContentPart::Text {
+                text: "policy".to_string(),
+            }],
+            cache_hint: None,
+        },
+    );
+
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(
+        encoded.body.get("system"),
+        Some(&json!([
+            {"type": "text", "text": "policy"},
+            {"type": "text", "text": "be terse"}
+        ]))
+    );
+    assert_eq!(encoded.body["messages"].as_array().map(Vec::len), Some(1));
+}
+
 #[test]
 fn test_encode_non_prefix_system_is_rejected() {
     let mut req = base_request();
@@ -340,16 +445,19 @@ fn test_encode_non_prefix_system_is_rejected() {
             content: vec![ContentPart::Text {
                 text: "hello".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::System,
             content: vec![ContentPart::Text {
                 text: "late system".to_string(),
             }],
+            cache_hint: None,
         },
     ];
 
-    let err = encode_anthropic_request(&req).expect_err("late system should fail");
+    let err = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect_err("late system should fail");
     assert!(matches!(err, ProviderError::Protocol { .. }));
 }
 
@@ -358,7 +466,8 @@ fn test_encode_max_output_tokens_zero_is_rejected() {
     let mut req = base_request();
     req.max_output_tokens = Some(0);
 
-    let err = encode_anthropic_request(&req).expect_err("max_output_tokens=0 should fail");
+    let err = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect_err("max_output_tokens=0 should fail");
     assert!(matches!(err, ProviderError::Protocol { .. }));
     assert!(
         err.to_string()
@@ -366,6 +475,63 @@ fn test_encode_max_output_tokens_zero_is_rejected() {
     );
 }
 
+#[test]
+fn test_encode_seed_is_dropped_with_warning() {
+    let mut req = base_request();
+    req.seed = Some(7);
+
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(encoded.body.get("seed"), None);
+    assert!(
+        encoded
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "seed_unsupported")
+    );
+}
+
+#[test]
+fn test_encode_request_provider_options_override_thinking() {
+    let mut req = base_request();
+    req.max_output_tokens = Some(4096);
+    req.provider_options.insert(
+        ProviderId::Anthropic,
+        json!({"thinking": {"budget_tokens": 2048}, "top_k": 5}),
+    );
+
+    let encoded = encode_anthropic_request(
+        &req,
+        &AnthropicTranslateOptions {
+            thinking_budget_tokens: Some(1024),
+            default_max_tokens: None,
+        },
+    )
+    .expect("encode should succeed");
+    assert_eq!(
+        encoded.body.get("thinking"),
+        Some(&json!({"type": "enabled", "budget_tokens": 2048}))
+    );
+    assert_eq!(encoded.body.get("top_k"), Some(&json!(5)));
+
+    req.provider_options
+        .insert(ProviderId::Anthropic, json!({"max_tokens": 10}));
+    let err = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect_err("reserved key should fail");
+    assert!(err.to_string().contains("'max_tokens'"));
+}
+
+#[test]
+fn test_encode_multiple_completions_is_unsupported() {
+    let mut req = base_request();
+    req.n = Some(2);
+
+    let err = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect_err("n>1 should fail");
+    assert!(matches!(err, ProviderError::Protocol { .. }));
+    assert!(err.to_string().contains("n must be 1"));
+}
+
 #[test]
 fn test_encode_tool_choice_specific_requires_declared_tool() {
     let mut req = base_request();
@@ -373,12 +539,14 @@ fn test_encode_tool_choice_specific_requires_declared_tool() {
         name: "lookup_weather".to_string(),
         description: None,
         parameters_schema: json!({"type":"object"}),
+        cache_hint: None,
     }];
     req.tool_choice = ToolChoice::Specific {
         name: "missing_tool".to_string(),
     };
 
-    let err = encode_anthropic_request(&req).expect_err("specific tool should be validated");
+    let err = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect_err("specific tool should be validated");
     assert!(matches!(err, ProviderError::Protocol { .. }));
     assert!(
         err.to_string()
@@ -395,6 +563,7 @@ fn test_encode_tool_call_arguments_must_be_object() {
             content: vec![ContentPart::Text {
                 text: "call tool".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Assistant,
@@ -405,6 +574,7 @@ fn test_encode_tool_call_arguments_must_be_object() {
                     arguments_json: json!(["not-object"]),
                 },
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Tool,
@@ -415,12 +585,15 @@ fn test_encode_tool_call_arguments_must_be_object() {
                         text: "{\"temp\":55}".to_string(),
                     },
                     raw_provider_content: None,
+                    is_error: false,
                 },
             }],
+            cache_hint: None,
         },
     ];
 
-    let err = encode_anthropic_request(&req).expect_err("non-object tool args should fail");
+    let err = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect_err("non-object tool args should fail");
     assert!(matches!(err, ProviderError::Protocol { .. }));
     assert!(
         err.to_string()
@@ -437,6 +610,7 @@ fn test_encode_tool_result_without_matching_tool_call_is_rejected() {
             content: vec![ContentPart::Text {
                 text: "hello".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Tool,
@@ -447,12 +621,15 @@ fn test_encode_tool_result_without_matching_tool_call_is_rejected() {
                         text: "tool output".to_string(),
                     },
                     raw_provider_content: None,
+                    is_error: false,
                 },
             }],
+            cache_hint: None,
         },
     ];
 
-    let err = encode_anthropic_request(&req).expect_err("missing tool call should fail");
+    let err = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect_err("missing tool call should fail");
     assert!(matches!(err, ProviderError::Protocol { .. }));
     assert!(
         err.to_string()
@@ -469,6 +646,7 @@ fn test_encode_assistant_tool_use_requires_following_tool_result() {
             content: vec![ContentPart::Text {
                 text: "call the tool".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Assistant,
@@ -479,16 +657,19 @@ fn test_encode_assistant_tool_use_requires_following_tool_result() {
                     arguments_json: json!({"city":"SF"}),
                 },
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "where are we now?".to_string(),
             }],
+            cache_hint: None,
         },
     ];
 
-    let err = encode_anthropic_request(&req).expect_err("missing tool_result should fail");
+    let err = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect_err("missing tool_result should fail");
     assert!(matches!(err, ProviderError::Protocol { .. }));
     assert!(
         err.to_string()
@@ -505,6 +686,7 @@ fn test_encode_tool_result_content_non_text_is_rejected() {
             content: vec![ContentPart::Text {
                 text: "call the tool".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Assistant,
@@ -515,6 +697,7 @@ fn test_encode_tool_result_content_non_text_is_rejected() {
                     arguments_json: json!({"city":"SF"}),
                 },
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Tool,
@@ -531,12 +714,15 @@ fn test_encode_tool_result_content_non_text_is_rejected() {
                         }],
                     },
                     raw_provider_content: None,
+                    is_error: false,
                 },
             }],
+            cache_hint: None,
         },
     ];
 
-    let err = encode_anthropic_request(&req).expect_err("non-text tool_result should fail");
+    let err = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect_err("non-text tool_result should fail");
     assert!(matches!(err, ProviderError::Protocol { .. }));
     assert!(
         err.to_string()
@@ -553,6 +739,7 @@ fn test_encode_tool_result_json_coerces_to_text_block() {
             content: vec![ContentPart::Text {
                 text: "call the tool".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Assistant,
@@ -563,6 +750,7 @@ fn test_encode_tool_result_json_coerces_to_text_block() {
                     arguments_json: json!({"city":"SF"}),
                 },
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Tool,
@@ -573,12 +761,15 @@ fn test_encode_tool_result_json_coerces_to_text_block() {
                         value: json!({"b": 2, "a": 1}),
                     },
                     raw_provider_content: None,
+                    is_error: false,
                 },
             }],
+            cache_hint: None,
         },
     ];
 
-    let encoded = encode_anthropic_request(&req).expect("encode should succeed");
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
     assert_eq!(
         encoded.body.pointer("/messages/2/content/0/content/0/text"),
         Some(&json!("{\"a\":1,\"b\":2}"))
@@ -600,6 +791,7 @@ fn test_encode_tool_result_uses_raw_provider_content_when_array() {
             content: vec![ContentPart::Text {
                 text: "call the tool".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Assistant,
@@ -610,6 +802,7 @@ fn test_encode_tool_result_uses_raw_provider_content_when_array() {
                     arguments_json: json!({"city":"SF"}),
                 },
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Tool,
@@ -623,12 +816,15 @@ fn test_encode_tool_result_uses_raw_provider_content_when_array() {
                         {"type":"text","text":"from-raw"},
                         {"type":"text","text":"second"}
                     ])),
+                    is_error: false,
                 },
             }],
+            cache_hint: None,
         },
     ];
 
-    let encoded = encode_anthropic_request(&req).expect("encode should succeed");
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
     assert_eq!(
         encoded.body.pointer("/messages/2/content/0/content/0/text"),
         Some(&json!("from-raw"))
@@ -639,6 +835,307 @@ fn test_encode_tool_result_uses_raw_provider_content_when_array() {
     );
 }
 
+#[test]
+fn test_encode_assistant_thinking_round_trips_with_signature() {
+    let mut req = base_request();
+    req.messages = vec![
+        Message {
+            role: MessageRole::User,
+            content: vec![ContentPart::Text {
+                text: "call the tool".to_string(),
+            }],
+            cache_hint: None,
+        },
+        Message {
+            role: MessageRole::Assistant,
+            content: vec![
+                ContentPart::Thinking {
+                    text: "need weather".to_string(),
+                    signature: Some("sig_1".to_string()),
+                },
+                ContentPart::Thinking {
+                    text: "unsigned".to_string(),
+                    signature: None,
+                },
+                ContentPart::ToolCall {
+                    tool_call: ToolCall {
+                        id: "tool_1".to_string(),
+                        name: "lookup_weather".to_string(),
+                        arguments_json: json!({"city":"SF"}),
+                    },
+                },
+            ],
+            cache_hint: None,
+        },
+        Message {
+            role: MessageRole::Tool,
+            content: vec![ContentPart::ToolResult {
+                tool_result: ToolResult {
+                    tool_call_id: "tool_1".to_string(),
+                    content: ToolResultContent::Text {
+                        text: "55F".to_string(),
+                    },
+                    raw_provider_content: None,
+                    is_error: false,
+                },
+            }],
+            cache_hint: None,
+        },
+    ];
+
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(
+        encoded.body.pointer("/messages/1/content"),
+        Some(&json!([
+            {"type": "thinking", "thinking": "need weather", "signature": "sig_1"},
+            {"type": "tool_use", "id": "tool_1", "name": "lookup_weather", "input": {"city": "SF"}}
+        ]))
+    );
+    assert!(
+        encoded
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "thinking_without_signature_dropped")
+    );
+}
+
+#[test]
+fn test_unknown_content_block_round_trips_as_other_and_is_dropped_on_encode() {
+    let payload = AnthropicDecodeEnvelope {
+        body: json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "model": "claude-sonnet-4-5",
+            "stop_reason": "end_turn",
+            "content": [
+                {"type": "text", "text": "uploaded"},
+                {"type": "container_upload", "file_id": "file_1"}
+            ],
+            "usage": { "input_tokens": 1, "output_tokens": 1 }
+        }),
+        requested_response_format: ResponseFormat::Text,
+    };
+    let decoded = decode_anthropic_response(&payload).expect("decode should succeed");
+
+    let mut req = base_request();
+    req.messages.push(Message {
+        role: MessageRole::Assistant,
+        content: decoded.output.content,
+        cache_hint: None,
+    });
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(
+        encoded.body.pointer("/messages/1/content"),
+        Some(&json!([{"type": "text", "text": "uploaded"}]))
+    );
+    assert!(
+        encoded
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "unknown_content_part_dropped")
+    );
+}
+
+#[test]
+fn test_encode_thinking_budget_option() {
+    let options = AnthropicTranslateOptions {
+        thinking_budget_tokens: Some(2048),
+        default_max_tokens: None,
+    };
+
+    let encoded =
+        encode_anthropic_request(&base_request(), &options).expect("encode should succeed");
+    assert_eq!(
+        encoded.body.get("thinking"),
+        Some(&json!({"type": "enabled", "budget_tokens": 2048}))
+    );
+    assert_eq!(encoded.body.get("max_tokens"), Some(&json!(3072)));
+
+    let mut req = base_request();
+    req.max_output_tokens = Some(4096);
+    let encoded = encode_anthropic_request(&req, &options).expect("encode should succeed");
+    assert_eq!(encoded.body.get("max_tokens"), Some(&json!(4096)));
+
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(encoded.body.get("thinking"), None);
+}
+
+#[test]
+fn test_encode_configured_default_max_tokens() {
+    let options = AnthropicTranslateOptions {
+        thinking_budget_tokens: None,
+        default_max_tokens: Some(8192),
+    };
+
+    let encoded =
+        encode_anthropic_request(&base_request(), &options).expect("encode should succeed");
+    assert_eq!(encoded.body.get("max_tokens"), Some(&json!(8192)));
+    assert!(
+        encoded
+            .warnings
+            .iter()
+            .any(|warning| warning.message.contains("defaulting to 8192"))
+    );
+}
+
+#[test]
+fn test_encode_thinking_budget_constraints() {
+    let options = AnthropicTranslateOptions {
+        thinking_budget_tokens: Some(2048),
+        default_max_tokens: None,
+    };
+
+    let mut req = base_request();
+    req.max_output_tokens = Some(2048);
+    let error = encode_anthropic_request(&req, &options).expect_err("budget must fit");
+    assert!(matches!(error, ProviderError::Protocol { .. }));
+
+    let mut req = base_request();
+    req.tools = vec![ToolDefinition {
+        name: "lookup_weather".to_string(),
+        description: None,
+        parameters_schema: json!({"type":"object"}),
+        cache_hint: None,
+    }];
+    req.tool_choice = ToolChoice::Required;
+    let error = encode_anthropic_request(&req, &options).expect_err("forced tool use rejected");
+    assert!(matches!(error, ProviderError::Protocol { .. }));
+
+    let mut req = base_request();
+    req.temperature = Some(0.5);
+    let error = encode_anthropic_request(&req, &options).expect_err("temperature rejected");
+    assert!(matches!(error, ProviderError::Protocol { .. }));
+}
+
+#[test]
+fn test_encode_thinking_outside_assistant_is_rejected() {
+    let mut req = base_request();
+    req.messages = vec![Message {
+        role: MessageRole::User,
+        content: vec![ContentPart::Thinking {
+            text: "not mine".to_string(),
+            signature: Some("sig".to_string()),
+        }],
+        cache_hint: None,
+    }];
+
+    let error = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect_err("encode should fail");
+    assert!(matches!(error, ProviderError::Protocol { .. }));
+}
+
+#[test]
+fn test_encode_cache_hints_mark_last_block_and_tools() {
+    let mut req = base_request();
+    req.messages = vec![
+        Message {
+            role: MessageRole::System,
+            content: vec![
+                ContentPart::Text {
+                    text: "policy".to_string(),
+                },
+                ContentPart::Text {
+                    text: "long reference document".to_string(),
+                },
+            ],
+            cache_hint: Some(CacheHint::Ephemeral),
+        },
+        Message {
+            role: MessageRole::User,
+            content: vec![ContentPart::Text {
+                text: "hello".to_string(),
+            }],
+            cache_hint: None,
+        },
+    ];
+    req.tools = vec![ToolDefinition {
+        name: "lookup".to_string(),
+        description: None,
+        parameters_schema: json!({"type": "object"}),
+        cache_hint: Some(CacheHint::Ephemeral),
+    }];
+
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
+
+    assert_eq!(
+        encoded.body.get("system"),
+        Some(&json!([
+            {"type": "text", "text": "policy"},
+            {
+                "type": "text",
+                "text": "long reference document",
+                "cache_control": {"type": "ephemeral"}
+            }
+        ]))
+    );
+    assert_eq!(
+        encoded.body.get("messages"),
+        Some(&json!([
+            {"role": "user", "content": [{"type": "text", "text": "hello"}]}
+        ]))
+    );
+    assert_eq!(
+        encoded.body["tools"][0].get("cache_control"),
+        Some(&json!({"type": "ephemeral"}))
+    );
+}
+
+#[test]
+fn test_encode_cache_hint_skips_trailing_thinking_block() {
+    let mut req = base_request();
+    req.messages.push(Message {
+        role: MessageRole::Assistant,
+        content: vec![
+            ContentPart::Text {
+                text: "answer".to_string(),
+            },
+            ContentPart::Thinking {
+                text: "reasoning".to_string(),
+                signature: Some("sig".to_string()),
+            },
+        ],
+        cache_hint: Some(CacheHint::Ephemeral),
+    });
+
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
+
+    assert_eq!(
+        encoded.body["messages"][1]["content"],
+        json!([
+            {"type": "text", "text": "answer", "cache_control": {"type": "ephemeral"}},
+            {"type": "thinking", "thinking": "reasoning", "signature": "sig"}
+        ])
+    );
+}
+
+#[test]
+fn test_encode_too_many_cache_breakpoints_is_rejected() {
+    let mut req = base_request();
+    req.messages = (0..5)
+        .map(|index| Message {
+            role: if index % 2 == 0 {
+                MessageRole::User
+            } else {
+                MessageRole::Assistant
+            },
+            content: vec![ContentPart::Text {
+                text: format!("turn {index}"),
+            }],
+            cache_hint: Some(CacheHint::Ephemeral),
+        })
+        .collect();
+
+    let error = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect_err("encode should fail");
+    assert!(matches!(error, ProviderError::Protocol { .. }));
+}
+
 #[test]
 fn test_decode_anthropic_translator_category_contract() {
     let payload = AnthropicDecodeEnvelope {
@@ -651,9 +1148,9 @@ fn test_decode_anthropic_translator_category_contract() {
             "content": [
                 {"type": "text", "text": "I will call a tool."},
                 {"type": "tool_use", "id": "call_1", "name": "lookup_weather", "input": {"city": "SF"}},
-                {"type": "thinking", "thinking": "hidden chain"},
+                {"type": "thinking", "thinking": "hidden chain", "signature": "sig_1"},
                 {"type": "redacted_thinking"},
-                {"type": "server_tool_use", "name": "web_search"}
+                {"type": "container_upload", "file_id": "file_1"}
             ],
             "usage": {
                 "input_tokens": 10,
@@ -670,11 +1167,19 @@ fn test_decode_anthropic_translator_category_contract() {
     assert_eq!(decoded.provider, ProviderId::Anthropic);
     assert_eq!(decoded.model, "claude-sonnet-4-5");
     assert_eq!(decoded.finish_reason, FinishReason::ToolCalls);
-    assert_eq!(decoded.usage.input_tokens, Some(15));
+    assert_eq!(decoded.usage.input_tokens, Some(13));
     assert_eq!(decoded.usage.cached_input_tokens, Some(3));
+    assert_eq!(decoded.usage.cache_creation_input_tokens, Some(2));
     assert_eq!(decoded.usage.output_tokens, Some(5));
     assert_eq!(decoded.usage.total_tokens, Some(20));
-    assert_eq!(decoded.output.content.len(), 3);
+    assert_eq!(decoded.output.content.len(), 4);
+    assert_eq!(
+        decoded.output.content[3],
+        ContentPart::Other {
+            kind: "container_upload".to_string(),
+            raw: json!({"type": "container_upload", "file_id": "file_1"}),
+        }
+    );
 
     assert!(matches!(
         &decoded.output.content[0],
@@ -684,11 +1189,18 @@ fn test_decode_anthropic_translator_category_contract() {
         &decoded.output.content[1],
         ContentPart::ToolCall { tool_call } if tool_call.id == "call_1"
     ));
+    assert_eq!(
+        decoded.output.content[2],
+        ContentPart::Thinking {
+            text: "hidden chain".to_string(),
+            signature: Some("sig_1".to_string()),
+        }
+    );
     assert!(
         decoded
             .warnings
             .iter()
-            .any(|warning| warning.code == "unknown_content_block_mapped_to_text")
+            .any(|warning| warning.code == "unknown_content_block_kept")
     );
     assert_eq!(decoded.output.structured_output, None);
 }
@@ -838,8 +1350,10 @@ fn test_decode_empty_output_emits_warning() {
 #[test]
 fn test_anthropic_translator_determinism_contract() {
     let req = base_request();
-    let first_encode = encode_anthropic_request(&req).expect("encode should succeed");
-    let second_encode = encode_anthropic_request(&req).expect("encode should succeed");
+    let first_encode = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
+    let second_encode = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
     assert_eq!(first_encode, second_encode);
 
     let payload = AnthropicDecodeEnvelope {
@@ -881,6 +1395,7 @@ fn test_decode_anthropic_models_list_success_and_invalid_payload() {
         supports_structured_output: true,
         supports_thinking: true,
         supports_remote_discovery: true,
+        supports_parallel_tool_calls: true,
     };
 
     let payload = json!({
@@ -902,3 +1417,198 @@ fn test_decode_anthropic_models_list_success_and_invalid_payload() {
         .expect_err("missing data should fail");
     assert!(matches!(err, ProviderError::Protocol { .. }));
 }
+
+#[test]
+fn test_encode_hosted_tools_as_server_tools() {
+    let mut req = base_request();
+    req.hosted_tools = vec![
+        HostedTool::WebSearch {
+            allowed_domains: vec!["example.com".to_string()],
+        },
+        HostedTool::CodeInterpreter,
+        HostedTool::FileSearch {
+            vector_store_ids: vec!["vs_1".to_string()],
+            max_num_results: None,
+        },
+    ];
+
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(
+        encoded.body.get("tools"),
+        Some(&json!([
+            {
+                "type": "web_search_20250305",
+                "name": "web_search",
+                "allowed_domains": ["example.com"]
+            },
+            { "type": "code_execution_20250522", "name": "code_execution" }
+        ]))
+    );
+    assert!(
+        encoded
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "hosted_tool_unsupported")
+    );
+}
+
+#[test]
+fn test_decode_server_tool_blocks_and_replay() {
+    let server_tool_use = json!({
+        "type": "server_tool_use",
+        "id": "srvtoolu_1",
+        "name": "web_search",
+        "input": { "query": "weather in SF" }
+    });
+    let search_result = json!({
+        "type": "web_search_tool_result",
+        "tool_use_id": "srvtoolu_1",
+        "content": [{
+            "type": "web_search_result",
+            "url": "https://example.com/weather",
+            "title": "Weather",
+            "encrypted_content": "enc_1"
+        }]
+    });
+    let payload = AnthropicDecodeEnvelope {
+        body: json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "model": "claude-sonnet-4-5",
+            "stop_reason": "end_turn",
+            "content": [
+                server_tool_use,
+                search_result,
+                { "type": "text", "text": "It is sunny." }
+            ],
+            "usage": {
+                "input_tokens": 10,
+                "output_tokens": 5,
+                "server_tool_use": { "web_search_requests": 1 }
+            }
+        }),
+        requested_response_format: ResponseFormat::Text,
+    };
+
+    let decoded = decode_anthropic_response(&payload).expect("decode should succeed");
+    assert_eq!(decoded.usage.web_search_requests, Some(1));
+    assert!(decoded.warnings.is_empty());
+    let ContentPart::HostedToolCall { hosted_tool_call } = &decoded.output.content[0] else {
+        panic!("expected hosted tool call");
+    };
+    assert_eq!(hosted_tool_call.id, "srvtoolu_1");
+    assert_eq!(hosted_tool_call.name, "web_search");
+    assert_eq!(hosted_tool_call.provider, ProviderId::Anthropic);
+    let ContentPart::HostedToolCall { hosted_tool_call } = &decoded.output.content[1] else {
+        panic!("expected hosted tool result");
+    };
+    assert_eq!(hosted_tool_call.id, "srvtoolu_1");
+    assert_eq!(hosted_tool_call.name, "web_search_tool_result");
+
+    let mut req = base_request();
+    req.messages.push(Message {
+        role: MessageRole::Assistant,
+        content: decoded.output.content.clone(),
+        cache_hint: None,
+    });
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(
+        encoded.body.pointer("/messages/1/content"),
+        Some(&json!([
+            server_tool_use,
+            search_result,
+            { "type": "text", "text": "It is sunny." }
+        ]))
+    );
+}
+
+#[test]
+fn test_decode_web_search_citations_become_annotations() {
+    let payload = AnthropicDecodeEnvelope {
+        body: json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "model": "claude-sonnet-4-5",
+            "stop_reason": "end_turn",
+            "content": [
+                { "type": "text", "text": "Here is what I found." },
+                {
+                    "type": "text",
+                    "text": "It is sunny in SF.",
+                    "citations": [
+                        {
+                            "type": "web_search_result_location",
+                            "url": "https://example.com/weather",
+                            "title": "Weather",
+                            "cited_text": "Sunny skies over San Francisco",
+                            "encrypted_index": "enc"
+                        },
+                        { "type": "char_location", "cited_text": "x" }
+                    ]
+                }
+            ],
+            "usage": { "input_tokens": 10, "output_tokens": 5 }
+        }),
+        requested_response_format: ResponseFormat::Text,
+    };
+
+    let decoded = decode_anthropic_response(&payload).expect("decode should succeed");
+    assert_eq!(
+        decoded.output.annotations,
+        vec![Annotation::UrlCitation {
+            url: "https://example.com/weather".to_string(),
+            title: Some("Weather".to_string()),
+            content: Some("Sunny skies over San Francisco".to_string()),
+            start_index: None,
+            end_index: None,
+            part_index: Some(1),
+        }]
+    );
+    assert!(
+        decoded
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "unknown_annotation_dropped")
+    );
+}
+
+#[test]
+fn test_encode_tool_result_error_flag() {
+    let mut req = base_request();
+    req.messages.push(Message {
+        role: MessageRole::Assistant,
+        content: vec![ContentPart::ToolCall {
+            tool_call: ToolCall {
+                id: "tool_1".to_string(),
+                name: "lookup".to_string(),
+                arguments_json: json!({}),
+            },
+        }],
+        cache_hint: None,
+    });
+    req.messages.push(Message {
+        role: MessageRole::Tool,
+        content: vec![ContentPart::ToolResult {
+            tool_result: ToolResult {
+                tool_call_id: "tool_1".to_string(),
+                content: ToolResultContent::Text {
+                    text: "lookup timed out".to_string(),
+                },
+                raw_provider_content: None,
+                is_error: true,
+            },
+        }],
+        cache_hint: None,
+    });
+
+    let encoded = encode_anthropic_request(&req, &AnthropicTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(
+        encoded.body.pointer("/messages/2/content/0/is_error"),
+        Some(&json!(true))
+    );
+}
@@ -0,0 +1,269 @@
+//! OAuth 2.0 token providers for deployments that put an identity provider in front of the
+//! model API, such as Azure AD-protected OpenAI deployments.
+
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::core::error::{ConfigError, ErrorSource, RuntimeError};
+use crate::core::traits::TokenProvider;
+use crate::core::types::{AccessToken, ProviderId};
+use crate::transport::http::{
+    JitterMode, RetryPolicy, is_retryable_transport, parse_retry_after_ms,
+};
+
+const OAUTH_DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Fetches bearer tokens with the OAuth 2.0 client-credentials grant (RFC 6749 §4.4).
+///
+/// Every call requests a new token. The runtime caches it by its `expires_in`, refreshes it
+/// [`TOKEN_REFRESH_MARGIN`](crate::registry::TOKEN_REFRESH_MARGIN) before it expires, and fetches
+/// another as soon as the provider rejects it. Transport failures and retryable statuses are
+/// retried with jittered exponential backoff. The same token is returned for every `ProviderId`.
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use provider_runtime::prelude::*;
+/// # use provider_runtime::providers::auth::OAuthTokenProvider;
+/// # fn main() -> Result<(), ConfigError> {
+/// let tokens = OAuthTokenProvider::new(
+///     "https://login.microsoftonline.com/<tenant>/oauth2/v2.0/token",
+///     "<client-id>",
+///     "<client-secret>",
+/// )?
+/// .with_scope("https://cognitiveservices.azure.com/.default");
+/// let runtime = ProviderRuntime::builder()
+///     .with_adapter(Arc::new(OpenAiAdapter::new(None)?))
+///     .with_token_provider(ProviderId::Openai, Arc::new(tokens))
+///     .build();
+/// # Ok(())
+/// # }
+/// ```
+pub struct OAuthTokenProvider {
+    client: reqwest::Client,
+    token_url: String,
+    client_id: String,
+    client_secret: String,
+    scopes: Vec<String>,
+    params: Vec<(String, String)>,
+    retry_policy: RetryPolicy,
+}
+
+impl OAuthTokenProvider {
+    pub fn new(
+        token_url: impl Into<String>,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Result<Self, ConfigError> {
+        let invalid = |reason: &str| ConfigError::InvalidOAuthConfig {
+            reason: reason.to_string(),
+        };
+        let token_url = token_url.into().trim().to_string();
+        if !token_url.starts_with("https://") && !token_url.starts_with("http://") {
+            return Err(invalid("token_url must be an http(s) URL"));
+        }
+        let client_id = client_id.into();
+        if client_id.trim().is_empty() {
+            return Err(invalid("client_id must not be empty"));
+        }
+        let client_secret = client_secret.into();
+        if client_secret.trim().is_empty() {
+            return Err(invalid("client_secret must not be empty"));
+        }
+        let client = reqwest::Client::builder()
+            .timeout(OAUTH_DEFAULT_TIMEOUT)
+            .build()
+            .map_err(|error| ConfigError::InvalidOAuthConfig {
+                reason: format!("failed to build HTTP client: {error}"),
+            })?;
+
+        Ok(Self {
+            client,
+            token_url,
+            client_id,
+            client_secret,
+            scopes: Vec::new(),
+            params: Vec::new(),
+            retry_policy: RetryPolicy {
+                jitter: JitterMode::Full,
+                ..RetryPolicy::default()
+            },
+        })
+    }
+
+    /// Requests `scope`; several scopes are sent space-separated.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scopes.push(scope.into());
+        self
+    }
+
+    /// Adds a form parameter to the token request, such as `audience` or `resource`.
+    pub fn with_param(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.push((name.into(), value.into()));
+        self
+    }
+
+    /// Retries token requests per `retry_policy` instead of three attempts with full jitter.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Result<Self, ConfigError> {
+        retry_policy.validate()?;
+        self.retry_policy = retry_policy;
+        Ok(self)
+    }
+
+    /// Replaces the HTTP client, for proxies, custom roots, or a different timeout.
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    async fn request_token(&self, provider: &ProviderId) -> Result<AccessToken, RuntimeError> {
+        let mut form = vec![
+            ("grant_type".to_string(), "client_credentials".to_string()),
+            ("client_id".to_string(), self.client_id.clone()),
+            ("client_secret".to_string(), self.client_secret.clone()),
+        ];
+        if !self.scopes.is_empty() {
+            form.push(("scope".to_string(), self.scopes.join(" ")));
+        }
+        form.extend(self.params.iter().cloned());
+
+        let mut attempt = 1;
+        loop {
+            let (error, retry_after) = match self
+                .client
+                .post(&self.token_url)
+                .header(reqwest::header::ACCEPT, "application/json")
+                .form(&form)
+                .send()
+                .await
+            {
+                Ok(response) if response.status().is_success() => {
+                    let body = response
+                        .bytes()
+                        .await
+                        .map_err(|error| transport_error(provider, error))?;
+                    return parse_token_response(provider, &body);
+                }
+                Ok(response) => {
+                    let status_code = response.status().as_u16();
+                    let retry_after = response
+                        .headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(parse_retry_after_ms)
+                        .map(|ms| self.retry_policy.retry_after_duration(ms));
+                    let body = response.text().await.unwrap_or_default();
+                    let error = RuntimeError::ProviderProtocolError {
+                        provider: Some(provider.clone()),
+                        model: None,
+                        request_id: None,
+                        status_code: Some(status_code),
+                        message: format!(
+                            "OAuth token request failed: {}",
+                            oauth_error_message(&body)
+                        ),
+                        rate_limit: None,
+                    };
+                    if !self.retry_policy.should_retry_status(status_code) {
+                        return Err(error);
+                    }
+                    (error, retry_after)
+                }
+                Err(error) => {
+                    if !is_retryable_transport(&error) {
+                        return Err(transport_error(provider, error));
+                    }
+                    (transport_error(provider, error), None)
+                }
+            };
+
+            if attempt >= self.retry_policy.max_attempts {
+                return Err(error);
+            }
+            let delay = retry_after
+                .unwrap_or_else(|| self.retry_policy.backoff_duration_for_retry(attempt - 1));
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+impl std::fmt::Debug for OAuthTokenProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OAuthTokenProvider")
+            .field("token_url", &self.token_url)
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"<redacted>")
+            .field("scopes", &self.scopes)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl TokenProvider for OAuthTokenProvider {
+    async fn get_token(&self, provider: ProviderId) -> Result<String, RuntimeError> {
+        Ok(self.access_token(provider).await?.token)
+    }
+
+    async fn access_token(&self, provider: ProviderId) -> Result<AccessToken, RuntimeError> {
+        self.request_token(&provider).await
+    }
+}
+
+/// Reads `access_token` and `expires_in`, which some identity providers, including Azure AD
+/// v1, send as a string.
+fn parse_token_response(provider: &ProviderId, body: &[u8]) -> Result<AccessToken, RuntimeError> {
+    let invalid = |message: String| RuntimeError::SerializationError {
+        provider: Some(provider.clone()),
+        model: None,
+        request_id: None,
+        message,
+        source: None,
+    };
+    let value: Value = serde_json::from_slice(body)
+        .map_err(|error| invalid(format!("malformed OAuth token response: {error}")))?;
+    let token = value
+        .get("access_token")
+        .and_then(Value::as_str)
+        .filter(|token| !token.trim().is_empty())
+        .ok_or_else(|| invalid("OAuth token response has no access_token".to_string()))?;
+    let expires_in = match value.get("expires_in") {
+        Some(Value::Number(seconds)) => seconds.as_u64(),
+        Some(Value::String(seconds)) => seconds.trim().parse().ok(),
+        _ => None,
+    };
+
+    let mut access_token = AccessToken::new(token);
+    if let Some(seconds) = expires_in {
+        access_token =
+            access_token.with_expires_at(SystemTime::now() + Duration::from_secs(seconds));
+    }
+    Ok(access_token)
+}
+
+/// Uses the RFC 6749 `error` and `error_description` fields when present.
+fn oauth_error_message(body: &str) -> String {
+    let Ok(value) = serde_json::from_str::<Value>(body) else {
+        return body.trim().to_string();
+    };
+    let field = |name: &str| value.get(name).and_then(Value::as_str);
+    match (field("error"), field("error_description")) {
+        (Some(error), Some(description)) => format!("{error}: {description}"),
+        (Some(error), None) => error.to_string(),
+        _ => body.trim().to_string(),
+    }
+}
+
+fn transport_error(provider: &ProviderId, error: reqwest::Error) -> RuntimeError {
+    RuntimeError::TransportError {
+        provider: Some(provider.clone()),
+        model: None,
+        request_id: None,
+        message: format!("OAuth token request failed: {error}"),
+        source: Some(ErrorSource::new(error)),
+    }
+}
+
+#[cfg(test)]
+mod tests;
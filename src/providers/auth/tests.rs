@@ -0,0 +1,320 @@
+use std::collections::VecDeque;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use async_trait::async_trait;
+
+use super::*;
+use crate::core::error::ProviderError;
+use crate::core::traits::ProviderAdapter;
+use crate::core::types::{
+    AdapterContext, AssistantOutput, DiscoveryOptions, FinishReason, ModelInfo, ModelRef,
+    ProviderCapabilities, ProviderRequest, ProviderResponse, Usage,
+};
+use crate::registry::ACCESS_TOKEN_METADATA_KEY;
+use crate::runtime::ProviderRuntime;
+
+/// Answers each connection with the next canned `(status, body)` and records the request bodies.
+struct TokenServer {
+    addr: std::net::SocketAddr,
+    bodies: Arc<Mutex<Vec<String>>>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl TokenServer {
+    fn start(responses: Vec<(u16, &str)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind test listener");
+        let addr = listener.local_addr().expect("listener addr");
+        let mut queue: VecDeque<(u16, String)> = responses
+            .into_iter()
+            .map(|(status, body)| (status, body.to_string()))
+            .collect();
+        let bodies = Arc::new(Mutex::new(Vec::new()));
+        let bodies_clone = Arc::clone(&bodies);
+
+        let handle = thread::spawn(move || {
+            while let Some((status, body)) = queue.pop_front() {
+                let (mut stream, _) = listener.accept().expect("accept connection");
+                stream
+                    .set_read_timeout(Some(Duration::from_secs(3)))
+                    .expect("set stream timeout");
+                bodies_clone
+                    .lock()
+                    .expect("bodies lock")
+                    .push(read_request_body(&mut stream));
+                let response = format!(
+                    "HTTP/1.1 {status} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream
+                    .write_all(response.as_bytes())
+                    .expect("write response");
+            }
+        });
+
+        Self {
+            addr,
+            bodies,
+            handle: Some(handle),
+        }
+    }
+
+    fn url(&self) -> String {
+        format!("http://{}/token", self.addr)
+    }
+
+    fn bodies(&mut self) -> Vec<String> {
+        if let Some(handle) = self.handle.take() {
+            handle.join().expect("join token server");
+        }
+        self.bodies.lock().expect("bodies lock").clone()
+    }
+}
+
+fn read_request_body(stream: &mut std::net::TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut chunk = [0_u8; 1024];
+    loop {
+        let read = stream.read(&mut chunk).expect("read request");
+        request.extend_from_slice(&chunk[..read]);
+        let text = String::from_utf8_lossy(&request).to_string();
+        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+            let length = head
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+            if body.len() >= length {
+                return body.to_string();
+            }
+        }
+        if read == 0 {
+            return String::new();
+        }
+    }
+}
+
+fn fast_retries() -> RetryPolicy {
+    RetryPolicy {
+        initial_backoff_ms: 1,
+        max_backoff_ms: 5,
+        jitter: JitterMode::Full,
+        ..RetryPolicy::default()
+    }
+}
+
+#[test]
+fn test_oauth_provider_rejects_invalid_config() {
+    for (url, id, secret) in [
+        ("login.example.com/token", "id", "secret"),
+        ("https://login.example.com/token", " ", "secret"),
+        ("https://login.example.com/token", "id", ""),
+    ] {
+        let error = OAuthTokenProvider::new(url, id, secret).expect_err("config should fail");
+        assert!(matches!(error, ConfigError::InvalidOAuthConfig { .. }));
+    }
+}
+
+#[tokio::test]
+async fn test_oauth_provider_posts_client_credentials() {
+    let mut server = TokenServer::start(vec![(
+        200,
+        r#"{"access_token":"token-1","token_type":"Bearer","expires_in":3600}"#,
+    )]);
+    let tokens = OAuthTokenProvider::new(server.url(), "client-a", "secret-a")
+        .expect("provider")
+        .with_scope("https://cognitiveservices.azure.com/.default")
+        .with_param("audience", "gateway");
+
+    let token = tokens
+        .access_token(ProviderId::Openai)
+        .await
+        .expect("token");
+    assert_eq!(token.token, "token-1");
+    assert!(token.expires_at.is_some());
+
+    let bodies = server.bodies();
+    assert_eq!(bodies.len(), 1);
+    assert_eq!(
+        bodies[0],
+        "grant_type=client_credentials&client_id=client-a&client_secret=secret-a\
+         &scope=https%3A%2F%2Fcognitiveservices.azure.com%2F.default&audience=gateway"
+    );
+}
+
+/// Rejects calls made with `revoked_token` as the bearer token and records every token seen.
+struct BearerCheckAdapter {
+    revoked_token: &'static str,
+    tokens_seen: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl ProviderAdapter for BearerCheckAdapter {
+    fn id(&self) -> ProviderId {
+        ProviderId::Openai
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            supports_tools: false,
+            supports_structured_output: false,
+            supports_thinking: false,
+            supports_remote_discovery: false,
+            supports_parallel_tool_calls: false,
+        }
+    }
+
+    async fn run(
+        &self,
+        req: &ProviderRequest,
+        ctx: &AdapterContext,
+    ) -> Result<ProviderResponse, ProviderError> {
+        let token = ctx
+            .metadata
+            .get(ACCESS_TOKEN_METADATA_KEY)
+            .cloned()
+            .unwrap_or_default();
+        self.tokens_seen
+            .lock()
+            .expect("tokens lock")
+            .push(token.clone());
+        if token == self.revoked_token {
+            return Err(ProviderError::CredentialsRejected {
+                provider: ProviderId::Openai,
+                request_id: None,
+                message: "token revoked".to_string(),
+            });
+        }
+        Ok(ProviderResponse {
+            output: AssistantOutput {
+                content: Vec::new(),
+                structured_output: None,
+                annotations: Vec::new(),
+            },
+            usage: Usage::default(),
+            cost: None,
+            provider: ProviderId::Openai,
+            model: req.model.model_id.clone(),
+            raw_provider_response: None,
+            finish_reason: FinishReason::Stop,
+            warnings: Vec::new(),
+            alternatives: Vec::new(),
+            response_id: None,
+            request_id: None,
+            rate_limit: None,
+        })
+    }
+
+    async fn discover_models(
+        &self,
+        _opts: &DiscoveryOptions,
+        _ctx: &AdapterContext,
+    ) -> Result<Vec<ModelInfo>, ProviderError> {
+        Ok(Vec::new())
+    }
+}
+
+#[tokio::test]
+async fn test_runtime_fetches_new_oauth_token_after_rejection() {
+    let mut server = TokenServer::start(vec![
+        (200, r#"{"access_token":"token-1","expires_in":3600}"#),
+        (200, r#"{"access_token":"token-2","expires_in":"3600"}"#),
+    ]);
+    let tokens = OAuthTokenProvider::new(server.url(), "client-a", "secret-a").expect("provider");
+    let adapter = Arc::new(BearerCheckAdapter {
+        revoked_token: "token-1",
+        tokens_seen: Mutex::new(Vec::new()),
+    });
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_token_provider(ProviderId::Openai, Arc::new(tokens))
+        .build();
+    let request = || {
+        ProviderRequest::new(
+            ModelRef {
+                provider_hint: Some(ProviderId::Openai),
+                model_id: "gpt-5-mini".to_string(),
+                requirements: None,
+            },
+            Vec::new(),
+        )
+    };
+
+    runtime
+        .run(request())
+        .await
+        .expect("retry should use a new token");
+    runtime
+        .run(request())
+        .await
+        .expect("new token should stay cached");
+
+    assert_eq!(
+        *adapter.tokens_seen.lock().expect("tokens lock"),
+        ["token-1", "token-2", "token-2"]
+    );
+    assert_eq!(server.bodies().len(), 2);
+}
+
+#[tokio::test]
+async fn test_oauth_provider_retries_retryable_statuses() {
+    let mut server = TokenServer::start(vec![
+        (503, r#"{"error":"temporarily_unavailable"}"#),
+        (200, r#"{"access_token":"token-1","expires_in":3600}"#),
+    ]);
+    let tokens = OAuthTokenProvider::new(server.url(), "client-a", "secret-a")
+        .expect("provider")
+        .with_retry_policy(fast_retries())
+        .expect("retry policy");
+
+    assert_eq!(
+        tokens.get_token(ProviderId::Openai).await.expect("token"),
+        "token-1"
+    );
+    assert_eq!(server.bodies().len(), 2);
+}
+
+#[tokio::test]
+async fn test_oauth_provider_surfaces_rejected_credentials_without_retry() {
+    let mut server = TokenServer::start(vec![(
+        401,
+        r#"{"error":"invalid_client","error_description":"bad secret"}"#,
+    )]);
+    let tokens = OAuthTokenProvider::new(server.url(), "client-a", "secret-a")
+        .expect("provider")
+        .with_retry_policy(fast_retries())
+        .expect("retry policy");
+
+    let error = tokens
+        .get_token(ProviderId::Openai)
+        .await
+        .expect_err("token request should fail");
+    match error {
+        RuntimeError::ProviderProtocolError {
+            status_code,
+            message,
+            ..
+        } => {
+            assert_eq!(status_code, Some(401));
+            assert!(message.contains("invalid_client: bad secret"), "{message}");
+        }
+        other => panic!("expected protocol error, got {other:?}"),
+    }
+    assert_eq!(server.bodies().len(), 1);
+}
+
+#[test]
+fn test_parse_token_response_requires_access_token() {
+    let error = parse_token_response(&ProviderId::Openai, br#"{"token_type":"Bearer"}"#)
+        .expect_err("missing token");
+    assert!(matches!(error, RuntimeError::SerializationError { .. }));
+
+    let token = parse_token_response(&ProviderId::Openai, br#"{"access_token":"t"}"#)
+        .expect("token without expiry");
+    assert_eq!(token.expires_at, None);
+}
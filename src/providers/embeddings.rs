@@ -0,0 +1,156 @@
+//! OpenAI-compatible `/embeddings` wire format, shared by the OpenAI and OpenRouter adapters.
+
+use serde_json::{Map, Value, json};
+
+use crate::core::error::ProviderError;
+use crate::core::types::{
+    EmbeddingRequest, EmbeddingResponse, ProviderId, RuntimeWarning, Usage, WarningCode,
+};
+
+pub(crate) fn encode_embedding_request(
+    provider: &ProviderId,
+    req: &EmbeddingRequest,
+) -> Result<Value, ProviderError> {
+    if let Some(provider_hint) = &req.model.provider_hint
+        && provider_hint != provider
+    {
+        return Err(protocol_error(
+            provider,
+            Some(&req.model.model_id),
+            format!("provider_hint must be {provider:?}, got {provider_hint:?}"),
+        ));
+    }
+    if req.model.model_id.trim().is_empty() {
+        return Err(protocol_error(provider, None, "missing model_id"));
+    }
+    if req.inputs.is_empty() {
+        return Err(protocol_error(
+            provider,
+            Some(&req.model.model_id),
+            "embedding request has no inputs",
+        ));
+    }
+
+    let mut body = json!({
+        "model": req.model.model_id,
+        "input": req.inputs,
+        "encoding_format": "float",
+    });
+    if let Some(dimensions) = req.dimensions {
+        body["dimensions"] = json!(dimensions);
+    }
+    Ok(body)
+}
+
+pub(crate) fn decode_embedding_response(
+    provider: &ProviderId,
+    req: &EmbeddingRequest,
+    body: &Value,
+) -> Result<EmbeddingResponse, ProviderError> {
+    let model_id = req.model.model_id.as_str();
+    let root = body.as_object().ok_or_else(|| {
+        protocol_error(
+            provider,
+            Some(model_id),
+            "embeddings payload must be a JSON object",
+        )
+    })?;
+    let data = root.get("data").and_then(Value::as_array).ok_or_else(|| {
+        protocol_error(
+            provider,
+            Some(model_id),
+            "embeddings payload missing data array",
+        )
+    })?;
+
+    let mut indexed = Vec::with_capacity(data.len());
+    for (position, entry) in data.iter().enumerate() {
+        let entry = entry.as_object().ok_or_else(|| {
+            protocol_error(
+                provider,
+                Some(model_id),
+                format!("embeddings payload contains non-object entry at index {position}"),
+            )
+        })?;
+        let index = entry
+            .get("index")
+            .and_then(Value::as_u64)
+            .map_or(position, |index| index as usize);
+        let vector = entry
+            .get("embedding")
+            .and_then(Value::as_array)
+            .and_then(|values| {
+                values
+                    .iter()
+                    .map(|value| value.as_f64().map(|value| value as f32))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or_else(|| {
+                protocol_error(
+                    provider,
+                    Some(model_id),
+                    format!("embeddings payload entry {position} has no float embedding"),
+                )
+            })?;
+        indexed.push((index, vector));
+    }
+    if indexed.len() != req.inputs.len() {
+        return Err(protocol_error(
+            provider,
+            Some(model_id),
+            format!(
+                "embeddings payload has {} vectors for {} inputs",
+                indexed.len(),
+                req.inputs.len()
+            ),
+        ));
+    }
+    indexed.sort_by_key(|(index, _)| *index);
+
+    let mut warnings = Vec::new();
+    let usage = decode_usage(root.get("usage").and_then(Value::as_object), &mut warnings);
+
+    Ok(EmbeddingResponse {
+        embeddings: indexed.into_iter().map(|(_, vector)| vector).collect(),
+        usage,
+        cost: None,
+        provider: provider.clone(),
+        model: root
+            .get("model")
+            .and_then(Value::as_str)
+            .unwrap_or(model_id)
+            .to_string(),
+        request_id: None,
+        warnings,
+    })
+}
+
+fn decode_usage(usage: Option<&Map<String, Value>>, warnings: &mut Vec<RuntimeWarning>) -> Usage {
+    let Some(usage) = usage else {
+        warnings.push(RuntimeWarning {
+            code: WarningCode::UsageMissing,
+            message: "embeddings response missing usage details".to_string(),
+        });
+        return Usage::default();
+    };
+
+    Usage {
+        input_tokens: usage.get("prompt_tokens").and_then(Value::as_u64),
+        output_tokens: Some(0),
+        total_tokens: usage.get("total_tokens").and_then(Value::as_u64),
+        ..Usage::default()
+    }
+}
+
+fn protocol_error(
+    provider: &ProviderId,
+    model: Option<&str>,
+    message: impl Into<String>,
+) -> ProviderError {
+    ProviderError::Protocol {
+        provider: provider.clone(),
+        model: model.map(str::to_string),
+        request_id: None,
+        message: message.into(),
+    }
+}
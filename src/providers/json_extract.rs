@@ -0,0 +1,88 @@
+use serde_json::Value;
+
+/// The first balanced `{...}` span of `text`, skipping braces inside JSON strings. Finds
+/// objects wrapped in prose or Markdown code fences.
+pub(crate) fn extract_first_json_object(text: &str) -> Option<&str> {
+    first_object_span(text).map(|(start, end)| &text[start..=end])
+}
+
+/// The first balanced `{...}` span of `text` that parses as a JSON object, moving past spans
+/// that do not parse, such as `{placeholders}` in the surrounding prose.
+pub(crate) fn extract_json_object(text: &str) -> Option<Value> {
+    let mut rest = text;
+    while let Some((start, end)) = first_object_span(rest) {
+        if let Ok(value) = serde_json::from_str::<Value>(&rest[start..=end])
+            && value.is_object()
+        {
+            return Some(value);
+        }
+        rest = &rest[start + 1..];
+    }
+    None
+}
+
+/// Byte offsets of the opening and closing brace of the first balanced object.
+fn first_object_span(text: &str) -> Option<(usize, usize)> {
+    let mut start = None;
+    let mut depth = 0_u64;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (index, byte) in text.bytes().enumerate() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if byte == b'\\' {
+                escaped = true;
+            } else if byte == b'"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        match byte {
+            b'"' if start.is_some() => in_string = true,
+            b'{' => {
+                start.get_or_insert(index);
+                depth += 1;
+            }
+            b'}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0
+                    && let Some(start_index) = start
+                {
+                    return Some((start_index, index));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{extract_first_json_object, extract_json_object};
+
+    #[test]
+    fn test_extract_first_json_object_skips_braces_in_strings() {
+        assert_eq!(
+            extract_first_json_object("Sure! ```json\n{\"a\": \"}{\", \"b\": {\"c\": 1}}\n```"),
+            Some("{\"a\": \"}{\", \"b\": {\"c\": 1}}")
+        );
+        assert_eq!(extract_first_json_object("no object here"), None);
+        assert_eq!(extract_first_json_object("{\"unbalanced\": 1"), None);
+    }
+
+    #[test]
+    fn test_extract_json_object_moves_past_unparseable_spans() {
+        assert_eq!(
+            extract_json_object("Fill in {name} later. Result: {\"name\": \"Ada\"}"),
+            Some(json!({"name": "Ada"}))
+        );
+        assert_eq!(extract_json_object("only {placeholders} here"), None);
+    }
+}
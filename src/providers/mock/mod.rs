@@ -0,0 +1,329 @@
+//! Scripted in-process adapter for testing applications built on `ProviderRuntime`.
+//!
+//! `MockAdapter` answers requests from rules registered up front instead of calling a provider,
+//! so tool loops and error handling can be exercised without HTTP mocks.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use crate::core::error::ProviderError;
+use crate::core::traits::ProviderAdapter;
+use crate::core::types::{
+    AdapterContext, AssistantOutput, ContentPart, DiscoveryOptions, FinishReason, ModelInfo,
+    ProviderCapabilities, ProviderId, ProviderRequest, ProviderResponse, ToolCall,
+    ToolResultContent, Usage,
+};
+
+type RequestPredicate = Arc<dyn Fn(&ProviderRequest) -> bool + Send + Sync>;
+
+/// What a matched rule answers with.
+#[derive(Debug, Clone)]
+pub enum MockOutcome {
+    /// A text reply, returned as a `ProviderResponse` for the requested model.
+    Text(String),
+    /// Tool calls with `FinishReason::ToolCalls`.
+    ToolCalls(Vec<ToolCall>),
+    /// A fully specified response, returned as-is.
+    Response(Box<ProviderResponse>),
+    Error(ProviderError),
+}
+
+impl MockOutcome {
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+
+    pub fn tool_call(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        arguments_json: serde_json::Value,
+    ) -> Self {
+        Self::ToolCalls(vec![ToolCall {
+            id: id.into(),
+            name: name.into(),
+            arguments_json,
+        }])
+    }
+
+    pub fn response(response: ProviderResponse) -> Self {
+        Self::Response(Box::new(response))
+    }
+
+    pub fn error(error: ProviderError) -> Self {
+        Self::Error(error)
+    }
+}
+
+/// Selects which requests a rule answers. All configured conditions must hold; an empty matcher
+/// matches every request.
+#[derive(Clone, Default)]
+pub struct MockMatcher {
+    model: Option<String>,
+    last_message_contains: Option<String>,
+    predicate: Option<RequestPredicate>,
+}
+
+impl MockMatcher {
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Matches requests for exactly this model id.
+    pub fn model(mut self, model_id: impl Into<String>) -> Self {
+        self.model = Some(model_id.into());
+        self
+    }
+
+    /// Matches when the text of the last message, including text tool results, contains
+    /// `needle`.
+    pub fn last_message_contains(mut self, needle: impl Into<String>) -> Self {
+        self.last_message_contains = Some(needle.into());
+        self
+    }
+
+    /// Matches requests accepted by an arbitrary predicate.
+    pub fn when(
+        mut self,
+        predicate: impl Fn(&ProviderRequest) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.predicate = Some(Arc::new(predicate));
+        self
+    }
+
+    fn matches(&self, req: &ProviderRequest) -> bool {
+        if self
+            .model
+            .as_ref()
+            .is_some_and(|model| *model != req.model.model_id)
+        {
+            return false;
+        }
+        if let Some(needle) = &self.last_message_contains
+            && !last_message_text(req).contains(needle.as_str())
+        {
+            return false;
+        }
+        self.predicate
+            .as_ref()
+            .is_none_or(|predicate| predicate(req))
+    }
+}
+
+struct MockRule {
+    matcher: MockMatcher,
+    /// Answered in order; the last outcome repeats once the others are used up.
+    outcomes: VecDeque<MockOutcome>,
+}
+
+/// A `ProviderAdapter` that returns scripted outcomes and records every request it receives.
+///
+/// Rules are checked in registration order and the first match answers. Requests no rule
+/// matches fail with a `ProviderError::Protocol` naming the model.
+pub struct MockAdapter {
+    provider: ProviderId,
+    capabilities: ProviderCapabilities,
+    models: Vec<ModelInfo>,
+    rules: Mutex<Vec<MockRule>>,
+    requests: Mutex<Vec<ProviderRequest>>,
+    health_checks: Mutex<VecDeque<Result<(), ProviderError>>>,
+}
+
+impl MockAdapter {
+    /// Creates an adapter for `provider` that supports tools and structured output.
+    pub fn new(provider: ProviderId) -> Self {
+        Self {
+            provider,
+            capabilities: ProviderCapabilities {
+                supports_tools: true,
+                supports_structured_output: true,
+                supports_thinking: false,
+                supports_remote_discovery: false,
+                supports_parallel_tool_calls: true,
+            },
+            models: Vec::new(),
+            rules: Mutex::new(Vec::new()),
+            requests: Mutex::new(Vec::new()),
+            health_checks: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn with_capabilities(mut self, capabilities: ProviderCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Models returned from `discover_models`.
+    pub fn with_models(mut self, models: Vec<ModelInfo>) -> Self {
+        self.models = models;
+        self
+    }
+
+    /// Answers every matching request with `outcome`.
+    pub fn on(self, matcher: MockMatcher, outcome: MockOutcome) -> Self {
+        self.on_sequence(matcher, vec![outcome])
+    }
+
+    /// Answers successive matching requests with `outcomes` in order, repeating the last one.
+    /// Useful for tool loops, where the first turn calls a tool and the next one answers.
+    pub fn on_sequence(self, matcher: MockMatcher, outcomes: Vec<MockOutcome>) -> Self {
+        if !outcomes.is_empty() {
+            self.lock_rules().push(MockRule {
+                matcher,
+                outcomes: outcomes.into(),
+            });
+        }
+        self
+    }
+
+    /// Answers successive health checks with `results` in order, repeating the last one.
+    /// Without scripted results every check passes.
+    pub fn with_health_checks(self, results: Vec<Result<(), ProviderError>>) -> Self {
+        *self
+            .health_checks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = results.into();
+        self
+    }
+
+    /// Requests received so far, oldest first.
+    pub fn requests(&self) -> Vec<ProviderRequest> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    fn lock_rules(&self) -> std::sync::MutexGuard<'_, Vec<MockRule>> {
+        self.rules
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn next_outcome(&self, req: &ProviderRequest) -> Option<MockOutcome> {
+        let mut rules = self.lock_rules();
+        let rule = rules.iter_mut().find(|rule| rule.matcher.matches(req))?;
+        if rule.outcomes.len() > 1 {
+            rule.outcomes.pop_front()
+        } else {
+            rule.outcomes.front().cloned()
+        }
+    }
+
+    fn build_response(&self, req: &ProviderRequest, content: Vec<ContentPart>) -> ProviderResponse {
+        let finish_reason = if content
+            .iter()
+            .any(|part| matches!(part, ContentPart::ToolCall { .. }))
+        {
+            FinishReason::ToolCalls
+        } else {
+            FinishReason::Stop
+        };
+
+        ProviderResponse {
+            output: AssistantOutput {
+                content,
+                structured_output: None,
+                annotations: Vec::new(),
+            },
+            usage: Usage::default(),
+            cost: None,
+            provider: self.provider.clone(),
+            model: req.model.model_id.clone(),
+            raw_provider_response: None,
+            request_id: None,
+            rate_limit: None,
+            finish_reason,
+            warnings: Vec::new(),
+            alternatives: Vec::new(),
+            response_id: None,
+        }
+    }
+}
+
+#[async_trait]
+impl ProviderAdapter for MockAdapter {
+    fn id(&self) -> ProviderId {
+        self.provider.clone()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.capabilities.clone()
+    }
+
+    async fn run(
+        &self,
+        req: &ProviderRequest,
+        _ctx: &AdapterContext,
+    ) -> Result<ProviderResponse, ProviderError> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(req.clone());
+
+        match self.next_outcome(req) {
+            Some(MockOutcome::Text(text)) => {
+                Ok(self.build_response(req, vec![ContentPart::Text { text }]))
+            }
+            Some(MockOutcome::ToolCalls(tool_calls)) => Ok(self.build_response(
+                req,
+                tool_calls
+                    .into_iter()
+                    .map(|tool_call| ContentPart::ToolCall { tool_call })
+                    .collect(),
+            )),
+            Some(MockOutcome::Response(response)) => Ok(*response),
+            Some(MockOutcome::Error(error)) => Err(error),
+            None => Err(ProviderError::Protocol {
+                provider: self.provider.clone(),
+                model: Some(req.model.model_id.clone()),
+                request_id: None,
+                message: "no scripted mock response matches the request".to_string(),
+            }),
+        }
+    }
+
+    async fn discover_models(
+        &self,
+        _opts: &DiscoveryOptions,
+        _ctx: &AdapterContext,
+    ) -> Result<Vec<ModelInfo>, ProviderError> {
+        Ok(self.models.clone())
+    }
+
+    async fn health_check(&self, _ctx: &AdapterContext) -> Result<(), ProviderError> {
+        let mut results = self
+            .health_checks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if results.len() > 1 {
+            results.pop_front().unwrap_or(Ok(()))
+        } else {
+            results.front().cloned().unwrap_or(Ok(()))
+        }
+    }
+}
+
+fn last_message_text(req: &ProviderRequest) -> String {
+    let Some(message) = req.messages.last() else {
+        return String::new();
+    };
+
+    message
+        .content
+        .iter()
+        .filter_map(|part| match part {
+            ContentPart::Text { text } => Some(text.as_str()),
+            ContentPart::ToolResult { tool_result } => match &tool_result.content {
+                ToolResultContent::Text { text } => Some(text.as_str()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests;
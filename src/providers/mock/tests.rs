@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use super::{MockAdapter, MockMatcher, MockOutcome};
+use crate::core::error::{ProviderError, RuntimeError};
+use crate::core::types::{
+    ContentPart, FinishReason, Message, MessageRole, ModelRef, ProviderId, ProviderRequest,
+    ToolResult, ToolResultContent,
+};
+use crate::runtime::ProviderRuntime;
+
+fn request(model_id: &str, messages: Vec<Message>) -> ProviderRequest {
+    ProviderRequest::new(
+        ModelRef {
+            provider_hint: Some(ProviderId::Openai),
+            model_id: model_id.to_string(),
+            requirements: None,
+        },
+        messages,
+    )
+}
+
+fn user(text: &str) -> Message {
+    Message {
+        role: MessageRole::User,
+        content: vec![ContentPart::Text {
+            text: text.to_string(),
+        }],
+        cache_hint: None,
+    }
+}
+
+fn tool_result(text: &str) -> Message {
+    Message {
+        role: MessageRole::Tool,
+        content: vec![ContentPart::ToolResult {
+            tool_result: ToolResult {
+                tool_call_id: "call_1".to_string(),
+                content: ToolResultContent::Text {
+                    text: text.to_string(),
+                },
+                raw_provider_content: None,
+                is_error: false,
+            },
+        }],
+        cache_hint: None,
+    }
+}
+
+#[tokio::test]
+async fn test_mock_adapter_scripts_tool_loop_through_runtime() {
+    let adapter = Arc::new(
+        MockAdapter::new(ProviderId::Openai)
+            .on(
+                MockMatcher::any().last_message_contains("sunny"),
+                MockOutcome::text("It is sunny."),
+            )
+            .on(
+                MockMatcher::any()
+                    .model("gpt-5-mini")
+                    .last_message_contains("weather"),
+                MockOutcome::tool_call("call_1", "get_weather", json!({ "city": "Oslo" })),
+            ),
+    );
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .build();
+
+    let first = runtime
+        .run(request("gpt-5-mini", vec![user("what is the weather?")]))
+        .await
+        .expect("first turn should succeed");
+    assert_eq!(first.finish_reason, FinishReason::ToolCalls);
+    assert_eq!(first.model, "gpt-5-mini");
+
+    let second = runtime
+        .run(request(
+            "gpt-5-mini",
+            vec![user("what is the weather?"), tool_result("sunny, 21C")],
+        ))
+        .await
+        .expect("second turn should succeed");
+    assert_eq!(
+        second.output.content,
+        vec![ContentPart::Text {
+            text: "It is sunny.".to_string()
+        }]
+    );
+    assert_eq!(adapter.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_mock_adapter_sequences_repeat_last_and_unmatched_requests_fail() {
+    let adapter = MockAdapter::new(ProviderId::Openai).on_sequence(
+        MockMatcher::any().model("gpt-5-mini"),
+        vec![
+            MockOutcome::error(ProviderError::Transport {
+                provider: ProviderId::Openai,
+                request_id: None,
+                message: "connection reset".to_string(),
+                source: None,
+            }),
+            MockOutcome::text("recovered"),
+        ],
+    );
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(adapter))
+        .build();
+    let req = request("gpt-5-mini", vec![user("hi")]);
+
+    assert!(matches!(
+        runtime.run(req.clone()).await,
+        Err(RuntimeError::TransportError { .. })
+    ));
+    for _ in 0..2 {
+        let response = runtime.run(req.clone()).await.expect("should recover");
+        assert_eq!(
+            response.output.content,
+            vec![ContentPart::Text {
+                text: "recovered".to_string()
+            }]
+        );
+    }
+
+    let unmatched = runtime
+        .run(request("gpt-5", vec![user("hi")]))
+        .await
+        .expect_err("unscripted model should fail");
+    assert!(unmatched.to_string().contains("no scripted mock response"));
+}
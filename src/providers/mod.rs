@@ -1,9 +1,16 @@
 pub mod anthropic;
 pub(crate) mod anthropic_translate;
+#[cfg(feature = "oauth")]
+pub mod auth;
+pub(crate) mod embeddings;
+pub(crate) mod json_extract;
+pub mod mock;
 pub mod openai;
 pub(crate) mod openai_translate;
 pub mod openrouter;
 pub(crate) mod openrouter_translate;
+pub(crate) mod request_options;
+pub mod translate;
 pub(crate) mod translator_contract;
 
 #[cfg(test)]
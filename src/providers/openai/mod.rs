@@ -1,54 +1,242 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use serde_json::Value;
 
 use crate::core::error::{ConfigError, ProviderError};
 use crate::core::traits::ProviderAdapter;
 use crate::core::types::{
-    AdapterContext, DiscoveryOptions, ModelInfo, ProviderCapabilities, ProviderId, ProviderRequest,
-    ProviderResponse,
+    AdapterContext, AudioSpeechRequest, AudioSpeechResponse, AudioTranscriptionRequest,
+    AudioTranscriptionResponse, DiscoveryOptions, EmbeddingRequest, EmbeddingResponse,
+    ImageGenerationRequest, ImageGenerationResponse, ModelInfo, ModerationRequest,
+    ModerationResponse, ProviderCapabilities, ProviderId, ProviderRequest, ProviderResponse,
+    RuntimeWarning,
 };
+use crate::providers::embeddings::{decode_embedding_response, encode_embedding_request};
 use crate::providers::openai_translate::{
-    OpenAiDecodeEnvelope, OpenAiTranslator, decode_openai_models_list, format_openai_error_message,
-    parse_openai_error_envelope,
+    OpenAiDecodeEnvelope, OpenAiTranslateOptions, OpenAiTranslator, decode_openai_image_response,
+    decode_openai_models_list, decode_openai_moderation_response,
+    decode_openai_transcription_response, encode_openai_image_request,
+    encode_openai_moderation_request, encode_openai_speech_request,
+    encode_openai_transcription_form, format_openai_error_message, parse_openai_error_envelope,
 };
 use crate::providers::translator_contract::ProviderTranslator;
-use crate::transport::http::{HttpTransport, RetryPolicy};
+use crate::registry::{ACCESS_TOKEN_METADATA_KEY, ChainCredentialStore, credentials};
+use crate::runtime::trace;
+use crate::transport::Transport;
+use crate::transport::http::{HttpTransport, JsonResponse, RetryPolicy};
 
 const OPENAI_DEFAULT_BASE_URL: &str = "https://api.openai.com";
 const OPENAI_API_KEY_ENV: &str = "OPENAI_API_KEY";
 const OPENAI_API_KEY_METADATA: &str = "openai.api_key";
+const OPENAI_DEFAULT_TIMEOUT_MS: u64 = 30_000;
 const TRANSPORT_AUTH_BEARER_TOKEN_KEY: &str = "transport.auth.bearer_token";
+const TRANSPORT_HEADER_OPENAI_ORGANIZATION: &str = "transport.header.openai-organization";
+const TRANSPORT_HEADER_OPENAI_PROJECT: &str = "transport.header.openai-project";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReasoningEffort {
+    Minimal,
+    Low,
+    Medium,
+    High,
+}
+
+impl ReasoningEffort {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Minimal => "minimal",
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReasoningSummary {
+    Auto,
+    Concise,
+    Detailed,
+}
+
+impl ReasoningSummary {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Concise => "concise",
+            Self::Detailed => "detailed",
+        }
+    }
+}
+
+/// Processing tier for Responses requests. `Flex` trades latency for lower prices; `Priority`
+/// the reverse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceTier {
+    Auto,
+    Default,
+    Flex,
+    Priority,
+}
+
+impl ServiceTier {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Default => "default",
+            Self::Flex => "flex",
+            Self::Priority => "priority",
+        }
+    }
+}
+
+/// What the Responses API does when input exceeds the model's context window: `Auto` drops
+/// items from the start of the conversation, `Disabled` fails the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Truncation {
+    Auto,
+    Disabled,
+}
+
+impl Truncation {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Disabled => "disabled",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OpenAiAdapterOptions {
+    pub reasoning_effort: Option<ReasoningEffort>,
+    pub reasoning_summary: Option<ReasoningSummary>,
+    /// When a request asks for `ResponseFormat::JsonObject` but no message mentions "JSON",
+    /// inject a system instruction (with a warning) instead of rejecting the request.
+    pub inject_json_instruction: bool,
+    /// Sent as the `OpenAI-Organization` header.
+    pub organization: Option<String>,
+    /// Sent as the `OpenAI-Project` header.
+    pub project: Option<String>,
+    pub service_tier: Option<ServiceTier>,
+    pub parallel_tool_calls: Option<bool>,
+    pub truncation: Option<Truncation>,
+    /// Accept requests with `stop` sequences by truncating the decoded output at the first one
+    /// and reporting `FinishReason::Stop` with a `stop_sequence_emulated` warning. The model
+    /// still generates (and bills) the text past the stop, so prefer a tight
+    /// `max_output_tokens`. When disabled, requests with stop sequences are rejected.
+    pub emulate_stop_sequences: bool,
+    /// Send `store: true` so OpenAI keeps each response. A follow-up request can then set the
+    /// `previous_response_id` provider option to `ProviderResponse::response_id` and carry only
+    /// the new messages; the server supplies the earlier turns.
+    pub store: bool,
+    /// Per-attempt HTTP timeout; defaults to 30 seconds. Ignored by `with_transport`.
+    pub timeout_ms: Option<u64>,
+    /// Ignored by `with_transport`, where the caller's transport owns retries.
+    pub retry_policy: Option<RetryPolicy>,
+}
+
+impl OpenAiAdapterOptions {
+    fn validate(&self) -> Result<(), ConfigError> {
+        for (field, value) in [
+            ("organization", &self.organization),
+            ("project", &self.project),
+        ] {
+            if let Some(value) = value
+                && value.trim().is_empty()
+            {
+                return Err(ConfigError::InvalidProviderConfig {
+                    provider: ProviderId::Openai,
+                    reason: format!("{field} must be non-empty when provided"),
+                });
+            }
+        }
+
+        if let Some(retry_policy) = &self.retry_policy {
+            retry_policy.validate()?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn to_translate_options(&self) -> OpenAiTranslateOptions {
+        OpenAiTranslateOptions {
+            reasoning_effort: self
+                .reasoning_effort
+                .map(|effort| effort.as_str().to_string()),
+            reasoning_summary: self
+                .reasoning_summary
+                .map(|summary| summary.as_str().to_string()),
+            inject_json_instruction: self.inject_json_instruction,
+            service_tier: self.service_tier.map(|tier| tier.as_str().to_string()),
+            parallel_tool_calls: self.parallel_tool_calls,
+            truncation: self
+                .truncation
+                .map(|truncation| truncation.as_str().to_string()),
+            emulate_stop_sequences: self.emulate_stop_sequences,
+            store: self.store,
+        }
+    }
+}
 
 pub struct OpenAiAdapter {
-    transport: HttpTransport,
+    transport: Arc<dyn Transport>,
     translator: OpenAiTranslator,
     base_url: String,
-    api_key: Option<String>,
+    credentials: ChainCredentialStore,
+    organization: Option<String>,
+    project: Option<String>,
 }
 
 impl OpenAiAdapter {
     pub fn new(api_key: Option<String>) -> Result<Self, ConfigError> {
-        Self::with_base_url(api_key, OPENAI_DEFAULT_BASE_URL)
+        Self::with_base_url_and_options(api_key, OPENAI_DEFAULT_BASE_URL, Default::default())
     }
 
     pub fn with_base_url(
         api_key: Option<String>,
         base_url: impl Into<String>,
     ) -> Result<Self, ConfigError> {
-        let transport = HttpTransport::new(30_000, RetryPolicy::default())?;
-        Ok(Self::with_transport(api_key, base_url, transport))
+        Self::with_base_url_and_options(api_key, base_url, Default::default())
+    }
+
+    pub fn with_base_url_and_options(
+        api_key: Option<String>,
+        base_url: impl Into<String>,
+        options: OpenAiAdapterOptions,
+    ) -> Result<Self, ConfigError> {
+        options.validate()?;
+        let transport = HttpTransport::new(
+            options.timeout_ms.unwrap_or(OPENAI_DEFAULT_TIMEOUT_MS),
+            options.retry_policy.clone().unwrap_or_default(),
+        )?;
+        Ok(Self::with_transport(
+            api_key,
+            base_url,
+            options,
+            Arc::new(transport),
+        ))
     }
 
-    pub(crate) fn with_transport(
+    /// Builds the adapter over a caller-supplied transport, such as a test fake or a gateway
+    /// client.
+    pub fn with_transport(
         api_key: Option<String>,
         base_url: impl Into<String>,
-        transport: HttpTransport,
+        options: OpenAiAdapterOptions,
+        transport: Arc<dyn Transport>,
     ) -> Self {
         Self {
             transport,
-            translator: OpenAiTranslator,
+            translator: OpenAiTranslator::new(options.to_translate_options()),
             base_url: normalize_base_url(base_url),
-            api_key: sanitize_api_key(api_key),
+            credentials: ChainCredentialStore::adapter_default(
+                ProviderId::Openai,
+                sanitize_api_key(api_key),
+            ),
+            organization: options.organization,
+            project: options.project,
         }
     }
 
@@ -56,24 +244,36 @@ impl OpenAiAdapter {
         format!("{}/v1/responses", self.base_url)
     }
 
+    fn embeddings_url(&self) -> String {
+        format!("{}/v1/embeddings", self.base_url)
+    }
+
+    fn moderations_url(&self) -> String {
+        format!("{}/v1/moderations", self.base_url)
+    }
+
+    fn images_url(&self) -> String {
+        format!("{}/v1/images/generations", self.base_url)
+    }
+
+    fn speech_url(&self) -> String {
+        format!("{}/v1/audio/speech", self.base_url)
+    }
+
+    fn transcriptions_url(&self) -> String {
+        format!("{}/v1/audio/transcriptions", self.base_url)
+    }
+
     fn models_url(&self) -> String {
         format!("{}/v1/models", self.base_url)
     }
 
-    fn resolve_api_key(&self, ctx: &AdapterContext) -> Option<String> {
-        if let Some(key) = self.api_key.as_ref().cloned() {
-            return Some(key);
-        }
-
-        if let Some(key) = ctx.metadata.get(OPENAI_API_KEY_METADATA) {
-            if !key.trim().is_empty() {
-                return Some(key.clone());
-            }
+    /// A runtime-supplied access token is sent as the bearer credential in place of an API key.
+    fn resolve_api_key(&self, ctx: &AdapterContext) -> Result<Option<String>, ProviderError> {
+        if let Some(token) = ctx.metadata.get(ACCESS_TOKEN_METADATA_KEY) {
+            return Ok(Some(token.clone()));
         }
-
-        std::env::var(OPENAI_API_KEY_ENV)
-            .ok()
-            .and_then(|value| sanitize_api_key(Some(value)))
+        credentials::resolve_api_key(&ProviderId::Openai, &self.credentials, ctx)
     }
 
     fn missing_api_key_error(model: Option<&str>) -> ProviderError {
@@ -87,6 +287,25 @@ impl OpenAiAdapter {
         }
     }
 
+    fn attach_transport_context(&self, ctx: &AdapterContext, api_key: String) -> AdapterContext {
+        let mut request_ctx = ctx.clone();
+        request_ctx
+            .metadata
+            .insert(TRANSPORT_AUTH_BEARER_TOKEN_KEY.to_string(), api_key);
+        if let Some(organization) = &self.organization {
+            request_ctx.metadata.insert(
+                TRANSPORT_HEADER_OPENAI_ORGANIZATION.to_string(),
+                organization.clone(),
+            );
+        }
+        if let Some(project) = &self.project {
+            request_ctx
+                .metadata
+                .insert(TRANSPORT_HEADER_OPENAI_PROJECT.to_string(), project.clone());
+        }
+        request_ctx
+    }
+
     fn normalize_transport_error(
         error: ProviderError,
         requested_model: Option<&str>,
@@ -97,6 +316,7 @@ impl OpenAiAdapter {
                 request_id,
                 message,
                 model,
+                rate_limit,
                 ..
             } => {
                 let model = requested_model.map(str::to_string).or(model);
@@ -111,22 +331,24 @@ impl OpenAiAdapter {
                         };
                     }
 
-                    return ProviderError::Status {
-                        provider: ProviderId::Openai,
+                    return ProviderError::from_status(
+                        ProviderId::Openai,
                         model,
                         status_code,
                         request_id,
                         message,
-                    };
+                        rate_limit,
+                    );
                 }
 
-                ProviderError::Status {
-                    provider: ProviderId::Openai,
+                ProviderError::from_status(
+                    ProviderId::Openai,
                     model,
                     status_code,
                     request_id,
                     message,
-                }
+                    rate_limit,
+                )
             }
             other => other,
         }
@@ -143,28 +365,33 @@ impl ProviderAdapter for OpenAiAdapter {
         ProviderCapabilities {
             supports_tools: true,
             supports_structured_output: true,
-            supports_thinking: false,
+            supports_thinking: true,
             supports_remote_discovery: true,
+            supports_parallel_tool_calls: true,
         }
     }
 
+    fn encode_warnings(&self, req: &ProviderRequest) -> Result<Vec<RuntimeWarning>, ProviderError> {
+        self.translator
+            .encode_request(req)
+            .map(|encoded| encoded.warnings)
+    }
+
     async fn run(
         &self,
         req: &ProviderRequest,
         ctx: &AdapterContext,
     ) -> Result<ProviderResponse, ProviderError> {
         let api_key = self
-            .resolve_api_key(ctx)
+            .resolve_api_key(ctx)?
             .ok_or_else(|| Self::missing_api_key_error(Some(&req.model.model_id)))?;
 
         let encoded = self.translator.encode_request(req)?;
 
-        let mut request_ctx = ctx.clone();
-        request_ctx
-            .metadata
-            .insert(TRANSPORT_AUTH_BEARER_TOKEN_KEY.to_string(), api_key);
+        let request_ctx = self.attach_transport_context(ctx, api_key);
 
-        let response_body: Value = self
+        trace::record_encoded_request(&encoded.body);
+        let response: JsonResponse<Value> = self
             .transport
             .post_json(
                 ProviderId::Openai,
@@ -176,9 +403,12 @@ impl ProviderAdapter for OpenAiAdapter {
             .await
             .map_err(|error| Self::normalize_transport_error(error, Some(&req.model.model_id)))?;
 
+        let response_body = response.body;
+        trace::record_raw_response(&response_body);
         let envelope = OpenAiDecodeEnvelope {
             body: response_body,
             requested_response_format: req.response_format.clone(),
+            stop_sequences: req.stop.clone(),
         };
 
         let mut decoded = self.translator.decode_response(&envelope)?;
@@ -188,28 +418,178 @@ impl ProviderAdapter for OpenAiAdapter {
             decoded.warnings = warnings;
         }
 
+        decoded.request_id = response.request_id;
+        decoded.rate_limit = response.rate_limit;
         Ok(decoded)
     }
 
+    async fn embed(
+        &self,
+        req: &EmbeddingRequest,
+        ctx: &AdapterContext,
+    ) -> Result<Option<EmbeddingResponse>, ProviderError> {
+        let api_key = self
+            .resolve_api_key(ctx)?
+            .ok_or_else(|| Self::missing_api_key_error(Some(&req.model.model_id)))?;
+
+        let body = encode_embedding_request(&ProviderId::Openai, req)?;
+        let request_ctx = self.attach_transport_context(ctx, api_key);
+
+        let response = self
+            .transport
+            .post_json(
+                ProviderId::Openai,
+                Some(req.model.model_id.as_str()),
+                &self.embeddings_url(),
+                &body,
+                &request_ctx,
+            )
+            .await
+            .map_err(|error| Self::normalize_transport_error(error, Some(&req.model.model_id)))?;
+
+        let mut decoded = decode_embedding_response(&ProviderId::Openai, req, &response.body)?;
+        decoded.request_id = response.request_id;
+        Ok(Some(decoded))
+    }
+
+    async fn moderate(
+        &self,
+        req: &ModerationRequest,
+        ctx: &AdapterContext,
+    ) -> Result<Option<ModerationResponse>, ProviderError> {
+        let api_key = self
+            .resolve_api_key(ctx)?
+            .ok_or_else(|| Self::missing_api_key_error(Some(&req.model.model_id)))?;
+
+        let body = encode_openai_moderation_request(req)?;
+        let request_ctx = self.attach_transport_context(ctx, api_key);
+
+        let response = self
+            .transport
+            .post_json(
+                ProviderId::Openai,
+                Some(req.model.model_id.as_str()),
+                &self.moderations_url(),
+                &body,
+                &request_ctx,
+            )
+            .await
+            .map_err(|error| Self::normalize_transport_error(error, Some(&req.model.model_id)))?;
+
+        let mut decoded = decode_openai_moderation_response(req, &response.body)?;
+        decoded.request_id = response.request_id;
+        Ok(Some(decoded))
+    }
+
+    async fn generate_images(
+        &self,
+        req: &ImageGenerationRequest,
+        ctx: &AdapterContext,
+    ) -> Result<Option<ImageGenerationResponse>, ProviderError> {
+        let api_key = self
+            .resolve_api_key(ctx)?
+            .ok_or_else(|| Self::missing_api_key_error(Some(&req.model.model_id)))?;
+
+        let body = encode_openai_image_request(req)?;
+        let request_ctx = self.attach_transport_context(ctx, api_key);
+
+        let response = self
+            .transport
+            .post_json(
+                ProviderId::Openai,
+                Some(req.model.model_id.as_str()),
+                &self.images_url(),
+                &body,
+                &request_ctx,
+            )
+            .await
+            .map_err(|error| Self::normalize_transport_error(error, Some(&req.model.model_id)))?;
+
+        let mut decoded = decode_openai_image_response(req, &response.body)?;
+        decoded.request_id = response.request_id;
+        Ok(Some(decoded))
+    }
+
+    async fn synthesize_speech(
+        &self,
+        req: &AudioSpeechRequest,
+        ctx: &AdapterContext,
+    ) -> Result<Option<AudioSpeechResponse>, ProviderError> {
+        let api_key = self
+            .resolve_api_key(ctx)?
+            .ok_or_else(|| Self::missing_api_key_error(Some(&req.model.model_id)))?;
+
+        let body = encode_openai_speech_request(req)?;
+        let request_ctx = self.attach_transport_context(ctx, api_key);
+
+        let response = self
+            .transport
+            .post_json_binary(
+                ProviderId::Openai,
+                Some(req.model.model_id.as_str()),
+                &self.speech_url(),
+                &body,
+                &request_ctx,
+            )
+            .await
+            .map_err(|error| Self::normalize_transport_error(error, Some(&req.model.model_id)))?;
+
+        Ok(Some(AudioSpeechResponse {
+            audio: response.body,
+            media_type: response.content_type,
+            provider: ProviderId::Openai,
+            model: req.model.model_id.clone(),
+            request_id: response.request_id,
+            warnings: Vec::new(),
+        }))
+    }
+
+    async fn transcribe(
+        &self,
+        req: &AudioTranscriptionRequest,
+        ctx: &AdapterContext,
+    ) -> Result<Option<AudioTranscriptionResponse>, ProviderError> {
+        let api_key = self
+            .resolve_api_key(ctx)?
+            .ok_or_else(|| Self::missing_api_key_error(Some(&req.model.model_id)))?;
+
+        let form = encode_openai_transcription_form(req)?;
+        let request_ctx = self.attach_transport_context(ctx, api_key);
+
+        let response = self
+            .transport
+            .post_multipart(
+                ProviderId::Openai,
+                Some(req.model.model_id.as_str()),
+                &self.transcriptions_url(),
+                &form,
+                &request_ctx,
+            )
+            .await
+            .map_err(|error| Self::normalize_transport_error(error, Some(&req.model.model_id)))?;
+
+        let mut decoded = decode_openai_transcription_response(req, &response.body)?;
+        decoded.request_id = response.request_id;
+        Ok(Some(decoded))
+    }
+
     async fn discover_models(
         &self,
         _opts: &DiscoveryOptions,
         ctx: &AdapterContext,
     ) -> Result<Vec<ModelInfo>, ProviderError> {
         let api_key = self
-            .resolve_api_key(ctx)
+            .resolve_api_key(ctx)?
             .ok_or_else(|| Self::missing_api_key_error(None))?;
 
-        let mut request_ctx = ctx.clone();
-        request_ctx
-            .metadata
-            .insert(TRANSPORT_AUTH_BEARER_TOKEN_KEY.to_string(), api_key);
+        let request_ctx = self.attach_transport_context(ctx, api_key);
 
-        let payload: Value = self
+        let payload = self
             .transport
             .get_json(ProviderId::Openai, None, &self.models_url(), &request_ctx)
             .await
-            .map_err(|error| Self::normalize_transport_error(error, None))?;
+            .map_err(|error| Self::normalize_transport_error(error, None))?
+            .body;
 
         decode_openai_models_list(&payload, &self.capabilities())
     }
@@ -6,14 +6,20 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
-use crate::core::error::ProviderError;
+use crate::core::error::{ConfigError, ProviderError};
 use crate::core::traits::ProviderAdapter;
 use crate::core::types::{
-    AdapterContext, ContentPart, DiscoveryOptions, Message, MessageRole, ModelRef, ProviderId,
-    ProviderRequest, ResponseFormat, ToolChoice,
+    AdapterContext, AudioSpeechRequest, AudioTranscriptionRequest, ContentPart, DiscoveryOptions,
+    EmbeddingRequest, ImageGenerationRequest, ImageSource, Message, MessageRole, ModelRef,
+    ProviderId, ProviderRequest, ResponseFormat, ToolChoice,
 };
-use crate::providers::openai::OpenAiAdapter;
-use crate::transport::http::{HttpTransport, RetryPolicy};
+use crate::providers::openai::{
+    OpenAiAdapter, OpenAiAdapterOptions, ReasoningEffort, ReasoningSummary, ServiceTier, Truncation,
+};
+use crate::transport::http::{
+    BinaryResponse, HttpTransport, JitterMode, JsonResponse, RetryPolicy,
+};
+use crate::transport::{MultipartForm, SseStream, Transport};
 
 #[derive(Debug, Clone)]
 struct MockResponse {
@@ -128,21 +134,28 @@ fn base_request() -> ProviderRequest {
         model: ModelRef {
             provider_hint: Some(ProviderId::Openai),
             model_id: "gpt-5-mini".to_string(),
+            requirements: None,
         },
         messages: vec![Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "hello".to_string(),
             }],
+            cache_hint: None,
         }],
         tools: Vec::new(),
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::Text,
         temperature: None,
         top_p: None,
         max_output_tokens: None,
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     }
 }
 
@@ -153,10 +166,35 @@ fn test_openai_adapter_capabilities() {
     let capabilities = adapter.capabilities();
     assert!(capabilities.supports_tools);
     assert!(capabilities.supports_structured_output);
-    assert!(!capabilities.supports_thinking);
+    assert!(capabilities.supports_thinking);
     assert!(capabilities.supports_remote_discovery);
 }
 
+#[test]
+fn test_openai_adapter_options_map_reasoning_controls() {
+    let options = OpenAiAdapterOptions {
+        reasoning_effort: Some(ReasoningEffort::Low),
+        reasoning_summary: Some(ReasoningSummary::Detailed),
+        inject_json_instruction: true,
+        ..OpenAiAdapterOptions::default()
+    };
+
+    let translate_options = options.to_translate_options();
+    assert_eq!(translate_options.reasoning_effort.as_deref(), Some("low"));
+    assert_eq!(
+        translate_options.reasoning_summary.as_deref(),
+        Some("detailed")
+    );
+    assert!(translate_options.inject_json_instruction);
+
+    OpenAiAdapter::with_base_url_and_options(
+        Some("test-key".to_string()),
+        "http://example.com",
+        options,
+    )
+    .expect("adapter with options");
+}
+
 #[tokio::test]
 async fn test_openai_adapter_missing_key_error() {
     let adapter = OpenAiAdapter::with_base_url(None, "http://127.0.0.1:1").expect("create adapter");
@@ -193,6 +231,9 @@ async fn test_openai_adapter_uses_translator_boundary() {
             initial_backoff_ms: 0,
             max_backoff_ms: 0,
             retryable_status_codes: vec![429],
+            max_retry_after_ms: 0,
+            max_total_retry_duration_ms: None,
+            jitter: JitterMode::None,
         },
     )
     .expect("create transport");
@@ -200,7 +241,8 @@ async fn test_openai_adapter_uses_translator_boundary() {
     let adapter = OpenAiAdapter::with_transport(
         Some("test-key".to_string()),
         "http://127.0.0.1:1",
-        transport,
+        OpenAiAdapterOptions::default(),
+        Arc::new(transport),
     );
 
     let mut req = base_request();
@@ -245,12 +287,19 @@ async fn test_openai_adapter_propagates_encode_warnings() {
             initial_backoff_ms: 0,
             max_backoff_ms: 0,
             retryable_status_codes: vec![429],
+            max_retry_after_ms: 0,
+            max_total_retry_duration_ms: None,
+            jitter: JitterMode::None,
         },
     )
     .expect("create transport");
 
-    let adapter =
-        OpenAiAdapter::with_transport(Some("test-key".to_string()), server.url(), transport);
+    let adapter = OpenAiAdapter::with_transport(
+        Some("test-key".to_string()),
+        server.url(),
+        OpenAiAdapterOptions::default(),
+        Arc::new(transport),
+    );
 
     let mut req = base_request();
     req.temperature = Some(0.2);
@@ -277,6 +326,47 @@ async fn test_openai_adapter_propagates_encode_warnings() {
     );
 }
 
+#[tokio::test]
+async fn test_openai_adapter_returns_reasoning_as_thinking() {
+    let mut server = MockServer::start(vec![MockResponse::new(
+        200,
+        Vec::new(),
+        r#"{
+            "status": "completed",
+            "model": "gpt-5-mini",
+            "output": [
+                {
+                    "type": "reasoning",
+                    "summary": [{ "type": "summary_text", "text": "weighing options" }],
+                    "encrypted_content": "enc_1"
+                },
+                {
+                    "type": "message",
+                    "role": "assistant",
+                    "content": [{ "type": "output_text", "text": "done" }]
+                }
+            ],
+            "usage": { "input_tokens": 5, "output_tokens": 9, "total_tokens": 14 }
+        }"#,
+    )]);
+    let adapter = OpenAiAdapter::with_base_url(Some("test-key".to_string()), server.url())
+        .expect("create adapter");
+    assert!(adapter.capabilities().supports_thinking);
+
+    let response = adapter
+        .run(&base_request(), &AdapterContext::default())
+        .await
+        .expect("reasoning response should decode");
+
+    assert!(matches!(
+        &response.output.content[0],
+        ContentPart::Thinking { text, signature }
+            if text.contains("weighing options") && signature.as_deref() == Some("enc_1")
+    ));
+
+    server.shutdown();
+}
+
 #[tokio::test]
 async fn test_openai_adapter_maps_auth_status_to_credentials_rejected() {
     let mut server = MockServer::start(vec![MockResponse::new(
@@ -317,10 +407,13 @@ async fn test_openai_adapter_maps_auth_status_to_credentials_rejected() {
 }
 
 #[tokio::test]
-async fn test_openai_adapter_maps_non_auth_status_to_normalized_status() {
+async fn test_openai_adapter_maps_429_to_rate_limited() {
     let mut server = MockServer::start(vec![MockResponse::new(
         429,
-        vec![("x-request-id".to_string(), "req-rate-1".to_string())],
+        vec![
+            ("x-request-id".to_string(), "req-rate-1".to_string()),
+            ("retry-after".to_string(), "2".to_string()),
+        ],
         r#"{
             "error": {
                 "message": "Rate limit exceeded",
@@ -337,11 +430,18 @@ async fn test_openai_adapter_maps_non_auth_status_to_normalized_status() {
             initial_backoff_ms: 0,
             max_backoff_ms: 0,
             retryable_status_codes: vec![429],
+            max_retry_after_ms: 0,
+            max_total_retry_duration_ms: None,
+            jitter: JitterMode::None,
         },
     )
     .expect("create transport");
-    let adapter =
-        OpenAiAdapter::with_transport(Some("test-key".to_string()), server.url(), transport);
+    let adapter = OpenAiAdapter::with_transport(
+        Some("test-key".to_string()),
+        server.url(),
+        OpenAiAdapterOptions::default(),
+        Arc::new(transport),
+    );
 
     let err = adapter
         .run(&base_request(), &AdapterContext::default())
@@ -349,22 +449,23 @@ async fn test_openai_adapter_maps_non_auth_status_to_normalized_status() {
         .expect_err("rate limit should fail");
 
     match err {
-        ProviderError::Status {
+        ProviderError::RateLimited {
             provider,
             model,
-            status_code,
             request_id,
+            retry_after,
             message,
+            ..
         } => {
             assert_eq!(provider, ProviderId::Openai);
             assert_eq!(model, Some("gpt-5-mini".to_string()));
-            assert_eq!(status_code, 429);
+            assert_eq!(retry_after, Some(Duration::from_secs(2)));
             assert_eq!(request_id, Some("req-rate-1".to_string()));
             assert!(message.contains("Rate limit exceeded"));
             assert!(message.contains("type=rate_limit_error"));
             assert!(message.contains("param=model"));
         }
-        other => panic!("expected status error, got {other:?}"),
+        other => panic!("expected rate limited error, got {other:?}"),
     }
 
     server.shutdown();
@@ -373,7 +474,7 @@ async fn test_openai_adapter_maps_non_auth_status_to_normalized_status() {
 #[tokio::test]
 async fn test_openai_adapter_status_fallback_when_error_body_is_not_json() {
     let mut server = MockServer::start(vec![MockResponse::new(
-        429,
+        503,
         vec![("x-request-id".to_string(), "req-raw-1".to_string())],
         "not-json",
     )]);
@@ -383,12 +484,19 @@ async fn test_openai_adapter_status_fallback_when_error_body_is_not_json() {
             max_attempts: 1,
             initial_backoff_ms: 0,
             max_backoff_ms: 0,
-            retryable_status_codes: vec![429],
+            retryable_status_codes: vec![503],
+            max_retry_after_ms: 0,
+            max_total_retry_duration_ms: None,
+            jitter: JitterMode::None,
         },
     )
     .expect("create transport");
-    let adapter =
-        OpenAiAdapter::with_transport(Some("test-key".to_string()), server.url(), transport);
+    let adapter = OpenAiAdapter::with_transport(
+        Some("test-key".to_string()),
+        server.url(),
+        OpenAiAdapterOptions::default(),
+        Arc::new(transport),
+    );
 
     let err = adapter
         .run(&base_request(), &AdapterContext::default())
@@ -402,7 +510,7 @@ async fn test_openai_adapter_status_fallback_when_error_body_is_not_json() {
             message,
             ..
         } => {
-            assert_eq!(status_code, 429);
+            assert_eq!(status_code, 503);
             assert_eq!(request_id, Some("req-raw-1".to_string()));
             assert_eq!(message, "not-json");
         }
@@ -606,3 +714,507 @@ fn status_reason(status_code: u16) -> &'static str {
         _ => "Unknown",
     }
 }
+
+/// Serves a canned JSON body and records the requests it was given, without any network I/O.
+struct FakeTransport {
+    body: serde_json::Value,
+    calls: Mutex<Vec<(String, Option<serde_json::Value>, AdapterContext)>>,
+}
+
+#[async_trait::async_trait]
+impl Transport for FakeTransport {
+    async fn post_json(
+        &self,
+        _provider: ProviderId,
+        _model: Option<&str>,
+        url: &str,
+        body: &serde_json::Value,
+        ctx: &AdapterContext,
+    ) -> Result<JsonResponse<serde_json::Value>, ProviderError> {
+        self.calls.lock().expect("calls lock").push((
+            url.to_string(),
+            Some(body.clone()),
+            ctx.clone(),
+        ));
+        Ok(JsonResponse {
+            body: self.body.clone(),
+            request_id: Some("fake-req".to_string()),
+            rate_limit: None,
+        })
+    }
+
+    async fn get_json(
+        &self,
+        _provider: ProviderId,
+        _model: Option<&str>,
+        url: &str,
+        ctx: &AdapterContext,
+    ) -> Result<JsonResponse<serde_json::Value>, ProviderError> {
+        self.calls
+            .lock()
+            .expect("calls lock")
+            .push((url.to_string(), None, ctx.clone()));
+        Ok(JsonResponse {
+            body: self.body.clone(),
+            request_id: None,
+            rate_limit: None,
+        })
+    }
+
+    async fn post_sse(
+        &self,
+        provider: ProviderId,
+        _model: Option<&str>,
+        _url: &str,
+        _body: &serde_json::Value,
+        _ctx: &AdapterContext,
+    ) -> Result<SseStream, ProviderError> {
+        Err(ProviderError::Transport {
+            provider,
+            request_id: None,
+            message: "streaming is not faked".to_string(),
+            source: None,
+        })
+    }
+
+    async fn post_json_binary(
+        &self,
+        _provider: ProviderId,
+        _model: Option<&str>,
+        url: &str,
+        body: &serde_json::Value,
+        ctx: &AdapterContext,
+    ) -> Result<BinaryResponse, ProviderError> {
+        self.calls.lock().expect("calls lock").push((
+            url.to_string(),
+            Some(body.clone()),
+            ctx.clone(),
+        ));
+        Ok(BinaryResponse {
+            body: self.body.as_str().unwrap_or_default().as_bytes().to_vec(),
+            content_type: Some("audio/mpeg".to_string()),
+            request_id: Some("fake-req".to_string()),
+            rate_limit: None,
+        })
+    }
+
+    /// Records the form's file name and its text fields as a JSON object.
+    async fn post_multipart(
+        &self,
+        _provider: ProviderId,
+        _model: Option<&str>,
+        url: &str,
+        form: &MultipartForm,
+        ctx: &AdapterContext,
+    ) -> Result<JsonResponse<serde_json::Value>, ProviderError> {
+        let mut recorded = serde_json::Map::new();
+        if let Some((filename, _)) = form.file_field("file") {
+            recorded.insert("file".to_string(), serde_json::json!(filename));
+        }
+        for name in [
+            "model",
+            "response_format",
+            "language",
+            "prompt",
+            "temperature",
+        ] {
+            if let Some(value) = form.text_field(name) {
+                recorded.insert(name.to_string(), serde_json::json!(value));
+            }
+        }
+        self.calls.lock().expect("calls lock").push((
+            url.to_string(),
+            Some(serde_json::Value::Object(recorded)),
+            ctx.clone(),
+        ));
+        Ok(JsonResponse {
+            body: self.body.clone(),
+            request_id: Some("fake-req".to_string()),
+            rate_limit: None,
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_openai_adapter_runs_over_custom_transport() {
+    let transport = Arc::new(FakeTransport {
+        body: serde_json::json!({
+            "status": "completed",
+            "model": "gpt-5-mini",
+            "output": [{
+                "type": "message",
+                "role": "assistant",
+                "content": [{ "type": "output_text", "text": "faked" }]
+            }],
+            "usage": { "input_tokens": 1, "output_tokens": 1, "total_tokens": 2 }
+        }),
+        calls: Mutex::new(Vec::new()),
+    });
+    let adapter = OpenAiAdapter::with_transport(
+        Some("test-key".to_string()),
+        "http://gateway.internal",
+        OpenAiAdapterOptions::default(),
+        transport.clone(),
+    );
+
+    let response = adapter
+        .run(&base_request(), &AdapterContext::default())
+        .await
+        .expect("run should succeed");
+
+    assert_eq!(response.request_id.as_deref(), Some("fake-req"));
+    let calls = transport.calls.lock().expect("calls lock");
+    assert_eq!(calls.len(), 1);
+    let (url, body, ctx) = &calls[0];
+    assert_eq!(url, "http://gateway.internal/v1/responses");
+    assert_eq!(
+        body.as_ref().and_then(|body| body.get("model")),
+        Some(&serde_json::json!("gpt-5-mini"))
+    );
+    assert_eq!(
+        ctx.metadata.get("transport.auth.bearer_token"),
+        Some(&"test-key".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_openai_adapter_embed_posts_to_embeddings_endpoint() {
+    let transport = Arc::new(FakeTransport {
+        body: serde_json::json!({
+            "object": "list",
+            "model": "text-embedding-3-small",
+            "data": [{ "object": "embedding", "index": 0, "embedding": [0.1, 0.2, 0.3] }],
+            "usage": { "prompt_tokens": 3, "total_tokens": 3 }
+        }),
+        calls: Mutex::new(Vec::new()),
+    });
+    let adapter = OpenAiAdapter::with_transport(
+        Some("test-key".to_string()),
+        "http://gateway.internal",
+        OpenAiAdapterOptions::default(),
+        transport.clone(),
+    );
+    let request = EmbeddingRequest {
+        model: ModelRef {
+            provider_hint: Some(ProviderId::Openai),
+            model_id: "text-embedding-3-small".to_string(),
+            requirements: None,
+        },
+        inputs: vec!["hello".to_string()],
+        dimensions: Some(3),
+    };
+
+    let response = adapter
+        .embed(&request, &AdapterContext::default())
+        .await
+        .expect("embed should succeed")
+        .expect("openai supports embeddings");
+
+    assert_eq!(response.embeddings, vec![vec![0.1, 0.2, 0.3]]);
+    assert_eq!(response.usage.input_tokens, Some(3));
+    assert_eq!(response.request_id.as_deref(), Some("fake-req"));
+    let calls = transport.calls.lock().expect("calls lock");
+    let (url, body, _) = &calls[0];
+    assert_eq!(url, "http://gateway.internal/v1/embeddings");
+    assert_eq!(
+        body.as_ref().expect("post body"),
+        &serde_json::json!({
+            "model": "text-embedding-3-small",
+            "input": ["hello"],
+            "encoding_format": "float",
+            "dimensions": 3
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_openai_adapter_embed_rejects_mismatched_vector_count() {
+    let transport = Arc::new(FakeTransport {
+        body: serde_json::json!({ "data": [] }),
+        calls: Mutex::new(Vec::new()),
+    });
+    let adapter = OpenAiAdapter::with_transport(
+        Some("test-key".to_string()),
+        "http://gateway.internal",
+        OpenAiAdapterOptions::default(),
+        transport,
+    );
+    let request = EmbeddingRequest {
+        model: ModelRef {
+            provider_hint: None,
+            model_id: "text-embedding-3-small".to_string(),
+            requirements: None,
+        },
+        inputs: vec!["hello".to_string()],
+        dimensions: None,
+    };
+
+    let err = adapter
+        .embed(&request, &AdapterContext::default())
+        .await
+        .expect_err("missing vectors should fail");
+
+    assert!(matches!(err, ProviderError::Protocol { .. }));
+}
+
+#[tokio::test]
+async fn test_openai_adapter_generate_images_decodes_urls_and_inline_data() {
+    let transport = Arc::new(FakeTransport {
+        body: serde_json::json!({
+            "created": 1_713_833_628,
+            "output_format": "png",
+            "data": [
+                { "b64_json": "aGVsbG8=", "revised_prompt": "a red lighthouse at dusk" },
+                { "url": "https://images.example/1.png" }
+            ]
+        }),
+        calls: Mutex::new(Vec::new()),
+    });
+    let adapter = OpenAiAdapter::with_transport(
+        Some("test-key".to_string()),
+        "http://gateway.internal",
+        OpenAiAdapterOptions::default(),
+        transport.clone(),
+    );
+    let request = ImageGenerationRequest {
+        model: ModelRef {
+            provider_hint: Some(ProviderId::Openai),
+            model_id: "gpt-image-1".to_string(),
+            requirements: None,
+        },
+        prompt: "a lighthouse at dusk".to_string(),
+        n: Some(2),
+        size: Some("1024x1024".to_string()),
+        quality: None,
+    };
+
+    let response = adapter
+        .generate_images(&request, &AdapterContext::default())
+        .await
+        .expect("image generation should succeed")
+        .expect("openai supports image generation");
+
+    assert_eq!(response.images.len(), 2);
+    assert_eq!(response.images[0].bytes(), Some(b"hello".to_vec()));
+    assert_eq!(
+        response.images[0].revised_prompt.as_deref(),
+        Some("a red lighthouse at dusk")
+    );
+    assert!(matches!(
+        &response.images[0].source,
+        ImageSource::Base64 { media_type: Some(media_type), .. } if media_type == "image/png"
+    ));
+    assert_eq!(
+        response.images[1].source,
+        ImageSource::Url {
+            url: "https://images.example/1.png".to_string()
+        }
+    );
+    assert_eq!(response.request_id.as_deref(), Some("fake-req"));
+    let calls = transport.calls.lock().expect("calls lock");
+    let (url, body, _) = &calls[0];
+    assert_eq!(url, "http://gateway.internal/v1/images/generations");
+    assert_eq!(
+        body.as_ref().expect("post body"),
+        &serde_json::json!({
+            "model": "gpt-image-1",
+            "prompt": "a lighthouse at dusk",
+            "n": 2,
+            "size": "1024x1024"
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_openai_adapter_synthesize_speech_returns_audio_bytes() {
+    let transport = Arc::new(FakeTransport {
+        body: serde_json::json!("ID3-fake-mp3"),
+        calls: Mutex::new(Vec::new()),
+    });
+    let adapter = OpenAiAdapter::with_transport(
+        Some("test-key".to_string()),
+        "http://gateway.internal",
+        OpenAiAdapterOptions::default(),
+        transport.clone(),
+    );
+    let request = AudioSpeechRequest {
+        model: ModelRef {
+            provider_hint: Some(ProviderId::Openai),
+            model_id: "gpt-4o-mini-tts".to_string(),
+            requirements: None,
+        },
+        input: "Hello there.".to_string(),
+        voice: "alloy".to_string(),
+        format: Some("mp3".to_string()),
+        speed: None,
+        instructions: Some("Speak cheerfully.".to_string()),
+    };
+
+    let response = adapter
+        .synthesize_speech(&request, &AdapterContext::default())
+        .await
+        .expect("speech should succeed")
+        .expect("openai supports speech");
+
+    assert_eq!(response.audio, b"ID3-fake-mp3".to_vec());
+    assert_eq!(response.media_type.as_deref(), Some("audio/mpeg"));
+    assert_eq!(response.request_id.as_deref(), Some("fake-req"));
+    let calls = transport.calls.lock().expect("calls lock");
+    let (url, body, ctx) = &calls[0];
+    assert_eq!(url, "http://gateway.internal/v1/audio/speech");
+    assert_eq!(
+        body.as_ref().expect("post body"),
+        &serde_json::json!({
+            "model": "gpt-4o-mini-tts",
+            "input": "Hello there.",
+            "voice": "alloy",
+            "response_format": "mp3",
+            "instructions": "Speak cheerfully."
+        })
+    );
+    assert_eq!(
+        ctx.metadata.get("transport.auth.bearer_token"),
+        Some(&"test-key".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_openai_adapter_transcribe_uploads_audio_form() {
+    let transport = Arc::new(FakeTransport {
+        body: serde_json::json!({
+            "text": "Hello there.",
+            "usage": {
+                "type": "tokens",
+                "input_tokens": 14,
+                "output_tokens": 4,
+                "total_tokens": 18
+            }
+        }),
+        calls: Mutex::new(Vec::new()),
+    });
+    let adapter = OpenAiAdapter::with_transport(
+        Some("test-key".to_string()),
+        "http://gateway.internal",
+        OpenAiAdapterOptions::default(),
+        transport.clone(),
+    );
+    let request = AudioTranscriptionRequest {
+        model: ModelRef {
+            provider_hint: None,
+            model_id: "gpt-4o-transcribe".to_string(),
+            requirements: None,
+        },
+        audio: b"RIFF-fake-wav".to_vec(),
+        filename: "greeting.wav".to_string(),
+        media_type: Some("audio/wav".to_string()),
+        language: Some("en".to_string()),
+        prompt: None,
+        temperature: None,
+    };
+
+    let response = adapter
+        .transcribe(&request, &AdapterContext::default())
+        .await
+        .expect("transcription should succeed")
+        .expect("openai supports transcription");
+
+    assert_eq!(response.text, "Hello there.");
+    assert_eq!(response.usage.input_tokens, Some(14));
+    assert_eq!(response.usage.output_tokens, Some(4));
+    assert_eq!(response.request_id.as_deref(), Some("fake-req"));
+    let calls = transport.calls.lock().expect("calls lock");
+    let (url, form, _) = &calls[0];
+    assert_eq!(url, "http://gateway.internal/v1/audio/transcriptions");
+    assert_eq!(
+        form.as_ref().expect("form fields"),
+        &serde_json::json!({
+            "file": "greeting.wav",
+            "model": "gpt-4o-transcribe",
+            "response_format": "json",
+            "language": "en"
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_openai_adapter_options_set_org_headers_and_body_fields() {
+    let transport = Arc::new(FakeTransport {
+        body: serde_json::json!({
+            "status": "completed",
+            "model": "gpt-5-mini",
+            "output": [{
+                "type": "message",
+                "role": "assistant",
+                "content": [{ "type": "output_text", "text": "ok" }]
+            }],
+            "usage": { "input_tokens": 1, "output_tokens": 1, "total_tokens": 2 }
+        }),
+        calls: Mutex::new(Vec::new()),
+    });
+    let adapter = OpenAiAdapter::with_transport(
+        Some("test-key".to_string()),
+        "http://gateway.internal",
+        OpenAiAdapterOptions {
+            organization: Some("org-123".to_string()),
+            project: Some("proj_abc".to_string()),
+            service_tier: Some(ServiceTier::Flex),
+            parallel_tool_calls: Some(false),
+            truncation: Some(Truncation::Auto),
+            ..OpenAiAdapterOptions::default()
+        },
+        transport.clone(),
+    );
+
+    adapter
+        .run(&base_request(), &AdapterContext::default())
+        .await
+        .expect("run should succeed");
+
+    let calls = transport.calls.lock().expect("calls lock");
+    let (_, body, ctx) = &calls[0];
+    let body = body.as_ref().expect("post body");
+    assert_eq!(body["service_tier"], "flex");
+    assert_eq!(body["parallel_tool_calls"], false);
+    assert_eq!(body["truncation"], "auto");
+    assert_eq!(
+        ctx.metadata.get("transport.header.openai-organization"),
+        Some(&"org-123".to_string())
+    );
+    assert_eq!(
+        ctx.metadata.get("transport.header.openai-project"),
+        Some(&"proj_abc".to_string())
+    );
+}
+
+#[test]
+fn test_openai_adapter_options_reject_invalid_settings() {
+    let error = OpenAiAdapter::with_base_url_and_options(
+        Some("test-key".to_string()),
+        "http://example.com",
+        OpenAiAdapterOptions {
+            project: Some(" ".to_string()),
+            ..OpenAiAdapterOptions::default()
+        },
+    )
+    .err()
+    .expect("blank project should be rejected");
+    assert!(matches!(
+        error,
+        ConfigError::InvalidProviderConfig { ref reason, .. } if reason.contains("project")
+    ));
+
+    let error = OpenAiAdapter::with_base_url_and_options(
+        Some("test-key".to_string()),
+        "http://example.com",
+        OpenAiAdapterOptions {
+            retry_policy: Some(RetryPolicy {
+                max_attempts: 0,
+                ..RetryPolicy::default()
+            }),
+            ..OpenAiAdapterOptions::default()
+        },
+    )
+    .err()
+    .expect("invalid retry policy should be rejected");
+    assert!(matches!(error, ConfigError::InvalidRetryPolicy { .. }));
+}
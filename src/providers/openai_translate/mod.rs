@@ -1,28 +1,56 @@
 use serde_json::{Map, Value, json};
 
-use crate::core::error::ProviderError;
+use crate::core::error::{ErrorSource, ProviderError};
 use crate::core::types::{
-    AssistantOutput, ContentPart, FinishReason, Message, MessageRole, ModelInfo,
+    Annotation, AssistantOutput, AudioSpeechRequest, AudioTranscriptionRequest,
+    AudioTranscriptionResponse, ContentPart, FinishReason, GeneratedImage, HostedTool,
+    HostedToolCall, ImageGenerationRequest, ImageGenerationResponse, ImageSource, Message,
+    MessageRole, ModelInfo, ModelRef, ModerationRequest, ModerationResponse, ModerationResult,
     ProviderCapabilities, ProviderId, ProviderRequest, ProviderResponse, ResponseFormat,
     RuntimeWarning, ToolCall, ToolChoice, ToolDefinition, ToolResult, ToolResultContent, Usage,
+    WarningCode,
 };
+use crate::providers::request_options::merge_provider_options;
 use crate::providers::translator_contract::ProviderTranslator;
-
-const WARN_BOTH_TEMPERATURE_AND_TOP_P_SET: &str = "both_temperature_and_top_p_set";
-const WARN_TOOL_SCHEMA_NOT_STRICT_COMPATIBLE: &str =
-    "tool_schema_not_strict_compatible_strict_disabled";
-const WARN_TOOL_ARGUMENTS_INVALID_JSON: &str = "tool_arguments_invalid_json";
-const WARN_USAGE_MISSING: &str = "usage_missing";
-const WARN_MODEL_REFUSAL: &str = "model_refusal";
-const WARN_STRUCTURED_OUTPUT_PARSE_FAILED: &str = "structured_output_parse_failed";
-const WARN_OPENAI_INCOMPLETE_MAX_OUTPUT_TOKENS: &str = "openai_incomplete_max_output_tokens";
-const WARN_OPENAI_INCOMPLETE_CONTENT_FILTER: &str = "openai_incomplete_content_filter";
-const WARN_OPENAI_INCOMPLETE_UNKNOWN_REASON: &str = "openai_incomplete_unknown_reason";
-const WARN_OPENAI_INCOMPLETE_MISSING_REASON: &str = "openai_incomplete_missing_reason";
-const WARN_EMPTY_OUTPUT: &str = "empty_output";
-const WARN_TOOL_RESULT_COERCED: &str = "tool_result_coerced";
-const WARN_TOOL_RESULT_RAW_PROVIDER_CONTENT_IGNORED: &str =
-    "tool_result_raw_provider_content_ignored";
+use crate::transport::MultipartForm;
+
+/// Output item types of OpenAI built-in tools, decoded as `ContentPart::HostedToolCall`.
+const HOSTED_TOOL_CALL_ITEM_TYPES: &[&str] = &[
+    "web_search_call",
+    "file_search_call",
+    "code_interpreter_call",
+];
+
+/// Body fields owned by typed `ProviderRequest` fields; `provider_options` may not set them.
+const RESERVED_PROVIDER_OPTION_KEYS: &[&str] = &[
+    "model",
+    "input",
+    "stream",
+    "tools",
+    "tool_choice",
+    "parallel_tool_calls",
+    "temperature",
+    "top_p",
+    "max_output_tokens",
+    "metadata",
+];
+
+const JSON_OBJECT_INSTRUCTION: &str = "Respond with a single valid JSON object.";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct OpenAiTranslateOptions {
+    pub reasoning_effort: Option<String>,
+    pub reasoning_summary: Option<String>,
+    pub inject_json_instruction: bool,
+    pub service_tier: Option<String>,
+    pub parallel_tool_calls: Option<bool>,
+    pub truncation: Option<String>,
+    /// Accept requests with stop sequences and apply them to the decoded output instead of
+    /// rejecting them; the Responses API has no `stop` parameter.
+    pub emulate_stop_sequences: bool,
+    /// Keep responses server-side so later requests can chain from them.
+    pub store: bool,
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct OpenAiEncodedRequest {
@@ -34,6 +62,9 @@ pub(crate) struct OpenAiEncodedRequest {
 pub(crate) struct OpenAiDecodeEnvelope {
     pub body: Value,
     pub requested_response_format: ResponseFormat,
+    /// Stop sequences to emulate by truncating the decoded text; see
+    /// `OpenAiTranslateOptions::emulate_stop_sequences`.
+    pub stop_sequences: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -44,15 +75,23 @@ pub(crate) struct OpenAiErrorEnvelope {
     pub param: Option<String>,
 }
 
-#[derive(Debug, Default, Clone, Copy)]
-pub(crate) struct OpenAiTranslator;
+#[derive(Debug, Default, Clone)]
+pub(crate) struct OpenAiTranslator {
+    options: OpenAiTranslateOptions,
+}
+
+impl OpenAiTranslator {
+    pub(crate) fn new(options: OpenAiTranslateOptions) -> Self {
+        Self { options }
+    }
+}
 
 impl ProviderTranslator for OpenAiTranslator {
     type RequestPayload = OpenAiEncodedRequest;
     type ResponsePayload = OpenAiDecodeEnvelope;
 
     fn encode_request(&self, req: &ProviderRequest) -> Result<Self::RequestPayload, ProviderError> {
-        encode_openai_request(req)
+        encode_openai_request(req, &self.options)
     }
 
     fn decode_response(
@@ -65,25 +104,47 @@ impl ProviderTranslator for OpenAiTranslator {
 
 pub(crate) fn encode_openai_request(
     req: &ProviderRequest,
+    options: &OpenAiTranslateOptions,
 ) -> Result<OpenAiEncodedRequest, ProviderError> {
     validate_provider_hint(req)?;
     validate_model_id(req)?;
-    validate_stop(req)?;
+    validate_stop(req, options)?;
     validate_metadata(req)?;
     validate_sampling_controls(req)?;
 
     let mut warnings = Vec::new();
     if req.temperature.is_some() && req.top_p.is_some() {
         warnings.push(RuntimeWarning {
-            code: WARN_BOTH_TEMPERATURE_AND_TOP_P_SET.to_string(),
+            code: WarningCode::BothTemperatureAndTopPSet,
             message: "OpenAI recommends setting temperature or top_p, but not both".to_string(),
         });
     }
+    if req.seed.is_some() {
+        warnings.push(RuntimeWarning {
+            code: WarningCode::SeedUnsupported,
+            message: "OpenAI Responses API does not support seed; dropped".to_string(),
+        });
+    }
 
-    let text_format = map_response_format(req)?;
+    let (text_format, needs_json_instruction) = map_response_format(req, options)?;
     let tool_choice = map_tool_choice(req)?;
     let tools = map_tools(req, &mut warnings)?;
-    let input = map_messages(req, &mut warnings)?;
+    let mut input = map_messages(req, &mut warnings)?;
+
+    if needs_json_instruction {
+        input.insert(
+            0,
+            json!({
+                "type": "message",
+                "role": "system",
+                "content": [{ "type": "input_text", "text": JSON_OBJECT_INSTRUCTION }]
+            }),
+        );
+        warnings.push(RuntimeWarning {
+            code: WarningCode::JsonInstructionInjected,
+            message: "json_object response format requires 'JSON' in message text; injected a system instruction".to_string(),
+        });
+    }
 
     if input.is_empty() {
         return Err(protocol_error(Some(&req.model.model_id), "empty input"));
@@ -94,7 +155,7 @@ pub(crate) fn encode_openai_request(
         "model".to_string(),
         Value::String(req.model.model_id.clone()),
     );
-    body.insert("store".to_string(), Value::Bool(false));
+    body.insert("store".to_string(), Value::Bool(options.store));
     body.insert("input".to_string(), Value::Array(input));
     body.insert("text".to_string(), json!({ "format": text_format }));
     if !tools.is_empty() {
@@ -114,6 +175,37 @@ pub(crate) fn encode_openai_request(
     if !req.metadata.is_empty() {
         body.insert("metadata".to_string(), json!(req.metadata));
     }
+    if let Some(reasoning) = map_reasoning(options) {
+        body.insert("reasoning".to_string(), reasoning);
+        // Encrypted reasoning is the only way to replay thinking when `store` is false.
+        if !options.store {
+            body.insert(
+                "include".to_string(),
+                json!(["reasoning.encrypted_content"]),
+            );
+        }
+    }
+    if let Some(service_tier) = &options.service_tier {
+        body.insert("service_tier".to_string(), json!(service_tier));
+    }
+    if let Some(parallel_tool_calls) = req.parallel_tool_calls.or(options.parallel_tool_calls) {
+        body.insert(
+            "parallel_tool_calls".to_string(),
+            json!(parallel_tool_calls),
+        );
+    }
+    if let Some(truncation) = &options.truncation {
+        body.insert("truncation".to_string(), json!(truncation));
+    }
+
+    merge_provider_options(
+        &mut body,
+        req,
+        &ProviderId::Openai,
+        RESERVED_PROVIDER_OPTION_KEYS,
+    )
+    .map_err(|message| protocol_error(Some(&req.model.model_id), message))?;
+    validate_previous_response_id(&body, req)?;
 
     Ok(OpenAiEncodedRequest {
         body: Value::Object(body),
@@ -157,6 +249,7 @@ pub(crate) fn decode_openai_response(
 
     let mut warnings = Vec::new();
     let mut content = Vec::new();
+    let mut annotations = Vec::new();
 
     let output_items = root
         .get("output")
@@ -165,12 +258,17 @@ pub(crate) fn decode_openai_response(
         .unwrap_or_default();
 
     for item in output_items {
-        decode_output_item(&item, &mut content, &mut warnings)?;
+        decode_output_item(&item, &mut content, &mut annotations, &mut warnings)?;
+    }
+
+    let stopped_at = truncate_at_stop_sequence(&mut content, &payload.stop_sequences);
+    if let Some((_, cut_index)) = &stopped_at {
+        retain_annotations_within_content(&mut annotations, &content, *cut_index);
     }
 
     if content.is_empty() {
         warnings.push(RuntimeWarning {
-            code: WARN_EMPTY_OUTPUT.to_string(),
+            code: WarningCode::EmptyOutput,
             message: "openai response contained no decodable output content".to_string(),
         });
     }
@@ -189,12 +287,32 @@ pub(crate) fn decode_openai_response(
         .and_then(|details| details.get("reason"))
         .and_then(Value::as_str);
 
-    let finish_reason = map_finish_reason(status, incomplete_reason, &content, &mut warnings)?;
+    let mut finish_reason = map_finish_reason(status, incomplete_reason, &content, &mut warnings)?;
+    if let Some((stop, _)) = stopped_at {
+        if finish_reason != FinishReason::ContentFilter {
+            finish_reason = if content
+                .iter()
+                .any(|part| matches!(part, ContentPart::ToolCall { .. }))
+            {
+                FinishReason::ToolCalls
+            } else {
+                FinishReason::Stop
+            };
+        }
+        warnings.retain(|warning| warning.code != WarningCode::OpenaiIncompleteMaxOutputTokens);
+        warnings.push(RuntimeWarning {
+            code: WarningCode::StopSequenceEmulated,
+            message: format!(
+                "truncated output at stop sequence {stop:?}; OpenAI generated and billed the text after it"
+            ),
+        });
+    }
 
     Ok(ProviderResponse {
         output: AssistantOutput {
             content,
             structured_output,
+            annotations,
         },
         usage,
         cost: None,
@@ -203,9 +321,28 @@ pub(crate) fn decode_openai_response(
         raw_provider_response: None,
         finish_reason,
         warnings,
+        alternatives: Vec::new(),
+        response_id: root.get("id").and_then(Value::as_str).map(str::to_string),
+        request_id: None,
+        rate_limit: None,
     })
 }
 
+/// `previous_response_id` arrives through `provider_options`; it must name a stored response.
+fn validate_previous_response_id(
+    body: &Map<String, Value>,
+    req: &ProviderRequest,
+) -> Result<(), ProviderError> {
+    match body.get("previous_response_id") {
+        None => Ok(()),
+        Some(Value::String(id)) if !id.trim().is_empty() => Ok(()),
+        Some(_) => Err(protocol_error(
+            Some(&req.model.model_id),
+            "provider_options 'previous_response_id' must be a non-empty string",
+        )),
+    }
+}
+
 pub(crate) fn parse_openai_error_envelope(body: &str) -> Option<OpenAiErrorEnvelope> {
     let payload = serde_json::from_str::<Value>(body).ok()?;
     let root = payload.as_object()?;
@@ -286,20 +423,348 @@ pub(crate) fn decode_openai_models_list(
             max_output_tokens: None,
             supports_tools: capabilities.supports_tools,
             supports_structured_output: capabilities.supports_structured_output,
+            pricing: None,
+            input_modalities: Vec::new(),
+            output_modalities: Vec::new(),
+            deprecated: false,
+            knowledge_cutoff: None,
         });
     }
 
     Ok(discovered)
 }
 
+/// Checks the model of a request to one of the standalone endpoints (moderation, images, audio).
+fn validate_endpoint_model(model: &ModelRef) -> Result<(), ProviderError> {
+    if let Some(provider_hint) = &model.provider_hint
+        && *provider_hint != ProviderId::Openai
+    {
+        return Err(protocol_error(
+            Some(&model.model_id),
+            format!("provider_hint must be Openai, got {provider_hint:?}"),
+        ));
+    }
+    if model.model_id.trim().is_empty() {
+        return Err(protocol_error(None, "missing model_id"));
+    }
+    Ok(())
+}
+
+pub(crate) fn encode_openai_moderation_request(
+    req: &ModerationRequest,
+) -> Result<Value, ProviderError> {
+    validate_endpoint_model(&req.model)?;
+    if req.inputs.is_empty() {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            "moderation request has no inputs",
+        ));
+    }
+
+    Ok(json!({
+        "model": req.model.model_id,
+        "input": req.inputs,
+    }))
+}
+
+pub(crate) fn decode_openai_moderation_response(
+    req: &ModerationRequest,
+    body: &Value,
+) -> Result<ModerationResponse, ProviderError> {
+    let model_id = req.model.model_id.as_str();
+    let results = body
+        .get("results")
+        .and_then(Value::as_array)
+        .ok_or_else(|| {
+            protocol_error(
+                Some(model_id),
+                "openai moderation payload missing results array",
+            )
+        })?;
+    if results.len() != req.inputs.len() {
+        return Err(protocol_error(
+            Some(model_id),
+            format!(
+                "openai moderation payload has {} results for {} inputs",
+                results.len(),
+                req.inputs.len()
+            ),
+        ));
+    }
+
+    let results = results
+        .iter()
+        .enumerate()
+        .map(|(index, result)| {
+            let flagged = result
+                .get("flagged")
+                .and_then(Value::as_bool)
+                .ok_or_else(|| {
+                    protocol_error(
+                        Some(model_id),
+                        format!("openai moderation result {index} missing flagged"),
+                    )
+                })?;
+            let categories = result
+                .get("categories")
+                .and_then(Value::as_object)
+                .map(|categories| {
+                    categories
+                        .iter()
+                        .filter_map(|(name, value)| Some((name.clone(), value.as_bool()?)))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let category_scores = result
+                .get("category_scores")
+                .and_then(Value::as_object)
+                .map(|scores| {
+                    scores
+                        .iter()
+                        .filter_map(|(name, value)| Some((name.clone(), value.as_f64()?)))
+                        .collect()
+                })
+                .unwrap_or_default();
+            Ok(ModerationResult {
+                flagged,
+                categories,
+                category_scores,
+            })
+        })
+        .collect::<Result<Vec<_>, ProviderError>>()?;
+
+    Ok(ModerationResponse {
+        results,
+        provider: ProviderId::Openai,
+        model: body
+            .get("model")
+            .and_then(Value::as_str)
+            .unwrap_or(model_id)
+            .to_string(),
+        request_id: None,
+        warnings: Vec::new(),
+    })
+}
+
+pub(crate) fn encode_openai_image_request(
+    req: &ImageGenerationRequest,
+) -> Result<Value, ProviderError> {
+    validate_endpoint_model(&req.model)?;
+    if req.prompt.trim().is_empty() {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            "image generation prompt is empty",
+        ));
+    }
+
+    let mut body = json!({
+        "model": req.model.model_id,
+        "prompt": req.prompt,
+    });
+    if let Some(n) = req.n {
+        body["n"] = json!(n);
+    }
+    if let Some(size) = &req.size {
+        body["size"] = json!(size);
+    }
+    if let Some(quality) = &req.quality {
+        body["quality"] = json!(quality);
+    }
+    Ok(body)
+}
+
+/// Decodes an Images API response. DALL-E models return URLs by default and GPT image models
+/// always return `b64_json`; both are accepted.
+pub(crate) fn decode_openai_image_response(
+    req: &ImageGenerationRequest,
+    body: &Value,
+) -> Result<ImageGenerationResponse, ProviderError> {
+    let model_id = req.model.model_id.as_str();
+    let data = body.get("data").and_then(Value::as_array).ok_or_else(|| {
+        protocol_error(Some(model_id), "openai images payload missing data array")
+    })?;
+
+    let images = data
+        .iter()
+        .enumerate()
+        .map(|(index, image)| {
+            let source = if let Some(data) = image.get("b64_json").and_then(Value::as_str) {
+                ImageSource::Base64 {
+                    media_type: body
+                        .get("output_format")
+                        .and_then(Value::as_str)
+                        .map(|format| format!("image/{format}")),
+                    data: data.to_string(),
+                }
+            } else if let Some(url) = image.get("url").and_then(Value::as_str) {
+                ImageSource::Url {
+                    url: url.to_string(),
+                }
+            } else {
+                return Err(protocol_error(
+                    Some(model_id),
+                    format!("openai images payload entry {index} has neither b64_json nor url"),
+                ));
+            };
+            Ok(GeneratedImage {
+                source,
+                revised_prompt: image
+                    .get("revised_prompt")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            })
+        })
+        .collect::<Result<Vec<_>, ProviderError>>()?;
+
+    // Only GPT image models report token usage; per-image pricing covers the rest.
+    let usage = body
+        .get("usage")
+        .and_then(Value::as_object)
+        .map(|usage| Usage {
+            input_tokens: usage.get("input_tokens").and_then(Value::as_u64),
+            output_tokens: usage.get("output_tokens").and_then(Value::as_u64),
+            total_tokens: usage.get("total_tokens").and_then(Value::as_u64),
+            ..Usage::default()
+        })
+        .unwrap_or_default();
+
+    Ok(ImageGenerationResponse {
+        images,
+        usage,
+        cost: None,
+        provider: ProviderId::Openai,
+        model: model_id.to_string(),
+        request_id: None,
+        warnings: Vec::new(),
+    })
+}
+
+pub(crate) fn encode_openai_speech_request(
+    req: &AudioSpeechRequest,
+) -> Result<Value, ProviderError> {
+    validate_endpoint_model(&req.model)?;
+    if req.input.trim().is_empty() {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            "speech request input is empty",
+        ));
+    }
+    if req.voice.trim().is_empty() {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            "speech request has no voice",
+        ));
+    }
+
+    let mut body = json!({
+        "model": req.model.model_id,
+        "input": req.input,
+        "voice": req.voice,
+    });
+    if let Some(format) = &req.format {
+        body["response_format"] = json!(format);
+    }
+    if let Some(speed) = req.speed {
+        body["speed"] = json!(speed);
+    }
+    if let Some(instructions) = &req.instructions {
+        body["instructions"] = json!(instructions);
+    }
+    Ok(body)
+}
+
+pub(crate) fn encode_openai_transcription_form(
+    req: &AudioTranscriptionRequest,
+) -> Result<MultipartForm, ProviderError> {
+    validate_endpoint_model(&req.model)?;
+    if req.audio.is_empty() {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            "transcription request has no audio",
+        ));
+    }
+
+    // `json` is the one response format every transcription model supports.
+    let mut form = MultipartForm::new()
+        .file(
+            "file",
+            req.filename.clone(),
+            req.media_type.clone(),
+            req.audio.clone(),
+        )
+        .text("model", req.model.model_id.clone())
+        .text("response_format", "json");
+    if let Some(language) = &req.language {
+        form = form.text("language", language.clone());
+    }
+    if let Some(prompt) = &req.prompt {
+        form = form.text("prompt", prompt.clone());
+    }
+    if let Some(temperature) = req.temperature {
+        form = form.text("temperature", temperature.to_string());
+    }
+    Ok(form)
+}
+
+pub(crate) fn decode_openai_transcription_response(
+    req: &AudioTranscriptionRequest,
+    body: &Value,
+) -> Result<AudioTranscriptionResponse, ProviderError> {
+    let model_id = req.model.model_id.as_str();
+    let text = body.get("text").and_then(Value::as_str).ok_or_else(|| {
+        protocol_error(Some(model_id), "openai transcription payload missing text")
+    })?;
+
+    // Token-billed models report `usage.type = "tokens"`; duration-billed ones report seconds.
+    let usage_body = body.get("usage");
+    let usage = match usage_body
+        .and_then(|usage| usage.get("type"))
+        .and_then(Value::as_str)
+    {
+        Some("tokens") => Usage {
+            input_tokens: usage_body
+                .and_then(|usage| usage.get("input_tokens"))
+                .and_then(Value::as_u64),
+            output_tokens: usage_body
+                .and_then(|usage| usage.get("output_tokens"))
+                .and_then(Value::as_u64),
+            total_tokens: usage_body
+                .and_then(|usage| usage.get("total_tokens"))
+                .and_then(Value::as_u64),
+            ..Usage::default()
+        },
+        _ => Usage::default(),
+    };
+    let duration_seconds = body.get("duration").and_then(Value::as_f64).or_else(|| {
+        usage_body
+            .filter(|usage| usage.get("type").and_then(Value::as_str) == Some("duration"))
+            .and_then(|usage| usage.get("seconds"))
+            .and_then(Value::as_f64)
+    });
+
+    Ok(AudioTranscriptionResponse {
+        text: text.to_string(),
+        language: body
+            .get("language")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        duration_seconds,
+        usage,
+        provider: ProviderId::Openai,
+        model: model_id.to_string(),
+        request_id: None,
+        warnings: Vec::new(),
+    })
+}
+
 fn validate_provider_hint(req: &ProviderRequest) -> Result<(), ProviderError> {
-    if let Some(provider_hint) = &req.model.provider_hint {
-        if *provider_hint != ProviderId::Openai {
-            return Err(protocol_error(
-                Some(&req.model.model_id),
-                format!("provider_hint must be Openai, got {provider_hint:?}"),
-            ));
-        }
+    if let Some(provider_hint) = &req.model.provider_hint
+        && *provider_hint != ProviderId::Openai
+    {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            format!("provider_hint must be Openai, got {provider_hint:?}"),
+        ));
     }
 
     Ok(())
@@ -313,14 +778,18 @@ fn validate_model_id(req: &ProviderRequest) -> Result<(), ProviderError> {
     Ok(())
 }
 
-fn validate_stop(req: &ProviderRequest) -> Result<(), ProviderError> {
-    if req.stop.is_empty() {
+fn validate_stop(
+    req: &ProviderRequest,
+    options: &OpenAiTranslateOptions,
+) -> Result<(), ProviderError> {
+    if req.stop.is_empty() || options.emulate_stop_sequences {
         return Ok(());
     }
 
     Err(protocol_error(
         Some(&req.model.model_id),
-        "stop sequences are unsupported by OpenAI Responses API",
+        "stop sequences are unsupported by OpenAI Responses API; enable stop sequence emulation \
+         to apply them to the decoded output",
     ))
 }
 
@@ -351,38 +820,69 @@ fn validate_metadata(req: &ProviderRequest) -> Result<(), ProviderError> {
 }
 
 fn validate_sampling_controls(req: &ProviderRequest) -> Result<(), ProviderError> {
-    if let Some(temperature) = req.temperature {
-        if !(0.0..=2.0).contains(&temperature) {
-            return Err(protocol_error(
-                Some(&req.model.model_id),
-                format!("temperature must be in [0.0, 2.0], got {temperature}"),
-            ));
-        }
+    if let Some(temperature) = req.temperature
+        && !(0.0..=2.0).contains(&temperature)
+    {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            format!("temperature must be in [0.0, 2.0], got {temperature}"),
+        ));
     }
 
-    if let Some(top_p) = req.top_p {
-        if !(0.0..=1.0).contains(&top_p) {
-            return Err(protocol_error(
-                Some(&req.model.model_id),
-                format!("top_p must be in [0.0, 1.0], got {top_p}"),
-            ));
-        }
+    if let Some(top_p) = req.top_p
+        && !(0.0..=1.0).contains(&top_p)
+    {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            format!("top_p must be in [0.0, 1.0], got {top_p}"),
+        ));
+    }
+
+    if let Some(n) = req.n
+        && n != 1
+    {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            format!("openai responses does not support multiple completions; n must be 1, got {n}"),
+        ));
     }
 
     Ok(())
 }
 
-fn map_response_format(req: &ProviderRequest) -> Result<Value, ProviderError> {
+fn map_reasoning(options: &OpenAiTranslateOptions) -> Option<Value> {
+    if options.reasoning_effort.is_none() && options.reasoning_summary.is_none() {
+        return None;
+    }
+
+    let mut reasoning = Map::new();
+    if let Some(effort) = &options.reasoning_effort {
+        reasoning.insert("effort".to_string(), Value::String(effort.clone()));
+    }
+    if let Some(summary) = &options.reasoning_summary {
+        reasoning.insert("summary".to_string(), Value::String(summary.clone()));
+    }
+
+    Some(Value::Object(reasoning))
+}
+
+/// Returns the `text.format` value and whether a JSON instruction must be injected to satisfy
+/// OpenAI's json_object precondition.
+fn map_response_format(
+    req: &ProviderRequest,
+    options: &OpenAiTranslateOptions,
+) -> Result<(Value, bool), ProviderError> {
     match &req.response_format {
-        ResponseFormat::Text => Ok(json!({ "type": "text" })),
+        ResponseFormat::Text => Ok((json!({ "type": "text" }), false)),
         ResponseFormat::JsonObject => {
-            if !contains_json_keyword(&req.messages) {
+            let has_keyword = contains_json_keyword(&req.messages);
+            if !has_keyword && !options.inject_json_instruction {
                 return Err(protocol_error(
                     Some(&req.model.model_id),
                     "json_object response format requires the string 'JSON' in message text",
                 ));
             }
-            Ok(json!({ "type": "json_object" }))
+            Ok((json!({ "type": "json_object" }), !has_keyword))
         }
         ResponseFormat::JsonSchema { name, schema } => {
             if name.trim().is_empty() {
@@ -392,12 +892,15 @@ fn map_response_format(req: &ProviderRequest) -> Result<Value, ProviderError> {
                 ));
             }
 
-            Ok(json!({
-                "type": "json_schema",
-                "name": name,
-                "schema": schema,
-                "strict": true
-            }))
+            Ok((
+                json!({
+                    "type": "json_schema",
+                    "name": name,
+                    "schema": schema,
+                    "strict": true
+                }),
+                false,
+            ))
         }
     }
 }
@@ -446,10 +949,48 @@ fn map_tools(
     for tool in &req.tools {
         tools.push(map_tool_definition(tool, &req.model.model_id, warnings)?);
     }
+    for tool in &req.hosted_tools {
+        tools.push(map_hosted_tool(tool, &req.model.model_id)?);
+    }
 
     Ok(tools)
 }
 
+fn map_hosted_tool(tool: &HostedTool, model_id: &str) -> Result<Value, ProviderError> {
+    match tool {
+        HostedTool::WebSearch { allowed_domains } => {
+            let mut payload = json!({ "type": "web_search" });
+            if !allowed_domains.is_empty() {
+                payload["filters"] = json!({ "allowed_domains": allowed_domains });
+            }
+            Ok(payload)
+        }
+        HostedTool::FileSearch {
+            vector_store_ids,
+            max_num_results,
+        } => {
+            if vector_store_ids.is_empty() {
+                return Err(protocol_error(
+                    Some(model_id),
+                    "file_search hosted tool requires at least one vector_store_id",
+                ));
+            }
+            let mut payload = json!({
+                "type": "file_search",
+                "vector_store_ids": vector_store_ids,
+            });
+            if let Some(max_num_results) = max_num_results {
+                payload["max_num_results"] = json!(max_num_results);
+            }
+            Ok(payload)
+        }
+        HostedTool::CodeInterpreter => Ok(json!({
+            "type": "code_interpreter",
+            "container": { "type": "auto" },
+        })),
+    }
+}
+
 fn map_tool_definition(
     tool: &ToolDefinition,
     model_id: &str,
@@ -475,7 +1016,7 @@ fn map_tool_definition(
     let strict = is_strict_compatible_schema(&tool.parameters_schema);
     if !strict {
         warnings.push(RuntimeWarning {
-            code: WARN_TOOL_SCHEMA_NOT_STRICT_COMPATIBLE.to_string(),
+            code: WarningCode::ToolSchemaNotStrictCompatibleStrictDisabled,
             message: format!(
                 "tool '{}' schema is not strict-compatible; strict disabled",
                 tool.name
@@ -545,6 +1086,7 @@ fn map_messages(
                                     "failed to serialize tool_call arguments for '{}': {e}",
                                     tool_call.name
                                 ),
+                                source: Some(ErrorSource::new(e)),
                             }
                         })?;
 
@@ -583,6 +1125,68 @@ fn map_messages(
                         "output": output
                     }));
                 }
+                ContentPart::Thinking { text, signature } => {
+                    if message.role != MessageRole::Assistant {
+                        return Err(protocol_error(
+                            Some(&req.model.model_id),
+                            "thinking content is only valid for assistant role messages",
+                        ));
+                    }
+
+                    // With `store: false`, reasoning can only be replayed through its
+                    // encrypted content, which the canonical signature carries.
+                    let Some(signature) = signature else {
+                        warnings.push(RuntimeWarning {
+                            code: WarningCode::ThinkingWithoutSignatureDropped,
+                            message: "thinking part without encrypted content dropped for OpenAI"
+                                .to_string(),
+                        });
+                        continue;
+                    };
+
+                    flush_message_item(&mut input_items, &message.role, &mut message_parts);
+
+                    let summary = if text.is_empty() {
+                        Vec::new()
+                    } else {
+                        vec![json!({ "type": "summary_text", "text": text })]
+                    };
+                    input_items.push(json!({
+                        "type": "reasoning",
+                        "summary": summary,
+                        "encrypted_content": signature
+                    }));
+                }
+                ContentPart::HostedToolCall { hosted_tool_call } => {
+                    if message.role != MessageRole::Assistant {
+                        return Err(protocol_error(
+                            Some(&req.model.model_id),
+                            "hosted_tool_call content is only valid for assistant role messages",
+                        ));
+                    }
+
+                    if hosted_tool_call.provider != ProviderId::Openai {
+                        warnings.push(RuntimeWarning {
+                            code: WarningCode::HostedToolCallDropped,
+                            message: format!(
+                                "{:?} hosted tool call '{}' cannot be replayed to OpenAI; dropped",
+                                hosted_tool_call.provider, hosted_tool_call.name
+                            ),
+                        });
+                        continue;
+                    }
+
+                    flush_message_item(&mut input_items, &message.role, &mut message_parts);
+                    input_items.push(hosted_tool_call.raw_provider_content.clone());
+                }
+                ContentPart::Other { kind, .. } => {
+                    warnings.push(RuntimeWarning {
+                        code: WarningCode::UnknownContentPartDropped,
+                        message: format!(
+                            "unknown '{kind}' content cannot be replayed to OpenAI; dropped"
+                        ),
+                    });
+                }
             }
         }
 
@@ -603,6 +1207,7 @@ fn flush_message_item(
 
     let role_value = match role {
         MessageRole::System => "system",
+        MessageRole::Developer => "developer",
         MessageRole::User => "user",
         MessageRole::Assistant => "assistant",
         MessageRole::Tool => return,
@@ -627,27 +1232,27 @@ fn serialize_tool_result_output(
         }
 
         warnings.push(RuntimeWarning {
-            code: WARN_TOOL_RESULT_RAW_PROVIDER_CONTENT_IGNORED.to_string(),
+            code: WarningCode::ToolResultRawProviderContentIgnored,
             message:
                 "tool_result raw_provider_content ignored for OpenAI because it is not a string"
                     .to_string(),
         });
     }
 
-    match &tool_result.content {
-        ToolResultContent::Text { text } => Ok(text.clone()),
+    let output = match &tool_result.content {
+        ToolResultContent::Text { text } => text.clone(),
         ToolResultContent::Json { value } => {
             warnings.push(RuntimeWarning {
-                code: WARN_TOOL_RESULT_COERCED.to_string(),
+                code: WarningCode::ToolResultCoerced,
                 message:
                     "tool_result JSON content coerced to string for OpenAI function_call_output"
                         .to_string(),
             });
-            Ok(stable_json_string(&canonicalize_json(value)))
+            stable_json_string(&canonicalize_json(value))
         }
         ToolResultContent::Parts { parts } => {
             warnings.push(RuntimeWarning {
-                code: WARN_TOOL_RESULT_COERCED.to_string(),
+                code: WarningCode::ToolResultCoerced,
                 message: "tool_result parts content coerced to newline-delimited string for OpenAI function_call_output".to_string(),
             });
 
@@ -663,9 +1268,19 @@ fn serialize_tool_result_output(
                     }
                 }
             }
-            Ok(lines.join("\n"))
+            lines.join("\n")
         }
+    };
+    Ok(mark_tool_error(tool_result, output))
+}
+
+/// Function outputs are plain strings, so failed tool results are wrapped in a JSON envelope
+/// the model can tell apart from a successful output.
+fn mark_tool_error(tool_result: &ToolResult, output: String) -> String {
+    if !tool_result.is_error {
+        return output;
     }
+    stable_json_string(&json!({ "is_error": true, "content": output }))
 }
 
 fn canonicalize_json(value: &Value) -> Value {
@@ -753,6 +1368,7 @@ fn is_object_type(type_value: Option<&Value>) -> bool {
 fn decode_output_item(
     item: &Value,
     content: &mut Vec<ContentPart>,
+    annotations: &mut Vec<Annotation>,
     warnings: &mut Vec<RuntimeWarning>,
 ) -> Result<(), ProviderError> {
     let item_obj = item
@@ -764,29 +1380,95 @@ fn decode_output_item(
         .ok_or_else(|| protocol_error(None, "output item missing type"))?;
 
     match item_type {
-        "message" => decode_output_message(item_obj, content, warnings),
+        "message" => decode_output_message(item_obj, content, annotations, warnings),
         "function_call" => decode_output_tool_call(item_obj, content, warnings),
-        "reasoning" => Ok(()),
+        hosted if HOSTED_TOOL_CALL_ITEM_TYPES.contains(&hosted) => {
+            decode_output_hosted_tool_call(item_obj, hosted, content)
+        }
+        "reasoning" => {
+            decode_output_reasoning(item_obj, content);
+            Ok(())
+        }
         "refusal" => {
             if let Some(text) = extract_refusal_text(item_obj) {
                 content.push(ContentPart::Text { text });
                 warnings.push(RuntimeWarning {
-                    code: WARN_MODEL_REFUSAL.to_string(),
+                    code: WarningCode::ModelRefusal,
                     message: "OpenAI refusal content mapped to canonical text".to_string(),
                 });
             }
             Ok(())
         }
-        other => Err(protocol_error(
-            None,
-            format!("unsupported output item type: {other}"),
-        )),
+        other => {
+            warnings.push(RuntimeWarning {
+                code: WarningCode::UnknownContentBlockKept,
+                message: format!("openai output item type '{other}' kept as unknown content"),
+            });
+            content.push(ContentPart::Other {
+                kind: other.to_string(),
+                raw: item.clone(),
+            });
+            Ok(())
+        }
+    }
+}
+
+fn decode_output_hosted_tool_call(
+    item_obj: &Map<String, Value>,
+    item_type: &str,
+    content: &mut Vec<ContentPart>,
+) -> Result<(), ProviderError> {
+    let id = item_obj
+        .get("id")
+        .and_then(Value::as_str)
+        .ok_or_else(|| protocol_error(None, format!("{item_type} output item missing id")))?;
+    content.push(ContentPart::HostedToolCall {
+        hosted_tool_call: HostedToolCall {
+            id: id.to_string(),
+            name: item_type.to_string(),
+            status: item_obj
+                .get("status")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            provider: ProviderId::Openai,
+            raw_provider_content: Value::Object(item_obj.clone()),
+        },
+    });
+    Ok(())
+}
+
+fn decode_output_reasoning(item_obj: &Map<String, Value>, content: &mut Vec<ContentPart>) {
+    let summary = item_obj
+        .get("summary")
+        .and_then(Value::as_array)
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(|part| part.get("text").and_then(Value::as_str))
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        })
+        .unwrap_or_default();
+    let signature = item_obj
+        .get("encrypted_content")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    if summary.is_empty() && signature.is_none() {
+        return;
     }
+
+    content.push(ContentPart::Thinking {
+        text: summary,
+        signature,
+    });
 }
 
 fn decode_output_message(
     item_obj: &Map<String, Value>,
     content: &mut Vec<ContentPart>,
+    annotations: &mut Vec<Annotation>,
     warnings: &mut Vec<RuntimeWarning>,
 ) -> Result<(), ProviderError> {
     let parts = item_obj
@@ -817,6 +1499,12 @@ fn decode_output_message(
                     .and_then(Value::as_str)
                     .unwrap_or_default();
                 if !text.is_empty() {
+                    decode_output_text_annotations(
+                        part_obj.get("annotations"),
+                        content.len(),
+                        annotations,
+                        warnings,
+                    );
                     content.push(ContentPart::Text {
                         text: text.to_string(),
                     });
@@ -827,7 +1515,7 @@ fn decode_output_message(
                 if let Some(text) = extract_refusal_text(part_obj) {
                     content.push(ContentPart::Text { text });
                     warnings.push(RuntimeWarning {
-                        code: WARN_MODEL_REFUSAL.to_string(),
+                        code: WarningCode::ModelRefusal,
                         message: "OpenAI refusal content mapped to canonical text".to_string(),
                     });
                 }
@@ -844,6 +1532,56 @@ fn decode_output_message(
     Ok(())
 }
 
+/// Decodes the `annotations` of the `output_text` part that becomes `content[part_index]`.
+fn decode_output_text_annotations(
+    value: Option<&Value>,
+    part_index: usize,
+    annotations: &mut Vec<Annotation>,
+    warnings: &mut Vec<RuntimeWarning>,
+) {
+    let Some(items) = value.and_then(Value::as_array) else {
+        return;
+    };
+
+    for item in items {
+        let item_type = item.get("type").and_then(Value::as_str);
+        let string_field = |field: &str| item.get(field).and_then(Value::as_str);
+        let decoded = match (item_type, string_field("url"), string_field("file_id")) {
+            (Some("url_citation"), Some(url), _) => Some(Annotation::UrlCitation {
+                url: url.to_string(),
+                title: string_field("title").map(str::to_string),
+                content: None,
+                start_index: item.get("start_index").and_then(Value::as_u64),
+                end_index: item.get("end_index").and_then(Value::as_u64),
+                part_index: Some(part_index),
+            }),
+            (Some("file_citation" | "container_file_citation"), _, Some(file_id)) => {
+                Some(Annotation::FileCitation {
+                    file_id: file_id.to_string(),
+                    filename: string_field("filename").map(str::to_string),
+                    index: item
+                        .get("index")
+                        .or_else(|| item.get("start_index"))
+                        .and_then(Value::as_u64),
+                    part_index: Some(part_index),
+                })
+            }
+            _ => None,
+        };
+
+        match decoded {
+            Some(annotation) => annotations.push(annotation),
+            None => warnings.push(RuntimeWarning {
+                code: WarningCode::UnknownAnnotationDropped,
+                message: format!(
+                    "openai annotation type {} dropped",
+                    item_type.unwrap_or("<missing>")
+                ),
+            }),
+        }
+    }
+}
+
 fn decode_output_tool_call(
     item_obj: &Map<String, Value>,
     content: &mut Vec<ContentPart>,
@@ -866,7 +1604,7 @@ fn decode_output_tool_call(
         Ok(value) => value,
         Err(_) => {
             warnings.push(RuntimeWarning {
-                code: WARN_TOOL_ARGUMENTS_INVALID_JSON.to_string(),
+                code: WarningCode::ToolArgumentsInvalidJson,
                 message: "OpenAI tool call arguments were not valid JSON; stored raw string"
                     .to_string(),
             });
@@ -886,16 +1624,16 @@ fn decode_output_tool_call(
 }
 
 fn extract_refusal_text(obj: &Map<String, Value>) -> Option<String> {
-    if let Some(text) = obj.get("text").and_then(Value::as_str) {
-        if !text.is_empty() {
-            return Some(text.to_string());
-        }
+    if let Some(text) = obj.get("text").and_then(Value::as_str)
+        && !text.is_empty()
+    {
+        return Some(text.to_string());
     }
 
-    if let Some(text) = obj.get("refusal").and_then(Value::as_str) {
-        if !text.is_empty() {
-            return Some(text.to_string());
-        }
+    if let Some(text) = obj.get("refusal").and_then(Value::as_str)
+        && !text.is_empty()
+    {
+        return Some(text.to_string());
     }
 
     None
@@ -931,7 +1669,7 @@ fn decode_structured_output(
                     Some(parsed)
                 } else {
                     warnings.push(RuntimeWarning {
-                        code: WARN_STRUCTURED_OUTPUT_PARSE_FAILED.to_string(),
+                        code: WarningCode::StructuredOutputParseFailed,
                         message: "structured output was valid JSON but not an object".to_string(),
                     });
                     None
@@ -942,7 +1680,7 @@ fn decode_structured_output(
         },
         Err(error) => {
             warnings.push(RuntimeWarning {
-                code: WARN_STRUCTURED_OUTPUT_PARSE_FAILED.to_string(),
+                code: WarningCode::StructuredOutputParseFailed,
                 message: format!(
                     "failed to parse structured output JSON{}: {error}",
                     format_model_context(model)
@@ -956,7 +1694,7 @@ fn decode_structured_output(
 fn decode_usage(usage: Option<&Value>, warnings: &mut Vec<RuntimeWarning>) -> Usage {
     let Some(usage_obj) = usage.and_then(Value::as_object) else {
         warnings.push(RuntimeWarning {
-            code: WARN_USAGE_MISSING.to_string(),
+            code: WarningCode::UsageMissing,
             message: "openai response missing usage details".to_string(),
         });
         return Usage::default();
@@ -970,13 +1708,89 @@ fn decode_usage(usage: Option<&Value>, warnings: &mut Vec<RuntimeWarning>) -> Us
         .and_then(Value::as_object)
         .and_then(|details| details.get("cached_tokens"))
         .and_then(Value::as_u64);
+    let reasoning_tokens = usage_obj
+        .get("output_tokens_details")
+        .and_then(Value::as_object)
+        .and_then(|details| details.get("reasoning_tokens"))
+        .and_then(Value::as_u64);
 
     Usage {
         input_tokens,
         output_tokens,
         cached_input_tokens,
         total_tokens,
+        reasoning_tokens,
+        cache_creation_input_tokens: None,
+        web_search_requests: None,
+    }
+}
+
+/// Cuts the output at the earliest stop sequence in the first text part that contains one,
+/// dropping every later part. Returns the matched stop sequence and the index of the cut part.
+fn truncate_at_stop_sequence(
+    content: &mut Vec<ContentPart>,
+    stops: &[String],
+) -> Option<(String, usize)> {
+    if stops.is_empty() {
+        return None;
     }
+
+    for (index, part) in content.iter_mut().enumerate() {
+        let ContentPart::Text { text } = part else {
+            continue;
+        };
+        let Some((position, stop)) = stops
+            .iter()
+            .filter(|stop| !stop.is_empty())
+            .filter_map(|stop| text.find(stop.as_str()).map(|position| (position, stop)))
+            .min_by_key(|(position, _)| *position)
+        else {
+            continue;
+        };
+
+        let stop = stop.clone();
+        text.truncate(position);
+        let keep = if text.is_empty() { index } else { index + 1 };
+        content.truncate(keep);
+        return Some((stop, index));
+    }
+    None
+}
+
+/// Drops annotations that refer to parts removed by stop-sequence truncation, or to character
+/// offsets past the end of the part that was cut.
+fn retain_annotations_within_content(
+    annotations: &mut Vec<Annotation>,
+    content: &[ContentPart],
+    cut_index: usize,
+) {
+    let cut_len = match content.get(cut_index) {
+        Some(ContentPart::Text { text }) => Some(text.chars().count() as u64),
+        _ => None,
+    };
+    let within_cut =
+        |offset: &Option<u64>| cut_len.is_some_and(|len| offset.is_none_or(|offset| offset <= len));
+
+    annotations.retain(|annotation| match annotation {
+        Annotation::UrlCitation {
+            part_index,
+            start_index,
+            end_index,
+            ..
+        } => match part_index {
+            Some(index) if *index == cut_index => within_cut(start_index) && within_cut(end_index),
+            Some(index) => *index < content.len(),
+            None => true,
+        },
+        Annotation::FileCitation {
+            part_index, index, ..
+        } => match part_index {
+            Some(part) if *part == cut_index => within_cut(index),
+            Some(part) => *part < content.len(),
+            None => true,
+        },
+        _ => true,
+    });
 }
 
 fn map_finish_reason(
@@ -996,7 +1810,7 @@ fn map_finish_reason(
         "incomplete" => match incomplete_reason {
             Some("max_output_tokens") | Some("max_tokens") => {
                 warnings.push(RuntimeWarning {
-                    code: WARN_OPENAI_INCOMPLETE_MAX_OUTPUT_TOKENS.to_string(),
+                    code: WarningCode::OpenaiIncompleteMaxOutputTokens,
                     message: "openai response incomplete because max_output_tokens was reached"
                         .to_string(),
                 });
@@ -1004,21 +1818,21 @@ fn map_finish_reason(
             }
             Some("content_filter") => {
                 warnings.push(RuntimeWarning {
-                    code: WARN_OPENAI_INCOMPLETE_CONTENT_FILTER.to_string(),
+                    code: WarningCode::OpenaiIncompleteContentFilter,
                     message: "openai response incomplete because of content filtering".to_string(),
                 });
                 Ok(FinishReason::ContentFilter)
             }
             Some(reason) => {
                 warnings.push(RuntimeWarning {
-                    code: WARN_OPENAI_INCOMPLETE_UNKNOWN_REASON.to_string(),
+                    code: WarningCode::OpenaiIncompleteUnknownReason,
                     message: format!("openai response incomplete for reason: {reason}"),
                 });
                 Ok(FinishReason::Other)
             }
             None => {
                 warnings.push(RuntimeWarning {
-                    code: WARN_OPENAI_INCOMPLETE_MISSING_REASON.to_string(),
+                    code: WarningCode::OpenaiIncompleteMissingReason,
                     message: "openai response incomplete with no reason".to_string(),
                 });
                 Ok(FinishReason::Other)
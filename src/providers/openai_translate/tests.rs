@@ -3,13 +3,15 @@ use std::collections::BTreeMap;
 use serde_json::json;
 
 use super::{
-    OpenAiDecodeEnvelope, decode_openai_models_list, decode_openai_response, encode_openai_request,
-    format_openai_error_message, parse_openai_error_envelope,
+    OpenAiDecodeEnvelope, OpenAiTranslateOptions, decode_openai_models_list,
+    decode_openai_response, encode_openai_request, format_openai_error_message,
+    parse_openai_error_envelope,
 };
 use crate::core::error::ProviderError;
 use crate::core::types::{
-    ContentPart, FinishReason, Message, MessageRole, ModelRef, ProviderCapabilities, ProviderId,
-    ProviderRequest, ResponseFormat, ToolChoice, ToolDefinition, ToolResultContent,
+    Annotation, ContentPart, FinishReason, HostedTool, Message, MessageRole, ModelRef,
+    ProviderCapabilities, ProviderId, ProviderRequest, ResponseFormat, ToolCall, ToolChoice,
+    ToolDefinition, ToolResult, ToolResultContent,
 };
 
 fn base_request() -> ProviderRequest {
@@ -17,21 +19,28 @@ fn base_request() -> ProviderRequest {
         model: ModelRef {
             provider_hint: Some(ProviderId::Openai),
             model_id: "gpt-5-mini".to_string(),
+            requirements: None,
         },
         messages: vec![Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "hello".to_string(),
             }],
+            cache_hint: None,
         }],
         tools: Vec::new(),
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::Text,
         temperature: None,
         top_p: None,
         max_output_tokens: None,
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     }
 }
 
@@ -44,12 +53,14 @@ fn test_encode_openai_translator_category_contract() {
             content: vec![ContentPart::Text {
                 text: "You must return JSON.".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "Respond in JSON with weather details".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Assistant,
@@ -60,6 +71,7 @@ fn test_encode_openai_translator_category_contract() {
                     arguments_json: json!({ "city": "SF" }),
                 },
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Tool,
@@ -70,8 +82,10 @@ fn test_encode_openai_translator_category_contract() {
                         text: "{\"temp\":55}".to_string(),
                     },
                     raw_provider_content: None,
+                    is_error: false,
                 },
             }],
+            cache_hint: None,
         },
     ];
     req.tools = vec![ToolDefinition {
@@ -84,6 +98,7 @@ fn test_encode_openai_translator_category_contract() {
             },
             "required": []
         }),
+        cache_hint: None,
     }];
     req.tool_choice = ToolChoice::Specific {
         name: "lookup_weather".to_string(),
@@ -103,7 +118,8 @@ fn test_encode_openai_translator_category_contract() {
     req.metadata
         .insert("trace_id".to_string(), "abc-123".to_string());
 
-    let encoded = encode_openai_request(&req).expect("encode should succeed");
+    let encoded = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect("encode should succeed");
 
     assert_eq!(encoded.body.pointer("/model"), Some(&json!("gpt-5-mini")));
     assert_eq!(encoded.body.pointer("/store"), Some(&json!(false)));
@@ -180,6 +196,7 @@ fn test_decode_openai_translator_category_contract() {
             }
         }),
         requested_response_format: ResponseFormat::JsonObject,
+        stop_sequences: Vec::new(),
     };
 
     let decoded = decode_openai_response(&payload).expect("decode should succeed");
@@ -210,8 +227,10 @@ fn test_decode_openai_translator_category_contract() {
 #[test]
 fn test_openai_translator_determinism_contract() {
     let req = base_request();
-    let first_encode = encode_openai_request(&req).expect("encode should succeed");
-    let second_encode = encode_openai_request(&req).expect("encode should succeed");
+    let first_encode = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect("encode should succeed");
+    let second_encode = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect("encode should succeed");
     assert_eq!(first_encode, second_encode);
 
     let payload = OpenAiDecodeEnvelope {
@@ -234,6 +253,7 @@ fn test_openai_translator_determinism_contract() {
             }
         }),
         requested_response_format: ResponseFormat::Text,
+        stop_sequences: Vec::new(),
     };
 
     let first_decode = decode_openai_response(&payload).expect("decode should succeed");
@@ -246,11 +266,249 @@ fn test_encode_stop_is_unsupported() {
     let mut req = base_request();
     req.stop.push("STOP".to_string());
 
-    let err = encode_openai_request(&req).expect_err("stop should be unsupported");
+    let err = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect_err("stop should be unsupported");
     assert!(matches!(err, ProviderError::Protocol { .. }));
     assert!(err.to_string().contains("stop sequences are unsupported"));
 }
 
+#[test]
+fn test_stop_sequence_emulation_truncates_decoded_text() {
+    let mut req = base_request();
+    req.stop = vec!["END".to_string(), "\n\n".to_string()];
+    let options = OpenAiTranslateOptions {
+        emulate_stop_sequences: true,
+        ..Default::default()
+    };
+    let encoded = encode_openai_request(&req, &options).expect("emulated stop should encode");
+    assert_eq!(encoded.body.get("stop"), None);
+
+    let payload = OpenAiDecodeEnvelope {
+        body: json!({
+            "status": "incomplete",
+            "incomplete_details": { "reason": "max_output_tokens" },
+            "model": "gpt-5-mini",
+            "output": [{
+                "type": "message",
+                "role": "assistant",
+                "content": [
+                    { "type": "output_text", "text": "first line\n\nsecond END" },
+                    { "type": "output_text", "text": "trailing" }
+                ]
+            }],
+            "usage": { "input_tokens": 1, "output_tokens": 9, "total_tokens": 10 }
+        }),
+        requested_response_format: ResponseFormat::Text,
+        stop_sequences: req.stop.clone(),
+    };
+
+    let decoded = decode_openai_response(&payload).expect("decode should succeed");
+    assert_eq!(
+        decoded.output.content,
+        vec![ContentPart::Text {
+            text: "first line".to_string()
+        }]
+    );
+    assert_eq!(decoded.finish_reason, FinishReason::Stop);
+    let codes = decoded
+        .warnings
+        .iter()
+        .map(|warning| warning.code.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(codes, vec!["stop_sequence_emulated"]);
+}
+
+#[test]
+fn test_stop_sequence_emulation_keeps_content_filter_finish_reason() {
+    let payload = OpenAiDecodeEnvelope {
+        body: json!({
+            "status": "incomplete",
+            "incomplete_details": { "reason": "content_filter" },
+            "model": "gpt-5-mini",
+            "output": [{
+                "type": "message",
+                "content": [{ "type": "output_text", "text": "partial END filtered" }]
+            }],
+            "usage": { "input_tokens": 1, "output_tokens": 3, "total_tokens": 4 }
+        }),
+        requested_response_format: ResponseFormat::Text,
+        stop_sequences: vec!["END".to_string()],
+    };
+
+    let decoded = decode_openai_response(&payload).expect("decode should succeed");
+    assert_eq!(decoded.finish_reason, FinishReason::ContentFilter);
+    let codes = decoded
+        .warnings
+        .iter()
+        .map(|warning| warning.code.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        codes,
+        vec!["openai_incomplete_content_filter", "stop_sequence_emulated"]
+    );
+}
+
+#[test]
+fn test_stop_sequence_emulation_keeps_tool_calls_before_cut() {
+    let payload = OpenAiDecodeEnvelope {
+        body: json!({
+            "status": "completed",
+            "model": "gpt-5-mini",
+            "output": [
+                {
+                    "type": "function_call",
+                    "call_id": "call_1",
+                    "name": "lookup_weather",
+                    "arguments": "{\"city\":\"SF\"}"
+                },
+                {
+                    "type": "message",
+                    "content": [{ "type": "output_text", "text": "checking END more" }]
+                }
+            ],
+            "usage": { "input_tokens": 1, "output_tokens": 3, "total_tokens": 4 }
+        }),
+        requested_response_format: ResponseFormat::Text,
+        stop_sequences: vec!["END".to_string()],
+    };
+
+    let decoded = decode_openai_response(&payload).expect("decode should succeed");
+    assert!(matches!(
+        decoded.output.content[0],
+        ContentPart::ToolCall { .. }
+    ));
+    assert_eq!(decoded.finish_reason, FinishReason::ToolCalls);
+}
+
+#[test]
+fn test_stop_sequence_emulation_drops_annotations_past_cut() {
+    let payload = OpenAiDecodeEnvelope {
+        body: json!({
+            "status": "completed",
+            "model": "gpt-5-mini",
+            "output": [{
+                "type": "message",
+                "content": [{
+                    "type": "output_text",
+                    "text": "Rust shipped. END Later text.",
+                    "annotations": [
+                        {
+                            "type": "url_citation",
+                            "url": "https://blog.rust-lang.org/",
+                            "start_index": 0,
+                            "end_index": 13
+                        },
+                        {
+                            "type": "url_citation",
+                            "url": "https://example.com/later",
+                            "start_index": 18,
+                            "end_index": 29
+                        },
+                        { "type": "file_citation", "file_id": "file_1", "index": 25 }
+                    ]
+                }]
+            }],
+            "usage": { "input_tokens": 1, "output_tokens": 3, "total_tokens": 4 }
+        }),
+        requested_response_format: ResponseFormat::Text,
+        stop_sequences: vec!["END".to_string()],
+    };
+
+    let decoded = decode_openai_response(&payload).expect("decode should succeed");
+    assert_eq!(
+        decoded.output.annotations,
+        vec![Annotation::UrlCitation {
+            url: "https://blog.rust-lang.org/".to_string(),
+            title: None,
+            content: None,
+            start_index: Some(0),
+            end_index: Some(13),
+            part_index: Some(0),
+        }]
+    );
+}
+
+#[test]
+fn test_encode_developer_role_is_preserved() {
+    let mut req = base_request();
+    req.messages.insert(
+        0,
+        Message {
+            role: MessageRole::Developer,
+            content: vec![ContentPart::Text {
+                text: "be terse".to_string(),
+            }],
+            cache_hint: None,
+        },
+    );
+
+    let encoded = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(
+        encoded.body["input"][0],
+        json!({
+            "type": "message",
+            "role": "developer",
+            "content": [{"type": "input_text", "text": "be terse"}]
+        })
+    );
+}
+
+#[test]
+fn test_encode_seed_is_dropped_with_warning() {
+    let mut req = base_request();
+    req.seed = Some(7);
+
+    let encoded = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(encoded.body.get("seed"), None);
+    assert!(
+        encoded
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "seed_unsupported")
+    );
+}
+
+#[test]
+fn test_encode_request_provider_options_merge_into_body() {
+    let mut req = base_request();
+    req.provider_options.insert(
+        ProviderId::Openai,
+        json!({"text": {"verbosity": "low"}, "service_tier": "flex"}),
+    );
+
+    let encoded = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(encoded.body.pointer("/text/verbosity"), Some(&json!("low")));
+    assert_eq!(
+        encoded.body.pointer("/text/format/type"),
+        Some(&json!("text"))
+    );
+    assert_eq!(encoded.body.get("service_tier"), Some(&json!("flex")));
+
+    req.provider_options
+        .insert(ProviderId::Openai, json!({"text": "plain"}));
+    let err = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect_err("scalar over object should fail");
+    assert!(err.to_string().contains("'text'"));
+}
+
+#[test]
+fn test_encode_multiple_completions_is_unsupported() {
+    let mut req = base_request();
+    req.n = Some(2);
+
+    let err = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect_err("n>1 should fail");
+    assert!(matches!(err, ProviderError::Protocol { .. }));
+    assert!(err.to_string().contains("n must be 1"));
+
+    req.n = Some(1);
+    encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect("n=1 should be accepted");
+}
+
 #[test]
 fn test_decode_incomplete_content_filter_maps_finish_reason() {
     let payload = OpenAiDecodeEnvelope {
@@ -270,6 +528,7 @@ fn test_decode_incomplete_content_filter_maps_finish_reason() {
             "usage": null
         }),
         requested_response_format: ResponseFormat::Text,
+        stop_sequences: Vec::new(),
     };
 
     let decoded = decode_openai_response(&payload).expect("decode should succeed");
@@ -304,6 +563,7 @@ fn test_decode_structured_output_parse_failure_warns() {
             }
         }),
         requested_response_format: ResponseFormat::JsonObject,
+        stop_sequences: Vec::new(),
     };
 
     let decoded = decode_openai_response(&payload).expect("decode should succeed");
@@ -326,6 +586,7 @@ fn test_decode_cancelled_status_is_protocol_error() {
             "usage": null
         }),
         requested_response_format: ResponseFormat::Text,
+        stop_sequences: Vec::new(),
     };
 
     let err = decode_openai_response(&payload).expect_err("cancelled must be protocol error");
@@ -334,13 +595,13 @@ fn test_decode_cancelled_status_is_protocol_error() {
 }
 
 #[test]
-fn test_decode_unknown_output_item_type_is_protocol_error() {
+fn test_decode_unknown_output_item_type_is_kept_as_other() {
     let payload = OpenAiDecodeEnvelope {
         body: json!({
             "status": "completed",
             "model": "gpt-5-mini",
             "output": [
-                { "type": "web_search_call" }
+                { "type": "computer_call" }
             ],
             "usage": {
                 "input_tokens": 1,
@@ -349,11 +610,23 @@ fn test_decode_unknown_output_item_type_is_protocol_error() {
             }
         }),
         requested_response_format: ResponseFormat::Text,
+        stop_sequences: Vec::new(),
     };
 
-    let err = decode_openai_response(&payload).expect_err("unknown types must fail");
-    assert!(matches!(err, ProviderError::Protocol { .. }));
-    assert!(err.to_string().contains("unsupported output item type"));
+    let decoded = decode_openai_response(&payload).expect("unknown types should be kept");
+    assert_eq!(
+        decoded.output.content,
+        vec![ContentPart::Other {
+            kind: "computer_call".to_string(),
+            raw: json!({ "type": "computer_call" }),
+        }]
+    );
+    assert!(
+        decoded
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "unknown_content_block_kept")
+    );
 }
 
 #[test]
@@ -377,6 +650,7 @@ fn test_decode_tool_call_invalid_json_warns_and_preserves_raw_string() {
             }
         }),
         requested_response_format: ResponseFormat::Text,
+        stop_sequences: Vec::new(),
     };
 
     let decoded = decode_openai_response(&payload).expect("decode should succeed");
@@ -400,11 +674,49 @@ fn test_encode_json_object_requires_json_keyword() {
     let mut req = base_request();
     req.response_format = ResponseFormat::JsonObject;
 
-    let err = encode_openai_request(&req).expect_err("JSON keyword check should fail");
+    let err = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect_err("JSON keyword check should fail");
     assert!(matches!(err, ProviderError::Protocol { .. }));
     assert!(err.to_string().contains("requires the string 'JSON'"));
 }
 
+#[test]
+fn test_encode_json_object_injects_instruction_when_enabled() {
+    let mut req = base_request();
+    req.response_format = ResponseFormat::JsonObject;
+    let options = OpenAiTranslateOptions {
+        inject_json_instruction: true,
+        ..Default::default()
+    };
+
+    let encoded = encode_openai_request(&req, &options).expect("encode should succeed");
+    assert_eq!(
+        encoded.body["input"][0],
+        json!({
+            "type": "message",
+            "role": "system",
+            "content": [{"type": "input_text", "text": "Respond with a single valid JSON object."}]
+        })
+    );
+    assert_eq!(
+        encoded.body["text"]["format"],
+        json!({"type": "json_object"})
+    );
+    assert!(
+        encoded
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "json_instruction_injected")
+    );
+
+    req.messages[0].content = vec![ContentPart::Text {
+        text: "Reply in JSON".to_string(),
+    }];
+    let encoded = encode_openai_request(&req, &options).expect("encode should succeed");
+    assert_eq!(encoded.body["input"].as_array().map(Vec::len), Some(1));
+    assert!(encoded.warnings.is_empty());
+}
+
 #[test]
 fn test_encode_metadata_bounds_validation() {
     let mut req = base_request();
@@ -413,7 +725,8 @@ fn test_encode_metadata_bounds_validation() {
             .insert(format!("k{index}"), format!("value-{index}"));
     }
 
-    let err = encode_openai_request(&req).expect_err("metadata size should fail");
+    let err = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect_err("metadata size should fail");
     assert!(matches!(err, ProviderError::Protocol { .. }));
     assert!(err.to_string().contains("at most 16 entries"));
 }
@@ -430,11 +743,14 @@ fn test_encode_tool_result_role_validation() {
                     text: "result".to_string(),
                 },
                 raw_provider_content: None,
+                is_error: false,
             },
         }],
+        cache_hint: None,
     }];
 
-    let err = encode_openai_request(&req).expect_err("tool_result role mismatch should fail");
+    let err = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect_err("tool_result role mismatch should fail");
     assert!(matches!(err, ProviderError::Protocol { .. }));
     assert!(
         err.to_string()
@@ -455,6 +771,7 @@ fn test_encode_tool_result_json_coerces_to_string() {
                     arguments_json: json!({"city":"SF"}),
                 },
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Tool,
@@ -465,12 +782,15 @@ fn test_encode_tool_result_json_coerces_to_string() {
                         value: json!({"b": 2, "a": 1}),
                     },
                     raw_provider_content: None,
+                    is_error: false,
                 },
             }],
+            cache_hint: None,
         },
     ];
 
-    let encoded = encode_openai_request(&req).expect("encode should succeed");
+    let encoded = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect("encode should succeed");
     assert_eq!(
         encoded.body.pointer("/input/1/output"),
         Some(&json!("{\"a\":1,\"b\":2}"))
@@ -496,6 +816,7 @@ fn test_encode_tool_result_uses_raw_provider_content_when_string() {
                     arguments_json: json!({"city":"SF"}),
                 },
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Tool,
@@ -506,12 +827,15 @@ fn test_encode_tool_result_uses_raw_provider_content_when_string() {
                         value: json!({"a": 1}),
                     },
                     raw_provider_content: Some(json!("raw-output")),
+                    is_error: false,
                 },
             }],
+            cache_hint: None,
         },
     ];
 
-    let encoded = encode_openai_request(&req).expect("encode should succeed");
+    let encoded = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect("encode should succeed");
     assert_eq!(
         encoded.body.pointer("/input/1/output"),
         Some(&json!("raw-output"))
@@ -531,7 +855,8 @@ fn test_encode_tool_choice_specific_requires_declared_tool() {
         name: "missing_tool".to_string(),
     };
 
-    let err = encode_openai_request(&req).expect_err("unknown specific tool must fail");
+    let err = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect_err("unknown specific tool must fail");
     assert!(matches!(err, ProviderError::Protocol { .. }));
     assert!(err.to_string().contains("references unknown tool"));
 }
@@ -545,22 +870,26 @@ fn test_encode_assistant_text_history_uses_output_text() {
             content: vec![ContentPart::Text {
                 text: "hello".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Assistant,
             content: vec![ContentPart::Text {
                 text: "hi there".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "what did I just say?".to_string(),
             }],
+            cache_hint: None,
         },
     ];
 
-    let encoded = encode_openai_request(&req).expect("encode should succeed");
+    let encoded = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect("encode should succeed");
 
     assert_eq!(
         encoded.body.pointer("/input/0/content/0/type"),
@@ -576,6 +905,139 @@ fn test_encode_assistant_text_history_uses_output_text() {
     );
 }
 
+#[test]
+fn test_encode_assistant_thinking_replays_encrypted_reasoning() {
+    let mut req = base_request();
+    req.messages = vec![
+        Message {
+            role: MessageRole::User,
+            content: vec![ContentPart::Text {
+                text: "hello".to_string(),
+            }],
+            cache_hint: None,
+        },
+        Message {
+            role: MessageRole::Assistant,
+            content: vec![
+                ContentPart::Thinking {
+                    text: "greeting".to_string(),
+                    signature: Some("enc_1".to_string()),
+                },
+                ContentPart::Thinking {
+                    text: "unsigned".to_string(),
+                    signature: None,
+                },
+                ContentPart::Text {
+                    text: "hi there".to_string(),
+                },
+            ],
+            cache_hint: None,
+        },
+    ];
+
+    let encoded = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect("encode should succeed");
+
+    assert_eq!(
+        encoded.body.pointer("/input/1"),
+        Some(&json!({
+            "type": "reasoning",
+            "summary": [{ "type": "summary_text", "text": "greeting" }],
+            "encrypted_content": "enc_1"
+        }))
+    );
+    assert_eq!(
+        encoded.body.pointer("/input/2/content/0/text"),
+        Some(&json!("hi there"))
+    );
+    assert!(
+        encoded
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "thinking_without_signature_dropped")
+    );
+}
+
+#[test]
+fn test_encode_reasoning_options() {
+    let options = OpenAiTranslateOptions {
+        reasoning_effort: Some("high".to_string()),
+        reasoning_summary: Some("auto".to_string()),
+        ..Default::default()
+    };
+
+    let encoded = encode_openai_request(&base_request(), &options).expect("encode should succeed");
+    assert_eq!(
+        encoded.body.get("reasoning"),
+        Some(&json!({ "effort": "high", "summary": "auto" }))
+    );
+    assert_eq!(
+        encoded.body.get("include"),
+        Some(&json!(["reasoning.encrypted_content"]))
+    );
+
+    let encoded = encode_openai_request(&base_request(), &OpenAiTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(encoded.body.get("reasoning"), None);
+    assert_eq!(encoded.body.get("include"), None);
+}
+
+#[test]
+fn test_decode_reasoning_item_maps_to_thinking() {
+    let payload = OpenAiDecodeEnvelope {
+        body: json!({
+            "status": "completed",
+            "model": "gpt-5-mini",
+            "output": [
+                {
+                    "type": "reasoning",
+                    "summary": [
+                        { "type": "summary_text", "text": "first" },
+                        { "type": "summary_text", "text": "second" }
+                    ],
+                    "encrypted_content": "enc_1"
+                },
+                {
+                    "type": "reasoning",
+                    "summary": []
+                },
+                {
+                    "type": "message",
+                    "role": "assistant",
+                    "content": [
+                        { "type": "output_text", "text": "done" }
+                    ]
+                }
+            ],
+            "usage": {
+                "input_tokens": 5,
+                "output_tokens": 40,
+                "total_tokens": 45,
+                "output_tokens_details": { "reasoning_tokens": 32 }
+            }
+        }),
+        requested_response_format: ResponseFormat::Text,
+        stop_sequences: Vec::new(),
+    };
+
+    let decoded = decode_openai_response(&payload).expect("decode should succeed");
+
+    assert_eq!(decoded.usage.reasoning_tokens, Some(32));
+    assert_eq!(
+        decoded.output.content,
+        vec![
+            ContentPart::Thinking {
+                text: "first\n\nsecond".to_string(),
+                signature: Some("enc_1".to_string()),
+            },
+            ContentPart::Text {
+                text: "done".to_string(),
+            },
+        ]
+    );
+    assert_eq!(decoded.finish_reason, FinishReason::Stop);
+}
+
 #[test]
 fn test_parse_openai_error_envelope_and_format() {
     let envelope = parse_openai_error_envelope(
@@ -611,6 +1073,7 @@ fn test_decode_openai_models_list_success() {
         supports_structured_output: true,
         supports_thinking: false,
         supports_remote_discovery: true,
+        supports_parallel_tool_calls: true,
     };
 
     let models = decode_openai_models_list(
@@ -640,6 +1103,7 @@ fn test_decode_openai_models_list_rejects_missing_data_array() {
         supports_structured_output: true,
         supports_thinking: false,
         supports_remote_discovery: true,
+        supports_parallel_tool_calls: true,
     };
 
     let err = decode_openai_models_list(&json!({"object":"list"}), &capabilities)
@@ -649,8 +1113,261 @@ fn test_decode_openai_models_list_rejects_missing_data_array() {
 }
 
 #[test]
-fn test_encode_openai_request_always_sets_store_false() {
+fn test_encode_openai_request_sets_store_false_by_default() {
     let req = base_request();
-    let encoded = encode_openai_request(&req).expect("encode should succeed");
+    let encoded = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect("encode should succeed");
     assert_eq!(encoded.body.pointer("/store"), Some(&json!(false)));
 }
+
+#[test]
+fn test_encode_stored_response_chaining() {
+    let mut req = base_request();
+    req.provider_options.insert(
+        ProviderId::Openai,
+        json!({ "previous_response_id": "resp_123" }),
+    );
+    let options = OpenAiTranslateOptions {
+        store: true,
+        reasoning_effort: Some("low".to_string()),
+        ..Default::default()
+    };
+
+    let encoded = encode_openai_request(&req, &options).expect("encode should succeed");
+    assert_eq!(encoded.body.pointer("/store"), Some(&json!(true)));
+    assert_eq!(
+        encoded.body.pointer("/previous_response_id"),
+        Some(&json!("resp_123"))
+    );
+    // Stored reasoning is replayed server-side, so encrypted content is not requested.
+    assert_eq!(encoded.body.get("include"), None);
+
+    req.provider_options
+        .insert(ProviderId::Openai, json!({ "previous_response_id": 7 }));
+    let err = encode_openai_request(&req, &options).expect_err("non-string id should fail");
+    assert!(err.to_string().contains("previous_response_id"));
+}
+
+#[test]
+fn test_decode_surfaces_response_id() {
+    let payload = OpenAiDecodeEnvelope {
+        body: json!({
+            "id": "resp_abc",
+            "status": "completed",
+            "model": "gpt-5-mini",
+            "output": [{
+                "type": "message",
+                "content": [{ "type": "output_text", "text": "hi" }]
+            }],
+            "usage": { "input_tokens": 3, "output_tokens": 1, "total_tokens": 4 }
+        }),
+        requested_response_format: ResponseFormat::Text,
+        stop_sequences: Vec::new(),
+    };
+
+    let response = decode_openai_response(&payload).expect("decode should succeed");
+    assert_eq!(response.response_id.as_deref(), Some("resp_abc"));
+}
+
+#[test]
+fn test_encode_hosted_tools() {
+    let mut req = base_request();
+    req.hosted_tools = vec![
+        HostedTool::WebSearch {
+            allowed_domains: vec!["example.com".to_string()],
+        },
+        HostedTool::FileSearch {
+            vector_store_ids: vec!["vs_1".to_string()],
+            max_num_results: Some(4),
+        },
+        HostedTool::CodeInterpreter,
+    ];
+
+    let encoded = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(
+        encoded.body.get("tools"),
+        Some(&json!([
+            { "type": "web_search", "filters": { "allowed_domains": ["example.com"] } },
+            { "type": "file_search", "vector_store_ids": ["vs_1"], "max_num_results": 4 },
+            { "type": "code_interpreter", "container": { "type": "auto" } }
+        ]))
+    );
+
+    req.hosted_tools = vec![HostedTool::FileSearch {
+        vector_store_ids: Vec::new(),
+        max_num_results: None,
+    }];
+    let err = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect_err("file_search without vector stores should fail");
+    assert!(err.to_string().contains("vector_store_id"));
+}
+
+#[test]
+fn test_decode_hosted_tool_call_round_trips_into_history() {
+    let web_search_call = json!({
+        "type": "web_search_call",
+        "id": "ws_1",
+        "status": "completed",
+        "action": { "type": "search", "query": "weather" }
+    });
+    let payload = OpenAiDecodeEnvelope {
+        body: json!({
+            "status": "completed",
+            "model": "gpt-5-mini",
+            "output": [
+                web_search_call,
+                {
+                    "type": "message",
+                    "content": [{ "type": "output_text", "text": "sunny" }]
+                }
+            ],
+            "usage": { "input_tokens": 3, "output_tokens": 1, "total_tokens": 4 }
+        }),
+        requested_response_format: ResponseFormat::Text,
+        stop_sequences: Vec::new(),
+    };
+
+    let response = decode_openai_response(&payload).expect("decode should succeed");
+    let ContentPart::HostedToolCall { hosted_tool_call } = &response.output.content[0] else {
+        panic!(
+            "expected hosted tool call, got {:?}",
+            response.output.content[0]
+        );
+    };
+    assert_eq!(hosted_tool_call.id, "ws_1");
+    assert_eq!(hosted_tool_call.name, "web_search_call");
+    assert_eq!(hosted_tool_call.status.as_deref(), Some("completed"));
+    assert_eq!(hosted_tool_call.provider, ProviderId::Openai);
+
+    let mut req = base_request();
+    req.messages.push(Message {
+        role: MessageRole::Assistant,
+        content: response.output.content.clone(),
+        cache_hint: None,
+    });
+    let encoded = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(encoded.body.pointer("/input/1"), Some(&web_search_call));
+}
+
+#[test]
+fn test_decode_output_text_annotations() {
+    let payload = OpenAiDecodeEnvelope {
+        body: json!({
+            "status": "completed",
+            "model": "gpt-5-mini",
+            "output": [{
+                "type": "message",
+                "content": [{
+                    "type": "output_text",
+                    "text": "Rust 1.0 shipped in 2015.",
+                    "annotations": [
+                        {
+                            "type": "url_citation",
+                            "url": "https://blog.rust-lang.org/",
+                            "title": "Rust Blog",
+                            "start_index": 0,
+                            "end_index": 24
+                        },
+                        {
+                            "type": "file_citation",
+                            "file_id": "file_1",
+                            "filename": "notes.md",
+                            "index": 24
+                        },
+                        { "type": "file_path", "file_id": "file_2", "index": 3 }
+                    ]
+                }]
+            }],
+            "usage": { "input_tokens": 3, "output_tokens": 1, "total_tokens": 4 }
+        }),
+        requested_response_format: ResponseFormat::Text,
+        stop_sequences: Vec::new(),
+    };
+
+    let response = decode_openai_response(&payload).expect("decode should succeed");
+    assert_eq!(
+        response.output.annotations,
+        vec![
+            Annotation::UrlCitation {
+                url: "https://blog.rust-lang.org/".to_string(),
+                title: Some("Rust Blog".to_string()),
+                content: None,
+                start_index: Some(0),
+                end_index: Some(24),
+                part_index: Some(0),
+            },
+            Annotation::FileCitation {
+                file_id: "file_1".to_string(),
+                filename: Some("notes.md".to_string()),
+                index: Some(24),
+                part_index: Some(0),
+            },
+        ]
+    );
+    assert!(
+        response
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "unknown_annotation_dropped")
+    );
+}
+
+#[test]
+fn test_encode_request_parallel_tool_calls_overrides_adapter_option() {
+    let mut req = base_request();
+    let options = OpenAiTranslateOptions {
+        parallel_tool_calls: Some(true),
+        ..Default::default()
+    };
+
+    let encoded = encode_openai_request(&req, &options).expect("encode should succeed");
+    assert_eq!(encoded.body.get("parallel_tool_calls"), Some(&json!(true)));
+
+    req.parallel_tool_calls = Some(false);
+    let encoded = encode_openai_request(&req, &options).expect("encode should succeed");
+    assert_eq!(encoded.body.get("parallel_tool_calls"), Some(&json!(false)));
+
+    req.provider_options
+        .insert(ProviderId::Openai, json!({ "parallel_tool_calls": true }));
+    let err = encode_openai_request(&req, &options).expect_err("typed field is reserved");
+    assert!(err.to_string().contains("parallel_tool_calls"));
+}
+
+#[test]
+fn test_encode_tool_result_error_is_wrapped() {
+    let mut req = base_request();
+    req.messages.push(Message {
+        role: MessageRole::Assistant,
+        content: vec![ContentPart::ToolCall {
+            tool_call: ToolCall {
+                id: "call_1".to_string(),
+                name: "lookup".to_string(),
+                arguments_json: json!({}),
+            },
+        }],
+        cache_hint: None,
+    });
+    req.messages.push(Message {
+        role: MessageRole::Tool,
+        content: vec![ContentPart::ToolResult {
+            tool_result: ToolResult {
+                tool_call_id: "call_1".to_string(),
+                content: ToolResultContent::Text {
+                    text: "lookup timed out".to_string(),
+                },
+                raw_provider_content: None,
+                is_error: true,
+            },
+        }],
+        cache_hint: None,
+    });
+
+    let encoded = encode_openai_request(&req, &OpenAiTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(
+        encoded.body.pointer("/input/2/output"),
+        Some(&json!(r#"{"content":"lookup timed out","is_error":true}"#))
+    );
+}
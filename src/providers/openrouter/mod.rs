@@ -1,41 +1,368 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
-use serde_json::Value;
+use serde_json::{Map, Value, json};
 
 use crate::core::error::{ConfigError, ProviderError};
 use crate::core::traits::ProviderAdapter;
 use crate::core::types::{
-    AdapterContext, DiscoveryOptions, ModelInfo, ProviderCapabilities, ProviderId, ProviderRequest,
-    ProviderResponse,
+    AdapterContext, DiscoveryOptions, EmbeddingRequest, EmbeddingResponse, ImageGenerationRequest,
+    ImageGenerationResponse, ModelInfo, ProviderCapabilities, ProviderId, ProviderRequest,
+    ProviderResponse, RuntimeWarning,
 };
+use crate::providers::embeddings::{decode_embedding_response, encode_embedding_request};
 use crate::providers::openrouter_translate::{
     OpenRouterDecodeEnvelope, OpenRouterTranslateOptions, OpenRouterTranslator,
-    decode_openrouter_models_list, format_openrouter_error_message,
-    parse_openrouter_error_envelope,
+    decode_openrouter_image_response, decode_openrouter_models_list,
+    encode_openrouter_image_request, fallback_model_served_warning,
+    format_openrouter_error_message, parse_openrouter_error_envelope,
 };
 use crate::providers::translator_contract::ProviderTranslator;
-use crate::transport::http::{HttpTransport, RetryPolicy};
+use crate::registry::{ACCESS_TOKEN_METADATA_KEY, ChainCredentialStore, credentials};
+use crate::runtime::trace;
+use crate::transport::Transport;
+use crate::transport::http::{HttpTransport, JsonResponse, RetryPolicy};
 
 const OPENROUTER_DEFAULT_BASE_URL: &str = "https://openrouter.ai";
 const OPENROUTER_API_KEY_ENV: &str = "OPENROUTER_API_KEY";
 const OPENROUTER_API_KEY_METADATA: &str = "openrouter.api_key";
+const OPENROUTER_DEFAULT_TIMEOUT_MS: u64 = 30_000;
 
 const TRANSPORT_AUTH_BEARER_TOKEN_KEY: &str = "transport.auth.bearer_token";
 const TRANSPORT_HEADER_HTTP_REFERER: &str = "transport.header.http-referer";
 const TRANSPORT_HEADER_X_TITLE: &str = "transport.header.x-title";
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderSort {
+    Price,
+    Throughput,
+    Latency,
+}
+
+impl ProviderSort {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Price => "price",
+            Self::Throughput => "throughput",
+            Self::Latency => "latency",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataCollection {
+    Allow,
+    Deny,
+}
+
+impl DataCollection {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Deny => "deny",
+        }
+    }
+}
+
+/// OpenRouter provider routing preferences (`provider` request field).
+///
+/// `extra` is a raw escape hatch for fields not modeled here; its keys must not overlap the
+/// typed fields.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProviderPreferences {
+    pub order: Vec<String>,
+    pub allow_fallbacks: Option<bool>,
+    pub require_parameters: Option<bool>,
+    pub data_collection: Option<DataCollection>,
+    pub quantizations: Vec<String>,
+    pub sort: Option<ProviderSort>,
+    pub extra: Map<String, Value>,
+}
+
+impl ProviderPreferences {
+    const TYPED_KEYS: &[&str] = &[
+        "order",
+        "allow_fallbacks",
+        "require_parameters",
+        "data_collection",
+        "quantizations",
+        "sort",
+    ];
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.order.iter().any(|provider| provider.trim().is_empty()) {
+            return Err(OpenRouterAdapterOptions::invalid_config(
+                "provider_preferences.order must not include empty provider names",
+            ));
+        }
+
+        if self
+            .quantizations
+            .iter()
+            .any(|quantization| quantization.trim().is_empty())
+        {
+            return Err(OpenRouterAdapterOptions::invalid_config(
+                "provider_preferences.quantizations must not include empty values",
+            ));
+        }
+
+        validate_extra_keys("provider_preferences", &self.extra, Self::TYPED_KEYS)
+    }
+
+    pub(crate) fn to_json(&self) -> Value {
+        let mut map = self.extra.clone();
+        if !self.order.is_empty() {
+            map.insert("order".to_string(), json!(self.order));
+        }
+        if let Some(allow_fallbacks) = self.allow_fallbacks {
+            map.insert("allow_fallbacks".to_string(), Value::Bool(allow_fallbacks));
+        }
+        if let Some(require_parameters) = self.require_parameters {
+            map.insert(
+                "require_parameters".to_string(),
+                Value::Bool(require_parameters),
+            );
+        }
+        if let Some(data_collection) = self.data_collection {
+            map.insert(
+                "data_collection".to_string(),
+                json!(data_collection.as_str()),
+            );
+        }
+        if !self.quantizations.is_empty() {
+            map.insert("quantizations".to_string(), json!(self.quantizations));
+        }
+        if let Some(sort) = self.sort {
+            map.insert("sort".to_string(), json!(sort.as_str()));
+        }
+        Value::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReasoningEffort {
+    Minimal,
+    Low,
+    Medium,
+    High,
+}
+
+impl ReasoningEffort {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Minimal => "minimal",
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+/// OpenRouter unified reasoning controls (`reasoning` request field). `effort` and `max_tokens`
+/// are mutually exclusive.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ReasoningConfig {
+    pub effort: Option<ReasoningEffort>,
+    pub max_tokens: Option<u32>,
+    pub exclude: Option<bool>,
+    pub enabled: Option<bool>,
+    pub extra: Map<String, Value>,
+}
+
+impl ReasoningConfig {
+    const TYPED_KEYS: &[&str] = &["effort", "max_tokens", "exclude", "enabled"];
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.effort.is_some() && self.max_tokens.is_some() {
+            return Err(OpenRouterAdapterOptions::invalid_config(
+                "reasoning.effort and reasoning.max_tokens are mutually exclusive",
+            ));
+        }
+
+        if self.max_tokens == Some(0) {
+            return Err(OpenRouterAdapterOptions::invalid_config(
+                "reasoning.max_tokens must be at least 1",
+            ));
+        }
+
+        validate_extra_keys("reasoning", &self.extra, Self::TYPED_KEYS)
+    }
+
+    pub(crate) fn to_json(&self) -> Value {
+        let mut map = self.extra.clone();
+        if let Some(effort) = self.effort {
+            map.insert("effort".to_string(), json!(effort.as_str()));
+        }
+        if let Some(max_tokens) = self.max_tokens {
+            map.insert("max_tokens".to_string(), json!(max_tokens));
+        }
+        if let Some(exclude) = self.exclude {
+            map.insert("exclude".to_string(), Value::Bool(exclude));
+        }
+        if let Some(enabled) = self.enabled {
+            map.insert("enabled".to_string(), Value::Bool(enabled));
+        }
+        Value::Object(map)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebSearchEngine {
+    Native,
+    Exa,
+}
+
+impl WebSearchEngine {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Native => "native",
+            Self::Exa => "exa",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WebSearchPluginOptions {
+    pub engine: Option<WebSearchEngine>,
+    pub max_results: Option<u32>,
+    pub search_prompt: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfEngine {
+    PdfText,
+    MistralOcr,
+    Native,
+}
+
+impl PdfEngine {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::PdfText => "pdf-text",
+            Self::MistralOcr => "mistral-ocr",
+            Self::Native => "native",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FileParserPluginOptions {
+    pub pdf_engine: Option<PdfEngine>,
+}
+
+/// An OpenRouter plugin entry. Plugin-specific settings go in `config`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Plugin {
+    pub id: String,
+    pub enabled: Option<bool>,
+    pub config: Map<String, Value>,
+}
+
+impl Plugin {
+    const TYPED_KEYS: &[&str] = &["id", "enabled"];
+
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
+    /// The `web` plugin; search results come back as URL citation annotations.
+    pub fn web_search(options: WebSearchPluginOptions) -> Self {
+        let mut plugin = Self::new("web");
+        if let Some(engine) = options.engine {
+            plugin
+                .config
+                .insert("engine".to_string(), json!(engine.as_str()));
+        }
+        if let Some(max_results) = options.max_results {
+            plugin
+                .config
+                .insert("max_results".to_string(), json!(max_results));
+        }
+        if let Some(search_prompt) = options.search_prompt {
+            plugin
+                .config
+                .insert("search_prompt".to_string(), Value::String(search_prompt));
+        }
+        plugin
+    }
+
+    /// The `file-parser` plugin; parsed files come back as file annotations.
+    pub fn file_parser(options: FileParserPluginOptions) -> Self {
+        let mut plugin = Self::new("file-parser");
+        if let Some(pdf_engine) = options.pdf_engine {
+            plugin
+                .config
+                .insert("pdf".to_string(), json!({ "engine": pdf_engine.as_str() }));
+        }
+        plugin
+    }
+
+    pub fn response_healing() -> Self {
+        Self::new("response-healing")
+    }
+
+    fn validate(&self, index: usize) -> Result<(), ConfigError> {
+        if self.id.trim().is_empty() {
+            return Err(OpenRouterAdapterOptions::invalid_config(format!(
+                "plugins[{index}].id must be non-empty"
+            )));
+        }
+
+        validate_extra_keys(&format!("plugins[{index}]"), &self.config, Self::TYPED_KEYS)
+    }
+
+    pub(crate) fn to_json(&self) -> Value {
+        let mut map = self.config.clone();
+        map.insert("id".to_string(), Value::String(self.id.clone()));
+        if let Some(enabled) = self.enabled {
+            map.insert("enabled".to_string(), Value::Bool(enabled));
+        }
+        Value::Object(map)
+    }
+}
+
+/// OpenRouter `debug` settings. Echoed upstream bodies are captured into the run trace; this is
+/// meant for local debugging, not production traffic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DebugOptions {
+    pub echo_upstream_body: bool,
+}
+
+impl DebugOptions {
+    pub(crate) fn to_json(self) -> Value {
+        json!({ "echo_upstream_body": self.echo_upstream_body })
+    }
+}
+
+fn validate_extra_keys(
+    field: &str,
+    extra: &Map<String, Value>,
+    typed_keys: &[&str],
+) -> Result<(), ConfigError> {
+    if let Some(key) = extra.keys().find(|key| typed_keys.contains(&key.as_str())) {
+        return Err(OpenRouterAdapterOptions::invalid_config(format!(
+            "{field} raw key '{key}' conflicts with a typed field"
+        )));
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct OpenRouterAdapterOptions {
     pub fallback_models: Vec<String>,
-    pub provider_preferences: Option<Value>,
-    pub plugins: Vec<Value>,
+    pub provider_preferences: Option<ProviderPreferences>,
+    pub plugins: Vec<Plugin>,
     pub parallel_tool_calls: Option<bool>,
     pub frequency_penalty: Option<f32>,
     pub presence_penalty: Option<f32>,
     pub logit_bias: Option<Value>,
     pub logprobs: Option<bool>,
     pub top_logprobs: Option<u8>,
-    pub reasoning: Option<Value>,
-    pub seed: Option<i64>,
+    pub reasoning: Option<ReasoningConfig>,
     pub user: Option<String>,
     pub session_id: Option<String>,
     pub trace: Option<Value>,
@@ -43,10 +370,14 @@ pub struct OpenRouterAdapterOptions {
     pub max_tokens: Option<u32>,
     pub modalities: Option<Vec<String>>,
     pub image_config: Option<Value>,
-    pub debug: Option<Value>,
+    pub debug: Option<DebugOptions>,
     pub stream_options: Option<Value>,
     pub http_referer: Option<String>,
     pub x_title: Option<String>,
+    /// Per-attempt HTTP timeout; defaults to 30 seconds. Ignored by `with_transport`.
+    pub timeout_ms: Option<u64>,
+    /// Ignored by `with_transport`, where the caller's transport owns retries.
+    pub retry_policy: Option<RetryPolicy>,
 }
 
 impl OpenRouterAdapterOptions {
@@ -59,20 +390,12 @@ impl OpenRouterAdapterOptions {
             }
         }
 
-        if let Some(provider_preferences) = &self.provider_preferences
-            && !provider_preferences.is_object()
-        {
-            return Err(Self::invalid_config(
-                "provider_preferences must be a JSON object",
-            ));
+        if let Some(provider_preferences) = &self.provider_preferences {
+            provider_preferences.validate()?;
         }
 
         for (index, plugin) in self.plugins.iter().enumerate() {
-            if !plugin.is_object() {
-                return Err(Self::invalid_config(format!(
-                    "plugins[{index}] must be a JSON object"
-                )));
-            }
+            plugin.validate(index)?;
         }
 
         if let Some(frequency_penalty) = self.frequency_penalty
@@ -112,10 +435,8 @@ impl OpenRouterAdapterOptions {
             }
         }
 
-        if let Some(reasoning) = &self.reasoning
-            && !reasoning.is_object()
-        {
-            return Err(Self::invalid_config("reasoning must be a JSON object"));
+        if let Some(reasoning) = &self.reasoning {
+            reasoning.validate()?;
         }
 
         if let Some(trace) = &self.trace
@@ -177,12 +498,6 @@ impl OpenRouterAdapterOptions {
             ));
         }
 
-        if self.debug.is_some() {
-            return Err(Self::invalid_config(
-                "debug is unsupported in non-streaming canonical mode",
-            ));
-        }
-
         if self.stream_options.is_some() {
             return Err(Self::invalid_config(
                 "stream_options is unsupported in non-streaming canonical mode",
@@ -205,22 +520,28 @@ impl OpenRouterAdapterOptions {
             ));
         }
 
+        if let Some(retry_policy) = &self.retry_policy {
+            retry_policy.validate()?;
+        }
+
         Ok(())
     }
 
     pub(crate) fn to_translate_options(&self) -> OpenRouterTranslateOptions {
         OpenRouterTranslateOptions {
             fallback_models: self.fallback_models.clone(),
-            provider_preferences: self.provider_preferences.clone(),
-            plugins: self.plugins.clone(),
+            provider_preferences: self
+                .provider_preferences
+                .as_ref()
+                .map(ProviderPreferences::to_json),
+            plugins: self.plugins.iter().map(Plugin::to_json).collect(),
             parallel_tool_calls: self.parallel_tool_calls,
             frequency_penalty: self.frequency_penalty,
             presence_penalty: self.presence_penalty,
             logit_bias: self.logit_bias.clone(),
             logprobs: self.logprobs,
             top_logprobs: self.top_logprobs,
-            reasoning: self.reasoning.clone(),
-            seed: self.seed,
+            reasoning: self.reasoning.as_ref().map(ReasoningConfig::to_json),
             user: self.user.clone(),
             session_id: self.session_id.clone(),
             trace: self.trace.clone(),
@@ -228,7 +549,7 @@ impl OpenRouterAdapterOptions {
             max_tokens: self.max_tokens,
             modalities: self.modalities.clone(),
             image_config: self.image_config.clone(),
-            debug: self.debug.clone(),
+            debug: self.debug.map(DebugOptions::to_json),
             stream_options: self.stream_options.clone(),
         }
     }
@@ -242,10 +563,10 @@ impl OpenRouterAdapterOptions {
 }
 
 pub struct OpenRouterAdapter {
-    transport: HttpTransport,
+    transport: Arc<dyn Transport>,
     translator: OpenRouterTranslator,
     base_url: String,
-    api_key: Option<String>,
+    credentials: ChainCredentialStore,
     options: OpenRouterAdapterOptions,
 }
 
@@ -267,15 +588,25 @@ impl OpenRouterAdapter {
         options: OpenRouterAdapterOptions,
     ) -> Result<Self, ConfigError> {
         options.validate()?;
-        let transport = HttpTransport::new(30_000, RetryPolicy::default())?;
-        Ok(Self::with_transport(api_key, base_url, options, transport))
+        let transport = HttpTransport::new(
+            options.timeout_ms.unwrap_or(OPENROUTER_DEFAULT_TIMEOUT_MS),
+            options.retry_policy.clone().unwrap_or_default(),
+        )?;
+        Ok(Self::with_transport(
+            api_key,
+            base_url,
+            options,
+            Arc::new(transport),
+        ))
     }
 
-    pub(crate) fn with_transport(
+    /// Builds the adapter over a caller-supplied transport, such as a test fake or a gateway
+    /// client.
+    pub fn with_transport(
         api_key: Option<String>,
         base_url: impl Into<String>,
         options: OpenRouterAdapterOptions,
-        transport: HttpTransport,
+        transport: Arc<dyn Transport>,
     ) -> Self {
         let translator = OpenRouterTranslator::new(options.to_translate_options());
 
@@ -283,7 +614,10 @@ impl OpenRouterAdapter {
             transport,
             translator,
             base_url: normalize_base_url(base_url),
-            api_key: sanitize_api_key(api_key),
+            credentials: ChainCredentialStore::adapter_default(
+                ProviderId::Openrouter,
+                sanitize_api_key(api_key),
+            ),
             options,
         }
     }
@@ -292,24 +626,20 @@ impl OpenRouterAdapter {
         format!("{}/api/v1/chat/completions", self.base_url)
     }
 
+    fn embeddings_url(&self) -> String {
+        format!("{}/api/v1/embeddings", self.base_url)
+    }
+
     fn models_url(&self) -> String {
         format!("{}/api/v1/models", self.base_url)
     }
 
-    fn resolve_api_key(&self, ctx: &AdapterContext) -> Option<String> {
-        if let Some(key) = self.api_key.as_ref().cloned() {
-            return Some(key);
+    /// A runtime-supplied access token is sent as the bearer credential in place of an API key.
+    fn resolve_api_key(&self, ctx: &AdapterContext) -> Result<Option<String>, ProviderError> {
+        if let Some(token) = ctx.metadata.get(ACCESS_TOKEN_METADATA_KEY) {
+            return Ok(Some(token.clone()));
         }
-
-        if let Some(key) = ctx.metadata.get(OPENROUTER_API_KEY_METADATA)
-            && !key.trim().is_empty()
-        {
-            return Some(key.clone());
-        }
-
-        std::env::var(OPENROUTER_API_KEY_ENV)
-            .ok()
-            .and_then(|value| sanitize_api_key(Some(value)))
+        credentials::resolve_api_key(&ProviderId::Openrouter, &self.credentials, ctx)
     }
 
     fn missing_api_key_error(model: Option<&str>) -> ProviderError {
@@ -362,6 +692,7 @@ impl OpenRouterAdapter {
                 request_id,
                 message,
                 model,
+                rate_limit,
                 ..
             } => {
                 let model = requested_model.map(str::to_string).or(model);
@@ -376,13 +707,14 @@ impl OpenRouterAdapter {
                         };
                     }
 
-                    return ProviderError::Status {
-                        provider: ProviderId::Openrouter,
+                    return ProviderError::from_status(
+                        ProviderId::Openrouter,
                         model,
                         status_code,
                         request_id,
                         message,
-                    };
+                        rate_limit,
+                    );
                 }
 
                 if status_code == 401 || status_code == 403 {
@@ -393,13 +725,14 @@ impl OpenRouterAdapter {
                     };
                 }
 
-                ProviderError::Status {
-                    provider: ProviderId::Openrouter,
+                ProviderError::from_status(
+                    ProviderId::Openrouter,
                     model,
                     status_code,
                     request_id,
                     message,
-                }
+                    rate_limit,
+                )
             }
             other => other,
         }
@@ -418,22 +751,30 @@ impl ProviderAdapter for OpenRouterAdapter {
             supports_structured_output: true,
             supports_thinking: true,
             supports_remote_discovery: true,
+            supports_parallel_tool_calls: true,
         }
     }
 
+    fn encode_warnings(&self, req: &ProviderRequest) -> Result<Vec<RuntimeWarning>, ProviderError> {
+        self.translator
+            .encode_request(req)
+            .map(|encoded| encoded.warnings)
+    }
+
     async fn run(
         &self,
         req: &ProviderRequest,
         ctx: &AdapterContext,
     ) -> Result<ProviderResponse, ProviderError> {
         let api_key = self
-            .resolve_api_key(ctx)
+            .resolve_api_key(ctx)?
             .ok_or_else(|| Self::missing_api_key_error(Some(&req.model.model_id)))?;
 
         let encoded = self.translator.encode_request(req)?;
         let request_ctx = self.attach_transport_context(ctx, Some(api_key));
 
-        let response_body: Value = self
+        trace::record_encoded_request(&encoded.body);
+        let response: JsonResponse<Value> = self
             .transport
             .post_json(
                 ProviderId::Openrouter,
@@ -445,6 +786,11 @@ impl ProviderAdapter for OpenRouterAdapter {
             .await
             .map_err(|error| Self::normalize_transport_error(error, Some(&req.model.model_id)))?;
 
+        let response_body = response.body;
+        trace::record_raw_response(&response_body);
+        if let Some(debug) = response_body.get("debug") {
+            trace::record_provider_debug(debug);
+        }
         let envelope = OpenRouterDecodeEnvelope {
             body: response_body,
             requested_response_format: req.response_format.clone(),
@@ -457,17 +803,85 @@ impl ProviderAdapter for OpenRouterAdapter {
             decoded.warnings = warnings;
         }
 
+        if let Some(warning) = fallback_model_served_warning(
+            &req.model.model_id,
+            &decoded.model,
+            &self.options.fallback_models,
+        ) {
+            decoded.warnings.push(warning);
+        }
+
+        decoded.request_id = response.request_id;
+        decoded.rate_limit = response.rate_limit;
         Ok(decoded)
     }
 
+    async fn embed(
+        &self,
+        req: &EmbeddingRequest,
+        ctx: &AdapterContext,
+    ) -> Result<Option<EmbeddingResponse>, ProviderError> {
+        let api_key = self
+            .resolve_api_key(ctx)?
+            .ok_or_else(|| Self::missing_api_key_error(Some(&req.model.model_id)))?;
+
+        let body = encode_embedding_request(&ProviderId::Openrouter, req)?;
+        let request_ctx = self.attach_transport_context(ctx, Some(api_key));
+
+        let response = self
+            .transport
+            .post_json(
+                ProviderId::Openrouter,
+                Some(req.model.model_id.as_str()),
+                &self.embeddings_url(),
+                &body,
+                &request_ctx,
+            )
+            .await
+            .map_err(|error| Self::normalize_transport_error(error, Some(&req.model.model_id)))?;
+
+        let mut decoded = decode_embedding_response(&ProviderId::Openrouter, req, &response.body)?;
+        decoded.request_id = response.request_id;
+        Ok(Some(decoded))
+    }
+
+    async fn generate_images(
+        &self,
+        req: &ImageGenerationRequest,
+        ctx: &AdapterContext,
+    ) -> Result<Option<ImageGenerationResponse>, ProviderError> {
+        let api_key = self
+            .resolve_api_key(ctx)?
+            .ok_or_else(|| Self::missing_api_key_error(Some(&req.model.model_id)))?;
+
+        let (body, warnings) = encode_openrouter_image_request(req)?;
+        let request_ctx = self.attach_transport_context(ctx, Some(api_key));
+
+        let response = self
+            .transport
+            .post_json(
+                ProviderId::Openrouter,
+                Some(req.model.model_id.as_str()),
+                &self.chat_completions_url(),
+                &body,
+                &request_ctx,
+            )
+            .await
+            .map_err(|error| Self::normalize_transport_error(error, Some(&req.model.model_id)))?;
+
+        let mut decoded = decode_openrouter_image_response(req, &response.body, warnings)?;
+        decoded.request_id = response.request_id;
+        Ok(Some(decoded))
+    }
+
     async fn discover_models(
         &self,
         _opts: &DiscoveryOptions,
         ctx: &AdapterContext,
     ) -> Result<Vec<ModelInfo>, ProviderError> {
-        let request_ctx = self.attach_transport_context(ctx, self.resolve_api_key(ctx));
+        let request_ctx = self.attach_transport_context(ctx, self.resolve_api_key(ctx)?);
 
-        let payload: Value = self
+        let payload = self
             .transport
             .get_json(
                 ProviderId::Openrouter,
@@ -476,7 +890,8 @@ impl ProviderAdapter for OpenRouterAdapter {
                 &request_ctx,
             )
             .await
-            .map_err(|error| Self::normalize_transport_error(error, None))?;
+            .map_err(|error| Self::normalize_transport_error(error, None))?
+            .body;
 
         decode_openrouter_models_list(&payload)
     }
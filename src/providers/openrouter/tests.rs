@@ -9,11 +9,16 @@ use std::time::Duration;
 use crate::core::error::ProviderError;
 use crate::core::traits::ProviderAdapter;
 use crate::core::types::{
-    AdapterContext, ContentPart, DiscoveryOptions, Message, MessageRole, ModelRef, ProviderId,
-    ProviderRequest, ResponseFormat, ToolChoice,
+    AdapterContext, ContentPart, DiscoveryOptions, EmbeddingRequest, ImageGenerationRequest,
+    ImageSource, Message, MessageRole, ModelRef, ProviderId, ProviderRequest, ResponseFormat,
+    ToolChoice,
 };
-use crate::providers::openrouter::{OpenRouterAdapter, OpenRouterAdapterOptions};
-use crate::transport::http::{HttpTransport, RetryPolicy};
+use crate::providers::openrouter::{
+    DataCollection, FileParserPluginOptions, OpenRouterAdapter, OpenRouterAdapterOptions,
+    PdfEngine, Plugin, ProviderPreferences, ProviderSort, ReasoningConfig, ReasoningEffort,
+    WebSearchEngine, WebSearchPluginOptions,
+};
+use crate::transport::http::{HttpTransport, JitterMode, RetryPolicy};
 
 #[derive(Debug, Clone)]
 struct MockResponse {
@@ -145,21 +150,28 @@ fn base_request() -> ProviderRequest {
         model: ModelRef {
             provider_hint: Some(ProviderId::Openrouter),
             model_id: "openai/gpt-4o-mini".to_string(),
+            requirements: None,
         },
         messages: vec![Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "hello".to_string(),
             }],
+            cache_hint: None,
         }],
         tools: Vec::new(),
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::Text,
         temperature: None,
         top_p: None,
         max_output_tokens: None,
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     }
 }
 
@@ -209,6 +221,9 @@ async fn test_openrouter_adapter_uses_translator_boundary() {
             initial_backoff_ms: 0,
             max_backoff_ms: 0,
             retryable_status_codes: vec![429],
+            max_retry_after_ms: 0,
+            max_total_retry_duration_ms: None,
+            jitter: JitterMode::None,
         },
     )
     .expect("transport");
@@ -217,7 +232,7 @@ async fn test_openrouter_adapter_uses_translator_boundary() {
         Some("test-key".to_string()),
         "http://127.0.0.1:1",
         OpenRouterAdapterOptions::default(),
-        transport,
+        Arc::new(transport),
     );
 
     let mut req = base_request();
@@ -291,6 +306,52 @@ async fn test_openrouter_adapter_sets_auth_and_attribution_headers() {
     );
 }
 
+#[tokio::test]
+async fn test_openrouter_adapter_warns_when_fallback_model_served() {
+    let mut server = MockServer::start(vec![MockResponse::new(
+        200,
+        vec![],
+        r#"{
+            "id":"chatcmpl_1",
+            "object":"chat.completion",
+            "created":123,
+            "model":"anthropic/claude-3.5-haiku",
+            "choices":[{
+                "index":0,
+                "finish_reason":"stop",
+                "message":{"role":"assistant","content":"ok"}
+            }],
+            "usage":{"prompt_tokens":1,"completion_tokens":1,"total_tokens":2}
+        }"#,
+    )]);
+
+    let options = OpenRouterAdapterOptions {
+        fallback_models: vec!["anthropic/claude-3.5-haiku".to_string()],
+        ..Default::default()
+    };
+    let adapter = OpenRouterAdapter::with_base_url_and_options(
+        Some("test-key".to_string()),
+        server.url(),
+        options,
+    )
+    .expect("adapter");
+
+    let response = adapter
+        .run(&base_request(), &AdapterContext::default())
+        .await
+        .expect("run should succeed");
+
+    let warning = response
+        .warnings
+        .iter()
+        .find(|warning| warning.code == "fallback_model_served")
+        .expect("fallback warning should be attached");
+    assert!(warning.message.contains("anthropic/claude-3.5-haiku"));
+    assert!(warning.message.contains("openai/gpt-4o-mini"));
+
+    server.shutdown();
+}
+
 #[tokio::test]
 async fn test_openrouter_adapter_maps_extended_options_to_request_body() {
     let mut server = MockServer::start(vec![MockResponse::new(
@@ -316,8 +377,28 @@ async fn test_openrouter_adapter_maps_extended_options_to_request_body() {
         logit_bias: Some(json::from_str(r#"{"12":-100,"44":2}"#).expect("logit_bias")),
         logprobs: Some(true),
         top_logprobs: Some(4),
-        reasoning: Some(json::from_str(r#"{"effort":"medium"}"#).expect("reasoning")),
-        seed: Some(42),
+        reasoning: Some(ReasoningConfig {
+            effort: Some(ReasoningEffort::Medium),
+            exclude: Some(true),
+            ..Default::default()
+        }),
+        provider_preferences: Some(ProviderPreferences {
+            order: vec!["openai".to_string(), "azure".to_string()],
+            allow_fallbacks: Some(false),
+            require_parameters: Some(true),
+            data_collection: Some(DataCollection::Deny),
+            quantizations: vec!["fp8".to_string()],
+            sort: Some(ProviderSort::Throughput),
+            extra: json::from_str(r#"{"zdr":true}"#)
+                .expect("extra")
+                .as_object()
+                .cloned()
+                .expect("object"),
+        }),
+        plugins: vec![Plugin {
+            enabled: Some(true),
+            ..Plugin::new("response-healing")
+        }],
         user: Some("user-123".to_string()),
         session_id: Some("session-abc".to_string()),
         trace: Some(json::from_str(r#"{"trace_id":"t-1"}"#).expect("trace")),
@@ -333,8 +414,10 @@ async fn test_openrouter_adapter_maps_extended_options_to_request_body() {
     )
     .expect("adapter");
 
+    let mut req = base_request();
+    req.seed = Some(42);
     let _response = adapter
-        .run(&base_request(), &AdapterContext::default())
+        .run(&req, &AdapterContext::default())
         .await
         .expect("run should succeed");
 
@@ -356,8 +439,24 @@ async fn test_openrouter_adapter_maps_extended_options_to_request_body() {
     assert_eq!(body.pointer("/logprobs"), Some(&serde_json::json!(true)));
     assert_eq!(body.pointer("/top_logprobs"), Some(&serde_json::json!(4)));
     assert_eq!(
-        body.pointer("/reasoning/effort"),
-        Some(&serde_json::json!("medium"))
+        body.pointer("/reasoning"),
+        Some(&serde_json::json!({"effort": "medium", "exclude": true}))
+    );
+    assert_eq!(
+        body.pointer("/provider"),
+        Some(&serde_json::json!({
+            "order": ["openai", "azure"],
+            "allow_fallbacks": false,
+            "require_parameters": true,
+            "data_collection": "deny",
+            "quantizations": ["fp8"],
+            "sort": "throughput",
+            "zdr": true
+        }))
+    );
+    assert_eq!(
+        body.pointer("/plugins"),
+        Some(&serde_json::json!([{"id": "response-healing", "enabled": true}]))
     );
     assert_eq!(body.pointer("/seed"), Some(&serde_json::json!(42)));
     assert_eq!(body.pointer("/user"), Some(&serde_json::json!("user-123")));
@@ -410,7 +509,114 @@ async fn test_openrouter_adapter_maps_auth_status_to_credentials_rejected() {
 }
 
 #[tokio::test]
-async fn test_openrouter_adapter_maps_non_auth_status_to_status_error() {
+async fn test_openrouter_adapter_embed_decodes_vectors_in_input_order() {
+    let mut server = MockServer::start(vec![MockResponse::new(
+        200,
+        vec![("x-request-id".to_string(), "req-embed-1".to_string())],
+        r#"{
+            "object": "list",
+            "model": "openai/text-embedding-3-small",
+            "data": [
+                {"object": "embedding", "index": 1, "embedding": [0.5, 0.25]},
+                {"object": "embedding", "index": 0, "embedding": [1.0, -1.0]}
+            ],
+            "usage": {"prompt_tokens": 6, "total_tokens": 6}
+        }"#,
+    )]);
+    let adapter = OpenRouterAdapter::with_base_url(Some("test-key".to_string()), server.url())
+        .expect("adapter");
+    let request = EmbeddingRequest {
+        model: ModelRef {
+            provider_hint: Some(ProviderId::Openrouter),
+            model_id: "openai/text-embedding-3-small".to_string(),
+            requirements: None,
+        },
+        inputs: vec!["first".to_string(), "second".to_string()],
+        dimensions: None,
+    };
+
+    let response = adapter
+        .embed(&request, &AdapterContext::default())
+        .await
+        .expect("embed should succeed")
+        .expect("openrouter supports embeddings");
+
+    assert_eq!(response.embeddings, vec![vec![1.0, -1.0], vec![0.5, 0.25]]);
+    assert_eq!(response.usage.input_tokens, Some(6));
+    assert_eq!(response.usage.output_tokens, Some(0));
+    assert_eq!(response.request_id.as_deref(), Some("req-embed-1"));
+
+    server.shutdown();
+}
+
+#[tokio::test]
+async fn test_openrouter_adapter_generate_images_reads_message_images() {
+    let mut server = MockServer::start(vec![MockResponse::new(
+        200,
+        vec![("x-request-id".to_string(), "req-image-1".to_string())],
+        r#"{
+            "id": "gen-1",
+            "model": "google/gemini-2.5-flash-image",
+            "choices": [{
+                "index": 0,
+                "finish_reason": "stop",
+                "message": {
+                    "role": "assistant",
+                    "content": "Here is your lighthouse.",
+                    "images": [
+                        {"type": "image_url", "image_url": {"url": "data:image/png;base64,aGVsbG8="}}
+                    ]
+                }
+            }],
+            "usage": {"prompt_tokens": 8, "completion_tokens": 1290, "total_tokens": 1298}
+        }"#,
+    )]);
+    let adapter = OpenRouterAdapter::with_base_url(Some("test-key".to_string()), server.url())
+        .expect("adapter");
+    let request = ImageGenerationRequest {
+        model: ModelRef {
+            provider_hint: Some(ProviderId::Openrouter),
+            model_id: "google/gemini-2.5-flash-image".to_string(),
+            requirements: None,
+        },
+        prompt: "a lighthouse at dusk".to_string(),
+        n: None,
+        size: Some("1024x1024".to_string()),
+        quality: None,
+    };
+
+    let response = adapter
+        .generate_images(&request, &AdapterContext::default())
+        .await
+        .expect("image generation should succeed")
+        .expect("openrouter supports image generation");
+
+    assert_eq!(response.images.len(), 1);
+    assert!(matches!(
+        &response.images[0].source,
+        ImageSource::Base64 { media_type: Some(media_type), .. } if media_type == "image/png"
+    ));
+    assert_eq!(response.images[0].bytes(), Some(b"hello".to_vec()));
+    assert_eq!(response.usage.output_tokens, Some(1290));
+    assert_eq!(response.request_id.as_deref(), Some("req-image-1"));
+    assert!(
+        response
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "image_option_unsupported")
+    );
+
+    let bodies = server.captured_bodies();
+    let body: serde_json::Value =
+        serde_json::from_str(&bodies[0]).expect("request body should be json");
+    assert_eq!(body["modalities"], serde_json::json!(["image", "text"]));
+    assert_eq!(body["messages"][0]["content"], "a lighthouse at dusk");
+
+    server.shutdown();
+}
+
+#[tokio::test]
+async fn test_openrouter_adapter_maps_429_to_rate_limited() {
     let mut server = MockServer::start(vec![MockResponse::new(
         429,
         vec![("x-request-id".to_string(), "req-rate-1".to_string())],
@@ -424,6 +630,9 @@ async fn test_openrouter_adapter_maps_non_auth_status_to_status_error() {
             initial_backoff_ms: 0,
             max_backoff_ms: 0,
             retryable_status_codes: vec![429],
+            max_retry_after_ms: 0,
+            max_total_retry_duration_ms: None,
+            jitter: JitterMode::None,
         },
     )
     .expect("transport");
@@ -432,7 +641,7 @@ async fn test_openrouter_adapter_maps_non_auth_status_to_status_error() {
         Some("test-key".to_string()),
         server.url(),
         OpenRouterAdapterOptions::default(),
-        transport,
+        Arc::new(transport),
     );
 
     let err = adapter
@@ -441,21 +650,20 @@ async fn test_openrouter_adapter_maps_non_auth_status_to_status_error() {
         .expect_err("run should fail");
 
     match err {
-        ProviderError::Status {
+        ProviderError::RateLimited {
             provider,
             model,
-            status_code,
             request_id,
             message,
+            ..
         } => {
             assert_eq!(provider, ProviderId::Openrouter);
             assert_eq!(model, Some("openai/gpt-4o-mini".to_string()));
-            assert_eq!(status_code, 429);
             assert_eq!(request_id, Some("req-rate-1".to_string()));
             assert!(message.contains("Rate limit exceeded"));
             assert!(message.contains("openrouter error"));
         }
-        other => panic!("expected status error, got {other:?}"),
+        other => panic!("expected rate limited error, got {other:?}"),
     }
 
     server.shutdown();
@@ -562,6 +770,41 @@ async fn test_openrouter_adapter_discover_models_invalid_payload_is_protocol_err
     server.shutdown();
 }
 
+#[test]
+fn test_openrouter_plugin_helpers_build_expected_json() {
+    let web = Plugin::web_search(WebSearchPluginOptions {
+        engine: Some(WebSearchEngine::Exa),
+        max_results: Some(3),
+        search_prompt: Some("Sources:".to_string()),
+    });
+    assert_eq!(
+        web.to_json(),
+        serde_json::json!({
+            "id": "web",
+            "engine": "exa",
+            "max_results": 3,
+            "search_prompt": "Sources:"
+        })
+    );
+
+    let file_parser = Plugin::file_parser(FileParserPluginOptions {
+        pdf_engine: Some(PdfEngine::MistralOcr),
+    });
+    assert_eq!(
+        file_parser.to_json(),
+        serde_json::json!({"id": "file-parser", "pdf": {"engine": "mistral-ocr"}})
+    );
+
+    assert_eq!(
+        Plugin::response_healing().to_json(),
+        serde_json::json!({"id": "response-healing"})
+    );
+    assert_eq!(
+        Plugin::web_search(WebSearchPluginOptions::default()).to_json(),
+        serde_json::json!({"id": "web"})
+    );
+}
+
 #[test]
 fn test_openrouter_options_validation() {
     let bad = OpenRouterAdapter::with_base_url_and_options(
@@ -582,16 +825,60 @@ fn test_openrouter_options_validation() {
         None,
         "http://example.com",
         OpenRouterAdapterOptions {
-            provider_preferences: Some(json::from_str("[]").expect("json array")),
+            provider_preferences: Some(ProviderPreferences {
+                order: vec![" ".to_string()],
+                ..Default::default()
+            }),
             ..Default::default()
         },
     );
     let bad_provider = match bad_provider {
-        Ok(_) => panic!("provider preferences array should fail"),
+        Ok(_) => panic!("empty provider order entry should fail"),
         Err(error) => error,
     };
     assert!(bad_provider.to_string().contains("provider_preferences"));
 
+    let conflicting_extra = OpenRouterAdapter::with_base_url_and_options(
+        None,
+        "http://example.com",
+        OpenRouterAdapterOptions {
+            provider_preferences: Some(ProviderPreferences {
+                extra: json::from_str(r#"{"sort":"price"}"#)
+                    .expect("extra")
+                    .as_object()
+                    .cloned()
+                    .expect("object"),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    );
+    assert!(conflicting_extra.is_err());
+
+    let bad_reasoning = OpenRouterAdapter::with_base_url_and_options(
+        None,
+        "http://example.com",
+        OpenRouterAdapterOptions {
+            reasoning: Some(ReasoningConfig {
+                effort: Some(ReasoningEffort::High),
+                max_tokens: Some(1024),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    );
+    assert!(bad_reasoning.is_err());
+
+    let bad_plugin = OpenRouterAdapter::with_base_url_and_options(
+        None,
+        "http://example.com",
+        OpenRouterAdapterOptions {
+            plugins: vec![Plugin::new("")],
+            ..Default::default()
+        },
+    );
+    assert!(bad_plugin.is_err());
+
     let bad_frequency = OpenRouterAdapter::with_base_url_and_options(
         None,
         "http://example.com",
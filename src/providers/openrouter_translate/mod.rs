@@ -4,22 +4,33 @@ use serde_json::{Map, Value, json};
 
 use crate::core::error::ProviderError;
 use crate::core::types::{
-    AssistantOutput, ContentPart, FinishReason, Message, MessageRole, ModelInfo, ProviderId,
-    ProviderRequest, ProviderResponse, ResponseFormat, RuntimeWarning, ToolCall, ToolChoice,
-    ToolDefinition, ToolResult, ToolResultContent, Usage,
+    Annotation, AssistantOutput, ContentPart, CostBreakdown, FinishReason, GeneratedImage,
+    ImageGenerationRequest, ImageGenerationResponse, ImageSource, Message, MessageRole, ModelInfo,
+    ModelPricing, PricingSource, ProviderId, ProviderRequest, ProviderResponse,
+    ResponseAlternative, ResponseFormat, RuntimeWarning, ToolCall, ToolChoice, ToolDefinition,
+    ToolResult, ToolResultContent, Usage, WarningCode,
 };
+use crate::providers::request_options::merge_provider_options;
 use crate::providers::translator_contract::ProviderTranslator;
 
-const WARN_BOTH_TEMPERATURE_AND_TOP_P_SET: &str = "both_temperature_and_top_p_set";
-const WARN_TOOL_ARGUMENTS_INVALID_JSON: &str = "tool_arguments_invalid_json";
-const WARN_USAGE_MISSING: &str = "usage_missing";
-const WARN_USAGE_PARTIAL: &str = "usage_partial";
-const WARN_STRUCTURED_OUTPUT_PARSE_FAILED: &str = "structured_output_parse_failed";
-const WARN_UNKNOWN_FINISH_REASON: &str = "unknown_finish_reason";
-const WARN_EMPTY_OUTPUT: &str = "empty_output";
-const WARN_TOOL_RESULT_COERCED: &str = "tool_result_coerced";
-const WARN_TOOL_RESULT_RAW_PROVIDER_CONTENT_IGNORED: &str =
-    "tool_result_raw_provider_content_ignored";
+/// Body fields owned by typed `ProviderRequest` fields; `provider_options` may not set them.
+const RESERVED_PROVIDER_OPTION_KEYS: &[&str] = &[
+    "model",
+    "models",
+    "messages",
+    "stream",
+    "tools",
+    "tool_choice",
+    "parallel_tool_calls",
+    "response_format",
+    "temperature",
+    "top_p",
+    "max_completion_tokens",
+    "seed",
+    "n",
+    "stop",
+    "metadata",
+];
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub(crate) struct OpenRouterTranslateOptions {
@@ -33,7 +44,6 @@ pub(crate) struct OpenRouterTranslateOptions {
     pub logprobs: Option<bool>,
     pub top_logprobs: Option<u8>,
     pub reasoning: Option<Value>,
-    pub seed: Option<i64>,
     pub user: Option<String>,
     pub session_id: Option<String>,
     pub trace: Option<Value>,
@@ -103,10 +113,17 @@ pub(crate) fn encode_openrouter_request(
     let mut warnings = Vec::new();
     if req.temperature.is_some() && req.top_p.is_some() {
         warnings.push(RuntimeWarning {
-            code: WARN_BOTH_TEMPERATURE_AND_TOP_P_SET.to_string(),
+            code: WarningCode::BothTemperatureAndTopPSet,
             message: "OpenRouter recommends setting temperature or top_p, but not both".to_string(),
         });
     }
+    if !req.hosted_tools.is_empty() {
+        warnings.push(RuntimeWarning {
+            code: WarningCode::HostedToolUnsupported,
+            message: "OpenRouter has no hosted tools; use the web plugin for search. Dropped"
+                .to_string(),
+        });
+    }
 
     validate_options(options, &req.model.model_id)?;
     let tools = map_tools(req)?;
@@ -191,10 +208,14 @@ pub(crate) fn encode_openrouter_request(
         body.insert("max_tokens".to_string(), json!(max_tokens));
     }
 
-    if let Some(seed) = options.seed {
+    if let Some(seed) = req.seed {
         body.insert("seed".to_string(), json!(seed));
     }
 
+    if let Some(n) = req.n {
+        body.insert("n".to_string(), json!(n));
+    }
+
     if !req.stop.is_empty() {
         body.insert("stop".to_string(), json!(req.stop));
     }
@@ -203,7 +224,7 @@ pub(crate) fn encode_openrouter_request(
         body.insert("metadata".to_string(), json!(req.metadata));
     }
 
-    if let Some(value) = options.parallel_tool_calls {
+    if let Some(value) = req.parallel_tool_calls.or(options.parallel_tool_calls) {
         body.insert("parallel_tool_calls".to_string(), Value::Bool(value));
     }
 
@@ -236,6 +257,13 @@ pub(crate) fn encode_openrouter_request(
     }
 
     if let Some(debug) = &options.debug {
+        if debug.get("echo_upstream_body") == Some(&Value::Bool(true)) {
+            warnings.push(RuntimeWarning {
+                code: WarningCode::DebugEchoEnabled,
+                message: "OpenRouter debug echo is enabled; intended for non-production use"
+                    .to_string(),
+            });
+        }
         body.insert("debug".to_string(), debug.clone());
     }
 
@@ -247,6 +275,14 @@ pub(crate) fn encode_openrouter_request(
         body.insert("plugins".to_string(), Value::Array(options.plugins.clone()));
     }
 
+    merge_provider_options(
+        &mut body,
+        req,
+        &ProviderId::Openrouter,
+        RESERVED_PROVIDER_OPTION_KEYS,
+    )
+    .map_err(|message| protocol_error(Some(&req.model.model_id), message))?;
+
     Ok(OpenRouterEncodedRequest {
         body: Value::Object(body),
         warnings,
@@ -286,16 +322,67 @@ pub(crate) fn decode_openrouter_response(
         ));
     }
 
-    let choice = choices[0].as_object().ok_or_else(|| {
+    let mut warnings = Vec::new();
+    let (mut output, finish_reason_raw) = decode_choice(
+        &choices[0],
+        0,
+        &payload.requested_response_format,
+        &model,
+        &mut warnings,
+    )?;
+
+    let mut alternatives = Vec::with_capacity(choices.len() - 1);
+    for (index, choice) in choices.iter().enumerate().skip(1) {
+        let (output, finish_reason_raw) = decode_choice(
+            choice,
+            index,
+            &payload.requested_response_format,
+            &model,
+            &mut warnings,
+        )?;
+        alternatives.push(ResponseAlternative {
+            output,
+            finish_reason: map_finish_reason(finish_reason_raw, &model, &mut warnings),
+        });
+    }
+    append_root_citations(root.get("citations"), &mut output.annotations);
+
+    let finish_reason = map_finish_reason(finish_reason_raw, &model, &mut warnings);
+    let usage = decode_usage(root.get("usage"), &model, &mut warnings)?;
+
+    Ok(ProviderResponse {
+        output,
+        usage,
+        cost: decode_reported_cost(root.get("usage")),
+        provider: ProviderId::Openrouter,
+        model,
+        raw_provider_response: None,
+        finish_reason,
+        warnings,
+        alternatives,
+        response_id: root.get("id").and_then(Value::as_str).map(str::to_string),
+        request_id: None,
+        rate_limit: None,
+    })
+}
+
+fn decode_choice<'a>(
+    choice: &'a Value,
+    index: usize,
+    requested_response_format: &ResponseFormat,
+    model: &str,
+    warnings: &mut Vec<RuntimeWarning>,
+) -> Result<(AssistantOutput, Option<&'a str>), ProviderError> {
+    let choice = choice.as_object().ok_or_else(|| {
         protocol_error(
-            Some(&model),
-            "openrouter response choices[0] must be a JSON object",
+            Some(model),
+            format!("openrouter response choices[{index}] must be a JSON object"),
         )
     })?;
 
     if let Some(choice_error) = choice.get("error") {
         return Err(protocol_error(
-            Some(&model),
+            Some(model),
             format!(
                 "openrouter response choice contained error: {}",
                 stable_json_string(choice_error)
@@ -306,7 +393,7 @@ pub(crate) fn decode_openrouter_response(
     let finish_reason_raw = choice.get("finish_reason").and_then(Value::as_str);
     if finish_reason_raw == Some("error") {
         return Err(protocol_error(
-            Some(&model),
+            Some(model),
             "openrouter response finish_reason was error",
         ));
     }
@@ -314,60 +401,75 @@ pub(crate) fn decode_openrouter_response(
     let message = choice
         .get("message")
         .and_then(Value::as_object)
-        .ok_or_else(|| {
-            protocol_error(Some(&model), "openrouter response missing choice message")
-        })?;
+        .ok_or_else(|| protocol_error(Some(model), "openrouter response missing choice message"))?;
 
     if let Some(role) = message.get("role").and_then(Value::as_str)
         && role != "assistant"
     {
         return Err(protocol_error(
-            Some(&model),
+            Some(model),
             format!("openrouter response message role must be assistant, got {role}"),
         ));
     }
 
-    let mut warnings = Vec::new();
     let mut content = Vec::new();
     let mut text_blocks = Vec::new();
 
-    decode_message_content(message.get("content"), &mut content, &mut text_blocks)?;
-    decode_refusal(message.get("refusal"), &mut content, &mut text_blocks)?;
-    decode_tool_calls(
-        message.get("tool_calls"),
+    decode_reasoning(
+        message.get("reasoning"),
+        message.get("reasoning_details"),
         &mut content,
-        &mut warnings,
-        &model,
     )?;
+    decode_message_content(message.get("content"), &mut content, &mut text_blocks)?;
+    decode_refusal(message.get("refusal"), &mut content, &mut text_blocks)?;
+    decode_tool_calls(message.get("tool_calls"), &mut content, warnings, model)?;
+    // Chat completions return a single text part, which every citation range refers to.
+    let text_part = content
+        .iter()
+        .position(|part| matches!(part, ContentPart::Text { .. }));
+    let annotations = decode_annotations(message.get("annotations"), text_part, model, warnings)?;
 
     if content.is_empty() {
+        let message = if index == 0 {
+            "openrouter response contained no decodable output content".to_string()
+        } else {
+            format!("openrouter response choices[{index}] contained no decodable output content")
+        };
         warnings.push(RuntimeWarning {
-            code: WARN_EMPTY_OUTPUT.to_string(),
-            message: "openrouter response contained no decodable output content".to_string(),
+            code: WarningCode::EmptyOutput,
+            message,
         });
     }
 
-    let finish_reason = map_finish_reason(finish_reason_raw, &model, &mut warnings);
-    let usage = decode_usage(root.get("usage"), &model, &mut warnings)?;
-    let structured_output = decode_structured_output(
-        &payload.requested_response_format,
-        &text_blocks,
-        &model,
-        &mut warnings,
-    );
+    let structured_output =
+        decode_structured_output(requested_response_format, &text_blocks, model, warnings);
 
-    Ok(ProviderResponse {
-        output: AssistantOutput {
+    Ok((
+        AssistantOutput {
             content,
             structured_output,
+            annotations,
         },
-        usage,
-        cost: None,
-        provider: ProviderId::Openrouter,
-        model,
-        raw_provider_response: None,
-        finish_reason,
-        warnings,
+        finish_reason_raw,
+    ))
+}
+
+/// Flags responses served by a model other than the requested one when fallback models are
+/// configured, so cost and behavior drift are visible to callers.
+pub(crate) fn fallback_model_served_warning(
+    requested_model: &str,
+    served_model: &str,
+    fallback_models: &[String],
+) -> Option<RuntimeWarning> {
+    if fallback_models.is_empty() || served_model == requested_model {
+        return None;
+    }
+
+    Some(RuntimeWarning {
+        code: WarningCode::FallbackModelServed,
+        message: format!(
+            "openrouter served fallback model {served_model} instead of requested model {requested_model}"
+        ),
     })
 }
 
@@ -455,22 +557,144 @@ pub(crate) fn decode_openrouter_models_list(
             max_output_tokens,
             supports_tools,
             supports_structured_output,
+            pricing: model_obj
+                .get("pricing")
+                .and_then(Value::as_object)
+                .and_then(decode_model_pricing),
+            input_modalities: decode_modalities(model_obj, "input_modalities"),
+            output_modalities: decode_modalities(model_obj, "output_modalities"),
+            deprecated: model_obj
+                .get("expiration_date")
+                .is_some_and(|expiration| !expiration.is_null()),
+            knowledge_cutoff: model_obj
+                .get("knowledge_cutoff")
+                .and_then(Value::as_str)
+                .map(str::to_string),
         });
     }
 
     Ok(discovered)
 }
 
-fn validate_provider_hint(req: &ProviderRequest) -> Result<(), ProviderError> {
-    if let Some(provider_hint) = &req.model.provider_hint {
-        if *provider_hint != ProviderId::Openrouter {
-            return Err(protocol_error(
-                Some(&req.model.model_id),
-                format!("provider_hint must be Openrouter, got {provider_hint:?}"),
-            ));
+/// Encodes an image generation request as a chat completion with image output enabled, which is
+/// how OpenRouter exposes image-capable models. OpenRouter has no `n`, `size`, or `quality`
+/// controls for these models, so they are dropped with a warning.
+pub(crate) fn encode_openrouter_image_request(
+    req: &ImageGenerationRequest,
+) -> Result<(Value, Vec<RuntimeWarning>), ProviderError> {
+    if let Some(provider_hint) = &req.model.provider_hint
+        && *provider_hint != ProviderId::Openrouter
+    {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            format!("provider_hint must be Openrouter, got {provider_hint:?}"),
+        ));
+    }
+    if req.model.model_id.trim().is_empty() {
+        return Err(protocol_error(None, "missing model_id"));
+    }
+    if req.prompt.trim().is_empty() {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            "image generation prompt is empty",
+        ));
+    }
+
+    let mut warnings = Vec::new();
+    for (option, set) in [
+        ("n", req.n.is_some_and(|n| n != 1)),
+        ("size", req.size.is_some()),
+        ("quality", req.quality.is_some()),
+    ] {
+        if set {
+            warnings.push(RuntimeWarning {
+                code: WarningCode::ImageOptionUnsupported,
+                message: format!("openrouter image generation ignores {option}"),
+            });
         }
     }
 
+    Ok((
+        json!({
+            "model": req.model.model_id,
+            "messages": [{ "role": "user", "content": req.prompt }],
+            "modalities": ["image", "text"],
+        }),
+        warnings,
+    ))
+}
+
+pub(crate) fn decode_openrouter_image_response(
+    req: &ImageGenerationRequest,
+    body: &Value,
+    mut warnings: Vec<RuntimeWarning>,
+) -> Result<ImageGenerationResponse, ProviderError> {
+    let model_id = req.model.model_id.as_str();
+    let message = body
+        .pointer("/choices/0/message")
+        .and_then(Value::as_object)
+        .ok_or_else(|| protocol_error(Some(model_id), "image response has no message"))?;
+
+    let images = message
+        .get("images")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|image| image.pointer("/image_url/url").and_then(Value::as_str))
+        .map(|url| GeneratedImage {
+            source: image_source_from_url(url),
+            revised_prompt: None,
+        })
+        .collect::<Vec<_>>();
+    if images.is_empty() {
+        return Err(protocol_error(
+            Some(model_id),
+            "image response contained no images; check that the model supports image output",
+        ));
+    }
+
+    let usage = decode_usage(body.get("usage"), model_id, &mut warnings)?;
+    Ok(ImageGenerationResponse {
+        images,
+        usage,
+        cost: decode_reported_cost(body.get("usage")),
+        provider: ProviderId::Openrouter,
+        model: body
+            .get("model")
+            .and_then(Value::as_str)
+            .unwrap_or(model_id)
+            .to_string(),
+        request_id: None,
+        warnings,
+    })
+}
+
+/// Splits `data:<media type>;base64,<data>` URLs into inline image data.
+fn image_source_from_url(url: &str) -> ImageSource {
+    url.strip_prefix("data:")
+        .and_then(|rest| rest.split_once(";base64,"))
+        .map_or_else(
+            || ImageSource::Url {
+                url: url.to_string(),
+            },
+            |(media_type, data)| ImageSource::Base64 {
+                media_type: Some(media_type.to_string()).filter(|value| !value.is_empty()),
+                data: data.to_string(),
+            },
+        )
+}
+
+fn validate_provider_hint(req: &ProviderRequest) -> Result<(), ProviderError> {
+    if let Some(provider_hint) = &req.model.provider_hint
+        && *provider_hint != ProviderId::Openrouter
+    {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            format!("provider_hint must be Openrouter, got {provider_hint:?}"),
+        ));
+    }
+
     Ok(())
 }
 
@@ -520,22 +744,22 @@ fn validate_metadata(req: &ProviderRequest) -> Result<(), ProviderError> {
 }
 
 fn validate_sampling_controls(req: &ProviderRequest) -> Result<(), ProviderError> {
-    if let Some(temperature) = req.temperature {
-        if !(0.0..=2.0).contains(&temperature) {
-            return Err(protocol_error(
-                Some(&req.model.model_id),
-                format!("temperature must be in [0.0, 2.0], got {temperature}"),
-            ));
-        }
+    if let Some(temperature) = req.temperature
+        && !(0.0..=2.0).contains(&temperature)
+    {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            format!("temperature must be in [0.0, 2.0], got {temperature}"),
+        ));
     }
 
-    if let Some(top_p) = req.top_p {
-        if !(0.0..=1.0).contains(&top_p) {
-            return Err(protocol_error(
-                Some(&req.model.model_id),
-                format!("top_p must be in [0.0, 1.0], got {top_p}"),
-            ));
-        }
+    if let Some(top_p) = req.top_p
+        && !(0.0..=1.0).contains(&top_p)
+    {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            format!("top_p must be in [0.0, 1.0], got {top_p}"),
+        ));
     }
 
     if req.max_output_tokens == Some(0) {
@@ -545,6 +769,13 @@ fn validate_sampling_controls(req: &ProviderRequest) -> Result<(), ProviderError
         ));
     }
 
+    if req.n == Some(0) {
+        return Err(protocol_error(
+            Some(&req.model.model_id),
+            "n must be at least 1",
+        ));
+    }
+
     Ok(())
 }
 
@@ -708,11 +939,18 @@ fn validate_options(
         ));
     }
 
-    if options.debug.is_some() {
-        return Err(protocol_error(
-            Some(model_id),
-            "debug is unsupported in non-streaming canonical mode",
-        ));
+    if let Some(debug) = &options.debug {
+        let valid = debug.as_object().is_some_and(|debug| {
+            debug
+                .iter()
+                .all(|(key, value)| key == "echo_upstream_body" && value.is_boolean())
+        });
+        if !valid {
+            return Err(protocol_error(
+                Some(model_id),
+                "debug must be an object with only a boolean echo_upstream_body",
+            ));
+        }
     }
 
     if options.stream_options.is_some() {
@@ -883,9 +1121,11 @@ fn map_message(
     warnings: &mut Vec<RuntimeWarning>,
 ) -> Result<Value, ProviderError> {
     match message.role {
-        MessageRole::System => map_string_message("system", &message.content, model_id),
+        MessageRole::System | MessageRole::Developer => {
+            map_string_message("system", &message.content, model_id)
+        }
         MessageRole::User => map_string_message("user", &message.content, model_id),
-        MessageRole::Assistant => map_assistant_message(&message.content, model_id),
+        MessageRole::Assistant => map_assistant_message(&message.content, model_id, warnings),
         MessageRole::Tool => map_tool_message(&message.content, model_id, warnings),
     }
 }
@@ -902,13 +1142,46 @@ fn map_string_message(
     }))
 }
 
-fn map_assistant_message(content: &[ContentPart], model_id: &str) -> Result<Value, ProviderError> {
+fn map_assistant_message(
+    content: &[ContentPart],
+    model_id: &str,
+    warnings: &mut Vec<RuntimeWarning>,
+) -> Result<Value, ProviderError> {
     let mut text_parts = Vec::new();
     let mut tool_calls = Vec::new();
+    let mut reasoning_parts = Vec::new();
+    let mut reasoning_details = Vec::new();
 
     for part in content {
         match part {
             ContentPart::Text { text } => text_parts.push(text.clone()),
+            ContentPart::Thinking { text, signature } => {
+                reasoning_parts.push(text.clone());
+                if let Some(signature) = signature {
+                    reasoning_details.push(json!({
+                        "type": "reasoning.text",
+                        "text": text,
+                        "signature": signature,
+                    }));
+                }
+            }
+            ContentPart::HostedToolCall { hosted_tool_call } => {
+                warnings.push(RuntimeWarning {
+                    code: WarningCode::HostedToolCallDropped,
+                    message: format!(
+                        "hosted tool call '{}' cannot be replayed to OpenRouter; dropped",
+                        hosted_tool_call.name
+                    ),
+                });
+            }
+            ContentPart::Other { kind, .. } => {
+                warnings.push(RuntimeWarning {
+                    code: WarningCode::UnknownContentPartDropped,
+                    message: format!(
+                        "unknown '{kind}' content cannot be replayed to OpenRouter; dropped"
+                    ),
+                });
+            }
             ContentPart::ToolCall { tool_call } => {
                 if tool_call.id.trim().is_empty() {
                     return Err(protocol_error(
@@ -971,6 +1244,20 @@ fn map_assistant_message(content: &[ContentPart], model_id: &str) -> Result<Valu
         payload.insert("tool_calls".to_string(), Value::Array(tool_calls));
     }
 
+    if !reasoning_parts.is_empty() {
+        payload.insert(
+            "reasoning".to_string(),
+            Value::String(reasoning_parts.join("\n")),
+        );
+    }
+
+    if !reasoning_details.is_empty() {
+        payload.insert(
+            "reasoning_details".to_string(),
+            Value::Array(reasoning_details),
+        );
+    }
+
     Ok(Value::Object(payload))
 }
 
@@ -1023,31 +1310,41 @@ fn coerce_tool_result_output(
         }
 
         warnings.push(RuntimeWarning {
-            code: WARN_TOOL_RESULT_RAW_PROVIDER_CONTENT_IGNORED.to_string(),
+            code: WarningCode::ToolResultRawProviderContentIgnored,
             message:
                 "tool_result raw_provider_content ignored for OpenRouter because it is not a string"
                     .to_string(),
         });
     }
 
-    match &tool_result.content {
-        ToolResultContent::Text { text } => Ok(text.clone()),
+    let output = match &tool_result.content {
+        ToolResultContent::Text { text } => text.clone(),
         ToolResultContent::Json { value } => {
             warnings.push(RuntimeWarning {
-                code: WARN_TOOL_RESULT_COERCED.to_string(),
+                code: WarningCode::ToolResultCoerced,
                 message: "tool_result JSON content coerced to string for OpenRouter tool message"
                     .to_string(),
             });
-            Ok(stable_json_string(&canonicalize_json(value)))
+            stable_json_string(&canonicalize_json(value))
         }
         ToolResultContent::Parts { parts } => {
             warnings.push(RuntimeWarning {
-                code: WARN_TOOL_RESULT_COERCED.to_string(),
+                code: WarningCode::ToolResultCoerced,
                 message: "tool_result parts content coerced to newline-delimited string for OpenRouter tool message".to_string(),
             });
-            join_text_parts(parts, model_id, "tool_result", false)
+            join_text_parts(parts, model_id, "tool_result", false)?
         }
+    };
+    Ok(mark_tool_error(tool_result, output))
+}
+
+/// Tool messages carry plain strings, so failed tool results are wrapped in a JSON envelope the
+/// model can tell apart from a successful output.
+fn mark_tool_error(tool_result: &ToolResult, output: String) -> String {
+    if !tool_result.is_error {
+        return output;
     }
+    stable_json_string(&json!({ "is_error": true, "content": output }))
 }
 
 fn join_text_parts(
@@ -1088,6 +1385,42 @@ fn is_valid_tool_name(name: &str) -> bool {
         .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
 }
 
+fn decode_reasoning(
+    reasoning_value: Option<&Value>,
+    details_value: Option<&Value>,
+    content: &mut Vec<ContentPart>,
+) -> Result<(), ProviderError> {
+    let reasoning = match reasoning_value {
+        None | Some(Value::Null) => return Ok(()),
+        Some(Value::String(text)) => text,
+        Some(_) => {
+            return Err(protocol_error(
+                None,
+                "assistant reasoning must be a string or null",
+            ));
+        }
+    };
+
+    if reasoning.is_empty() {
+        return Ok(());
+    }
+
+    let signature = details_value
+        .and_then(Value::as_array)
+        .and_then(|details| {
+            details
+                .iter()
+                .find_map(|detail| detail.get("signature").and_then(Value::as_str))
+        })
+        .map(str::to_string);
+
+    content.push(ContentPart::Thinking {
+        text: reasoning.clone(),
+        signature,
+    });
+    Ok(())
+}
+
 fn decode_message_content(
     value: Option<&Value>,
     content: &mut Vec<ContentPart>,
@@ -1170,6 +1503,115 @@ fn decode_refusal(
     Ok(())
 }
 
+/// Decodes `message.annotations` produced by the `web` and `file-parser` plugins.
+fn decode_annotations(
+    value: Option<&Value>,
+    text_part: Option<usize>,
+    model: &str,
+    warnings: &mut Vec<RuntimeWarning>,
+) -> Result<Vec<Annotation>, ProviderError> {
+    let Some(value) = value else {
+        return Ok(Vec::new());
+    };
+    if value.is_null() {
+        return Ok(Vec::new());
+    }
+
+    let items = value.as_array().ok_or_else(|| {
+        protocol_error(
+            Some(model),
+            "openrouter message annotations must be an array",
+        )
+    })?;
+
+    let mut annotations = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        let item_type = item.get("type").and_then(Value::as_str);
+        match item_type {
+            Some("url_citation") => {
+                let citation = item
+                    .get("url_citation")
+                    .and_then(Value::as_object)
+                    .ok_or_else(|| {
+                        protocol_error(
+                            Some(model),
+                            format!("annotations[{index}] missing url_citation object"),
+                        )
+                    })?;
+                let url = citation.get("url").and_then(Value::as_str).ok_or_else(|| {
+                    protocol_error(
+                        Some(model),
+                        format!("annotations[{index}].url_citation missing url"),
+                    )
+                })?;
+                annotations.push(Annotation::UrlCitation {
+                    url: url.to_string(),
+                    title: optional_string(citation.get("title")),
+                    content: optional_string(citation.get("content")),
+                    start_index: citation.get("start_index").and_then(Value::as_u64),
+                    end_index: citation.get("end_index").and_then(Value::as_u64),
+                    part_index: text_part,
+                });
+            }
+            Some("file") => {
+                let file = item.get("file").and_then(Value::as_object).ok_or_else(|| {
+                    protocol_error(
+                        Some(model),
+                        format!("annotations[{index}] missing file object"),
+                    )
+                })?;
+                let hash = file.get("hash").and_then(Value::as_str).ok_or_else(|| {
+                    protocol_error(
+                        Some(model),
+                        format!("annotations[{index}].file missing hash"),
+                    )
+                })?;
+                annotations.push(Annotation::File {
+                    hash: hash.to_string(),
+                    name: optional_string(file.get("name")),
+                    content: file.get("content").cloned(),
+                });
+            }
+            other => warnings.push(RuntimeWarning {
+                code: WarningCode::UnknownAnnotationDropped,
+                message: format!(
+                    "openrouter annotation type {} dropped",
+                    other.unwrap_or("<missing>")
+                ),
+            }),
+        }
+    }
+
+    Ok(annotations)
+}
+
+/// Perplexity models list their sources as a top-level `citations` array of URLs, referenced
+/// from the text as `[1]`, `[2]`, ... . Sources already present as annotations are skipped.
+fn append_root_citations(value: Option<&Value>, annotations: &mut Vec<Annotation>) {
+    let Some(urls) = value.and_then(Value::as_array) else {
+        return;
+    };
+    for url in urls.iter().filter_map(Value::as_str) {
+        let known = annotations.iter().any(|annotation| {
+            matches!(annotation, Annotation::UrlCitation { url: existing, .. } if existing == url)
+        });
+        if !known {
+            annotations.push(Annotation::UrlCitation {
+                url: url.to_string(),
+                title: None,
+                content: None,
+                start_index: None,
+                end_index: None,
+                part_index: None,
+            });
+        }
+    }
+}
+
+fn optional_string(value: Option<&Value>) -> Option<String> {
+    value.and_then(Value::as_str).map(str::to_string)
+}
+
 fn decode_tool_calls(
     value: Option<&Value>,
     content: &mut Vec<ContentPart>,
@@ -1227,7 +1669,7 @@ fn decode_tool_calls(
             Ok(value) => value,
             Err(_) => {
                 warnings.push(RuntimeWarning {
-                    code: WARN_TOOL_ARGUMENTS_INVALID_JSON.to_string(),
+                    code: WarningCode::ToolArgumentsInvalidJson,
                     message: format!(
                         "openrouter tool_call arguments were not valid JSON for call_id={id}"
                     ),
@@ -1255,7 +1697,7 @@ fn decode_usage(
 ) -> Result<Usage, ProviderError> {
     let Some(usage_value) = usage_value else {
         warnings.push(RuntimeWarning {
-            code: WARN_USAGE_MISSING.to_string(),
+            code: WarningCode::UsageMissing,
             message: "openrouter response usage was missing".to_string(),
         });
         return Ok(Usage::default());
@@ -1263,7 +1705,7 @@ fn decode_usage(
 
     if usage_value.is_null() {
         warnings.push(RuntimeWarning {
-            code: WARN_USAGE_MISSING.to_string(),
+            code: WarningCode::UsageMissing,
             message: "openrouter response usage was null".to_string(),
         });
         return Ok(Usage::default());
@@ -1283,17 +1725,26 @@ fn decode_usage(
         .and_then(|details| details.get("cached_tokens"))
         .and_then(number_to_u64);
 
+    let reasoning_tokens = usage_obj
+        .get("completion_tokens_details")
+        .and_then(Value::as_object)
+        .and_then(|details| details.get("reasoning_tokens"))
+        .and_then(number_to_u64);
+
     let usage = Usage {
         input_tokens,
         output_tokens,
         cached_input_tokens,
         total_tokens,
+        reasoning_tokens,
+        cache_creation_input_tokens: None,
+        web_search_requests: None,
     };
 
     if usage.input_tokens.is_none() || usage.output_tokens.is_none() || usage.total_tokens.is_none()
     {
         warnings.push(RuntimeWarning {
-            code: WARN_USAGE_PARTIAL.to_string(),
+            code: WarningCode::UsagePartial,
             message: "openrouter response usage was partial".to_string(),
         });
     }
@@ -1301,6 +1752,80 @@ fn decode_usage(
     Ok(usage)
 }
 
+fn decode_modalities(model_obj: &Map<String, Value>, key: &str) -> Vec<String> {
+    model_obj
+        .get("architecture")
+        .and_then(|architecture| architecture.get(key))
+        .and_then(Value::as_array)
+        .map(|modalities| {
+            modalities
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_ascii_lowercase)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// OpenRouter bills in USD credits and reports the charge as `usage.cost`. The upstream split in
+/// `cost_details` is used when present; otherwise input and output are left at zero for the
+/// runtime to apportion from configured pricing.
+fn decode_reported_cost(usage_value: Option<&Value>) -> Option<CostBreakdown> {
+    let usage_obj = usage_value?.as_object()?;
+    let total_cost = usage_obj
+        .get("cost")
+        .and_then(Value::as_f64)
+        .filter(|cost| cost.is_finite() && *cost >= 0.0)?;
+
+    let details = usage_obj.get("cost_details").and_then(Value::as_object);
+    let detail = |key: &str| {
+        details
+            .and_then(|details| details.get(key))
+            .and_then(Value::as_f64)
+    };
+    let (input_cost, output_cost) = match (
+        detail("upstream_inference_prompt_cost"),
+        detail("upstream_inference_completions_cost"),
+    ) {
+        (Some(input), Some(output)) if input + output > 0.0 => {
+            let scale = total_cost / (input + output);
+            (input * scale, output * scale)
+        }
+        _ => (0.0, 0.0),
+    };
+
+    Some(CostBreakdown {
+        currency: "USD".to_string(),
+        input_cost,
+        cached_input_cost: 0.0,
+        output_cost,
+        fixed_cost: 0.0,
+        total_cost,
+        pricing_source: PricingSource::ProviderReported,
+    })
+}
+
+/// Decodes the `/models` price list, whose rates are decimal strings per token. Negative rates
+/// mark routers like `openrouter/auto` whose price depends on the chosen model.
+fn decode_model_pricing(pricing: &Map<String, Value>) -> Option<ModelPricing> {
+    let rate = |key: &str| {
+        let value = pricing.get(key)?;
+        value
+            .as_str()
+            .and_then(|rate| rate.trim().parse::<f64>().ok())
+            .or_else(|| value.as_f64())
+            .filter(|rate| rate.is_finite() && *rate >= 0.0)
+    };
+
+    Some(ModelPricing {
+        input_cost_per_token: rate("prompt")?,
+        output_cost_per_token: rate("completion")?,
+        cached_input_cost_per_token: rate("input_cache_read"),
+        cache_write_input_cost_per_token: rate("input_cache_write"),
+        reasoning_cost_per_token: rate("internal_reasoning").filter(|rate| *rate > 0.0),
+    })
+}
+
 fn decode_structured_output(
     response_format: &ResponseFormat,
     text_blocks: &[String],
@@ -1320,7 +1845,7 @@ fn decode_structured_output(
         Ok(value) => Some(value),
         Err(error) => {
             warnings.push(RuntimeWarning {
-                code: WARN_STRUCTURED_OUTPUT_PARSE_FAILED.to_string(),
+                code: WarningCode::StructuredOutputParseFailed,
                 message: format!("failed to parse structured output JSON: {error}"),
             });
             None
@@ -1341,7 +1866,7 @@ fn map_finish_reason(
         Some("error") => FinishReason::Error,
         Some(other) => {
             warnings.push(RuntimeWarning {
-                code: WARN_UNKNOWN_FINISH_REASON.to_string(),
+                code: WarningCode::UnknownFinishReason,
                 message: format!("openrouter finish_reason '{other}' mapped to Other"),
             });
             FinishReason::Other
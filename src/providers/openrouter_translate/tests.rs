@@ -9,8 +9,9 @@ use super::{
 };
 use crate::core::error::ProviderError;
 use crate::core::types::{
-    ContentPart, FinishReason, Message, MessageRole, ModelRef, ProviderId, ProviderRequest,
-    ResponseFormat, ToolCall, ToolChoice, ToolDefinition, ToolResult, ToolResultContent,
+    Annotation, ContentPart, FinishReason, HostedTool, HostedToolCall, Message, MessageRole,
+    ModelPricing, ModelRef, PricingSource, ProviderId, ProviderRequest, ResponseFormat, ToolCall,
+    ToolChoice, ToolDefinition, ToolResult, ToolResultContent,
 };
 
 fn base_request() -> ProviderRequest {
@@ -18,21 +19,28 @@ fn base_request() -> ProviderRequest {
         model: ModelRef {
             provider_hint: Some(ProviderId::Openrouter),
             model_id: "openai/gpt-4o-mini".to_string(),
+            requirements: None,
         },
         messages: vec![Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "hello".to_string(),
             }],
+            cache_hint: None,
         }],
         tools: Vec::new(),
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::Text,
         temperature: None,
         top_p: None,
         max_output_tokens: None,
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     }
 }
 
@@ -45,12 +53,14 @@ fn test_encode_openrouter_translator_category_contract() {
             content: vec![ContentPart::Text {
                 text: "Return JSON only".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "weather in sf".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Assistant,
@@ -61,6 +71,7 @@ fn test_encode_openrouter_translator_category_contract() {
                     arguments_json: json!({"city":"SF", "units":"f"}),
                 },
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Tool,
@@ -71,14 +82,17 @@ fn test_encode_openrouter_translator_category_contract() {
                         text: "{\"temp\":55}".to_string(),
                     },
                     raw_provider_content: None,
+                    is_error: false,
                 },
             }],
+            cache_hint: None,
         },
     ];
     req.tools = vec![ToolDefinition {
         name: "lookup_weather".to_string(),
         description: Some("Lookup weather".to_string()),
         parameters_schema: json!({"type":"object","properties":{"city":{"type":"string"}}}),
+        cache_hint: None,
     }];
     req.tool_choice = ToolChoice::Specific {
         name: "lookup_weather".to_string(),
@@ -91,6 +105,7 @@ fn test_encode_openrouter_translator_category_contract() {
     req.top_p = Some(0.9);
     req.max_output_tokens = Some(200);
     req.stop = vec!["DONE".to_string()];
+    req.seed = Some(42);
     req.metadata
         .insert("trace_id".to_string(), "abc".to_string());
 
@@ -105,7 +120,6 @@ fn test_encode_openrouter_translator_category_contract() {
         logprobs: Some(true),
         top_logprobs: Some(3),
         reasoning: Some(json!({"effort":"medium"})),
-        seed: Some(42),
         user: Some("user-123".to_string()),
         session_id: Some("session-123".to_string()),
         trace: Some(json!({"trace_id":"trace-1"})),
@@ -228,12 +242,19 @@ fn test_decode_openrouter_translator_category_contract() {
     assert_eq!(decoded.usage.cached_input_tokens, Some(2));
     assert_eq!(decoded.output.structured_output, Some(json!({"ok": true})));
 
-    assert_eq!(decoded.output.content.len(), 2);
+    assert_eq!(decoded.output.content.len(), 3);
+    assert_eq!(
+        decoded.output.content[0],
+        ContentPart::Thinking {
+            text: "short rationale".to_string(),
+            signature: None,
+        }
+    );
     assert!(
-        matches!(&decoded.output.content[0], ContentPart::Text { text } if text == "{\"ok\":true}")
+        matches!(&decoded.output.content[1], ContentPart::Text { text } if text == "{\"ok\":true}")
     );
     assert!(
-        matches!(&decoded.output.content[1], ContentPart::ToolCall { tool_call } if tool_call.id == "call_1")
+        matches!(&decoded.output.content[2], ContentPart::ToolCall { tool_call } if tool_call.id == "call_1")
     );
 }
 
@@ -317,6 +338,7 @@ fn test_encode_tool_choice_specific_requires_declared_tool() {
         name: "real_tool".to_string(),
         description: None,
         parameters_schema: json!({"type":"object"}),
+        cache_hint: None,
     }];
     req.tool_choice = ToolChoice::Specific {
         name: "missing_tool".to_string(),
@@ -335,6 +357,7 @@ fn test_encode_tool_role_content_validation() {
         content: vec![ContentPart::Text {
             text: "bad".to_string(),
         }],
+        cache_hint: None,
     }];
 
     let err = encode_openrouter_request(&req, &OpenRouterTranslateOptions::default())
@@ -349,6 +372,7 @@ fn test_encode_tool_result_json_coerces_to_string() {
         name: "lookup_weather".to_string(),
         description: None,
         parameters_schema: json!({"type":"object"}),
+        cache_hint: None,
     }];
     req.messages = vec![
         Message {
@@ -360,6 +384,7 @@ fn test_encode_tool_result_json_coerces_to_string() {
                     arguments_json: json!({"city":"SF"}),
                 },
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Tool,
@@ -370,8 +395,10 @@ fn test_encode_tool_result_json_coerces_to_string() {
                         value: json!({"b": 2, "a": 1}),
                     },
                     raw_provider_content: None,
+                    is_error: false,
                 },
             }],
+            cache_hint: None,
         },
     ];
 
@@ -396,6 +423,7 @@ fn test_encode_tool_result_uses_raw_provider_content_when_string() {
         name: "lookup_weather".to_string(),
         description: None,
         parameters_schema: json!({"type":"object"}),
+        cache_hint: None,
     }];
     req.messages = vec![
         Message {
@@ -407,6 +435,7 @@ fn test_encode_tool_result_uses_raw_provider_content_when_string() {
                     arguments_json: json!({"city":"SF"}),
                 },
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Tool,
@@ -417,8 +446,10 @@ fn test_encode_tool_result_uses_raw_provider_content_when_string() {
                         text: "fallback".to_string(),
                     },
                     raw_provider_content: Some(json!("raw-output")),
+                    is_error: false,
                 },
             }],
+            cache_hint: None,
         },
     ];
 
@@ -442,6 +473,7 @@ fn test_encode_assistant_tool_call_arguments_are_stable_json() {
                 arguments_json: json!({"b":2,"a":1}),
             },
         }],
+        cache_hint: None,
     }];
 
     let encoded = encode_openrouter_request(&req, &OpenRouterTranslateOptions::default())
@@ -510,6 +542,39 @@ fn test_encode_option_validation_failures() {
     assert!(err.to_string().contains("route"));
 }
 
+#[test]
+fn test_encode_request_provider_options_merge_over_adapter_options() {
+    let mut req = base_request();
+    req.provider_options.insert(
+        ProviderId::Openrouter,
+        json!({"provider": {"sort": "price"}, "transforms": ["middle-out"]}),
+    );
+    req.provider_options
+        .insert(ProviderId::Anthropic, json!({"user": "ignored"}));
+
+    let encoded = encode_openrouter_request(
+        &req,
+        &OpenRouterTranslateOptions {
+            provider_preferences: Some(json!({"allow_fallbacks": false})),
+            ..Default::default()
+        },
+    )
+    .expect("encode should succeed");
+    assert_eq!(
+        encoded.body.get("provider"),
+        Some(&json!({"allow_fallbacks": false, "sort": "price"}))
+    );
+    assert_eq!(encoded.body.get("transforms"), Some(&json!(["middle-out"])));
+    assert_eq!(encoded.body.get("user"), None);
+
+    req.provider_options
+        .insert(ProviderId::Openrouter, json!({"messages": []}));
+    let err = encode_openrouter_request(&req, &OpenRouterTranslateOptions::default())
+        .expect_err("reserved key should fail");
+    assert!(matches!(err, ProviderError::Protocol { .. }));
+    assert!(err.to_string().contains("'messages'"));
+}
+
 #[test]
 fn test_encode_rejects_unsupported_modes() {
     let req = base_request();
@@ -537,11 +602,11 @@ fn test_encode_rejects_unsupported_modes() {
     let err = encode_openrouter_request(
         &req,
         &OpenRouterTranslateOptions {
-            debug: Some(json!({"echo_upstream_body":true})),
+            debug: Some(json!({"echo_upstream_body":"yes"})),
             ..Default::default()
         },
     )
-    .expect_err("debug should fail");
+    .expect_err("malformed debug should fail");
     assert!(err.to_string().contains("debug"));
 
     let err = encode_openrouter_request(
@@ -555,6 +620,30 @@ fn test_encode_rejects_unsupported_modes() {
     assert!(err.to_string().contains("stream_options"));
 }
 
+#[test]
+fn test_encode_debug_echo_is_forwarded_with_warning() {
+    let req = base_request();
+
+    let encoded = encode_openrouter_request(
+        &req,
+        &OpenRouterTranslateOptions {
+            debug: Some(json!({"echo_upstream_body":true})),
+            ..Default::default()
+        },
+    )
+    .expect("encode should succeed");
+    assert_eq!(
+        encoded.body.get("debug"),
+        Some(&json!({"echo_upstream_body":true}))
+    );
+    assert!(
+        encoded
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "debug_echo_enabled")
+    );
+}
+
 #[test]
 fn test_encode_tool_name_regex_validation() {
     let mut req = base_request();
@@ -562,6 +651,7 @@ fn test_encode_tool_name_regex_validation() {
         name: "bad name".to_string(),
         description: None,
         parameters_schema: json!({"type":"object"}),
+        cache_hint: None,
     }];
 
     let err = encode_openrouter_request(&req, &OpenRouterTranslateOptions::default())
@@ -569,6 +659,243 @@ fn test_encode_tool_name_regex_validation() {
     assert!(err.to_string().contains("^[A-Za-z0-9_-]{1,64}$"));
 }
 
+#[test]
+fn test_encode_assistant_thinking_maps_to_reasoning_fields() {
+    let mut req = base_request();
+    req.messages = vec![
+        Message {
+            role: MessageRole::User,
+            content: vec![ContentPart::Text {
+                text: "hello".to_string(),
+            }],
+            cache_hint: None,
+        },
+        Message {
+            role: MessageRole::Assistant,
+            content: vec![
+                ContentPart::Thinking {
+                    text: "greeting".to_string(),
+                    signature: Some("sig_1".to_string()),
+                },
+                ContentPart::Text {
+                    text: "hi there".to_string(),
+                },
+            ],
+            cache_hint: None,
+        },
+    ];
+
+    let encoded = encode_openrouter_request(&req, &OpenRouterTranslateOptions::default())
+        .expect("encode should succeed");
+
+    assert_eq!(
+        encoded.body.pointer("/messages/1"),
+        Some(&json!({
+            "role": "assistant",
+            "content": "hi there",
+            "reasoning": "greeting",
+            "reasoning_details": [
+                {"type": "reasoning.text", "text": "greeting", "signature": "sig_1"}
+            ]
+        }))
+    );
+}
+
+#[test]
+fn test_encode_thinking_in_user_message_is_error() {
+    let mut req = base_request();
+    req.messages = vec![Message {
+        role: MessageRole::User,
+        content: vec![ContentPart::Thinking {
+            text: "not mine".to_string(),
+            signature: None,
+        }],
+        cache_hint: None,
+    }];
+
+    let error = encode_openrouter_request(&req, &OpenRouterTranslateOptions::default())
+        .expect_err("encode should fail");
+    assert!(matches!(error, ProviderError::Protocol { .. }));
+}
+
+#[test]
+fn test_decode_reasoning_details_signature_is_preserved() {
+    let payload = OpenRouterDecodeEnvelope {
+        body: json!({
+            "model": "anthropic/claude-sonnet-4.5",
+            "choices": [
+                {
+                    "finish_reason": "stop",
+                    "message": {
+                        "role": "assistant",
+                        "content": "done",
+                        "reasoning": "thought",
+                        "reasoning_details": [
+                            {"type": "reasoning.text", "text": "thought", "signature": "sig_1"}
+                        ]
+                    }
+                }
+            ]
+        }),
+        requested_response_format: ResponseFormat::Text,
+    };
+
+    let decoded = decode_openrouter_response(&payload).expect("decode should succeed");
+    assert_eq!(
+        decoded.output.content[0],
+        ContentPart::Thinking {
+            text: "thought".to_string(),
+            signature: Some("sig_1".to_string()),
+        }
+    );
+}
+
+#[test]
+fn test_encode_developer_role_folds_into_system() {
+    let mut req = base_request();
+    req.messages.insert(
+        0,
+        Message {
+            role: MessageRole::Developer,
+            content: vec![ContentPart::Text {
+                text: "be terse".to_string(),
+            }],
+            cache_hint: None,
+        },
+    );
+
+    let encoded = encode_openrouter_request(&req, &OpenRouterTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(
+        encoded.body["messages"][0],
+        json!({"role": "system", "content": "be terse"})
+    );
+}
+
+#[test]
+fn test_encode_n_is_forwarded_and_zero_rejected() {
+    let mut req = base_request();
+    req.n = Some(3);
+    let encoded = encode_openrouter_request(&req, &OpenRouterTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(encoded.body.get("n"), Some(&json!(3)));
+
+    req.n = Some(0);
+    let err = encode_openrouter_request(&req, &OpenRouterTranslateOptions::default())
+        .expect_err("n=0 should fail");
+    assert!(matches!(err, ProviderError::Protocol { .. }));
+}
+
+#[test]
+fn test_decode_additional_choices_become_alternatives() {
+    let payload = OpenRouterDecodeEnvelope {
+        body: json!({
+            "model": "openai/gpt-4o-mini",
+            "choices": [
+                {
+                    "index": 0,
+                    "finish_reason": "stop",
+                    "message": {"role": "assistant", "content": "first"}
+                },
+                {
+                    "index": 1,
+                    "finish_reason": "length",
+                    "message": {"role": "assistant", "content": "{\"k\":1}"}
+                }
+            ],
+            "usage": {"prompt_tokens": 3, "completion_tokens": 4, "total_tokens": 7}
+        }),
+        requested_response_format: ResponseFormat::JsonObject,
+    };
+
+    let decoded = decode_openrouter_response(&payload).expect("decode should succeed");
+    assert_eq!(decoded.finish_reason, FinishReason::Stop);
+    assert_eq!(
+        decoded.output.content,
+        vec![ContentPart::Text {
+            text: "first".to_string()
+        }]
+    );
+    assert_eq!(decoded.alternatives.len(), 1);
+    assert_eq!(decoded.alternatives[0].finish_reason, FinishReason::Length);
+    assert_eq!(
+        decoded.alternatives[0].output.content,
+        vec![ContentPart::Text {
+            text: "{\"k\":1}".to_string()
+        }]
+    );
+    assert_eq!(
+        decoded.alternatives[0].output.structured_output,
+        Some(json!({"k": 1}))
+    );
+}
+
+#[test]
+fn test_decode_plugin_annotations() {
+    let payload = OpenRouterDecodeEnvelope {
+        body: json!({
+            "model": "openai/gpt-4o-mini",
+            "choices": [
+                {
+                    "finish_reason": "stop",
+                    "message": {
+                        "role": "assistant",
+                        "content": "Rust 1.0 shipped in 2015.",
+                        "annotations": [
+                            {
+                                "type": "url_citation",
+                                "url_citation": {
+                                    "url": "https://blog.rust-lang.org/2015/05/15/Rust-1.0.html",
+                                    "title": "Announcing Rust 1.0",
+                                    "content": "Today we are very proud...",
+                                    "start_index": 0,
+                                    "end_index": 24
+                                }
+                            },
+                            {
+                                "type": "file",
+                                "file": {
+                                    "hash": "abc123",
+                                    "name": "report.pdf",
+                                    "content": [{"type": "text", "text": "page one"}]
+                                }
+                            },
+                            {"type": "mystery"}
+                        ]
+                    }
+                }
+            ]
+        }),
+        requested_response_format: ResponseFormat::Text,
+    };
+
+    let decoded = decode_openrouter_response(&payload).expect("decode should succeed");
+    assert_eq!(
+        decoded.output.annotations,
+        vec![
+            Annotation::UrlCitation {
+                url: "https://blog.rust-lang.org/2015/05/15/Rust-1.0.html".to_string(),
+                title: Some("Announcing Rust 1.0".to_string()),
+                content: Some("Today we are very proud...".to_string()),
+                start_index: Some(0),
+                end_index: Some(24),
+                part_index: Some(0),
+            },
+            Annotation::File {
+                hash: "abc123".to_string(),
+                name: Some("report.pdf".to_string()),
+                content: Some(json!([{"type": "text", "text": "page one"}])),
+            },
+        ]
+    );
+    assert!(
+        decoded
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "unknown_annotation_dropped")
+    );
+}
+
 #[test]
 fn test_decode_top_level_error_is_protocol_error() {
     let payload = OpenRouterDecodeEnvelope {
@@ -900,3 +1227,193 @@ fn test_decode_openrouter_models_list_success_and_invalid_payload() {
         .expect_err("missing data should fail");
     assert!(matches!(err, ProviderError::Protocol { .. }));
 }
+
+#[test]
+fn test_encode_hosted_tools_are_dropped_with_warnings() {
+    let mut req = base_request();
+    req.hosted_tools = vec![HostedTool::CodeInterpreter];
+    req.messages.push(Message {
+        role: MessageRole::Assistant,
+        content: vec![
+            ContentPart::HostedToolCall {
+                hosted_tool_call: HostedToolCall {
+                    id: "ws_1".to_string(),
+                    name: "web_search_call".to_string(),
+                    status: None,
+                    provider: ProviderId::Openai,
+                    raw_provider_content: json!({ "type": "web_search_call", "id": "ws_1" }),
+                },
+            },
+            ContentPart::Text {
+                text: "sunny".to_string(),
+            },
+        ],
+        cache_hint: None,
+    });
+
+    let encoded = encode_openrouter_request(&req, &OpenRouterTranslateOptions::default())
+        .expect("encode should succeed");
+    assert_eq!(encoded.body.get("tools"), None);
+    assert_eq!(
+        encoded.body.pointer("/messages/1"),
+        Some(&json!({ "role": "assistant", "content": "sunny" }))
+    );
+    let codes: Vec<&str> = encoded
+        .warnings
+        .iter()
+        .map(|warning| warning.code.as_str())
+        .collect();
+    assert!(codes.contains(&"hosted_tool_unsupported"));
+    assert!(codes.contains(&"hosted_tool_call_dropped"));
+}
+
+#[test]
+fn test_decode_root_citations_become_url_annotations() {
+    let payload = OpenRouterDecodeEnvelope {
+        body: json!({
+            "model": "perplexity/sonar",
+            "citations": ["https://example.com/a", "https://example.com/b"],
+            "choices": [
+                {
+                    "finish_reason": "stop",
+                    "message": {
+                        "role": "assistant",
+                        "content": "Sources agree [1][2].",
+                        "annotations": [
+                            {
+                                "type": "url_citation",
+                                "url_citation": { "url": "https://example.com/a" }
+                            }
+                        ]
+                    }
+                }
+            ]
+        }),
+        requested_response_format: ResponseFormat::Text,
+    };
+
+    let decoded = decode_openrouter_response(&payload).expect("decode should succeed");
+    let urls: Vec<&str> = decoded
+        .output
+        .annotations
+        .iter()
+        .map(|annotation| match annotation {
+            Annotation::UrlCitation { url, .. } => url.as_str(),
+            other => panic!("unexpected annotation {other:?}"),
+        })
+        .collect();
+    assert_eq!(urls, vec!["https://example.com/a", "https://example.com/b"]);
+}
+
+#[test]
+fn test_decode_models_list_pricing() {
+    let models = decode_openrouter_models_list(&json!({
+        "data": [
+            {
+                "id": "anthropic/claude-sonnet-4.5",
+                "pricing": {
+                    "prompt": "0.000003",
+                    "completion": "0.000015",
+                    "input_cache_read": "0.0000003",
+                    "input_cache_write": "0.00000375",
+                    "internal_reasoning": "0"
+                }
+            },
+            {
+                "id": "openrouter/auto",
+                "pricing": { "prompt": "-1", "completion": "-1" }
+            },
+            { "id": "no/pricing" }
+        ]
+    }))
+    .expect("models list should decode");
+
+    assert_eq!(
+        models[0].pricing,
+        Some(ModelPricing {
+            input_cost_per_token: 0.000003,
+            output_cost_per_token: 0.000015,
+            cached_input_cost_per_token: Some(0.0000003),
+            cache_write_input_cost_per_token: Some(0.00000375),
+            reasoning_cost_per_token: None,
+        })
+    );
+    assert_eq!(models[1].pricing, None);
+    assert_eq!(models[2].pricing, None);
+}
+
+#[test]
+fn test_decode_usage_cost_is_provider_reported() {
+    let body = |usage: serde_json::Value| OpenRouterDecodeEnvelope {
+        body: json!({
+            "id":"1",
+            "object":"chat.completion",
+            "created":1,
+            "model":"openai/gpt-4o-mini",
+            "choices":[{"index":0,"finish_reason":"stop","message":{"role":"assistant","content":"ok"}}],
+            "usage": usage
+        }),
+        requested_response_format: ResponseFormat::Text,
+    };
+
+    let total_only = decode_openrouter_response(&body(json!({
+        "prompt_tokens": 3,
+        "completion_tokens": 1,
+        "total_tokens": 4,
+        "cost": 0.002
+    })))
+    .expect("decode should succeed");
+    let cost = total_only.cost.expect("cost should be reported");
+    assert_eq!(cost.pricing_source, PricingSource::ProviderReported);
+    assert_eq!(cost.total_cost, 0.002);
+    assert_eq!((cost.input_cost, cost.output_cost), (0.0, 0.0));
+
+    let with_details = decode_openrouter_response(&body(json!({
+        "prompt_tokens": 3,
+        "completion_tokens": 1,
+        "total_tokens": 4,
+        "cost": 0.004,
+        "cost_details": {
+            "upstream_inference_prompt_cost": 0.001,
+            "upstream_inference_completions_cost": 0.003
+        }
+    })))
+    .expect("decode should succeed");
+    let cost = with_details.cost.expect("cost should be reported");
+    assert!((cost.input_cost - 0.001).abs() < 1e-12);
+    assert!((cost.output_cost - 0.003).abs() < 1e-12);
+
+    let without_cost = decode_openrouter_response(&body(json!({
+        "prompt_tokens": 3,
+        "completion_tokens": 1,
+        "total_tokens": 4
+    })))
+    .expect("decode should succeed");
+    assert_eq!(without_cost.cost, None);
+}
+
+#[test]
+fn test_decode_models_list_modalities_and_lifecycle() {
+    let models = decode_openrouter_models_list(&json!({
+        "data": [
+            {
+                "id": "google/gemini-2.5-flash-image",
+                "architecture": {
+                    "input_modalities": ["text", "image"],
+                    "output_modalities": ["Image", "text"]
+                },
+                "knowledge_cutoff": "2025-01",
+                "expiration_date": null
+            },
+            { "id": "old/model", "expiration_date": "2025-12-01" }
+        ]
+    }))
+    .expect("models list should decode");
+
+    assert_eq!(models[0].input_modalities, vec!["text", "image"]);
+    assert_eq!(models[0].output_modalities, vec!["image", "text"]);
+    assert_eq!(models[0].knowledge_cutoff.as_deref(), Some("2025-01"));
+    assert!(!models[0].deprecated);
+    assert!(models[1].deprecated);
+    assert!(models[1].input_modalities.is_empty());
+}
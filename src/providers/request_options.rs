@@ -0,0 +1,153 @@
+use serde_json::{Map, Value};
+
+use crate::core::types::{ProviderId, ProviderRequest};
+
+/// Merges the request's `provider_options` entry for `provider` into an encoded body.
+///
+/// Adapter-level options are already present in `body`, so request fields take precedence:
+/// objects are merged one level deep, any other value replaces the adapter value, and `null`
+/// removes the field. Keys listed in `reserved` are owned by typed `ProviderRequest` fields and
+/// are rejected rather than silently overwritten.
+pub(crate) fn merge_provider_options(
+    body: &mut Map<String, Value>,
+    req: &ProviderRequest,
+    provider: &ProviderId,
+    reserved: &[&str],
+) -> Result<(), String> {
+    let Some(options) = req.provider_options.get(provider) else {
+        return Ok(());
+    };
+    let options = options
+        .as_object()
+        .ok_or_else(|| "provider_options entry must be a JSON object".to_string())?;
+
+    for (key, value) in options {
+        if reserved.contains(&key.as_str()) {
+            return Err(format!(
+                "provider_options must not set '{key}'; use the typed request field instead"
+            ));
+        }
+
+        match (body.get_mut(key), value) {
+            (_, Value::Null) => {
+                body.remove(key);
+            }
+            (Some(Value::Object(existing)), Value::Object(overrides)) => {
+                for (field, field_value) in overrides {
+                    if field_value.is_null() {
+                        existing.remove(field);
+                    } else {
+                        existing.insert(field.clone(), field_value.clone());
+                    }
+                }
+            }
+            (Some(Value::Object(_)), _) => {
+                return Err(format!(
+                    "provider_options '{key}' must be a JSON object to merge with adapter options"
+                ));
+            }
+            _ => {
+                body.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::{Map, Value, json};
+
+    use super::merge_provider_options;
+    use crate::core::types::{ModelRef, ProviderId, ProviderRequest};
+
+    fn request(provider: ProviderId, options: Value) -> ProviderRequest {
+        ProviderRequest {
+            provider_options: BTreeMap::from([(provider, options)]),
+            ..ProviderRequest::new(
+                ModelRef {
+                    provider_hint: None,
+                    model_id: "model".to_string(),
+                    requirements: None,
+                },
+                Vec::new(),
+            )
+        }
+    }
+
+    fn body(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().expect("object")
+    }
+
+    #[test]
+    fn test_merge_overrides_and_merges_objects() {
+        let mut encoded = body(json!({
+            "provider": { "order": ["a"], "allow_fallbacks": false },
+            "user": "adapter"
+        }));
+        let req = request(
+            ProviderId::Openrouter,
+            json!({
+                "provider": { "order": ["b"], "allow_fallbacks": null },
+                "user": "request",
+                "transforms": ["middle-out"]
+            }),
+        );
+
+        merge_provider_options(&mut encoded, &req, &ProviderId::Openrouter, &["model"])
+            .expect("merge should succeed");
+
+        assert_eq!(
+            Value::Object(encoded),
+            json!({
+                "provider": { "order": ["b"] },
+                "user": "request",
+                "transforms": ["middle-out"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_merge_ignores_entries_for_other_providers() {
+        let mut encoded = body(json!({ "user": "adapter" }));
+        let req = request(ProviderId::Anthropic, json!({ "user": "request" }));
+
+        merge_provider_options(&mut encoded, &req, &ProviderId::Openrouter, &[])
+            .expect("merge should succeed");
+
+        assert_eq!(Value::Object(encoded), json!({ "user": "adapter" }));
+    }
+
+    #[test]
+    fn test_merge_null_removes_adapter_field() {
+        let mut encoded = body(json!({ "thinking": { "type": "enabled" } }));
+        let req = request(ProviderId::Anthropic, json!({ "thinking": null }));
+
+        merge_provider_options(&mut encoded, &req, &ProviderId::Anthropic, &[])
+            .expect("merge should succeed");
+
+        assert!(encoded.is_empty());
+    }
+
+    #[test]
+    fn test_merge_rejects_reserved_keys_and_invalid_shapes() {
+        let mut encoded = body(json!({ "provider": {} }));
+
+        let reserved = request(ProviderId::Openai, json!({ "model": "other" }));
+        let error =
+            merge_provider_options(&mut encoded, &reserved, &ProviderId::Openai, &["model"])
+                .expect_err("reserved key should fail");
+        assert!(error.contains("'model'"));
+
+        let not_object = request(ProviderId::Openai, json!(["x"]));
+        merge_provider_options(&mut encoded, &not_object, &ProviderId::Openai, &[])
+            .expect_err("non-object entry should fail");
+
+        let mismatched = request(ProviderId::Openai, json!({ "provider": "fast" }));
+        merge_provider_options(&mut encoded, &mismatched, &ProviderId::Openai, &[])
+            .expect_err("scalar over object should fail");
+    }
+}
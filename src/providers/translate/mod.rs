@@ -0,0 +1,260 @@
+//! Provider-agnostic access to the request/response translators the built-in adapters use.
+//!
+//! `Translator` turns a canonical `ProviderRequest` into the exact JSON body an adapter would
+//! send, and a provider response body back into a `ProviderResponse`, without any HTTP. Use it
+//! to inspect wire payloads offline, sign requests, or proxy them through your own client.
+//! `encode` and `decode` cover the common case of default adapter options, and
+//! `wire_snapshot` renders a deterministic body-plus-headers value for golden contract tests.
+
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value, json};
+
+use crate::core::error::ProviderError;
+use crate::core::types::{ProviderId, ProviderRequest, ProviderResponse, RuntimeWarning};
+use crate::providers::anthropic::{ANTHROPIC_VERSION, AnthropicAdapterOptions};
+use crate::providers::anthropic_translate::{AnthropicDecodeEnvelope, AnthropicTranslator};
+use crate::providers::openai::OpenAiAdapterOptions;
+use crate::providers::openai_translate::{OpenAiDecodeEnvelope, OpenAiTranslator};
+use crate::providers::openrouter::OpenRouterAdapterOptions;
+use crate::providers::openrouter_translate::{
+    OpenRouterDecodeEnvelope, OpenRouterTranslator, fallback_model_served_warning,
+};
+use crate::providers::translator_contract::ProviderTranslator;
+use crate::transport::redaction::REDACTED;
+
+/// Provider wire body produced by [`Translator::encode`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncodedRequest {
+    pub body: Value,
+    /// Lossy or ignored settings noticed while encoding. Adapters prepend these to the decoded
+    /// response's warnings; [`Translator::decode`] does not, since it never sees the encode.
+    pub warnings: Vec<RuntimeWarning>,
+}
+
+/// Encoder/decoder for one built-in provider's wire format.
+#[derive(Debug, Clone)]
+pub struct Translator {
+    inner: TranslatorKind,
+    /// Non-body headers the adapter sends, with credentials replaced by `REDACTED`.
+    headers: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+enum TranslatorKind {
+    OpenAi(OpenAiTranslator),
+    Anthropic(AnthropicTranslator),
+    OpenRouter {
+        translator: Box<OpenRouterTranslator>,
+        fallback_models: Vec<String>,
+    },
+}
+
+impl Translator {
+    /// Translates like an `OpenAiAdapter` built with `options`.
+    pub fn openai(options: &OpenAiAdapterOptions) -> Self {
+        let mut headers = json_headers("authorization", format!("Bearer {REDACTED}"));
+        if let Some(organization) = &options.organization {
+            headers.insert("openai-organization".to_string(), organization.clone());
+        }
+        if let Some(project) = &options.project {
+            headers.insert("openai-project".to_string(), project.clone());
+        }
+        Self {
+            inner: TranslatorKind::OpenAi(OpenAiTranslator::new(options.to_translate_options())),
+            headers,
+        }
+    }
+
+    /// Translates like an `AnthropicAdapter` built with `options`.
+    pub fn anthropic(options: &AnthropicAdapterOptions) -> Self {
+        let mut headers = json_headers("x-api-key", REDACTED.to_string());
+        headers.insert(
+            "anthropic-version".to_string(),
+            options
+                .api_version
+                .clone()
+                .unwrap_or_else(|| ANTHROPIC_VERSION.to_string()),
+        );
+        if !options.beta_features.is_empty() {
+            headers.insert(
+                "anthropic-beta".to_string(),
+                options.beta_features.join(","),
+            );
+        }
+        Self {
+            inner: TranslatorKind::Anthropic(AnthropicTranslator::new(
+                options.to_translate_options(),
+            )),
+            headers,
+        }
+    }
+
+    /// Translates like an `OpenRouterAdapter` built with `options`.
+    pub fn openrouter(options: &OpenRouterAdapterOptions) -> Self {
+        let mut headers = json_headers("authorization", format!("Bearer {REDACTED}"));
+        if let Some(http_referer) = &options.http_referer {
+            headers.insert("http-referer".to_string(), http_referer.clone());
+        }
+        if let Some(x_title) = &options.x_title {
+            headers.insert("x-title".to_string(), x_title.clone());
+        }
+        Self {
+            inner: TranslatorKind::OpenRouter {
+                translator: Box::new(OpenRouterTranslator::new(options.to_translate_options())),
+                fallback_models: options.fallback_models.clone(),
+            },
+            headers,
+        }
+    }
+
+    /// The translator for a built-in provider with default adapter options. Fails with
+    /// `ProviderError::Protocol` for `ProviderId::Other`, which has no built-in wire format.
+    pub fn for_provider(provider: &ProviderId) -> Result<Self, ProviderError> {
+        match provider {
+            ProviderId::Openai => Ok(Self::openai(&OpenAiAdapterOptions::default())),
+            ProviderId::Anthropic => Ok(Self::anthropic(&AnthropicAdapterOptions::default())),
+            ProviderId::Openrouter => Ok(Self::openrouter(&OpenRouterAdapterOptions::default())),
+            ProviderId::Other(name) => Err(ProviderError::Protocol {
+                provider: provider.clone(),
+                model: None,
+                request_id: None,
+                message: format!("no built-in translator for provider {name}"),
+            }),
+        }
+    }
+
+    pub fn provider(&self) -> ProviderId {
+        match &self.inner {
+            TranslatorKind::OpenAi(_) => ProviderId::Openai,
+            TranslatorKind::Anthropic(_) => ProviderId::Anthropic,
+            TranslatorKind::OpenRouter { .. } => ProviderId::Openrouter,
+        }
+    }
+
+    /// Encodes `req` into the provider's request body, applying the same validation the adapter
+    /// does before sending.
+    pub fn encode(&self, req: &ProviderRequest) -> Result<EncodedRequest, ProviderError> {
+        let (body, warnings) = match &self.inner {
+            TranslatorKind::OpenAi(translator) => {
+                let encoded = translator.encode_request(req)?;
+                (encoded.body, encoded.warnings)
+            }
+            TranslatorKind::Anthropic(translator) => {
+                let encoded = translator.encode_request(req)?;
+                (encoded.body, encoded.warnings)
+            }
+            TranslatorKind::OpenRouter { translator, .. } => {
+                let encoded = translator.encode_request(req)?;
+                (encoded.body, encoded.warnings)
+            }
+        };
+        Ok(EncodedRequest { body, warnings })
+    }
+
+    /// Renders `req` as the provider would receive it: `provider`, the request `headers`, the
+    /// encoded `body`, and encode-time `warnings`, with object keys sorted so the value is
+    /// stable to compare against a stored golden file. Credential headers read `[REDACTED]`;
+    /// Anthropic OAuth credentials would send `authorization` instead of `x-api-key`.
+    pub fn wire_snapshot(&self, req: &ProviderRequest) -> Result<Value, ProviderError> {
+        let encoded = self.encode(req)?;
+        let snapshot = json!({
+            "provider": self.provider().name(),
+            "headers": self.headers,
+            "body": encoded.body,
+            "warnings": encoded.warnings,
+        });
+        Ok(canonicalize(snapshot))
+    }
+
+    /// Decodes a provider response `body` to `req`. `req` supplies what decoding depends on,
+    /// such as the requested response format. `request_id` and `rate_limit` stay unset because
+    /// they come from HTTP headers.
+    pub fn decode(
+        &self,
+        req: &ProviderRequest,
+        body: Value,
+    ) -> Result<ProviderResponse, ProviderError> {
+        match &self.inner {
+            TranslatorKind::OpenAi(translator) => {
+                translator.decode_response(&OpenAiDecodeEnvelope {
+                    body,
+                    requested_response_format: req.response_format.clone(),
+                    stop_sequences: req.stop.clone(),
+                })
+            }
+            TranslatorKind::Anthropic(translator) => {
+                translator.decode_response(&AnthropicDecodeEnvelope {
+                    body,
+                    requested_response_format: req.response_format.clone(),
+                })
+            }
+            TranslatorKind::OpenRouter {
+                translator,
+                fallback_models,
+            } => {
+                let mut decoded = translator.decode_response(&OpenRouterDecodeEnvelope {
+                    body,
+                    requested_response_format: req.response_format.clone(),
+                })?;
+                if let Some(warning) = fallback_model_served_warning(
+                    &req.model.model_id,
+                    &decoded.model,
+                    fallback_models,
+                ) {
+                    decoded.warnings.push(warning);
+                }
+                Ok(decoded)
+            }
+        }
+    }
+}
+
+/// Encodes `req` for `provider` with default adapter options.
+pub fn encode(
+    provider: &ProviderId,
+    req: &ProviderRequest,
+) -> Result<EncodedRequest, ProviderError> {
+    Translator::for_provider(provider)?.encode(req)
+}
+
+/// Decodes a `provider` response `body` to `req` with default adapter options.
+pub fn decode(
+    provider: &ProviderId,
+    req: &ProviderRequest,
+    body: Value,
+) -> Result<ProviderResponse, ProviderError> {
+    Translator::for_provider(provider)?.decode(req, body)
+}
+
+/// Renders `req` for `provider` with default adapter options; see [`Translator::wire_snapshot`].
+pub fn wire_snapshot(provider: &ProviderId, req: &ProviderRequest) -> Result<Value, ProviderError> {
+    Translator::for_provider(provider)?.wire_snapshot(req)
+}
+
+/// Headers every JSON request carries, plus the credential header `name`.
+fn json_headers(name: &str, credential: String) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("content-type".to_string(), "application/json".to_string()),
+        (name.to_string(), credential),
+    ])
+}
+
+/// Rebuilds every object with sorted keys, so the rendering does not depend on whether
+/// serde_json's `preserve_order` feature is enabled somewhere in the dependency graph.
+fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let sorted: BTreeMap<String, Value> = object
+                .into_iter()
+                .map(|(key, value)| (key, canonicalize(value)))
+                .collect();
+            Value::Object(sorted.into_iter().collect::<Map<String, Value>>())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(canonicalize).collect()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests;
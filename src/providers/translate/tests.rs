@@ -0,0 +1,184 @@
+use serde_json::json;
+
+use super::{Translator, decode, encode, wire_snapshot};
+use crate::core::error::ProviderError;
+use crate::core::types::{
+    ContentPart, FinishReason, Message, MessageRole, ModelRef, ProviderId, ProviderRequest,
+    WarningCode,
+};
+use crate::providers::anthropic::AnthropicAdapterOptions;
+use crate::providers::openrouter::OpenRouterAdapterOptions;
+
+fn request(provider: ProviderId, model_id: &str) -> ProviderRequest {
+    ProviderRequest::new(
+        ModelRef {
+            provider_hint: Some(provider),
+            model_id: model_id.to_string(),
+            requirements: None,
+        },
+        vec![Message {
+            role: MessageRole::User,
+            content: vec![ContentPart::Text {
+                text: "hello".to_string(),
+            }],
+            cache_hint: None,
+        }],
+    )
+}
+
+#[test]
+fn test_encode_returns_provider_wire_body_and_warnings() {
+    let mut req = request(ProviderId::Openai, "gpt-5-mini");
+    req.seed = Some(7);
+
+    let encoded = encode(&ProviderId::Openai, &req).expect("openai encode should succeed");
+
+    assert_eq!(encoded.body["model"], json!("gpt-5-mini"));
+    assert!(encoded.body.get("input").is_some());
+    assert_eq!(encoded.warnings.len(), 1);
+    assert_eq!(encoded.warnings[0].code, WarningCode::SeedUnsupported);
+}
+
+#[test]
+fn test_translator_uses_adapter_options() {
+    let req = request(ProviderId::Anthropic, "claude-sonnet-4-5");
+    let translator = Translator::anthropic(&AnthropicAdapterOptions {
+        default_max_tokens: Some(321),
+        ..AnthropicAdapterOptions::default()
+    });
+
+    let encoded = translator
+        .encode(&req)
+        .expect("anthropic encode should succeed");
+
+    assert_eq!(translator.provider(), ProviderId::Anthropic);
+    assert_eq!(encoded.body["max_tokens"], json!(321));
+}
+
+#[test]
+fn test_decode_maps_provider_body_to_canonical_response() {
+    let req = request(ProviderId::Anthropic, "claude-sonnet-4-5");
+    let body = json!({
+        "id": "msg_1",
+        "type": "message",
+        "role": "assistant",
+        "model": "claude-sonnet-4-5",
+        "content": [{ "type": "text", "text": "hi there" }],
+        "stop_reason": "end_turn",
+        "usage": { "input_tokens": 3, "output_tokens": 2 }
+    });
+
+    let decoded =
+        decode(&ProviderId::Anthropic, &req, body).expect("anthropic decode should succeed");
+
+    assert_eq!(decoded.provider, ProviderId::Anthropic);
+    assert_eq!(decoded.finish_reason, FinishReason::Stop);
+    assert_eq!(decoded.usage.output_tokens, Some(2));
+    assert!(matches!(
+        &decoded.output.content[0],
+        ContentPart::Text { text } if text == "hi there"
+    ));
+    assert_eq!(decoded.request_id, None);
+}
+
+#[test]
+fn test_openrouter_decode_flags_fallback_model() {
+    let req = request(ProviderId::Openrouter, "openai/gpt-5-mini");
+    let translator = Translator::openrouter(&OpenRouterAdapterOptions {
+        fallback_models: vec!["anthropic/claude-haiku-4-5".to_string()],
+        ..OpenRouterAdapterOptions::default()
+    });
+    let body = json!({
+        "id": "gen_1",
+        "model": "anthropic/claude-haiku-4-5",
+        "choices": [{
+            "index": 0,
+            "message": { "role": "assistant", "content": "hi" },
+            "finish_reason": "stop"
+        }],
+        "usage": { "prompt_tokens": 3, "completion_tokens": 1, "total_tokens": 4 }
+    });
+
+    let decoded = translator
+        .decode(&req, body)
+        .expect("openrouter decode should succeed");
+
+    assert!(
+        decoded
+            .warnings
+            .iter()
+            .any(|warning| warning.code == WarningCode::FallbackModelServed)
+    );
+}
+
+#[test]
+fn test_other_provider_has_no_translator() {
+    let provider = ProviderId::Other("acme".to_string());
+    let req = request(provider.clone(), "acme-1");
+
+    let error = encode(&provider, &req).expect_err("other providers have no wire format");
+
+    assert!(matches!(
+        error,
+        ProviderError::Protocol { ref message, .. } if message.contains("acme")
+    ));
+}
+
+#[test]
+fn test_wire_snapshot_renders_headers_and_sorted_body() {
+    let mut req = request(ProviderId::Anthropic, "claude-sonnet-4-5");
+    req.max_output_tokens = Some(64);
+    let translator = Translator::anthropic(&AnthropicAdapterOptions {
+        beta_features: vec!["files-api-2025-04-14".to_string()],
+        ..AnthropicAdapterOptions::default()
+    });
+
+    let snapshot = translator
+        .wire_snapshot(&req)
+        .expect("anthropic snapshot should render");
+
+    assert_eq!(
+        snapshot,
+        json!({
+            "provider": "anthropic",
+            "headers": {
+                "anthropic-beta": "files-api-2025-04-14",
+                "anthropic-version": "2023-06-01",
+                "content-type": "application/json",
+                "x-api-key": "[REDACTED]"
+            },
+            "body": {
+                "max_tokens": 64,
+                "messages": [
+                    { "role": "user", "content": [{ "type": "text", "text": "hello" }] }
+                ],
+                "model": "claude-sonnet-4-5",
+                "tool_choice": { "type": "auto" }
+            },
+            "warnings": []
+        })
+    );
+}
+
+#[test]
+fn test_wire_snapshot_is_deterministic() {
+    let mut req = request(ProviderId::Openrouter, "openai/gpt-5-mini");
+    req.temperature = Some(0.2);
+    req.top_p = Some(0.9);
+
+    let first = wire_snapshot(&ProviderId::Openrouter, &req).expect("snapshot should render");
+    let second = wire_snapshot(&ProviderId::Openrouter, &req).expect("snapshot should render");
+
+    assert_eq!(
+        serde_json::to_string(&first).expect("snapshot should serialize"),
+        serde_json::to_string(&second).expect("snapshot should serialize")
+    );
+    assert_eq!(
+        first["headers"]["authorization"],
+        json!("Bearer [REDACTED]")
+    );
+    assert_eq!(
+        first["warnings"][0]["code"],
+        json!("both_temperature_and_top_p_set")
+    );
+}
@@ -68,6 +68,7 @@ mod tests {
                 output: AssistantOutput {
                     content: vec![ContentPart::Text { text }],
                     structured_output: None,
+                    annotations: Vec::new(),
                 },
                 usage: Usage::default(),
                 cost: None,
@@ -76,6 +77,10 @@ mod tests {
                 raw_provider_response: None,
                 finish_reason: FinishReason::Stop,
                 warnings: Vec::new(),
+                alternatives: Vec::new(),
+                response_id: None,
+                request_id: None,
+                rate_limit: None,
             })
         }
     }
@@ -85,21 +90,28 @@ mod tests {
             model: ModelRef {
                 provider_hint: Some(ProviderId::Openai),
                 model_id: "gpt-5-mini".to_string(),
+                requirements: None,
             },
             messages: vec![Message {
                 role: MessageRole::User,
                 content: vec![ContentPart::Text {
                     text: "hello".to_string(),
                 }],
+                cache_hint: None,
             }],
             tools: Vec::new(),
+            hosted_tools: Vec::new(),
             tool_choice: ToolChoice::Auto,
+            parallel_tool_calls: None,
             response_format: ResponseFormat::Text,
             temperature: None,
             top_p: None,
             max_output_tokens: None,
             stop: Vec::new(),
             metadata: BTreeMap::new(),
+            n: None,
+            seed: None,
+            provider_options: BTreeMap::new(),
         }
     }
 
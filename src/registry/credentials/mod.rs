@@ -0,0 +1,276 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::core::error::ProviderError;
+use crate::core::traits::CredentialStore;
+use crate::core::types::{AdapterContext, ProviderId};
+
+mod store;
+mod tokens;
+
+#[cfg(feature = "keychain")]
+pub use store::KeychainCredentialStore;
+pub(crate) use store::resolve_api_key;
+pub use store::{
+    ChainCredentialStore, EnvCredentialStore, FileCredentialStore, MetadataCredentialStore,
+    StaticCredentialStore,
+};
+pub(crate) use tokens::CachedTokenProvider;
+pub use tokens::{ACCESS_TOKEN_METADATA_KEY, TOKEN_REFRESH_MARGIN};
+
+/// Adapter context metadata key carrying the pooled API key chosen for a call. Adapters prefer
+/// it over their configured key.
+pub const API_KEY_METADATA_KEY: &str = "credentials.api_key";
+
+/// How long a rate-limited key sits out when the provider does not say when to retry.
+pub const DEFAULT_KEY_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Order in which a [`KeyPool`] hands out its keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyRotation {
+    /// Each usable key in turn, in registration order.
+    #[default]
+    RoundRobin,
+    /// The usable key that has gone longest without a call.
+    LeastRecentlyUsed,
+}
+
+/// Several API keys for one provider, rotated across calls.
+///
+/// A key the provider rejects (401/403) is retired for the life of the pool; a rate-limited
+/// key (429) sits out until the provider's `retry-after`, or [`DEFAULT_KEY_COOLDOWN`].
+#[derive(Debug)]
+pub struct KeyPool {
+    rotation: KeyRotation,
+    cooldown: Duration,
+    state: Mutex<PoolState>,
+}
+
+#[derive(Debug)]
+struct PoolState {
+    keys: Vec<PooledKey>,
+    next: usize,
+}
+
+#[derive(Debug)]
+struct PooledKey {
+    api_key: String,
+    last_used: Option<Instant>,
+    quarantine: Option<Quarantine>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Quarantine {
+    Until(Instant),
+    Rejected,
+}
+
+/// Where the runtime finds credentials for its calls: a provider's token provider first, then
+/// its key pool, then the runtime's credential store. Providers with none of these are called
+/// with the caller's context unchanged.
+#[derive(Clone, Default)]
+pub(crate) struct CredentialSources {
+    pub(crate) tokens: BTreeMap<ProviderId, Arc<CachedTokenProvider>>,
+    pub(crate) pools: BTreeMap<ProviderId, Arc<KeyPool>>,
+    pub(crate) store: Option<Arc<dyn CredentialStore>>,
+}
+
+impl CredentialSources {
+    pub(crate) fn has_token_provider(&self, provider: &ProviderId) -> bool {
+        self.tokens.contains_key(provider)
+    }
+
+    /// Returns `ctx` carrying the token or key for one call to `provider`. A pooled key comes
+    /// with a lease to pass to `report` once the call finishes.
+    pub(crate) async fn lease(
+        &self,
+        provider: &ProviderId,
+        ctx: &AdapterContext,
+    ) -> Result<(AdapterContext, Option<KeyLease>), ProviderError> {
+        if let Some(tokens) = self.tokens.get(provider) {
+            let mut ctx = ctx.clone();
+            ctx.metadata.insert(
+                ACCESS_TOKEN_METADATA_KEY.to_string(),
+                tokens.token(provider).await?,
+            );
+            return Ok((ctx, None));
+        }
+
+        if let Some(pool) = self.pools.get(provider) {
+            return pool
+                .checkout(provider, ctx)
+                .map(|(ctx, lease)| (ctx, Some(lease)));
+        }
+
+        let mut ctx = ctx.clone();
+        if let Some(store) = &self.store
+            && let Some(api_key) = store
+                .api_key(provider, &ctx)
+                .map_err(|error| store::credential_store_error(provider, error))?
+        {
+            ctx.metadata
+                .insert(API_KEY_METADATA_KEY.to_string(), api_key);
+        }
+        Ok((ctx, None))
+    }
+
+    /// Feeds a finished call's outcome back: pooled keys update their health, and rejected
+    /// access tokens are refetched on the next lease.
+    pub(crate) fn report(
+        &self,
+        provider: &ProviderId,
+        lease: Option<&KeyLease>,
+        error: Option<&ProviderError>,
+    ) {
+        if let (Some(tokens), Some(ProviderError::CredentialsRejected { .. })) =
+            (self.tokens.get(provider), error)
+        {
+            tokens.force_refresh();
+        }
+        if let (Some(pool), Some(lease)) = (self.pools.get(provider), lease) {
+            pool.report(lease, error);
+        }
+    }
+}
+
+/// The key handed out for one call, reported back once the call finishes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct KeyLease {
+    index: usize,
+}
+
+impl KeyPool {
+    pub fn new<I, S>(api_keys: I, rotation: KeyRotation) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let keys = api_keys
+            .into_iter()
+            .map(|api_key| PooledKey {
+                api_key: api_key.into(),
+                last_used: None,
+                quarantine: None,
+            })
+            .collect();
+        Self {
+            rotation,
+            cooldown: DEFAULT_KEY_COOLDOWN,
+            state: Mutex::new(PoolState { keys, next: 0 }),
+        }
+    }
+
+    /// Cooldown for rate-limited keys when the provider does not send `retry-after`.
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Number of keys that are not currently quarantined.
+    pub fn available_keys(&self) -> usize {
+        let now = Instant::now();
+        self.lock()
+            .keys
+            .iter()
+            .filter(|key| key.is_usable(now))
+            .count()
+    }
+
+    /// Picks the next usable key and returns `context` carrying it. Fails like the provider
+    /// would when every key is quarantined: rate limited while any key is only cooling down,
+    /// otherwise rejected credentials.
+    pub(crate) fn checkout(
+        &self,
+        provider: &ProviderId,
+        context: &AdapterContext,
+    ) -> Result<(AdapterContext, KeyLease), ProviderError> {
+        let now = Instant::now();
+        let mut state = self.lock();
+        let count = state.keys.len();
+        let index = match self.rotation {
+            KeyRotation::RoundRobin => (0..count)
+                .map(|offset| (state.next + offset) % count)
+                .find(|&index| state.keys[index].is_usable(now)),
+            KeyRotation::LeastRecentlyUsed => (0..count)
+                .filter(|&index| state.keys[index].is_usable(now))
+                .min_by_key(|&index| state.keys[index].last_used),
+        };
+        let Some(index) = index else {
+            return Err(exhausted_error(provider, &state.keys, now));
+        };
+
+        state.next = (index + 1) % count;
+        let key = &mut state.keys[index];
+        key.last_used = Some(now);
+        key.quarantine = None;
+
+        let mut context = context.clone();
+        context
+            .metadata
+            .insert(API_KEY_METADATA_KEY.to_string(), key.api_key.clone());
+        Ok((context, KeyLease { index }))
+    }
+
+    /// Quarantines the leased key when `error` shows the provider refused it.
+    pub(crate) fn report(&self, lease: &KeyLease, error: Option<&ProviderError>) {
+        let quarantine = match error {
+            Some(ProviderError::CredentialsRejected { .. })
+            | Some(ProviderError::Status {
+                status_code: 401 | 403,
+                ..
+            }) => Quarantine::Rejected,
+            Some(ProviderError::RateLimited { retry_after, .. }) => {
+                Quarantine::Until(Instant::now() + retry_after.unwrap_or(self.cooldown))
+            }
+            _ => return,
+        };
+        if let Some(key) = self.lock().keys.get_mut(lease.index) {
+            key.quarantine = Some(quarantine);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, PoolState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl PooledKey {
+    fn is_usable(&self, now: Instant) -> bool {
+        match self.quarantine {
+            None => true,
+            Some(Quarantine::Until(until)) => until <= now,
+            Some(Quarantine::Rejected) => false,
+        }
+    }
+}
+
+fn exhausted_error(provider: &ProviderId, keys: &[PooledKey], now: Instant) -> ProviderError {
+    let retry_at = keys
+        .iter()
+        .filter_map(|key| match key.quarantine {
+            Some(Quarantine::Until(until)) => Some(until),
+            _ => None,
+        })
+        .min();
+    match retry_at {
+        Some(retry_at) => ProviderError::RateLimited {
+            provider: provider.clone(),
+            model: None,
+            request_id: None,
+            retry_after: Some(retry_at.saturating_duration_since(now)),
+            message: "every pooled API key is rate limited".to_string(),
+            rate_limit: None,
+        },
+        None => ProviderError::CredentialsRejected {
+            provider: provider.clone(),
+            request_id: None,
+            message: "no pooled API key is usable; every key was rejected".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests;
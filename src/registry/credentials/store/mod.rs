@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::core::error::{ConfigError, ProviderError, RuntimeError};
+use crate::core::traits::CredentialStore;
+use crate::core::types::{AdapterContext, ProviderId};
+
+use super::API_KEY_METADATA_KEY;
+
+/// Reads each provider's key from an environment variable: `OPENAI_API_KEY`,
+/// `ANTHROPIC_API_KEY`, `OPENROUTER_API_KEY`, or `<NAME>_API_KEY` for other providers.
+#[derive(Debug, Clone, Default)]
+pub struct EnvCredentialStore {
+    overrides: BTreeMap<ProviderId, String>,
+}
+
+impl EnvCredentialStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads `provider`'s key from `var` instead of its default variable.
+    pub fn with_var(mut self, provider: ProviderId, var: impl Into<String>) -> Self {
+        self.overrides.insert(provider, var.into());
+        self
+    }
+
+    /// The variable consulted for `provider`.
+    pub fn var_name(&self, provider: &ProviderId) -> String {
+        self.overrides.get(provider).cloned().unwrap_or_else(|| {
+            format!(
+                "{}_API_KEY",
+                provider.name().to_ascii_uppercase().replace('-', "_")
+            )
+        })
+    }
+}
+
+impl CredentialStore for EnvCredentialStore {
+    fn api_key(
+        &self,
+        provider: &ProviderId,
+        _ctx: &AdapterContext,
+    ) -> Result<Option<String>, RuntimeError> {
+        Ok(non_empty(std::env::var(self.var_name(provider)).ok()))
+    }
+}
+
+/// Fixed keys supplied in code.
+#[derive(Debug, Clone, Default)]
+pub struct StaticCredentialStore {
+    keys: BTreeMap<ProviderId, String>,
+}
+
+impl StaticCredentialStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_key(mut self, provider: ProviderId, api_key: impl Into<String>) -> Self {
+        self.keys.insert(provider, api_key.into());
+        self
+    }
+}
+
+impl CredentialStore for StaticCredentialStore {
+    fn api_key(
+        &self,
+        provider: &ProviderId,
+        _ctx: &AdapterContext,
+    ) -> Result<Option<String>, RuntimeError> {
+        Ok(non_empty(self.keys.get(provider).cloned()))
+    }
+}
+
+/// Reads keys from a TOML file mapping provider names to keys, such as `openai = "sk-..."`.
+///
+/// The file is read on every lookup, so replacing it rotates keys without a restart. A missing
+/// file is an error; a provider missing from the file has no key.
+#[derive(Debug, Clone)]
+pub struct FileCredentialStore {
+    path: PathBuf,
+}
+
+impl FileCredentialStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl CredentialStore for FileCredentialStore {
+    fn api_key(
+        &self,
+        provider: &ProviderId,
+        _ctx: &AdapterContext,
+    ) -> Result<Option<String>, RuntimeError> {
+        let invalid = |reason: String| ConfigError::InvalidCredentialStore { reason };
+        let contents = std::fs::read_to_string(&self.path)
+            .map_err(|error| invalid(format!("failed to read {}: {error}", self.path.display())))?;
+        let keys: BTreeMap<String, String> = toml::from_str(&contents).map_err(|error| {
+            invalid(format!(
+                "malformed key file {}: {error}",
+                self.path.display()
+            ))
+        })?;
+        Ok(non_empty(keys.get(provider.name()).cloned()))
+    }
+}
+
+/// Reads the `<provider>.api_key` adapter context metadata, such as `openai.api_key`, for
+/// callers that pass keys per runtime.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MetadataCredentialStore;
+
+impl MetadataCredentialStore {
+    /// The metadata key consulted for `provider`.
+    pub fn metadata_key(provider: &ProviderId) -> String {
+        format!("{}.api_key", provider.name())
+    }
+}
+
+impl CredentialStore for MetadataCredentialStore {
+    fn api_key(
+        &self,
+        provider: &ProviderId,
+        ctx: &AdapterContext,
+    ) -> Result<Option<String>, RuntimeError> {
+        Ok(non_empty(
+            ctx.metadata.get(&Self::metadata_key(provider)).cloned(),
+        ))
+    }
+}
+
+/// Reads keys from the OS keychain: the macOS Keychain, Windows Credential Manager, or the Linux
+/// kernel keyring. Each provider's key is stored under `service` with the provider name as the
+/// account.
+#[cfg(feature = "keychain")]
+#[derive(Debug, Clone)]
+pub struct KeychainCredentialStore {
+    service: String,
+}
+
+#[cfg(feature = "keychain")]
+impl KeychainCredentialStore {
+    pub fn new(service: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+        }
+    }
+}
+
+#[cfg(feature = "keychain")]
+impl CredentialStore for KeychainCredentialStore {
+    fn api_key(
+        &self,
+        provider: &ProviderId,
+        _ctx: &AdapterContext,
+    ) -> Result<Option<String>, RuntimeError> {
+        let invalid = |error: keyring::Error| ConfigError::InvalidCredentialStore {
+            reason: format!(
+                "keychain lookup for {}/{} failed: {error}",
+                self.service,
+                provider.name()
+            ),
+        };
+        let entry = keyring::Entry::new(&self.service, provider.name()).map_err(invalid)?;
+        match entry.get_password() {
+            Ok(api_key) => Ok(non_empty(Some(api_key))),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(error) => Err(invalid(error).into()),
+        }
+    }
+}
+
+/// Tries each store in order and returns the first key found.
+#[derive(Clone, Default)]
+pub struct ChainCredentialStore {
+    stores: Vec<Arc<dyn CredentialStore>>,
+}
+
+impl ChainCredentialStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_store(mut self, store: Arc<dyn CredentialStore>) -> Self {
+        self.stores.push(store);
+        self
+    }
+
+    /// An adapter's default lookup: its constructor key, then the `<provider>.api_key`
+    /// metadata, then the provider's environment variable.
+    pub(crate) fn adapter_default(provider: ProviderId, api_key: Option<String>) -> Self {
+        let mut chain = Self::new();
+        if let Some(api_key) = non_empty(api_key) {
+            chain = chain.with_store(Arc::new(
+                StaticCredentialStore::new().with_key(provider, api_key),
+            ));
+        }
+        chain
+            .with_store(Arc::new(MetadataCredentialStore))
+            .with_store(Arc::new(EnvCredentialStore::new()))
+    }
+}
+
+impl std::fmt::Debug for ChainCredentialStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChainCredentialStore")
+            .field("stores", &self.stores.len())
+            .finish()
+    }
+}
+
+impl CredentialStore for ChainCredentialStore {
+    fn api_key(
+        &self,
+        provider: &ProviderId,
+        ctx: &AdapterContext,
+    ) -> Result<Option<String>, RuntimeError> {
+        for store in &self.stores {
+            if let Some(api_key) = store.api_key(provider, ctx)? {
+                return Ok(Some(api_key));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Resolves the key for one adapter call: the key the runtime placed in `ctx` from its key pool
+/// or credential store, then the adapter's own `store`.
+pub(crate) fn resolve_api_key(
+    provider: &ProviderId,
+    store: &dyn CredentialStore,
+    ctx: &AdapterContext,
+) -> Result<Option<String>, ProviderError> {
+    if let Some(api_key) = non_empty(ctx.metadata.get(API_KEY_METADATA_KEY).cloned()) {
+        return Ok(Some(api_key));
+    }
+    store
+        .api_key(provider, ctx)
+        .map_err(|error| credential_store_error(provider, error))
+}
+
+/// Store failures are local misconfiguration, not provider wire errors, so they surface as a
+/// missing credential.
+pub(crate) fn credential_store_error(provider: &ProviderId, error: RuntimeError) -> ProviderError {
+    ProviderError::CredentialMissing {
+        provider: provider.clone(),
+        message: format!("credential store failed: {error}"),
+    }
+}
+
+fn non_empty(api_key: Option<String>) -> Option<String> {
+    api_key
+        .map(|api_key| api_key.trim().to_string())
+        .filter(|api_key| !api_key.is_empty())
+}
+
+#[cfg(test)]
+mod tests;
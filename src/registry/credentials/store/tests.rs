@@ -0,0 +1,184 @@
+use super::*;
+
+#[derive(Debug)]
+struct FailingStore;
+
+impl CredentialStore for FailingStore {
+    fn api_key(
+        &self,
+        _provider: &ProviderId,
+        _ctx: &AdapterContext,
+    ) -> Result<Option<String>, RuntimeError> {
+        Err(ConfigError::InvalidCredentialStore {
+            reason: "vault sealed".to_string(),
+        }
+        .into())
+    }
+}
+
+fn lookup(store: &dyn CredentialStore, provider: ProviderId) -> Option<String> {
+    store
+        .api_key(&provider, &AdapterContext::default())
+        .expect("lookup should succeed")
+}
+
+#[test]
+fn test_static_store_trims_keys_and_ignores_blank_ones() {
+    let store = StaticCredentialStore::new()
+        .with_key(ProviderId::Openai, " sk-openai ")
+        .with_key(ProviderId::Anthropic, "   ");
+
+    assert_eq!(
+        lookup(&store, ProviderId::Openai).as_deref(),
+        Some("sk-openai")
+    );
+    assert_eq!(lookup(&store, ProviderId::Anthropic), None);
+    assert_eq!(lookup(&store, ProviderId::Openrouter), None);
+}
+
+#[test]
+fn test_env_store_variable_names_and_overrides() {
+    let store = EnvCredentialStore::new()
+        .with_var(ProviderId::Openai, "PATH")
+        .with_var(ProviderId::Anthropic, "PROVIDER_RUNTIME_TEST_UNSET_KEY");
+
+    assert_eq!(
+        EnvCredentialStore::new().var_name(&ProviderId::Openrouter),
+        "OPENROUTER_API_KEY"
+    );
+    assert_eq!(
+        EnvCredentialStore::new().var_name(&ProviderId::Anthropic),
+        "ANTHROPIC_API_KEY"
+    );
+    assert_eq!(
+        lookup(&store, ProviderId::Openai),
+        std::env::var("PATH").ok()
+    );
+    assert_eq!(lookup(&store, ProviderId::Anthropic), None);
+}
+
+#[test]
+fn test_file_store_rereads_the_file_on_each_lookup() {
+    let dir = std::env::temp_dir().join(format!("credential-store-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("temp dir should be created");
+    let path = dir.join("keys.toml");
+    let store = FileCredentialStore::new(&path);
+
+    let missing = store
+        .api_key(&ProviderId::Openai, &AdapterContext::default())
+        .expect_err("missing file should be rejected");
+    assert!(matches!(
+        missing,
+        RuntimeError::ConfigError(ConfigError::InvalidCredentialStore { .. })
+    ));
+
+    std::fs::write(&path, "openai = \"sk-first\"\n").expect("key file should be written");
+    assert_eq!(
+        lookup(&store, ProviderId::Openai).as_deref(),
+        Some("sk-first")
+    );
+    assert_eq!(lookup(&store, ProviderId::Anthropic), None);
+
+    std::fs::write(&path, "openai = \"sk-second\"\n").expect("key file should be rewritten");
+    assert_eq!(
+        lookup(&store, ProviderId::Openai).as_deref(),
+        Some("sk-second")
+    );
+
+    std::fs::write(&path, "openai = [").expect("key file should be rewritten");
+    let malformed = store
+        .api_key(&ProviderId::Openai, &AdapterContext::default())
+        .expect_err("malformed file should be rejected");
+    assert!(matches!(
+        malformed,
+        RuntimeError::ConfigError(ConfigError::InvalidCredentialStore { .. })
+    ));
+
+    std::fs::remove_dir_all(&dir).expect("temp dir should be removed");
+}
+
+#[test]
+fn test_metadata_store_reads_provider_scoped_key() {
+    let mut ctx = AdapterContext::default();
+    ctx.metadata
+        .insert("anthropic.api_key".to_string(), "sk-ant".to_string());
+
+    let store = MetadataCredentialStore;
+    assert_eq!(
+        store
+            .api_key(&ProviderId::Anthropic, &ctx)
+            .expect("lookup should succeed")
+            .as_deref(),
+        Some("sk-ant")
+    );
+    assert_eq!(
+        store
+            .api_key(&ProviderId::Openai, &ctx)
+            .expect("lookup should succeed"),
+        None
+    );
+}
+
+#[test]
+fn test_chain_store_returns_first_key_and_stops_on_errors() {
+    let chain = ChainCredentialStore::new()
+        .with_store(Arc::new(
+            StaticCredentialStore::new().with_key(ProviderId::Openai, "sk-first"),
+        ))
+        .with_store(Arc::new(
+            StaticCredentialStore::new()
+                .with_key(ProviderId::Openai, "sk-second")
+                .with_key(ProviderId::Anthropic, "sk-ant"),
+        ));
+    assert_eq!(
+        lookup(&chain, ProviderId::Openai).as_deref(),
+        Some("sk-first")
+    );
+    assert_eq!(
+        lookup(&chain, ProviderId::Anthropic).as_deref(),
+        Some("sk-ant")
+    );
+    assert_eq!(lookup(&chain, ProviderId::Openrouter), None);
+
+    let failing = chain.with_store(Arc::new(FailingStore));
+    assert!(
+        failing
+            .api_key(&ProviderId::Openrouter, &AdapterContext::default())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_resolve_api_key_prefers_runtime_supplied_key() {
+    let store = StaticCredentialStore::new().with_key(ProviderId::Openai, "sk-adapter");
+    let mut ctx = AdapterContext::default();
+    assert_eq!(
+        resolve_api_key(&ProviderId::Openai, &store, &ctx)
+            .expect("resolve")
+            .as_deref(),
+        Some("sk-adapter")
+    );
+
+    ctx.metadata
+        .insert(API_KEY_METADATA_KEY.to_string(), "sk-pooled".to_string());
+    assert_eq!(
+        resolve_api_key(&ProviderId::Openai, &store, &ctx)
+            .expect("resolve")
+            .as_deref(),
+        Some("sk-pooled")
+    );
+
+    let error = resolve_api_key(
+        &ProviderId::Openai,
+        &FailingStore,
+        &AdapterContext::default(),
+    )
+    .expect_err("store failures surface");
+    assert!(
+        matches!(
+            error,
+            ProviderError::CredentialMissing { ref message, .. } if message.contains("vault sealed")
+        ),
+        "{error}"
+    );
+}
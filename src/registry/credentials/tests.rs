@@ -0,0 +1,106 @@
+use super::*;
+
+fn leased_key(context: &AdapterContext) -> &str {
+    context
+        .metadata
+        .get(API_KEY_METADATA_KEY)
+        .expect("leased context should carry a key")
+}
+
+fn checkout(pool: &KeyPool) -> (String, KeyLease) {
+    let (context, lease) = pool
+        .checkout(&ProviderId::Openai, &AdapterContext::default())
+        .expect("a key should be available");
+    (leased_key(&context).to_string(), lease)
+}
+
+fn rate_limited(retry_after: Option<Duration>) -> ProviderError {
+    ProviderError::RateLimited {
+        provider: ProviderId::Openai,
+        model: None,
+        request_id: None,
+        retry_after,
+        message: "slow down".to_string(),
+        rate_limit: None,
+    }
+}
+
+#[test]
+fn test_round_robin_skips_quarantined_keys() {
+    let pool = KeyPool::new(["key-a", "key-b", "key-c"], KeyRotation::RoundRobin);
+
+    let (first, lease_a) = checkout(&pool);
+    let (second, lease_b) = checkout(&pool);
+    assert_eq!((first.as_str(), second.as_str()), ("key-a", "key-b"));
+
+    pool.report(
+        &lease_a,
+        Some(&ProviderError::CredentialsRejected {
+            provider: ProviderId::Openai,
+            request_id: None,
+            message: "invalid key".to_string(),
+        }),
+    );
+    pool.report(&lease_b, None);
+    assert_eq!(pool.available_keys(), 2);
+
+    let keys: Vec<String> = (0..4).map(|_| checkout(&pool).0).collect();
+    assert_eq!(keys, ["key-c", "key-b", "key-c", "key-b"]);
+}
+
+#[test]
+fn test_least_recently_used_prefers_idle_keys() {
+    let pool = KeyPool::new(["key-a", "key-b"], KeyRotation::LeastRecentlyUsed);
+
+    let (first, _) = checkout(&pool);
+    let (second, _) = checkout(&pool);
+    let (third, _) = checkout(&pool);
+    assert_eq!(
+        [first.as_str(), second.as_str(), third.as_str()],
+        ["key-a", "key-b", "key-a"]
+    );
+}
+
+#[test]
+fn test_rate_limited_keys_cool_down() {
+    let pool = KeyPool::new(["key-a"], KeyRotation::RoundRobin).with_cooldown(Duration::ZERO);
+    let (_, lease) = checkout(&pool);
+    pool.report(&lease, Some(&rate_limited(None)));
+    assert_eq!(checkout(&pool).0, "key-a");
+
+    let (_, lease) = checkout(&pool);
+    pool.report(&lease, Some(&rate_limited(Some(Duration::from_secs(30)))));
+    assert_eq!(pool.available_keys(), 0);
+    let error = pool
+        .checkout(&ProviderId::Openai, &AdapterContext::default())
+        .expect_err("the only key is cooling down");
+    assert!(matches!(
+        error,
+        ProviderError::RateLimited {
+            retry_after: Some(retry_after),
+            ..
+        } if retry_after > Duration::from_secs(25)
+    ));
+}
+
+#[test]
+fn test_exhausted_pool_reports_rejected_credentials() {
+    let pool = KeyPool::new(["key-a"], KeyRotation::RoundRobin);
+    let (_, lease) = checkout(&pool);
+    pool.report(
+        &lease,
+        Some(&ProviderError::Status {
+            provider: ProviderId::Openai,
+            model: None,
+            status_code: 401,
+            request_id: None,
+            message: "unauthorized".to_string(),
+            rate_limit: None,
+        }),
+    );
+
+    let error = pool
+        .checkout(&ProviderId::Openai, &AdapterContext::default())
+        .expect_err("the only key was rejected");
+    assert!(matches!(error, ProviderError::CredentialsRejected { .. }));
+}
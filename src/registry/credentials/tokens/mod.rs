@@ -0,0 +1,82 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+use tokio::sync::Mutex;
+
+use crate::core::error::ProviderError;
+use crate::core::traits::TokenProvider;
+use crate::core::types::{AccessToken, ProviderId};
+
+/// Adapter context metadata key carrying the bearer token fetched from a provider's
+/// `TokenProvider`. Adapters send it in place of an API key.
+pub const ACCESS_TOKEN_METADATA_KEY: &str = "credentials.access_token";
+
+/// How long before its expiry a cached token is refreshed, so a call never leaves with a token
+/// that lapses in flight.
+pub const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(30);
+
+/// A provider's `TokenProvider` with its last token. Concurrent calls wait on one refresh
+/// instead of each fetching a token. Tokens without an expiry are kept until `force_refresh`.
+pub(crate) struct CachedTokenProvider {
+    source: Arc<dyn TokenProvider>,
+    cached: Mutex<Option<AccessToken>>,
+    refresh_requested: AtomicBool,
+}
+
+impl CachedTokenProvider {
+    pub(crate) fn new(source: Arc<dyn TokenProvider>) -> Self {
+        Self {
+            source,
+            cached: Mutex::new(None),
+            refresh_requested: AtomicBool::new(false),
+        }
+    }
+
+    /// Makes the next `token` call fetch a new token, as after the provider rejects the cached
+    /// one.
+    pub(crate) fn force_refresh(&self) {
+        self.refresh_requested.store(true, Ordering::Release);
+    }
+
+    pub(crate) async fn token(&self, provider: &ProviderId) -> Result<String, ProviderError> {
+        let mut cached = self.cached.lock().await;
+        if let Some(token) = cached.as_ref()
+            && !self.refresh_requested.swap(false, Ordering::AcqRel)
+            && is_fresh(token, SystemTime::now())
+        {
+            return Ok(token.token.clone());
+        }
+
+        let token = self
+            .source
+            .access_token(provider.clone())
+            .await
+            .map_err(|error| ProviderError::CredentialMissing {
+                provider: provider.clone(),
+                message: format!("token provider failed: {error}"),
+            })?;
+        if token.token.trim().is_empty() {
+            return Err(ProviderError::CredentialMissing {
+                provider: provider.clone(),
+                message: "token provider returned an empty token".to_string(),
+            });
+        }
+        let value = token.token.clone();
+        *cached = Some(token);
+        Ok(value)
+    }
+}
+
+/// Tokens without an expiry never go stale on their own.
+fn is_fresh(token: &AccessToken, now: SystemTime) -> bool {
+    let Some(expires_at) = token.expires_at else {
+        return true;
+    };
+    expires_at
+        .checked_sub(TOKEN_REFRESH_MARGIN)
+        .is_some_and(|refresh_at| now < refresh_at)
+}
+
+#[cfg(test)]
+mod tests;
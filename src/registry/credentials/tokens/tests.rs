@@ -0,0 +1,144 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use super::*;
+use crate::core::error::RuntimeError;
+
+struct ScriptedTokens {
+    tokens: Mutex<Vec<Result<AccessToken, RuntimeError>>>,
+    calls: Mutex<u32>,
+}
+
+impl ScriptedTokens {
+    fn new(tokens: Vec<Result<AccessToken, RuntimeError>>) -> Arc<Self> {
+        Arc::new(Self {
+            tokens: Mutex::new(tokens),
+            calls: Mutex::new(0),
+        })
+    }
+
+    fn calls(&self) -> u32 {
+        *self.calls.lock().expect("calls lock")
+    }
+}
+
+#[async_trait]
+impl TokenProvider for ScriptedTokens {
+    async fn get_token(&self, _provider: ProviderId) -> Result<String, RuntimeError> {
+        unreachable!("access_token is overridden")
+    }
+
+    async fn access_token(&self, _provider: ProviderId) -> Result<AccessToken, RuntimeError> {
+        *self.calls.lock().expect("calls lock") += 1;
+        self.tokens.lock().expect("tokens lock").remove(0)
+    }
+}
+
+struct PlainTokens;
+
+#[async_trait]
+impl TokenProvider for PlainTokens {
+    async fn get_token(&self, _provider: ProviderId) -> Result<String, RuntimeError> {
+        Ok("plain-token".to_string())
+    }
+}
+
+fn expiring_in(token: &str, seconds: u64) -> AccessToken {
+    AccessToken::new(token).with_expires_at(SystemTime::now() + Duration::from_secs(seconds))
+}
+
+#[tokio::test]
+async fn test_cached_token_is_reused_until_refresh_margin() {
+    let source = ScriptedTokens::new(vec![
+        Ok(expiring_in("token-a", 3600)),
+        Ok(expiring_in("token-b", 3600)),
+    ]);
+    let cache = CachedTokenProvider::new(source.clone());
+
+    for _ in 0..3 {
+        assert_eq!(
+            cache.token(&ProviderId::Openai).await.expect("token"),
+            "token-a"
+        );
+    }
+    assert_eq!(source.calls(), 1);
+
+    let near_expiry = ScriptedTokens::new(vec![
+        Ok(expiring_in("token-a", TOKEN_REFRESH_MARGIN.as_secs() - 1)),
+        Ok(expiring_in("token-b", 3600)),
+    ]);
+    let cache = CachedTokenProvider::new(near_expiry.clone());
+    cache.token(&ProviderId::Openai).await.expect("token");
+    assert_eq!(
+        cache.token(&ProviderId::Openai).await.expect("token"),
+        "token-b"
+    );
+    assert_eq!(near_expiry.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_token_without_expiry_is_cached_until_forced_refresh() {
+    let source = ScriptedTokens::new(vec![
+        Ok(AccessToken::new("token-a")),
+        Ok(AccessToken::new("token-b")),
+    ]);
+    let cache = CachedTokenProvider::new(source.clone());
+
+    for _ in 0..2 {
+        assert_eq!(
+            cache.token(&ProviderId::Openai).await.expect("token"),
+            "token-a"
+        );
+    }
+    assert_eq!(source.calls(), 1);
+
+    cache.force_refresh();
+    assert_eq!(
+        cache.token(&ProviderId::Openai).await.expect("token"),
+        "token-b"
+    );
+    assert_eq!(
+        cache.token(&ProviderId::Openai).await.expect("token"),
+        "token-b"
+    );
+    assert_eq!(source.calls(), 2);
+
+    let plain = CachedTokenProvider::new(Arc::new(PlainTokens));
+    assert_eq!(
+        plain.token(&ProviderId::Openai).await.expect("token"),
+        "plain-token"
+    );
+}
+
+#[tokio::test]
+async fn test_token_failures_become_credential_missing() {
+    let source = ScriptedTokens::new(vec![
+        Err(RuntimeError::credential_missing(
+            ProviderId::Anthropic,
+            Vec::new(),
+        )),
+        Ok(AccessToken::new("  ")),
+    ]);
+    let cache = CachedTokenProvider::new(source);
+
+    let failed = cache
+        .token(&ProviderId::Anthropic)
+        .await
+        .expect_err("fetch failure");
+    assert!(matches!(
+        failed,
+        ProviderError::CredentialMissing { ref message, .. } if message.contains("token provider failed")
+    ));
+    let empty = cache
+        .token(&ProviderId::Anthropic)
+        .await
+        .expect_err("empty token");
+    assert!(matches!(
+        RuntimeError::from(empty),
+        RuntimeError::CredentialMissing {
+            provider: ProviderId::Anthropic,
+            ..
+        }
+    ));
+}
@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime};
+
+use crate::core::error::ProviderError;
+use crate::core::types::ProviderId;
+
+/// Consecutive failed health checks after which a provider is taken out of routing.
+pub const DEFAULT_UNHEALTHY_THRESHOLD: u32 = 3;
+
+/// Bound on a single provider's health check; a check that hangs counts as a failure.
+pub(crate) const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A provider's health as of its latest check. Providers that were never checked are healthy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderHealth {
+    pub provider: ProviderId,
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+    /// Error from the most recent check, cleared by the next successful one.
+    pub last_error: Option<ProviderError>,
+    pub last_checked: Option<SystemTime>,
+}
+
+impl ProviderHealth {
+    fn unchecked(provider: ProviderId) -> Self {
+        Self {
+            provider,
+            healthy: true,
+            consecutive_failures: 0,
+            last_error: None,
+            last_checked: None,
+        }
+    }
+}
+
+/// Health check results shared between the registry and its background check task.
+#[derive(Debug)]
+pub(crate) struct HealthTracker {
+    unhealthy_threshold: u32,
+    states: RwLock<BTreeMap<ProviderId, ProviderHealth>>,
+}
+
+impl HealthTracker {
+    pub(crate) fn new(unhealthy_threshold: u32) -> Self {
+        Self {
+            unhealthy_threshold: unhealthy_threshold.max(1),
+            states: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// Records one check. A success restores the provider; a failure quarantines it once it
+    /// reaches the threshold of consecutive failures.
+    pub(crate) fn record(&self, provider: &ProviderId, result: Result<(), ProviderError>) {
+        let mut states = self
+            .states
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let health = states
+            .entry(provider.clone())
+            .or_insert_with(|| ProviderHealth::unchecked(provider.clone()));
+        health.last_checked = Some(SystemTime::now());
+        match result {
+            Ok(()) => {
+                health.healthy = true;
+                health.consecutive_failures = 0;
+                health.last_error = None;
+            }
+            Err(error) => {
+                health.consecutive_failures += 1;
+                health.healthy = health.consecutive_failures < self.unhealthy_threshold;
+                health.last_error = Some(error);
+            }
+        }
+    }
+
+    pub(crate) fn is_healthy(&self, provider: &ProviderId) -> bool {
+        self.states
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(provider)
+            .is_none_or(|health| health.healthy)
+    }
+
+    pub(crate) fn status(&self, provider: &ProviderId) -> ProviderHealth {
+        self.states
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(provider)
+            .cloned()
+            .unwrap_or_else(|| ProviderHealth::unchecked(provider.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests;
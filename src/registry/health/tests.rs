@@ -0,0 +1,37 @@
+use super::*;
+
+fn check_failure(provider: &ProviderId) -> ProviderError {
+    ProviderError::Transport {
+        provider: provider.clone(),
+        request_id: None,
+        message: "connection refused".to_string(),
+        source: None,
+    }
+}
+
+#[test]
+fn test_health_tracker_quarantines_after_threshold_and_recovers() {
+    let tracker = HealthTracker::new(2);
+    let provider = ProviderId::Openai;
+    assert!(tracker.is_healthy(&provider));
+    assert_eq!(tracker.status(&provider).last_checked, None);
+
+    tracker.record(&provider, Err(check_failure(&provider)));
+    assert!(tracker.is_healthy(&provider));
+    assert_eq!(tracker.status(&provider).consecutive_failures, 1);
+
+    tracker.record(&provider, Err(check_failure(&provider)));
+    let status = tracker.status(&provider);
+    assert!(!status.healthy);
+    assert_eq!(status.consecutive_failures, 2);
+    assert_eq!(status.last_error, Some(check_failure(&provider)));
+    assert!(status.last_checked.is_some());
+    assert!(!tracker.is_healthy(&provider));
+    assert!(tracker.is_healthy(&ProviderId::Anthropic));
+
+    tracker.record(&provider, Ok(()));
+    let status = tracker.status(&provider);
+    assert!(status.healthy);
+    assert_eq!(status.consecutive_failures, 0);
+    assert_eq!(status.last_error, None);
+}
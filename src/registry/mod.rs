@@ -1,5 +1,19 @@
+pub(crate) mod credentials;
+mod health;
 #[allow(clippy::module_inception)]
-pub mod registry;
+mod registry;
+mod routing;
+
+#[cfg(feature = "keychain")]
+pub use credentials::KeychainCredentialStore;
+pub use credentials::{
+    ACCESS_TOKEN_METADATA_KEY, API_KEY_METADATA_KEY, ChainCredentialStore, DEFAULT_KEY_COOLDOWN,
+    EnvCredentialStore, FileCredentialStore, KeyPool, KeyRotation, MetadataCredentialStore,
+    StaticCredentialStore, TOKEN_REFRESH_MARGIN,
+};
+pub use health::{DEFAULT_UNHEALTHY_THRESHOLD, ProviderHealth};
+pub use registry::{DiscoveryFailure, DiscoveryReport, ProviderRegistry};
+pub use routing::{RoutingPolicy, SESSION_ID_METADATA_KEY};
 
 #[cfg(test)]
 mod tests;
@@ -1,15 +1,74 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
+use futures_util::future::join_all;
+
+use super::credentials::{CachedTokenProvider, CredentialSources, KeyLease, KeyPool};
+use super::health::{
+    DEFAULT_UNHEALTHY_THRESHOLD, HEALTH_CHECK_TIMEOUT, HealthTracker, ProviderHealth,
+};
+use super::routing::RoutingPolicy;
 use crate::catalog;
-use crate::core::error::{RoutingError, RuntimeError};
-use crate::core::traits::ProviderAdapter;
-use crate::core::types::{AdapterContext, DiscoveryOptions, ModelCatalog, ModelRef, ProviderId};
+use crate::core::error::{ProviderError, RoutingError, RuntimeError};
+use crate::core::traits::{CredentialStore, ProviderAdapter, TokenProvider};
+use crate::core::types::{
+    AdapterContext, DiscoveryOptions, ModelAlias, ModelCatalog, ModelInfo, ModelRef, ProviderId,
+    RuntimeWarning, WarningCode,
+};
+
+const LATENCY_SAMPLE_WEIGHT: f64 = 0.2;
+
+/// Outcome of a discovery refresh: the merged catalog plus any providers that failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveryReport {
+    pub catalog: ModelCatalog,
+    /// Number of adapters queried remotely; zero when the cached catalog was returned.
+    pub queried: usize,
+    pub failures: Vec<DiscoveryFailure>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveryFailure {
+    pub provider: ProviderId,
+    pub error: ProviderError,
+}
+
+impl DiscoveryReport {
+    /// One `discovery.provider_failed` warning per provider whose models could not be listed.
+    pub fn warnings(&self) -> Vec<RuntimeWarning> {
+        self.failures
+            .iter()
+            .map(|failure| RuntimeWarning {
+                code: WarningCode::DiscoveryProviderFailed,
+                message: format!(
+                    "model discovery failed for provider={:?}: {}",
+                    failure.provider, failure.error
+                ),
+            })
+            .collect()
+    }
+}
 
 pub struct ProviderRegistry {
     adapters: Vec<(ProviderId, Arc<dyn ProviderAdapter>)>,
     static_catalog: ModelCatalog,
     active_catalog: RwLock<ModelCatalog>,
     default_provider: Option<ProviderId>,
+    aliases: Vec<ModelAlias>,
+    routing_policies: BTreeMap<String, (RoutingPolicy, AtomicU64)>,
+    discovery_ttls: BTreeMap<ProviderId, Duration>,
+    discovery_timeout: Option<Duration>,
+    discovery_cache: RwLock<BTreeMap<ProviderId, CachedDiscovery>>,
+    observed_latency: RwLock<BTreeMap<(ProviderId, String), f64>>,
+    health: Arc<HealthTracker>,
+    credentials: Arc<CredentialSources>,
+}
+
+struct CachedDiscovery {
+    models: Vec<ModelInfo>,
+    fetched_at: Instant,
 }
 
 impl ProviderRegistry {
@@ -19,9 +78,29 @@ impl ProviderRegistry {
             active_catalog: RwLock::new(static_catalog.clone()),
             static_catalog,
             default_provider,
+            aliases: Vec::new(),
+            routing_policies: BTreeMap::new(),
+            discovery_ttls: BTreeMap::new(),
+            discovery_timeout: None,
+            discovery_cache: RwLock::new(BTreeMap::new()),
+            observed_latency: RwLock::new(BTreeMap::new()),
+            health: Arc::new(HealthTracker::new(DEFAULT_UNHEALTHY_THRESHOLD)),
+            credentials: Arc::new(CredentialSources::default()),
         }
     }
 
+    /// Reuses a provider's discovered models for `ttl` instead of listing them again on every
+    /// refresh. Providers without a TTL are listed on every refresh.
+    pub fn set_discovery_ttl(&mut self, provider: ProviderId, ttl: Duration) {
+        self.discovery_ttls.insert(provider, ttl);
+    }
+
+    /// Bounds each provider's model listing by `timeout`. A provider that does not answer in
+    /// time is reported as a failed provider while the others' results are still merged.
+    pub fn set_discovery_timeout(&mut self, timeout: Duration) {
+        self.discovery_timeout = Some(timeout);
+    }
+
     pub fn register(&mut self, adapter: Arc<dyn ProviderAdapter>) {
         let provider = adapter.id();
 
@@ -50,6 +129,120 @@ impl ProviderRegistry {
             })
     }
 
+    /// Adds an alias consulted by `resolve_route`. Aliases added first take priority when
+    /// several providers serve the same name.
+    pub fn add_alias(&mut self, alias: ModelAlias) {
+        self.aliases.push(alias);
+    }
+
+    /// Spreads requests for `alias` across its available routes by `policy` instead of always
+    /// taking the first. Requests with a provider hint still take the hinted route.
+    pub fn set_routing_policy(&mut self, alias: impl Into<String>, policy: RoutingPolicy) {
+        self.routing_policies
+            .insert(alias.into(), (policy, AtomicU64::new(0)));
+    }
+
+    /// Rewrites `model` to a concrete route, then resolves the provider like
+    /// `resolve_provider`. A model with requirements becomes the best matching catalog model;
+    /// an aliased model becomes its provider-specific id, chosen by the alias's routing policy
+    /// and the session in `context`. Either way the provider is pinned.
+    pub fn resolve_route(
+        &self,
+        model: &mut ModelRef,
+        context: &AdapterContext,
+    ) -> Result<ProviderId, RoutingError> {
+        if let Some(requirements) = model.requirements.take() {
+            let active_catalog = self.read_active_catalog();
+            let selected = catalog::select_model(
+                &active_catalog,
+                &requirements,
+                model.provider_hint.as_ref(),
+                |provider| self.is_routable(provider),
+                |info| self.observed_latency_ms(&info.provider, &info.model_id),
+            )
+            .ok_or_else(|| RoutingError::NoModelMatchesRequirements {
+                requirements: format!("{requirements:?}"),
+            })?;
+            model.model_id = selected.model_id.clone();
+            model.provider_hint = Some(selected.provider.clone());
+        }
+
+        if let Some(alias) = self.choose_alias_route(model, context) {
+            model.model_id = alias.model_id.clone();
+            model.provider_hint = Some(alias.provider.clone());
+        }
+
+        let provider = self.resolve_provider(model)?;
+        if !self.is_healthy(&provider) {
+            return Err(RoutingError::ProviderUnhealthy { provider });
+        }
+        Ok(provider)
+    }
+
+    /// Resolves `model` the way `resolve_route` would, without advancing routing policies or
+    /// consulting provider health. Used to check configured routes at build time.
+    pub(crate) fn check_route(&self, model: &ModelRef) -> Result<ProviderId, RoutingError> {
+        let mut model = model.clone();
+        if let Some(requirements) = model.requirements.take() {
+            let active_catalog = self.read_active_catalog();
+            let selected = catalog::select_model(
+                &active_catalog,
+                &requirements,
+                model.provider_hint.as_ref(),
+                |provider| self.resolve_adapter(provider).is_ok(),
+                |_| None,
+            )
+            .ok_or_else(|| RoutingError::NoModelMatchesRequirements {
+                requirements: format!("{requirements:?}"),
+            })?;
+            return Ok(selected.provider.clone());
+        }
+
+        if let Some(alias) = catalog::resolve_model_alias(
+            &self.aliases,
+            &model.model_id,
+            model.provider_hint.as_ref(),
+            |provider| self.resolve_adapter(provider).is_ok(),
+        ) {
+            model.model_id = alias.model_id.clone();
+            model.provider_hint = Some(alias.provider.clone());
+        }
+        self.resolve_provider(&model)
+    }
+
+    fn is_routable(&self, provider: &ProviderId) -> bool {
+        self.resolve_adapter(provider).is_ok() && self.is_healthy(provider)
+    }
+
+    fn choose_alias_route(
+        &self,
+        model: &ModelRef,
+        context: &AdapterContext,
+    ) -> Option<&ModelAlias> {
+        let is_available = |provider: &ProviderId| self.is_routable(provider);
+        let policy = self
+            .routing_policies
+            .get(&model.model_id)
+            .filter(|_| model.provider_hint.is_none());
+        let Some((policy, turns)) = policy else {
+            return catalog::resolve_model_alias(
+                &self.aliases,
+                &model.model_id,
+                model.provider_hint.as_ref(),
+                is_available,
+            );
+        };
+
+        let routes: Vec<&ModelAlias> = self
+            .aliases
+            .iter()
+            .filter(|alias| alias.alias == model.model_id && is_available(&alias.provider))
+            .collect();
+        policy
+            .choose(&routes, turns.fetch_add(1, Ordering::Relaxed), context)
+            .or_else(|| routes.first().copied())
+    }
+
     pub fn resolve_provider(&self, model: &ModelRef) -> Result<ProviderId, RoutingError> {
         if let Some(provider_hint) = &model.provider_hint {
             self.resolve_adapter(provider_hint)?;
@@ -74,34 +267,240 @@ impl ProviderRegistry {
         }
     }
 
+    /// Rotates calls to `provider` across `pool`'s keys, replacing any earlier pool.
+    pub fn set_key_pool(&mut self, provider: ProviderId, pool: KeyPool) {
+        Arc::make_mut(&mut self.credentials)
+            .pools
+            .insert(provider, Arc::new(pool));
+    }
+
+    pub fn key_pool(&self, provider: &ProviderId) -> Option<&KeyPool> {
+        self.credentials.pools.get(provider).map(Arc::as_ref)
+    }
+
+    /// Fetches a bearer token from `tokens` before each call to `provider`, in place of any API
+    /// key. Tokens are reused until they near expiry.
+    pub fn set_token_provider(&mut self, provider: ProviderId, tokens: Arc<dyn TokenProvider>) {
+        Arc::make_mut(&mut self.credentials)
+            .tokens
+            .insert(provider, Arc::new(CachedTokenProvider::new(tokens)));
+    }
+
+    /// Looks up keys in `store` for providers without a key pool. Keys found there take
+    /// precedence over the adapter's own key.
+    pub fn set_credential_store(&mut self, store: Arc<dyn CredentialStore>) {
+        Arc::make_mut(&mut self.credentials).store = Some(store);
+    }
+
+    /// Returns `ctx` carrying `provider`'s access token or next pooled key, or `ctx` unchanged
+    /// when it has neither. Pass the lease to `report_credential` once the call finishes.
+    pub(crate) async fn lease_credential(
+        &self,
+        provider: &ProviderId,
+        ctx: &AdapterContext,
+    ) -> Result<(AdapterContext, Option<KeyLease>), ProviderError> {
+        self.credentials.lease(provider, ctx).await
+    }
+
+    /// Whether `provider` authenticates with a `TokenProvider`, whose token a rejected call
+    /// refreshes.
+    pub(crate) fn has_token_provider(&self, provider: &ProviderId) -> bool {
+        self.credentials.has_token_provider(provider)
+    }
+
+    pub(crate) fn report_credential(
+        &self,
+        provider: &ProviderId,
+        lease: Option<&KeyLease>,
+        error: Option<&ProviderError>,
+    ) {
+        self.credentials.report(provider, lease, error);
+    }
+
+    /// Takes a provider out of routing after `threshold` consecutive failed health checks
+    /// instead of the default [`DEFAULT_UNHEALTHY_THRESHOLD`]. Resets recorded health.
+    pub fn set_unhealthy_threshold(&mut self, threshold: u32) {
+        self.health = Arc::new(HealthTracker::new(threshold));
+    }
+
+    /// Whether `provider` may be routed to. Only health checks mark a provider unhealthy, and
+    /// the next passing check restores it.
+    pub fn is_healthy(&self, provider: &ProviderId) -> bool {
+        self.health.is_healthy(provider)
+    }
+
+    /// Health of every registered provider, in registration order.
+    pub fn health_status(&self) -> Vec<ProviderHealth> {
+        self.adapters
+            .iter()
+            .map(|(provider, _)| self.health.status(provider))
+            .collect()
+    }
+
+    /// Runs every adapter's health check concurrently and records the results.
+    pub async fn check_health(&self, ctx: &AdapterContext) -> Vec<ProviderHealth> {
+        run_health_checks(&self.adapters, &self.credentials, &self.health, ctx).await;
+        self.health_status()
+    }
+
+    /// Spawns a task that runs `check_health` every `interval`, starting immediately, until
+    /// the returned handle is aborted. Must be called from within a Tokio runtime.
+    pub fn spawn_health_checks(
+        &self,
+        interval: Duration,
+        ctx: AdapterContext,
+    ) -> tokio::task::JoinHandle<()> {
+        let adapters = self.adapters.clone();
+        let credentials = Arc::clone(&self.credentials);
+        let health = Arc::clone(&self.health);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                run_health_checks(&adapters, &credentials, &health, &ctx).await;
+            }
+        })
+    }
+
+    /// Folds a completed call's latency into the moving average that
+    /// [`ModelPreference::Fastest`](crate::core::types::ModelPreference::Fastest) selection ranks by, weighting the newest call by a fifth.
+    pub fn record_latency(&self, provider: &ProviderId, model_id: &str, latency: Duration) {
+        let sample = latency.as_secs_f64() * 1000.0;
+        self.observed_latency
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry((provider.clone(), model_id.to_string()))
+            .and_modify(|average| *average += (sample - *average) * LATENCY_SAMPLE_WEIGHT)
+            .or_insert(sample);
+    }
+
+    /// The moving average latency of calls to `model_id`, or `None` before the first call.
+    pub fn observed_latency_ms(&self, provider: &ProviderId, model_id: &str) -> Option<f64> {
+        self.observed_latency
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(&(provider.clone(), model_id.to_string()))
+            .copied()
+    }
+
+    /// Looks up a model in the active catalog, which includes remotely discovered metadata
+    /// once `discover_models` has refreshed it.
+    pub fn find_model(&self, provider: &ProviderId, model_id: &str) -> Option<ModelInfo> {
+        self.active_catalog
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .models
+            .iter()
+            .find(|model| model.provider == *provider && model.model_id == model_id)
+            .cloned()
+    }
+
+    /// The active catalog's models matching `filter`, in catalog order.
+    pub fn query_models(&self, filter: &catalog::CatalogFilter) -> Vec<ModelInfo> {
+        self.active_catalog
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .query(filter)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Refreshes the catalog when `opts` asks for it and returns the merged result.
+    ///
+    /// Individual provider failures are tolerated; this only errors when every queried
+    /// provider failed. Use `discover_models_report` to inspect per-provider failures.
     pub async fn discover_models(
         &self,
         opts: &DiscoveryOptions,
         ctx: &AdapterContext,
     ) -> Result<ModelCatalog, RuntimeError> {
+        let report = self.discover_models_report(opts, ctx).await;
+        #[cfg(feature = "tracing")]
+        for warning in report.warnings() {
+            tracing::warn!(
+                target: crate::telemetry::TARGET,
+                code = %warning.code,
+                "{}",
+                warning.message
+            );
+        }
+        if report.queried > 0
+            && report.failures.len() == report.queried
+            && let Some(failure) = report.failures.into_iter().next()
+        {
+            return Err(failure.error.into());
+        }
+
+        Ok(report.catalog)
+    }
+
+    /// Queries remote-discovery adapters concurrently and merges every successful result.
+    ///
+    /// Providers whose cached result is within their discovery TTL are not queried again.
+    /// Models previously discovered from a provider that fails this time are kept, so one bad
+    /// credential does not drop that provider's entries from the active catalog.
+    pub async fn discover_models_report(
+        &self,
+        opts: &DiscoveryOptions,
+        ctx: &AdapterContext,
+    ) -> DiscoveryReport {
         if !opts.remote || !opts.refresh_cache {
-            return Ok(self.read_active_catalog());
+            return DiscoveryReport {
+                catalog: self.read_active_catalog(),
+                queried: 0,
+                failures: Vec::new(),
+            };
         }
 
         let mut adapters = self
             .adapters
             .iter()
+            .filter(|(provider, adapter)| {
+                (opts.include_provider.is_empty() || opts.include_provider.contains(provider))
+                    && adapter.capabilities().supports_remote_discovery
+            })
             .map(|(provider, adapter)| (provider.clone(), Arc::clone(adapter)))
             .collect::<Vec<_>>();
         adapters.sort_by_key(|(provider, _)| provider_sort_key(provider));
 
-        let mut remote_models = Vec::new();
-        for (provider, adapter) in adapters {
-            if !opts.include_provider.is_empty() && !opts.include_provider.contains(&provider) {
-                continue;
+        let cached = self.read_discovery_cache(&adapters);
+        let results = join_all(adapters.iter().map(|(provider, adapter)| {
+            let fresh = cached
+                .get(provider)
+                .filter(|(_, fetched_at)| self.is_fresh(provider, *fetched_at))
+                .map(|(models, _)| models.clone());
+            async move {
+                let result = match fresh {
+                    Some(models) => Ok((models, false)),
+                    None => self
+                        .discover_with_timeout(provider, adapter.as_ref(), opts, ctx)
+                        .await
+                        .map(|models| (models, true)),
+                };
+                (provider.clone(), result)
             }
+        }))
+        .await;
 
-            if !adapter.capabilities().supports_remote_discovery {
-                continue;
+        let mut remote_models = Vec::new();
+        let mut failures = Vec::new();
+        for (provider, result) in results {
+            match result {
+                Ok((discovered, fetched)) => {
+                    if fetched {
+                        self.write_discovery_cache(&provider, &discovered);
+                    }
+                    remote_models.extend(discovered);
+                }
+                Err(error) => {
+                    if let Some((stale, _)) = cached.get(&provider) {
+                        remote_models.extend(stale.iter().cloned());
+                    }
+                    failures.push(DiscoveryFailure { provider, error });
+                }
             }
-
-            let discovered = adapter.discover_models(opts, ctx).await?;
-            remote_models.extend(discovered);
         }
 
         let merged_catalog = catalog::merge_static_and_remote_catalog(
@@ -113,7 +512,76 @@ impl ProviderRegistry {
 
         self.write_active_catalog(merged_catalog.clone());
 
-        Ok(merged_catalog)
+        DiscoveryReport {
+            catalog: merged_catalog,
+            queried: adapters.len(),
+            failures,
+        }
+    }
+
+    async fn discover_with_timeout(
+        &self,
+        provider: &ProviderId,
+        adapter: &dyn ProviderAdapter,
+        opts: &DiscoveryOptions,
+        ctx: &AdapterContext,
+    ) -> Result<Vec<ModelInfo>, ProviderError> {
+        let (ctx, lease) = self.lease_credential(provider, ctx).await?;
+        let result = match self.discovery_timeout {
+            None => adapter.discover_models(opts, &ctx).await,
+            Some(timeout) => tokio::time::timeout(timeout, adapter.discover_models(opts, &ctx))
+                .await
+                .unwrap_or_else(|_| {
+                    Err(ProviderError::Timeout {
+                        provider: provider.clone(),
+                        model: None,
+                        message: format!(
+                            "model discovery did not finish within {} ms",
+                            timeout.as_millis()
+                        ),
+                        source: None,
+                    })
+                }),
+        };
+        self.report_credential(provider, lease.as_ref(), result.as_ref().err());
+        result
+    }
+
+    fn is_fresh(&self, provider: &ProviderId, fetched_at: Instant) -> bool {
+        self.discovery_ttls
+            .get(provider)
+            .is_some_and(|ttl| fetched_at.elapsed() < *ttl)
+    }
+
+    fn read_discovery_cache(
+        &self,
+        adapters: &[(ProviderId, Arc<dyn ProviderAdapter>)],
+    ) -> BTreeMap<ProviderId, (Vec<ModelInfo>, Instant)> {
+        let cache = self
+            .discovery_cache
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        adapters
+            .iter()
+            .filter_map(|(provider, _)| {
+                cache
+                    .get(provider)
+                    .map(|entry| (provider.clone(), (entry.models.clone(), entry.fetched_at)))
+            })
+            .collect()
+    }
+
+    fn write_discovery_cache(&self, provider: &ProviderId, models: &[ModelInfo]) {
+        self.discovery_cache
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(
+                provider.clone(),
+                CachedDiscovery {
+                    models: models.to_vec(),
+                    fetched_at: Instant::now(),
+                },
+            );
     }
 
     fn read_active_catalog(&self) -> ModelCatalog {
@@ -146,5 +614,47 @@ fn provider_sort_key(provider: &ProviderId) -> u8 {
     }
 }
 
+async fn run_health_checks(
+    adapters: &[(ProviderId, Arc<dyn ProviderAdapter>)],
+    credentials: &CredentialSources,
+    health: &HealthTracker,
+    ctx: &AdapterContext,
+) {
+    let results = join_all(adapters.iter().map(|(provider, adapter)| async move {
+        let (ctx, lease) = match credentials.lease(provider, ctx).await {
+            Ok(leased) => leased,
+            Err(error) => return (provider, Err(error)),
+        };
+        let result = tokio::time::timeout(HEALTH_CHECK_TIMEOUT, adapter.health_check(&ctx))
+            .await
+            .unwrap_or_else(|_| {
+                Err(ProviderError::Timeout {
+                    provider: provider.clone(),
+                    model: None,
+                    message: format!(
+                        "health check did not finish within {} ms",
+                        HEALTH_CHECK_TIMEOUT.as_millis()
+                    ),
+                    source: None,
+                })
+            });
+        credentials.report(provider, lease.as_ref(), result.as_ref().err());
+        (provider, result)
+    }))
+    .await;
+
+    for (provider, result) in results {
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &result {
+            tracing::warn!(
+                target: crate::telemetry::TARGET,
+                provider = ?provider,
+                "health check failed: {error}"
+            );
+        }
+        health.record(provider, result);
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -1,4 +1,6 @@
+use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use async_trait::async_trait;
 
@@ -6,16 +8,19 @@ use super::ProviderRegistry;
 use crate::core::error::{ProviderError, RoutingError};
 use crate::core::traits::ProviderAdapter;
 use crate::core::types::{
-    AdapterContext, AssistantOutput, ContentPart, DiscoveryOptions, FinishReason, ModelCatalog,
-    ModelInfo, ModelRef, ProviderCapabilities, ProviderId, ProviderRequest, ProviderResponse,
-    ToolChoice, Usage,
+    AdapterContext, AssistantOutput, ContentPart, DiscoveryOptions, FinishReason, ModelAlias,
+    ModelCatalog, ModelInfo, ModelPreference, ModelRef, ModelRequirements, ProviderCapabilities,
+    ProviderId, ProviderRequest, ProviderResponse, ToolChoice, Usage,
 };
+use crate::registry::{RoutingPolicy, SESSION_ID_METADATA_KEY};
 
 #[derive(Clone)]
 struct MockAdapter {
     provider: ProviderId,
     capabilities: ProviderCapabilities,
     discovered_models: Vec<ModelInfo>,
+    discover_error: Option<ProviderError>,
+    discover_delay: Option<Duration>,
     discover_calls: Arc<Mutex<u32>>,
 }
 
@@ -29,10 +34,26 @@ impl MockAdapter {
             provider,
             capabilities,
             discovered_models,
+            discover_error: None,
+            discover_delay: None,
             discover_calls: Arc::new(Mutex::new(0)),
         }
     }
 
+    fn failing_discovery(mut self, message: &str) -> Self {
+        self.discover_error = Some(ProviderError::CredentialsRejected {
+            provider: self.provider.clone(),
+            request_id: None,
+            message: message.to_string(),
+        });
+        self
+    }
+
+    fn slow_discovery(mut self, delay: Duration) -> Self {
+        self.discover_delay = Some(delay);
+        self
+    }
+
     fn discover_call_count(&self) -> u32 {
         *self
             .discover_calls
@@ -62,6 +83,7 @@ impl ProviderAdapter for MockAdapter {
                     text: "ok".to_string(),
                 }],
                 structured_output: None,
+                annotations: Vec::new(),
             },
             usage: Usage::default(),
             cost: None,
@@ -70,6 +92,10 @@ impl ProviderAdapter for MockAdapter {
             raw_provider_response: None,
             finish_reason: FinishReason::Stop,
             warnings: Vec::new(),
+            alternatives: Vec::new(),
+            response_id: None,
+            request_id: None,
+            rate_limit: None,
         })
     }
 
@@ -82,7 +108,13 @@ impl ProviderAdapter for MockAdapter {
             .discover_calls
             .lock()
             .expect("discover_calls lock should not be poisoned") += 1;
-        Ok(self.discovered_models.clone())
+        if let Some(delay) = self.discover_delay {
+            tokio::time::sleep(delay).await;
+        }
+        match &self.discover_error {
+            Some(error) => Err(error.clone()),
+            None => Ok(self.discovered_models.clone()),
+        }
     }
 }
 
@@ -98,6 +130,7 @@ fn adapter_with_models(
             supports_structured_output: true,
             supports_thinking: false,
             supports_remote_discovery,
+            supports_parallel_tool_calls: false,
         },
         discovered_models,
     )
@@ -118,6 +151,11 @@ fn model(
         max_output_tokens,
         supports_tools: true,
         supports_structured_output: true,
+        pricing: None,
+        input_modalities: Vec::new(),
+        output_modalities: Vec::new(),
+        deprecated: false,
+        knowledge_cutoff: None,
     }
 }
 
@@ -125,6 +163,7 @@ fn model_ref(model_id: &str, provider_hint: Option<ProviderId>) -> ModelRef {
     ModelRef {
         provider_hint,
         model_id: model_id.to_string(),
+        requirements: None,
     }
 }
 
@@ -160,6 +199,7 @@ fn test_registry_register_and_lookup() {
             supports_structured_output: true,
             supports_thinking: false,
             supports_remote_discovery: false,
+            supports_parallel_tool_calls: false,
         },
         Vec::new(),
     );
@@ -230,6 +270,220 @@ fn test_routing_precedence_order() {
     assert_eq!(default_wins, ProviderId::Openrouter);
 }
 
+#[test]
+fn test_resolve_route_applies_aliases() {
+    let mut registry = ProviderRegistry::new(ModelCatalog::default(), None);
+    registry.register(Arc::new(adapter_with_models(
+        ProviderId::Anthropic,
+        true,
+        Vec::new(),
+    )));
+    registry.register(Arc::new(adapter_with_models(
+        ProviderId::Openrouter,
+        true,
+        Vec::new(),
+    )));
+    for (provider, model_id) in [
+        (ProviderId::Openai, "claude-sonnet-4-5"),
+        (ProviderId::Anthropic, "claude-sonnet-4-5-20250929"),
+        (ProviderId::Openrouter, "anthropic/claude-sonnet-4.5"),
+    ] {
+        registry.add_alias(ModelAlias {
+            alias: "sonnet".to_string(),
+            provider,
+            model_id: model_id.to_string(),
+        });
+    }
+
+    let mut first_available = model_ref("sonnet", None);
+    let provider = registry
+        .resolve_route(&mut first_available, &AdapterContext::default())
+        .expect("alias should resolve to a registered provider");
+    assert_eq!(provider, ProviderId::Anthropic);
+    assert_eq!(
+        first_available,
+        model_ref("claude-sonnet-4-5-20250929", Some(ProviderId::Anthropic))
+    );
+
+    let mut hinted = model_ref("sonnet", Some(ProviderId::Openrouter));
+    registry
+        .resolve_route(&mut hinted, &AdapterContext::default())
+        .expect("hinted alias should resolve");
+    assert_eq!(
+        hinted,
+        model_ref("anthropic/claude-sonnet-4.5", Some(ProviderId::Openrouter))
+    );
+
+    let mut unaliased = model_ref("other-model", Some(ProviderId::Anthropic));
+    registry
+        .resolve_route(&mut unaliased, &AdapterContext::default())
+        .expect("unaliased model should resolve by hint");
+    assert_eq!(unaliased.model_id, "other-model");
+}
+
+#[test]
+fn test_resolve_route_spreads_alias_by_routing_policy() {
+    let mut registry = ProviderRegistry::new(ModelCatalog::default(), None);
+    for provider in [ProviderId::Anthropic, ProviderId::Openrouter] {
+        registry.register(Arc::new(adapter_with_models(provider, true, Vec::new())));
+    }
+    for (provider, model_id) in [
+        (ProviderId::Openai, "claude-sonnet-4-5"),
+        (ProviderId::Anthropic, "claude-sonnet-4-5-20250929"),
+        (ProviderId::Openrouter, "anthropic/claude-sonnet-4.5"),
+    ] {
+        registry.add_alias(ModelAlias {
+            alias: "sonnet".to_string(),
+            provider,
+            model_id: model_id.to_string(),
+        });
+    }
+    registry.set_routing_policy("sonnet", RoutingPolicy::RoundRobin);
+
+    let context = AdapterContext::default();
+    let providers: Vec<ProviderId> = (0..4)
+        .map(|_| {
+            registry
+                .resolve_route(&mut model_ref("sonnet", None), &context)
+                .expect("alias should resolve")
+        })
+        .collect();
+    assert_eq!(
+        providers,
+        vec![
+            ProviderId::Anthropic,
+            ProviderId::Openrouter,
+            ProviderId::Anthropic,
+            ProviderId::Openrouter,
+        ]
+    );
+
+    let mut hinted = model_ref("sonnet", Some(ProviderId::Openrouter));
+    registry
+        .resolve_route(&mut hinted, &context)
+        .expect("hinted alias should resolve");
+    assert_eq!(
+        hinted,
+        model_ref("anthropic/claude-sonnet-4.5", Some(ProviderId::Openrouter))
+    );
+
+    let session = AdapterContext {
+        metadata: BTreeMap::from([(
+            SESSION_ID_METADATA_KEY.to_string(),
+            "conversation-42".to_string(),
+        )]),
+    };
+    let pinned = registry
+        .resolve_route(&mut model_ref("sonnet", None), &session)
+        .expect("alias should resolve");
+    for _ in 0..5 {
+        assert_eq!(
+            registry.resolve_route(&mut model_ref("sonnet", None), &session),
+            Ok(pinned.clone())
+        );
+    }
+
+    // Only the unregistered OpenAI route carries weight, so the first available route is used.
+    registry.set_routing_policy(
+        "sonnet",
+        RoutingPolicy::Weighted(BTreeMap::from([(ProviderId::Openai, 100)])),
+    );
+    assert_eq!(
+        registry.resolve_route(&mut model_ref("sonnet", None), &context),
+        Ok(ProviderId::Anthropic)
+    );
+}
+
+#[test]
+fn test_resolve_route_selects_fastest_model_meeting_requirements() {
+    let catalog = ModelCatalog {
+        models: vec![
+            model(ProviderId::Openai, "gpt-5-mini", None, Some(400_000), None),
+            model(
+                ProviderId::Anthropic,
+                "claude-haiku-4-5",
+                None,
+                Some(200_000),
+                None,
+            ),
+            model(
+                ProviderId::Openrouter,
+                "openai/gpt-5-nano",
+                None,
+                Some(400_000),
+                None,
+            ),
+        ],
+    };
+    let mut registry = ProviderRegistry::new(catalog, None);
+    for provider in [ProviderId::Openai, ProviderId::Anthropic] {
+        registry.register(Arc::new(adapter_with_models(provider, false, Vec::new())));
+    }
+    registry.record_latency(
+        &ProviderId::Openai,
+        "gpt-5-mini",
+        Duration::from_millis(900),
+    );
+    registry.record_latency(
+        &ProviderId::Anthropic,
+        "claude-haiku-4-5",
+        Duration::from_millis(400),
+    );
+    registry.record_latency(
+        &ProviderId::Anthropic,
+        "claude-haiku-4-5",
+        Duration::from_millis(1400),
+    );
+    assert_eq!(
+        registry.observed_latency_ms(&ProviderId::Anthropic, "claude-haiku-4-5"),
+        Some(600.0)
+    );
+
+    let requirements = ModelRequirements {
+        prefer: ModelPreference::Fastest,
+        ..ModelRequirements::default()
+    };
+    let mut fastest = ModelRef {
+        requirements: Some(requirements.clone()),
+        ..model_ref("", None)
+    };
+    let provider = registry
+        .resolve_route(&mut fastest, &AdapterContext::default())
+        .expect("a registered model should be selected");
+    assert_eq!(provider, ProviderId::Anthropic);
+    assert_eq!(
+        fastest,
+        model_ref("claude-haiku-4-5", Some(ProviderId::Anthropic))
+    );
+
+    let mut large_context = ModelRef {
+        requirements: Some(ModelRequirements {
+            min_context_window: Some(300_000),
+            ..requirements
+        }),
+        ..model_ref("", None)
+    };
+    registry
+        .resolve_route(&mut large_context, &AdapterContext::default())
+        .expect("openai model should be selected");
+    assert_eq!(
+        large_context,
+        model_ref("gpt-5-mini", Some(ProviderId::Openai))
+    );
+
+    let mut unmet = ModelRef {
+        requirements: Some(ModelRequirements {
+            min_context_window: Some(2_000_000),
+            ..ModelRequirements::default()
+        }),
+        ..model_ref("", None)
+    };
+    assert!(matches!(
+        registry.resolve_route(&mut unmet, &AdapterContext::default()),
+        Err(RoutingError::NoModelMatchesRequirements { .. })
+    ));
+}
+
 #[test]
 fn test_ambiguous_model_returns_error() {
     let static_catalog = ModelCatalog {
@@ -421,6 +675,172 @@ async fn test_discover_models_remote_false_returns_cached_catalog() {
     assert_eq!(cached, refreshed);
 }
 
+#[tokio::test]
+async fn test_discover_models_tolerates_partial_provider_failure() {
+    let mut registry = ProviderRegistry::new(ModelCatalog::default(), None);
+    let openai = adapter_with_models(
+        ProviderId::Openai,
+        true,
+        vec![model(ProviderId::Openai, "gpt-5-mini", None, None, None)],
+    );
+    let anthropic = adapter_with_models(
+        ProviderId::Anthropic,
+        true,
+        vec![model(
+            ProviderId::Anthropic,
+            "claude-3-7-sonnet",
+            None,
+            None,
+            None,
+        )],
+    );
+    registry.register(Arc::new(openai.clone()));
+    registry.register(Arc::new(anthropic.clone()));
+
+    registry
+        .discover_models(
+            &discover_opts(true, true, Vec::new()),
+            &AdapterContext::default(),
+        )
+        .await
+        .expect("initial refresh should succeed");
+
+    registry.register(Arc::new(anthropic.clone().failing_discovery("bad key")));
+    let report = registry
+        .discover_models_report(
+            &discover_opts(true, true, Vec::new()),
+            &AdapterContext::default(),
+        )
+        .await;
+
+    assert_eq!(report.queried, 2);
+    assert_eq!(report.failures.len(), 1);
+    assert_eq!(report.failures[0].provider, ProviderId::Anthropic);
+    assert!(matches!(
+        report.failures[0].error,
+        ProviderError::CredentialsRejected { .. }
+    ));
+    assert_eq!(openai.discover_call_count(), 2);
+    assert_eq!(anthropic.discover_call_count(), 2);
+    let warnings = report.warnings();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].code, "discovery.provider_failed");
+    assert!(warnings[0].message.contains("bad key"));
+    let model_ids = report
+        .catalog
+        .models
+        .iter()
+        .map(|model| model.model_id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(model_ids, vec!["gpt-5-mini", "claude-3-7-sonnet"]);
+}
+
+#[tokio::test]
+async fn test_discover_models_errors_when_every_provider_fails() {
+    let mut registry = ProviderRegistry::new(ModelCatalog::default(), None);
+    let openai =
+        adapter_with_models(ProviderId::Openai, true, Vec::new()).failing_discovery("down");
+    registry.register(Arc::new(openai));
+
+    let err = registry
+        .discover_models(
+            &discover_opts(true, true, Vec::new()),
+            &AdapterContext::default(),
+        )
+        .await
+        .expect_err("all providers failing should error");
+
+    assert!(err.to_string().contains("down"));
+}
+
+#[tokio::test]
+async fn test_discover_models_reuses_cache_within_provider_ttl() {
+    let mut registry = ProviderRegistry::new(ModelCatalog::default(), None);
+    let openai = adapter_with_models(
+        ProviderId::Openai,
+        true,
+        vec![model(ProviderId::Openai, "gpt-5-mini", None, None, None)],
+    );
+    let openrouter = adapter_with_models(
+        ProviderId::Openrouter,
+        true,
+        vec![model(
+            ProviderId::Openrouter,
+            "openai/gpt-5",
+            None,
+            None,
+            None,
+        )],
+    );
+    registry.register(Arc::new(openai.clone()));
+    registry.register(Arc::new(openrouter.clone()));
+    registry.set_discovery_ttl(ProviderId::Openai, Duration::from_secs(3_600));
+
+    for _ in 0..2 {
+        let catalog = registry
+            .discover_models(
+                &discover_opts(true, true, Vec::new()),
+                &AdapterContext::default(),
+            )
+            .await
+            .expect("refresh should succeed");
+        assert_eq!(catalog.models.len(), 2);
+    }
+
+    assert_eq!(openai.discover_call_count(), 1);
+    assert_eq!(openrouter.discover_call_count(), 2);
+}
+
+#[tokio::test]
+async fn test_discover_models_times_out_slow_provider() {
+    let mut registry = ProviderRegistry::new(ModelCatalog::default(), None);
+    let openai = adapter_with_models(
+        ProviderId::Openai,
+        true,
+        vec![model(ProviderId::Openai, "gpt-5-mini", None, None, None)],
+    );
+    let anthropic = adapter_with_models(
+        ProviderId::Anthropic,
+        true,
+        vec![model(
+            ProviderId::Anthropic,
+            "claude-3-7-sonnet",
+            None,
+            None,
+            None,
+        )],
+    )
+    .slow_discovery(Duration::from_secs(30));
+    registry.register(Arc::new(openai));
+    registry.register(Arc::new(anthropic));
+    registry.set_discovery_timeout(Duration::from_millis(50));
+
+    let started = std::time::Instant::now();
+    let report = registry
+        .discover_models_report(
+            &discover_opts(true, true, Vec::new()),
+            &AdapterContext::default(),
+        )
+        .await;
+
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(report.failures.len(), 1);
+    assert_eq!(report.failures[0].provider, ProviderId::Anthropic);
+    assert!(matches!(
+        report.failures[0].error,
+        ProviderError::Timeout { .. }
+    ));
+    assert_eq!(
+        report
+            .catalog
+            .models
+            .iter()
+            .map(|model| model.model_id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["gpt-5-mini"]
+    );
+}
+
 #[test]
 fn test_resolve_provider_default_requires_registered_adapter() {
     let registry = ProviderRegistry::new(ModelCatalog::default(), Some(ProviderId::Openrouter));
@@ -475,13 +895,18 @@ async fn test_mock_adapter_run_smoke() {
         model: model_ref("gpt-5-mini", Some(ProviderId::Openai)),
         messages: Vec::new(),
         tools: Vec::new(),
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format: Default::default(),
         temperature: None,
         top_p: None,
         max_output_tokens: None,
         stop: Vec::new(),
         metadata: Default::default(),
+        n: None,
+        seed: None,
+        provider_options: Default::default(),
     };
     let response = adapter
         .run(&request, &AdapterContext::default())
@@ -490,3 +915,78 @@ async fn test_mock_adapter_run_smoke() {
     assert_eq!(response.provider, ProviderId::Openai);
     assert_eq!(response.model, "gpt-5-mini");
 }
+
+#[tokio::test]
+async fn test_unhealthy_provider_is_excluded_from_routing() {
+    let mut registry = ProviderRegistry::new(ModelCatalog::default(), None);
+    registry.register(Arc::new(
+        adapter_with_models(ProviderId::Anthropic, true, Vec::new()).failing_discovery("bad key"),
+    ));
+    registry.register(Arc::new(adapter_with_models(
+        ProviderId::Openrouter,
+        true,
+        Vec::new(),
+    )));
+    registry.set_unhealthy_threshold(2);
+    for (provider, model_id) in [
+        (ProviderId::Anthropic, "claude-sonnet-4-5-20250929"),
+        (ProviderId::Openrouter, "anthropic/claude-sonnet-4.5"),
+    ] {
+        registry.add_alias(ModelAlias {
+            alias: "sonnet".to_string(),
+            provider,
+            model_id: model_id.to_string(),
+        });
+    }
+    let context = AdapterContext::default();
+
+    let status = registry.check_health(&context).await;
+    assert_eq!(status.len(), 2);
+    assert!(status.iter().all(|health| health.healthy));
+    assert_eq!(status[0].consecutive_failures, 1);
+
+    let status = registry.check_health(&context).await;
+    assert_eq!(status[0].provider, ProviderId::Anthropic);
+    assert!(!status[0].healthy);
+    assert!(matches!(
+        status[0].last_error,
+        Some(ProviderError::CredentialsRejected { .. })
+    ));
+    assert!(status[1].healthy);
+    assert!(!registry.is_healthy(&ProviderId::Anthropic));
+
+    assert_eq!(
+        registry.resolve_route(&mut model_ref("sonnet", None), &context),
+        Ok(ProviderId::Openrouter)
+    );
+    assert_eq!(
+        registry.resolve_route(
+            &mut model_ref("claude-sonnet-4-5", Some(ProviderId::Anthropic)),
+            &context
+        ),
+        Err(RoutingError::ProviderUnhealthy {
+            provider: ProviderId::Anthropic,
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_spawned_health_checks_run_periodically() {
+    let adapter = adapter_with_models(ProviderId::Openai, true, Vec::new());
+    let mut registry = ProviderRegistry::new(ModelCatalog::default(), None);
+    registry.register(Arc::new(adapter.clone()));
+
+    let handle = registry.spawn_health_checks(Duration::from_millis(5), AdapterContext::default());
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while adapter.discover_call_count() < 3 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("health checks should repeat");
+    handle.abort();
+
+    let status = registry.health_status();
+    assert!(status[0].healthy);
+    assert!(status[0].last_checked.is_some());
+}
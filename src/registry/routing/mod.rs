@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+
+use crate::core::types::{AdapterContext, ModelAlias, ProviderId};
+
+/// Adapter context metadata key naming the session whose requests should keep the same route.
+pub const SESSION_ID_METADATA_KEY: &str = "session_id";
+
+/// How requests for an alias served by several providers are spread across its routes. Without
+/// a policy the first registered route wins.
+///
+/// When the adapter context carries a [`SESSION_ID_METADATA_KEY`], every request in that
+/// session takes the same route for as long as the set of available routes is unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoutingPolicy {
+    /// Each available route in turn, in registration order.
+    RoundRobin,
+    /// Routes in proportion to their provider's weight; providers without a weight get none
+    /// unless no weighted route is available. For example `{Anthropic: 70, Openrouter: 30}`
+    /// sends 7 of every 10 requests to Anthropic.
+    Weighted(BTreeMap<ProviderId, u32>),
+}
+
+impl RoutingPolicy {
+    /// Picks one of `routes` for the `turn`th request, or for the session in `context`.
+    pub(crate) fn choose<'a>(
+        &self,
+        routes: &[&'a ModelAlias],
+        turn: u64,
+        context: &AdapterContext,
+    ) -> Option<&'a ModelAlias> {
+        let ticket = context
+            .metadata
+            .get(SESSION_ID_METADATA_KEY)
+            .map_or(turn, |session_id| stable_hash(session_id));
+
+        match self {
+            RoutingPolicy::RoundRobin => {
+                let count = routes.len() as u64;
+                (count > 0).then(|| routes[(ticket % count) as usize])
+            }
+            RoutingPolicy::Weighted(weights) => {
+                let weight = |route: &ModelAlias| {
+                    u64::from(weights.get(&route.provider).copied().unwrap_or(0))
+                };
+                let total: u64 = routes.iter().map(|route| weight(route)).sum();
+                if total == 0 {
+                    return None;
+                }
+
+                let mut position = ticket % total;
+                routes.iter().copied().find(|route| {
+                    let weight = weight(route);
+                    if position < weight {
+                        return true;
+                    }
+                    position -= weight;
+                    false
+                })
+            }
+        }
+    }
+}
+
+/// FNV-1a, which unlike `DefaultHasher` is stable across processes and Rust releases.
+fn stable_hash(value: &str) -> u64 {
+    value.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+use super::*;
+
+fn route(provider: ProviderId, model_id: &str) -> ModelAlias {
+    ModelAlias {
+        alias: "sonnet".to_string(),
+        provider,
+        model_id: model_id.to_string(),
+    }
+}
+
+fn session(session_id: &str) -> AdapterContext {
+    AdapterContext {
+        metadata: BTreeMap::from([(SESSION_ID_METADATA_KEY.to_string(), session_id.to_string())]),
+    }
+}
+
+#[test]
+fn test_round_robin_cycles_through_routes() {
+    let anthropic = route(ProviderId::Anthropic, "claude-sonnet-4-5");
+    let openrouter = route(ProviderId::Openrouter, "anthropic/claude-sonnet-4.5");
+    let routes = [&anthropic, &openrouter];
+    let context = AdapterContext::default();
+
+    let chosen: Vec<ProviderId> = (0..4)
+        .map(|turn| {
+            RoutingPolicy::RoundRobin
+                .choose(&routes, turn, &context)
+                .expect("a route should be chosen")
+                .provider
+                .clone()
+        })
+        .collect();
+    assert_eq!(
+        chosen,
+        vec![
+            ProviderId::Anthropic,
+            ProviderId::Openrouter,
+            ProviderId::Anthropic,
+            ProviderId::Openrouter,
+        ]
+    );
+    assert_eq!(RoutingPolicy::RoundRobin.choose(&[], 0, &context), None);
+}
+
+#[test]
+fn test_weighted_routes_in_proportion() {
+    let anthropic = route(ProviderId::Anthropic, "claude-sonnet-4-5");
+    let openrouter = route(ProviderId::Openrouter, "anthropic/claude-sonnet-4.5");
+    let openai = route(ProviderId::Openai, "unweighted");
+    let routes = [&anthropic, &openrouter, &openai];
+    let policy = RoutingPolicy::Weighted(BTreeMap::from([
+        (ProviderId::Anthropic, 70),
+        (ProviderId::Openrouter, 30),
+    ]));
+    let context = AdapterContext::default();
+
+    let mut counts = BTreeMap::new();
+    for turn in 0..100 {
+        let chosen = policy
+            .choose(&routes, turn, &context)
+            .expect("a route should be chosen");
+        *counts.entry(chosen.provider.clone()).or_insert(0) += 1;
+    }
+    assert_eq!(
+        counts,
+        BTreeMap::from([(ProviderId::Anthropic, 70), (ProviderId::Openrouter, 30)])
+    );
+
+    assert_eq!(policy.choose(&[&openai], 0, &context), None);
+}
+
+#[test]
+fn test_session_id_pins_route_across_turns() {
+    let anthropic = route(ProviderId::Anthropic, "claude-sonnet-4-5");
+    let openrouter = route(ProviderId::Openrouter, "anthropic/claude-sonnet-4.5");
+    let routes = [&anthropic, &openrouter];
+    let policy = RoutingPolicy::Weighted(BTreeMap::from([
+        (ProviderId::Anthropic, 1),
+        (ProviderId::Openrouter, 1),
+    ]));
+
+    for session_id in ["session-a", "session-b", "session-c"] {
+        let context = session(session_id);
+        let first = policy.choose(&routes, 0, &context);
+        for turn in 1..10 {
+            assert_eq!(policy.choose(&routes, turn, &context), first);
+        }
+    }
+    assert_eq!(stable_hash("session-a"), stable_hash("session-a"));
+    assert_ne!(stable_hash("session-a"), stable_hash("session-b"));
+}
@@ -1,5 +1,5 @@
 use crate::core::types::ModelCatalog;
-use crate::registry::registry::ProviderRegistry;
+use crate::registry::ProviderRegistry;
 
 #[test]
 fn test_registry_exports_compile() {
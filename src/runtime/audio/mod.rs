@@ -0,0 +1,60 @@
+//! Speech synthesis and transcription through the runtime's configured adapters and
+//! credentials. Audio calls share the adapters' transport, so retries, rate limiting, and error
+//! normalization apply to them as they do to `ProviderRuntime::run`.
+
+use crate::core::error::RuntimeError;
+use crate::core::types::{
+    AudioSpeechRequest, AudioSpeechResponse, AudioTranscriptionRequest, AudioTranscriptionResponse,
+};
+use crate::runtime::ProviderRuntime;
+
+impl ProviderRuntime {
+    /// Synthesizes `request.input` as audio with the provider resolved for `request.model`.
+    pub async fn synthesize_speech(
+        &self,
+        mut request: AudioSpeechRequest,
+    ) -> Result<AudioSpeechResponse, RuntimeError> {
+        let provider = self
+            .registry
+            .resolve_route(&mut request.model, &self.call_context())?;
+        let adapter = self.registry.resolve_adapter(&provider)?;
+        let (ctx, lease) = self
+            .registry
+            .lease_credential(&provider, &self.call_context())
+            .await?;
+        let result = adapter.synthesize_speech(&request, &ctx).await;
+        self.registry
+            .report_credential(&provider, lease.as_ref(), result.as_ref().err());
+        result?.ok_or_else(|| RuntimeError::CapabilityMismatch {
+            provider,
+            model: request.model.model_id,
+            capability: "speech".to_string(),
+        })
+    }
+
+    /// Transcribes `request.audio` with the provider resolved for `request.model`.
+    pub async fn transcribe(
+        &self,
+        mut request: AudioTranscriptionRequest,
+    ) -> Result<AudioTranscriptionResponse, RuntimeError> {
+        let provider = self
+            .registry
+            .resolve_route(&mut request.model, &self.call_context())?;
+        let adapter = self.registry.resolve_adapter(&provider)?;
+        let (ctx, lease) = self
+            .registry
+            .lease_credential(&provider, &self.call_context())
+            .await?;
+        let result = adapter.transcribe(&request, &ctx).await;
+        self.registry
+            .report_credential(&provider, lease.as_ref(), result.as_ref().err());
+        result?.ok_or_else(|| RuntimeError::CapabilityMismatch {
+            provider,
+            model: request.model.model_id,
+            capability: "transcription".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
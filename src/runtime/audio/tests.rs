@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use crate::core::error::RuntimeError;
+use crate::core::types::{AudioSpeechRequest, AudioTranscriptionRequest, ModelRef, ProviderId};
+use crate::providers::mock::MockAdapter;
+use crate::runtime::ProviderRuntime;
+
+fn model() -> ModelRef {
+    ModelRef {
+        provider_hint: Some(ProviderId::Openai),
+        model_id: "gpt-4o-mini-tts".to_string(),
+        requirements: None,
+    }
+}
+
+#[tokio::test]
+async fn test_audio_without_adapter_support_is_capability_mismatch() {
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(MockAdapter::new(ProviderId::Openai)))
+        .build();
+
+    let speech_error = runtime
+        .synthesize_speech(AudioSpeechRequest {
+            model: model(),
+            input: "hello".to_string(),
+            voice: "alloy".to_string(),
+            format: None,
+            speed: None,
+            instructions: None,
+        })
+        .await
+        .expect_err("mock adapter has no speech");
+    let transcription_error = runtime
+        .transcribe(AudioTranscriptionRequest {
+            model: model(),
+            audio: vec![0, 1, 2],
+            filename: "clip.wav".to_string(),
+            media_type: None,
+            language: None,
+            prompt: None,
+            temperature: None,
+        })
+        .await
+        .expect_err("mock adapter has no transcription");
+
+    assert!(matches!(
+        speech_error,
+        RuntimeError::CapabilityMismatch { ref capability, .. } if capability == "speech"
+    ));
+    assert!(matches!(
+        transcription_error,
+        RuntimeError::CapabilityMismatch { ref capability, .. } if capability == "transcription"
+    ));
+}
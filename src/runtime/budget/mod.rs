@@ -0,0 +1,118 @@
+//! Usage accounting and spend caps per caller-supplied accounting key.
+//!
+//! The key is read from an `AdapterContext.metadata` entry, so a tenant id or API key label set
+//! on the builder, through `run_with_context`, or through `RequestContext` is enough to attribute
+//! usage. Request metadata is never consulted: it is caller-controlled (the OpenAI-compatible
+//! server forwards client `metadata` unchanged) and would let callers pick their own key.
+//! Requests without the key are not tracked.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use crate::core::error::RuntimeError;
+use crate::core::types::{AdapterContext, ProviderResponse};
+
+/// Accumulated usage for one accounting key.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsageTotals {
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cached_input_tokens: u64,
+    /// Sum of `CostBreakdown::total_cost` over responses that carried a cost.
+    pub total_cost: f64,
+    /// Successful responses without a cost, which `total_cost` therefore undercounts.
+    pub unpriced_responses: u64,
+}
+
+/// Tracks usage per accounting key and rejects requests for keys past their spend cap.
+///
+/// Caps are checked before a request is dispatched against the spend recorded so far, so the
+/// request that crosses a cap still completes; later ones fail with
+/// `RuntimeError::BudgetExceeded`. Costs are summed as reported, without currency conversion.
+#[derive(Debug)]
+pub struct BudgetTracker {
+    metadata_key: String,
+    spend_caps: BTreeMap<String, f64>,
+    default_spend_cap: Option<f64>,
+    totals: Mutex<BTreeMap<String, UsageTotals>>,
+}
+
+impl BudgetTracker {
+    /// Tracks usage keyed by the value of the `metadata_key` adapter context metadata entry.
+    pub fn new(metadata_key: impl Into<String>) -> Self {
+        Self {
+            metadata_key: metadata_key.into(),
+            spend_caps: BTreeMap::new(),
+            default_spend_cap: None,
+            totals: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn with_spend_cap(mut self, key: impl Into<String>, cap: f64) -> Self {
+        self.spend_caps.insert(key.into(), cap);
+        self
+    }
+
+    /// Cap applied to keys without their own cap.
+    pub fn with_default_spend_cap(mut self, cap: f64) -> Self {
+        self.default_spend_cap = Some(cap);
+        self
+    }
+
+    pub fn totals(&self, key: &str) -> Option<UsageTotals> {
+        self.lock().get(key).cloned()
+    }
+
+    pub fn all_totals(&self) -> BTreeMap<String, UsageTotals> {
+        self.lock().clone()
+    }
+
+    /// Clears the totals for `key`, e.g. at the start of a billing period.
+    pub fn reset(&self, key: &str) {
+        self.lock().remove(key);
+    }
+
+    pub(crate) fn accounting_key(&self, adapter_context: &AdapterContext) -> Option<String> {
+        adapter_context.metadata.get(&self.metadata_key).cloned()
+    }
+
+    pub(crate) fn check(&self, key: &str) -> Result<(), RuntimeError> {
+        let Some(cap) = self.spend_caps.get(key).copied().or(self.default_spend_cap) else {
+            return Ok(());
+        };
+
+        let spent = self.lock().get(key).map_or(0.0, |totals| totals.total_cost);
+        if spent >= cap {
+            return Err(RuntimeError::BudgetExceeded {
+                key: key.to_string(),
+                message: format!("spent {spent} of the {cap} spend cap"),
+            });
+        }
+        Ok(())
+    }
+
+    pub(crate) fn record(&self, key: &str, response: &ProviderResponse) {
+        let mut totals = self.lock();
+        let totals = totals.entry(key.to_string()).or_default();
+        let usage = &response.usage;
+
+        totals.requests += 1;
+        totals.input_tokens += usage.input_tokens.unwrap_or(0);
+        totals.output_tokens += usage.output_tokens.unwrap_or(0);
+        totals.cached_input_tokens += usage.cached_input_tokens.unwrap_or(0);
+        match &response.cost {
+            Some(cost) => totals.total_cost += cost.total_cost,
+            None => totals.unpriced_responses += 1,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, UsageTotals>> {
+        self.totals
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests;
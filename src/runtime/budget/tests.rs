@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use super::{BudgetTracker, UsageTotals};
+use crate::core::error::RuntimeError;
+use crate::core::types::{
+    AdapterContext, AssistantOutput, ContentPart, CostBreakdown, FinishReason, Message,
+    MessageRole, ModelRef, PricingSource, ProviderId, ProviderRequest, ProviderResponse, Usage,
+};
+use crate::providers::mock::{MockAdapter, MockMatcher, MockOutcome};
+use crate::runtime::ProviderRuntime;
+use crate::runtime::context::RequestContext;
+
+fn request(tenant: Option<&str>) -> ProviderRequest {
+    ProviderRequest {
+        metadata: tenant
+            .map(|tenant| BTreeMap::from([("tenant".to_string(), tenant.to_string())]))
+            .unwrap_or_default(),
+        ..ProviderRequest::new(
+            ModelRef {
+                provider_hint: Some(ProviderId::Openai),
+                model_id: "gpt-5-mini".to_string(),
+                requirements: None,
+            },
+            vec![Message {
+                role: MessageRole::User,
+                content: vec![ContentPart::Text {
+                    text: "hello".to_string(),
+                }],
+                cache_hint: None,
+            }],
+        )
+    }
+}
+
+fn tenant_context(tenant: &str) -> AdapterContext {
+    let mut ctx = AdapterContext::default();
+    ctx.metadata
+        .insert("tenant".to_string(), tenant.to_string());
+    ctx
+}
+
+fn priced_response(total_cost: f64) -> ProviderResponse {
+    ProviderResponse {
+        output: AssistantOutput {
+            content: vec![ContentPart::Text {
+                text: "hi".to_string(),
+            }],
+            structured_output: None,
+            annotations: Vec::new(),
+        },
+        usage: Usage {
+            input_tokens: Some(100),
+            output_tokens: Some(20),
+            ..Usage::default()
+        },
+        cost: Some(CostBreakdown {
+            currency: "USD".to_string(),
+            input_cost: total_cost / 2.0,
+            cached_input_cost: 0.0,
+            output_cost: total_cost / 2.0,
+            fixed_cost: 0.0,
+            total_cost,
+            pricing_source: PricingSource::Configured,
+        }),
+        provider: ProviderId::Openai,
+        model: "gpt-5-mini".to_string(),
+        raw_provider_response: None,
+        request_id: None,
+        rate_limit: None,
+        finish_reason: FinishReason::Stop,
+        warnings: Vec::new(),
+        alternatives: Vec::new(),
+        response_id: None,
+    }
+}
+
+fn runtime(budget: Arc<BudgetTracker>) -> (ProviderRuntime, Arc<MockAdapter>) {
+    let adapter = Arc::new(MockAdapter::new(ProviderId::Openai).on(
+        MockMatcher::any(),
+        MockOutcome::response(priced_response(0.75)),
+    ));
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_budget(budget)
+        .build();
+    (runtime, adapter)
+}
+
+#[tokio::test]
+async fn test_budget_accumulates_usage_and_rejects_after_cap() {
+    let budget = Arc::new(BudgetTracker::new("tenant").with_spend_cap("acme", 1.0));
+    let (runtime, adapter) = runtime(budget.clone());
+
+    let run = || runtime.run_with_context(request(None), tenant_context("acme"));
+
+    run().await.expect("under cap");
+    run().await.expect("still under cap before dispatch");
+    let err = run().await.expect_err("cap reached");
+
+    assert!(matches!(err, RuntimeError::BudgetExceeded { ref key, .. } if key == "acme"));
+    assert_eq!(adapter.requests().len(), 2);
+    assert_eq!(
+        budget.totals("acme"),
+        Some(UsageTotals {
+            requests: 2,
+            input_tokens: 200,
+            output_tokens: 40,
+            cached_input_tokens: 0,
+            total_cost: 1.5,
+            unpriced_responses: 0,
+        })
+    );
+
+    budget.reset("acme");
+    run().await.expect("reset clears spend");
+}
+
+#[tokio::test]
+async fn test_budget_reads_key_from_request_context_and_skips_untracked() {
+    let budget = Arc::new(BudgetTracker::new("tenant").with_default_spend_cap(0.5));
+    let (runtime, _adapter) = runtime(budget.clone());
+
+    runtime.run(request(None)).await.expect("untracked request");
+    runtime.run(request(None)).await.expect("untracked request");
+    assert!(budget.all_totals().is_empty());
+
+    let scoped = || RequestContext::new().with_tenant("beta");
+    scoped()
+        .scope(runtime.run(request(None)))
+        .await
+        .expect("first request under default cap");
+    scoped()
+        .scope(runtime.run(request(None)))
+        .await
+        .expect_err("default cap reached");
+    assert_eq!(budget.totals("beta").map(|totals| totals.requests), Some(1));
+}
+
+#[tokio::test]
+async fn test_budget_ignores_accounting_key_in_request_metadata() {
+    let budget = Arc::new(
+        BudgetTracker::new("tenant")
+            .with_spend_cap("acme", 0.5)
+            .with_spend_cap("unlimited", 1_000.0),
+    );
+    let (runtime, adapter) = runtime(budget.clone());
+
+    runtime
+        .run(request(Some("unlimited")))
+        .await
+        .expect("request metadata alone is untracked");
+    assert!(budget.all_totals().is_empty());
+
+    runtime
+        .run_with_context(request(Some("unlimited")), tenant_context("acme"))
+        .await
+        .expect("first request under cap");
+    let err = runtime
+        .run_with_context(request(Some("unlimited")), tenant_context("acme"))
+        .await
+        .expect_err("request metadata must not escape the context key's cap");
+
+    assert!(matches!(err, RuntimeError::BudgetExceeded { ref key, .. } if key == "acme"));
+    assert_eq!(budget.totals("unlimited"), None);
+    assert_eq!(adapter.requests().len(), 2);
+}
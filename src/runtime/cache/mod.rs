@@ -0,0 +1,221 @@
+//! Response caching for repeated identical requests.
+//!
+//! `ResponseCache` stores responses and `InFlightDedup` lets concurrent identical misses wait for
+//! one caller instead of each reaching the provider. Both are async so implementations can live
+//! outside the process; the `redis` feature provides Redis-backed ones shared across instances.
+
+use std::collections::hash_map::RandomState;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::hash::BuildHasher;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use async_trait::async_trait;
+use indexmap::IndexMap;
+use sha2::{Digest, Sha256};
+
+use crate::core::types::{AdapterContext, ProviderRequest, ProviderResponse};
+use crate::runtime::context::TRACE_ID_METADATA_KEY;
+
+/// Storage for cached responses, keyed by [`cache_key`].
+///
+/// Implementations decide eviction; `get` returning `None` is always a valid answer, so backends
+/// that fail to reach their store should report a miss rather than an error.
+#[async_trait]
+pub trait ResponseCache: Send + Sync {
+    async fn get(&self, key: &str) -> Option<ProviderResponse>;
+    async fn put(&self, key: String, response: ProviderResponse);
+}
+
+/// Claims on cache keys whose request is currently being run.
+///
+/// On a cache miss the runtime claims the key before calling the provider and releases it once
+/// the response is stored. Callers that fail to claim poll the cache until the holder releases,
+/// then claim and run themselves if the holder stored nothing.
+#[async_trait]
+pub trait InFlightDedup: Send + Sync {
+    /// Claims `key` for at most `ttl` and returns a token identifying this claim; `None` when
+    /// another caller holds an unexpired claim. The `ttl` bounds how long a caller that never
+    /// releases, such as a crashed process, blocks others.
+    async fn claim(&self, key: &str, ttl: Duration) -> Option<String>;
+
+    /// Releases the claim on `key` made with `token`. A claim that expired and was taken by
+    /// another caller must be left in place.
+    async fn release(&self, key: &str, token: &str);
+}
+
+/// Which requests `ProviderRuntime::run` answers from the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CachePolicy {
+    /// Only requests with `temperature` set to exactly `0.0`.
+    #[default]
+    DeterministicOnly,
+    /// Every request; for evaluation runs that accept replaying sampled output.
+    All,
+}
+
+impl CachePolicy {
+    pub(crate) fn allows(self, request: &ProviderRequest) -> bool {
+        match self {
+            Self::DeterministicOnly => request.temperature == Some(0.0),
+            Self::All => true,
+        }
+    }
+}
+
+/// SHA-256 of the canonical request JSON together with the adapter context the call runs with.
+///
+/// Request `metadata` is ignored, as is the context's `trace_id`, so per-call trace ids do not
+/// defeat the cache. Every other context entry is part of the key: callers with different
+/// credentials, org or project overrides, tenants, or budget accounting keys never share an
+/// entry, so one tenant's cache hit cannot skip another tenant's budget.
+///
+/// The key is stable across processes and Rust releases, so backends may persist it. `None` when
+/// the request does not serialize, in which case the runtime bypasses the cache rather than
+/// sharing a key between unrelated requests.
+pub fn cache_key(request: &ProviderRequest, adapter_context: &AdapterContext) -> Option<String> {
+    let mut canonical = request.clone();
+    canonical.metadata = BTreeMap::new();
+    let context: BTreeMap<&str, &str> = adapter_context
+        .metadata
+        .iter()
+        .filter(|(key, _)| key.as_str() != TRACE_ID_METADATA_KEY)
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect();
+    let encoded = serde_json::to_vec(&(&canonical, &context)).ok()?;
+
+    let digest = Sha256::digest(&encoded);
+    let mut key = String::with_capacity(digest.len() * 2);
+    for byte in digest {
+        let _ = write!(key, "{byte:02x}");
+    }
+    Some(key)
+}
+
+/// In-memory LRU `ResponseCache` with an entry limit and optional time-to-live.
+#[derive(Debug)]
+pub struct InMemoryResponseCache {
+    max_entries: usize,
+    ttl: Option<Duration>,
+    entries: Mutex<IndexMap<String, (Instant, ProviderResponse)>>,
+}
+
+impl InMemoryResponseCache {
+    /// Keeps at most `max_entries` responses, evicting the least recently used.
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            ttl: None,
+            entries: Mutex::new(IndexMap::new()),
+        }
+    }
+
+    /// Treats entries older than `ttl` as missing.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, IndexMap<String, (Instant, ProviderResponse)>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl ResponseCache for InMemoryResponseCache {
+    async fn get(&self, key: &str) -> Option<ProviderResponse> {
+        let mut entries = self.lock();
+        let (stored_at, response) = entries.shift_remove(key)?;
+        if self.ttl.is_some_and(|ttl| stored_at.elapsed() > ttl) {
+            return None;
+        }
+
+        entries.insert(key.to_string(), (stored_at, response.clone()));
+        Some(response)
+    }
+
+    async fn put(&self, key: String, response: ProviderResponse) {
+        if self.max_entries == 0 {
+            return;
+        }
+
+        let mut entries = self.lock();
+        entries.shift_remove(&key);
+        while entries.len() >= self.max_entries {
+            entries.shift_remove_index(0);
+        }
+        entries.insert(key, (Instant::now(), response));
+    }
+}
+
+/// A random 128-bit claim token, seeded from std's per-process random keys, so claims from
+/// different runtime instances do not collide.
+pub(crate) fn claim_token(key: &str) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    format!(
+        "{:016x}{:016x}",
+        RandomState::new().hash_one(key),
+        RandomState::new().hash_one(nanos)
+    )
+}
+
+/// In-process `InFlightDedup`; dedups concurrent requests within one `ProviderRuntime`.
+#[derive(Debug, Default)]
+pub struct InMemoryDedup {
+    claims: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl InMemoryDedup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (String, Instant)>> {
+        self.claims
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl InFlightDedup for InMemoryDedup {
+    async fn claim(&self, key: &str, ttl: Duration) -> Option<String> {
+        let mut claims = self.lock();
+        let now = Instant::now();
+        claims.retain(|_, (_, expires_at)| *expires_at > now);
+        if claims.contains_key(key) {
+            return None;
+        }
+        let token = claim_token(key);
+        claims.insert(key.to_string(), (token.clone(), now + ttl));
+        Some(token)
+    }
+
+    async fn release(&self, key: &str, token: &str) {
+        let mut claims = self.lock();
+        if claims.get(key).is_some_and(|(holder, _)| holder == token) {
+            claims.remove(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,264 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{
+    CachePolicy, InFlightDedup, InMemoryDedup, InMemoryResponseCache, ResponseCache, cache_key,
+};
+use crate::core::types::{
+    AdapterContext, AssistantOutput, ContentPart, FinishReason, Message, MessageRole, ModelRef,
+    ProviderId, ProviderRequest, ProviderResponse, Usage,
+};
+use crate::providers::mock::{MockAdapter, MockMatcher, MockOutcome};
+use crate::runtime::ProviderRuntime;
+
+fn request(prompt: &str, temperature: Option<f32>) -> ProviderRequest {
+    ProviderRequest {
+        temperature,
+        ..ProviderRequest::new(
+            ModelRef {
+                provider_hint: Some(ProviderId::Openai),
+                model_id: "gpt-5-mini".to_string(),
+                requirements: None,
+            },
+            vec![Message {
+                role: MessageRole::User,
+                content: vec![ContentPart::Text {
+                    text: prompt.to_string(),
+                }],
+                cache_hint: None,
+            }],
+        )
+    }
+}
+
+fn response(text: &str) -> ProviderResponse {
+    ProviderResponse {
+        output: AssistantOutput {
+            content: vec![ContentPart::Text {
+                text: text.to_string(),
+            }],
+            structured_output: None,
+            annotations: Vec::new(),
+        },
+        usage: Usage::default(),
+        cost: None,
+        provider: ProviderId::Openai,
+        model: "gpt-5-mini".to_string(),
+        raw_provider_response: None,
+        request_id: None,
+        rate_limit: None,
+        finish_reason: FinishReason::Stop,
+        warnings: Vec::new(),
+        alternatives: Vec::new(),
+        response_id: None,
+    }
+}
+
+#[tokio::test]
+async fn test_runtime_serves_repeated_deterministic_requests_from_cache() {
+    let adapter = Arc::new(
+        MockAdapter::new(ProviderId::Openai).on(MockMatcher::any(), MockOutcome::text("answer")),
+    );
+    let cache = Arc::new(InMemoryResponseCache::new(8));
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_response_cache(cache.clone(), CachePolicy::DeterministicOnly)
+        .build();
+
+    let first = runtime
+        .run(request("hello", Some(0.0)))
+        .await
+        .expect("first run");
+    assert!(first.warnings.is_empty());
+
+    let mut traced = request("hello", Some(0.0));
+    traced
+        .metadata
+        .insert("trace_id".to_string(), "t-2".to_string());
+    let second = runtime.run(traced).await.expect("cached run");
+    assert_eq!(second.output, first.output);
+    assert_eq!(second.warnings.len(), 1);
+    assert_eq!(second.warnings[0].code, "runtime.cache_hit");
+
+    runtime
+        .run(request("hello", Some(0.7)))
+        .await
+        .expect("sampled request bypasses cache");
+    assert_eq!(adapter.requests().len(), 2);
+    assert_eq!(cache.len(), 1);
+}
+
+#[tokio::test]
+async fn test_runtime_keeps_cache_entries_per_adapter_context() {
+    let adapter = Arc::new(
+        MockAdapter::new(ProviderId::Openai).on(MockMatcher::any(), MockOutcome::text("answer")),
+    );
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_response_cache(
+            Arc::new(InMemoryResponseCache::new(8)),
+            CachePolicy::DeterministicOnly,
+        )
+        .build();
+    let context = |tenant: &str, trace_id: &str| AdapterContext {
+        metadata: [
+            ("tenant".to_string(), tenant.to_string()),
+            ("openai.api_key".to_string(), format!("{tenant}-key")),
+            ("trace_id".to_string(), trace_id.to_string()),
+        ]
+        .into(),
+    };
+
+    runtime
+        .run_with_context(request("hello", Some(0.0)), context("acme", "t-1"))
+        .await
+        .expect("first tenant");
+    let other_tenant = runtime
+        .run_with_context(request("hello", Some(0.0)), context("globex", "t-2"))
+        .await
+        .expect("second tenant");
+    assert!(other_tenant.warnings.is_empty());
+
+    let retraced = runtime
+        .run_with_context(request("hello", Some(0.0)), context("acme", "t-3"))
+        .await
+        .expect("first tenant again");
+    assert_eq!(retraced.warnings[0].code, "runtime.cache_hit");
+    assert_eq!(adapter.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_in_memory_cache_evicts_least_recently_used_and_expires() {
+    let cache = InMemoryResponseCache::new(2);
+    cache.put("a".to_string(), response("a")).await;
+    cache.put("b".to_string(), response("b")).await;
+    assert!(cache.get("a").await.is_some());
+    cache.put("c".to_string(), response("c")).await;
+
+    assert!(cache.get("b").await.is_none());
+    assert!(cache.get("a").await.is_some());
+    assert!(cache.get("c").await.is_some());
+
+    let expiring = InMemoryResponseCache::new(2).with_ttl(Duration::ZERO);
+    expiring.put("a".to_string(), response("a")).await;
+    std::thread::sleep(Duration::from_millis(2));
+    assert!(expiring.get("a").await.is_none());
+    assert!(expiring.is_empty());
+}
+
+#[tokio::test]
+async fn test_dedup_waiter_is_served_by_the_claim_holder() {
+    let adapter = Arc::new(
+        MockAdapter::new(ProviderId::Openai).on(MockMatcher::any(), MockOutcome::text("answer")),
+    );
+    let cache = Arc::new(InMemoryResponseCache::new(8));
+    let dedup = Arc::new(InMemoryDedup::new());
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_response_cache(cache.clone(), CachePolicy::DeterministicOnly)
+        .with_request_dedup(dedup.clone(), Duration::from_secs(5))
+        .build();
+    let key = cache_key(&request("hello", Some(0.0)), &AdapterContext::default())
+        .expect("request should serialize");
+    let token = dedup
+        .claim(&key, Duration::from_secs(5))
+        .await
+        .expect("key should be unclaimed");
+
+    let waiter = tokio::spawn(async move { runtime.run(request("hello", Some(0.0))).await });
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    cache.put(key.clone(), response("from holder")).await;
+    dedup.release(&key, &token).await;
+
+    let served = waiter.await.expect("task").expect("waiter run");
+    assert_eq!(served.output, response("from holder").output);
+    assert_eq!(served.warnings[0].code, "runtime.cache_hit");
+    assert!(adapter.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_dedup_waiter_runs_itself_after_max_wait() {
+    let adapter = Arc::new(
+        MockAdapter::new(ProviderId::Openai).on(MockMatcher::any(), MockOutcome::text("answer")),
+    );
+    let dedup = Arc::new(InMemoryDedup::new());
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_response_cache(
+            Arc::new(InMemoryResponseCache::new(8)),
+            CachePolicy::DeterministicOnly,
+        )
+        .with_request_dedup(dedup.clone(), Duration::from_millis(50))
+        .build();
+    let key = cache_key(&request("hello", Some(0.0)), &AdapterContext::default())
+        .expect("request should serialize");
+    assert!(dedup.claim(&key, Duration::from_secs(5)).await.is_some());
+
+    let response = runtime
+        .run(request("hello", Some(0.0)))
+        .await
+        .expect("run after giving up on the holder");
+
+    assert!(response.warnings.is_empty());
+    assert_eq!(adapter.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_in_memory_dedup_claims_expire() {
+    let dedup = InMemoryDedup::new();
+    let expired = dedup.claim("a", Duration::ZERO).await.expect("first claim");
+    let current = dedup
+        .claim("a", Duration::from_secs(5))
+        .await
+        .expect("expired claim should not block");
+    assert_ne!(expired, current);
+    assert!(dedup.claim("a", Duration::from_secs(5)).await.is_none());
+
+    dedup.release("a", &expired).await;
+    assert!(
+        dedup.claim("a", Duration::from_secs(5)).await.is_none(),
+        "a stale token must not release the current claim"
+    );
+
+    dedup.release("a", &current).await;
+    assert!(dedup.claim("a", Duration::from_secs(5)).await.is_some());
+}
+
+#[test]
+fn test_cache_key_ignores_metadata_and_trace_ids_but_not_prompt_or_tenant() {
+    let mut with_metadata = request("hello", Some(0.0));
+    with_metadata
+        .metadata
+        .insert("tenant".to_string(), "acme".to_string());
+
+    assert_eq!(
+        cache_key(&request("hello", Some(0.0)), &AdapterContext::default()),
+        cache_key(&with_metadata, &AdapterContext::default())
+    );
+    assert_ne!(
+        cache_key(&request("hello", Some(0.0)), &AdapterContext::default()),
+        cache_key(&request("goodbye", Some(0.0)), &AdapterContext::default())
+    );
+
+    let traced = AdapterContext {
+        metadata: [("trace_id".to_string(), "t-1".to_string())].into(),
+    };
+    let tenant = AdapterContext {
+        metadata: [("tenant".to_string(), "acme".to_string())].into(),
+    };
+    assert_eq!(
+        cache_key(&request("hello", Some(0.0)), &traced),
+        cache_key(&request("hello", Some(0.0)), &AdapterContext::default())
+    );
+    assert_ne!(
+        cache_key(&request("hello", Some(0.0)), &tenant),
+        cache_key(&request("hello", Some(0.0)), &AdapterContext::default())
+    );
+
+    let key = cache_key(&request("hello", Some(0.0)), &AdapterContext::default())
+        .expect("request should serialize");
+    assert_eq!(key.len(), 64);
+    assert!(key.chars().all(|c| c.is_ascii_hexdigit()));
+    assert!(CachePolicy::All.allows(&request("hello", None)));
+    assert!(!CachePolicy::DeterministicOnly.allows(&request("hello", None)));
+}
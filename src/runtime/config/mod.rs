@@ -0,0 +1,394 @@
+//! Builds a `ProviderRuntimeBuilder` from a TOML file, so deployments can change providers,
+//! endpoints, retries, and prices without recompiling, or from environment variables alone with
+//! `ProviderRuntimeBuilder::from_env`.
+//!
+//! ```toml
+//! default_provider = "openai"
+//! # Relative paths are resolved against the config file's directory.
+//! pricing = "prices.toml"
+//! # Entries replace built-in catalog entries with the same provider and model id.
+//! catalog = "catalog.json"
+//!
+//! [providers.openai]
+//! base_url = "https://gateway.example.com"
+//! # Read once at load time; naming an unset variable fails. Omit it to use the adapter's own
+//! # lookup.
+//! api_key_env = "GATEWAY_OPENAI_KEY"
+//! timeout_ms = 20000
+//! organization = "org-123"
+//!
+//! [providers.openai.retry]
+//! max_attempts = 4
+//! initial_backoff_ms = 200
+//! max_backoff_ms = 5000
+//! jitter = "full"
+//!
+//! [providers.anthropic]
+//! beta_features = ["token-efficient-tools-2025-02-19"]
+//! thinking_budget_tokens = 2048
+//!
+//! [providers.openrouter]
+//! enabled = false
+//! ```
+//!
+//! Every listed provider is enabled unless it sets `enabled = false`. Unknown keys are rejected.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::catalog;
+use crate::core::error::ConfigError;
+use crate::core::traits::ProviderAdapter;
+use crate::core::types::{ModelCatalog, ProviderId};
+use crate::pricing::PricingTable;
+use crate::providers::anthropic::{AnthropicAdapter, AnthropicAdapterOptions, ThinkingConfig};
+use crate::providers::openai::{OpenAiAdapter, OpenAiAdapterOptions};
+use crate::providers::openrouter::{OpenRouterAdapter, OpenRouterAdapterOptions};
+use crate::runtime::ProviderRuntimeBuilder;
+use crate::transport::http::{JitterMode, RetryPolicy};
+
+type EnvAdapter = fn(String, String) -> Result<Arc<dyn ProviderAdapter>, ConfigError>;
+
+/// Providers `from_env` enables: API key variable, base URL override variable, and how to build
+/// the adapter from those values.
+const ENV_PROVIDERS: [(&str, &str, EnvAdapter); 3] = [
+    ("OPENAI_API_KEY", "OPENAI_BASE_URL", |api_key, base_url| {
+        Ok(Arc::new(OpenAiAdapter::with_base_url(
+            Some(api_key),
+            base_url,
+        )?))
+    }),
+    (
+        "ANTHROPIC_API_KEY",
+        "ANTHROPIC_BASE_URL",
+        |api_key, base_url| {
+            Ok(Arc::new(AnthropicAdapter::with_base_url(
+                Some(api_key),
+                base_url,
+            )?))
+        },
+    ),
+    (
+        "OPENROUTER_API_KEY",
+        "OPENROUTER_BASE_URL",
+        |api_key, base_url| {
+            Ok(Arc::new(OpenRouterAdapter::with_base_url(
+                Some(api_key),
+                base_url,
+            )?))
+        },
+    ),
+];
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuntimeConfig {
+    default_provider: Option<String>,
+    pricing: Option<PathBuf>,
+    catalog: Option<PathBuf>,
+    #[serde(default)]
+    providers: ProvidersConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProvidersConfig {
+    openai: Option<OpenAiConfig>,
+    anthropic: Option<AnthropicConfig>,
+    openrouter: Option<OpenRouterConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OpenAiConfig {
+    #[serde(default = "enabled")]
+    enabled: bool,
+    base_url: Option<String>,
+    api_key_env: Option<String>,
+    timeout_ms: Option<u64>,
+    retry: Option<RetryConfig>,
+    organization: Option<String>,
+    project: Option<String>,
+    #[serde(default)]
+    inject_json_instruction: bool,
+    #[serde(default)]
+    emulate_stop_sequences: bool,
+    #[serde(default)]
+    store: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnthropicConfig {
+    #[serde(default = "enabled")]
+    enabled: bool,
+    base_url: Option<String>,
+    api_key_env: Option<String>,
+    timeout_ms: Option<u64>,
+    retry: Option<RetryConfig>,
+    api_version: Option<String>,
+    #[serde(default)]
+    beta_features: Vec<String>,
+    default_max_tokens: Option<u32>,
+    thinking_budget_tokens: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OpenRouterConfig {
+    #[serde(default = "enabled")]
+    enabled: bool,
+    base_url: Option<String>,
+    api_key_env: Option<String>,
+    timeout_ms: Option<u64>,
+    retry: Option<RetryConfig>,
+    #[serde(default)]
+    fallback_models: Vec<String>,
+    http_referer: Option<String>,
+    x_title: Option<String>,
+}
+
+/// `RetryPolicy` fields; omitted ones keep `RetryPolicy::default()`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RetryConfig {
+    max_attempts: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+    max_backoff_ms: Option<u64>,
+    retryable_status_codes: Option<Vec<u16>>,
+    max_retry_after_ms: Option<u64>,
+    jitter: Option<JitterConfig>,
+    max_total_retry_duration_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum JitterConfig {
+    None,
+    Full,
+    Equal,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+impl RetryConfig {
+    fn to_policy(&self) -> RetryPolicy {
+        let defaults = RetryPolicy::default();
+        RetryPolicy {
+            max_attempts: self.max_attempts.unwrap_or(defaults.max_attempts),
+            initial_backoff_ms: self
+                .initial_backoff_ms
+                .unwrap_or(defaults.initial_backoff_ms),
+            max_backoff_ms: self.max_backoff_ms.unwrap_or(defaults.max_backoff_ms),
+            retryable_status_codes: self
+                .retryable_status_codes
+                .clone()
+                .unwrap_or(defaults.retryable_status_codes),
+            max_retry_after_ms: self
+                .max_retry_after_ms
+                .unwrap_or(defaults.max_retry_after_ms),
+            jitter: match self.jitter {
+                None => defaults.jitter,
+                Some(JitterConfig::None) => JitterMode::None,
+                Some(JitterConfig::Full) => JitterMode::Full,
+                Some(JitterConfig::Equal) => JitterMode::Equal,
+            },
+            max_total_retry_duration_ms: self
+                .max_total_retry_duration_ms
+                .or(defaults.max_total_retry_duration_ms),
+        }
+    }
+}
+
+impl ProviderRuntimeBuilder {
+    /// Starts a builder from the TOML runtime config at `path`; see the
+    /// [module docs](crate::runtime::config) for the format. The returned builder can be
+    /// customized further before `build`.
+    pub fn from_config_path(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        Self::from_config_path_with_vars(path.as_ref(), |name| std::env::var(name).ok())
+    }
+
+    fn from_config_path_with_vars(
+        path: &Path,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path).map_err(|error| {
+            invalid_config(format!("failed to read {}: {error}", path.display()))
+        })?;
+        let config: RuntimeConfig = toml::from_str(&contents).map_err(|error| {
+            invalid_config(format!("malformed config {}: {error}", path.display()))
+        })?;
+        let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+        config.into_builder(base_dir, &var)
+    }
+
+    /// Starts a builder with an adapter for each provider whose API key variable is set:
+    /// `OPENAI_API_KEY`, `ANTHROPIC_API_KEY`, `OPENROUTER_API_KEY`. `OPENAI_BASE_URL`,
+    /// `ANTHROPIC_BASE_URL`, and `OPENROUTER_BASE_URL` override the endpoints. Fails when no key
+    /// is set.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_env_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_env_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigError> {
+        let set = |name: &str| var(name).filter(|value| !value.trim().is_empty());
+        let mut builder = crate::runtime::ProviderRuntime::builder();
+        let mut found = false;
+
+        for (key_var, base_url_var, adapter) in ENV_PROVIDERS {
+            let Some(api_key) = set(key_var) else {
+                continue;
+            };
+            let base_url = set(base_url_var).unwrap_or_default();
+            builder = builder.with_adapter(adapter(api_key, base_url)?);
+            found = true;
+        }
+
+        if !found {
+            let vars: Vec<&str> = ENV_PROVIDERS
+                .iter()
+                .map(|(key_var, _, _)| *key_var)
+                .collect();
+            return Err(invalid_config(format!(
+                "no provider API key found; set one of {}",
+                vars.join(", ")
+            )));
+        }
+        Ok(builder)
+    }
+}
+
+impl RuntimeConfig {
+    fn into_builder(
+        self,
+        base_dir: &Path,
+        var: &dyn Fn(&str) -> Option<String>,
+    ) -> Result<ProviderRuntimeBuilder, ConfigError> {
+        let mut builder = crate::runtime::ProviderRuntime::builder();
+        let mut enabled = Vec::new();
+
+        if let Some(config) = self.providers.openai.filter(|config| config.enabled) {
+            let options = OpenAiAdapterOptions {
+                organization: config.organization,
+                project: config.project,
+                inject_json_instruction: config.inject_json_instruction,
+                emulate_stop_sequences: config.emulate_stop_sequences,
+                store: config.store,
+                timeout_ms: config.timeout_ms,
+                retry_policy: config.retry.as_ref().map(RetryConfig::to_policy),
+                ..OpenAiAdapterOptions::default()
+            };
+            let adapter = OpenAiAdapter::with_base_url_and_options(
+                api_key_from_env(config.api_key_env.as_deref(), var)?,
+                config.base_url.unwrap_or_default(),
+                options,
+            )?;
+            builder = builder.with_adapter(Arc::new(adapter));
+            enabled.push(ProviderId::Openai);
+        }
+
+        if let Some(config) = self.providers.anthropic.filter(|config| config.enabled) {
+            let options = AnthropicAdapterOptions {
+                thinking: config
+                    .thinking_budget_tokens
+                    .map(|budget_tokens| ThinkingConfig { budget_tokens }),
+                api_version: config.api_version,
+                beta_features: config.beta_features,
+                timeout_ms: config.timeout_ms,
+                retry_policy: config.retry.as_ref().map(RetryConfig::to_policy),
+                default_max_tokens: config.default_max_tokens,
+            };
+            let adapter = AnthropicAdapter::with_base_url_and_options(
+                api_key_from_env(config.api_key_env.as_deref(), var)?,
+                config.base_url.unwrap_or_default(),
+                options,
+            )?;
+            builder = builder.with_adapter(Arc::new(adapter));
+            enabled.push(ProviderId::Anthropic);
+        }
+
+        if let Some(config) = self.providers.openrouter.filter(|config| config.enabled) {
+            let options = OpenRouterAdapterOptions {
+                fallback_models: config.fallback_models,
+                http_referer: config.http_referer,
+                x_title: config.x_title,
+                timeout_ms: config.timeout_ms,
+                retry_policy: config.retry.as_ref().map(RetryConfig::to_policy),
+                ..OpenRouterAdapterOptions::default()
+            };
+            let adapter = OpenRouterAdapter::with_base_url_and_options(
+                api_key_from_env(config.api_key_env.as_deref(), var)?,
+                config.base_url.unwrap_or_default(),
+                options,
+            )?;
+            builder = builder.with_adapter(Arc::new(adapter));
+            enabled.push(ProviderId::Openrouter);
+        }
+
+        if let Some(name) = self.default_provider {
+            let provider = ProviderId::from_name(name.trim().to_string());
+            if !enabled.contains(&provider) {
+                return Err(invalid_config(format!(
+                    "default_provider '{name}' is not an enabled provider"
+                )));
+            }
+            builder = builder.with_default_provider(provider);
+        }
+
+        if let Some(pricing) = self.pricing {
+            builder = builder.with_pricing_table(PricingTable::from_path(base_dir.join(pricing))?);
+        }
+
+        if let Some(catalog_path) = self.catalog {
+            let overrides = ModelCatalog::from_path(base_dir.join(catalog_path))?;
+            builder = builder.with_model_catalog(apply_catalog_overrides(
+                catalog::builtin_static_catalog(),
+                overrides,
+            ));
+        }
+
+        Ok(builder)
+    }
+}
+
+/// Replaces base entries that share a provider and model id with an override, and appends the
+/// rest.
+fn apply_catalog_overrides(mut base: ModelCatalog, overrides: ModelCatalog) -> ModelCatalog {
+    for model in overrides.models {
+        match base.models.iter_mut().find(|existing| {
+            existing.provider == model.provider && existing.model_id == model.model_id
+        }) {
+            Some(existing) => *existing = model,
+            None => base.models.push(model),
+        }
+    }
+    base
+}
+
+/// Resolves a provider's `api_key_env`; naming a variable that is unset or blank is an error
+/// rather than a silent fallback to the adapter's default lookup.
+fn api_key_from_env(
+    name: Option<&str>,
+    var: &dyn Fn(&str) -> Option<String>,
+) -> Result<Option<String>, ConfigError> {
+    let Some(name) = name else {
+        return Ok(None);
+    };
+    match var(name).filter(|value| !value.trim().is_empty()) {
+        Some(key) => Ok(Some(key)),
+        None => Err(invalid_config(format!(
+            "api_key_env '{name}' is not set in the environment"
+        ))),
+    }
+}
+
+fn invalid_config(reason: String) -> ConfigError {
+    ConfigError::InvalidRuntimeConfig { reason }
+}
+
+#[cfg(test)]
+mod tests;
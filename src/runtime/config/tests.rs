@@ -0,0 +1,294 @@
+use super::*;
+
+/// Writes `files` into a fresh temp directory and returns it.
+fn config_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("runtime-config-{name}-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("temp dir should be created");
+    for (file, contents) in files {
+        std::fs::write(dir.join(file), contents).expect("config file should be written");
+    }
+    dir
+}
+
+fn reason(result: Result<ProviderRuntimeBuilder, ConfigError>) -> String {
+    match result {
+        Err(ConfigError::InvalidRuntimeConfig { reason }) => reason,
+        Err(other) => panic!("expected invalid runtime config, got {other:?}"),
+        Ok(_) => panic!("expected invalid runtime config"),
+    }
+}
+
+#[test]
+fn test_from_config_path_builds_providers_pricing_and_catalog() {
+    let dir = config_dir(
+        "full",
+        &[
+            (
+                "runtime.toml",
+                r#"
+                default_provider = "anthropic"
+                pricing = "prices.toml"
+                catalog = "catalog.json"
+
+                [providers.openai]
+                base_url = "https://gateway.example.com"
+                timeout_ms = 20000
+                organization = "org-123"
+
+                [providers.openai.retry]
+                max_attempts = 4
+                jitter = "full"
+
+                [providers.anthropic]
+                thinking_budget_tokens = 2048
+
+                [providers.openrouter]
+                enabled = false
+                "#,
+            ),
+            (
+                "prices.toml",
+                r#"
+                [[rules]]
+                provider = "openai"
+                pattern = "*"
+                input_per_million = 1.0
+                output_per_million = 2.0
+                "#,
+            ),
+            (
+                "catalog.json",
+                r#"{"version": 1, "models": [
+                    {"provider": {"type": "openai"}, "model_id": "gpt-5-mini",
+                     "context_window": 400000, "supports_tools": true,
+                     "supports_structured_output": true},
+                    {"provider": {"type": "openai"}, "model_id": "gpt-internal",
+                     "supports_tools": false, "supports_structured_output": false}
+                ]}"#,
+            ),
+        ],
+    );
+
+    let builder =
+        ProviderRuntimeBuilder::from_config_path(dir.join("runtime.toml")).expect("config loads");
+
+    let providers: Vec<ProviderId> = builder
+        .adapters
+        .iter()
+        .map(|adapter| adapter.id())
+        .collect();
+    assert_eq!(providers, [ProviderId::Openai, ProviderId::Anthropic]);
+    assert_eq!(builder.default_provider, Some(ProviderId::Anthropic));
+    assert_eq!(
+        builder
+            .pricing_table
+            .as_ref()
+            .map(|table| table.rules.len()),
+        Some(1)
+    );
+    let overridden = builder
+        .static_catalog
+        .models
+        .iter()
+        .find(|model| model.model_id == "gpt-5-mini")
+        .expect("built-in model kept");
+    assert_eq!(overridden.context_window, Some(400_000));
+    assert!(
+        builder
+            .static_catalog
+            .models
+            .iter()
+            .any(|model| model.model_id == "gpt-internal")
+    );
+    assert!(
+        builder
+            .static_catalog
+            .models
+            .iter()
+            .any(|model| model.provider == ProviderId::Anthropic)
+    );
+
+    builder.build();
+    std::fs::remove_dir_all(&dir).expect("temp dir should be removed");
+}
+
+#[test]
+fn test_from_config_path_rejects_invalid_configs() {
+    let dir = config_dir(
+        "invalid",
+        &[
+            ("unknown_key.toml", "[providers.openai]\nbase_uri = \"x\"\n"),
+            ("unknown_provider.toml", "[providers.mistral]\n"),
+            (
+                "disabled_default.toml",
+                "default_provider = \"openrouter\"\n[providers.openai]\n",
+            ),
+            (
+                "bad_jitter.toml",
+                "[providers.openai.retry]\njitter = \"some\"\n",
+            ),
+        ],
+    );
+
+    assert!(
+        reason(ProviderRuntimeBuilder::from_config_path(
+            dir.join("missing.toml")
+        ))
+        .contains("failed to read")
+    );
+    for file in [
+        "unknown_key.toml",
+        "unknown_provider.toml",
+        "bad_jitter.toml",
+    ] {
+        assert!(
+            reason(ProviderRuntimeBuilder::from_config_path(dir.join(file)))
+                .contains("malformed config"),
+            "{file}"
+        );
+    }
+    assert!(
+        reason(ProviderRuntimeBuilder::from_config_path(
+            dir.join("disabled_default.toml")
+        ))
+        .contains("default_provider 'openrouter'")
+    );
+
+    std::fs::remove_dir_all(&dir).expect("temp dir should be removed");
+}
+
+#[test]
+fn test_from_config_path_surfaces_adapter_and_retry_validation() {
+    let dir = config_dir(
+        "adapter",
+        &[
+            (
+                "retry.toml",
+                "[providers.anthropic.retry]\nmax_attempts = 0\n",
+            ),
+            (
+                "thinking.toml",
+                "[providers.anthropic]\nthinking_budget_tokens = 10\n",
+            ),
+        ],
+    );
+
+    assert!(matches!(
+        ProviderRuntimeBuilder::from_config_path(dir.join("retry.toml")),
+        Err(ConfigError::InvalidRetryPolicy { .. })
+    ));
+    assert!(matches!(
+        ProviderRuntimeBuilder::from_config_path(dir.join("thinking.toml")),
+        Err(ConfigError::InvalidProviderConfig {
+            provider: ProviderId::Anthropic,
+            ..
+        })
+    ));
+
+    std::fs::remove_dir_all(&dir).expect("temp dir should be removed");
+}
+
+#[test]
+fn test_retry_config_keeps_defaults_for_omitted_fields() {
+    let config: RetryConfig =
+        toml::from_str("max_attempts = 5\njitter = \"equal\"\n").expect("retry config parses");
+    let policy = config.to_policy();
+    assert_eq!(policy.max_attempts, 5);
+    assert_eq!(policy.jitter, JitterMode::Equal);
+    assert_eq!(
+        policy.retryable_status_codes,
+        RetryPolicy::default().retryable_status_codes
+    );
+}
+
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: Vec<(String, String)> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    move |name| {
+        vars.iter()
+            .find(|(var, _)| var == name)
+            .map(|(_, value)| value.clone())
+    }
+}
+
+#[test]
+fn test_from_env_enables_providers_with_keys() {
+    let builder = ProviderRuntimeBuilder::from_env_vars(env(&[
+        ("OPENAI_API_KEY", "sk-openai"),
+        ("ANTHROPIC_API_KEY", "  "),
+        ("OPENROUTER_API_KEY", "sk-or"),
+    ]))
+    .expect("env config loads");
+
+    let providers: Vec<ProviderId> = builder
+        .adapters
+        .iter()
+        .map(|adapter| adapter.id())
+        .collect();
+    assert_eq!(providers, [ProviderId::Openai, ProviderId::Openrouter]);
+
+    let error = ProviderRuntimeBuilder::from_env_vars(env(&[("OPENAI_BASE_URL", "http://x")]))
+        .map(|_| ())
+        .expect_err("no keys should fail");
+    assert!(matches!(
+        error,
+        ConfigError::InvalidRuntimeConfig { ref reason }
+            if reason.contains("OPENAI_API_KEY, ANTHROPIC_API_KEY, OPENROUTER_API_KEY")
+    ));
+}
+
+#[tokio::test]
+async fn test_from_env_applies_base_url_override() {
+    let runtime = ProviderRuntimeBuilder::from_env_vars(env(&[
+        ("OPENAI_API_KEY", "sk-openai"),
+        ("OPENAI_BASE_URL", "http://127.0.0.1:1/gateway/"),
+    ]))
+    .expect("env config loads")
+    .build();
+
+    let report = runtime
+        .discover_models_report(crate::core::types::DiscoveryOptions {
+            remote: true,
+            include_provider: vec![ProviderId::Openai],
+            refresh_cache: true,
+        })
+        .await;
+    let failure = report.failures.first().expect("unreachable gateway fails");
+    assert!(
+        failure
+            .error
+            .to_string()
+            .contains("127.0.0.1:1/gateway/v1/models"),
+        "{}",
+        failure.error
+    );
+}
+
+#[test]
+fn test_from_config_path_rejects_unresolved_api_key_env() {
+    let dir = config_dir(
+        "api-key-env",
+        &[(
+            "runtime.toml",
+            "[providers.openai]\napi_key_env = \"GATEWAY_OPENAI_KEY\"\n",
+        )],
+    );
+    let path = dir.join("runtime.toml");
+
+    let reason = reason(ProviderRuntimeBuilder::from_config_path_with_vars(
+        &path,
+        env(&[("OPENAI_API_KEY", "sk-openai")]),
+    ));
+    assert!(reason.contains("GATEWAY_OPENAI_KEY"), "{reason}");
+    assert!(
+        ProviderRuntimeBuilder::from_config_path_with_vars(
+            &path,
+            env(&[("GATEWAY_OPENAI_KEY", "sk-gateway")]),
+        )
+        .is_ok()
+    );
+
+    std::fs::remove_dir_all(&dir).expect("temp dir should be removed");
+}
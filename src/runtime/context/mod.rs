@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use crate::core::types::AdapterContext;
+
+tokio::task_local! {
+    static ACTIVE_CONTEXT: RequestContext;
+}
+
+/// Adapter context metadata key filled from [`RequestContext::trace_id`].
+pub const TRACE_ID_METADATA_KEY: &str = "trace_id";
+/// Adapter context metadata key filled from [`RequestContext::tenant`].
+pub const TENANT_METADATA_KEY: &str = "tenant";
+
+/// Ambient per-request context, propagated through a Tokio task-local instead of being passed to
+/// every call.
+///
+/// `ProviderRuntime::run` reads the active context for every provider call in the scope.
+/// `adapter_metadata` is layered over the builder's `AdapterContext`, such as a tenant's
+/// `openai.api_key` or extra `transport.header.*` entries, and `trace_id` and `tenant` are set
+/// on top as the `trace_id` and `tenant` entries. They stay runtime-side: the request, and so the
+/// provider wire metadata, is never modified. `deadline` bounds the provider call.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestContext {
+    pub trace_id: Option<String>,
+    pub tenant: Option<String>,
+    pub deadline: Option<Instant>,
+    pub adapter_metadata: BTreeMap<String, String>,
+}
+
+impl RequestContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_trace_id(mut self, trace_id: impl Into<String>) -> Self {
+        self.trace_id = Some(trace_id.into());
+        self
+    }
+
+    pub fn with_tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.tenant = Some(tenant.into());
+        self
+    }
+
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Adds one adapter context metadata entry, overriding the builder's value for the key.
+    pub fn with_adapter_metadata(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.adapter_metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets the deadline to `timeout` from now.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    /// Runs `future` with this context active for every runtime call it makes.
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        ACTIVE_CONTEXT.scope(self, future).await
+    }
+
+    /// Returns the context of the enclosing [`RequestContext::scope`], if any.
+    pub fn current() -> Option<Self> {
+        ACTIVE_CONTEXT.try_with(Clone::clone).ok()
+    }
+
+    /// Time left until the deadline, saturating at zero. `None` when no deadline is set.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Whether the context changes the adapter context of calls in its scope.
+    pub(crate) fn has_adapter_metadata(&self) -> bool {
+        !self.adapter_metadata.is_empty() || self.trace_id.is_some() || self.tenant.is_some()
+    }
+
+    /// `base` with this context's adapter metadata, then its `trace_id` and `tenant`, layered
+    /// on top.
+    pub(crate) fn apply_adapter_metadata(&self, base: &AdapterContext) -> AdapterContext {
+        let mut ctx = base.clone();
+        ctx.metadata.extend(
+            self.adapter_metadata
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+        for (key, value) in [
+            (TRACE_ID_METADATA_KEY, &self.trace_id),
+            (TENANT_METADATA_KEY, &self.tenant),
+        ] {
+            if let Some(value) = value {
+                ctx.metadata.insert(key.to_string(), value.clone());
+            }
+        }
+        ctx
+    }
+}
+
+#[cfg(test)]
+mod tests;
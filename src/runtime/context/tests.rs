@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use super::RequestContext;
+use crate::core::types::AdapterContext;
+
+#[tokio::test]
+async fn test_current_is_visible_only_inside_scope() {
+    assert!(RequestContext::current().is_none());
+
+    let context = RequestContext::new().with_trace_id("trace-1");
+    let seen = context
+        .clone()
+        .scope(async { RequestContext::current() })
+        .await;
+
+    assert_eq!(seen, Some(context));
+    assert!(RequestContext::current().is_none());
+}
+
+#[test]
+fn test_trace_id_and_tenant_override_adapter_metadata() {
+    let mut base = AdapterContext::default();
+    base.metadata
+        .insert("tenant".to_string(), "builder-tenant".to_string());
+    let context = RequestContext::new()
+        .with_trace_id("ambient-trace")
+        .with_tenant("ambient-tenant")
+        .with_adapter_metadata("tenant", "metadata-tenant");
+
+    let merged = context.apply_adapter_metadata(&base);
+
+    assert!(context.has_adapter_metadata());
+    assert!(!RequestContext::new().has_adapter_metadata());
+    assert_eq!(
+        merged.metadata,
+        BTreeMap::from([
+            ("tenant".to_string(), "ambient-tenant".to_string()),
+            ("trace_id".to_string(), "ambient-trace".to_string()),
+        ])
+    );
+}
+
+#[test]
+fn test_remaining_saturates_after_deadline() {
+    let expired = RequestContext::new().with_deadline(Instant::now() - Duration::from_secs(1));
+    assert_eq!(expired.remaining(), Some(Duration::ZERO));
+    assert_eq!(RequestContext::new().remaining(), None);
+}
+
+#[test]
+fn test_apply_adapter_metadata_overrides_base_entries() {
+    let mut base = AdapterContext::default();
+    base.metadata
+        .insert("openai.api_key".to_string(), "shared".to_string());
+    base.metadata
+        .insert("transport.header.x-team".to_string(), "core".to_string());
+    let context = RequestContext::new()
+        .with_adapter_metadata("openai.api_key", "tenant")
+        .with_adapter_metadata("transport.header.x-request-id", "req-1");
+
+    let merged = context.apply_adapter_metadata(&base);
+
+    assert_eq!(
+        merged.metadata,
+        BTreeMap::from([
+            ("openai.api_key".to_string(), "tenant".to_string()),
+            (
+                "transport.header.x-request-id".to_string(),
+                "req-1".to_string()
+            ),
+            ("transport.header.x-team".to_string(), "core".to_string()),
+        ])
+    );
+    assert_eq!(base.metadata["openai.api_key"], "shared");
+}
@@ -0,0 +1,52 @@
+//! Embeddings through the same routing, credentials, and pricing as `ProviderRuntime::run`.
+
+use crate::core::error::RuntimeError;
+use crate::core::types::{EmbeddingRequest, EmbeddingResponse};
+use crate::pricing;
+use crate::runtime::ProviderRuntime;
+
+impl ProviderRuntime {
+    /// Embeds `request.inputs` with the provider resolved for `request.model`. The cost is
+    /// estimated from the pricing table's input rate when the provider does not report one;
+    /// see `PriceRule::embedding`.
+    pub async fn embed(
+        &self,
+        mut request: EmbeddingRequest,
+    ) -> Result<EmbeddingResponse, RuntimeError> {
+        let provider = self
+            .registry
+            .resolve_route(&mut request.model, &self.call_context())?;
+        let adapter = self.registry.resolve_adapter(&provider)?;
+        let (ctx, lease) = self
+            .registry
+            .lease_credential(&provider, &self.call_context())
+            .await?;
+        let result = adapter.embed(&request, &ctx).await;
+        self.registry
+            .report_credential(&provider, lease.as_ref(), result.as_ref().err());
+        let Some(mut response) = result? else {
+            return Err(RuntimeError::CapabilityMismatch {
+                provider,
+                model: request.model.model_id,
+                capability: "embeddings".to_string(),
+            });
+        };
+
+        if response.cost.is_none()
+            && let Some(pricing_table) = &self.pricing_table
+        {
+            let (cost, warnings) = pricing::estimate_cost(
+                &response.provider,
+                &response.model,
+                &response.usage,
+                pricing_table,
+            );
+            response.cost = cost;
+            response.warnings.extend(warnings);
+        }
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests;
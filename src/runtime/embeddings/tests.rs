@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::core::error::{ProviderError, RuntimeError};
+use crate::core::traits::ProviderAdapter;
+use crate::core::types::{
+    AdapterContext, DiscoveryOptions, EmbeddingRequest, EmbeddingResponse, ModelInfo, ModelRef,
+    ProviderCapabilities, ProviderId, ProviderRequest, ProviderResponse, Usage,
+};
+use crate::pricing::{PriceRule, PricingTable};
+use crate::providers::mock::MockAdapter;
+use crate::runtime::ProviderRuntime;
+
+struct EmbeddingAdapter;
+
+#[async_trait]
+impl ProviderAdapter for EmbeddingAdapter {
+    fn id(&self) -> ProviderId {
+        ProviderId::Openai
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            supports_tools: false,
+            supports_structured_output: false,
+            supports_thinking: false,
+            supports_remote_discovery: false,
+            supports_parallel_tool_calls: false,
+        }
+    }
+
+    async fn run(
+        &self,
+        _req: &ProviderRequest,
+        _ctx: &AdapterContext,
+    ) -> Result<ProviderResponse, ProviderError> {
+        unreachable!("embedding tests never run chat requests")
+    }
+
+    async fn embed(
+        &self,
+        req: &EmbeddingRequest,
+        _ctx: &AdapterContext,
+    ) -> Result<Option<EmbeddingResponse>, ProviderError> {
+        Ok(Some(EmbeddingResponse {
+            embeddings: req.inputs.iter().map(|_| vec![0.0, 1.0]).collect(),
+            usage: Usage {
+                input_tokens: Some(1_000),
+                output_tokens: Some(0),
+                ..Usage::default()
+            },
+            cost: None,
+            provider: ProviderId::Openai,
+            model: req.model.model_id.clone(),
+            request_id: None,
+            warnings: Vec::new(),
+        }))
+    }
+
+    async fn discover_models(
+        &self,
+        _opts: &DiscoveryOptions,
+        _ctx: &AdapterContext,
+    ) -> Result<Vec<ModelInfo>, ProviderError> {
+        Ok(Vec::new())
+    }
+}
+
+fn request() -> EmbeddingRequest {
+    EmbeddingRequest {
+        model: ModelRef {
+            provider_hint: Some(ProviderId::Openai),
+            model_id: "text-embedding-3-small".to_string(),
+            requirements: None,
+        },
+        inputs: vec!["a".to_string(), "b".to_string()],
+        dimensions: None,
+    }
+}
+
+#[tokio::test]
+async fn test_embed_prices_input_tokens_with_embedding_rule() {
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(EmbeddingAdapter))
+        .with_pricing_table(PricingTable::new(vec![PriceRule::embedding(
+            ProviderId::Openai,
+            "text-embedding-3-*",
+            0.000_000_02,
+        )]))
+        .build();
+
+    let response = runtime
+        .embed(request())
+        .await
+        .expect("embed should succeed");
+
+    assert_eq!(response.embeddings.len(), 2);
+    let cost = response.cost.expect("cost should be estimated");
+    assert!((cost.total_cost - 0.000_02).abs() < 1e-12);
+    assert_eq!(cost.output_cost, 0.0);
+    assert!(response.warnings.is_empty());
+}
+
+#[tokio::test]
+async fn test_embed_without_adapter_support_is_capability_mismatch() {
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(MockAdapter::new(ProviderId::Openai)))
+        .build();
+
+    let error = runtime
+        .embed(request())
+        .await
+        .expect_err("mock adapter has no embeddings");
+
+    assert!(matches!(
+        error,
+        RuntimeError::CapabilityMismatch { ref capability, .. } if capability == "embeddings"
+    ));
+}
@@ -0,0 +1,286 @@
+//! Side-by-side evaluation of models over a fixed set of requests.
+//!
+//! An `EvalSuite` pairs named canonical requests with the models to try them on, listed
+//! explicitly or selected from the runtime's active catalog with a `CatalogFilter`.
+//! `ProviderRuntime::run_eval` sends every case to every model through `run_batch`, so routing,
+//! middleware, and pricing apply as usual, and collects each outcome into an `EvalReport`
+//! that can be summarized per model and exported as JSON or CSV.
+
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::CatalogFilter;
+use crate::core::types::{
+    CostBreakdown, ModelRef, ProviderId, ProviderRequest, ProviderResponse, Usage,
+};
+use crate::runtime::{ProviderRuntime, structured};
+
+/// Runs in flight at once when the suite does not set a limit.
+const DEFAULT_EVAL_CONCURRENCY: usize = 4;
+
+/// One request of a suite. Its `model` is replaced by each model under evaluation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvalCase {
+    pub name: String,
+    pub request: ProviderRequest,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvalSuite {
+    cases: Vec<EvalCase>,
+    models: Vec<ModelRef>,
+    catalog_filter: Option<CatalogFilter>,
+    max_concurrency: usize,
+}
+
+impl Default for EvalSuite {
+    fn default() -> Self {
+        Self {
+            cases: Vec::new(),
+            models: Vec::new(),
+            catalog_filter: None,
+            max_concurrency: DEFAULT_EVAL_CONCURRENCY,
+        }
+    }
+}
+
+impl EvalSuite {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_case(mut self, name: impl Into<String>, request: ProviderRequest) -> Self {
+        self.cases.push(EvalCase {
+            name: name.into(),
+            request,
+        });
+        self
+    }
+
+    pub fn with_model(mut self, model: ModelRef) -> Self {
+        self.models.push(model);
+        self
+    }
+
+    /// Also evaluates every model of the runtime's active catalog matching `filter`, after the
+    /// explicitly added models.
+    pub fn with_catalog_models(mut self, filter: CatalogFilter) -> Self {
+        self.catalog_filter = Some(filter);
+        self
+    }
+
+    /// Caps how many runs are in flight at once; at least one always runs.
+    pub fn with_max_concurrency(mut self, max_in_flight: usize) -> Self {
+        self.max_concurrency = max_in_flight.max(1);
+        self
+    }
+}
+
+/// Every run of a suite, ordered by model and then case.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EvalReport {
+    pub results: Vec<EvalResult>,
+}
+
+/// The outcome of one case on one model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EvalResult {
+    pub case: String,
+    pub model: ModelRef,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<ProviderResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Totals for one model across the cases of a report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EvalModelSummary {
+    pub model: ModelRef,
+    pub cases: usize,
+    pub failures: usize,
+    pub mean_latency_ms: f64,
+    pub usage: Usage,
+    /// Summed cost of the priced responses in `currency`; `None` when no response was priced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_cost: Option<f64>,
+    /// Currency of the first priced response, which every summed cost shares.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Priced responses left out of `total_cost` because they were billed in another currency.
+    /// Non-zero means the total understates the model's cost.
+    #[serde(default)]
+    pub mismatched_currency_costs: usize,
+}
+
+impl EvalResult {
+    pub fn usage(&self) -> Option<&Usage> {
+        self.response.as_ref().map(|response| &response.usage)
+    }
+
+    pub fn cost(&self) -> Option<&CostBreakdown> {
+        self.response
+            .as_ref()
+            .and_then(|response| response.cost.as_ref())
+    }
+
+    /// Concatenated text output of the response.
+    pub fn output_text(&self) -> Option<String> {
+        self.response.as_ref().map(structured::output_text)
+    }
+}
+
+impl EvalReport {
+    /// Per-model totals, in the order models were evaluated.
+    pub fn summaries(&self) -> Vec<EvalModelSummary> {
+        let mut summaries: Vec<EvalModelSummary> = Vec::new();
+        for result in &self.results {
+            let index = match summaries
+                .iter()
+                .position(|summary| summary.model == result.model)
+            {
+                Some(index) => index,
+                None => {
+                    summaries.push(EvalModelSummary {
+                        model: result.model.clone(),
+                        cases: 0,
+                        failures: 0,
+                        mean_latency_ms: 0.0,
+                        usage: Usage::default(),
+                        total_cost: None,
+                        currency: None,
+                        mismatched_currency_costs: 0,
+                    });
+                    summaries.len() - 1
+                }
+            };
+            let summary = &mut summaries[index];
+            summary.mean_latency_ms += result.latency_ms as f64;
+            summary.cases += 1;
+            if result.error.is_some() {
+                summary.failures += 1;
+            }
+            if let Some(usage) = result.usage() {
+                summary.usage.accumulate(usage);
+            }
+            if let Some(cost) = result.cost() {
+                let currency = summary
+                    .currency
+                    .get_or_insert_with(|| cost.currency.clone());
+                if *currency == cost.currency {
+                    *summary.total_cost.get_or_insert(0.0) += cost.total_cost;
+                } else {
+                    summary.mismatched_currency_costs += 1;
+                }
+            }
+        }
+        for summary in &mut summaries {
+            summary.mean_latency_ms /= summary.cases as f64;
+        }
+        summaries
+    }
+
+    pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// One row per result with a header row: case, provider, model, latency, token counts,
+    /// cost, error, and output text. Fields are quoted as RFC 4180 requires.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "case,provider,model,served_model,latency_ms,input_tokens,output_tokens,total_cost,currency,error,output\n",
+        );
+        for result in &self.results {
+            let response = result.response.as_ref();
+            let usage = result.usage();
+            let cost = result.cost();
+            let provider = response
+                .map(|response| &response.provider)
+                .or(result.model.provider_hint.as_ref())
+                .map(ProviderId::name);
+            let fields = [
+                Some(result.case.clone()),
+                provider.map(str::to_string),
+                Some(result.model.model_id.clone()),
+                response.map(|response| response.model.clone()),
+                Some(result.latency_ms.to_string()),
+                usage
+                    .and_then(|usage| usage.input_tokens)
+                    .map(|tokens| tokens.to_string()),
+                usage
+                    .and_then(|usage| usage.output_tokens)
+                    .map(|tokens| tokens.to_string()),
+                cost.map(|cost| cost.total_cost.to_string()),
+                cost.map(|cost| cost.currency.clone()),
+                result.error.clone(),
+                result.output_text(),
+            ];
+            let row = fields
+                .iter()
+                .map(|field| csv_field(field.as_deref().unwrap_or_default()))
+                .collect::<Vec<_>>()
+                .join(",");
+            csv.push_str(&row);
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+impl ProviderRuntime {
+    /// Runs every case of `suite` against every model, at most `max_concurrency` at a time.
+    /// Failed runs are recorded in the report rather than returned.
+    pub async fn run_eval(&self, suite: &EvalSuite) -> EvalReport {
+        let mut models = suite.models.clone();
+        if let Some(filter) = &suite.catalog_filter {
+            for model in self.registry.query_models(filter) {
+                let model = ModelRef {
+                    provider_hint: Some(model.provider),
+                    model_id: model.model_id,
+                    requirements: None,
+                };
+                if !models.contains(&model) {
+                    models.push(model);
+                }
+            }
+        }
+
+        let pairs: Vec<(&ModelRef, &EvalCase)> = models
+            .iter()
+            .flat_map(|model| suite.cases.iter().map(move |case| (model, case)))
+            .collect();
+        let requests = pairs.iter().map(|(model, case)| {
+            let mut request = case.request.clone();
+            request.model = (*model).clone();
+            request
+        });
+        let outcomes = self.run_batch_timed(requests, suite.max_concurrency).await;
+
+        let results = pairs
+            .into_iter()
+            .zip(outcomes)
+            .map(|((model, case), (result, latency_ms))| EvalResult {
+                case: case.name.clone(),
+                model: model.clone(),
+                latency_ms,
+                error: result.as_ref().err().map(ToString::to_string),
+                response: result.ok(),
+            })
+            .collect();
+        EvalReport { results }
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use super::{EvalReport, EvalSuite};
+use crate::catalog::CatalogFilter;
+use crate::core::error::ProviderError;
+use crate::core::types::{
+    AssistantOutput, ContentPart, FinishReason, Message, MessageRole, ModelCatalog, ModelInfo,
+    ModelRef, ProviderId, ProviderRequest, ProviderResponse, Usage,
+};
+use crate::pricing::{PriceRule, PricingTable};
+use crate::providers::mock::{MockAdapter, MockMatcher, MockOutcome};
+use crate::runtime::ProviderRuntime;
+
+fn catalog_model(provider: ProviderId, model_id: &str) -> ModelInfo {
+    ModelInfo {
+        provider,
+        model_id: model_id.to_string(),
+        display_name: None,
+        context_window: None,
+        max_output_tokens: None,
+        supports_tools: true,
+        supports_structured_output: true,
+        pricing: None,
+        input_modalities: Vec::new(),
+        output_modalities: Vec::new(),
+        deprecated: false,
+        knowledge_cutoff: None,
+    }
+}
+
+fn model_ref(provider: ProviderId, model_id: &str) -> ModelRef {
+    ModelRef {
+        provider_hint: Some(provider),
+        model_id: model_id.to_string(),
+        requirements: None,
+    }
+}
+
+fn case(text: &str) -> ProviderRequest {
+    ProviderRequest::new(
+        model_ref(ProviderId::Openai, "replaced"),
+        vec![Message {
+            role: MessageRole::User,
+            content: vec![ContentPart::Text {
+                text: text.to_string(),
+            }],
+            cache_hint: None,
+        }],
+    )
+}
+
+fn openai_answer() -> ProviderResponse {
+    ProviderResponse {
+        output: AssistantOutput {
+            content: vec![ContentPart::Text {
+                text: "Hello, \"world\"".to_string(),
+            }],
+            structured_output: None,
+            annotations: Vec::new(),
+        },
+        usage: Usage {
+            input_tokens: Some(10),
+            output_tokens: Some(5),
+            ..Usage::default()
+        },
+        cost: None,
+        provider: ProviderId::Openai,
+        model: "gpt-5-mini".to_string(),
+        raw_provider_response: None,
+        request_id: None,
+        rate_limit: None,
+        finish_reason: FinishReason::Stop,
+        warnings: Vec::new(),
+        alternatives: Vec::new(),
+        response_id: None,
+    }
+}
+
+async fn run_suite() -> EvalReport {
+    let openai = Arc::new(
+        MockAdapter::new(ProviderId::Openai)
+            .on(MockMatcher::any(), MockOutcome::response(openai_answer())),
+    );
+    let anthropic = Arc::new(MockAdapter::new(ProviderId::Anthropic).on(
+        MockMatcher::any(),
+        MockOutcome::error(ProviderError::Status {
+            provider: ProviderId::Anthropic,
+            model: Some("claude-haiku-4-5".to_string()),
+            status_code: 400,
+            request_id: None,
+            message: "bad request".to_string(),
+            rate_limit: None,
+        }),
+    ));
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(openai)
+        .with_adapter(anthropic)
+        .with_model_catalog(ModelCatalog {
+            models: vec![
+                catalog_model(ProviderId::Openai, "gpt-5-mini"),
+                catalog_model(ProviderId::Anthropic, "claude-haiku-4-5"),
+                catalog_model(ProviderId::Anthropic, "claude-opus-4-1"),
+            ],
+        })
+        .with_pricing_table(PricingTable::new(vec![PriceRule {
+            provider: ProviderId::Openai,
+            model_pattern: "gpt-5-mini".to_string(),
+            input_cost_per_token: 0.01,
+            output_cost_per_token: 0.02,
+            cached_input_cost_per_token: None,
+            cache_write_input_cost_per_token: None,
+            reasoning_cost_per_token: None,
+            currency: None,
+            tiers: Vec::new(),
+            per_request_cost: None,
+            per_web_search_cost: None,
+        }]))
+        .build();
+
+    let suite = EvalSuite::new()
+        .with_case("greet", case("Say hello"))
+        .with_case("math", case("What is 2 + 2?"))
+        .with_model(model_ref(ProviderId::Openai, "gpt-5-mini"))
+        .with_catalog_models(CatalogFilter {
+            providers: vec![ProviderId::Anthropic],
+            model_id_glob: Some("claude-haiku*".to_string()),
+            ..CatalogFilter::default()
+        })
+        .with_max_concurrency(2);
+    runtime.run_eval(&suite).await
+}
+
+#[tokio::test]
+async fn test_run_eval_covers_every_case_on_every_model() {
+    let report = run_suite().await;
+
+    let runs = report
+        .results
+        .iter()
+        .map(|result| (result.model.model_id.as_str(), result.case.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        runs,
+        [
+            ("gpt-5-mini", "greet"),
+            ("gpt-5-mini", "math"),
+            ("claude-haiku-4-5", "greet"),
+            ("claude-haiku-4-5", "math"),
+        ]
+    );
+    assert_eq!(
+        report.results[0].output_text().as_deref(),
+        Some("Hello, \"world\"")
+    );
+    assert!(report.results[2].response.is_none());
+    assert!(
+        report.results[2]
+            .error
+            .as_deref()
+            .is_some_and(|error| error.contains("bad request"))
+    );
+
+    let summaries = report.summaries();
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(
+        summaries[0].model,
+        model_ref(ProviderId::Openai, "gpt-5-mini")
+    );
+    assert_eq!((summaries[0].cases, summaries[0].failures), (2, 0));
+    assert_eq!(summaries[0].usage.input_tokens, Some(20));
+    assert!((summaries[0].total_cost.expect("openai runs are priced") - 0.4).abs() < 1e-9);
+    assert_eq!(summaries[0].currency.as_deref(), Some("USD"));
+    assert_eq!(summaries[0].mismatched_currency_costs, 0);
+    assert_eq!((summaries[1].cases, summaries[1].failures), (2, 2));
+    assert_eq!(summaries[1].total_cost, None);
+}
+
+#[tokio::test]
+async fn test_summary_skips_costs_in_another_currency() {
+    let mut report = run_suite().await;
+    report.results[1]
+        .response
+        .as_mut()
+        .and_then(|response| response.cost.as_mut())
+        .expect("openai runs are priced")
+        .currency = "EUR".to_string();
+
+    let summaries = report.summaries();
+
+    assert!((summaries[0].total_cost.expect("first cost is summed") - 0.2).abs() < 1e-9);
+    assert_eq!(summaries[0].currency.as_deref(), Some("USD"));
+    assert_eq!(summaries[0].mismatched_currency_costs, 1);
+}
+
+#[tokio::test]
+async fn test_eval_report_exports_json_and_csv() {
+    let report = run_suite().await;
+
+    let json = report.to_json_pretty().expect("report should serialize");
+    let restored: EvalReport = serde_json::from_str(&json).expect("report should parse");
+    assert_eq!(restored, report);
+
+    let csv = report.to_csv();
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(
+        lines[0],
+        "case,provider,model,served_model,latency_ms,input_tokens,output_tokens,total_cost,currency,error,output"
+    );
+    let latency = report.results[0].latency_ms;
+    assert_eq!(
+        lines[1],
+        format!(
+            "greet,openai,gpt-5-mini,gpt-5-mini,{latency},10,5,0.2,USD,,\"Hello, \"\"world\"\"\""
+        )
+    );
+    assert!(lines[3].starts_with("greet,anthropic,claude-haiku-4-5,,"));
+}
@@ -0,0 +1,52 @@
+//! Image generation through the runtime's configured adapters and credentials.
+
+use crate::core::error::RuntimeError;
+use crate::core::types::{ImageGenerationRequest, ImageGenerationResponse};
+use crate::pricing;
+use crate::runtime::ProviderRuntime;
+
+impl ProviderRuntime {
+    /// Generates images for `request.prompt` with the provider resolved for `request.model`.
+    /// When the provider reports no cost, it is estimated from the pricing table's per-image
+    /// rules.
+    pub async fn generate_images(
+        &self,
+        mut request: ImageGenerationRequest,
+    ) -> Result<ImageGenerationResponse, RuntimeError> {
+        let provider = self
+            .registry
+            .resolve_route(&mut request.model, &self.call_context())?;
+        let adapter = self.registry.resolve_adapter(&provider)?;
+        let (ctx, lease) = self
+            .registry
+            .lease_credential(&provider, &self.call_context())
+            .await?;
+        let result = adapter.generate_images(&request, &ctx).await;
+        self.registry
+            .report_credential(&provider, lease.as_ref(), result.as_ref().err());
+        let Some(mut response) = result? else {
+            return Err(RuntimeError::CapabilityMismatch {
+                provider,
+                model: request.model.model_id,
+                capability: "image_generation".to_string(),
+            });
+        };
+
+        if response.cost.is_none()
+            && let Some(pricing_table) = &self.pricing_table
+        {
+            let (cost, warnings) = pricing::estimate_image_cost(
+                &response.provider,
+                &response.model,
+                response.images.len(),
+                pricing_table,
+            );
+            response.cost = cost;
+            response.warnings.extend(warnings);
+        }
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests;
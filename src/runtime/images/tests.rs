@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::core::error::{ProviderError, RuntimeError};
+use crate::core::traits::ProviderAdapter;
+use crate::core::types::{
+    AdapterContext, DiscoveryOptions, GeneratedImage, ImageGenerationRequest,
+    ImageGenerationResponse, ImageSource, ModelInfo, ModelRef, ProviderCapabilities, ProviderId,
+    ProviderRequest, ProviderResponse, Usage,
+};
+use crate::pricing::{ImagePriceRule, PricingTable};
+use crate::providers::mock::MockAdapter;
+use crate::runtime::ProviderRuntime;
+
+struct ImageAdapter;
+
+#[async_trait]
+impl ProviderAdapter for ImageAdapter {
+    fn id(&self) -> ProviderId {
+        ProviderId::Openai
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            supports_tools: false,
+            supports_structured_output: false,
+            supports_thinking: false,
+            supports_remote_discovery: false,
+            supports_parallel_tool_calls: false,
+        }
+    }
+
+    async fn run(
+        &self,
+        _req: &ProviderRequest,
+        _ctx: &AdapterContext,
+    ) -> Result<ProviderResponse, ProviderError> {
+        unreachable!("image tests never run chat requests")
+    }
+
+    async fn generate_images(
+        &self,
+        req: &ImageGenerationRequest,
+        _ctx: &AdapterContext,
+    ) -> Result<Option<ImageGenerationResponse>, ProviderError> {
+        let count = usize::from(req.n.unwrap_or(1));
+        Ok(Some(ImageGenerationResponse {
+            images: (0..count)
+                .map(|index| GeneratedImage {
+                    source: ImageSource::Url {
+                        url: format!("https://images.example/{index}.png"),
+                    },
+                    revised_prompt: None,
+                })
+                .collect(),
+            usage: Usage::default(),
+            cost: None,
+            provider: ProviderId::Openai,
+            model: req.model.model_id.clone(),
+            request_id: None,
+            warnings: Vec::new(),
+        }))
+    }
+
+    async fn discover_models(
+        &self,
+        _opts: &DiscoveryOptions,
+        _ctx: &AdapterContext,
+    ) -> Result<Vec<ModelInfo>, ProviderError> {
+        Ok(Vec::new())
+    }
+}
+
+fn request(n: u8) -> ImageGenerationRequest {
+    ImageGenerationRequest {
+        model: ModelRef {
+            provider_hint: Some(ProviderId::Openai),
+            model_id: "dall-e-3".to_string(),
+            requirements: None,
+        },
+        prompt: "a lighthouse at dusk".to_string(),
+        n: Some(n),
+        size: None,
+        quality: None,
+    }
+}
+
+#[tokio::test]
+async fn test_generate_images_prices_each_image() {
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(ImageAdapter))
+        .with_pricing_table(
+            PricingTable::new(Vec::new()).with_image_rule(ImagePriceRule {
+                provider: ProviderId::Openai,
+                model_pattern: "dall-e-*".to_string(),
+                cost_per_image: 0.04,
+                currency: None,
+            }),
+        )
+        .build();
+
+    let response = runtime
+        .generate_images(request(3))
+        .await
+        .expect("image generation should succeed");
+
+    assert_eq!(response.images.len(), 3);
+    let cost = response.cost.expect("cost should be estimated");
+    assert!((cost.total_cost - 0.12).abs() < 1e-12);
+    assert_eq!(cost.input_cost, 0.0);
+    assert!(response.warnings.is_empty());
+}
+
+#[tokio::test]
+async fn test_generate_images_without_image_rule_warns() {
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(ImageAdapter))
+        .with_pricing_table(PricingTable::new(Vec::new()))
+        .build();
+
+    let response = runtime
+        .generate_images(request(1))
+        .await
+        .expect("image generation should succeed");
+
+    assert!(response.cost.is_none());
+    assert_eq!(response.warnings[0].code, "pricing.missing_rule");
+}
+
+#[tokio::test]
+async fn test_generate_images_without_adapter_support_is_capability_mismatch() {
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(MockAdapter::new(ProviderId::Openai)))
+        .build();
+
+    let error = runtime
+        .generate_images(request(1))
+        .await
+        .expect_err("mock adapter has no image generation");
+
+    assert!(matches!(
+        error,
+        RuntimeError::CapabilityMismatch { ref capability, .. } if capability == "image_generation"
+    ));
+}
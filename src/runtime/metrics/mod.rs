@@ -0,0 +1,105 @@
+use crate::core::error::RuntimeError;
+use crate::core::types::{ProviderId, ProviderResponse, WarningCode};
+use crate::runtime::trace::RunTrace;
+
+/// Counter: one per `ProviderRuntime::run` call, labeled with `outcome` (`success`, `error`,
+/// `cache_hit`, or `budget_exceeded`).
+pub const REQUESTS: &str = "provider_runtime.requests";
+/// Histogram: wall-clock duration of each run in milliseconds, including retries and failover.
+pub const REQUEST_LATENCY_MS: &str = "provider_runtime.request_latency_ms";
+/// Counter: HTTP attempts beyond the first made by the transport's retry policy.
+pub const RETRIES: &str = "provider_runtime.retries";
+pub const INPUT_TOKENS: &str = "provider_runtime.input_tokens";
+pub const OUTPUT_TOKENS: &str = "provider_runtime.output_tokens";
+/// Histogram: computed or provider-reported cost per run, labeled with `currency`.
+pub const COST: &str = "provider_runtime.cost";
+/// Histogram: milliseconds an adapter call waited for a `with_max_concurrency` slot.
+pub const QUEUE_WAIT_MS: &str = "provider_runtime.queue_wait_ms";
+
+/// Sink for runtime metrics, e.g. an adapter over a Prometheus or StatsD client.
+///
+/// Every metric carries `provider` and `model` labels; `provider` is `unknown` when routing
+/// failed before a provider was chosen. Both methods default to no-ops, so implementations only
+/// override what they export.
+pub trait RuntimeMetrics: Send + Sync {
+    fn increment_counter(&self, name: &str, value: u64, labels: &[(&str, &str)]) {
+        let _ = (name, value, labels);
+    }
+
+    fn record_histogram(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+        let _ = (name, value, labels);
+    }
+}
+
+/// A `RuntimeMetrics` that discards everything.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopMetrics;
+
+impl RuntimeMetrics for NoopMetrics {}
+
+/// Reports one finished run, using its trace for latency and transport attempts. Cache hits
+/// count as requests but not toward tokens or cost, which their original call already reported.
+pub(crate) fn record_run(
+    metrics: &dyn RuntimeMetrics,
+    trace: &RunTrace,
+    result: &Result<ProviderResponse, RuntimeError>,
+) {
+    let provider = match result {
+        Ok(response) => Some(&response.provider),
+        Err(_) => trace.provider.as_ref(),
+    }
+    .map_or("unknown", ProviderId::name);
+    let model = match result {
+        Ok(response) => response.model.as_str(),
+        Err(_) => trace.requested_model.model_id.as_str(),
+    };
+    let outcome = match result {
+        Ok(response)
+            if response
+                .warnings
+                .iter()
+                .any(|warning| warning.code == WarningCode::CacheHit) =>
+        {
+            "cache_hit"
+        }
+        Ok(_) => "success",
+        Err(RuntimeError::BudgetExceeded { .. }) => "budget_exceeded",
+        Err(_) => "error",
+    };
+
+    let labels = [("provider", provider), ("model", model)];
+    metrics.increment_counter(REQUESTS, 1, &[labels[0], labels[1], ("outcome", outcome)]);
+    metrics.record_histogram(REQUEST_LATENCY_MS, trace.elapsed_ms as f64, &labels);
+
+    let retries = trace
+        .attempts
+        .iter()
+        .filter(|attempt| attempt.attempt > 1)
+        .count() as u64;
+    if retries > 0 {
+        metrics.increment_counter(RETRIES, retries, &labels);
+    }
+
+    let Ok(response) = result else {
+        return;
+    };
+    if outcome == "cache_hit" {
+        return;
+    }
+    if let Some(input_tokens) = response.usage.input_tokens {
+        metrics.increment_counter(INPUT_TOKENS, input_tokens, &labels);
+    }
+    if let Some(output_tokens) = response.usage.output_tokens {
+        metrics.increment_counter(OUTPUT_TOKENS, output_tokens, &labels);
+    }
+    if let Some(cost) = &response.cost {
+        metrics.record_histogram(
+            COST,
+            cost.total_cost,
+            &[labels[0], labels[1], ("currency", cost.currency.as_str())],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests;
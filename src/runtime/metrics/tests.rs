@@ -0,0 +1,251 @@
+use std::sync::{Arc, Mutex};
+
+use super::{
+    COST, INPUT_TOKENS, OUTPUT_TOKENS, REQUEST_LATENCY_MS, REQUESTS, RETRIES, RuntimeMetrics,
+    record_run,
+};
+use crate::core::error::RuntimeError;
+use crate::core::types::{
+    AssistantOutput, ContentPart, CostBreakdown, FinishReason, Message, MessageRole, ModelRef,
+    PricingSource, ProviderId, ProviderRequest, ProviderResponse, Usage,
+};
+use crate::providers::mock::{MockAdapter, MockMatcher, MockOutcome};
+use crate::runtime::ProviderRuntime;
+use crate::runtime::budget::BudgetTracker;
+use crate::runtime::cache::{CachePolicy, InMemoryResponseCache};
+use crate::runtime::context::RequestContext;
+use crate::runtime::trace::{RunTrace, TraceAttempt};
+
+type Labels = Vec<(String, String)>;
+
+#[derive(Default)]
+struct Recorded {
+    counters: Mutex<Vec<(String, u64, Labels)>>,
+    histograms: Mutex<Vec<(String, f64)>>,
+}
+
+impl Recorded {
+    fn counter(&self, name: &str) -> Option<u64> {
+        self.counters
+            .lock()
+            .expect("counters lock")
+            .iter()
+            .find(|(counter, _, _)| counter == name)
+            .map(|(_, value, _)| *value)
+    }
+
+    fn outcomes(&self) -> Vec<String> {
+        self.counters
+            .lock()
+            .expect("counters lock")
+            .iter()
+            .filter(|(counter, _, _)| counter == REQUESTS)
+            .filter_map(|(_, _, labels)| {
+                labels
+                    .iter()
+                    .find(|(key, _)| key == "outcome")
+                    .map(|(_, value)| value.clone())
+            })
+            .collect()
+    }
+
+    fn histogram_names(&self) -> Vec<String> {
+        self.histograms
+            .lock()
+            .expect("histograms lock")
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+impl RuntimeMetrics for Recorded {
+    fn increment_counter(&self, name: &str, value: u64, labels: &[(&str, &str)]) {
+        self.counters.lock().expect("counters lock").push((
+            name.to_string(),
+            value,
+            labels
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        ));
+    }
+
+    fn record_histogram(&self, name: &str, value: f64, _labels: &[(&str, &str)]) {
+        self.histograms
+            .lock()
+            .expect("histograms lock")
+            .push((name.to_string(), value));
+    }
+}
+
+fn model_ref() -> ModelRef {
+    ModelRef {
+        provider_hint: Some(ProviderId::Openai),
+        model_id: "gpt-5-mini".to_string(),
+        requirements: None,
+    }
+}
+
+fn response() -> ProviderResponse {
+    ProviderResponse {
+        output: AssistantOutput {
+            content: vec![ContentPart::Text {
+                text: "hi".to_string(),
+            }],
+            structured_output: None,
+            annotations: Vec::new(),
+        },
+        usage: Usage {
+            input_tokens: Some(12),
+            output_tokens: Some(3),
+            ..Usage::default()
+        },
+        cost: Some(CostBreakdown {
+            currency: "USD".to_string(),
+            input_cost: 0.001,
+            cached_input_cost: 0.0,
+            output_cost: 0.002,
+            fixed_cost: 0.0,
+            total_cost: 0.003,
+            pricing_source: PricingSource::Configured,
+        }),
+        provider: ProviderId::Openai,
+        model: "gpt-5-mini".to_string(),
+        raw_provider_response: None,
+        request_id: None,
+        rate_limit: None,
+        finish_reason: FinishReason::Stop,
+        warnings: Vec::new(),
+        alternatives: Vec::new(),
+        response_id: None,
+    }
+}
+
+fn attempt(attempt: u32) -> TraceAttempt {
+    TraceAttempt {
+        attempt,
+        method: "POST".to_string(),
+        url: "https://api.openai.com/v1/responses".to_string(),
+        status_code: Some(if attempt == 1 { 503 } else { 200 }),
+        request_id: None,
+        elapsed_ms: 5,
+        error: None,
+    }
+}
+
+#[tokio::test]
+async fn test_runtime_reports_usage_and_cost_metrics() {
+    let metrics = Arc::new(Recorded::default());
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(
+            MockAdapter::new(ProviderId::Openai)
+                .on(MockMatcher::any(), MockOutcome::response(response())),
+        ))
+        .with_metrics(metrics.clone())
+        .build();
+
+    runtime
+        .run(ProviderRequest::new(
+            model_ref(),
+            vec![Message {
+                role: MessageRole::User,
+                content: vec![ContentPart::Text {
+                    text: "hello".to_string(),
+                }],
+                cache_hint: None,
+            }],
+        ))
+        .await
+        .expect("run should succeed");
+
+    assert_eq!(metrics.counter(REQUESTS), Some(1));
+    assert_eq!(metrics.counter(INPUT_TOKENS), Some(12));
+    assert_eq!(metrics.counter(OUTPUT_TOKENS), Some(3));
+    assert_eq!(metrics.counter(RETRIES), None);
+    assert_eq!(metrics.histogram_names(), vec![REQUEST_LATENCY_MS, COST]);
+
+    let counters = metrics.counters.lock().expect("counters lock");
+    let labels = &counters[0].2;
+    assert!(labels.contains(&("provider".to_string(), "openai".to_string())));
+    assert!(labels.contains(&("outcome".to_string(), "success".to_string())));
+}
+
+#[tokio::test]
+async fn test_runtime_reports_cache_hits_and_budget_rejections() {
+    let metrics = Arc::new(Recorded::default());
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(
+            MockAdapter::new(ProviderId::Openai)
+                .on(MockMatcher::any(), MockOutcome::response(response())),
+        ))
+        .with_response_cache(Arc::new(InMemoryResponseCache::new(8)), CachePolicy::All)
+        .with_budget(Arc::new(
+            BudgetTracker::new("tenant").with_spend_cap("acme", 0.001),
+        ))
+        .with_metrics(metrics.clone())
+        .with_run_trace_capture(4)
+        .build();
+    let run = |text: &str| {
+        RequestContext::new()
+            .with_tenant("acme")
+            .scope(runtime.run(ProviderRequest::new(
+                model_ref(),
+                vec![Message {
+                    role: MessageRole::User,
+                    content: vec![ContentPart::Text {
+                        text: text.to_string(),
+                    }],
+                    cache_hint: None,
+                }],
+            )))
+    };
+
+    run("hello").await.expect("first run should succeed");
+    run("hello").await.expect("repeat should hit the cache");
+    let err = run("goodbye").await.expect_err("spend cap reached");
+    assert!(matches!(err, RuntimeError::BudgetExceeded { .. }));
+
+    assert_eq!(
+        metrics.outcomes(),
+        vec!["success", "cache_hit", "budget_exceeded"]
+    );
+    assert_eq!(metrics.counter(INPUT_TOKENS), Some(12));
+    assert_eq!(runtime.recent_run_traces().len(), 3);
+}
+
+#[test]
+fn test_record_run_counts_retries_and_labels_failures() {
+    let metrics = Recorded::default();
+    let trace = RunTrace {
+        request_id: None,
+        requested_model: model_ref(),
+        provider: None,
+        encoded_request: None,
+        attempts: vec![attempt(1), attempt(2), attempt(3)],
+        raw_response: None,
+        provider_debug: None,
+        warnings: Vec::new(),
+        response: None,
+        error: None,
+        elapsed_ms: 40,
+    };
+    let result = Err(RuntimeError::MiddlewareRejected {
+        message: "blocked".to_string(),
+    });
+
+    record_run(&metrics, &trace, &result);
+
+    assert_eq!(metrics.counter(RETRIES), Some(2));
+    assert_eq!(metrics.counter(INPUT_TOKENS), None);
+    assert_eq!(metrics.histogram_names(), vec![REQUEST_LATENCY_MS]);
+    let counters = metrics.counters.lock().expect("counters lock");
+    assert_eq!(
+        counters[0].2,
+        vec![
+            ("provider".to_string(), "unknown".to_string()),
+            ("model".to_string(), "gpt-5-mini".to_string()),
+            ("outcome".to_string(), "error".to_string()),
+        ]
+    );
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::core::error::RuntimeError;
+use crate::core::types::{ProviderRequest, ProviderResponse};
+
+/// Hooks run around every adapter call made by `ProviderRuntime::run`, including fallback and
+/// hedged calls.
+///
+/// `before_request` hooks run in registration order before routing, so they can rewrite the
+/// prompt or model, or reject the request by returning an error. `after_response` and
+/// `on_error` run in reverse registration order, after cost estimation. All hooks default to
+/// no-ops.
+#[async_trait]
+pub trait RuntimeMiddleware: Send + Sync {
+    async fn before_request(&self, request: &mut ProviderRequest) -> Result<(), RuntimeError> {
+        let _ = request;
+        Ok(())
+    }
+
+    /// Inspects or rewrites a successful response. Returning an error fails the call.
+    async fn after_response(
+        &self,
+        request: &ProviderRequest,
+        response: &mut ProviderResponse,
+    ) -> Result<(), RuntimeError> {
+        let _ = (request, response);
+        Ok(())
+    }
+
+    /// Observes a failed call, including rejections from other middleware.
+    async fn on_error(&self, request: &ProviderRequest, error: &RuntimeError) {
+        let _ = (request, error);
+    }
+}
+
+/// Registered middleware, applied as a stack.
+#[derive(Clone, Default)]
+pub(crate) struct MiddlewareStack {
+    layers: Vec<Arc<dyn RuntimeMiddleware>>,
+}
+
+impl MiddlewareStack {
+    pub(crate) fn push(&mut self, middleware: Arc<dyn RuntimeMiddleware>) {
+        self.layers.push(middleware);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    pub(crate) async fn before_request(
+        &self,
+        request: &mut ProviderRequest,
+    ) -> Result<(), RuntimeError> {
+        for layer in &self.layers {
+            layer.before_request(request).await?;
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn after_response(
+        &self,
+        request: &ProviderRequest,
+        response: &mut ProviderResponse,
+    ) -> Result<(), RuntimeError> {
+        for layer in self.layers.iter().rev() {
+            layer.after_response(request, response).await?;
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn on_error(&self, request: &ProviderRequest, error: &RuntimeError) {
+        for layer in self.layers.iter().rev() {
+            layer.on_error(request, error).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
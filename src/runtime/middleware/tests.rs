@@ -0,0 +1,149 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use super::RuntimeMiddleware;
+use crate::core::error::RuntimeError;
+use crate::core::types::{
+    ContentPart, Message, MessageRole, ModelRef, ProviderId, ProviderRequest, ProviderResponse,
+    RuntimeWarning,
+};
+use crate::providers::mock::{MockAdapter, MockMatcher, MockOutcome};
+use crate::runtime::ProviderRuntime;
+
+struct Recorder {
+    name: &'static str,
+    events: Arc<Mutex<Vec<String>>>,
+    reject: bool,
+}
+
+#[async_trait]
+impl RuntimeMiddleware for Recorder {
+    async fn before_request(&self, request: &mut ProviderRequest) -> Result<(), RuntimeError> {
+        self.events
+            .lock()
+            .expect("events lock")
+            .push(format!("{}.before", self.name));
+        if self.reject {
+            return Err(RuntimeError::MiddlewareRejected {
+                message: "blocked by guardrail".to_string(),
+            });
+        }
+        request
+            .metadata
+            .insert("audited_by".to_string(), self.name.to_string());
+        Ok(())
+    }
+
+    async fn after_response(
+        &self,
+        _request: &ProviderRequest,
+        response: &mut ProviderResponse,
+    ) -> Result<(), RuntimeError> {
+        self.events
+            .lock()
+            .expect("events lock")
+            .push(format!("{}.after", self.name));
+        response.warnings.push(RuntimeWarning {
+            code: format!("test.{}", self.name).into(),
+            message: "seen".to_string(),
+        });
+        Ok(())
+    }
+
+    async fn on_error(&self, _request: &ProviderRequest, error: &RuntimeError) {
+        self.events
+            .lock()
+            .expect("events lock")
+            .push(format!("{}.error: {error}", self.name));
+    }
+}
+
+fn request() -> ProviderRequest {
+    ProviderRequest::new(
+        ModelRef {
+            provider_hint: Some(ProviderId::Openai),
+            model_id: "gpt-5-mini".to_string(),
+            requirements: None,
+        },
+        vec![Message {
+            role: MessageRole::User,
+            content: vec![ContentPart::Text {
+                text: "hello".to_string(),
+            }],
+            cache_hint: None,
+        }],
+    )
+}
+
+#[tokio::test]
+async fn test_middleware_wraps_adapter_calls_in_stack_order() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let adapter = Arc::new(
+        MockAdapter::new(ProviderId::Openai).on(MockMatcher::any(), MockOutcome::text("hi")),
+    );
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_middleware(Arc::new(Recorder {
+            name: "outer",
+            events: events.clone(),
+            reject: false,
+        }))
+        .with_middleware(Arc::new(Recorder {
+            name: "inner",
+            events: events.clone(),
+            reject: false,
+        }))
+        .build();
+
+    let response = runtime.run(request()).await.expect("run should succeed");
+
+    assert_eq!(
+        *events.lock().expect("events lock"),
+        vec!["outer.before", "inner.before", "inner.after", "outer.after"]
+    );
+    let codes = response
+        .warnings
+        .iter()
+        .map(|warning| warning.code.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(codes, vec!["test.inner", "test.outer"]);
+    assert_eq!(
+        adapter.requests()[0]
+            .metadata
+            .get("audited_by")
+            .map(String::as_str),
+        Some("inner")
+    );
+}
+
+#[tokio::test]
+async fn test_middleware_rejection_skips_dispatch_and_reports_error() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let adapter = Arc::new(
+        MockAdapter::new(ProviderId::Openai).on(MockMatcher::any(), MockOutcome::text("hi")),
+    );
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_middleware(Arc::new(Recorder {
+            name: "guardrail",
+            events: events.clone(),
+            reject: true,
+        }))
+        .build();
+
+    let err = runtime
+        .run(request())
+        .await
+        .expect_err("guardrail should reject");
+
+    assert!(matches!(err, RuntimeError::MiddlewareRejected { .. }));
+    assert!(adapter.requests().is_empty());
+    assert_eq!(
+        *events.lock().expect("events lock"),
+        vec![
+            "guardrail.before",
+            "guardrail.error: rejected by middleware: blocked by guardrail"
+        ]
+    );
+}
@@ -1,19 +1,101 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_util::stream::{self, StreamExt};
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
 
 use crate::catalog;
-use crate::core::error::RuntimeError;
-use crate::core::traits::ProviderAdapter;
+use crate::core::error::{ConfigError, ErrorSource, ProviderError, RoutingError, RuntimeError};
+use crate::core::traits::{CredentialStore, ProviderAdapter, Redactor, TokenProvider};
 use crate::core::types::{
-    AdapterContext, DiscoveryOptions, ModelCatalog, ProviderId, ProviderRequest, ProviderResponse,
-    ResponseFormat,
+    AdapterContext, CostBreakdown, DiscoveryOptions, ModelAlias, ModelCatalog, ModelRef,
+    ProviderId, ProviderRequest, ProviderResponse, ResponseFormat, RuntimeWarning, WarningCode,
 };
 use crate::pricing::{self, PricingTable};
-use crate::registry::registry::ProviderRegistry;
+use crate::registry::{DiscoveryReport, KeyPool, ProviderHealth, ProviderRegistry, RoutingPolicy};
+use crate::transport::redaction::DefaultRedactor;
+
+pub mod audio;
+pub mod budget;
+pub mod cache;
+pub mod config;
+pub mod context;
+pub mod embeddings;
+pub mod eval;
+pub mod images;
+pub mod metrics;
+pub mod middleware;
+pub mod moderation;
+pub mod prompt;
+pub mod session;
+pub mod structured;
+pub mod tokens;
+pub mod tool_loop;
+pub mod trace;
+pub mod transform;
+pub mod truncation;
+
+use budget::BudgetTracker;
+use cache::{CachePolicy, InFlightDedup, ResponseCache};
+use context::RequestContext;
+use metrics::RuntimeMetrics;
+use middleware::{MiddlewareStack, RuntimeMiddleware};
+use tokens::{HeuristicTokenCounter, TokenCountSource, TokenCounter, TokenEstimate};
+use tool_loop::{ToolLoopOptions, ToolLoopOutcome, ToolLoopStop, ToolSet};
+use trace::{RunTrace, TraceRecorder, TraceStore};
+use transform::ResponseTransform;
+use truncation::TruncationStrategy;
+
+/// How often a caller waiting on another caller's in-flight request re-checks the response cache.
+const DEDUP_POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+/// Callback invoked with the encode-time warnings of each request before it is dispatched.
+pub type EncodeWarningHook = Arc<dyn Fn(&ProviderRequest, &[RuntimeWarning]) + Send + Sync>;
 
 pub struct ProviderRuntime {
     registry: ProviderRegistry,
     adapter_context: AdapterContext,
     pricing_table: Option<PricingTable>,
+    trace_store: Option<TraceStore>,
+    fail_fast_warning_codes: BTreeSet<WarningCode>,
+    encode_warning_hook: Option<EncodeWarningHook>,
+    fallback_chains: Vec<FallbackChain>,
+    hedges: Vec<HedgePolicy>,
+    middleware: MiddlewareStack,
+    metrics: Option<Arc<dyn RuntimeMetrics>>,
+    budget: Option<Arc<BudgetTracker>>,
+    response_cache: Option<(Arc<dyn ResponseCache>, CachePolicy)>,
+    request_dedup: Option<(Arc<dyn InFlightDedup>, Duration)>,
+    concurrency_limits: BTreeMap<ProviderId, Arc<Semaphore>>,
+    token_counter: Arc<dyn TokenCounter>,
+    truncation: Option<TruncationStrategy>,
+    catalog_output_token_defaults: bool,
+    structured_output_repair_attempts: u32,
+    request_deadline: Option<Duration>,
+    redactor: Arc<dyn Redactor>,
+    response_transforms: Vec<Arc<dyn ResponseTransform>>,
+    #[cfg(feature = "jsonschema")]
+    structured_output_policy: structured::StructuredOutputPolicy,
+}
+
+/// Models tried in order when a request for a model matching `model_pattern` fails with a
+/// retryable error.
+#[derive(Debug, Clone)]
+struct FallbackChain {
+    model_pattern: String,
+    fallbacks: Vec<ModelRef>,
+}
+
+/// A second model raced against requests for models matching `model_pattern` once the primary
+/// has been outstanding for `delay`.
+#[derive(Debug, Clone)]
+struct HedgePolicy {
+    model_pattern: String,
+    hedge_model: ModelRef,
+    delay: Duration,
 }
 
 pub struct ProviderRuntimeBuilder {
@@ -22,6 +104,34 @@ pub struct ProviderRuntimeBuilder {
     default_provider: Option<ProviderId>,
     pricing_table: Option<PricingTable>,
     adapter_context: AdapterContext,
+    trace_capacity: Option<usize>,
+    discovery_ttls: BTreeMap<ProviderId, Duration>,
+    discovery_timeout: Option<Duration>,
+    unhealthy_threshold: Option<u32>,
+    key_pools: Vec<(ProviderId, KeyPool)>,
+    credential_store: Option<Arc<dyn CredentialStore>>,
+    token_providers: Vec<(ProviderId, Arc<dyn TokenProvider>)>,
+    model_aliases: Vec<ModelAlias>,
+    routing_policies: Vec<(String, RoutingPolicy)>,
+    fail_fast_warning_codes: BTreeSet<WarningCode>,
+    encode_warning_hook: Option<EncodeWarningHook>,
+    fallback_chains: Vec<FallbackChain>,
+    hedges: Vec<HedgePolicy>,
+    middleware: MiddlewareStack,
+    metrics: Option<Arc<dyn RuntimeMetrics>>,
+    budget: Option<Arc<BudgetTracker>>,
+    response_cache: Option<(Arc<dyn ResponseCache>, CachePolicy)>,
+    request_dedup: Option<(Arc<dyn InFlightDedup>, Duration)>,
+    concurrency_limits: BTreeMap<ProviderId, Arc<Semaphore>>,
+    token_counter: Arc<dyn TokenCounter>,
+    truncation: Option<TruncationStrategy>,
+    catalog_output_token_defaults: bool,
+    structured_output_repair_attempts: u32,
+    request_deadline: Option<Duration>,
+    redactor: Arc<dyn Redactor>,
+    response_transforms: Vec<Arc<dyn ResponseTransform>>,
+    #[cfg(feature = "jsonschema")]
+    structured_output_policy: structured::StructuredOutputPolicy,
 }
 
 impl ProviderRuntime {
@@ -32,11 +142,487 @@ impl ProviderRuntime {
             default_provider: None,
             pricing_table: None,
             adapter_context: AdapterContext::default(),
+            trace_capacity: None,
+            discovery_ttls: BTreeMap::new(),
+            discovery_timeout: None,
+            unhealthy_threshold: None,
+            key_pools: Vec::new(),
+            credential_store: None,
+            token_providers: Vec::new(),
+            model_aliases: Vec::new(),
+            routing_policies: Vec::new(),
+            fail_fast_warning_codes: BTreeSet::new(),
+            encode_warning_hook: None,
+            fallback_chains: Vec::new(),
+            hedges: Vec::new(),
+            middleware: MiddlewareStack::default(),
+            metrics: None,
+            budget: None,
+            response_cache: None,
+            request_dedup: None,
+            concurrency_limits: BTreeMap::new(),
+            token_counter: Arc::new(HeuristicTokenCounter),
+            truncation: None,
+            catalog_output_token_defaults: false,
+            structured_output_repair_attempts: 0,
+            request_deadline: None,
+            redactor: Arc::new(DefaultRedactor::new()),
+            response_transforms: Vec::new(),
+            #[cfg(feature = "jsonschema")]
+            structured_output_policy: structured::StructuredOutputPolicy::default(),
         }
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "provider_runtime.run",
+            target = "provider_runtime",
+            skip_all,
+            fields(
+                model = %request.model.model_id,
+                provider = tracing::field::Empty,
+                served_model = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn run(&self, request: ProviderRequest) -> Result<ProviderResponse, RuntimeError> {
-        let provider = self.registry.resolve_provider(&request.model)?;
+        #[cfg(feature = "tracing")]
+        let started_at = std::time::Instant::now();
+        // Boxed so the deadline scope does not put the whole run future on the caller's stack.
+        let run = Box::pin(self.run_in_context(request));
+        let result = match self.request_deadline {
+            Some(timeout) => {
+                let mut context = RequestContext::current().unwrap_or_default();
+                let deadline = Instant::now() + timeout;
+                context.deadline = Some(context.deadline.map_or(deadline, |set| set.min(deadline)));
+                context.scope(run).await
+            }
+            None => run.await,
+        };
+        let result = self.redact_result(result);
+        #[cfg(feature = "tracing")]
+        crate::telemetry::record_run_result(&result, started_at);
+        result
+    }
+
+    async fn run_in_context(
+        &self,
+        mut request: ProviderRequest,
+    ) -> Result<ProviderResponse, RuntimeError> {
+        match self.truncate_to_fit(&mut request).await {
+            Ok(truncation) => self.run_captured(request).await.map(|mut response| {
+                truncation.apply(&mut response);
+                response
+            }),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Runs `request` with `ctx`'s metadata layered over the builder's adapter context, so one
+    /// runtime can serve callers with their own API keys, headers, or tracing metadata. Same as
+    /// running inside a [`RequestContext`] scope that sets `adapter_metadata`.
+    pub async fn run_with_context(
+        &self,
+        request: ProviderRequest,
+        ctx: AdapterContext,
+    ) -> Result<ProviderResponse, RuntimeError> {
+        let mut context = RequestContext::current().unwrap_or_default();
+        context.adapter_metadata.extend(ctx.metadata);
+        context.scope(self.run(request)).await
+    }
+
+    /// Runs each of `requests` through `run`, at most `max_concurrency` at a time, and returns
+    /// their results in request order. One failed request does not stop the others.
+    pub async fn run_batch(
+        &self,
+        requests: impl IntoIterator<Item = ProviderRequest>,
+        max_concurrency: usize,
+    ) -> Vec<Result<ProviderResponse, RuntimeError>> {
+        self.run_batch_timed(requests, max_concurrency)
+            .await
+            .into_iter()
+            .map(|(result, _)| result)
+            .collect()
+    }
+
+    /// `run_batch`, with each run's wall-clock latency in milliseconds.
+    pub(crate) async fn run_batch_timed(
+        &self,
+        requests: impl IntoIterator<Item = ProviderRequest>,
+        max_concurrency: usize,
+    ) -> Vec<(Result<ProviderResponse, RuntimeError>, u64)> {
+        let runs = requests.into_iter().map(|request| async move {
+            let started_at = Instant::now();
+            let result = self.run(request).await;
+            (result, trace::elapsed_ms(started_at))
+        });
+        stream::iter(runs)
+            .buffered(max_concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// The builder's adapter context with the active [`RequestContext`]'s adapter metadata
+    /// layered on top.
+    pub(crate) fn call_context(&self) -> Cow<'_, AdapterContext> {
+        match RequestContext::current().filter(RequestContext::has_adapter_metadata) {
+            Some(context) => Cow::Owned(context.apply_adapter_metadata(&self.adapter_context)),
+            None => Cow::Borrowed(&self.adapter_context),
+        }
+    }
+
+    /// Passes the error message or the response's warning messages through the redactor.
+    fn redact_result(
+        &self,
+        mut result: Result<ProviderResponse, RuntimeError>,
+    ) -> Result<ProviderResponse, RuntimeError> {
+        match &mut result {
+            Ok(response) => {
+                for warning in &mut response.warnings {
+                    warning.message = self.redactor.redact(&warning.message);
+                }
+            }
+            Err(error) => {
+                if let Some(message) = error.message_mut() {
+                    *message = self.redactor.redact(message);
+                }
+            }
+        }
+        result
+    }
+
+    /// Redacts the captured bodies and attempt errors of a finished trace. Its error, response,
+    /// and warnings come from the already redacted result.
+    fn redact_trace(&self, trace: &mut RunTrace) {
+        for body in [
+            &mut trace.encoded_request,
+            &mut trace.raw_response,
+            &mut trace.provider_debug,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            self.redactor.redact_json(body);
+        }
+        for error in trace
+            .attempts
+            .iter_mut()
+            .filter_map(|attempt| attempt.error.as_mut())
+        {
+            *error = self.redactor.redact(error);
+        }
+    }
+
+    /// Answers eligible requests from the response cache, storing successful responses. Cache
+    /// hits carry a `runtime.cache_hit` warning and skip budget checks. With request dedup,
+    /// concurrent misses wait for the caller holding the key's claim instead of each running.
+    async fn run_cached(&self, request: ProviderRequest) -> Result<ProviderResponse, RuntimeError> {
+        let Some(((cache, _), key)) = self
+            .response_cache
+            .as_ref()
+            .filter(|(_, policy)| policy.allows(&request))
+            .and_then(|entry| {
+                cache::cache_key(&request, &self.call_context()).map(|key| (entry, key))
+            })
+        else {
+            return self.run_accounted(request).await;
+        };
+
+        if let Some(response) = cached_response(cache.as_ref(), &key).await {
+            return Ok(response);
+        }
+
+        let mut claimed = None;
+        if let Some((dedup, max_wait)) = &self.request_dedup {
+            let deadline = tokio::time::Instant::now() + *max_wait;
+            loop {
+                if let Some(token) = dedup.claim(&key, *max_wait).await {
+                    claimed = Some((dedup, token));
+                    break;
+                }
+                let now = tokio::time::Instant::now();
+                if now >= deadline {
+                    break;
+                }
+                tokio::time::sleep(DEDUP_POLL_INTERVAL.min(deadline - now)).await;
+                if let Some(response) = cached_response(cache.as_ref(), &key).await {
+                    return Ok(response);
+                }
+            }
+        }
+
+        let result = self.run_accounted(request).await;
+        if let Ok(response) = &result {
+            cache.put(key.clone(), response.clone()).await;
+        }
+        if let Some((dedup, token)) = claimed {
+            dedup.release(&key, &token).await;
+        }
+        result
+    }
+
+    /// Enforces and records the configured budget around the run.
+    async fn run_accounted(
+        &self,
+        request: ProviderRequest,
+    ) -> Result<ProviderResponse, RuntimeError> {
+        let Some((budget, key)) = self.budget.as_ref().and_then(|budget| {
+            budget
+                .accounting_key(&self.call_context())
+                .map(|key| (budget, key))
+        }) else {
+            return self.run_request(request).await;
+        };
+
+        budget.check(&key)?;
+        let response = self.run_request(request).await?;
+        budget.record(&key, &response);
+        Ok(response)
+    }
+
+    /// Captures the trace and metrics of the run, including cache hits and budget rejections.
+    async fn run_captured(
+        &self,
+        request: ProviderRequest,
+    ) -> Result<ProviderResponse, RuntimeError> {
+        if self.trace_store.is_none() && self.metrics.is_none() {
+            return self.run_cached(request).await;
+        }
+
+        // Metrics read latency and transport attempts from the trace, so one is recorded even
+        // when trace capture is off.
+        let recorder = TraceRecorder::new(&request.model);
+        let result = trace::scope(recorder.clone(), self.run_cached(request)).await;
+        // `run` redacts the returned result; the trace gets its own redacted copy, taken before
+        // it renders the error, while the message is still a bare body.
+        let redacted = self.redact_result(result.clone());
+        let mut trace = recorder.finish(&redacted);
+        self.redact_trace(&mut trace);
+        if let Some(metrics) = &self.metrics {
+            metrics::record_run(metrics.as_ref(), &trace, &redacted);
+        }
+        if let Some(trace_store) = &self.trace_store {
+            trace_store.push(trace);
+        }
+        result
+    }
+
+    /// Runs `request` as a tool loop: tool calls in each response are executed through `tools`
+    /// and their results sent back, until the model stops calling tools or
+    /// `options.max_iterations` provider calls have been made. Definitions from `tools` are
+    /// added to `request.tools` unless a tool of the same name is already declared.
+    pub async fn run_with_tools(
+        &self,
+        mut request: ProviderRequest,
+        tools: &ToolSet,
+        options: ToolLoopOptions,
+    ) -> Result<ToolLoopOutcome, RuntimeError> {
+        for definition in tools.definitions() {
+            if !request
+                .tools
+                .iter()
+                .any(|tool| tool.name == definition.name)
+            {
+                request.tools.push(definition);
+            }
+        }
+
+        let mut iterations = 0;
+        loop {
+            iterations += 1;
+            let response = self.run(request.clone()).await?;
+            request
+                .messages
+                .push(tool_loop::assistant_message(&response));
+
+            let stop = match tool_loop::pending_tool_calls(&response) {
+                None => ToolLoopStop::Completed,
+                Some(_) if iterations >= options.max_iterations => ToolLoopStop::MaxIterations,
+                Some(calls) => {
+                    let results = tools.execute_all(&calls).await;
+                    request
+                        .messages
+                        .push(tool_loop::tool_results_message(results));
+                    continue;
+                }
+            };
+
+            return Ok(ToolLoopOutcome {
+                response,
+                messages: request.messages,
+                iterations,
+                stop,
+            });
+        }
+    }
+
+    /// Returns the most recent captured trace whose provider request id matches.
+    pub fn run_trace(&self, request_id: &str) -> Option<RunTrace> {
+        self.trace_store
+            .as_ref()
+            .and_then(|store| store.find(request_id))
+    }
+
+    /// Returns captured traces, oldest first. Empty unless trace capture is enabled.
+    pub fn recent_run_traces(&self) -> Vec<RunTrace> {
+        self.trace_store
+            .as_ref()
+            .map(TraceStore::recent)
+            .unwrap_or_default()
+    }
+
+    /// Runs `request`, failing over along the first matching fallback chain while attempts fail
+    /// with retryable errors. Once the request context deadline passes, no further fallback is
+    /// tried and the error becomes a timeout naming every model attempted.
+    async fn run_request(
+        &self,
+        request: ProviderRequest,
+    ) -> Result<ProviderResponse, RuntimeError> {
+        let deadline = RequestContext::current().and_then(|context| context.deadline);
+        let expired = || deadline.is_some_and(|deadline| Instant::now() >= deadline);
+        let mut attempted = vec![request.model.clone()];
+        let Some(chain) = self.fallback_chains.iter().find(|chain| {
+            pricing::match_pattern(&chain.model_pattern, &request.model.model_id).is_some()
+        }) else {
+            return self.run_hedged(request).await.map_err(|error| {
+                if expired() {
+                    deadline_exceeded(error, &attempted)
+                } else {
+                    error
+                }
+            });
+        };
+
+        let mut error = match self.run_hedged(request.clone()).await {
+            Ok(response) => return Ok(response),
+            Err(error) => error,
+        };
+        let mut failover_warnings = Vec::new();
+
+        for fallback in &chain.fallbacks {
+            if expired() || !is_failover_error(&error) {
+                break;
+            }
+            if fallback
+                .provider_hint
+                .as_ref()
+                .is_some_and(|provider| !self.registry.is_healthy(provider))
+            {
+                continue;
+            }
+            failover_warnings.push(RuntimeWarning {
+                code: WarningCode::ProviderFailover,
+                message: format!(
+                    "{} failed ({error}); retrying with {}",
+                    describe_model(attempted.last().unwrap_or(&request.model)),
+                    describe_model(fallback)
+                ),
+            });
+
+            let mut fallback_request = request.clone();
+            fallback_request.model = fallback.clone();
+            attempted.push(fallback.clone());
+            match self.run_single(fallback_request).await {
+                Ok(mut response) => {
+                    failover_warnings.append(&mut response.warnings);
+                    response.warnings = failover_warnings;
+                    return Ok(response);
+                }
+                Err(next_error) => error = next_error,
+            }
+        }
+
+        if expired() {
+            error = deadline_exceeded(error, &attempted);
+        }
+        Err(error)
+    }
+
+    /// Runs `request`, racing it against the first matching hedge policy's model once the
+    /// primary has been outstanding for the hedge delay. The first successful response wins and
+    /// the other request is cancelled (see `ProviderRuntimeBuilder::with_hedging`). A primary
+    /// that fails before the delay is returned as-is, leaving failover to the fallback chains.
+    async fn run_hedged(&self, request: ProviderRequest) -> Result<ProviderResponse, RuntimeError> {
+        let Some(hedge) = self.hedges.iter().find(|hedge| {
+            pricing::match_pattern(&hedge.model_pattern, &request.model.model_id).is_some()
+        }) else {
+            return self.run_single(request).await;
+        };
+
+        let mut hedge_request = request.clone();
+        hedge_request.model = hedge.hedge_model.clone();
+        let primary_model = request.model.clone();
+
+        let primary = self.run_single(request);
+        tokio::pin!(primary);
+        tokio::select! {
+            result = &mut primary => return result,
+            _ = tokio::time::sleep(hedge.delay) => {}
+        }
+
+        let hedged = self.run_single(hedge_request);
+        tokio::pin!(hedged);
+        let with_hedge_warning = |mut response: ProviderResponse| {
+            response.warnings.push(RuntimeWarning {
+                code: WarningCode::HedgeWon,
+                message: format!(
+                    "hedged request to {} answered before {}",
+                    describe_model(&hedge.hedge_model),
+                    describe_model(&primary_model)
+                ),
+            });
+            response
+        };
+
+        tokio::select! {
+            result = &mut primary => match result {
+                Ok(response) => Ok(response),
+                Err(error) => hedged.await.map(with_hedge_warning).map_err(|_| error),
+            },
+            result = &mut hedged => match result {
+                Ok(response) => Ok(with_hedge_warning(response)),
+                Err(_) => primary.await,
+            },
+        }
+    }
+
+    /// Runs one adapter call wrapped in the middleware stack.
+    async fn run_single(
+        &self,
+        mut request: ProviderRequest,
+    ) -> Result<ProviderResponse, RuntimeError> {
+        if self.middleware.is_empty() {
+            return self.dispatch(request).await;
+        }
+
+        let result = match self.middleware.before_request(&mut request).await {
+            Ok(()) => match self.dispatch(request.clone()).await {
+                Ok(mut response) => self
+                    .middleware
+                    .after_response(&request, &mut response)
+                    .await
+                    .map(|()| response),
+                Err(error) => Err(error),
+            },
+            Err(error) => Err(error),
+        };
+
+        if let Err(error) = &result {
+            self.middleware.on_error(&request, error).await;
+        }
+        result
+    }
+
+    async fn dispatch(
+        &self,
+        mut request: ProviderRequest,
+    ) -> Result<ProviderResponse, RuntimeError> {
+        let selected = request.model.requirements.is_some();
+        let provider = self
+            .registry
+            .resolve_route(&mut request.model, &self.call_context())?;
+        trace::record_provider(&provider);
         let adapter = self.registry.resolve_adapter(&provider)?;
         let capabilities = adapter.capabilities();
 
@@ -58,36 +644,397 @@ impl ProviderRuntime {
             });
         }
 
-        let mut response = adapter.run(&request, &self.adapter_context).await?;
+        let mut runtime_warnings = Vec::new();
+        if selected {
+            runtime_warnings.push(RuntimeWarning {
+                code: WarningCode::ModelSelected,
+                message: format!(
+                    "selected {} to meet the model requirements",
+                    describe_model(&request.model)
+                ),
+            });
+        }
+        if request.parallel_tool_calls.is_some() && !capabilities.supports_parallel_tool_calls {
+            request.parallel_tool_calls = None;
+            runtime_warnings.push(RuntimeWarning {
+                code: WarningCode::ParallelToolCallsUnsupported,
+                message: format!(
+                    "provider {provider:?} cannot control parallel tool calls; parallel_tool_calls dropped"
+                ),
+            });
+        }
+
+        let deadline = RequestContext::current().and_then(|context| context.deadline);
+        runtime_warnings.extend(self.apply_catalog_output_limit(&provider, &mut request));
+        self.check_encode_warnings(&provider, adapter.as_ref(), &request)?;
+        let started = Instant::now();
+        let mut response = self
+            .call_adapter_before(adapter.as_ref(), &provider, &request, deadline)
+            .await?;
+        self.registry
+            .record_latency(&provider, &request.model.model_id, started.elapsed());
+        response = self
+            .repair_structured_output(
+                adapter.as_ref(),
+                &provider,
+                &mut request,
+                deadline,
+                response,
+            )
+            .await?;
+        response.warnings.extend(runtime_warnings);
+
+        if let Some(pricing_table) = &self.pricing_table {
+            match response.cost.take() {
+                None => {
+                    let (cost, warnings) = pricing::estimate_cost(
+                        &response.provider,
+                        &response.model,
+                        &response.usage,
+                        pricing_table,
+                    );
+                    response.cost = cost;
+                    response.warnings.extend(warnings);
+                }
+                Some(reported) => {
+                    let (reported, warning) = pricing_table.convert_cost(reported);
+                    response.warnings.extend(warning);
+                    let (estimate, _) = pricing::estimate_cost(
+                        &response.provider,
+                        &response.model,
+                        &response.usage,
+                        pricing_table,
+                    );
+                    response.cost = Some(match estimate {
+                        Some(estimate) => pricing::merge_reported_cost(reported, &estimate),
+                        None => reported,
+                    });
+                }
+            }
+        }
+
+        #[cfg(feature = "jsonschema")]
+        structured::validate_structured_output(
+            self.structured_output_policy,
+            &request,
+            &mut response,
+        )?;
 
-        if response.cost.is_none() {
-            if let Some(pricing_table) = &self.pricing_table {
-                let (cost, warnings) = pricing::estimate_cost(
-                    &response.provider,
-                    &response.model,
-                    &response.usage,
-                    pricing_table,
-                );
-                response.cost = cost;
-                response.warnings.extend(warnings);
+        Ok(response)
+    }
+
+    /// Calls the adapter, bounded by the request context deadline when one is set, and runs the
+    /// response transforms over its response.
+    async fn call_adapter_before(
+        &self,
+        adapter: &dyn ProviderAdapter,
+        provider: &ProviderId,
+        request: &ProviderRequest,
+        deadline: Option<Instant>,
+    ) -> Result<ProviderResponse, RuntimeError> {
+        let mut response = match deadline {
+            None => self.call_adapter(adapter, request).await?,
+            Some(deadline) => {
+                tokio::time::timeout_at(deadline.into(), self.call_adapter(adapter, request))
+                    .await
+                    .map_err(|_| RuntimeError::Timeout {
+                        provider: Some(provider.clone()),
+                        model: Some(request.model.model_id.clone()),
+                        message: "request context deadline exceeded".to_string(),
+                        source: None,
+                    })??
             }
+        };
+        for transform in &self.response_transforms {
+            transform.transform(request, &mut response);
+        }
+        Ok(response)
+    }
+
+    /// Re-prompts the same adapter while the response carries a
+    /// `structured_output_parse_failed` warning, up to the configured number of repair
+    /// attempts. The returned response combines the usage and cost of every attempt.
+    async fn repair_structured_output(
+        &self,
+        adapter: &dyn ProviderAdapter,
+        provider: &ProviderId,
+        request: &mut ProviderRequest,
+        deadline: Option<Instant>,
+        mut response: ProviderResponse,
+    ) -> Result<ProviderResponse, RuntimeError> {
+        let mut repairs = 0;
+        let mut first_error = None;
+        while repairs < self.structured_output_repair_attempts {
+            let Some(error) = response
+                .warnings
+                .iter()
+                .find(|warning| warning.code == WarningCode::StructuredOutputParseFailed)
+                .map(|warning| warning.message.clone())
+            else {
+                break;
+            };
+
+            repairs += 1;
+            structured::append_repair_turn(request, &response, &error);
+            let mut repaired = self
+                .call_adapter_before(adapter, provider, request, deadline)
+                .await?;
+            repaired.usage.accumulate(&response.usage);
+            repaired.cost = match combine_costs(response.cost, repaired.cost.take()) {
+                Ok(cost) => cost,
+                Err((previous, current)) => {
+                    repaired.warnings.push(RuntimeWarning {
+                        code: WarningCode::PricingCurrencyMismatch,
+                        message: format!(
+                            "repair attempts were priced in {previous} and {current}; cost dropped"
+                        ),
+                    });
+                    None
+                }
+            };
+            first_error.get_or_insert(error);
+            response = repaired;
+        }
+
+        if let Some(error) = first_error {
+            response.warnings.push(RuntimeWarning {
+                code: WarningCode::StructuredOutputRepaired,
+                message: format!(
+                    "re-prompted {repairs} time(s) after structured output failed to parse: {error}"
+                ),
+            });
+        }
+        Ok(response)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "provider_runtime.adapter",
+            target = "provider_runtime",
+            skip_all,
+            fields(provider = ?adapter.id(), model = %request.model.model_id)
+        )
+    )]
+    async fn call_adapter(
+        &self,
+        adapter: &dyn ProviderAdapter,
+        request: &ProviderRequest,
+    ) -> Result<ProviderResponse, ProviderError> {
+        let provider = adapter.id();
+        let result = self.call_adapter_leased(adapter, &provider, request).await;
+        // Reporting the rejection forced a token refresh, so one retry can succeed where the
+        // cached token no longer does.
+        if matches!(result, Err(ProviderError::CredentialsRejected { .. }))
+            && self.registry.has_token_provider(&provider)
+        {
+            return self.call_adapter_leased(adapter, &provider, request).await;
         }
+        result
+    }
+
+    /// Calls the adapter with a freshly leased credential and reports the outcome.
+    async fn call_adapter_leased(
+        &self,
+        adapter: &dyn ProviderAdapter,
+        provider: &ProviderId,
+        request: &ProviderRequest,
+    ) -> Result<ProviderResponse, ProviderError> {
+        let (ctx, lease) = self
+            .registry
+            .lease_credential(provider, &self.call_context())
+            .await?;
+        let result = self
+            .call_adapter_limited(adapter, provider, request, &ctx)
+            .await;
+        self.registry
+            .report_credential(provider, lease.as_ref(), result.as_ref().err());
+        result
+    }
 
+    /// Calls the adapter once a concurrency slot for its provider is free.
+    async fn call_adapter_limited(
+        &self,
+        adapter: &dyn ProviderAdapter,
+        provider: &ProviderId,
+        request: &ProviderRequest,
+        ctx: &AdapterContext,
+    ) -> Result<ProviderResponse, ProviderError> {
+        let Some(limit) = self.concurrency_limits.get(provider) else {
+            return adapter.run(request, ctx).await;
+        };
+
+        let queued_at = Instant::now();
+        let _permit = limit.acquire().await;
+        let queue_wait = queued_at.elapsed();
+        if let Some(metrics) = &self.metrics {
+            metrics.record_histogram(
+                metrics::QUEUE_WAIT_MS,
+                queue_wait.as_secs_f64() * 1000.0,
+                &[
+                    ("provider", provider.name()),
+                    ("model", request.model.model_id.as_str()),
+                ],
+            );
+        }
+
+        let mut response = adapter.run(request, ctx).await?;
+        if queue_wait >= Duration::from_millis(1) {
+            response.warnings.push(RuntimeWarning {
+                code: WarningCode::ConcurrencyQueued,
+                message: format!(
+                    "waited {} ms for a free {provider:?} concurrency slot",
+                    queue_wait.as_millis()
+                ),
+            });
+        }
         Ok(response)
     }
 
+    /// Runs the encode-warning policy: reports warnings to the hook, then rejects the request if
+    /// any warning code is configured to fail fast. Skipped when no policy is configured.
+    fn check_encode_warnings(
+        &self,
+        provider: &ProviderId,
+        adapter: &dyn ProviderAdapter,
+        request: &ProviderRequest,
+    ) -> Result<(), RuntimeError> {
+        if self.fail_fast_warning_codes.is_empty() && self.encode_warning_hook.is_none() {
+            return Ok(());
+        }
+
+        let warnings = adapter.encode_warnings(request)?;
+        if let Some(hook) = &self.encode_warning_hook {
+            hook(request, &warnings);
+        }
+
+        match warnings
+            .into_iter()
+            .find(|warning| self.fail_fast_warning_codes.contains(&warning.code))
+        {
+            Some(warning) => Err(RuntimeError::EncodeWarningRejected {
+                provider: provider.clone(),
+                model: request.model.model_id.clone(),
+                code: warning.code,
+                message: warning.message,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Lowers `max_output_tokens` to the catalog limit of the served model, so a blanket output
+    /// cap does not turn into a provider-side 400 for models with smaller limits. When
+    /// `max_output_tokens` is unset and catalog defaults are enabled, sets it to that limit.
+    fn apply_catalog_output_limit(
+        &self,
+        provider: &ProviderId,
+        request: &mut ProviderRequest,
+    ) -> Option<RuntimeWarning> {
+        let limit = self
+            .registry
+            .find_model(provider, &request.model.model_id)?
+            .max_output_tokens?;
+        let Some(requested) = request.max_output_tokens else {
+            if !self.catalog_output_token_defaults {
+                return None;
+            }
+            request.max_output_tokens = Some(limit);
+            return Some(RuntimeWarning {
+                code: WarningCode::MaxOutputTokensDefaulted,
+                message: format!(
+                    "max_output_tokens not set; defaulting to the {limit} token limit for model '{}'",
+                    request.model.model_id
+                ),
+            });
+        };
+        if requested <= limit {
+            return None;
+        }
+
+        request.max_output_tokens = Some(limit);
+        Some(RuntimeWarning {
+            code: WarningCode::MaxOutputTokensClamped,
+            message: format!(
+                "max_output_tokens {requested} exceeds the {limit} token limit for model '{}'; clamped",
+                request.model.model_id
+            ),
+        })
+    }
+
+    /// Counts the input tokens `request` would consume and reports them against the resolved
+    /// model's catalog context window. Uses the provider's count when the adapter supports one,
+    /// falling back to the configured `TokenCounter` if it does not or the count fails.
+    pub async fn estimate_tokens(
+        &self,
+        request: &ProviderRequest,
+    ) -> Result<TokenEstimate, RuntimeError> {
+        let mut request = request.clone();
+        let provider = self
+            .registry
+            .resolve_route(&mut request.model, &self.call_context())?;
+        let adapter = self.registry.resolve_adapter(&provider)?;
+        let model = self.registry.find_model(&provider, &request.model.model_id);
+
+        let (ctx, lease) = self
+            .registry
+            .lease_credential(&provider, &self.call_context())
+            .await?;
+        let counted = adapter.count_tokens(&request, &ctx).await;
+        self.registry
+            .report_credential(&provider, lease.as_ref(), counted.as_ref().err());
+        let (input_tokens, source) = match counted {
+            Ok(Some(count)) => (count, TokenCountSource::Provider),
+            Ok(None) | Err(_) => (
+                self.token_counter.count_request(&request),
+                TokenCountSource::Counter,
+            ),
+        };
+
+        Ok(TokenEstimate {
+            input_tokens,
+            source,
+            context_window: model.as_ref().and_then(|model| model.context_window),
+            reserved_output_tokens: request.max_output_tokens,
+        })
+    }
+
     pub async fn discover_models(
         &self,
         opts: DiscoveryOptions,
     ) -> Result<ModelCatalog, RuntimeError> {
         self.registry
-            .discover_models(&opts, &self.adapter_context)
+            .discover_models(&opts, &self.call_context())
+            .await
+    }
+
+    /// Like `discover_models`, but reports per-provider failures instead of hiding them.
+    pub async fn discover_models_report(&self, opts: DiscoveryOptions) -> DiscoveryReport {
+        self.registry
+            .discover_models_report(&opts, &self.call_context())
             .await
     }
 
     pub fn export_catalog_json(&self, catalog: &ModelCatalog) -> Result<String, RuntimeError> {
         catalog::export_catalog_json(catalog)
     }
+
+    /// Health of every registered provider as of its latest check.
+    pub fn provider_health(&self) -> Vec<ProviderHealth> {
+        self.registry.health_status()
+    }
+
+    /// Health-checks every provider now. Providers that fail repeatedly are left out of
+    /// routing and fallback until a later check passes.
+    pub async fn check_provider_health(&self) -> Vec<ProviderHealth> {
+        self.registry.check_health(&self.adapter_context).await
+    }
+
+    /// Health-checks every provider every `interval` in a background task until the returned
+    /// handle is aborted. Must be called from within a Tokio runtime.
+    pub fn spawn_health_checks(&self, interval: Duration) -> JoinHandle<()> {
+        self.registry
+            .spawn_health_checks(interval, self.adapter_context.clone())
+    }
 }
 
 impl ProviderRuntimeBuilder {
@@ -116,17 +1063,487 @@ impl ProviderRuntimeBuilder {
         self
     }
 
+    /// Enables run trace capture, keeping at most `capacity` of the most recent traces.
+    pub fn with_run_trace_capture(mut self, capacity: usize) -> Self {
+        self.trace_capacity = Some(capacity);
+        self
+    }
+
+    /// Caches `provider`'s discovered models for `ttl`; refreshes within that window reuse them.
+    pub fn with_discovery_ttl(mut self, provider: ProviderId, ttl: Duration) -> Self {
+        self.discovery_ttls.insert(provider, ttl);
+        self
+    }
+
+    /// Routes requests for `alias` to `model_id` on `provider`. Register the same alias for
+    /// several providers to serve it from whichever is available, in registration order; a
+    /// request's `provider_hint` selects among them.
+    pub fn with_model_alias(
+        mut self,
+        alias: impl Into<String>,
+        provider: ProviderId,
+        model_id: impl Into<String>,
+    ) -> Self {
+        self.model_aliases.push(ModelAlias {
+            alias: alias.into(),
+            provider,
+            model_id: model_id.into(),
+        });
+        self
+    }
+
+    /// Rotates calls to `provider` across the keys in `pool`. Pooled keys take precedence over
+    /// the adapter's own key; keys the provider rejects or rate limits are set aside.
+    pub fn with_key_pool(mut self, provider: ProviderId, pool: KeyPool) -> Self {
+        self.key_pools.push((provider, pool));
+        self
+    }
+
+    /// Looks up API keys in `store` for every provider without a key pool. Keys found there take
+    /// precedence over each adapter's own key; when `store` has none, the adapter falls back to
+    /// its constructor key, `<provider>.api_key` metadata, and environment variable.
+    pub fn with_credential_store(mut self, store: Arc<dyn CredentialStore>) -> Self {
+        self.credential_store = Some(store);
+        self
+    }
+
+    /// Authenticates calls to `provider` with bearer tokens from `tokens`, such as OAuth or STS
+    /// credentials, in place of API keys. Tokens are reused until shortly before they expire; a
+    /// call the provider rejects is retried once with a fresh token, and a failed fetch fails the
+    /// call with `RuntimeError::CredentialMissing`.
+    pub fn with_token_provider(
+        mut self,
+        provider: ProviderId,
+        tokens: Arc<dyn TokenProvider>,
+    ) -> Self {
+        self.token_providers.push((provider, tokens));
+        self
+    }
+
+    /// Number of consecutive failed health checks after which a provider is left out of
+    /// routing. Defaults to `registry::DEFAULT_UNHEALTHY_THRESHOLD`.
+    pub fn with_unhealthy_threshold(mut self, threshold: u32) -> Self {
+        self.unhealthy_threshold = Some(threshold);
+        self
+    }
+
+    /// Spreads requests for `alias` across the providers it is registered for with
+    /// `with_model_alias`, by weight or in turn. Sessions named by the adapter context's
+    /// `session_id` metadata keep one route.
+    pub fn with_routing_policy(mut self, alias: impl Into<String>, policy: RoutingPolicy) -> Self {
+        self.routing_policies.push((alias.into(), policy));
+        self
+    }
+
+    /// Bounds each provider's model listing during discovery; slow providers are reported as
+    /// failures instead of delaying the whole refresh.
+    pub fn with_discovery_timeout(mut self, timeout: Duration) -> Self {
+        self.discovery_timeout = Some(timeout);
+        self
+    }
+
+    /// Rejects requests before dispatch when encoding produces a warning with one of `codes`.
+    pub fn with_fail_fast_warnings<I, S>(mut self, codes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<WarningCode>,
+    {
+        self.fail_fast_warning_codes
+            .extend(codes.into_iter().map(Into::into));
+        self
+    }
+
+    /// Calls `hook` with every request's encode-time warnings before it is dispatched.
+    pub fn with_encode_warning_hook(
+        mut self,
+        hook: impl Fn(&ProviderRequest, &[RuntimeWarning]) + Send + Sync + 'static,
+    ) -> Self {
+        self.encode_warning_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Fails over to `fallbacks`, in order, when a request for a model matching `model_pattern`
+    /// fails with a rate limit, timeout, transport error, or 5xx status. The request is
+    /// re-encoded for each fallback model and the response carries a
+    /// `runtime.provider_failover` warning per failed attempt. Patterns are an exact model id,
+    /// `*`, or a `prefix*`; the first chain registered for a matching pattern is used.
+    pub fn with_fallback_chain(
+        mut self,
+        model_pattern: impl Into<String>,
+        fallbacks: Vec<ModelRef>,
+    ) -> Self {
+        self.fallback_chains.push(FallbackChain {
+            model_pattern: model_pattern.into(),
+            fallbacks,
+        });
+        self
+    }
+
+    /// Hedges requests for models matching `model_pattern`: if no response has arrived after
+    /// `delay`, the same request is also sent to `hedge_model` and the first success is returned,
+    /// cancelling the slower call. Responses served by the hedge carry a `runtime.hedge_won`
+    /// warning. Patterns follow `with_fallback_chain`.
+    ///
+    /// The cancelled call never reports usage, so `with_budget` and `with_metrics` see only the
+    /// winner's tokens; the provider may still bill for the loser.
+    pub fn with_hedging(
+        mut self,
+        model_pattern: impl Into<String>,
+        hedge_model: ModelRef,
+        delay: Duration,
+    ) -> Self {
+        self.hedges.push(HedgePolicy {
+            model_pattern: model_pattern.into(),
+            hedge_model,
+            delay,
+        });
+        self
+    }
+
+    /// Adds `middleware` around every adapter call. See `RuntimeMiddleware` for hook order.
+    pub fn with_middleware(mut self, middleware: Arc<dyn RuntimeMiddleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Reports request counts, latency, retries, token usage, and cost for every run to
+    /// `metrics`. See `runtime::metrics` for metric names and labels.
+    pub fn with_metrics(mut self, metrics: Arc<dyn RuntimeMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Attributes usage to accounting keys in `budget` and rejects requests from keys that have
+    /// reached their spend cap. Keep a clone of the `Arc` to read totals.
+    pub fn with_budget(mut self, budget: Arc<BudgetTracker>) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Returns cached responses for identical requests allowed by `policy` made with the same
+    /// adapter context. The cache key ignores request metadata and trace ids; see
+    /// `runtime::cache::cache_key`.
+    pub fn with_response_cache(
+        mut self,
+        cache: Arc<dyn ResponseCache>,
+        policy: CachePolicy,
+    ) -> Self {
+        self.response_cache = Some((cache, policy));
+        self
+    }
+
+    /// Lets concurrent identical cache misses share one provider call: the first caller runs the
+    /// request while the rest poll the response cache for up to `max_wait` before running it
+    /// themselves. Has no effect without `with_response_cache`.
+    pub fn with_request_dedup(mut self, dedup: Arc<dyn InFlightDedup>, max_wait: Duration) -> Self {
+        self.request_dedup = Some((dedup, max_wait));
+        self
+    }
+
+    /// Allows at most `max_in_flight` concurrent adapter calls to `provider`; further calls wait
+    /// client-side for a free slot. Calls that waited carry a `runtime.concurrency_queued`
+    /// warning, and every limited call reports its wait as the `queue_wait_ms` metric. A limit
+    /// of 0 is treated as 1.
+    pub fn with_max_concurrency(mut self, provider: ProviderId, max_in_flight: usize) -> Self {
+        self.concurrency_limits
+            .insert(provider, Arc::new(Semaphore::new(max_in_flight.max(1))));
+        self
+    }
+
+    /// Counts tokens with `counter` when `estimate_tokens` cannot get a count from the provider.
+    /// Defaults to `HeuristicTokenCounter`.
+    pub fn with_token_counter(mut self, counter: Arc<dyn TokenCounter>) -> Self {
+        self.token_counter = counter;
+        self
+    }
+
+    /// Shortens requests that would exceed their model's catalog context window using
+    /// `strategy` before they are sent. Each run pays for an `estimate_tokens` call, and a
+    /// truncated run for a second one confirming the result fits; see `runtime::truncation`.
+    pub fn with_context_truncation(mut self, strategy: TruncationStrategy) -> Self {
+        self.truncation = Some(strategy);
+        self
+    }
+
+    /// Validates `structured_output` against the request's `ResponseFormat::JsonSchema` and
+    /// handles violations per `policy`.
+    #[cfg(feature = "jsonschema")]
+    pub fn with_structured_output_policy(
+        mut self,
+        policy: structured::StructuredOutputPolicy,
+    ) -> Self {
+        self.structured_output_policy = policy;
+        self
+    }
+
+    /// Fills an unset `max_output_tokens` with the catalog limit of the served model, adding a
+    /// `runtime.max_output_tokens_defaulted` warning. Without this, adapters that must send a
+    /// limit fall back to their own default (1024 tokens for Anthropic unless
+    /// `AnthropicAdapterOptions::default_max_tokens` is set), which can cut long generations
+    /// short. Models without a catalog limit are left alone.
+    pub fn with_catalog_output_token_defaults(mut self, enabled: bool) -> Self {
+        self.catalog_output_token_defaults = enabled;
+        self
+    }
+
+    /// Bounds each `run` to `timeout` in total, across transport retries, structured-output
+    /// repairs, hedges, and fallbacks. An earlier [`RequestContext`] deadline still wins. Once it
+    /// passes, the run fails with a timeout naming every model attempted.
+    pub fn with_request_deadline(mut self, timeout: Duration) -> Self {
+        self.request_deadline = Some(timeout);
+        self
+    }
+
+    /// Scrubs error messages, warnings, and captured run traces through `redactor` before `run`
+    /// returns or records them. Defaults to [`DefaultRedactor`], which masks bearer tokens and
+    /// API keys. Adapters' transports redact provider error bodies separately, through
+    /// `HttpTransportBuilder::with_redactor`.
+    pub fn with_redactor(mut self, redactor: Arc<dyn Redactor>) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
+    /// Runs `transform` over every decoded response, after the transforms registered before it.
+    /// See [`transform`] for the built-in transforms.
+    pub fn with_response_transform(mut self, transform: Arc<dyn ResponseTransform>) -> Self {
+        self.response_transforms.push(transform);
+        self
+    }
+
+    /// Re-prompts the same provider up to `max_attempts` times when a response's structured
+    /// output fails to parse, appending the previous output and the parse error to the
+    /// conversation. Repaired responses report the combined usage and cost of all attempts and
+    /// carry a `runtime.structured_output_repaired` warning. Defaults to 0 (disabled).
+    pub fn with_structured_output_repair(mut self, max_attempts: u32) -> Self {
+        self.structured_output_repair_attempts = max_attempts;
+        self
+    }
+
+    /// Like [`build`](Self::build), but first checks the configuration for mistakes that would
+    /// otherwise surface only when a request hits them:
+    ///
+    /// - the default provider has a registered adapter;
+    /// - catalog models and pricing rules name a registered provider. The built-in providers are
+    ///   always accepted, since the bundled catalog and price list cover them whether or not
+    ///   they are registered;
+    /// - every fallback chain and hedge model resolves to a registered provider.
+    pub fn try_build(self) -> Result<ProviderRuntime, ConfigError> {
+        let registered: BTreeSet<ProviderId> =
+            self.adapters.iter().map(|adapter| adapter.id()).collect();
+        let known = |provider: &ProviderId| {
+            registered.contains(provider) || !matches!(provider, ProviderId::Other(_))
+        };
+
+        if let Some(provider) = &self.default_provider
+            && !registered.contains(provider)
+        {
+            return Err(ConfigError::DefaultProviderNotRegistered {
+                provider: provider.clone(),
+            });
+        }
+        if let Some(model) = self
+            .static_catalog
+            .models
+            .iter()
+            .find(|model| !known(&model.provider))
+        {
+            return Err(ConfigError::CatalogProviderNotRegistered {
+                provider: model.provider.clone(),
+                model: model.model_id.clone(),
+            });
+        }
+        if let Some(pricing_table) = &self.pricing_table {
+            let rules = pricing_table
+                .rules
+                .iter()
+                .map(|rule| (&rule.provider, &rule.model_pattern));
+            let image_rules = pricing_table
+                .image_rules
+                .iter()
+                .map(|rule| (&rule.provider, &rule.model_pattern));
+            if let Some((provider, pattern)) = rules
+                .chain(image_rules)
+                .find(|(provider, _)| !known(provider))
+            {
+                return Err(ConfigError::PricingProviderNotRegistered {
+                    provider: provider.clone(),
+                    pattern: pattern.clone(),
+                });
+            }
+        }
+
+        let runtime = self.build();
+        let routes = runtime
+            .fallback_chains
+            .iter()
+            .flat_map(|chain| {
+                chain.fallbacks.iter().map(|model| {
+                    (
+                        format!("fallback chain for '{}'", chain.model_pattern),
+                        model,
+                    )
+                })
+            })
+            .chain(runtime.hedges.iter().map(|hedge| {
+                (
+                    format!("hedge for '{}'", hedge.model_pattern),
+                    &hedge.hedge_model,
+                )
+            }));
+        for (rule, model) in routes {
+            if let Err(error) = runtime.registry.check_route(model) {
+                return Err(ConfigError::UnresolvableModelRoute {
+                    rule,
+                    model: describe_model(model),
+                    error,
+                });
+            }
+        }
+        Ok(runtime)
+    }
+
     pub fn build(self) -> ProviderRuntime {
         let mut registry = ProviderRegistry::new(self.static_catalog, self.default_provider);
         for adapter in self.adapters {
             registry.register(adapter);
         }
+        for (provider, ttl) in self.discovery_ttls {
+            registry.set_discovery_ttl(provider, ttl);
+        }
+        if let Some(timeout) = self.discovery_timeout {
+            registry.set_discovery_timeout(timeout);
+        }
+        if let Some(threshold) = self.unhealthy_threshold {
+            registry.set_unhealthy_threshold(threshold);
+        }
+        for (provider, pool) in self.key_pools {
+            registry.set_key_pool(provider, pool);
+        }
+        if let Some(store) = self.credential_store {
+            registry.set_credential_store(store);
+        }
+        for (provider, tokens) in self.token_providers {
+            registry.set_token_provider(provider, tokens);
+        }
+        for alias in self.model_aliases {
+            registry.add_alias(alias);
+        }
+        for (alias, policy) in self.routing_policies {
+            registry.set_routing_policy(alias, policy);
+        }
 
         ProviderRuntime {
             registry,
             adapter_context: self.adapter_context,
             pricing_table: self.pricing_table,
+            trace_store: self.trace_capacity.map(TraceStore::new),
+            fail_fast_warning_codes: self.fail_fast_warning_codes,
+            encode_warning_hook: self.encode_warning_hook,
+            fallback_chains: self.fallback_chains,
+            hedges: self.hedges,
+            middleware: self.middleware,
+            metrics: self.metrics,
+            budget: self.budget,
+            response_cache: self.response_cache,
+            request_dedup: self.request_dedup,
+            concurrency_limits: self.concurrency_limits,
+            token_counter: self.token_counter,
+            truncation: self.truncation,
+            catalog_output_token_defaults: self.catalog_output_token_defaults,
+            structured_output_repair_attempts: self.structured_output_repair_attempts,
+            request_deadline: self.request_deadline,
+            redactor: self.redactor,
+            response_transforms: self.response_transforms,
+            #[cfg(feature = "jsonschema")]
+            structured_output_policy: self.structured_output_policy,
+        }
+    }
+}
+
+/// Looks `key` up in `cache`, marking a hit with a `runtime.cache_hit` warning.
+async fn cached_response(cache: &dyn ResponseCache, key: &str) -> Option<ProviderResponse> {
+    let mut response = cache.get(key).await?;
+    response.warnings.push(RuntimeWarning {
+        code: WarningCode::CacheHit,
+        message: format!("served from the response cache (key {key})"),
+    });
+    Some(response)
+}
+
+/// Sums the cost of an earlier call into a later one, for responses that report the calls made
+/// on their behalf. `None` when either call is unpriced; the two currencies when they differ.
+fn combine_costs(
+    earlier: Option<CostBreakdown>,
+    later: Option<CostBreakdown>,
+) -> Result<Option<CostBreakdown>, (String, String)> {
+    match (earlier, later) {
+        (Some(earlier), Some(mut cost)) if earlier.currency == cost.currency => {
+            cost.input_cost += earlier.input_cost;
+            cost.cached_input_cost += earlier.cached_input_cost;
+            cost.output_cost += earlier.output_cost;
+            cost.fixed_cost += earlier.fixed_cost;
+            cost.total_cost += earlier.total_cost;
+            Ok(Some(cost))
         }
+        (Some(earlier), Some(cost)) => Err((earlier.currency, cost.currency)),
+        _ => Ok(None),
+    }
+}
+
+/// Errors worth retrying against another provider: the request itself was fine, the provider
+/// was not.
+fn is_failover_error(error: &RuntimeError) -> bool {
+    match error {
+        RuntimeError::RateLimited { .. }
+        | RuntimeError::Timeout { .. }
+        | RuntimeError::TransportError { .. }
+        | RuntimeError::RoutingError(RoutingError::ProviderUnhealthy { .. }) => true,
+        RuntimeError::ProviderProtocolError { status_code, .. } => {
+            status_code.is_some_and(|status| status == 408 || status >= 500)
+        }
+        _ => false,
+    }
+}
+
+/// The timeout returned when the request deadline passes partway through a run, naming each
+/// model tried. A timeout keeps its provider and source; any other error becomes the source.
+fn deadline_exceeded(error: RuntimeError, attempted: &[ModelRef]) -> RuntimeError {
+    let message = format!(
+        "request deadline exceeded after attempting {}",
+        attempted
+            .iter()
+            .map(describe_model)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    match error {
+        RuntimeError::Timeout {
+            provider,
+            model,
+            source,
+            ..
+        } => RuntimeError::Timeout {
+            provider,
+            model,
+            message,
+            source,
+        },
+        error => {
+            let last = attempted.last();
+            RuntimeError::Timeout {
+                provider: last.and_then(|model| model.provider_hint.clone()),
+                model: last.map(|model| model.model_id.clone()),
+                message,
+                source: Some(ErrorSource::new(error)),
+            }
+        }
+    }
+}
+
+fn describe_model(model: &ModelRef) -> String {
+    match &model.provider_hint {
+        Some(provider) => format!("{provider:?} model '{}'", model.model_id),
+        None => format!("model '{}'", model.model_id),
     }
 }
 
@@ -0,0 +1,34 @@
+//! Content moderation through the runtime's configured adapters and credentials, so input can be
+//! pre-screened before it is sent to a model.
+
+use crate::core::error::RuntimeError;
+use crate::core::types::{ModerationRequest, ModerationResponse};
+use crate::runtime::ProviderRuntime;
+
+impl ProviderRuntime {
+    /// Classifies `request.inputs` with the provider resolved for `request.model`.
+    pub async fn moderate(
+        &self,
+        mut request: ModerationRequest,
+    ) -> Result<ModerationResponse, RuntimeError> {
+        let provider = self
+            .registry
+            .resolve_route(&mut request.model, &self.call_context())?;
+        let adapter = self.registry.resolve_adapter(&provider)?;
+        let (ctx, lease) = self
+            .registry
+            .lease_credential(&provider, &self.call_context())
+            .await?;
+        let result = adapter.moderate(&request, &ctx).await;
+        self.registry
+            .report_credential(&provider, lease.as_ref(), result.as_ref().err());
+        result?.ok_or_else(|| RuntimeError::CapabilityMismatch {
+            provider,
+            model: request.model.model_id,
+            capability: "moderation".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,132 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use crate::core::error::{ProviderError, RuntimeError};
+use crate::core::types::{AdapterContext, ModelRef, ModerationRequest, ProviderId};
+use crate::providers::mock::MockAdapter;
+use crate::providers::openai::{OpenAiAdapter, OpenAiAdapterOptions};
+use crate::runtime::ProviderRuntime;
+use crate::transport::http::JsonResponse;
+use crate::transport::{SseStream, Transport};
+
+/// Answers every POST with a canned moderation payload and records the URL it was sent to.
+struct ModerationTransport {
+    urls: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl Transport for ModerationTransport {
+    async fn post_json(
+        &self,
+        _provider: ProviderId,
+        _model: Option<&str>,
+        url: &str,
+        _body: &serde_json::Value,
+        _ctx: &AdapterContext,
+    ) -> Result<JsonResponse<serde_json::Value>, ProviderError> {
+        self.urls.lock().expect("urls lock").push(url.to_string());
+        Ok(JsonResponse {
+            body: serde_json::json!({
+                "model": "omni-moderation-2024-09-26",
+                "results": [{
+                    "flagged": true,
+                    "categories": { "harassment": true, "violence": false },
+                    "category_scores": { "harassment": 0.91, "violence": 0.02 }
+                }]
+            }),
+            request_id: Some("modr-1".to_string()),
+            rate_limit: None,
+        })
+    }
+
+    async fn get_json(
+        &self,
+        provider: ProviderId,
+        _model: Option<&str>,
+        _url: &str,
+        _ctx: &AdapterContext,
+    ) -> Result<JsonResponse<serde_json::Value>, ProviderError> {
+        Err(ProviderError::Transport {
+            provider,
+            request_id: None,
+            message: "GET is not faked".to_string(),
+            source: None,
+        })
+    }
+
+    async fn post_sse(
+        &self,
+        provider: ProviderId,
+        _model: Option<&str>,
+        _url: &str,
+        _body: &serde_json::Value,
+        _ctx: &AdapterContext,
+    ) -> Result<SseStream, ProviderError> {
+        Err(ProviderError::Transport {
+            provider,
+            request_id: None,
+            message: "streaming is not faked".to_string(),
+            source: None,
+        })
+    }
+}
+
+fn request() -> ModerationRequest {
+    ModerationRequest {
+        model: ModelRef {
+            provider_hint: Some(ProviderId::Openai),
+            model_id: "omni-moderation-latest".to_string(),
+            requirements: None,
+        },
+        inputs: vec!["you are terrible".to_string()],
+    }
+}
+
+#[tokio::test]
+async fn test_moderate_uses_openai_moderations_endpoint() {
+    let transport = Arc::new(ModerationTransport {
+        urls: Mutex::new(Vec::new()),
+    });
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(OpenAiAdapter::with_transport(
+            Some("test-key".to_string()),
+            "http://gateway.internal",
+            OpenAiAdapterOptions::default(),
+            transport.clone(),
+        )))
+        .build();
+
+    let response = runtime
+        .moderate(request())
+        .await
+        .expect("moderation should succeed");
+
+    assert_eq!(
+        transport.urls.lock().expect("urls lock").as_slice(),
+        ["http://gateway.internal/v1/moderations"]
+    );
+    assert_eq!(response.model, "omni-moderation-2024-09-26");
+    assert_eq!(response.request_id.as_deref(), Some("modr-1"));
+    let result = &response.results[0];
+    assert!(result.flagged);
+    assert_eq!(result.categories.get("harassment"), Some(&true));
+    assert_eq!(result.category_scores.get("violence"), Some(&0.02));
+}
+
+#[tokio::test]
+async fn test_moderate_without_adapter_support_is_capability_mismatch() {
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(MockAdapter::new(ProviderId::Openai)))
+        .build();
+
+    let error = runtime
+        .moderate(request())
+        .await
+        .expect_err("mock adapter has no moderation");
+
+    assert!(matches!(
+        error,
+        RuntimeError::CapabilityMismatch { ref capability, .. } if capability == "moderation"
+    ));
+}
@@ -0,0 +1,253 @@
+//! Reusable prompt templates rendered into canonical messages.
+//!
+//! A `PromptTemplate` is an ordered list of message templates whose text may reference
+//! `{variables}`, resolved from any `Serialize` value (`{user.name}` reads a nested field), and
+//! `{>partials}`, named snippets shared across messages that may themselves use variables.
+//! `{{` and `}}` write literal braces. Providers that need different wording get their own
+//! message list with `with_provider_messages`. Templates are plain serde values, so they can be
+//! versioned and stored next to the code that uses them.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::error::ConfigError;
+use crate::core::types::{ContentPart, Message, MessageRole, ProviderId};
+
+/// How deep partials may include other partials, which also catches include cycles.
+const MAX_PARTIAL_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PromptTemplate {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(default)]
+    messages: Vec<MessageTemplate>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    partials: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    provider_overrides: Vec<ProviderMessages>,
+}
+
+/// One message of a template: a role and text with `{variable}` and `{>partial}` references.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MessageTemplate {
+    pub role: MessageRole,
+    pub text: String,
+}
+
+/// Messages used in place of the template's own when rendering for `provider`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProviderMessages {
+    provider: ProviderId,
+    messages: Vec<MessageTemplate>,
+}
+
+impl MessageTemplate {
+    pub fn new(role: MessageRole, text: impl Into<String>) -> Self {
+        Self {
+            role,
+            text: text.into(),
+        }
+    }
+}
+
+impl PromptTemplate {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: None,
+            messages: Vec::new(),
+            partials: BTreeMap::new(),
+            provider_overrides: Vec::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    pub fn with_message(mut self, role: MessageRole, text: impl Into<String>) -> Self {
+        self.messages.push(MessageTemplate::new(role, text));
+        self
+    }
+
+    pub fn with_system(self, text: impl Into<String>) -> Self {
+        self.with_message(MessageRole::System, text)
+    }
+
+    pub fn with_user(self, text: impl Into<String>) -> Self {
+        self.with_message(MessageRole::User, text)
+    }
+
+    pub fn with_assistant(self, text: impl Into<String>) -> Self {
+        self.with_message(MessageRole::Assistant, text)
+    }
+
+    /// Registers a snippet included with `{>name}`, replacing any partial of the same name.
+    pub fn with_partial(mut self, name: impl Into<String>, text: impl Into<String>) -> Self {
+        self.partials.insert(name.into(), text.into());
+        self
+    }
+
+    /// Renders `messages` instead of the template's own when rendering for `provider`.
+    /// Partials are shared.
+    pub fn with_provider_messages(
+        mut self,
+        provider: ProviderId,
+        messages: Vec<MessageTemplate>,
+    ) -> Self {
+        self.provider_overrides
+            .retain(|entry| entry.provider != provider);
+        self.provider_overrides
+            .push(ProviderMessages { provider, messages });
+        self
+    }
+
+    /// Renders the template's messages with `variables`, which must serialize to a map.
+    pub fn render<T: Serialize + ?Sized>(
+        &self,
+        variables: &T,
+    ) -> Result<Vec<Message>, ConfigError> {
+        self.render_messages(&self.messages, variables)
+    }
+
+    /// Renders the messages for `provider`, falling back to the template's own messages when it
+    /// has no override.
+    pub fn render_for<T: Serialize + ?Sized>(
+        &self,
+        provider: &ProviderId,
+        variables: &T,
+    ) -> Result<Vec<Message>, ConfigError> {
+        let messages = self
+            .provider_overrides
+            .iter()
+            .find(|entry| &entry.provider == provider)
+            .map_or(&self.messages, |entry| &entry.messages);
+        self.render_messages(messages, variables)
+    }
+
+    fn render_messages<T: Serialize + ?Sized>(
+        &self,
+        messages: &[MessageTemplate],
+        variables: &T,
+    ) -> Result<Vec<Message>, ConfigError> {
+        let variables = serde_json::to_value(variables)
+            .map_err(|error| self.error(format!("variables failed to serialize: {error}")))?;
+        if !variables.is_object() {
+            return Err(self.error("variables must serialize to a map".to_string()));
+        }
+
+        messages
+            .iter()
+            .map(|message| {
+                Ok(Message {
+                    role: message.role.clone(),
+                    content: vec![ContentPart::Text {
+                        text: self.render_text(&message.text, &variables, 0)?,
+                    }],
+                    cache_hint: None,
+                })
+            })
+            .collect()
+    }
+
+    fn render_text(
+        &self,
+        text: &str,
+        variables: &Value,
+        depth: usize,
+    ) -> Result<String, ConfigError> {
+        let mut rendered = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find(['{', '}']) {
+            rendered.push_str(&rest[..start]);
+            let tail = &rest[start..];
+            if let Some(after) = tail.strip_prefix("{{") {
+                rendered.push('{');
+                rest = after;
+                continue;
+            }
+            if let Some(after) = tail.strip_prefix("}}") {
+                rendered.push('}');
+                rest = after;
+                continue;
+            }
+            if tail.starts_with('}') {
+                return Err(self.error("unmatched '}'; write '}}' for a literal brace".to_string()));
+            }
+
+            let Some(end) = tail.find('}') else {
+                return Err(self.error("unclosed '{'; write '{{' for a literal brace".to_string()));
+            };
+            let reference = tail[1..end].trim();
+            match reference.strip_prefix('>') {
+                Some(partial) => {
+                    rendered.push_str(&self.render_partial(partial.trim(), variables, depth)?)
+                }
+                None => rendered.push_str(&self.lookup(reference, variables)?),
+            }
+            rest = &tail[end + 1..];
+        }
+        rendered.push_str(rest);
+        Ok(rendered)
+    }
+
+    fn render_partial(
+        &self,
+        name: &str,
+        variables: &Value,
+        depth: usize,
+    ) -> Result<String, ConfigError> {
+        if depth >= MAX_PARTIAL_DEPTH {
+            return Err(self.error(format!(
+                "partial '{name}' nests deeper than {MAX_PARTIAL_DEPTH} levels"
+            )));
+        }
+        let text = self
+            .partials
+            .get(name)
+            .ok_or_else(|| self.error(format!("unknown partial '{name}'")))?;
+        self.render_text(text, variables, depth + 1)
+    }
+
+    /// Resolves a dotted variable path. Strings render as-is and other values as JSON.
+    fn lookup(&self, path: &str, variables: &Value) -> Result<String, ConfigError> {
+        if path.is_empty() {
+            return Err(self.error("empty variable reference '{}'".to_string()));
+        }
+        let value = path
+            .split('.')
+            .try_fold(variables, |value, key| value.get(key))
+            .filter(|value| !value.is_null())
+            .ok_or_else(|| self.error(format!("missing variable '{path}'")))?;
+        Ok(match value {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        })
+    }
+
+    fn error(&self, reason: String) -> ConfigError {
+        ConfigError::InvalidPromptTemplate {
+            name: self.name.clone(),
+            reason,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
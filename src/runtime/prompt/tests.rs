@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::json;
+
+use super::{MessageTemplate, PromptTemplate};
+use crate::core::error::ConfigError;
+use crate::core::types::{ContentPart, Message, MessageRole, ProviderId};
+
+#[derive(Serialize)]
+struct Ticket {
+    customer: Customer,
+    issue: String,
+    priority: u8,
+}
+
+#[derive(Serialize)]
+struct Customer {
+    name: String,
+}
+
+fn ticket() -> Ticket {
+    Ticket {
+        customer: Customer {
+            name: "Ada".to_string(),
+        },
+        issue: "refund".to_string(),
+        priority: 2,
+    }
+}
+
+fn text(message: &Message) -> &str {
+    match message.content.as_slice() {
+        [ContentPart::Text { text }] => text,
+        other => panic!("expected one text part, got {other:?}"),
+    }
+}
+
+fn support_template() -> PromptTemplate {
+    PromptTemplate::new("support")
+        .with_version("2")
+        .with_partial("tone", "Be brief{>sign_off}")
+        .with_partial("sign_off", " and sign as {agent}.")
+        .with_system("You help {customer.name}. {>tone}")
+        .with_user("Issue: {issue} (priority {priority}); reply as {{json}}.")
+}
+
+#[test]
+fn test_render_substitutes_variables_partials_and_escapes() {
+    let messages = support_template()
+        .render(&json!({
+            "customer": {"name": "Ada"},
+            "issue": "refund",
+            "priority": 2,
+            "agent": "Sam",
+        }))
+        .expect("template should render");
+
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0].role, MessageRole::System);
+    assert_eq!(
+        text(&messages[0]),
+        "You help Ada. Be brief and sign as Sam."
+    );
+    assert_eq!(messages[1].role, MessageRole::User);
+    assert_eq!(
+        text(&messages[1]),
+        "Issue: refund (priority 2); reply as {json}."
+    );
+}
+
+#[test]
+fn test_render_for_uses_provider_overrides() {
+    let template = PromptTemplate::new("summary")
+        .with_user("Summarize the {issue} ticket for {customer.name}.")
+        .with_provider_messages(
+            ProviderId::Anthropic,
+            vec![
+                MessageTemplate::new(MessageRole::System, "Answer in one sentence."),
+                MessageTemplate::new(MessageRole::User, "<ticket>{issue}</ticket>"),
+            ],
+        );
+
+    let default = template
+        .render_for(&ProviderId::Openai, &ticket())
+        .expect("default messages should render");
+    assert_eq!(
+        default.iter().map(text).collect::<Vec<_>>(),
+        ["Summarize the refund ticket for Ada."]
+    );
+
+    let anthropic = template
+        .render_for(&ProviderId::Anthropic, &ticket())
+        .expect("override should render");
+    assert_eq!(
+        anthropic.iter().map(text).collect::<Vec<_>>(),
+        ["Answer in one sentence.", "<ticket>refund</ticket>"]
+    );
+}
+
+#[test]
+fn test_render_reports_template_errors() {
+    let reason = |template: PromptTemplate, variables: &BTreeMap<&str, &str>| match template
+        .render(variables)
+    {
+        Err(ConfigError::InvalidPromptTemplate { name, reason }) => {
+            assert_eq!(name, "broken");
+            reason
+        }
+        other => panic!("expected InvalidPromptTemplate, got {other:?}"),
+    };
+    let broken = || PromptTemplate::new("broken");
+    let empty = BTreeMap::new();
+
+    assert_eq!(
+        reason(broken().with_user("Hi {name}"), &empty),
+        "missing variable 'name'"
+    );
+    assert_eq!(
+        reason(broken().with_user("Hi {>greeting}"), &empty),
+        "unknown partial 'greeting'"
+    );
+    assert!(reason(broken().with_user("Hi {name"), &empty).starts_with("unclosed '{'"));
+    assert!(reason(broken().with_user("Hi name}"), &empty).starts_with("unmatched '}'"));
+    assert_eq!(
+        reason(
+            broken()
+                .with_partial("loop", "{>loop}")
+                .with_user("{>loop}"),
+            &empty
+        ),
+        "partial 'loop' nests deeper than 8 levels"
+    );
+    assert!(matches!(
+        broken().with_user("Hi").render(&"not a map"),
+        Err(ConfigError::InvalidPromptTemplate { .. })
+    ));
+}
+
+#[test]
+fn test_template_round_trips_through_json() {
+    let template = support_template().with_provider_messages(
+        ProviderId::Openrouter,
+        vec![MessageTemplate::new(MessageRole::User, "{issue}")],
+    );
+
+    let json = serde_json::to_string(&template).expect("template should serialize");
+    let restored: PromptTemplate = serde_json::from_str(&json).expect("template should parse");
+
+    assert_eq!(restored, template);
+    assert_eq!(restored.name(), "support");
+    assert_eq!(restored.version(), Some("2"));
+}
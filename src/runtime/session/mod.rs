@@ -0,0 +1,189 @@
+//! Multi-turn conversation state on top of `ProviderRuntime::run`.
+//!
+//! A `Conversation` owns the message history of a session along with the usage it has consumed.
+//! `ProviderRuntime::run_conversation` appends the new turn, sends the whole history, and records
+//! the assistant reply, so callers no longer manage message vectors by hand. Conversations are
+//! plain serde values and can be persisted between turns with `to_json`/`from_json`, or with
+//! `to_encrypted`/`from_encrypted` to keep stored transcripts out of plaintext.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::{ErrorSource, RuntimeError};
+use crate::core::traits::Encryptor;
+use crate::core::types::{
+    ContentPart, Message, MessageRole, ProviderId, ProviderRequest, ProviderResponse, ToolResult,
+    Usage,
+};
+use crate::handoff;
+use crate::runtime::{ProviderRuntime, tool_loop};
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Conversation {
+    #[serde(default)]
+    messages: Vec<Message>,
+    /// Usage summed over every recorded response.
+    #[serde(default)]
+    usage: Usage,
+    /// Responses recorded so far.
+    #[serde(default)]
+    turns: u32,
+    /// Provider that served the last recorded response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_provider: Option<ProviderId>,
+    /// Model that served the last recorded response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_model: Option<String>,
+}
+
+impl Conversation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a conversation with a system prompt.
+    pub fn with_system(text: impl Into<String>) -> Self {
+        let mut conversation = Self::new();
+        conversation.push(text_message(MessageRole::System, text));
+        conversation
+    }
+
+    pub fn push(&mut self, message: Message) {
+        self.messages.push(message);
+    }
+
+    pub fn push_user(&mut self, text: impl Into<String>) {
+        self.push(text_message(MessageRole::User, text));
+    }
+
+    /// Appends a tool message answering the tool calls of the last assistant turn.
+    pub fn push_tool_results(&mut self, results: Vec<ToolResult>) {
+        self.push(tool_loop::tool_results_message(results));
+    }
+
+    /// Appends `response`'s output as an assistant turn and adds its usage to the running total.
+    /// When a different provider served `response` than the previous turn, as after a fallback
+    /// or hedge, the history is first normalized for the provider that served it.
+    pub fn record_response(&mut self, response: &ProviderResponse) {
+        if self
+            .last_provider
+            .as_ref()
+            .is_some_and(|last_provider| *last_provider != response.provider)
+        {
+            self.messages = handoff::normalize_handoff_messages(&self.messages, &response.provider);
+        }
+        self.push(tool_loop::assistant_message(response));
+        self.usage.accumulate(&response.usage);
+        self.turns += 1;
+        self.last_provider = Some(response.provider.clone());
+        self.last_model = Some(response.model.clone());
+    }
+
+    pub fn messages(&self) -> &[Message] {
+        &self.messages
+    }
+
+    pub fn usage(&self) -> &Usage {
+        &self.usage
+    }
+
+    pub fn turns(&self) -> u32 {
+        self.turns
+    }
+
+    pub fn last_provider(&self) -> Option<&ProviderId> {
+        self.last_provider.as_ref()
+    }
+
+    pub fn last_model(&self) -> Option<&str> {
+        self.last_model.as_deref()
+    }
+
+    /// The history prepared for `provider`. Messages are normalized for handoff when the last
+    /// turn was served by a different provider.
+    pub fn messages_for(&self, provider: &ProviderId) -> Vec<Message> {
+        match &self.last_provider {
+            Some(last_provider) if last_provider != provider => {
+                handoff::normalize_handoff_messages(&self.messages, provider)
+            }
+            _ => self.messages.clone(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, RuntimeError> {
+        serde_json::to_string(self).map_err(serialization_error)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, RuntimeError> {
+        serde_json::from_str(json).map_err(serialization_error)
+    }
+
+    /// The conversation's JSON encrypted with `encryptor`, for storing transcripts at rest.
+    pub fn to_encrypted(&self, encryptor: &dyn Encryptor) -> Result<Vec<u8>, RuntimeError> {
+        let json = serde_json::to_vec(self).map_err(serialization_error)?;
+        encryptor
+            .encrypt(&json)
+            .map_err(|error| encryption_error("encrypt", error))
+    }
+
+    pub fn from_encrypted(
+        ciphertext: &[u8],
+        encryptor: &dyn Encryptor,
+    ) -> Result<Self, RuntimeError> {
+        let json = encryptor
+            .decrypt(ciphertext)
+            .map_err(|error| encryption_error("decrypt", error))?;
+        serde_json::from_slice(&json).map_err(serialization_error)
+    }
+}
+
+impl ProviderRuntime {
+    /// Runs one turn of `conversation`. `request.messages` holds the new turn's messages; they
+    /// are appended to the conversation, and the full history is sent with `request`'s model and
+    /// settings. On success the assistant reply and its usage are recorded. On failure the new
+    /// messages stay in the conversation so the turn can be retried.
+    pub async fn run_conversation(
+        &self,
+        conversation: &mut Conversation,
+        mut request: ProviderRequest,
+    ) -> Result<ProviderResponse, RuntimeError> {
+        for message in request.messages.drain(..) {
+            conversation.push(message);
+        }
+        request.messages = conversation.messages.clone();
+
+        let response = self.run(request).await?;
+        conversation.record_response(&response);
+        Ok(response)
+    }
+}
+
+fn text_message(role: MessageRole, text: impl Into<String>) -> Message {
+    Message {
+        role,
+        content: vec![ContentPart::Text { text: text.into() }],
+        cache_hint: None,
+    }
+}
+
+fn serialization_error(error: serde_json::Error) -> RuntimeError {
+    RuntimeError::SerializationError {
+        provider: None,
+        model: None,
+        request_id: None,
+        message: error.to_string(),
+        source: Some(ErrorSource::new(error)),
+    }
+}
+
+fn encryption_error(operation: &str, error: ErrorSource) -> RuntimeError {
+    RuntimeError::SerializationError {
+        provider: None,
+        model: None,
+        request_id: None,
+        message: format!("failed to {operation} conversation: {error}"),
+        source: Some(error),
+    }
+}
+
+#[cfg(test)]
+mod tests;
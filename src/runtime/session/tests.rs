@@ -0,0 +1,217 @@
+use std::sync::Arc;
+
+use super::Conversation;
+use crate::core::error::{ErrorSource, ProviderError, RuntimeError};
+use crate::core::traits::Encryptor;
+use crate::core::types::{
+    AssistantOutput, ContentPart, FinishReason, Message, MessageRole, ModelRef, ProviderId,
+    ProviderRequest, ProviderResponse, Usage,
+};
+use crate::providers::mock::{MockAdapter, MockMatcher, MockOutcome};
+use crate::runtime::ProviderRuntime;
+
+fn turn(text: &str) -> ProviderRequest {
+    ProviderRequest::new(
+        ModelRef {
+            provider_hint: Some(ProviderId::Openai),
+            model_id: "gpt-5-mini".to_string(),
+            requirements: None,
+        },
+        vec![Message {
+            role: MessageRole::User,
+            content: vec![ContentPart::Text {
+                text: text.to_string(),
+            }],
+            cache_hint: None,
+        }],
+    )
+}
+
+fn response(text: &str, input_tokens: u64) -> ProviderResponse {
+    ProviderResponse {
+        output: AssistantOutput {
+            content: vec![ContentPart::Text {
+                text: text.to_string(),
+            }],
+            structured_output: None,
+            annotations: Vec::new(),
+        },
+        usage: Usage {
+            input_tokens: Some(input_tokens),
+            output_tokens: Some(1),
+            ..Usage::default()
+        },
+        cost: None,
+        provider: ProviderId::Openai,
+        model: "gpt-5-mini".to_string(),
+        raw_provider_response: None,
+        finish_reason: FinishReason::Stop,
+        warnings: Vec::new(),
+        alternatives: Vec::new(),
+        response_id: None,
+        request_id: None,
+        rate_limit: None,
+    }
+}
+
+#[tokio::test]
+async fn test_run_conversation_sends_history_and_accumulates_usage() {
+    let adapter = Arc::new(MockAdapter::new(ProviderId::Openai).on_sequence(
+        MockMatcher::any(),
+        vec![
+            MockOutcome::response(response("first", 10)),
+            MockOutcome::response(response("second", 20)),
+        ],
+    ));
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .build();
+    let mut conversation = Conversation::with_system("be brief");
+
+    runtime
+        .run_conversation(&mut conversation, turn("hello"))
+        .await
+        .expect("first turn should succeed");
+    runtime
+        .run_conversation(&mut conversation, turn("again"))
+        .await
+        .expect("second turn should succeed");
+
+    let requests = adapter.requests();
+    assert_eq!(requests[0].messages.len(), 2);
+    assert_eq!(requests[1].messages.len(), 4);
+    assert_eq!(requests[1].messages[2].role, MessageRole::Assistant);
+    assert_eq!(conversation.messages().len(), 5);
+    assert_eq!(conversation.turns(), 2);
+    assert_eq!(conversation.usage().input_tokens, Some(30));
+    assert_eq!(conversation.usage().output_tokens, Some(2));
+    assert_eq!(conversation.last_provider(), Some(&ProviderId::Openai));
+}
+
+#[tokio::test]
+async fn test_failed_turn_keeps_new_messages_for_retry() {
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(MockAdapter::new(ProviderId::Openai)))
+        .build();
+    let mut conversation = Conversation::new();
+
+    let error = runtime
+        .run_conversation(&mut conversation, turn("hello"))
+        .await
+        .expect_err("unscripted mock should fail");
+
+    assert!(matches!(error, RuntimeError::ProviderProtocolError { .. }));
+    assert_eq!(conversation.messages().len(), 1);
+    assert_eq!(conversation.turns(), 0);
+}
+
+#[tokio::test]
+async fn test_run_conversation_records_provider_that_served_fallback() {
+    let primary = Arc::new(MockAdapter::new(ProviderId::Openai).on(
+        MockMatcher::any(),
+        MockOutcome::error(ProviderError::Status {
+            provider: ProviderId::Openai,
+            model: Some("gpt-5-mini".to_string()),
+            status_code: 503,
+            request_id: None,
+            message: "overloaded".to_string(),
+            rate_limit: None,
+        }),
+    ));
+    let fallback = Arc::new(
+        MockAdapter::new(ProviderId::Anthropic)
+            .on(MockMatcher::any(), MockOutcome::text("from fallback")),
+    );
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(primary)
+        .with_adapter(fallback.clone())
+        .with_fallback_chain(
+            "gpt-5*",
+            vec![ModelRef {
+                provider_hint: Some(ProviderId::Anthropic),
+                model_id: "claude-sonnet-4-5".to_string(),
+                requirements: None,
+            }],
+        )
+        .build();
+    let mut conversation = Conversation::with_system("be brief");
+
+    let response = runtime
+        .run_conversation(&mut conversation, turn("hello"))
+        .await
+        .expect("fallback should serve the turn");
+
+    assert_eq!(response.provider, ProviderId::Anthropic);
+    assert_eq!(conversation.last_provider(), Some(&ProviderId::Anthropic));
+    assert_eq!(conversation.last_model(), Some("claude-sonnet-4-5"));
+    assert_eq!(fallback.requests()[0].messages.len(), 2);
+    assert_eq!(conversation.messages().len(), 3);
+}
+
+#[test]
+fn test_conversation_json_round_trip() {
+    let mut conversation = Conversation::with_system("be brief");
+    conversation.push_user("hello");
+    conversation.record_response(&response("hi", 5));
+
+    let json = conversation
+        .to_json()
+        .expect("conversation should serialize");
+    let restored = Conversation::from_json(&json).expect("conversation should deserialize");
+
+    assert_eq!(restored, conversation);
+    assert!(matches!(
+        Conversation::from_json("{\"messages\": 3}"),
+        Err(RuntimeError::SerializationError { .. })
+    ));
+}
+
+/// XORs every byte with a fixed key and rejects input that does not start with its marker.
+struct XorEncryptor(u8);
+
+impl Encryptor for XorEncryptor {
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, ErrorSource> {
+        let mut ciphertext = b"xor:".to_vec();
+        ciphertext.extend(plaintext.iter().map(|byte| byte ^ self.0));
+        Ok(ciphertext)
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, ErrorSource> {
+        let body = ciphertext.strip_prefix(b"xor:").ok_or_else(|| {
+            ErrorSource::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "missing marker",
+            ))
+        })?;
+        Ok(body.iter().map(|byte| byte ^ self.0).collect())
+    }
+}
+
+#[test]
+fn test_conversation_encrypted_round_trip() {
+    let mut conversation = Conversation::with_system("be brief");
+    conversation.push_user("my account number is 1234");
+    let encryptor = XorEncryptor(0x5a);
+
+    let stored = conversation
+        .to_encrypted(&encryptor)
+        .expect("conversation should encrypt");
+    assert!(
+        !String::from_utf8_lossy(&stored).contains("account number"),
+        "transcript must not be stored in plaintext"
+    );
+    let restored =
+        Conversation::from_encrypted(&stored, &encryptor).expect("conversation should decrypt");
+    assert_eq!(restored, conversation);
+
+    let error = Conversation::from_encrypted(b"{}", &encryptor).expect_err("not ciphertext");
+    match error {
+        RuntimeError::SerializationError { message, .. } => {
+            assert!(
+                message.contains("failed to decrypt conversation"),
+                "{message}"
+            );
+        }
+        other => panic!("expected serialization error, got {other:?}"),
+    }
+}
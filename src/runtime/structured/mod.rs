@@ -0,0 +1,226 @@
+//! Typed structured output on top of `ProviderRuntime::run`.
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::core::error::RuntimeError;
+use crate::core::types::{ContentPart, Message, MessageRole, ProviderRequest, ProviderResponse};
+use crate::runtime::ProviderRuntime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StructuredOptions {
+    /// Extra attempts made after an output fails to decode. Each one re-sends the conversation
+    /// with the invalid output and the decode error appended. Defaults to 0 (no repair).
+    pub max_repair_attempts: u32,
+}
+
+/// What the runtime does when `structured_output` violates the requested JSON Schema.
+#[cfg(feature = "jsonschema")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StructuredOutputPolicy {
+    /// Skip validation.
+    #[default]
+    Off,
+    /// Return the response with a `runtime.structured_output_schema_violation` warning.
+    Warn,
+    /// Fail with `RuntimeError::StructuredOutputInvalid`.
+    Error,
+}
+
+/// A decoded structured output together with the response it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructuredResponse<T> {
+    pub value: T,
+    pub response: ProviderResponse,
+}
+
+impl ProviderRuntime {
+    /// Runs `request` with `ResponseFormat::JsonSchema` generated from `T` and decodes the
+    /// structured output into `T`.
+    #[cfg(feature = "schemars")]
+    pub async fn run_structured<T>(
+        &self,
+        request: ProviderRequest,
+    ) -> Result<StructuredResponse<T>, RuntimeError>
+    where
+        T: schemars::JsonSchema + DeserializeOwned,
+    {
+        self.run_structured_with(request, StructuredOptions::default())
+            .await
+    }
+
+    /// Like `run_structured`, with opt-in repair attempts for outputs that fail to decode.
+    #[cfg(feature = "schemars")]
+    pub async fn run_structured_with<T>(
+        &self,
+        mut request: ProviderRequest,
+        options: StructuredOptions,
+    ) -> Result<StructuredResponse<T>, RuntimeError>
+    where
+        T: schemars::JsonSchema + DeserializeOwned,
+    {
+        request.response_format = crate::core::types::ResponseFormat::JsonSchema {
+            name: schema_name(&T::schema_name()),
+            schema: crate::runtime::tool_loop::json_schema_for::<T>(),
+        };
+        self.run_decoded(request, options).await
+    }
+
+    /// Runs `request` as-is and decodes its structured output into `T`, re-prompting with the
+    /// decode error up to `options.max_repair_attempts` times. Use this when the request already
+    /// carries a hand-written `ResponseFormat`.
+    pub async fn run_decoded<T: DeserializeOwned>(
+        &self,
+        mut request: ProviderRequest,
+        options: StructuredOptions,
+    ) -> Result<StructuredResponse<T>, RuntimeError> {
+        let mut repairs_left = options.max_repair_attempts;
+        loop {
+            let response = self.run(request.clone()).await?;
+            let error = match decode_output::<T>(&response) {
+                Ok(value) => return Ok(StructuredResponse { value, response }),
+                Err(error) => error,
+            };
+
+            if repairs_left == 0 {
+                return Err(RuntimeError::SerializationError {
+                    provider: Some(response.provider),
+                    model: Some(response.model),
+                    request_id: response.request_id,
+                    message: format!("structured output did not decode: {error}"),
+                    source: None,
+                });
+            }
+            repairs_left -= 1;
+            append_repair_turn(&mut request, &response, &error);
+        }
+    }
+}
+
+/// Checks a `ResponseFormat::JsonSchema` response's parsed `structured_output` against the
+/// schema. Outputs that failed to parse are left to the translator's
+/// `structured_output_parse_failed` warning.
+#[cfg(feature = "jsonschema")]
+pub(crate) fn validate_structured_output(
+    policy: StructuredOutputPolicy,
+    request: &ProviderRequest,
+    response: &mut ProviderResponse,
+) -> Result<(), RuntimeError> {
+    use crate::core::types::{ResponseFormat, RuntimeWarning, WarningCode};
+
+    const MAX_REPORTED_VIOLATIONS: usize = 5;
+
+    if policy == StructuredOutputPolicy::Off {
+        return Ok(());
+    }
+    let (ResponseFormat::JsonSchema { schema, .. }, Some(output)) =
+        (&request.response_format, &response.output.structured_output)
+    else {
+        return Ok(());
+    };
+
+    let validator = match jsonschema::validator_for(schema) {
+        Ok(validator) => validator,
+        Err(error) => {
+            response.warnings.push(RuntimeWarning {
+                code: WarningCode::StructuredOutputSchemaInvalid,
+                message: format!("requested JSON schema could not be compiled: {error}"),
+            });
+            return Ok(());
+        }
+    };
+
+    let violations = validator
+        .iter_errors(output)
+        .take(MAX_REPORTED_VIOLATIONS)
+        .map(|error| format!("{}: {error}", error.instance_path))
+        .collect::<Vec<_>>();
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    let message = format!("schema violations: {}", violations.join("; "));
+    match policy {
+        StructuredOutputPolicy::Error => Err(RuntimeError::StructuredOutputInvalid {
+            provider: Some(response.provider.clone()),
+            model: Some(response.model.clone()),
+            request_id: response.request_id.clone(),
+            message,
+        }),
+        _ => {
+            response.warnings.push(RuntimeWarning {
+                code: WarningCode::StructuredOutputSchemaViolation,
+                message,
+            });
+            Ok(())
+        }
+    }
+}
+
+/// Decodes the parsed structured output, falling back to the response text for providers or
+/// failures that left `structured_output` unset.
+pub(crate) fn decode_output<T: DeserializeOwned>(response: &ProviderResponse) -> Result<T, String> {
+    let value = match &response.output.structured_output {
+        Some(value) => value.clone(),
+        None => serde_json::from_str::<Value>(&output_text(response))
+            .map_err(|error| format!("output is not valid JSON: {error}"))?,
+    };
+    serde_json::from_value(value).map_err(|error| error.to_string())
+}
+
+/// Appends the rejected output and a correction request to the conversation.
+pub(crate) fn append_repair_turn(
+    request: &mut ProviderRequest,
+    response: &ProviderResponse,
+    error: &str,
+) {
+    request.messages.push(Message {
+        role: MessageRole::Assistant,
+        content: vec![ContentPart::Text {
+            text: output_text(response),
+        }],
+        cache_hint: None,
+    });
+    request.messages.push(Message {
+        role: MessageRole::User,
+        content: vec![ContentPart::Text {
+            text: format!(
+                "Your previous reply could not be parsed: {error}. Reply again with only JSON \
+                 that matches the requested schema."
+            ),
+        }],
+        cache_hint: None,
+    });
+}
+
+pub(crate) fn output_text(response: &ProviderResponse) -> String {
+    response
+        .output
+        .content
+        .iter()
+        .filter_map(|part| match part {
+            ContentPart::Text { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("")
+}
+
+/// Response format names are limited to `[A-Za-z0-9_-]` by OpenAI; other characters (such as
+/// the `<>` of generic type names) become `_`.
+#[cfg(feature = "schemars")]
+fn schema_name(type_name: &str) -> String {
+    type_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,259 @@
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use super::StructuredOptions;
+use crate::core::error::RuntimeError;
+use crate::core::types::{
+    ContentPart, Message, MessageRole, ModelRef, ProviderId, ProviderRequest, ResponseFormat,
+    WarningCode,
+};
+use crate::providers::mock::{MockAdapter, MockMatcher, MockOutcome};
+use crate::runtime::ProviderRuntime;
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+struct Verdict {
+    label: String,
+    score: f64,
+}
+
+fn request() -> ProviderRequest {
+    ProviderRequest {
+        response_format: ResponseFormat::JsonObject,
+        ..ProviderRequest::new(
+            ModelRef {
+                provider_hint: Some(ProviderId::Openai),
+                model_id: "gpt-5-mini".to_string(),
+                requirements: None,
+            },
+            vec![Message {
+                role: MessageRole::User,
+                content: vec![ContentPart::Text {
+                    text: "classify: great product".to_string(),
+                }],
+                cache_hint: None,
+            }],
+        )
+    }
+}
+
+fn scripted_runtime(outcomes: Vec<MockOutcome>) -> (ProviderRuntime, Arc<MockAdapter>) {
+    let adapter =
+        Arc::new(MockAdapter::new(ProviderId::Openai).on_sequence(MockMatcher::any(), outcomes));
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .build();
+    (runtime, adapter)
+}
+
+#[tokio::test]
+async fn test_run_decoded_repairs_invalid_output_when_enabled() {
+    let outcomes = vec![
+        MockOutcome::text("label: positive"),
+        MockOutcome::text(r#"{"label":"positive","score":0.9}"#),
+    ];
+
+    let (runtime, _) = scripted_runtime(outcomes.clone());
+    let err = runtime
+        .run_decoded::<Verdict>(request(), StructuredOptions::default())
+        .await
+        .expect_err("repair is opt-in");
+    assert!(matches!(err, RuntimeError::SerializationError { .. }));
+
+    let (runtime, adapter) = scripted_runtime(outcomes);
+    let decoded = runtime
+        .run_decoded::<Verdict>(
+            request(),
+            StructuredOptions {
+                max_repair_attempts: 1,
+            },
+        )
+        .await
+        .expect("repair should succeed");
+
+    assert_eq!(
+        decoded.value,
+        Verdict {
+            label: "positive".to_string(),
+            score: 0.9,
+        }
+    );
+    let repair = &adapter.requests()[1];
+    assert_eq!(repair.messages.len(), 3);
+    assert_eq!(repair.messages[1].role, MessageRole::Assistant);
+    assert!(matches!(
+        &repair.messages[2].content[0],
+        ContentPart::Text { text } if text.contains("could not be parsed")
+    ));
+}
+
+#[cfg(feature = "schemars")]
+#[tokio::test]
+async fn test_run_structured_fills_json_schema_from_type() {
+    let (runtime, adapter) = scripted_runtime(vec![MockOutcome::text(
+        r#"{"label":"positive","score":0.5}"#,
+    )]);
+
+    let decoded = runtime
+        .run_structured::<Verdict>(request())
+        .await
+        .expect("structured run");
+
+    assert_eq!(decoded.value.label, "positive");
+    let ResponseFormat::JsonSchema { name, schema } = &adapter.requests()[0].response_format else {
+        panic!("expected a JSON schema response format");
+    };
+    assert_eq!(name, "Verdict");
+    assert_eq!(schema["required"], serde_json::json!(["label", "score"]));
+}
+
+#[cfg(feature = "jsonschema")]
+#[tokio::test]
+async fn test_structured_output_policy_validates_against_schema() {
+    use super::StructuredOutputPolicy;
+    use crate::core::types::{AssistantOutput, FinishReason, ProviderResponse, Usage};
+
+    let output = serde_json::json!({ "label": "positive", "score": "high" });
+    let response = ProviderResponse {
+        output: AssistantOutput {
+            content: vec![ContentPart::Text {
+                text: output.to_string(),
+            }],
+            structured_output: Some(output),
+            annotations: Vec::new(),
+        },
+        usage: Usage::default(),
+        cost: None,
+        provider: ProviderId::Openai,
+        model: "gpt-5-mini".to_string(),
+        raw_provider_response: None,
+        request_id: None,
+        rate_limit: None,
+        finish_reason: FinishReason::Stop,
+        warnings: Vec::new(),
+        alternatives: Vec::new(),
+        response_id: None,
+    };
+    let mut req = request();
+    req.response_format = ResponseFormat::JsonSchema {
+        name: "verdict".to_string(),
+        schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "label": { "type": "string" },
+                "score": { "type": "number" }
+            },
+            "required": ["label", "score"]
+        }),
+    };
+    let runtime_with = |policy| {
+        ProviderRuntime::builder()
+            .with_adapter(Arc::new(
+                MockAdapter::new(ProviderId::Openai)
+                    .on(MockMatcher::any(), MockOutcome::response(response.clone())),
+            ))
+            .with_structured_output_policy(policy)
+            .build()
+    };
+
+    let warned = runtime_with(StructuredOutputPolicy::Warn)
+        .run(req.clone())
+        .await
+        .expect("warn policy returns the response");
+    assert_eq!(warned.warnings.len(), 1);
+    assert_eq!(
+        warned.warnings[0].code,
+        "runtime.structured_output_schema_violation"
+    );
+    assert!(warned.warnings[0].message.contains("/score"));
+
+    let err = runtime_with(StructuredOutputPolicy::Error)
+        .run(req.clone())
+        .await
+        .expect_err("error policy rejects the response");
+    assert!(matches!(err, RuntimeError::StructuredOutputInvalid { .. }));
+
+    let unchecked = runtime_with(StructuredOutputPolicy::Off)
+        .run(req)
+        .await
+        .expect("validation is off");
+    assert!(unchecked.warnings.is_empty());
+}
+
+#[tokio::test]
+async fn test_runtime_repairs_unparseable_structured_output() {
+    use crate::core::types::{
+        AssistantOutput, CostBreakdown, FinishReason, PricingSource, ProviderResponse,
+        RuntimeWarning, Usage,
+    };
+
+    let attempt = |text: &str, structured_output, warnings| ProviderResponse {
+        output: AssistantOutput {
+            content: vec![ContentPart::Text {
+                text: text.to_string(),
+            }],
+            structured_output,
+            annotations: Vec::new(),
+        },
+        usage: Usage {
+            input_tokens: Some(10),
+            output_tokens: Some(5),
+            ..Usage::default()
+        },
+        cost: Some(CostBreakdown {
+            currency: "USD".to_string(),
+            input_cost: 0.1,
+            cached_input_cost: 0.0,
+            output_cost: 0.2,
+            fixed_cost: 0.0,
+            total_cost: 0.3,
+            pricing_source: PricingSource::ProviderReported,
+        }),
+        provider: ProviderId::Openai,
+        model: "gpt-5-mini".to_string(),
+        raw_provider_response: None,
+        request_id: None,
+        rate_limit: None,
+        finish_reason: FinishReason::Stop,
+        warnings,
+        alternatives: Vec::new(),
+        response_id: None,
+    };
+    let adapter = Arc::new(MockAdapter::new(ProviderId::Openai).on_sequence(
+        MockMatcher::any(),
+        vec![
+            MockOutcome::response(attempt(
+                "{\"label\": ",
+                None,
+                vec![RuntimeWarning {
+                    code: WarningCode::StructuredOutputParseFailed,
+                    message: "EOF while parsing an object".to_string(),
+                }],
+            )),
+            MockOutcome::response(attempt(
+                r#"{"label":"positive","score":1.0}"#,
+                Some(serde_json::json!({ "label": "positive", "score": 1.0 })),
+                Vec::new(),
+            )),
+        ],
+    ));
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_structured_output_repair(2)
+        .build();
+
+    let response = runtime.run(request()).await.expect("repair should succeed");
+
+    assert_eq!(adapter.requests().len(), 2);
+    assert_eq!(response.usage.input_tokens, Some(20));
+    assert_eq!(response.usage.output_tokens, Some(10));
+    let cost = response.cost.expect("combined cost");
+    assert!((cost.total_cost - 0.6).abs() < 1e-9);
+    assert_eq!(response.warnings.len(), 1);
+    assert_eq!(
+        response.warnings[0].code,
+        "runtime.structured_output_repaired"
+    );
+    assert!(response.warnings[0].message.contains("EOF while parsing"));
+}
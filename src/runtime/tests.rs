@@ -1,19 +1,23 @@
 use std::collections::BTreeMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use async_trait::async_trait;
 use serde_json::json;
 
 use super::ProviderRuntime;
-use crate::core::error::ProviderError;
-use crate::core::traits::ProviderAdapter;
+use super::context::RequestContext;
+use crate::core::error::{ConfigError, ProviderError, RoutingError, RuntimeError};
+use crate::core::traits::{ProviderAdapter, Redactor, TokenProvider};
 use crate::core::types::{
     AdapterContext, AssistantOutput, ContentPart, CostBreakdown, DiscoveryOptions, FinishReason,
-    Message, MessageRole, ModelCatalog, ModelInfo, ModelRef, PricingSource, ProviderCapabilities,
-    ProviderId, ProviderRequest, ProviderResponse, ResponseFormat, RuntimeWarning, ToolChoice,
-    ToolDefinition, Usage,
+    Message, MessageRole, ModelCatalog, ModelInfo, ModelPreference, ModelRef, ModelRequirements,
+    PricingSource, ProviderCapabilities, ProviderId, ProviderRequest, ProviderResponse,
+    RateLimitInfo, ResponseFormat, RuntimeWarning, ToolChoice, ToolDefinition, Usage, WarningCode,
 };
 use crate::pricing::{PriceRule, PricingTable};
+use crate::registry::{KeyPool, KeyRotation, RoutingPolicy, StaticCredentialStore};
+use crate::transport::redaction::DefaultRedactor;
 
 #[derive(Clone)]
 struct MockAdapter {
@@ -21,6 +25,10 @@ struct MockAdapter {
     capabilities: ProviderCapabilities,
     run_response: ProviderResponse,
     discovered_models: Vec<ModelInfo>,
+    encode_warnings: Vec<RuntimeWarning>,
+    last_request: Arc<Mutex<Option<ProviderRequest>>>,
+    last_context: Arc<Mutex<Option<AdapterContext>>>,
+    run_delay: Option<Duration>,
 }
 
 impl MockAdapter {
@@ -35,8 +43,30 @@ impl MockAdapter {
             capabilities,
             run_response,
             discovered_models,
+            encode_warnings: Vec::new(),
+            last_request: Arc::new(Mutex::new(None)),
+            last_context: Arc::new(Mutex::new(None)),
+            run_delay: None,
         }
     }
+
+    fn with_run_delay(mut self, run_delay: Duration) -> Self {
+        self.run_delay = Some(run_delay);
+        self
+    }
+
+    fn with_encode_warnings(mut self, encode_warnings: Vec<RuntimeWarning>) -> Self {
+        self.encode_warnings = encode_warnings;
+        self
+    }
+
+    fn last_request(&self) -> Option<ProviderRequest> {
+        self.last_request.lock().expect("request lock").clone()
+    }
+
+    fn last_context(&self) -> Option<AdapterContext> {
+        self.last_context.lock().expect("context lock").clone()
+    }
 }
 
 #[async_trait]
@@ -49,11 +79,23 @@ impl ProviderAdapter for MockAdapter {
         self.capabilities.clone()
     }
 
-    async fn run(
+    fn encode_warnings(
         &self,
         _req: &ProviderRequest,
-        _ctx: &AdapterContext,
+    ) -> Result<Vec<RuntimeWarning>, ProviderError> {
+        Ok(self.encode_warnings.clone())
+    }
+
+    async fn run(
+        &self,
+        req: &ProviderRequest,
+        ctx: &AdapterContext,
     ) -> Result<ProviderResponse, ProviderError> {
+        *self.last_request.lock().expect("request lock") = Some(req.clone());
+        *self.last_context.lock().expect("context lock") = Some(ctx.clone());
+        if let Some(run_delay) = self.run_delay {
+            tokio::time::sleep(run_delay).await;
+        }
         Ok(self.run_response.clone())
     }
 
@@ -76,6 +118,7 @@ fn provider_capabilities(
         supports_structured_output,
         supports_thinking: false,
         supports_remote_discovery,
+        supports_parallel_tool_calls: supports_tools,
     }
 }
 
@@ -94,6 +137,11 @@ fn model(
         max_output_tokens,
         supports_tools: true,
         supports_structured_output: true,
+        pricing: None,
+        input_modalities: Vec::new(),
+        output_modalities: Vec::new(),
+        deprecated: false,
+        knowledge_cutoff: None,
     }
 }
 
@@ -110,6 +158,7 @@ fn response(
                 text: "ok".to_string(),
             }],
             structured_output: None,
+            annotations: Vec::new(),
         },
         usage,
         cost,
@@ -118,6 +167,10 @@ fn response(
         raw_provider_response: None,
         finish_reason: FinishReason::Stop,
         warnings,
+        alternatives: Vec::new(),
+        response_id: None,
+        request_id: None,
+        rate_limit: None,
     }
 }
 
@@ -131,21 +184,28 @@ fn request(
         model: ModelRef {
             provider_hint,
             model_id: model_id.to_string(),
+            requirements: None,
         },
         messages: vec![Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "hello".to_string(),
             }],
+            cache_hint: None,
         }],
         tools,
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format,
         temperature: None,
         top_p: None,
         max_output_tokens: None,
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     }
 }
 
@@ -204,6 +264,9 @@ async fn test_runtime_cost_attached_when_pricing_available() {
                 output_tokens: Some(20),
                 cached_input_tokens: None,
                 total_tokens: None,
+                reasoning_tokens: None,
+                cache_creation_input_tokens: None,
+                web_search_requests: None,
             },
             None,
             Vec::new(),
@@ -216,6 +279,13 @@ async fn test_runtime_cost_attached_when_pricing_available() {
         model_pattern: "gpt-5-mini".to_string(),
         input_cost_per_token: 0.01,
         output_cost_per_token: 0.02,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: None,
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
     }]);
 
     let runtime = runtime_with_adapter(adapter, Some(pricing_table));
@@ -334,6 +404,7 @@ async fn test_runtime_tools_capability_mismatch() {
             name: "lookup".to_string(),
             description: Some("tool".to_string()),
             parameters_schema: json!({"type":"object"}),
+            cache_hint: None,
         }],
         ResponseFormat::Text,
     );
@@ -353,6 +424,49 @@ async fn test_runtime_tools_capability_mismatch() {
     );
 }
 
+#[tokio::test]
+async fn test_runtime_drops_unsupported_parallel_tool_calls_with_warning() {
+    let mut capabilities = provider_capabilities(true, true, false);
+    capabilities.supports_parallel_tool_calls = false;
+    let adapter = Arc::new(MockAdapter::new(
+        ProviderId::Openai,
+        capabilities,
+        response(
+            ProviderId::Openai,
+            "gpt-5-mini",
+            Usage::default(),
+            None,
+            Vec::new(),
+        ),
+        Vec::new(),
+    ));
+
+    let runtime = runtime_with_adapter(adapter.clone(), None);
+    let mut req = request(
+        Some(ProviderId::Openai),
+        "gpt-5-mini",
+        Vec::new(),
+        ResponseFormat::Text,
+    );
+    req.parallel_tool_calls = Some(false);
+
+    let response = runtime.run(req).await.expect("run should succeed");
+
+    assert_eq!(
+        adapter
+            .last_request()
+            .expect("adapter should see the request")
+            .parallel_tool_calls,
+        None
+    );
+    assert!(
+        response
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "runtime.parallel_tool_calls_unsupported")
+    );
+}
+
 #[tokio::test]
 async fn test_runtime_structured_output_capability_mismatch() {
     let adapter = Arc::new(MockAdapter::new(
@@ -396,7 +510,9 @@ async fn test_runtime_preserves_existing_provider_cost() {
     let provider_cost = CostBreakdown {
         currency: "USD".to_string(),
         input_cost: 1.0,
+        cached_input_cost: 0.0,
         output_cost: 2.0,
+        fixed_cost: 0.0,
         total_cost: 3.0,
         pricing_source: PricingSource::ProviderReported,
     };
@@ -412,10 +528,13 @@ async fn test_runtime_preserves_existing_provider_cost() {
                 output_tokens: Some(20),
                 cached_input_tokens: None,
                 total_tokens: None,
+                reasoning_tokens: None,
+                cache_creation_input_tokens: None,
+                web_search_requests: None,
             },
             Some(provider_cost.clone()),
             vec![RuntimeWarning {
-                code: "provider.warning".to_string(),
+                code: WarningCode::Other("provider.warning".to_string()),
                 message: "from provider".to_string(),
             }],
         ),
@@ -427,6 +546,13 @@ async fn test_runtime_preserves_existing_provider_cost() {
         model_pattern: "gpt-5-mini".to_string(),
         input_cost_per_token: 0.01,
         output_cost_per_token: 0.02,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: None,
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
     }]);
 
     let runtime = runtime_with_adapter(adapter, Some(pricing_table));
@@ -443,3 +569,1443 @@ async fn test_runtime_preserves_existing_provider_cost() {
     assert_eq!(actual.warnings.len(), 1);
     assert_eq!(actual.warnings[0].code, "provider.warning");
 }
+
+#[tokio::test]
+async fn test_runtime_clamps_max_output_tokens_to_catalog_limit() {
+    let adapter = Arc::new(MockAdapter::new(
+        ProviderId::Openrouter,
+        provider_capabilities(true, true, false),
+        response(
+            ProviderId::Openrouter,
+            "vendor/small-model",
+            Usage::default(),
+            None,
+            Vec::new(),
+        ),
+        Vec::new(),
+    ));
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_model_catalog(ModelCatalog {
+            models: vec![model(
+                ProviderId::Openrouter,
+                "vendor/small-model",
+                None,
+                Some(16_000),
+                Some(4_096),
+            )],
+        })
+        .build();
+
+    let mut req = request(
+        Some(ProviderId::Openrouter),
+        "vendor/small-model",
+        Vec::new(),
+        ResponseFormat::Text,
+    );
+    req.max_output_tokens = Some(8_192);
+    let clamped = runtime.run(req.clone()).await.expect("run should succeed");
+
+    assert_eq!(
+        adapter.last_request().and_then(|req| req.max_output_tokens),
+        Some(4_096)
+    );
+    assert!(
+        clamped
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "runtime.max_output_tokens_clamped")
+    );
+
+    req.max_output_tokens = Some(1_024);
+    let within_limit = runtime.run(req).await.expect("run should succeed");
+
+    assert_eq!(
+        adapter.last_request().and_then(|req| req.max_output_tokens),
+        Some(1_024)
+    );
+    assert!(within_limit.warnings.is_empty());
+}
+
+#[tokio::test]
+async fn test_runtime_defaults_max_output_tokens_from_catalog_when_enabled() {
+    let adapter = Arc::new(MockAdapter::new(
+        ProviderId::Anthropic,
+        provider_capabilities(true, true, false),
+        response(
+            ProviderId::Anthropic,
+            "claude-sonnet-4-5",
+            Usage::default(),
+            None,
+            Vec::new(),
+        ),
+        Vec::new(),
+    ));
+    let catalog = ModelCatalog {
+        models: vec![model(
+            ProviderId::Anthropic,
+            "claude-sonnet-4-5",
+            None,
+            Some(200_000),
+            Some(64_000),
+        )],
+    };
+    let req = request(
+        Some(ProviderId::Anthropic),
+        "claude-sonnet-4-5",
+        Vec::new(),
+        ResponseFormat::Text,
+    );
+
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_model_catalog(catalog.clone())
+        .build();
+    runtime.run(req.clone()).await.expect("run should succeed");
+    assert_eq!(
+        adapter.last_request().and_then(|req| req.max_output_tokens),
+        None
+    );
+
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_model_catalog(catalog)
+        .with_catalog_output_token_defaults(true)
+        .build();
+    let defaulted = runtime.run(req).await.expect("run should succeed");
+    assert_eq!(
+        adapter.last_request().and_then(|req| req.max_output_tokens),
+        Some(64_000)
+    );
+    assert!(
+        defaulted
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "runtime.max_output_tokens_defaulted")
+    );
+}
+
+#[tokio::test]
+async fn test_runtime_encode_warning_policy_runs_before_dispatch() {
+    let adapter = Arc::new(
+        MockAdapter::new(
+            ProviderId::Openai,
+            provider_capabilities(true, true, false),
+            response(
+                ProviderId::Openai,
+                "gpt-5-mini",
+                Usage::default(),
+                None,
+                Vec::new(),
+            ),
+            Vec::new(),
+        )
+        .with_encode_warnings(vec![RuntimeWarning {
+            code: WarningCode::BothTemperatureAndTopPSet,
+            message: "set one".to_string(),
+        }]),
+    );
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let hook_seen = Arc::clone(&seen);
+    let req = request(
+        Some(ProviderId::Openai),
+        "gpt-5-mini",
+        Vec::new(),
+        ResponseFormat::Text,
+    );
+
+    let observing = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_encode_warning_hook(move |_req, warnings| {
+            hook_seen
+                .lock()
+                .expect("hook lock")
+                .extend(warnings.iter().map(|warning| warning.code.clone()));
+        })
+        .build();
+    observing
+        .run(req.clone())
+        .await
+        .expect("hook alone should not block");
+    assert_eq!(
+        *seen.lock().expect("hook lock"),
+        vec![WarningCode::BothTemperatureAndTopPSet]
+    );
+
+    *adapter.last_request.lock().expect("request lock") = None;
+    let strict = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_fail_fast_warnings(["both_temperature_and_top_p_set"])
+        .build();
+    let err = strict.run(req).await.expect_err("warning should fail fast");
+
+    assert!(matches!(
+        err,
+        RuntimeError::EncodeWarningRejected { ref code, .. } if code == "both_temperature_and_top_p_set"
+    ));
+    assert!(adapter.last_request().is_none());
+}
+
+#[tokio::test]
+async fn test_runtime_reads_task_local_request_context() {
+    let adapter = Arc::new(
+        MockAdapter::new(
+            ProviderId::Openai,
+            provider_capabilities(true, true, false),
+            response(
+                ProviderId::Openai,
+                "gpt-5-mini",
+                Usage::default(),
+                None,
+                Vec::new(),
+            ),
+            Vec::new(),
+        )
+        .with_run_delay(Duration::from_millis(200)),
+    );
+    let runtime = runtime_with_adapter(adapter.clone(), None);
+    let mut req = request(
+        Some(ProviderId::Openai),
+        "gpt-5-mini",
+        Vec::new(),
+        ResponseFormat::Text,
+    );
+    req.metadata
+        .insert("tenant".to_string(), "explicit".to_string());
+
+    RequestContext::new()
+        .with_trace_id("trace-1")
+        .with_tenant("ambient")
+        .scope(runtime.run(req.clone()))
+        .await
+        .expect("run should succeed");
+
+    let sent = adapter.last_request().expect("request should be sent");
+    assert_eq!(sent.metadata, req.metadata);
+    let ctx = adapter.last_context().expect("context should be passed");
+    assert_eq!(
+        ctx.metadata.get("trace_id").map(String::as_str),
+        Some("trace-1")
+    );
+    assert_eq!(
+        ctx.metadata.get("tenant").map(String::as_str),
+        Some("ambient")
+    );
+
+    let err = RequestContext::new()
+        .with_timeout(Duration::from_millis(20))
+        .scope(runtime.run(req))
+        .await
+        .expect_err("deadline should expire");
+    assert!(matches!(
+        err,
+        RuntimeError::Timeout {
+            provider: Some(ProviderId::Openai),
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_runtime_fails_over_along_fallback_chain() {
+    use crate::providers::mock::{MockAdapter as ScriptedAdapter, MockMatcher, MockOutcome};
+
+    let primary = Arc::new(ScriptedAdapter::new(ProviderId::Openai).on(
+        MockMatcher::any(),
+        MockOutcome::error(ProviderError::Status {
+            provider: ProviderId::Openai,
+            model: Some("gpt-5-mini".to_string()),
+            status_code: 503,
+            request_id: None,
+            message: "overloaded".to_string(),
+            rate_limit: None,
+        }),
+    ));
+    let secondary = Arc::new(
+        ScriptedAdapter::new(ProviderId::Openrouter)
+            .on(MockMatcher::any(), MockOutcome::text("from fallback")),
+    );
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(primary.clone())
+        .with_adapter(secondary.clone())
+        .with_fallback_chain(
+            "gpt-5*",
+            vec![ModelRef {
+                provider_hint: Some(ProviderId::Openrouter),
+                model_id: "openai/gpt-5-mini".to_string(),
+                requirements: None,
+            }],
+        )
+        .build();
+
+    let response = runtime
+        .run(request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        ))
+        .await
+        .expect("fallback should succeed");
+
+    assert_eq!(response.provider, ProviderId::Openrouter);
+    assert_eq!(response.model, "openai/gpt-5-mini");
+    assert_eq!(response.warnings.len(), 1);
+    assert_eq!(response.warnings[0].code, "runtime.provider_failover");
+    assert!(response.warnings[0].message.contains("overloaded"));
+    assert_eq!(primary.requests().len(), 1);
+    assert_eq!(secondary.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_request_deadline_spans_fallbacks_and_names_attempts() {
+    use crate::providers::mock::{MockAdapter as ScriptedAdapter, MockMatcher, MockOutcome};
+
+    let primary = Arc::new(ScriptedAdapter::new(ProviderId::Openai).on(
+        MockMatcher::any(),
+        MockOutcome::error(ProviderError::Status {
+            provider: ProviderId::Openai,
+            model: Some("gpt-5-mini".to_string()),
+            status_code: 503,
+            request_id: None,
+            message: "overloaded".to_string(),
+            rate_limit: None,
+        }),
+    ));
+    let slow_fallback = Arc::new(
+        MockAdapter::new(
+            ProviderId::Openrouter,
+            provider_capabilities(true, true, false),
+            response(
+                ProviderId::Openrouter,
+                "openai/gpt-5-mini",
+                Usage::default(),
+                None,
+                Vec::new(),
+            ),
+            Vec::new(),
+        )
+        .with_run_delay(Duration::from_millis(200)),
+    );
+    let last_resort = Arc::new(
+        ScriptedAdapter::new(ProviderId::Anthropic)
+            .on(MockMatcher::any(), MockOutcome::text("too late")),
+    );
+    let fallbacks = vec![
+        model_ref(Some(ProviderId::Openrouter), "openai/gpt-5-mini"),
+        model_ref(Some(ProviderId::Anthropic), "claude-sonnet-4-5"),
+    ];
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(primary.clone())
+        .with_adapter(slow_fallback.clone())
+        .with_adapter(last_resort.clone())
+        .with_fallback_chain("gpt-5*", fallbacks)
+        .with_request_deadline(Duration::from_millis(50))
+        .build();
+
+    let err = runtime
+        .run(request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        ))
+        .await
+        .expect_err("deadline should expire in the fallback chain");
+
+    let RuntimeError::Timeout {
+        provider, message, ..
+    } = err
+    else {
+        panic!("expected a timeout, got {err:?}");
+    };
+    assert_eq!(provider, Some(ProviderId::Openrouter));
+    assert_eq!(
+        message,
+        "request deadline exceeded after attempting Openai model 'gpt-5-mini', \
+         Openrouter model 'openai/gpt-5-mini'"
+    );
+    assert_eq!(primary.requests().len(), 1);
+    assert!(last_resort.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_request_context_deadline_wins_over_longer_runtime_deadline() {
+    let adapter = Arc::new(
+        MockAdapter::new(
+            ProviderId::Openai,
+            provider_capabilities(true, true, false),
+            response(
+                ProviderId::Openai,
+                "gpt-5-mini",
+                Usage::default(),
+                None,
+                Vec::new(),
+            ),
+            Vec::new(),
+        )
+        .with_run_delay(Duration::from_millis(200)),
+    );
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter)
+        .with_request_deadline(Duration::from_secs(60))
+        .build();
+
+    let err = RequestContext::new()
+        .with_timeout(Duration::from_millis(20))
+        .scope(runtime.run(request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        )))
+        .await
+        .expect_err("context deadline should expire first");
+    assert!(matches!(
+        err,
+        RuntimeError::Timeout { ref message, .. }
+            if message == "request deadline exceeded after attempting Openai model 'gpt-5-mini'"
+    ));
+}
+
+#[tokio::test]
+async fn test_runtime_redacts_provider_errors_and_traces() {
+    use crate::providers::mock::{MockAdapter as ScriptedAdapter, MockMatcher, MockOutcome};
+
+    let adapter = Arc::new(ScriptedAdapter::new(ProviderId::Openai).on(
+        MockMatcher::any(),
+        MockOutcome::error(ProviderError::Status {
+            provider: ProviderId::Openai,
+            model: Some("gpt-5-mini".to_string()),
+            status_code: 400,
+            request_id: None,
+            message:
+                r#"{"error":"bad auth Bearer tok-123","content":"my secret prompt"}"#.to_string(),
+            rate_limit: None,
+        }),
+    ));
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter)
+        .with_run_trace_capture(4)
+        .with_redactor(Arc::new(
+            DefaultRedactor::new().with_message_content_masking(true),
+        ))
+        .build();
+
+    let err = runtime
+        .run(request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        ))
+        .await
+        .expect_err("provider error should surface");
+
+    let RuntimeError::ProviderProtocolError { message, .. } = &err else {
+        panic!("expected a protocol error, got {err:?}");
+    };
+    assert_eq!(
+        message,
+        r#"{"content":"[REDACTED]","error":"bad auth Bearer [REDACTED]"}"#
+    );
+    let traces = runtime.recent_run_traces();
+    let trace_error = traces[0]
+        .error
+        .as_deref()
+        .expect("trace should record the error");
+    assert!(!trace_error.contains("tok-123"));
+    assert!(!trace_error.contains("my secret prompt"));
+}
+
+#[derive(Debug)]
+struct MarkingRedactor;
+
+impl Redactor for MarkingRedactor {
+    fn redact(&self, text: &str) -> String {
+        format!("{text} [redacted]")
+    }
+}
+
+#[tokio::test]
+async fn test_runtime_redacts_captured_runs_once() {
+    use crate::providers::mock::{MockAdapter as ScriptedAdapter, MockMatcher, MockOutcome};
+
+    let adapter = Arc::new(ScriptedAdapter::new(ProviderId::Openai).on(
+        MockMatcher::any(),
+        MockOutcome::error(ProviderError::Status {
+            provider: ProviderId::Openai,
+            model: Some("gpt-5-mini".to_string()),
+            status_code: 400,
+            request_id: None,
+            message: "bad request".to_string(),
+            rate_limit: None,
+        }),
+    ));
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter)
+        .with_run_trace_capture(4)
+        .with_redactor(Arc::new(MarkingRedactor))
+        .build();
+
+    let err = runtime
+        .run(request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        ))
+        .await
+        .expect_err("provider error should surface");
+
+    let RuntimeError::ProviderProtocolError { message, .. } = &err else {
+        panic!("expected a protocol error, got {err:?}");
+    };
+    assert_eq!(message, "bad request [redacted]");
+    let traces = runtime.recent_run_traces();
+    let trace_error = traces[0]
+        .error
+        .as_deref()
+        .expect("trace should record the error");
+    assert!(trace_error.ends_with(": bad request [redacted]"));
+}
+
+#[tokio::test]
+async fn test_runtime_fallback_skips_non_retryable_errors() {
+    use crate::providers::mock::{MockAdapter as ScriptedAdapter, MockMatcher, MockOutcome};
+
+    let primary = Arc::new(ScriptedAdapter::new(ProviderId::Openai).on(
+        MockMatcher::any(),
+        MockOutcome::error(ProviderError::Status {
+            provider: ProviderId::Openai,
+            model: Some("gpt-5-mini".to_string()),
+            status_code: 400,
+            request_id: None,
+            message: "bad request".to_string(),
+            rate_limit: None,
+        }),
+    ));
+    let secondary = Arc::new(
+        ScriptedAdapter::new(ProviderId::Openrouter)
+            .on(MockMatcher::any(), MockOutcome::text("unused")),
+    );
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(primary)
+        .with_adapter(secondary.clone())
+        .with_fallback_chain(
+            "*",
+            vec![ModelRef {
+                provider_hint: Some(ProviderId::Openrouter),
+                model_id: "openai/gpt-5-mini".to_string(),
+                requirements: None,
+            }],
+        )
+        .build();
+
+    let err = runtime
+        .run(request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        ))
+        .await
+        .expect_err("400 should not fail over");
+
+    assert!(matches!(
+        err,
+        RuntimeError::ProviderProtocolError {
+            status_code: Some(400),
+            ..
+        }
+    ));
+    assert!(secondary.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_runtime_error_carries_rate_limit_info() {
+    use crate::providers::mock::{MockAdapter as ScriptedAdapter, MockMatcher, MockOutcome};
+
+    let rate_limit = RateLimitInfo {
+        requests_remaining: Some(0),
+        requests_reset: Some("6m0s".to_string()),
+        retry_after_ms: Some(2_000),
+        ..Default::default()
+    };
+    let adapter = Arc::new(ScriptedAdapter::new(ProviderId::Openai).on(
+        MockMatcher::any(),
+        MockOutcome::error(ProviderError::from_status(
+            ProviderId::Openai,
+            Some("gpt-5-mini".to_string()),
+            429,
+            None,
+            "slow down".to_string(),
+            Some(Box::new(rate_limit.clone())),
+        )),
+    ));
+    let runtime = ProviderRuntime::builder().with_adapter(adapter).build();
+
+    let err = runtime
+        .run(request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        ))
+        .await
+        .expect_err("429 should surface");
+
+    let RuntimeError::RateLimited {
+        retry_after,
+        rate_limit: Some(info),
+        ..
+    } = err
+    else {
+        panic!("expected rate limited error with rate limit info, got {err:?}");
+    };
+    assert_eq!(retry_after, Some(Duration::from_secs(2)));
+    assert_eq!(*info, rate_limit);
+}
+
+#[tokio::test]
+async fn test_runtime_hedges_slow_primary() {
+    use crate::providers::mock::{MockAdapter as ScriptedAdapter, MockMatcher, MockOutcome};
+
+    let hedge_model = ModelRef {
+        provider_hint: Some(ProviderId::Openrouter),
+        model_id: "openai/gpt-5-mini".to_string(),
+        requirements: None,
+    };
+    let slow_primary = |delay: Duration| {
+        Arc::new(
+            MockAdapter::new(
+                ProviderId::Openai,
+                provider_capabilities(true, true, false),
+                response(
+                    ProviderId::Openai,
+                    "gpt-5-mini",
+                    Usage::default(),
+                    None,
+                    Vec::new(),
+                ),
+                Vec::new(),
+            )
+            .with_run_delay(delay),
+        )
+    };
+    let req = request(
+        Some(ProviderId::Openai),
+        "gpt-5-mini",
+        Vec::new(),
+        ResponseFormat::Text,
+    );
+
+    let hedge = Arc::new(
+        ScriptedAdapter::new(ProviderId::Openrouter)
+            .on(MockMatcher::any(), MockOutcome::text("from hedge")),
+    );
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(slow_primary(Duration::from_secs(5)))
+        .with_adapter(hedge.clone())
+        .with_hedging("gpt-5*", hedge_model.clone(), Duration::from_millis(20))
+        .build();
+
+    let response = runtime.run(req.clone()).await.expect("hedge should win");
+    assert_eq!(response.provider, ProviderId::Openrouter);
+    assert_eq!(response.warnings.len(), 1);
+    assert_eq!(response.warnings[0].code, "runtime.hedge_won");
+    assert_eq!(hedge.requests().len(), 1);
+
+    let unused_hedge = Arc::new(
+        ScriptedAdapter::new(ProviderId::Openrouter)
+            .on(MockMatcher::any(), MockOutcome::text("from hedge")),
+    );
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(slow_primary(Duration::from_millis(1)))
+        .with_adapter(unused_hedge.clone())
+        .with_hedging("gpt-5*", hedge_model, Duration::from_secs(5))
+        .build();
+
+    let response = runtime.run(req).await.expect("primary should answer");
+    assert_eq!(response.provider, ProviderId::Openai);
+    assert!(response.warnings.is_empty());
+    assert!(unused_hedge.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_runtime_queues_calls_beyond_max_concurrency() {
+    let adapter = Arc::new(
+        MockAdapter::new(
+            ProviderId::Openai,
+            provider_capabilities(true, true, false),
+            response(
+                ProviderId::Openai,
+                "gpt-5-mini",
+                Usage::default(),
+                None,
+                Vec::new(),
+            ),
+            Vec::new(),
+        )
+        .with_run_delay(Duration::from_millis(50)),
+    );
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter)
+        .with_max_concurrency(ProviderId::Openai, 1)
+        .build();
+    let req = || {
+        request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        )
+    };
+
+    let started = std::time::Instant::now();
+    let (first, second) = tokio::join!(runtime.run(req()), runtime.run(req()));
+    let (first, second) = (first.expect("first run"), second.expect("second run"));
+
+    assert!(started.elapsed() >= Duration::from_millis(100));
+    let queued = [&first, &second]
+        .iter()
+        .filter(|response| {
+            response
+                .warnings
+                .iter()
+                .any(|warning| warning.code == "runtime.concurrency_queued")
+        })
+        .count();
+    assert_eq!(queued, 1);
+}
+
+#[tokio::test]
+async fn test_runtime_model_alias_rewrites_model_id() {
+    let adapter = Arc::new(MockAdapter::new(
+        ProviderId::Openrouter,
+        provider_capabilities(true, true, false),
+        response(
+            ProviderId::Openrouter,
+            "openai/gpt-5-mini",
+            Usage::default(),
+            None,
+            Vec::new(),
+        ),
+        Vec::new(),
+    ));
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_model_alias("gpt-5-mini", ProviderId::Openai, "gpt-5-mini")
+        .with_model_alias("gpt-5-mini", ProviderId::Openrouter, "openai/gpt-5-mini")
+        .build();
+
+    runtime
+        .run(request(
+            None,
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        ))
+        .await
+        .expect("aliased run should succeed");
+
+    let sent = adapter.last_request().expect("adapter should be called");
+    assert_eq!(
+        sent.model,
+        ModelRef {
+            provider_hint: Some(ProviderId::Openrouter),
+            model_id: "openai/gpt-5-mini".to_string(),
+            requirements: None,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_runtime_selects_model_from_requirements() {
+    let adapter = Arc::new(MockAdapter::new(
+        ProviderId::Openai,
+        provider_capabilities(true, true, false),
+        response(
+            ProviderId::Openai,
+            "gpt-5-mini",
+            Usage::default(),
+            None,
+            Vec::new(),
+        ),
+        Vec::new(),
+    ));
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_model_catalog(ModelCatalog {
+            models: vec![
+                model(ProviderId::Openai, "gpt-5", None, Some(400_000), None),
+                model(ProviderId::Openai, "gpt-5-mini", None, Some(400_000), None),
+                model(ProviderId::Anthropic, "claude-haiku-4-5", None, None, None),
+            ],
+        })
+        .build();
+
+    let mut req = request(None, "", Vec::new(), ResponseFormat::Text);
+    req.model.requirements = Some(ModelRequirements {
+        requires_tools: true,
+        min_context_window: Some(200_000),
+        prefer: ModelPreference::Fastest,
+        ..ModelRequirements::default()
+    });
+    runtime
+        .run(req.clone())
+        .await
+        .expect("first selected run should succeed");
+    let first = adapter.last_request().expect("adapter should be called");
+    assert_eq!(first.model.model_id, "gpt-5");
+    assert_eq!(first.model.requirements, None);
+
+    // gpt-5 now has an observed latency, so the untried gpt-5-mini ranks after it.
+    let response = runtime
+        .run(req)
+        .await
+        .expect("second selected run should succeed");
+    let second = adapter.last_request().expect("adapter should be called");
+    assert_eq!(second.model.model_id, "gpt-5");
+    assert!(response.warnings.iter().any(|warning| {
+        warning.code == "runtime.model_selected" && warning.message.contains("'gpt-5'")
+    }));
+}
+
+#[tokio::test]
+async fn test_runtime_weighted_routing_policy_spreads_alias() {
+    let openai = Arc::new(MockAdapter::new(
+        ProviderId::Openai,
+        provider_capabilities(true, true, false),
+        response(
+            ProviderId::Openai,
+            "gpt-5-mini",
+            Usage::default(),
+            None,
+            Vec::new(),
+        ),
+        Vec::new(),
+    ));
+    let openrouter = Arc::new(MockAdapter::new(
+        ProviderId::Openrouter,
+        provider_capabilities(true, true, false),
+        response(
+            ProviderId::Openrouter,
+            "openai/gpt-5-mini",
+            Usage::default(),
+            None,
+            Vec::new(),
+        ),
+        Vec::new(),
+    ));
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(openai)
+        .with_adapter(openrouter)
+        .with_model_alias("mini", ProviderId::Openai, "gpt-5-mini")
+        .with_model_alias("mini", ProviderId::Openrouter, "openai/gpt-5-mini")
+        .with_routing_policy(
+            "mini",
+            RoutingPolicy::Weighted(BTreeMap::from([
+                (ProviderId::Openai, 3),
+                (ProviderId::Openrouter, 1),
+            ])),
+        )
+        .build();
+
+    let mut providers = Vec::new();
+    for _ in 0..4 {
+        let response = runtime
+            .run(request(None, "mini", Vec::new(), ResponseFormat::Text))
+            .await
+            .expect("routed run should succeed");
+        providers.push(response.provider);
+    }
+    assert_eq!(
+        providers,
+        vec![
+            ProviderId::Openai,
+            ProviderId::Openai,
+            ProviderId::Openai,
+            ProviderId::Openrouter,
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_runtime_fallback_skips_quarantined_provider() {
+    use crate::providers::mock::{MockAdapter as ScriptedAdapter, MockMatcher, MockOutcome};
+
+    let outage = || ProviderError::Transport {
+        provider: ProviderId::Openai,
+        request_id: None,
+        message: "connection refused".to_string(),
+        source: None,
+    };
+    let primary = Arc::new(
+        ScriptedAdapter::new(ProviderId::Openai)
+            .on(MockMatcher::any(), MockOutcome::text("from primary"))
+            .with_health_checks(vec![Err(outage()), Ok(())]),
+    );
+    let quarantined = Arc::new(
+        ScriptedAdapter::new(ProviderId::Anthropic)
+            .on(MockMatcher::any(), MockOutcome::text("from quarantined"))
+            .with_health_checks(vec![Err(outage())]),
+    );
+    let secondary = Arc::new(
+        ScriptedAdapter::new(ProviderId::Openrouter)
+            .on(MockMatcher::any(), MockOutcome::text("from fallback")),
+    );
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(primary.clone())
+        .with_adapter(quarantined.clone())
+        .with_adapter(secondary.clone())
+        .with_unhealthy_threshold(1)
+        .with_fallback_chain(
+            "gpt-5*",
+            vec![
+                ModelRef {
+                    provider_hint: Some(ProviderId::Anthropic),
+                    model_id: "claude-sonnet-4-5".to_string(),
+                    requirements: None,
+                },
+                ModelRef {
+                    provider_hint: Some(ProviderId::Openrouter),
+                    model_id: "openai/gpt-5-mini".to_string(),
+                    requirements: None,
+                },
+            ],
+        )
+        .build();
+    let primary_request = || {
+        request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        )
+    };
+
+    let health = runtime.check_provider_health().await;
+    assert_eq!(
+        health
+            .iter()
+            .map(|health| (health.provider.clone(), health.healthy))
+            .collect::<Vec<_>>(),
+        vec![
+            (ProviderId::Openai, false),
+            (ProviderId::Anthropic, false),
+            (ProviderId::Openrouter, true),
+        ]
+    );
+
+    let response = runtime
+        .run(primary_request())
+        .await
+        .expect("fallback should succeed");
+    assert_eq!(response.provider, ProviderId::Openrouter);
+    assert_eq!(response.warnings.len(), 1);
+    assert!(response.warnings[0].message.contains("provider unhealthy"));
+    assert!(primary.requests().is_empty());
+    assert!(quarantined.requests().is_empty());
+
+    runtime.check_provider_health().await;
+    assert!(runtime.provider_health()[0].healthy);
+    let response = runtime
+        .run(primary_request())
+        .await
+        .expect("recovered primary should answer");
+    assert_eq!(response.provider, ProviderId::Openai);
+    assert_eq!(primary.requests().len(), 1);
+}
+
+/// Answers with the API key it was called with, rate limiting `limited_key`.
+struct KeyEchoAdapter {
+    limited_key: &'static str,
+    rejected_token: &'static str,
+    keys_seen: Mutex<Vec<String>>,
+    tokens_seen: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl ProviderAdapter for KeyEchoAdapter {
+    fn id(&self) -> ProviderId {
+        ProviderId::Openai
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        provider_capabilities(true, true, false)
+    }
+
+    async fn run(
+        &self,
+        req: &ProviderRequest,
+        ctx: &AdapterContext,
+    ) -> Result<ProviderResponse, ProviderError> {
+        if let Some(token) = ctx.metadata.get(crate::registry::ACCESS_TOKEN_METADATA_KEY) {
+            self.tokens_seen
+                .lock()
+                .expect("tokens lock")
+                .push(token.clone());
+            if token == self.rejected_token {
+                return Err(ProviderError::CredentialsRejected {
+                    provider: ProviderId::Openai,
+                    request_id: None,
+                    message: "token revoked".to_string(),
+                });
+            }
+        }
+        let key = ctx
+            .metadata
+            .get(crate::registry::API_KEY_METADATA_KEY)
+            .cloned()
+            .unwrap_or_default();
+        self.keys_seen.lock().expect("keys lock").push(key.clone());
+        if key == self.limited_key {
+            return Err(ProviderError::RateLimited {
+                provider: ProviderId::Openai,
+                model: Some(req.model.model_id.clone()),
+                request_id: None,
+                retry_after: Some(Duration::from_secs(60)),
+                message: "slow down".to_string(),
+                rate_limit: None,
+            });
+        }
+        Ok(response(
+            ProviderId::Openai,
+            &req.model.model_id,
+            Usage::default(),
+            None,
+            Vec::new(),
+        ))
+    }
+
+    async fn discover_models(
+        &self,
+        _opts: &DiscoveryOptions,
+        _ctx: &AdapterContext,
+    ) -> Result<Vec<ModelInfo>, ProviderError> {
+        Ok(Vec::new())
+    }
+}
+
+#[tokio::test]
+async fn test_runtime_rotates_pooled_keys_and_sets_aside_rate_limited_ones() {
+    let adapter = Arc::new(KeyEchoAdapter {
+        limited_key: "key-b",
+        rejected_token: "",
+        keys_seen: Mutex::new(Vec::new()),
+        tokens_seen: Mutex::new(Vec::new()),
+    });
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_key_pool(
+            ProviderId::Openai,
+            KeyPool::new(["key-a", "key-b", "key-c"], KeyRotation::RoundRobin),
+        )
+        .build();
+    let run = || {
+        runtime.run(request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        ))
+    };
+
+    run().await.expect("first key should answer");
+    let error = run().await.expect_err("second key is rate limited");
+    assert!(matches!(error, RuntimeError::RateLimited { .. }));
+    for _ in 0..3 {
+        run().await.expect("remaining keys should answer");
+    }
+
+    assert_eq!(
+        *adapter.keys_seen.lock().expect("keys lock"),
+        ["key-a", "key-b", "key-c", "key-a", "key-c"]
+    );
+    assert_eq!(
+        runtime
+            .registry
+            .key_pool(&ProviderId::Openai)
+            .map(KeyPool::available_keys),
+        Some(2)
+    );
+}
+
+struct FixedTokens(Option<&'static str>);
+
+#[async_trait]
+impl TokenProvider for FixedTokens {
+    async fn get_token(&self, provider: ProviderId) -> Result<String, RuntimeError> {
+        self.0
+            .map(str::to_string)
+            .ok_or_else(|| RuntimeError::credential_missing(provider, Vec::new()))
+    }
+}
+
+#[tokio::test]
+async fn test_runtime_token_provider_authenticates_calls() {
+    let adapter = Arc::new(KeyEchoAdapter {
+        limited_key: "limited-key",
+        rejected_token: "",
+        keys_seen: Mutex::new(Vec::new()),
+        tokens_seen: Mutex::new(Vec::new()),
+    });
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_token_provider(ProviderId::Openai, Arc::new(FixedTokens(Some("sts-token"))))
+        .build();
+    runtime
+        .run(request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        ))
+        .await
+        .expect("token should authenticate");
+    assert_eq!(
+        *adapter.tokens_seen.lock().expect("tokens lock"),
+        ["sts-token"]
+    );
+
+    let failing = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_token_provider(ProviderId::Openai, Arc::new(FixedTokens(None)))
+        .build();
+    let error = failing
+        .run(request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        ))
+        .await
+        .expect_err("token failure should fail the call");
+    assert!(matches!(
+        error,
+        RuntimeError::CredentialMissing {
+            provider: ProviderId::Openai,
+            ..
+        }
+    ));
+    assert_eq!(adapter.tokens_seen.lock().expect("tokens lock").len(), 1);
+}
+
+/// Issues `token-1`, `token-2`, ... without an expiry.
+#[derive(Default)]
+struct CountingTokens(Mutex<u32>);
+
+#[async_trait]
+impl TokenProvider for CountingTokens {
+    async fn get_token(&self, _provider: ProviderId) -> Result<String, RuntimeError> {
+        let mut issued = self.0.lock().expect("issued lock");
+        *issued += 1;
+        Ok(format!("token-{issued}"))
+    }
+}
+
+#[tokio::test]
+async fn test_runtime_refreshes_rejected_token_and_retries_once() {
+    let adapter = Arc::new(KeyEchoAdapter {
+        limited_key: "limited-key",
+        rejected_token: "token-1",
+        keys_seen: Mutex::new(Vec::new()),
+        tokens_seen: Mutex::new(Vec::new()),
+    });
+    let tokens = Arc::new(CountingTokens::default());
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_token_provider(ProviderId::Openai, tokens.clone())
+        .build();
+    let request = || {
+        request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        )
+    };
+
+    runtime
+        .run(request())
+        .await
+        .expect("refreshed token should authenticate");
+    runtime
+        .run(request())
+        .await
+        .expect("refreshed token should stay cached");
+
+    assert_eq!(
+        *adapter.tokens_seen.lock().expect("tokens lock"),
+        ["token-1", "token-2", "token-2"]
+    );
+    assert_eq!(*tokens.0.lock().expect("issued lock"), 2);
+}
+
+#[tokio::test]
+async fn test_runtime_credential_store_supplies_keys_but_yields_to_pools() {
+    let adapter = Arc::new(KeyEchoAdapter {
+        limited_key: "limited-key",
+        rejected_token: "",
+        keys_seen: Mutex::new(Vec::new()),
+        tokens_seen: Mutex::new(Vec::new()),
+    });
+    let store = StaticCredentialStore::new().with_key(ProviderId::Openai, "stored-key");
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_credential_store(Arc::new(store))
+        .build();
+    runtime
+        .run(request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        ))
+        .await
+        .expect("stored key should answer");
+
+    let pooled = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_credential_store(Arc::new(
+            StaticCredentialStore::new().with_key(ProviderId::Openai, "stored-key"),
+        ))
+        .with_key_pool(
+            ProviderId::Openai,
+            KeyPool::new(["pooled-key"], KeyRotation::RoundRobin),
+        )
+        .build();
+    pooled
+        .run(request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        ))
+        .await
+        .expect("pooled key should answer");
+
+    assert_eq!(
+        *adapter.keys_seen.lock().expect("keys lock"),
+        ["stored-key", "pooled-key"]
+    );
+}
+
+#[tokio::test]
+async fn test_run_batch_returns_results_in_request_order() {
+    use crate::providers::mock::{MockAdapter as ScriptedAdapter, MockMatcher, MockOutcome};
+
+    let adapter = Arc::new(
+        ScriptedAdapter::new(ProviderId::Openai)
+            .on(
+                MockMatcher::any().model("gpt-5-nano"),
+                MockOutcome::error(ProviderError::Status {
+                    provider: ProviderId::Openai,
+                    model: Some("gpt-5-nano".to_string()),
+                    status_code: 400,
+                    request_id: None,
+                    message: "bad request".to_string(),
+                    rate_limit: None,
+                }),
+            )
+            .on(MockMatcher::any(), MockOutcome::text("ok")),
+    );
+    let runtime = ProviderRuntime::builder().with_adapter(adapter).build();
+    let requests = ["gpt-5-mini", "gpt-5-nano", "gpt-4.1"].map(|model| {
+        request(
+            Some(ProviderId::Openai),
+            model,
+            Vec::new(),
+            ResponseFormat::Text,
+        )
+    });
+
+    let results = runtime.run_batch(requests, 2).await;
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(
+        results[0]
+            .as_ref()
+            .map(|response| response.model.as_str())
+            .ok(),
+        Some("gpt-5-mini")
+    );
+    assert!(matches!(
+        results[1],
+        Err(RuntimeError::ProviderProtocolError {
+            status_code: Some(400),
+            ..
+        })
+    ));
+    assert_eq!(
+        results[2]
+            .as_ref()
+            .map(|response| response.model.as_str())
+            .ok(),
+        Some("gpt-4.1")
+    );
+}
+
+#[tokio::test]
+async fn test_run_with_context_overrides_builder_metadata_per_call() {
+    let adapter = Arc::new(KeyEchoAdapter {
+        limited_key: "limited-key",
+        rejected_token: "",
+        keys_seen: Mutex::new(Vec::new()),
+        tokens_seen: Mutex::new(Vec::new()),
+    });
+    let mut builder_context = AdapterContext::default();
+    builder_context.metadata.insert(
+        crate::registry::API_KEY_METADATA_KEY.to_string(),
+        "shared-key".to_string(),
+    );
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .with_adapter_context(builder_context)
+        .build();
+    let run_request = || {
+        request(
+            Some(ProviderId::Openai),
+            "gpt-5-mini",
+            Vec::new(),
+            ResponseFormat::Text,
+        )
+    };
+
+    let mut tenant_context = AdapterContext::default();
+    tenant_context.metadata.insert(
+        crate::registry::API_KEY_METADATA_KEY.to_string(),
+        "tenant-key".to_string(),
+    );
+    runtime
+        .run_with_context(run_request(), tenant_context)
+        .await
+        .expect("tenant key should answer");
+    runtime
+        .run(run_request())
+        .await
+        .expect("shared key should answer");
+    RequestContext::new()
+        .with_adapter_metadata(crate::registry::API_KEY_METADATA_KEY, "scoped-key")
+        .scope(runtime.run(run_request()))
+        .await
+        .expect("scoped key should answer");
+
+    assert_eq!(
+        *adapter.keys_seen.lock().expect("keys lock"),
+        ["tenant-key", "shared-key", "scoped-key"]
+    );
+}
+
+fn model_ref(provider_hint: Option<ProviderId>, model_id: &str) -> ModelRef {
+    ModelRef {
+        provider_hint,
+        model_id: model_id.to_string(),
+        requirements: None,
+    }
+}
+
+#[test]
+fn test_try_build_accepts_valid_configuration() {
+    use crate::providers::mock::MockAdapter as ScriptedAdapter;
+
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(ScriptedAdapter::new(ProviderId::Openai)))
+        .with_adapter(Arc::new(ScriptedAdapter::new(ProviderId::Anthropic)))
+        .with_default_provider(ProviderId::Openai)
+        .with_pricing_table(PricingTable::builtin())
+        .with_model_alias("fast", ProviderId::Anthropic, "claude-haiku-4-5")
+        .with_fallback_chain(
+            "gpt-5*",
+            vec![
+                model_ref(None, "fast"),
+                model_ref(Some(ProviderId::Anthropic), "claude-sonnet-4-5"),
+            ],
+        )
+        .with_hedging(
+            "claude-*",
+            model_ref(None, "gpt-5-mini"),
+            Duration::from_millis(500),
+        )
+        .try_build();
+
+    assert!(runtime.is_ok(), "{:?}", runtime.err());
+}
+
+#[test]
+fn test_try_build_reports_misconfiguration() {
+    use crate::providers::mock::MockAdapter as ScriptedAdapter;
+    let builder = || {
+        ProviderRuntime::builder().with_adapter(Arc::new(ScriptedAdapter::new(ProviderId::Openai)))
+    };
+    let error = |builder: super::ProviderRuntimeBuilder| {
+        builder
+            .try_build()
+            .map(|_| ())
+            .expect_err("configuration should be rejected")
+    };
+
+    assert_eq!(
+        error(builder().with_default_provider(ProviderId::Anthropic)),
+        ConfigError::DefaultProviderNotRegistered {
+            provider: ProviderId::Anthropic
+        }
+    );
+
+    let mistral = ProviderId::Other("mistral".to_string());
+    let mut catalog = crate::catalog::builtin_static_catalog();
+    catalog
+        .models
+        .push(model(mistral.clone(), "mistral-large", None, None, None));
+    assert_eq!(
+        error(builder().with_model_catalog(catalog)),
+        ConfigError::CatalogProviderNotRegistered {
+            provider: mistral.clone(),
+            model: "mistral-large".to_string(),
+        }
+    );
+
+    let pricing: PricingTable = r#"
+        [[rules]]
+        provider = "mistral"
+        pattern = "mistral-*"
+        input_per_million = 1.0
+        output_per_million = 2.0
+    "#
+    .parse()
+    .expect("pricing TOML should parse");
+    assert_eq!(
+        error(builder().with_pricing_table(pricing)),
+        ConfigError::PricingProviderNotRegistered {
+            provider: mistral,
+            pattern: "mistral-*".to_string(),
+        }
+    );
+
+    let unregistered_fallback = error(builder().with_fallback_chain(
+        "gpt-5*",
+        vec![model_ref(Some(ProviderId::Anthropic), "claude-sonnet-4-5")],
+    ));
+    assert!(matches!(
+        unregistered_fallback,
+        ConfigError::UnresolvableModelRoute {
+            ref rule,
+            error: RoutingError::ProviderNotRegistered { .. },
+            ..
+        } if rule == "fallback chain for 'gpt-5*'"
+    ));
+
+    let unknown_hedge = error(builder().with_hedging(
+        "gpt-5*",
+        model_ref(None, "no-such-model"),
+        Duration::from_millis(500),
+    ));
+    assert!(matches!(
+        unknown_hedge,
+        ConfigError::UnresolvableModelRoute {
+            error: RoutingError::ModelNotFound { .. },
+            ..
+        }
+    ));
+}
@@ -0,0 +1,145 @@
+//! Input token counting for checking a request against a model's context window before sending.
+//!
+//! `ProviderRuntime::estimate_tokens` prefers the provider's own count when the adapter offers
+//! one (Anthropic's `count_tokens` endpoint) and otherwise falls back to the runtime's
+//! `TokenCounter`.
+
+use crate::core::types::{
+    ContentPart, Message, ProviderRequest, ResponseFormat, ToolResultContent,
+};
+
+/// Fixed per-message framing (role and separators) added on top of message content.
+const MESSAGE_OVERHEAD_TOKENS: u64 = 3;
+/// Tokens that prime the assistant reply at the end of every prompt.
+const REPLY_PRIMING_TOKENS: u64 = 3;
+/// Average characters per token for English text under current BPE vocabularies.
+const HEURISTIC_CHARS_PER_TOKEN: u64 = 4;
+
+/// Counts the input tokens a request consumes.
+pub trait TokenCounter: Send + Sync {
+    /// Counts `text` as it would be tokenized for `model_id`.
+    fn count_text(&self, model_id: &str, text: &str) -> u64;
+
+    /// Counts every message, tool definition, and response schema in `request`, plus framing
+    /// overhead.
+    fn count_request(&self, request: &ProviderRequest) -> u64 {
+        count_request_with(request, |text| {
+            self.count_text(&request.model.model_id, text)
+        })
+    }
+}
+
+/// Provider-independent estimate of one token per four characters, rounded up per text segment.
+///
+/// This is the runtime default. It is only an approximation; use `TiktokenCounter` (feature
+/// `tiktoken`) or the provider count when close to the limit.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicTokenCounter;
+
+impl TokenCounter for HeuristicTokenCounter {
+    fn count_text(&self, _model_id: &str, text: &str) -> u64 {
+        (text.chars().count() as u64).div_ceil(HEURISTIC_CHARS_PER_TOKEN)
+    }
+}
+
+/// Counts with the OpenAI BPE vocabulary matching the model id, falling back to `o200k_base`
+/// for models tiktoken does not know (including non-OpenAI models, where it is an estimate).
+#[cfg(feature = "tiktoken")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TiktokenCounter;
+
+#[cfg(feature = "tiktoken")]
+impl TokenCounter for TiktokenCounter {
+    fn count_text(&self, model_id: &str, text: &str) -> u64 {
+        let model_id = model_id.rsplit('/').next().unwrap_or(model_id);
+        let bpe = tiktoken_rs::bpe_for_model(model_id)
+            .unwrap_or_else(|_| tiktoken_rs::o200k_base_singleton());
+        bpe.encode_with_special_tokens(text).len() as u64
+    }
+}
+
+/// Where a `TokenEstimate` count came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenCountSource {
+    /// Counted by the provider for the exact request.
+    Provider,
+    /// Estimated locally by the runtime's `TokenCounter`.
+    Counter,
+}
+
+/// Input token count for a request, alongside the limits of the model it resolves to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenEstimate {
+    pub input_tokens: u64,
+    pub source: TokenCountSource,
+    /// Context window of the resolved model, when the catalog knows it.
+    pub context_window: Option<u32>,
+    /// Output tokens the request reserves (`max_output_tokens`), if set.
+    pub reserved_output_tokens: Option<u32>,
+}
+
+impl TokenEstimate {
+    /// Tokens left in the context window after the input and the reserved output, negative when
+    /// the request overflows. `None` when the context window is unknown.
+    pub fn remaining_tokens(&self) -> Option<i64> {
+        let context_window = i64::from(self.context_window?);
+        let reserved = i64::from(self.reserved_output_tokens.unwrap_or(0));
+        Some(context_window - self.input_tokens as i64 - reserved)
+    }
+
+    /// Whether the request fits its model's context window. Requests for models with an unknown
+    /// window are assumed to fit.
+    pub fn fits(&self) -> bool {
+        self.remaining_tokens()
+            .is_none_or(|remaining| remaining >= 0)
+    }
+}
+
+/// Tokens `message` adds to a request, including its framing overhead.
+pub(crate) fn count_message(counter: &dyn TokenCounter, model_id: &str, message: &Message) -> u64 {
+    MESSAGE_OVERHEAD_TOKENS
+        + count_parts(&message.content, &|text| counter.count_text(model_id, text))
+}
+
+fn count_request_with(request: &ProviderRequest, count_text: impl Fn(&str) -> u64) -> u64 {
+    let mut total = REPLY_PRIMING_TOKENS;
+    for message in &request.messages {
+        total += MESSAGE_OVERHEAD_TOKENS;
+        total += count_parts(&message.content, &count_text);
+    }
+    for tool in &request.tools {
+        total += count_text(&tool.name);
+        if let Some(description) = &tool.description {
+            total += count_text(description);
+        }
+        total += count_text(&tool.parameters_schema.to_string());
+    }
+    if let ResponseFormat::JsonSchema { name, schema } = &request.response_format {
+        total += count_text(name) + count_text(&schema.to_string());
+    }
+    total
+}
+
+fn count_parts(parts: &[ContentPart], count_text: &impl Fn(&str) -> u64) -> u64 {
+    parts
+        .iter()
+        .map(|part| match part {
+            ContentPart::Text { text } | ContentPart::Thinking { text, .. } => count_text(text),
+            ContentPart::ToolCall { tool_call } => {
+                count_text(&tool_call.name) + count_text(&tool_call.arguments_json.to_string())
+            }
+            ContentPart::HostedToolCall { hosted_tool_call } => {
+                count_text(&hosted_tool_call.raw_provider_content.to_string())
+            }
+            ContentPart::Other { raw, .. } => count_text(&raw.to_string()),
+            ContentPart::ToolResult { tool_result } => match &tool_result.content {
+                ToolResultContent::Text { text } => count_text(text),
+                ToolResultContent::Json { value } => count_text(&value.to_string()),
+                ToolResultContent::Parts { parts } => count_parts(parts, count_text),
+            },
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests;
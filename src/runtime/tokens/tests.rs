@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use super::{HeuristicTokenCounter, TokenCountSource, TokenCounter, TokenEstimate};
+use crate::core::types::{
+    ContentPart, Message, MessageRole, ModelCatalog, ModelInfo, ModelRef, ProviderId,
+    ProviderRequest, ToolDefinition,
+};
+use crate::providers::mock::MockAdapter;
+use crate::runtime::ProviderRuntime;
+
+fn request(prompt: &str, max_output_tokens: Option<u32>) -> ProviderRequest {
+    ProviderRequest {
+        max_output_tokens,
+        ..ProviderRequest::new(
+            ModelRef {
+                provider_hint: Some(ProviderId::Openai),
+                model_id: "gpt-5-mini".to_string(),
+                requirements: None,
+            },
+            vec![Message {
+                role: MessageRole::User,
+                content: vec![ContentPart::Text {
+                    text: prompt.to_string(),
+                }],
+                cache_hint: None,
+            }],
+        )
+    }
+}
+
+fn runtime(context_window: Option<u32>) -> ProviderRuntime {
+    ProviderRuntime::builder()
+        .with_adapter(Arc::new(MockAdapter::new(ProviderId::Openai)))
+        .with_model_catalog(ModelCatalog {
+            models: vec![ModelInfo {
+                provider: ProviderId::Openai,
+                model_id: "gpt-5-mini".to_string(),
+                display_name: None,
+                context_window,
+                max_output_tokens: None,
+                supports_tools: true,
+                supports_structured_output: true,
+                pricing: None,
+                input_modalities: Vec::new(),
+                output_modalities: Vec::new(),
+                deprecated: false,
+                knowledge_cutoff: None,
+            }],
+        })
+        .build()
+}
+
+#[test]
+fn test_heuristic_counter_rounds_up_per_segment() {
+    let counter = HeuristicTokenCounter;
+
+    assert_eq!(counter.count_text("any", ""), 0);
+    assert_eq!(counter.count_text("any", "abcd"), 1);
+    assert_eq!(counter.count_text("any", "abcde"), 2);
+}
+
+#[test]
+fn test_count_request_includes_framing_and_tools() {
+    let counter = HeuristicTokenCounter;
+    let plain = request("abcdefgh", None);
+    // 3 reply priming + 3 message framing + 2 text tokens.
+    assert_eq!(counter.count_request(&plain), 8);
+
+    let mut with_tool = plain.clone();
+    with_tool.tools.push(ToolDefinition {
+        name: "lookup".to_string(),
+        description: None,
+        parameters_schema: json!({}),
+        cache_hint: None,
+    });
+    assert!(counter.count_request(&with_tool) > counter.count_request(&plain));
+}
+
+#[test]
+fn test_estimate_remaining_tokens_accounts_for_reserved_output() {
+    let estimate = TokenEstimate {
+        input_tokens: 900,
+        source: TokenCountSource::Counter,
+        context_window: Some(1_000),
+        reserved_output_tokens: Some(200),
+    };
+
+    assert_eq!(estimate.remaining_tokens(), Some(-100));
+    assert!(!estimate.fits());
+
+    let unknown_window = TokenEstimate {
+        context_window: None,
+        ..estimate
+    };
+    assert_eq!(unknown_window.remaining_tokens(), None);
+    assert!(unknown_window.fits());
+}
+
+#[tokio::test]
+async fn test_runtime_estimate_tokens_falls_back_to_counter_with_catalog_window() {
+    let runtime = runtime(Some(16));
+
+    let estimate = runtime
+        .estimate_tokens(&request("abcdefgh", Some(4)))
+        .await
+        .expect("estimate should succeed");
+
+    assert_eq!(estimate.source, TokenCountSource::Counter);
+    assert_eq!(estimate.input_tokens, 8);
+    assert_eq!(estimate.context_window, Some(16));
+    assert_eq!(estimate.remaining_tokens(), Some(4));
+    assert!(estimate.fits());
+}
+
+#[tokio::test]
+async fn test_runtime_estimate_tokens_uses_configured_counter() {
+    struct FixedCounter;
+
+    impl TokenCounter for FixedCounter {
+        fn count_text(&self, _model_id: &str, _text: &str) -> u64 {
+            0
+        }
+
+        fn count_request(&self, _request: &ProviderRequest) -> u64 {
+            50
+        }
+    }
+
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(MockAdapter::new(ProviderId::Openai)))
+        .with_token_counter(Arc::new(FixedCounter))
+        .build();
+
+    let estimate = runtime
+        .estimate_tokens(&request("hello", None))
+        .await
+        .expect("estimate should succeed");
+
+    assert_eq!(estimate.input_tokens, 50);
+}
+
+#[cfg(feature = "tiktoken")]
+#[test]
+fn test_tiktoken_counter_counts_bpe_tokens() {
+    let counter = super::TiktokenCounter;
+
+    assert_eq!(counter.count_text("gpt-4o", "hello world"), 2);
+    assert_eq!(counter.count_text("openai/gpt-4o", "hello world"), 2);
+}
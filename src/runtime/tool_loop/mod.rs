@@ -0,0 +1,156 @@
+//! Tool-calling loop orchestration for `ProviderRuntime::run_with_tools`.
+//!
+//! The loop sends the request, executes any returned tool calls through registered
+//! `ToolExecutor`s, appends the assistant turn and the tool results to the conversation, and
+//! repeats until the model stops calling tools or the iteration limit is reached.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures_util::future::join_all;
+
+#[cfg(feature = "schemars")]
+mod typed;
+
+#[cfg(feature = "schemars")]
+pub use typed::TypedTool;
+#[cfg(feature = "schemars")]
+pub(crate) use typed::json_schema_for;
+
+use crate::core::types::{
+    ContentPart, FinishReason, Message, MessageRole, ProviderResponse, ToolCall, ToolDefinition,
+    ToolResult, ToolResultContent,
+};
+
+/// Executes calls to one tool.
+#[async_trait]
+pub trait ToolExecutor: Send + Sync {
+    /// The definition advertised to the model. Its `name` is the name calls are routed by.
+    fn definition(&self) -> ToolDefinition;
+
+    /// Runs the call. An `Err` is reported back to the model as an error tool result rather
+    /// than aborting the loop, so the model can correct its arguments.
+    async fn execute(&self, call: &ToolCall) -> Result<ToolResultContent, String>;
+}
+
+/// Executors available to a tool loop, keyed by tool name.
+#[derive(Clone, Default)]
+pub struct ToolSet {
+    executors: BTreeMap<String, Arc<dyn ToolExecutor>>,
+}
+
+impl ToolSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `executor` under its definition's name, replacing an earlier executor with
+    /// the same name.
+    pub fn with_tool(mut self, executor: Arc<dyn ToolExecutor>) -> Self {
+        self.executors.insert(executor.definition().name, executor);
+        self
+    }
+
+    pub fn definitions(&self) -> Vec<ToolDefinition> {
+        self.executors
+            .values()
+            .map(|executor| executor.definition())
+            .collect()
+    }
+
+    /// Executes `calls` concurrently, returning results in call order.
+    pub(crate) async fn execute_all(&self, calls: &[ToolCall]) -> Vec<ToolResult> {
+        join_all(calls.iter().map(|call| async move {
+            let outcome = match self.executors.get(&call.name) {
+                Some(executor) => executor.execute(call).await,
+                None => Err(format!("unknown tool '{}'", call.name)),
+            };
+            let is_error = outcome.is_err();
+            let content = outcome.unwrap_or_else(|message| ToolResultContent::Text {
+                text: format!("error: {message}"),
+            });
+            ToolResult {
+                tool_call_id: call.id.clone(),
+                content,
+                raw_provider_content: None,
+                is_error,
+            }
+        }))
+        .await
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolLoopOptions {
+    /// Maximum provider calls, including the final answer. Defaults to 8.
+    pub max_iterations: u32,
+}
+
+impl Default for ToolLoopOptions {
+    fn default() -> Self {
+        Self { max_iterations: 8 }
+    }
+}
+
+/// Why a tool loop returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolLoopStop {
+    /// The model answered without requesting tools, or finished for another terminal reason.
+    Completed,
+    /// `max_iterations` was reached while the model was still requesting tools; the final
+    /// response's tool calls were not executed.
+    MaxIterations,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolLoopOutcome {
+    /// The last provider response.
+    pub response: ProviderResponse,
+    /// The full conversation: the request messages followed by every assistant turn and tool
+    /// result message, ending with the final assistant turn.
+    pub messages: Vec<Message>,
+    /// Provider calls made.
+    pub iterations: u32,
+    pub stop: ToolLoopStop,
+}
+
+/// Tool calls the loop should execute, or `None` when the response is terminal.
+pub(crate) fn pending_tool_calls(response: &ProviderResponse) -> Option<Vec<ToolCall>> {
+    if response.finish_reason != FinishReason::ToolCalls {
+        return None;
+    }
+
+    let calls = response
+        .output
+        .content
+        .iter()
+        .filter_map(|part| match part {
+            ContentPart::ToolCall { tool_call } => Some(tool_call.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    (!calls.is_empty()).then_some(calls)
+}
+
+pub(crate) fn assistant_message(response: &ProviderResponse) -> Message {
+    Message {
+        role: MessageRole::Assistant,
+        content: response.output.content.clone(),
+        cache_hint: None,
+    }
+}
+
+pub(crate) fn tool_results_message(results: Vec<ToolResult>) -> Message {
+    Message {
+        role: MessageRole::Tool,
+        content: results
+            .into_iter()
+            .map(|tool_result| ContentPart::ToolResult { tool_result })
+            .collect(),
+        cache_hint: None,
+    }
+}
+
+#[cfg(test)]
+mod tests;
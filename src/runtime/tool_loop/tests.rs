@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::json;
+
+use super::{ToolExecutor, ToolLoopOptions, ToolLoopStop, ToolSet};
+use crate::core::types::{
+    ContentPart, Message, MessageRole, ModelRef, ProviderId, ProviderRequest, ToolCall,
+    ToolDefinition, ToolResultContent,
+};
+use crate::providers::mock::{MockAdapter, MockMatcher, MockOutcome};
+use crate::runtime::ProviderRuntime;
+
+struct AddTool;
+
+#[async_trait]
+impl ToolExecutor for AddTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "add".to_string(),
+            description: Some("Adds two integers".to_string()),
+            parameters_schema: json!({
+                "type": "object",
+                "properties": { "a": { "type": "integer" }, "b": { "type": "integer" } },
+                "required": ["a", "b"]
+            }),
+            cache_hint: None,
+        }
+    }
+
+    async fn execute(&self, call: &ToolCall) -> Result<ToolResultContent, String> {
+        let arg = |name: &str| {
+            call.arguments_json[name]
+                .as_i64()
+                .ok_or_else(|| format!("missing integer '{name}'"))
+        };
+        Ok(ToolResultContent::Text {
+            text: (arg("a")? + arg("b")?).to_string(),
+        })
+    }
+}
+
+fn request() -> ProviderRequest {
+    ProviderRequest::new(
+        ModelRef {
+            provider_hint: Some(ProviderId::Openai),
+            model_id: "gpt-5-mini".to_string(),
+            requirements: None,
+        },
+        vec![Message {
+            role: MessageRole::User,
+            content: vec![ContentPart::Text {
+                text: "what is 2 + 3?".to_string(),
+            }],
+            cache_hint: None,
+        }],
+    )
+}
+
+fn tool_results(message: &Message) -> Vec<String> {
+    message
+        .content
+        .iter()
+        .filter_map(|part| match part {
+            ContentPart::ToolResult { tool_result } => match &tool_result.content {
+                ToolResultContent::Text { text } => Some(text.clone()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_run_with_tools_executes_calls_until_final_answer() {
+    let adapter = Arc::new(MockAdapter::new(ProviderId::Openai).on_sequence(
+        MockMatcher::any(),
+        vec![
+            MockOutcome::tool_call("call-1", "add", json!({ "a": 2, "b": 3 })),
+            MockOutcome::text("2 + 3 = 5"),
+        ],
+    ));
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .build();
+    let tools = ToolSet::new().with_tool(Arc::new(AddTool));
+
+    let outcome = runtime
+        .run_with_tools(request(), &tools, ToolLoopOptions::default())
+        .await
+        .expect("loop should finish");
+
+    assert_eq!(outcome.stop, ToolLoopStop::Completed);
+    assert_eq!(outcome.iterations, 2);
+    assert_eq!(
+        outcome
+            .messages
+            .iter()
+            .map(|message| message.role.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            MessageRole::User,
+            MessageRole::Assistant,
+            MessageRole::Tool,
+            MessageRole::Assistant,
+        ]
+    );
+    assert_eq!(tool_results(&outcome.messages[2]), vec!["5"]);
+
+    let sent = adapter.requests();
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[0].tools.len(), 1);
+    assert_eq!(sent[1].messages.len(), 3);
+}
+
+#[tokio::test]
+async fn test_run_with_tools_reports_tool_errors_and_stops_at_max_iterations() {
+    let adapter = Arc::new(MockAdapter::new(ProviderId::Openai).on(
+        MockMatcher::any(),
+        MockOutcome::ToolCalls(vec![
+            ToolCall {
+                id: "call-1".to_string(),
+                name: "add".to_string(),
+                arguments_json: json!({ "a": 2 }),
+            },
+            ToolCall {
+                id: "call-2".to_string(),
+                name: "search".to_string(),
+                arguments_json: json!({}),
+            },
+        ]),
+    ));
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter.clone())
+        .build();
+    let tools = ToolSet::new().with_tool(Arc::new(AddTool));
+
+    let outcome = runtime
+        .run_with_tools(request(), &tools, ToolLoopOptions { max_iterations: 2 })
+        .await
+        .expect("loop should stop");
+
+    assert_eq!(outcome.stop, ToolLoopStop::MaxIterations);
+    assert_eq!(outcome.iterations, 2);
+    assert_eq!(adapter.requests().len(), 2);
+    assert_eq!(
+        tool_results(&outcome.messages[2]),
+        vec![
+            "error: missing integer 'b'".to_string(),
+            "error: unknown tool 'search'".to_string(),
+        ]
+    );
+    assert!(outcome.messages[2].content.iter().all(|part| matches!(
+        part,
+        ContentPart::ToolResult { tool_result } if tool_result.is_error
+    )));
+}
+
+#[cfg(feature = "schemars")]
+#[tokio::test]
+async fn test_typed_tool_generates_schema_and_decodes_arguments() {
+    use serde::{Deserialize, Serialize};
+
+    use super::TypedTool;
+
+    #[derive(Deserialize, schemars::JsonSchema)]
+    struct WeatherQuery {
+        /// City name.
+        city: String,
+        days: Option<u8>,
+    }
+
+    #[derive(Serialize)]
+    struct Forecast {
+        city: String,
+        days: u8,
+    }
+
+    let tool = TypedTool::new("forecast", |query: WeatherQuery| async move {
+        Ok(Forecast {
+            city: query.city,
+            days: query.days.unwrap_or(1),
+        })
+    })
+    .with_description("Looks up a forecast");
+
+    let definition = tool.definition();
+    assert_eq!(definition.name, "forecast");
+    assert_eq!(definition.parameters_schema["type"], "object");
+    assert_eq!(
+        definition.parameters_schema["required"],
+        json!(["city"]),
+        "{}",
+        definition.parameters_schema
+    );
+    assert!(definition.parameters_schema.get("$schema").is_none());
+
+    let call = |arguments_json| ToolCall {
+        id: "call-1".to_string(),
+        name: "forecast".to_string(),
+        arguments_json,
+    };
+    assert_eq!(
+        tool.execute(&call(json!({ "city": "Oslo", "days": 3 })))
+            .await,
+        Ok(ToolResultContent::Json {
+            value: json!({ "city": "Oslo", "days": 3 })
+        })
+    );
+    let error = tool
+        .execute(&call(json!({ "days": 3 })))
+        .await
+        .expect_err("missing city should fail");
+    assert!(error.starts_with("invalid arguments for 'forecast'"));
+}
@@ -0,0 +1,87 @@
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures_util::future::BoxFuture;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::ToolExecutor;
+use crate::core::types::{ToolCall, ToolDefinition, ToolResultContent};
+
+type Handler<I, O> = Arc<dyn Fn(I) -> BoxFuture<'static, Result<O, String>> + Send + Sync>;
+
+/// A `ToolExecutor` built from an async function over typed arguments.
+///
+/// The parameters schema is generated from `I`, and call arguments are deserialized into `I`
+/// before the handler runs; arguments that do not fit are reported back to the model as an
+/// error result. String outputs become text results, anything else a JSON result.
+pub struct TypedTool<I, O> {
+    name: String,
+    description: Option<String>,
+    handler: Handler<I, O>,
+    _types: PhantomData<fn(I) -> O>,
+}
+
+impl<I, O> TypedTool<I, O>
+where
+    I: JsonSchema + DeserializeOwned + Send + 'static,
+    O: Serialize + Send + 'static,
+{
+    pub fn new<F, Fut>(name: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(I) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<O, String>> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            description: None,
+            handler: Arc::new(move |input| Box::pin(handler(input))),
+            _types: PhantomData,
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+#[async_trait]
+impl<I, O> ToolExecutor for TypedTool<I, O>
+where
+    I: JsonSchema + DeserializeOwned + Send + 'static,
+    O: Serialize + Send + 'static,
+{
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            parameters_schema: json_schema_for::<I>(),
+            cache_hint: None,
+        }
+    }
+
+    async fn execute(&self, call: &ToolCall) -> Result<ToolResultContent, String> {
+        let input = serde_json::from_value::<I>(call.arguments_json.clone())
+            .map_err(|error| format!("invalid arguments for '{}': {error}", self.name))?;
+        let output = (self.handler)(input).await?;
+
+        match serde_json::to_value(output)
+            .map_err(|error| format!("failed to serialize '{}' output: {error}", self.name))?
+        {
+            Value::String(text) => Ok(ToolResultContent::Text { text }),
+            value => Ok(ToolResultContent::Json { value }),
+        }
+    }
+}
+
+/// The JSON Schema for `T`, without the `$schema` dialect marker providers reject.
+pub(crate) fn json_schema_for<T: JsonSchema>() -> Value {
+    let mut schema = schemars::schema_for!(T);
+    schema.remove("$schema");
+    schema.to_value()
+}
@@ -0,0 +1,211 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::error::RuntimeError;
+use crate::core::types::{ModelRef, ProviderId, ProviderResponse, RuntimeWarning};
+
+tokio::task_local! {
+    static ACTIVE_TRACE: TraceRecorder;
+}
+
+/// Full record of a single `ProviderRuntime::run` call, captured when trace capture is enabled.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RunTrace {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    pub requested_model: ModelRef,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<ProviderId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoded_request: Option<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<TraceAttempt>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_response: Option<Value>,
+    /// Provider debug payload echoed back with the response, such as OpenRouter's
+    /// `debug.echo_upstream_body`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_debug: Option<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<RuntimeWarning>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<ProviderResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+/// One HTTP attempt made by the transport, including retried attempts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TraceAttempt {
+    pub attempt: u32,
+    pub method: String,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    pub elapsed_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RunTrace {
+    pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TraceRecorder {
+    trace: Arc<Mutex<RunTrace>>,
+    started_at: Instant,
+}
+
+impl TraceRecorder {
+    pub(crate) fn new(requested_model: &ModelRef) -> Self {
+        Self {
+            trace: Arc::new(Mutex::new(RunTrace {
+                request_id: None,
+                requested_model: requested_model.clone(),
+                provider: None,
+                encoded_request: None,
+                attempts: Vec::new(),
+                raw_response: None,
+                provider_debug: None,
+                warnings: Vec::new(),
+                response: None,
+                error: None,
+                elapsed_ms: 0,
+            })),
+            started_at: Instant::now(),
+        }
+    }
+
+    pub(crate) fn finish(self, result: &Result<ProviderResponse, RuntimeError>) -> RunTrace {
+        let mut trace = self
+            .trace
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+
+        trace.elapsed_ms = elapsed_ms(self.started_at);
+        trace.request_id = trace
+            .attempts
+            .iter()
+            .rev()
+            .find_map(|attempt| attempt.request_id.clone());
+
+        match result {
+            Ok(response) => {
+                trace.warnings = response.warnings.clone();
+                trace.response = Some(response.clone());
+            }
+            Err(error) => trace.error = Some(error.to_string()),
+        }
+
+        trace
+    }
+
+    fn update(&self, f: impl FnOnce(&mut RunTrace)) {
+        f(&mut self
+            .trace
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()));
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TraceStore {
+    capacity: usize,
+    traces: Mutex<VecDeque<RunTrace>>,
+}
+
+impl TraceStore {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            traces: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub(crate) fn push(&self, trace: RunTrace) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut traces = self
+            .traces
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        while traces.len() >= self.capacity {
+            traces.pop_front();
+        }
+        traces.push_back(trace);
+    }
+
+    pub(crate) fn find(&self, request_id: &str) -> Option<RunTrace> {
+        self.traces
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .rev()
+            .find(|trace| trace.request_id.as_deref() == Some(request_id))
+            .cloned()
+    }
+
+    pub(crate) fn recent(&self) -> Vec<RunTrace> {
+        self.traces
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+}
+
+pub(crate) async fn scope<F: Future>(recorder: TraceRecorder, future: F) -> F::Output {
+    ACTIVE_TRACE.scope(recorder, future).await
+}
+
+pub(crate) fn record_provider(provider: &ProviderId) {
+    with_active(|trace| trace.provider = Some(provider.clone()));
+}
+
+pub(crate) fn record_encoded_request(body: &Value) {
+    with_active(|trace| trace.encoded_request = Some(body.clone()));
+}
+
+pub(crate) fn record_raw_response(body: &Value) {
+    with_active(|trace| trace.raw_response = Some(body.clone()));
+}
+
+pub(crate) fn record_provider_debug(debug: &Value) {
+    with_active(|trace| trace.provider_debug = Some(debug.clone()));
+}
+
+pub(crate) fn record_attempt(attempt: TraceAttempt) {
+    with_active(|trace| trace.attempts.push(attempt));
+}
+
+pub(crate) fn is_active() -> bool {
+    ACTIVE_TRACE.try_with(|_| ()).is_ok()
+}
+
+pub(crate) fn elapsed_ms(started_at: Instant) -> u64 {
+    u64::try_from(started_at.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+fn with_active(f: impl FnOnce(&mut RunTrace)) {
+    let _ = ACTIVE_TRACE.try_with(|recorder| recorder.update(f));
+}
+
+#[cfg(test)]
+mod tests;
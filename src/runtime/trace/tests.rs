@@ -0,0 +1,100 @@
+use serde_json::json;
+
+use super::{RunTrace, TraceAttempt, TraceRecorder, TraceStore, record_attempt, record_provider};
+use crate::core::error::RuntimeError;
+use crate::core::types::{ModelRef, ProviderId};
+
+fn model_ref() -> ModelRef {
+    ModelRef {
+        provider_hint: Some(ProviderId::Openai),
+        model_id: "gpt-5-mini".to_string(),
+        requirements: None,
+    }
+}
+
+fn attempt(attempt: u32, request_id: Option<&str>) -> TraceAttempt {
+    TraceAttempt {
+        attempt,
+        method: "POST".to_string(),
+        url: "http://localhost/v1/responses".to_string(),
+        status_code: Some(200),
+        request_id: request_id.map(str::to_string),
+        elapsed_ms: 1,
+        error: None,
+    }
+}
+
+fn trace_with_request_id(request_id: &str) -> RunTrace {
+    let recorder = TraceRecorder::new(&model_ref());
+    recorder.update(|trace| trace.attempts.push(attempt(1, Some(request_id))));
+    recorder.finish(&Err(RuntimeError::CostCalculationError {
+        provider: None,
+        model: None,
+        message: "unused".to_string(),
+    }))
+}
+
+#[tokio::test]
+async fn test_trace_hooks_record_only_inside_scope() {
+    record_provider(&ProviderId::Anthropic);
+
+    let recorder = TraceRecorder::new(&model_ref());
+    super::scope(recorder.clone(), async {
+        record_provider(&ProviderId::Openai);
+        record_attempt(attempt(1, None));
+        record_attempt(attempt(2, Some("req_2")));
+    })
+    .await;
+
+    let trace = recorder.finish(&Err(RuntimeError::CostCalculationError {
+        provider: None,
+        model: None,
+        message: "boom".to_string(),
+    }));
+    assert_eq!(trace.provider, Some(ProviderId::Openai));
+    assert_eq!(trace.attempts.len(), 2);
+    assert_eq!(trace.request_id.as_deref(), Some("req_2"));
+    assert!(
+        trace
+            .error
+            .as_deref()
+            .is_some_and(|error| error.contains("boom"))
+    );
+    assert!(trace.response.is_none());
+}
+
+#[test]
+fn test_trace_store_is_bounded_and_finds_latest_by_request_id() {
+    let store = TraceStore::new(2);
+    store.push(trace_with_request_id("req_1"));
+    store.push(trace_with_request_id("req_2"));
+    store.push(trace_with_request_id("req_3"));
+
+    assert!(store.find("req_1").is_none());
+    assert!(store.find("req_3").is_some());
+    assert_eq!(
+        store
+            .recent()
+            .iter()
+            .map(|trace| trace.request_id.clone().unwrap_or_default())
+            .collect::<Vec<_>>(),
+        vec!["req_2".to_string(), "req_3".to_string()]
+    );
+
+    let disabled = TraceStore::new(0);
+    disabled.push(trace_with_request_id("req_1"));
+    assert!(disabled.recent().is_empty());
+}
+
+#[test]
+fn test_run_trace_serde_round_trip() {
+    let mut trace = trace_with_request_id("req_1");
+    trace.encoded_request = Some(json!({"model": "gpt-5-mini"}));
+
+    let encoded = serde_json::to_value(&trace).expect("serialize trace");
+    assert_eq!(encoded.get("request_id"), Some(&json!("req_1")));
+    assert!(encoded.get("response").is_none());
+
+    let decoded: RunTrace = serde_json::from_value(encoded).expect("deserialize trace");
+    assert_eq!(decoded, trace);
+}
@@ -0,0 +1,184 @@
+//! Post-processing applied to each decoded response, before the runtime repairs structured
+//! output, prices, and validates it.
+//!
+//! Transforms registered with `ProviderRuntimeBuilder::with_response_transform` run in
+//! registration order on every adapter response, including fallback, hedged, and repair
+//! calls. The built-in transforms cover the usual cleanups; implement `ResponseTransform` for
+//! anything else.
+
+use serde_json::Value;
+
+use crate::core::types::{
+    ContentPart, FinishReason, ProviderRequest, ProviderResponse, ResponseFormat, WarningCode,
+};
+use crate::providers::json_extract;
+
+/// Rewrites a decoded response in place.
+pub trait ResponseTransform: Send + Sync {
+    fn transform(&self, request: &ProviderRequest, response: &mut ProviderResponse);
+}
+
+/// Unwraps text parts that are a single Markdown code fence, such as a ```` ```json ```` block
+/// around a JSON answer. When the request asked for JSON and the unwrapped text parses, the
+/// parsed value becomes `structured_output` and the `structured_output_parse_failed` warning
+/// is dropped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StripJsonFences;
+
+/// Recovers `structured_output` for JSON responses whose text wraps the object in prose or
+/// code fences, taking the first embedded object that parses, and drops the
+/// `structured_output_parse_failed` warning. Applies to `json_object` requests; see
+/// `with_json_schema`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExtractJsonObject {
+    include_json_schema: bool,
+}
+
+/// Trims leading and trailing whitespace from text parts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TrimWhitespace;
+
+/// Keeps only the first of warnings with the same code and message.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DedupeWarnings;
+
+/// Reports `ToolCalls` exactly when the output contains tool calls, correcting providers that
+/// answer tool calls with `Stop` or send `ToolCalls` without any.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NormalizeFinishReason;
+
+impl ResponseTransform for StripJsonFences {
+    fn transform(&self, request: &ProviderRequest, response: &mut ProviderResponse) {
+        let mut unwrapped = false;
+        for part in &mut response.output.content {
+            if let ContentPart::Text { text } = part
+                && let Some(inner) = strip_fence(text)
+            {
+                *text = inner.to_string();
+                unwrapped = true;
+            }
+        }
+
+        if !unwrapped
+            || matches!(request.response_format, ResponseFormat::Text)
+            || response.output.structured_output.is_some()
+        {
+            return;
+        }
+        let text = response
+            .output
+            .content
+            .iter()
+            .filter_map(|part| match part {
+                ContentPart::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<String>();
+        if let Ok(value) = serde_json::from_str(&text) {
+            recover_structured_output(response, value);
+        }
+    }
+}
+
+impl ExtractJsonObject {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also recovers `json_schema` responses whose schema describes an object.
+    pub fn with_json_schema(mut self, enabled: bool) -> Self {
+        self.include_json_schema = enabled;
+        self
+    }
+}
+
+impl ResponseTransform for ExtractJsonObject {
+    fn transform(&self, request: &ProviderRequest, response: &mut ProviderResponse) {
+        let applies = match request.response_format {
+            ResponseFormat::Text => false,
+            ResponseFormat::JsonObject => true,
+            ResponseFormat::JsonSchema { .. } => self.include_json_schema,
+        };
+        if !applies || response.output.structured_output.is_some() {
+            return;
+        }
+
+        let text = response
+            .output
+            .content
+            .iter()
+            .filter_map(|part| match part {
+                ContentPart::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        if let Some(value) = json_extract::extract_json_object(&text) {
+            recover_structured_output(response, value);
+        }
+    }
+}
+
+impl ResponseTransform for TrimWhitespace {
+    fn transform(&self, _request: &ProviderRequest, response: &mut ProviderResponse) {
+        for part in &mut response.output.content {
+            if let ContentPart::Text { text } = part
+                && text.trim().len() != text.len()
+            {
+                *text = text.trim().to_string();
+            }
+        }
+    }
+}
+
+impl ResponseTransform for DedupeWarnings {
+    fn transform(&self, _request: &ProviderRequest, response: &mut ProviderResponse) {
+        let mut seen = Vec::with_capacity(response.warnings.len());
+        response.warnings.retain(|warning| {
+            if seen.contains(warning) {
+                return false;
+            }
+            seen.push(warning.clone());
+            true
+        });
+    }
+}
+
+impl ResponseTransform for NormalizeFinishReason {
+    fn transform(&self, _request: &ProviderRequest, response: &mut ProviderResponse) {
+        let has_tool_calls = response
+            .output
+            .content
+            .iter()
+            .any(|part| matches!(part, ContentPart::ToolCall { .. }));
+        match response.finish_reason {
+            FinishReason::Stop | FinishReason::Other if has_tool_calls => {
+                response.finish_reason = FinishReason::ToolCalls;
+            }
+            FinishReason::ToolCalls if !has_tool_calls => {
+                response.finish_reason = FinishReason::Stop;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn recover_structured_output(response: &mut ProviderResponse, value: Value) {
+    response.output.structured_output = Some(value);
+    response
+        .warnings
+        .retain(|warning| warning.code != WarningCode::StructuredOutputParseFailed);
+}
+
+/// The body of a text that is exactly one fenced code block, without the info string.
+fn strip_fence(text: &str) -> Option<&str> {
+    let inner = text.trim().strip_prefix("```")?.strip_suffix("```")?;
+    let (info, body) = inner.split_once('\n')?;
+    if info.contains('`') || body.contains("```") {
+        return None;
+    }
+    Some(body.trim())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,217 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use super::{
+    DedupeWarnings, ExtractJsonObject, NormalizeFinishReason, ResponseTransform, StripJsonFences,
+    TrimWhitespace,
+};
+use crate::core::types::{
+    AssistantOutput, ContentPart, FinishReason, ModelRef, ProviderId, ProviderRequest,
+    ProviderResponse, ResponseFormat, RuntimeWarning, ToolCall, Usage,
+};
+use crate::providers::mock::{MockAdapter, MockMatcher, MockOutcome};
+use crate::runtime::ProviderRuntime;
+
+fn request(response_format: ResponseFormat) -> ProviderRequest {
+    ProviderRequest {
+        response_format,
+        ..ProviderRequest::new(
+            ModelRef {
+                provider_hint: Some(ProviderId::Openai),
+                model_id: "gpt-5-mini".to_string(),
+                requirements: None,
+            },
+            Vec::new(),
+        )
+    }
+}
+
+fn response(content: Vec<ContentPart>, finish_reason: FinishReason) -> ProviderResponse {
+    ProviderResponse {
+        output: AssistantOutput {
+            content,
+            structured_output: None,
+            annotations: Vec::new(),
+        },
+        usage: Usage::default(),
+        cost: None,
+        provider: ProviderId::Openai,
+        model: "gpt-5-mini".to_string(),
+        raw_provider_response: None,
+        request_id: None,
+        rate_limit: None,
+        finish_reason,
+        warnings: Vec::new(),
+        alternatives: Vec::new(),
+        response_id: None,
+    }
+}
+
+fn text(text: &str) -> ContentPart {
+    ContentPart::Text {
+        text: text.to_string(),
+    }
+}
+
+fn warning(code: &str, message: &str) -> RuntimeWarning {
+    RuntimeWarning::new(code.into(), message)
+}
+
+#[test]
+fn test_strip_json_fences_recovers_structured_output() {
+    let mut fenced = response(
+        vec![text("\n```json\n{\"answer\": 4}\n```\n")],
+        FinishReason::Stop,
+    );
+    fenced.warnings = vec![
+        warning("structured_output_parse_failed", "expected value at line 1"),
+        warning("other", "kept"),
+    ];
+
+    StripJsonFences.transform(&request(ResponseFormat::JsonObject), &mut fenced);
+
+    assert_eq!(fenced.output.content, [text("{\"answer\": 4}")]);
+    assert_eq!(fenced.output.structured_output, Some(json!({"answer": 4})));
+    assert_eq!(fenced.warnings, [warning("other", "kept")]);
+
+    let mut prose = response(
+        vec![text("Use ``` for code, e.g.\n```rust\nfn main() {}\n```")],
+        FinishReason::Stop,
+    );
+    let original = prose.clone();
+    StripJsonFences.transform(&request(ResponseFormat::Text), &mut prose);
+    assert_eq!(prose, original);
+}
+
+#[test]
+fn test_trim_and_dedupe_transforms() {
+    let mut padded = response(vec![text("  hello \n")], FinishReason::Stop);
+    padded.warnings = vec![
+        warning("a", "one"),
+        warning("b", "two"),
+        warning("a", "one"),
+        warning("a", "other"),
+    ];
+
+    TrimWhitespace.transform(&request(ResponseFormat::Text), &mut padded);
+    DedupeWarnings.transform(&request(ResponseFormat::Text), &mut padded);
+
+    assert_eq!(padded.output.content, [text("hello")]);
+    assert_eq!(
+        padded.warnings,
+        [
+            warning("a", "one"),
+            warning("b", "two"),
+            warning("a", "other")
+        ]
+    );
+}
+
+#[test]
+fn test_normalize_finish_reason_matches_tool_calls() {
+    let tool_call = ContentPart::ToolCall {
+        tool_call: ToolCall {
+            id: "call_1".to_string(),
+            name: "lookup".to_string(),
+            arguments_json: json!({}),
+        },
+    };
+    let mut stopped_with_call = response(vec![tool_call], FinishReason::Stop);
+    NormalizeFinishReason.transform(&request(ResponseFormat::Text), &mut stopped_with_call);
+    assert_eq!(stopped_with_call.finish_reason, FinishReason::ToolCalls);
+
+    let mut claimed_call = response(vec![text("done")], FinishReason::ToolCalls);
+    NormalizeFinishReason.transform(&request(ResponseFormat::Text), &mut claimed_call);
+    assert_eq!(claimed_call.finish_reason, FinishReason::Stop);
+
+    let mut truncated = response(vec![text("cut")], FinishReason::Length);
+    NormalizeFinishReason.transform(&request(ResponseFormat::Text), &mut truncated);
+    assert_eq!(truncated.finish_reason, FinishReason::Length);
+}
+
+#[tokio::test]
+async fn test_runtime_applies_transforms_in_registration_order() {
+    let adapter = Arc::new(MockAdapter::new(ProviderId::Openai).on(
+        MockMatcher::any(),
+        MockOutcome::text("  ```json\n[1, 2]\n```  "),
+    ));
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(adapter)
+        .with_response_transform(Arc::new(TrimWhitespace))
+        .with_response_transform(Arc::new(StripJsonFences))
+        .build();
+
+    let response = runtime
+        .run(request(ResponseFormat::JsonObject))
+        .await
+        .expect("run should succeed");
+
+    assert_eq!(response.output.content, [text("[1, 2]")]);
+    assert_eq!(response.output.structured_output, Some(json!([1, 2])));
+}
+
+#[test]
+fn test_extract_json_object_recovers_wrapped_objects() {
+    let wrapped = || {
+        let mut response = response(
+            vec![text(
+                "Here you go:\n```json\n{\"city\": \"Paris\"}\n```\nAnything else?",
+            )],
+            FinishReason::Stop,
+        );
+        response.warnings = vec![warning(
+            "structured_output_parse_failed",
+            "expected value at line 1",
+        )];
+        response
+    };
+    let schema = ResponseFormat::JsonSchema {
+        name: "city".to_string(),
+        schema: json!({"type": "object"}),
+    };
+
+    let mut object = wrapped();
+    ExtractJsonObject::new().transform(&request(ResponseFormat::JsonObject), &mut object);
+    assert_eq!(
+        object.output.structured_output,
+        Some(json!({"city": "Paris"}))
+    );
+    assert!(object.warnings.is_empty());
+
+    let mut schema_off = wrapped();
+    ExtractJsonObject::new().transform(&request(schema.clone()), &mut schema_off);
+    assert_eq!(schema_off, wrapped());
+
+    let mut schema_on = wrapped();
+    ExtractJsonObject::new()
+        .with_json_schema(true)
+        .transform(&request(schema), &mut schema_on);
+    assert_eq!(
+        schema_on.output.structured_output,
+        Some(json!({"city": "Paris"}))
+    );
+}
+
+#[tokio::test]
+async fn test_extract_json_object_applies_across_providers() {
+    let answer = "Sure! {\"ok\": true} Let me know if you need more.";
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(
+            MockAdapter::new(ProviderId::Openai).on(MockMatcher::any(), MockOutcome::text(answer)),
+        ))
+        .with_adapter(Arc::new(
+            MockAdapter::new(ProviderId::Openrouter)
+                .on(MockMatcher::any(), MockOutcome::text(answer)),
+        ))
+        .with_response_transform(Arc::new(ExtractJsonObject::new()))
+        .build();
+
+    for provider in [ProviderId::Openai, ProviderId::Openrouter] {
+        let mut req = request(ResponseFormat::JsonObject);
+        req.model.provider_hint = Some(provider.clone());
+        let response = runtime.run(req).await.expect("run should succeed");
+        assert_eq!(response.provider, provider);
+        assert_eq!(response.output.structured_output, Some(json!({"ok": true})));
+    }
+}
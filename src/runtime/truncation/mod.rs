@@ -0,0 +1,338 @@
+//! Trimming conversations that would overflow the target model's context window.
+//!
+//! When a strategy is set with `ProviderRuntimeBuilder::with_context_truncation`,
+//! `ProviderRuntime::run` estimates each request with `estimate_tokens` and, if it does not fit,
+//! shortens `messages` before sending. System and developer messages are always kept, as is the
+//! latest message, and tool results are never separated from the assistant turn that called
+//! them. Every truncation adds a `runtime.context_truncated` warning to the response, and a
+//! request that still overflows afterwards fails with `RuntimeError::ContextWindowExceeded`.
+
+use crate::core::error::RuntimeError;
+use crate::core::types::{
+    ContentPart, Message, MessageRole, ModelRef, ProviderRequest, ProviderResponse, RuntimeWarning,
+    WarningCode,
+};
+use crate::runtime::tokens::{self, TokenEstimate};
+use crate::runtime::{ProviderRuntime, combine_costs};
+
+const SUMMARY_INSTRUCTIONS: &str = "Summarize the following conversation excerpt in a few \
+    sentences. Keep facts, decisions, names, and open questions; omit pleasantries.";
+
+/// How `ProviderRuntime::run` shortens a request that exceeds its model's context window.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TruncationStrategy {
+    /// Drop the oldest conversation messages until the request fits.
+    DropOldest,
+    /// Keep only the last `n` conversation messages.
+    KeepSystemAndLastN(usize),
+    /// Replace everything between the first conversation message and the last `keep_last` with
+    /// a summary written by `model`, which should be a cheap model with a large context window.
+    /// The summary call goes through the runtime like any other run.
+    SummarizeMiddle { model: ModelRef, keep_last: usize },
+}
+
+/// What `truncate_to_fit` did to a request, to be reported on its response.
+#[derive(Debug, Default)]
+pub(super) struct Truncation {
+    warnings: Vec<RuntimeWarning>,
+    /// The `SummarizeMiddle` call, whose usage and cost the response takes on.
+    summary: Option<ProviderResponse>,
+}
+
+impl Truncation {
+    /// Adds the truncation warnings to `response`, along with the summary call's usage and cost.
+    pub(super) fn apply(self, response: &mut ProviderResponse) {
+        response.warnings.extend(self.warnings);
+        let Some(summary) = self.summary else {
+            return;
+        };
+        response.usage.accumulate(&summary.usage);
+        if summary.cost.is_none() && response.cost.is_some() {
+            response.warnings.push(RuntimeWarning {
+                code: WarningCode::ContextSummaryUnpriced,
+                message: "the truncation summary call has no cost; the response's cost excludes it"
+                    .to_string(),
+            });
+            return;
+        }
+        response.cost = match combine_costs(summary.cost, response.cost.take()) {
+            Ok(cost) => cost,
+            Err((summary_currency, currency)) => {
+                response.warnings.push(RuntimeWarning {
+                    code: WarningCode::PricingCurrencyMismatch,
+                    message: format!(
+                        "the truncation summary was priced in {summary_currency} and the \
+                         response in {currency}; cost dropped"
+                    ),
+                });
+                None
+            }
+        };
+    }
+}
+
+impl ProviderRuntime {
+    /// Applies the configured truncation strategy when `request` would overflow its model's
+    /// context window. Fails with `RuntimeError::ContextWindowExceeded` when the truncated
+    /// request still does not fit.
+    pub(super) async fn truncate_to_fit(
+        &self,
+        request: &mut ProviderRequest,
+    ) -> Result<Truncation, RuntimeError> {
+        let Some(strategy) = &self.truncation else {
+            return Ok(Truncation::default());
+        };
+        let estimate = self.estimate_tokens(request).await?;
+        let Some(overflow) = overflow_tokens(&estimate) else {
+            return Ok(Truncation::default());
+        };
+
+        let window = estimate.context_window.unwrap_or_default();
+        let mut summary = None;
+        let message = match strategy {
+            TruncationStrategy::DropOldest => {
+                let (dropped, freed) = self.drop_oldest(request, overflow);
+                format!(
+                    "dropped the {dropped} oldest message(s) (~{freed} tokens) to fit the \
+                     {window} token context window of model '{}'",
+                    request.model.model_id
+                )
+            }
+            TruncationStrategy::KeepSystemAndLastN(keep_last) => {
+                let dropped = retain_tail(&mut request.messages, *keep_last).len();
+                format!(
+                    "dropped {dropped} message(s), keeping the last {keep_last}, to fit the \
+                     {window} token context window of model '{}'",
+                    request.model.model_id
+                )
+            }
+            TruncationStrategy::SummarizeMiddle { model, keep_last } => {
+                let (summarized, response) =
+                    self.summarize_middle(request, model, *keep_last).await?;
+                summary = response;
+                format!(
+                    "summarized {summarized} message(s) with model '{}' to fit the {window} \
+                     token context window of model '{}'; the response's usage and cost include \
+                     the summary call",
+                    model.model_id, request.model.model_id
+                )
+            }
+        };
+
+        let estimate = self.estimate_tokens(request).await?;
+        if let Some(overflow) = overflow_tokens(&estimate) {
+            return Err(RuntimeError::ContextWindowExceeded {
+                model: request.model.model_id.clone(),
+                message: format!(
+                    "still {overflow} token(s) over the {window} token context window after \
+                     truncation ({message})"
+                ),
+            });
+        }
+
+        Ok(Truncation {
+            warnings: vec![RuntimeWarning {
+                code: WarningCode::ContextTruncated,
+                message,
+            }],
+            summary,
+        })
+    }
+
+    /// Drops conversation messages oldest first until at least `overflow` tokens, as counted by
+    /// the runtime's `TokenCounter`, have been removed. Returns the messages and tokens dropped.
+    fn drop_oldest(&self, request: &mut ProviderRequest, overflow: u64) -> (usize, u64) {
+        let model_id = request.model.model_id.clone();
+        let count = |message: &Message| {
+            tokens::count_message(self.token_counter.as_ref(), &model_id, message)
+        };
+        let conversation = conversation_indices(&request.messages);
+        // Each cut is counted as `retain_tail` will apply it, after `tail_start` pulls it back
+        // over tool results.
+        let mut keep_last = conversation.len();
+        let mut freed = 0;
+        while keep_last > 1 && freed < overflow {
+            keep_last -= 1;
+            let start = tail_start(&request.messages, &conversation, keep_last);
+            freed = conversation[..start]
+                .iter()
+                .map(|index| count(&request.messages[*index]))
+                .sum();
+        }
+
+        let removed = retain_tail(&mut request.messages, keep_last);
+        (removed.len(), removed.iter().map(count).sum())
+    }
+
+    /// Replaces the middle of the conversation with a summary message. Returns how many messages
+    /// were summarized and the summary call's response, if one was made.
+    async fn summarize_middle(
+        &self,
+        request: &mut ProviderRequest,
+        model: &ModelRef,
+        keep_last: usize,
+    ) -> Result<(usize, Option<ProviderResponse>), RuntimeError> {
+        let conversation = conversation_indices(&request.messages);
+        let tail_start = tail_start(&request.messages, &conversation, keep_last);
+        // The first conversation message usually states the task, so it is kept verbatim, along
+        // with any tool results answering it.
+        let mut head_end = 1.min(tail_start);
+        while head_end < tail_start
+            && request.messages[conversation[head_end]].role == MessageRole::Tool
+        {
+            head_end += 1;
+        }
+        let middle = conversation[head_end..tail_start].to_vec();
+        if middle.is_empty() {
+            return Ok((0, None));
+        }
+
+        let transcript = middle
+            .iter()
+            .map(|index| transcript_line(&request.messages[*index]))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut summary_request = single_message_request(
+            &model.model_id,
+            Message {
+                role: MessageRole::User,
+                content: vec![ContentPart::Text {
+                    text: format!("{SUMMARY_INSTRUCTIONS}\n\n{transcript}"),
+                }],
+                cache_hint: None,
+            },
+        );
+        summary_request.model = model.clone();
+        let summary = self.run_captured(summary_request).await?;
+        let summary_text = summary
+            .output
+            .content
+            .iter()
+            .filter_map(|part| match part {
+                ContentPart::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("");
+
+        let insert_at = middle[0];
+        let mut position = 0;
+        request.messages.retain(|_| {
+            let keep = !middle.contains(&position);
+            position += 1;
+            keep
+        });
+        request.messages.insert(
+            insert_at,
+            Message {
+                role: MessageRole::User,
+                content: vec![ContentPart::Text {
+                    text: format!("Summary of the earlier conversation: {summary_text}"),
+                }],
+                cache_hint: None,
+            },
+        );
+        Ok((middle.len(), Some(summary)))
+    }
+}
+
+/// Tokens by which `estimate` overflows its model's context window, if it does.
+fn overflow_tokens(estimate: &TokenEstimate) -> Option<u64> {
+    estimate
+        .remaining_tokens()
+        .filter(|remaining| *remaining < 0)
+        .map(i64::unsigned_abs)
+}
+
+/// Indices of messages other than system and developer instructions.
+fn conversation_indices(messages: &[Message]) -> Vec<usize> {
+    messages
+        .iter()
+        .enumerate()
+        .filter(|(_, message)| {
+            !matches!(message.role, MessageRole::System | MessageRole::Developer)
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Position in `conversation` where the last `keep_last` messages start, moved back so the tail
+/// does not open with tool results whose call would be dropped. The latest message is always
+/// kept.
+fn tail_start(messages: &[Message], conversation: &[usize], keep_last: usize) -> usize {
+    let mut start = conversation.len().saturating_sub(keep_last.max(1));
+    while start > 0 && messages[conversation[start]].role == MessageRole::Tool {
+        start -= 1;
+    }
+    start
+}
+
+/// Removes all conversation messages before the last `keep_last`, returning the removed ones.
+fn retain_tail(messages: &mut Vec<Message>, keep_last: usize) -> Vec<Message> {
+    let conversation = conversation_indices(messages);
+    let start = tail_start(messages, &conversation, keep_last);
+    let drop: Vec<usize> = conversation[..start].to_vec();
+
+    let mut removed = Vec::with_capacity(drop.len());
+    let mut kept = Vec::with_capacity(messages.len() - drop.len());
+    for (index, message) in messages.drain(..).enumerate() {
+        if drop.contains(&index) {
+            removed.push(message);
+        } else {
+            kept.push(message);
+        }
+    }
+    *messages = kept;
+    removed
+}
+
+fn single_message_request(model_id: &str, message: Message) -> ProviderRequest {
+    ProviderRequest::new(
+        ModelRef {
+            provider_hint: None,
+            model_id: model_id.to_string(),
+            requirements: None,
+        },
+        vec![message],
+    )
+}
+
+fn transcript_line(message: &Message) -> String {
+    let role = match message.role {
+        MessageRole::System => "system",
+        MessageRole::Developer => "developer",
+        MessageRole::User => "user",
+        MessageRole::Assistant => "assistant",
+        MessageRole::Tool => "tool",
+    };
+    let text = message
+        .content
+        .iter()
+        .map(|part| match part {
+            ContentPart::Text { text } => text.clone(),
+            ContentPart::Thinking { .. } => String::new(),
+            ContentPart::ToolCall { tool_call } => {
+                format!(
+                    "[called {} with {}]",
+                    tool_call.name, tool_call.arguments_json
+                )
+            }
+            ContentPart::HostedToolCall { hosted_tool_call } => {
+                format!("[ran hosted tool {}]", hosted_tool_call.name)
+            }
+            ContentPart::Other { kind, .. } => format!("[{kind} content]"),
+            ContentPart::ToolResult { tool_result } => {
+                format!(
+                    "[tool result: {}]",
+                    serde_json::to_string(&tool_result.content).unwrap_or_default()
+                )
+            }
+        })
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("{role}: {text}")
+}
+
+#[cfg(test)]
+mod tests;
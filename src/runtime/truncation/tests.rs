@@ -0,0 +1,406 @@
+use std::sync::Arc;
+
+use super::TruncationStrategy;
+use crate::core::error::RuntimeError;
+use crate::core::types::{
+    AssistantOutput, ContentPart, CostBreakdown, FinishReason, Message, MessageRole, ModelCatalog,
+    ModelInfo, ModelRef, PricingSource, ProviderId, ProviderRequest, ProviderResponse, ToolCall,
+    ToolResult, ToolResultContent, Usage,
+};
+use crate::providers::mock::{MockAdapter, MockMatcher, MockOutcome};
+use crate::runtime::ProviderRuntime;
+
+fn text_message(role: MessageRole, text: &str) -> Message {
+    Message {
+        role,
+        content: vec![ContentPart::Text {
+            text: text.to_string(),
+        }],
+        cache_hint: None,
+    }
+}
+
+fn request(messages: Vec<Message>) -> ProviderRequest {
+    ProviderRequest::new(
+        ModelRef {
+            provider_hint: Some(ProviderId::Openai),
+            model_id: "gpt-5-mini".to_string(),
+            requirements: None,
+        },
+        messages,
+    )
+}
+
+/// Five conversation turns of 5 heuristic tokens each behind a system prompt: 34 tokens with
+/// reply priming.
+fn long_conversation() -> Vec<Message> {
+    vec![
+        text_message(MessageRole::System, "be brief"),
+        text_message(MessageRole::User, "turn-one"),
+        text_message(MessageRole::Assistant, "turn-two"),
+        text_message(MessageRole::User, "turn-thr"),
+        text_message(MessageRole::Assistant, "turn-fou"),
+        text_message(MessageRole::User, "turn-fiv"),
+    ]
+}
+
+/// `long_conversation` ending in a tool call and its result instead of the last user turn.
+fn tool_call_conversation() -> Vec<Message> {
+    let mut messages = long_conversation();
+    messages.pop();
+    messages.push(Message {
+        role: MessageRole::Assistant,
+        content: vec![ContentPart::ToolCall {
+            tool_call: ToolCall {
+                id: "call_1".to_string(),
+                name: "lookup".to_string(),
+                arguments_json: serde_json::json!({ "query": "the weather today" }),
+            },
+        }],
+        cache_hint: None,
+    });
+    messages.push(Message {
+        role: MessageRole::Tool,
+        content: vec![ContentPart::ToolResult {
+            tool_result: ToolResult {
+                tool_call_id: "call_1".to_string(),
+                content: ToolResultContent::Text {
+                    text: "sunny".to_string(),
+                },
+                raw_provider_content: None,
+                is_error: false,
+            },
+        }],
+        cache_hint: None,
+    });
+    messages
+}
+
+fn runtime(adapter: Arc<MockAdapter>, strategy: TruncationStrategy) -> ProviderRuntime {
+    runtime_with_window(adapter, strategy, 24)
+}
+
+fn runtime_with_window(
+    adapter: Arc<MockAdapter>,
+    strategy: TruncationStrategy,
+    context_window: u32,
+) -> ProviderRuntime {
+    ProviderRuntime::builder()
+        .with_adapter(adapter)
+        .with_model_catalog(ModelCatalog {
+            models: vec![ModelInfo {
+                provider: ProviderId::Openai,
+                model_id: "gpt-5-mini".to_string(),
+                display_name: None,
+                context_window: Some(context_window),
+                max_output_tokens: None,
+                supports_tools: true,
+                supports_structured_output: true,
+                pricing: None,
+                input_modalities: Vec::new(),
+                output_modalities: Vec::new(),
+                deprecated: false,
+                knowledge_cutoff: None,
+            }],
+        })
+        .with_context_truncation(strategy)
+        .build()
+}
+
+fn sent_texts(adapter: &MockAdapter) -> Vec<String> {
+    adapter.requests()[0]
+        .messages
+        .iter()
+        .map(|message| match &message.content[0] {
+            ContentPart::Text { text } => text.clone(),
+            other => format!("{other:?}"),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_drop_oldest_removes_just_enough_messages() {
+    let adapter = Arc::new(
+        MockAdapter::new(ProviderId::Openai).on(MockMatcher::any(), MockOutcome::text("ok")),
+    );
+    let runtime = runtime(adapter.clone(), TruncationStrategy::DropOldest);
+
+    let response = runtime
+        .run(request(long_conversation()))
+        .await
+        .expect("run should succeed");
+
+    assert_eq!(
+        sent_texts(&adapter),
+        vec!["be brief", "turn-thr", "turn-fou", "turn-fiv"]
+    );
+    let warning = response
+        .warnings
+        .iter()
+        .find(|warning| warning.code == "runtime.context_truncated")
+        .expect("truncation warning");
+    assert!(warning.message.contains("dropped the 2 oldest message(s)"));
+}
+
+#[tokio::test]
+async fn test_requests_that_fit_are_left_alone() {
+    let adapter = Arc::new(
+        MockAdapter::new(ProviderId::Openai).on(MockMatcher::any(), MockOutcome::text("ok")),
+    );
+    let runtime = runtime(adapter.clone(), TruncationStrategy::KeepSystemAndLastN(1));
+
+    let response = runtime
+        .run(request(vec![text_message(MessageRole::User, "short")]))
+        .await
+        .expect("run should succeed");
+
+    assert_eq!(adapter.requests()[0].messages.len(), 1);
+    assert!(response.warnings.is_empty());
+}
+
+#[tokio::test]
+async fn test_keep_last_n_does_not_orphan_tool_results() {
+    let adapter = Arc::new(
+        MockAdapter::new(ProviderId::Openai).on(MockMatcher::any(), MockOutcome::text("ok")),
+    );
+    let runtime = runtime_with_window(
+        adapter.clone(),
+        TruncationStrategy::KeepSystemAndLastN(1),
+        30,
+    );
+
+    runtime
+        .run(request(tool_call_conversation()))
+        .await
+        .expect("run should succeed");
+
+    let sent = &adapter.requests()[0].messages;
+    assert_eq!(sent.len(), 3);
+    assert_eq!(sent[0].role, MessageRole::System);
+    assert_eq!(sent[1].role, MessageRole::Assistant);
+    assert_eq!(sent[2].role, MessageRole::Tool);
+}
+
+/// `long_conversation` with four more turns before the last one.
+fn nine_turn_conversation() -> Vec<Message> {
+    let mut messages = long_conversation();
+    let last = messages.pop().expect("conversation has a last turn");
+    messages.extend([
+        text_message(MessageRole::User, "turn-six"),
+        text_message(MessageRole::Assistant, "turn-sev"),
+        text_message(MessageRole::User, "turn-eig"),
+        text_message(MessageRole::Assistant, "turn-nin"),
+        last,
+    ]);
+    messages
+}
+
+#[tokio::test]
+async fn test_summarize_middle_replaces_middle_with_summary() {
+    let adapter = Arc::new(
+        MockAdapter::new(ProviderId::Openai)
+            .on(
+                MockMatcher::any().model("gpt-5-nano"),
+                MockOutcome::response(summary_response("they talked")),
+            )
+            .on(MockMatcher::any(), MockOutcome::text("ok")),
+    );
+    let runtime = runtime_with_window(
+        adapter.clone(),
+        TruncationStrategy::SummarizeMiddle {
+            model: ModelRef {
+                provider_hint: Some(ProviderId::Openai),
+                model_id: "gpt-5-nano".to_string(),
+                requirements: None,
+            },
+            keep_last: 1,
+        },
+        40,
+    );
+    let response = runtime
+        .run(request(nine_turn_conversation()))
+        .await
+        .expect("run should succeed");
+
+    let requests = adapter.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].model.model_id, "gpt-5-nano");
+    let ContentPart::Text { text } = &requests[0].messages[0].content[0] else {
+        panic!("summary prompt should be text");
+    };
+    assert!(text.contains("assistant: turn-two"));
+    assert!(!text.contains("turn-one"));
+
+    let sent = &requests[1].messages;
+    assert_eq!(sent.len(), 4);
+    assert_eq!(
+        sent[2].content,
+        vec![ContentPart::Text {
+            text: "Summary of the earlier conversation: they talked".to_string(),
+        }]
+    );
+    assert!(
+        response
+            .warnings
+            .iter()
+            .any(|warning| warning.message.contains("summarized 7 message(s)"))
+    );
+    assert_eq!(response.usage.input_tokens, Some(40));
+    assert_eq!(response.usage.output_tokens, Some(8));
+}
+
+fn summarize_runtime(adapter: Arc<MockAdapter>, context_window: u32) -> ProviderRuntime {
+    runtime_with_window(
+        adapter,
+        TruncationStrategy::SummarizeMiddle {
+            model: ModelRef {
+                provider_hint: Some(ProviderId::Openai),
+                model_id: "gpt-5-nano".to_string(),
+                requirements: None,
+            },
+            keep_last: 1,
+        },
+        context_window,
+    )
+}
+
+#[tokio::test]
+async fn test_summarize_middle_keeps_tool_results_with_first_message() {
+    let adapter = Arc::new(
+        MockAdapter::new(ProviderId::Openai)
+            .on(
+                MockMatcher::any().model("gpt-5-nano"),
+                MockOutcome::response(summary_response("they talked")),
+            )
+            .on(MockMatcher::any(), MockOutcome::text("ok")),
+    );
+    let runtime = summarize_runtime(adapter.clone(), 50);
+    let mut messages = tool_call_conversation();
+    let result = messages
+        .pop()
+        .expect("conversation ends with a tool result");
+    let call = messages.pop().expect("conversation has a tool call");
+    messages.splice(1..2, [call, result]);
+    messages.extend([
+        text_message(MessageRole::User, "turn-six"),
+        text_message(MessageRole::Assistant, "turn-sev"),
+        text_message(MessageRole::User, "turn-eig"),
+    ]);
+
+    runtime
+        .run(request(messages))
+        .await
+        .expect("run should succeed");
+
+    let requests = adapter.requests();
+    let ContentPart::Text { text } = &requests[0].messages[0].content[0] else {
+        panic!("summary prompt should be text");
+    };
+    assert!(!text.contains("tool result"), "{text}");
+    let roles = requests[1]
+        .messages
+        .iter()
+        .map(|message| message.role.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        roles,
+        vec![
+            MessageRole::System,
+            MessageRole::Assistant,
+            MessageRole::Tool,
+            MessageRole::User,
+            MessageRole::User,
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_summarize_middle_keeps_response_cost_when_summary_is_unpriced() {
+    let mut answer = summary_response("ok");
+    answer.model = "gpt-5-mini".to_string();
+    answer.cost = Some(CostBreakdown {
+        currency: "USD".to_string(),
+        input_cost: 0.001,
+        cached_input_cost: 0.0,
+        output_cost: 0.002,
+        fixed_cost: 0.0,
+        total_cost: 0.003,
+        pricing_source: PricingSource::Configured,
+    });
+    let adapter = Arc::new(
+        MockAdapter::new(ProviderId::Openai)
+            .on(
+                MockMatcher::any().model("gpt-5-nano"),
+                MockOutcome::response(summary_response("they talked")),
+            )
+            .on(MockMatcher::any(), MockOutcome::response(answer)),
+    );
+    let runtime = summarize_runtime(adapter, 40);
+
+    let response = runtime
+        .run(request(nine_turn_conversation()))
+        .await
+        .expect("run should succeed");
+
+    assert_eq!(
+        response.cost.as_ref().map(|cost| cost.total_cost),
+        Some(0.003)
+    );
+    assert!(
+        response
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "runtime.context_summary_unpriced")
+    );
+}
+
+/// The summary model's reply, with usage so it can be traced onto the final response.
+fn summary_response(text: &str) -> ProviderResponse {
+    ProviderResponse {
+        output: AssistantOutput {
+            content: vec![ContentPart::Text {
+                text: text.to_string(),
+            }],
+            structured_output: None,
+            annotations: Vec::new(),
+        },
+        usage: Usage {
+            input_tokens: Some(40),
+            output_tokens: Some(8),
+            ..Usage::default()
+        },
+        cost: None,
+        provider: ProviderId::Openai,
+        model: "gpt-5-nano".to_string(),
+        raw_provider_response: None,
+        finish_reason: FinishReason::Stop,
+        warnings: Vec::new(),
+        alternatives: Vec::new(),
+        response_id: None,
+        request_id: None,
+        rate_limit: None,
+    }
+}
+
+#[tokio::test]
+async fn test_drop_oldest_fails_when_tool_results_keep_request_too_long() {
+    let adapter = Arc::new(
+        MockAdapter::new(ProviderId::Openai).on(MockMatcher::any(), MockOutcome::text("ok")),
+    );
+    let runtime = runtime(adapter.clone(), TruncationStrategy::DropOldest);
+
+    let error = runtime
+        .run(request(tool_call_conversation()))
+        .await
+        .expect_err("the tool call and its result alone overflow the window");
+
+    let RuntimeError::ContextWindowExceeded { model, message } = error else {
+        panic!("expected context window error, got {error:?}");
+    };
+    assert_eq!(model, "gpt-5-mini");
+    assert!(
+        message.contains("dropped the 4 oldest message(s) (~20 tokens)"),
+        "{message}"
+    );
+    assert!(adapter.requests().is_empty());
+}
@@ -0,0 +1,81 @@
+//! `tracing` instrumentation, compiled in with the `tracing` feature.
+//!
+//! Spans: `provider_runtime.run` per `ProviderRuntime::run`, `provider_runtime.adapter` per
+//! adapter call (including fallback and hedged calls), and `provider_runtime.http` per
+//! transport request. Each HTTP attempt and each finished run also emits an event under
+//! the `provider_runtime` target.
+
+use std::time::Instant;
+
+use tracing::Span;
+
+use crate::core::error::{ProviderError, RuntimeError};
+use crate::core::types::ProviderResponse;
+
+pub(crate) const TARGET: &str = "provider_runtime";
+
+/// Records the outcome of a run on the current `provider_runtime.run` span and emits a
+/// completion event with latency and token counts.
+pub(crate) fn record_run_result(
+    result: &Result<ProviderResponse, RuntimeError>,
+    started_at: Instant,
+) {
+    let latency_ms = started_at.elapsed().as_millis() as u64;
+    match result {
+        Ok(response) => {
+            let span = Span::current();
+            span.record("provider", tracing::field::debug(&response.provider));
+            span.record("served_model", response.model.as_str());
+
+            let usage = &response.usage;
+            tracing::info!(
+                target: TARGET,
+                latency_ms,
+                input_tokens = usage.input_tokens,
+                output_tokens = usage.output_tokens,
+                cached_input_tokens = usage.cached_input_tokens,
+                reasoning_tokens = usage.reasoning_tokens,
+                total_cost = response.cost.as_ref().map(|cost| cost.total_cost),
+                finish_reason = ?response.finish_reason,
+                warnings = response.warnings.len(),
+                "provider run completed"
+            );
+        }
+        Err(error) => {
+            tracing::warn!(target: TARGET, latency_ms, error = %error, "provider run failed");
+        }
+    }
+}
+
+/// Emits one event per HTTP attempt, including retried attempts.
+pub(crate) fn record_http_attempt(
+    attempt: u32,
+    status_code: Option<u16>,
+    request_id: Option<&str>,
+    started_at: Instant,
+    error: Option<&ProviderError>,
+) {
+    let latency_ms = started_at.elapsed().as_millis() as u64;
+    match error {
+        None => tracing::debug!(
+            target: TARGET,
+            attempt,
+            status = status_code,
+            request_id,
+            latency_ms,
+            "http attempt succeeded"
+        ),
+        Some(error) => tracing::debug!(
+            target: TARGET,
+            attempt,
+            status = status_code,
+            request_id,
+            latency_ms,
+            error = %error,
+            "http attempt failed"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests;
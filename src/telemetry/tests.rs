@@ -0,0 +1,88 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+use crate::core::types::{
+    ContentPart, Message, MessageRole, ModelRef, ProviderId, ProviderRequest,
+};
+use crate::providers::mock::{MockAdapter, MockMatcher, MockOutcome};
+use crate::runtime::ProviderRuntime;
+
+/// Collects span names and event targets; just enough to assert instrumentation is wired.
+#[derive(Default)]
+struct CollectingSubscriber {
+    next_id: AtomicU64,
+    spans: Arc<Mutex<Vec<String>>>,
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+impl Subscriber for CollectingSubscriber {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.spans
+            .lock()
+            .expect("spans lock")
+            .push(span.metadata().name().to_string());
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        self.events
+            .lock()
+            .expect("events lock")
+            .push(event.metadata().target().to_string());
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[tokio::test]
+async fn test_run_emits_runtime_and_adapter_spans() {
+    let subscriber = CollectingSubscriber::default();
+    let spans = subscriber.spans.clone();
+    let events = subscriber.events.clone();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(
+            MockAdapter::new(ProviderId::Openai).on(MockMatcher::any(), MockOutcome::text("hi")),
+        ))
+        .build();
+    runtime
+        .run(ProviderRequest::new(
+            ModelRef {
+                provider_hint: Some(ProviderId::Openai),
+                model_id: "gpt-5-mini".to_string(),
+                requirements: None,
+            },
+            vec![Message {
+                role: MessageRole::User,
+                content: vec![ContentPart::Text {
+                    text: "hello".to_string(),
+                }],
+                cache_hint: None,
+            }],
+        ))
+        .await
+        .expect("run should succeed");
+
+    assert_eq!(
+        *spans.lock().expect("spans lock"),
+        vec!["provider_runtime.run", "provider_runtime.adapter"]
+    );
+    assert_eq!(
+        *events.lock().expect("events lock"),
+        vec![super::TARGET.to_string()]
+    );
+}
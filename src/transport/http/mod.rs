@@ -1,24 +1,54 @@
-use std::time::Duration;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
-use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
+use async_trait::async_trait;
+use futures_util::stream;
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
 use reqwest::{Method, Response};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
+use serde_json::Value;
 
-use crate::core::error::{ConfigError, ProviderError};
-use crate::core::types::{AdapterContext, ProviderId};
+use crate::core::error::{ConfigError, ErrorSource, ProviderError};
+use crate::core::traits::Redactor;
+use crate::core::types::{AdapterContext, ProviderId, RateLimitInfo};
+use crate::runtime::trace::{self, TraceAttempt};
+use crate::transport::ratelimit::RateLimitBackend;
+use crate::transport::redaction::DefaultRedactor;
+use crate::transport::{MultipartForm, SseEvent, SseStream, Transport};
 
 const AUTH_BEARER_TOKEN_KEY: &str = "transport.auth.bearer_token";
 const CUSTOM_HEADER_PREFIX: &str = "transport.header.";
 const REQUEST_ID_HEADER_KEY: &str = "transport.request_id_header";
 const DEFAULT_REQUEST_ID_HEADER: &str = "x-request-id";
 
+/// How computed backoff delays are randomized so concurrent callers don't retry in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JitterMode {
+    /// Sleep exactly the computed backoff.
+    #[default]
+    None,
+    /// Sleep a uniformly random duration in `[0, backoff]`.
+    Full,
+    /// Sleep half the backoff plus a uniformly random duration in `[0, backoff / 2]`.
+    Equal,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RetryPolicy {
     pub max_attempts: u32,
     pub initial_backoff_ms: u64,
     pub max_backoff_ms: u64,
     pub retryable_status_codes: Vec<u16>,
+    /// Upper bound on a server-requested `Retry-After` delay, which replaces the computed backoff
+    /// when present.
+    pub max_retry_after_ms: u64,
+    pub jitter: JitterMode,
+    /// Wall-clock budget measured from the first attempt. A retry whose delay would end past the
+    /// budget is not attempted and the last error is returned instead.
+    pub max_total_retry_duration_ms: Option<u64>,
 }
 
 impl RetryPolicy {
@@ -46,19 +76,56 @@ impl RetryPolicy {
         Ok(())
     }
 
-    fn should_retry_status(&self, status_code: u16) -> bool {
+    pub(crate) fn should_retry_status(&self, status_code: u16) -> bool {
         self.retryable_status_codes.contains(&status_code)
     }
 
-    fn backoff_duration_for_retry(&self, retry_index: u32) -> Duration {
+    pub(crate) fn retry_after_duration(&self, retry_after_ms: u64) -> Duration {
+        Duration::from_millis(retry_after_ms.min(self.max_retry_after_ms))
+    }
+
+    pub(crate) fn backoff_duration_for_retry(&self, retry_index: u32) -> Duration {
         let shift = retry_index.min(63);
         let multiplier = 1_u64.checked_shl(shift).unwrap_or(u64::MAX);
         let backoff_ms = self
             .initial_backoff_ms
             .saturating_mul(multiplier)
             .min(self.max_backoff_ms);
-        Duration::from_millis(backoff_ms)
+        Duration::from_millis(self.apply_jitter(backoff_ms))
     }
+
+    fn apply_jitter(&self, backoff_ms: u64) -> u64 {
+        match self.jitter {
+            JitterMode::None => backoff_ms,
+            JitterMode::Full => random_up_to(backoff_ms),
+            JitterMode::Equal => {
+                let half = backoff_ms / 2;
+                (backoff_ms - half).saturating_add(random_up_to(half))
+            }
+        }
+    }
+
+    fn retry_fits_budget(&self, retry_started_at: Instant, delay: Duration) -> bool {
+        match self.max_total_retry_duration_ms {
+            Some(budget_ms) => {
+                retry_started_at.elapsed().saturating_add(delay) <= Duration::from_millis(budget_ms)
+            }
+            None => true,
+        }
+    }
+}
+
+/// Returns a uniformly distributed value in `[0, max]`, seeded from std's per-process random keys.
+fn random_up_to(max: u64) -> u64 {
+    if max == 0 {
+        return 0;
+    }
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let sample = RandomState::new().hash_one(nanos);
+    sample % max.saturating_add(1)
 }
 
 impl Default for RetryPolicy {
@@ -68,27 +135,221 @@ impl Default for RetryPolicy {
             initial_backoff_ms: 100,
             max_backoff_ms: 2_000,
             retryable_status_codes: vec![408, 429, 500, 502, 503, 504],
+            max_retry_after_ms: 60_000,
+            jitter: JitterMode::None,
+            max_total_retry_duration_ms: None,
         }
     }
 }
 
+/// A decoded JSON response body with the request id and rate-limit state from its headers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonResponse<T> {
+    pub body: T,
+    pub request_id: Option<String>,
+    pub rate_limit: Option<RateLimitInfo>,
+}
+
+/// A raw response body, such as synthesized audio, with its content type and header metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryResponse {
+    pub body: Vec<u8>,
+    pub content_type: Option<String>,
+    pub request_id: Option<String>,
+    pub rate_limit: Option<RateLimitInfo>,
+}
+
 #[derive(Debug, Clone)]
 pub struct HttpTransport {
     client: reqwest::Client,
     retry_policy: RetryPolicy,
     timeout_ms: u64,
+    rate_limiter: Option<Arc<dyn RateLimitBackend>>,
+    redactor: Arc<dyn Redactor>,
+}
+
+/// Minimum TLS protocol version accepted for provider connections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlsVersion {
+    Tls12,
+    Tls13,
+}
+
+/// Configures the `reqwest::Client` an `HttpTransport` owns: egress proxy, extra trusted root
+/// certificates for private CAs, the minimum TLS version, and connection pool tuning. Unset
+/// options keep reqwest's defaults.
+#[derive(Debug, Clone)]
+pub struct HttpTransportBuilder {
+    timeout_ms: u64,
+    retry_policy: RetryPolicy,
+    proxy_url: Option<String>,
+    root_certificates_pem: Vec<Vec<u8>>,
+    min_tls_version: Option<TlsVersion>,
+    pool_idle_timeout_ms: Option<u64>,
+    pool_max_idle_per_host: Option<usize>,
+    http2_keep_alive_interval_ms: Option<u64>,
+    http2_keep_alive_timeout_ms: Option<u64>,
+    tcp_nodelay: Option<bool>,
+    rate_limiter: Option<Arc<dyn RateLimitBackend>>,
+    redactor: Option<Arc<dyn Redactor>>,
+}
+
+impl HttpTransportBuilder {
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Routes all provider traffic, HTTP and HTTPS, through the proxy at `proxy_url`.
+    pub fn with_proxy(mut self, proxy_url: impl Into<String>) -> Self {
+        self.proxy_url = Some(proxy_url.into());
+        self
+    }
+
+    /// Trusts the certificates in a PEM bundle in addition to the built-in roots.
+    pub fn with_root_certificate_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.root_certificates_pem.push(pem.into());
+        self
+    }
+
+    pub fn with_min_tls_version(mut self, version: TlsVersion) -> Self {
+        self.min_tls_version = Some(version);
+        self
+    }
+
+    /// How long an idle pooled connection is kept before it is closed.
+    pub fn with_pool_idle_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.pool_idle_timeout_ms = Some(timeout_ms);
+        self
+    }
+
+    pub fn with_pool_max_idle_per_host(mut self, max_idle: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max_idle);
+        self
+    }
+
+    /// Sends HTTP/2 PING frames at this interval to keep connections alive, closing the
+    /// connection when a ping is not acknowledged within `timeout_ms` (when set).
+    pub fn with_http2_keep_alive(mut self, interval_ms: u64, timeout_ms: Option<u64>) -> Self {
+        self.http2_keep_alive_interval_ms = Some(interval_ms);
+        self.http2_keep_alive_timeout_ms = timeout_ms;
+        self
+    }
+
+    pub fn with_tcp_nodelay(mut self, enabled: bool) -> Self {
+        self.tcp_nodelay = Some(enabled);
+        self
+    }
+
+    /// Throttles every attempt, retries included, through `rate_limiter` before it is sent.
+    /// Token usage is estimated as one token per four bytes of request body.
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<dyn RateLimitBackend>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Scrubs error response bodies through `redactor` before they become error messages.
+    /// Defaults to [`DefaultRedactor`].
+    pub fn with_redactor(mut self, redactor: Arc<dyn Redactor>) -> Self {
+        self.redactor = Some(redactor);
+        self
+    }
+
+    pub fn build(self) -> Result<HttpTransport, ConfigError> {
+        let invalid = |reason: String| ConfigError::InvalidTransportConfig { reason };
+        let mut client = reqwest::Client::builder();
+
+        if let Some(proxy_url) = &self.proxy_url {
+            let proxy = reqwest::Proxy::all(proxy_url)
+                .map_err(|error| invalid(format!("invalid proxy url {proxy_url}: {error}")))?;
+            client = client.proxy(proxy);
+        }
+
+        for pem in &self.root_certificates_pem {
+            let certificates = reqwest::Certificate::from_pem_bundle(pem)
+                .map_err(|error| invalid(format!("invalid root certificate PEM: {error}")))?;
+            if certificates.is_empty() {
+                return Err(invalid(
+                    "root certificate PEM contains no certificates".to_string(),
+                ));
+            }
+            for certificate in certificates {
+                client = client.add_root_certificate(certificate);
+            }
+        }
+
+        if let Some(version) = self.min_tls_version {
+            client = client.min_tls_version(match version {
+                TlsVersion::Tls12 => reqwest::tls::Version::TLS_1_2,
+                TlsVersion::Tls13 => reqwest::tls::Version::TLS_1_3,
+            });
+        }
+
+        if let Some(timeout_ms) = self.pool_idle_timeout_ms {
+            client = client.pool_idle_timeout(Duration::from_millis(timeout_ms));
+        }
+        if let Some(max_idle) = self.pool_max_idle_per_host {
+            client = client.pool_max_idle_per_host(max_idle);
+        }
+        if let Some(interval_ms) = self.http2_keep_alive_interval_ms {
+            if interval_ms == 0 {
+                return Err(invalid(
+                    "http2 keep-alive interval must be greater than 0".to_string(),
+                ));
+            }
+            client = client
+                .http2_keep_alive_interval(Duration::from_millis(interval_ms))
+                .http2_keep_alive_while_idle(true);
+            if let Some(timeout_ms) = self.http2_keep_alive_timeout_ms {
+                client = client.http2_keep_alive_timeout(Duration::from_millis(timeout_ms));
+            }
+        }
+        if let Some(enabled) = self.tcp_nodelay {
+            client = client.tcp_nodelay(enabled);
+        }
+
+        let client = client
+            .build()
+            .map_err(|error| invalid(format!("failed to build http client: {error}")))?;
+        let mut transport = HttpTransport::with_client(client, self.timeout_ms, self.retry_policy)?;
+        transport.rate_limiter = self.rate_limiter;
+        if let Some(redactor) = self.redactor {
+            transport.redactor = redactor;
+        }
+        Ok(transport)
+    }
 }
 
 impl HttpTransport {
     pub fn new(timeout_ms: u64, retry_policy: RetryPolicy) -> Result<Self, ConfigError> {
-        Self::validate_timeout(timeout_ms)?;
-        retry_policy.validate()?;
+        Self::builder()
+            .with_timeout_ms(timeout_ms)
+            .with_retry_policy(retry_policy)
+            .build()
+    }
 
-        Ok(Self {
-            client: reqwest::Client::new(),
-            retry_policy,
-            timeout_ms,
-        })
+    /// Starts a builder with the adapters' defaults: a 30 second timeout and the default retry
+    /// policy.
+    pub fn builder() -> HttpTransportBuilder {
+        HttpTransportBuilder {
+            timeout_ms: 30_000,
+            retry_policy: RetryPolicy::default(),
+            proxy_url: None,
+            root_certificates_pem: Vec::new(),
+            min_tls_version: None,
+            pool_idle_timeout_ms: None,
+            pool_max_idle_per_host: None,
+            http2_keep_alive_interval_ms: None,
+            http2_keep_alive_timeout_ms: None,
+            tcp_nodelay: None,
+            rate_limiter: None,
+            redactor: None,
+        }
     }
 
     pub fn with_client(
@@ -103,6 +364,8 @@ impl HttpTransport {
             client,
             retry_policy,
             timeout_ms,
+            rate_limiter: None,
+            redactor: Arc::new(DefaultRedactor::new()),
         })
     }
 
@@ -118,6 +381,7 @@ impl HttpTransport {
     {
         self.execute_json_request(provider, model, Method::GET, url, None, ctx)
             .await
+            .map(|response| response.body)
     }
 
     pub async fn post_json<TReq, TResp>(
@@ -132,35 +396,247 @@ impl HttpTransport {
         TReq: Serialize + ?Sized,
         TResp: DeserializeOwned,
     {
-        let payload = serde_json::to_vec(body).map_err(|error| ProviderError::Serialization {
-            provider: provider.clone(),
-            model: model.map(str::to_string),
-            request_id: None,
-            message: error.to_string(),
-        })?;
+        self.post_json_response(provider, model, url, body, ctx)
+            .await
+            .map(|response| response.body)
+    }
+
+    /// Like `post_json`, but also returns the provider request id of the successful response.
+    pub async fn post_json_response<TReq, TResp>(
+        &self,
+        provider: ProviderId,
+        model: Option<&str>,
+        url: &str,
+        body: &TReq,
+        ctx: &AdapterContext,
+    ) -> Result<JsonResponse<TResp>, ProviderError>
+    where
+        TReq: Serialize + ?Sized,
+        TResp: DeserializeOwned,
+    {
+        let payload = json_body(&provider, model, body)?;
 
         self.execute_json_request(provider, model, Method::POST, url, Some(payload), ctx)
             .await
     }
 
+    /// Posts a JSON body and returns the raw response bytes, for endpoints such as speech
+    /// synthesis that answer with a file instead of JSON.
+    pub async fn post_json_binary<TReq>(
+        &self,
+        provider: ProviderId,
+        model: Option<&str>,
+        url: &str,
+        body: &TReq,
+        ctx: &AdapterContext,
+    ) -> Result<BinaryResponse, ProviderError>
+    where
+        TReq: Serialize + ?Sized,
+    {
+        let payload = json_body(&provider, model, body)?;
+        let sent = self
+            .send_with_retry(
+                &provider,
+                model,
+                &Method::POST,
+                url,
+                Some(payload),
+                ctx,
+                None,
+            )
+            .await?;
+
+        let content_type = sent
+            .response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let bytes = sent
+            .response
+            .bytes()
+            .await
+            .map_err(|error| ProviderError::Transport {
+                provider: provider.clone(),
+                request_id: sent.request_id.clone(),
+                message: format!("failed to read response body: {error}"),
+                source: Some(ErrorSource::new(error)),
+            });
+        record_trace_attempt(
+            sent.attempt,
+            &Method::POST,
+            url,
+            Some(sent.status_code),
+            sent.request_id.clone(),
+            sent.started_at,
+            bytes.as_ref().err(),
+        );
+
+        bytes.map(|bytes| BinaryResponse {
+            body: bytes.to_vec(),
+            content_type,
+            request_id: sent.request_id,
+            rate_limit: sent.rate_limit,
+        })
+    }
+
+    /// Posts a `multipart/form-data` body, such as an audio upload, and decodes the JSON
+    /// response. The form is encoded once and resent unchanged on retries.
+    pub async fn post_multipart_response<TResp>(
+        &self,
+        provider: ProviderId,
+        model: Option<&str>,
+        url: &str,
+        form: &MultipartForm,
+        ctx: &AdapterContext,
+    ) -> Result<JsonResponse<TResp>, ProviderError>
+    where
+        TResp: DeserializeOwned,
+    {
+        let (content_type, bytes) = form.encode();
+        let content_type =
+            HeaderValue::from_str(&content_type).map_err(|error| ProviderError::Protocol {
+                provider: provider.clone(),
+                model: model.map(str::to_string),
+                request_id: None,
+                message: format!("invalid multipart content type: {error}"),
+            })?;
+        let payload = RequestBody {
+            content_type,
+            bytes,
+        };
+
+        self.execute_json_request(provider, model, Method::POST, url, Some(payload), ctx)
+            .await
+    }
+
+    /// Posts `body` and streams the `text/event-stream` response. Retries apply only until a
+    /// successful status arrives; errors while reading the stream end it.
+    pub async fn post_sse<TReq>(
+        &self,
+        provider: ProviderId,
+        model: Option<&str>,
+        url: &str,
+        body: &TReq,
+        ctx: &AdapterContext,
+    ) -> Result<SseStream, ProviderError>
+    where
+        TReq: Serialize + ?Sized,
+    {
+        let payload = json_body(&provider, model, body)?;
+
+        let sent = self
+            .send_with_retry(
+                &provider,
+                model,
+                &Method::POST,
+                url,
+                Some(payload),
+                ctx,
+                Some(HeaderValue::from_static("text/event-stream")),
+            )
+            .await?;
+        record_trace_attempt(
+            sent.attempt,
+            &Method::POST,
+            url,
+            Some(sent.status_code),
+            sent.request_id.clone(),
+            sent.started_at,
+            None,
+        );
+
+        Ok(sse_stream(
+            sent.response,
+            provider,
+            model.map(str::to_string),
+            sent.request_id,
+        ))
+    }
+
     async fn execute_json_request<TResp>(
         &self,
         provider: ProviderId,
         model: Option<&str>,
         method: Method,
         url: &str,
-        body: Option<Vec<u8>>,
+        body: Option<RequestBody>,
         ctx: &AdapterContext,
-    ) -> Result<TResp, ProviderError>
+    ) -> Result<JsonResponse<TResp>, ProviderError>
     where
         TResp: DeserializeOwned,
     {
-        let header_config = self.build_header_config(&provider, model, ctx)?;
+        let sent = self
+            .send_with_retry(&provider, model, &method, url, body, ctx, None)
+            .await?;
+
+        let parsed =
+            sent.response
+                .json::<TResp>()
+                .await
+                .map_err(|error| ProviderError::Serialization {
+                    provider: provider.clone(),
+                    model: model.map(str::to_string),
+                    request_id: sent.request_id.clone(),
+                    message: error.to_string(),
+                    source: Some(ErrorSource::new(error)),
+                });
+        record_trace_attempt(
+            sent.attempt,
+            &method,
+            url,
+            Some(sent.status_code),
+            sent.request_id.clone(),
+            sent.started_at,
+            parsed.as_ref().err(),
+        );
+
+        parsed.map(|body| JsonResponse {
+            body,
+            request_id: sent.request_id,
+            rate_limit: sent.rate_limit,
+        })
+    }
+
+    /// Sends the request, retrying per the policy, and returns the first successful response with
+    /// its body unread. The caller records the trace attempt for the successful response once the
+    /// body has been consumed.
+    #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "provider_runtime.http",
+            target = "provider_runtime",
+            skip_all,
+            fields(provider = ?provider, model, method = %method, url)
+        )
+    )]
+    async fn send_with_retry(
+        &self,
+        provider: &ProviderId,
+        model: Option<&str>,
+        method: &Method,
+        url: &str,
+        body: Option<RequestBody>,
+        ctx: &AdapterContext,
+        accept: Option<HeaderValue>,
+    ) -> Result<SentResponse, ProviderError> {
+        let mut header_config = self.build_header_config(provider, model, ctx)?;
+        if let Some(accept) = accept {
+            header_config.headers.insert(ACCEPT, accept);
+        }
         let model_owned = model.map(str::to_string);
 
+        let estimated_tokens = body
+            .as_ref()
+            .map_or(0, |payload| payload.bytes.len() as u64 / 4);
+        let retry_started_at = Instant::now();
         let mut attempt: u32 = 0;
         loop {
             attempt += 1;
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire(provider, estimated_tokens).await;
+            }
 
             let mut request_builder = self
                 .client
@@ -170,57 +646,94 @@ impl HttpTransport {
 
             if let Some(payload) = &body {
                 request_builder = request_builder
-                    .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
-                    .body(payload.clone());
+                    .header(CONTENT_TYPE, payload.content_type.clone())
+                    .body(payload.bytes.clone());
             }
 
+            let started_at = Instant::now();
             match request_builder.send().await {
                 Ok(response) => {
                     let status_code = response.status().as_u16();
                     let request_id =
                         extract_request_id(response.headers(), &header_config.request_id_header);
+                    let rate_limit = extract_rate_limit(response.headers());
+                    let retry_after_ms = rate_limit.as_ref().and_then(|info| info.retry_after_ms);
 
                     if !response.status().is_success() {
                         let status_error = self
                             .build_status_error(
-                                &provider,
+                                provider,
                                 model_owned.as_deref(),
                                 status_code,
-                                request_id,
+                                request_id.clone(),
+                                rate_limit,
                                 response,
                             )
                             .await;
+                        record_trace_attempt(
+                            attempt,
+                            method,
+                            url,
+                            Some(status_code),
+                            request_id,
+                            started_at,
+                            Some(&status_error),
+                        );
 
                         if attempt < self.retry_policy.max_attempts
                             && self.retry_policy.should_retry_status(status_code)
+                            && self
+                                .sleep_before_retry(attempt, retry_after_ms, retry_started_at)
+                                .await
                         {
-                            self.sleep_before_retry(attempt).await;
                             continue;
                         }
 
                         return Err(status_error);
                     }
 
-                    let parsed = response.json::<TResp>().await.map_err(|error| {
-                        ProviderError::Serialization {
+                    return Ok(SentResponse {
+                        response,
+                        status_code,
+                        request_id,
+                        rate_limit,
+                        attempt,
+                        started_at,
+                    });
+                }
+                Err(error) => {
+                    let retryable = is_retryable_transport(&error);
+                    let transport_error = if error.is_timeout() {
+                        ProviderError::Timeout {
                             provider: provider.clone(),
                             model: model_owned.clone(),
-                            request_id,
                             message: error.to_string(),
+                            source: Some(ErrorSource::new(error)),
+                        }
+                    } else {
+                        ProviderError::Transport {
+                            provider: provider.clone(),
+                            request_id: None,
+                            message: error.to_string(),
+                            source: Some(ErrorSource::new(error)),
                         }
-                    })?;
-
-                    return Ok(parsed);
-                }
-                Err(error) => {
-                    let transport_error = ProviderError::Transport {
-                        provider: provider.clone(),
-                        request_id: None,
-                        message: error.to_string(),
                     };
+                    record_trace_attempt(
+                        attempt,
+                        method,
+                        url,
+                        None,
+                        None,
+                        started_at,
+                        Some(&transport_error),
+                    );
 
-                    if attempt < self.retry_policy.max_attempts && is_retryable_transport(&error) {
-                        self.sleep_before_retry(attempt).await;
+                    if attempt < self.retry_policy.max_attempts
+                        && retryable
+                        && self
+                            .sleep_before_retry(attempt, None, retry_started_at)
+                            .await
+                    {
                         continue;
                     }
 
@@ -236,10 +749,11 @@ impl HttpTransport {
         model: Option<&str>,
         status_code: u16,
         request_id: Option<String>,
+        rate_limit: Option<RateLimitInfo>,
         response: Response,
     ) -> ProviderError {
         let message = match response.text().await {
-            Ok(body) if !body.trim().is_empty() => body,
+            Ok(body) if !body.trim().is_empty() => self.redactor.redact(&body),
             Ok(_) => format!("http status {status_code}"),
             Err(error) => {
                 format!("http status {status_code}; failed to read response body: {error}")
@@ -252,6 +766,7 @@ impl HttpTransport {
             status_code,
             request_id,
             message,
+            rate_limit: rate_limit.map(Box::new),
         }
     }
 
@@ -307,13 +822,231 @@ impl HttpTransport {
         Ok(())
     }
 
-    async fn sleep_before_retry(&self, attempt: u32) {
-        let retry_index = attempt.saturating_sub(1);
-        let backoff = self.retry_policy.backoff_duration_for_retry(retry_index);
+    /// Sleeps before the next attempt, or returns `false` without sleeping when the delay would
+    /// exceed the retry budget.
+    async fn sleep_before_retry(
+        &self,
+        attempt: u32,
+        retry_after_ms: Option<u64>,
+        retry_started_at: Instant,
+    ) -> bool {
+        let backoff = match retry_after_ms {
+            Some(retry_after_ms) => self.retry_policy.retry_after_duration(retry_after_ms),
+            None => {
+                let retry_index = attempt.saturating_sub(1);
+                self.retry_policy.backoff_duration_for_retry(retry_index)
+            }
+        };
+        if !self
+            .retry_policy
+            .retry_fits_budget(retry_started_at, backoff)
+        {
+            return false;
+        }
         tokio::time::sleep(backoff).await;
+        true
     }
 }
 
+struct RequestBody {
+    content_type: HeaderValue,
+    bytes: Vec<u8>,
+}
+
+fn json_body<TReq>(
+    provider: &ProviderId,
+    model: Option<&str>,
+    body: &TReq,
+) -> Result<RequestBody, ProviderError>
+where
+    TReq: Serialize + ?Sized,
+{
+    let bytes = serde_json::to_vec(body).map_err(|error| ProviderError::Serialization {
+        provider: provider.clone(),
+        model: model.map(str::to_string),
+        request_id: None,
+        message: error.to_string(),
+        source: Some(ErrorSource::new(error)),
+    })?;
+    Ok(RequestBody {
+        content_type: HeaderValue::from_static("application/json"),
+        bytes,
+    })
+}
+
+struct SentResponse {
+    response: Response,
+    status_code: u16,
+    request_id: Option<String>,
+    rate_limit: Option<RateLimitInfo>,
+    attempt: u32,
+    started_at: Instant,
+}
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn post_json(
+        &self,
+        provider: ProviderId,
+        model: Option<&str>,
+        url: &str,
+        body: &Value,
+        ctx: &AdapterContext,
+    ) -> Result<JsonResponse<Value>, ProviderError> {
+        self.post_json_response(provider, model, url, body, ctx)
+            .await
+    }
+
+    async fn get_json(
+        &self,
+        provider: ProviderId,
+        model: Option<&str>,
+        url: &str,
+        ctx: &AdapterContext,
+    ) -> Result<JsonResponse<Value>, ProviderError> {
+        self.execute_json_request(provider, model, Method::GET, url, None, ctx)
+            .await
+    }
+
+    async fn post_sse(
+        &self,
+        provider: ProviderId,
+        model: Option<&str>,
+        url: &str,
+        body: &Value,
+        ctx: &AdapterContext,
+    ) -> Result<SseStream, ProviderError> {
+        HttpTransport::post_sse(self, provider, model, url, body, ctx).await
+    }
+
+    async fn post_json_binary(
+        &self,
+        provider: ProviderId,
+        model: Option<&str>,
+        url: &str,
+        body: &Value,
+        ctx: &AdapterContext,
+    ) -> Result<BinaryResponse, ProviderError> {
+        HttpTransport::post_json_binary(self, provider, model, url, body, ctx).await
+    }
+
+    async fn post_multipart(
+        &self,
+        provider: ProviderId,
+        model: Option<&str>,
+        url: &str,
+        form: &MultipartForm,
+        ctx: &AdapterContext,
+    ) -> Result<JsonResponse<Value>, ProviderError> {
+        self.post_multipart_response(provider, model, url, form, ctx)
+            .await
+    }
+}
+
+/// Incrementally splits a response body into server-sent events.
+struct SseReader {
+    response: Option<Response>,
+    buffer: Vec<u8>,
+    provider: ProviderId,
+    model: Option<String>,
+    request_id: Option<String>,
+}
+
+impl SseReader {
+    /// Removes and parses the next complete event from the buffer. Events without data, such as
+    /// keep-alive comments, are skipped.
+    fn next_buffered_event(&mut self) -> Option<SseEvent> {
+        loop {
+            let end = self
+                .buffer
+                .windows(2)
+                .position(|window| window == b"\n\n")?;
+            let block = self.buffer.drain(..end + 2).collect::<Vec<_>>();
+            if let Some(event) = parse_sse_block(&String::from_utf8_lossy(&block[..end])) {
+                return Some(event);
+            }
+        }
+    }
+
+    fn stream_error(&self, message: String, source: ErrorSource) -> ProviderError {
+        ProviderError::Transport {
+            provider: self.provider.clone(),
+            request_id: self.request_id.clone(),
+            message: format!(
+                "failed to read event stream{}: {message}",
+                self.model
+                    .as_deref()
+                    .map(|model| format!(" for model {model}"))
+                    .unwrap_or_default()
+            ),
+            source: Some(source),
+        }
+    }
+}
+
+fn sse_stream(
+    response: Response,
+    provider: ProviderId,
+    model: Option<String>,
+    request_id: Option<String>,
+) -> SseStream {
+    let reader = SseReader {
+        response: Some(response),
+        buffer: Vec::new(),
+        provider,
+        model,
+        request_id,
+    };
+
+    Box::pin(stream::unfold(reader, |mut reader| async move {
+        loop {
+            if let Some(event) = reader.next_buffered_event() {
+                return Some((Ok(event), reader));
+            }
+
+            let response = reader.response.as_mut()?;
+            match response.chunk().await {
+                Ok(Some(chunk)) => reader
+                    .buffer
+                    .extend(chunk.iter().copied().filter(|byte| *byte != b'\r')),
+                Ok(None) => {
+                    reader.response = None;
+                    reader.buffer.extend_from_slice(b"\n\n");
+                }
+                Err(error) => {
+                    reader.response = None;
+                    let error = reader.stream_error(error.to_string(), ErrorSource::new(error));
+                    return Some((Err(error), reader));
+                }
+            }
+        }
+    }))
+}
+
+fn parse_sse_block(block: &str) -> Option<SseEvent> {
+    let mut event = SseEvent::default();
+    let mut data_lines = Vec::new();
+    for line in block.lines() {
+        if line.starts_with(':') {
+            continue;
+        }
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => event.event = Some(value.to_string()),
+            "data" => data_lines.push(value),
+            "id" => event.id = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    if data_lines.is_empty() {
+        return None;
+    }
+    event.data = data_lines.join("\n");
+    Some(event)
+}
+
 struct HeaderConfig {
     headers: HeaderMap,
     request_id_header: HeaderName,
@@ -339,7 +1072,85 @@ fn extract_request_id(headers: &HeaderMap, request_id_header: &HeaderName) -> Op
         .map(str::to_string)
 }
 
-fn is_retryable_transport(error: &reqwest::Error) -> bool {
+/// Reads OpenAI-style `x-ratelimit-*`, Anthropic `anthropic-ratelimit-*`, `retry-after-ms`, and
+/// `retry-after` headers. Returns `None` when the response carries none of them.
+fn extract_rate_limit(headers: &HeaderMap) -> Option<RateLimitInfo> {
+    let text = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let number = |name: &str| text(name).and_then(|value| value.parse::<u64>().ok());
+
+    let info = RateLimitInfo {
+        requests_limit: number("x-ratelimit-limit-requests")
+            .or_else(|| number("anthropic-ratelimit-requests-limit")),
+        requests_remaining: number("x-ratelimit-remaining-requests")
+            .or_else(|| number("anthropic-ratelimit-requests-remaining")),
+        requests_reset: text("x-ratelimit-reset-requests")
+            .or_else(|| text("anthropic-ratelimit-requests-reset")),
+        tokens_limit: number("x-ratelimit-limit-tokens")
+            .or_else(|| number("anthropic-ratelimit-tokens-limit")),
+        tokens_remaining: number("x-ratelimit-remaining-tokens")
+            .or_else(|| number("anthropic-ratelimit-tokens-remaining")),
+        tokens_reset: text("x-ratelimit-reset-tokens")
+            .or_else(|| text("anthropic-ratelimit-tokens-reset")),
+        retry_after_ms: number("retry-after-ms")
+            .or_else(|| text("retry-after").and_then(|value| parse_retry_after_ms(&value))),
+    };
+
+    (info != RateLimitInfo::default()).then_some(info)
+}
+
+/// `Retry-After` is either delay seconds or an HTTP date.
+pub(crate) fn parse_retry_after_ms(value: &str) -> Option<u64> {
+    if let Ok(seconds) = value.parse::<f64>() {
+        return (seconds.is_finite() && seconds >= 0.0).then_some((seconds * 1_000.0) as u64);
+    }
+
+    let retry_at = httpdate::parse_http_date(value).ok()?;
+    let delay = retry_at
+        .duration_since(SystemTime::now())
+        .unwrap_or(Duration::ZERO);
+    Some(u64::try_from(delay.as_millis()).unwrap_or(u64::MAX))
+}
+
+fn record_trace_attempt(
+    attempt: u32,
+    method: &Method,
+    url: &str,
+    status_code: Option<u16>,
+    request_id: Option<String>,
+    started_at: Instant,
+    error: Option<&ProviderError>,
+) {
+    #[cfg(feature = "tracing")]
+    crate::telemetry::record_http_attempt(
+        attempt,
+        status_code,
+        request_id.as_deref(),
+        started_at,
+        error,
+    );
+    if !trace::is_active() {
+        return;
+    }
+
+    trace::record_attempt(TraceAttempt {
+        attempt,
+        method: method.to_string(),
+        url: url.to_string(),
+        status_code,
+        request_id,
+        elapsed_ms: trace::elapsed_ms(started_at),
+        error: error.map(ToString::to_string),
+    });
+}
+
+pub(crate) fn is_retryable_transport(error: &reqwest::Error) -> bool {
     error.is_timeout() || error.is_connect() || error.is_request()
 }
 
@@ -8,9 +8,11 @@ use std::time::Duration;
 
 use serde::Deserialize;
 
-use crate::core::error::ProviderError;
-use crate::core::types::{AdapterContext, ProviderId};
-use crate::transport::http::{HttpTransport, RetryPolicy};
+use crate::core::error::{ConfigError, ProviderError};
+use crate::core::types::{AdapterContext, ProviderId, RateLimitInfo};
+use crate::transport::http::{HttpTransport, JitterMode, JsonResponse, RetryPolicy, TlsVersion};
+use crate::transport::redaction::DefaultRedactor;
+use crate::transport::{MultipartForm, SseEvent};
 
 #[derive(Debug, Clone)]
 struct MockResponse {
@@ -140,6 +142,9 @@ async fn test_http_transport_maps_status_errors() {
             initial_backoff_ms: 0,
             max_backoff_ms: 0,
             retryable_status_codes: vec![429],
+            max_retry_after_ms: 0,
+            max_total_retry_duration_ms: None,
+            jitter: JitterMode::None,
         },
     )
     .expect("create transport");
@@ -174,6 +179,46 @@ async fn test_http_transport_maps_status_errors() {
     assert_eq!(server.request_count(), 1);
 }
 
+#[tokio::test]
+async fn test_http_transport_redacts_status_bodies() {
+    let mut server = MockServer::start(vec![
+        MockResponse::new(
+            401,
+            Vec::new(),
+            r#"{"error":"invalid key sk-proj-abcdefghijklmnopqrst"}"#,
+        ),
+        MockResponse::new(400, Vec::new(), r#"{"error":"bad","prompt":"secret"}"#),
+    ]);
+    let ctx = AdapterContext::default();
+    let status_message = |result: Result<OkResponse, ProviderError>| match result {
+        Err(ProviderError::Status { message, .. }) => message,
+        other => panic!("expected ProviderError::Status, got {other:?}"),
+    };
+
+    let default = HttpTransport::new(1_000, RetryPolicy::default()).expect("create transport");
+    let message = status_message(
+        default
+            .get_json::<OkResponse>(ProviderId::Openai, None, &server.url(), &ctx)
+            .await,
+    );
+    assert_eq!(message, r#"{"error":"invalid key [REDACTED]"}"#);
+
+    let masking = HttpTransport::builder()
+        .with_redactor(Arc::new(
+            DefaultRedactor::new().with_message_content_masking(true),
+        ))
+        .build()
+        .expect("create transport");
+    let message = status_message(
+        masking
+            .get_json::<OkResponse>(ProviderId::Openai, None, &server.url(), &ctx)
+            .await,
+    );
+    assert_eq!(message, r#"{"error":"bad","prompt":"[REDACTED]"}"#);
+
+    server.shutdown();
+}
+
 #[tokio::test]
 async fn test_retry_policy_respects_max_attempts() {
     let max_attempts = 3;
@@ -189,6 +234,9 @@ async fn test_retry_policy_respects_max_attempts() {
             initial_backoff_ms: 0,
             max_backoff_ms: 0,
             retryable_status_codes: vec![429],
+            max_retry_after_ms: 0,
+            max_total_retry_duration_ms: None,
+            jitter: JitterMode::None,
         },
     )
     .expect("create transport");
@@ -327,3 +375,490 @@ fn status_reason(status_code: u16) -> &'static str {
         _ => "Unknown",
     }
 }
+
+#[tokio::test]
+async fn test_http_transport_preserves_deserialize_error_source() {
+    let mut server = MockServer::start(vec![MockResponse::new(200, vec![], r#"{"ok":"yes"}"#)]);
+
+    let transport = HttpTransport::new(1_000, RetryPolicy::default()).expect("create transport");
+    let result = transport
+        .get_json::<OkResponse>(
+            ProviderId::Openai,
+            Some("gpt-5-mini"),
+            &format!("{}/bad-shape", server.url()),
+            &AdapterContext::default(),
+        )
+        .await;
+
+    let error = result.expect_err("body shape mismatch should fail");
+    assert!(matches!(error, ProviderError::Serialization { .. }));
+    let source = std::error::Error::source(&error).expect("source should be preserved");
+    assert!(source.to_string().contains("error decoding response body"));
+
+    server.shutdown();
+}
+
+#[tokio::test]
+async fn test_http_transport_surfaces_rate_limit_headers() {
+    let mut server = MockServer::start(vec![
+        MockResponse::new(
+            200,
+            vec![
+                ("x-request-id".to_string(), "req-ok".to_string()),
+                ("x-ratelimit-limit-requests".to_string(), "500".to_string()),
+                (
+                    "x-ratelimit-remaining-requests".to_string(),
+                    "499".to_string(),
+                ),
+                (
+                    "x-ratelimit-reset-requests".to_string(),
+                    "120ms".to_string(),
+                ),
+                (
+                    "x-ratelimit-remaining-tokens".to_string(),
+                    "9000".to_string(),
+                ),
+            ],
+            r#"{"ok":true}"#,
+        ),
+        MockResponse::new(
+            429,
+            vec![
+                (
+                    "anthropic-ratelimit-tokens-remaining".to_string(),
+                    "0".to_string(),
+                ),
+                (
+                    "anthropic-ratelimit-tokens-reset".to_string(),
+                    "2026-01-01T00:00:30Z".to_string(),
+                ),
+                ("retry-after".to_string(), "1.5".to_string()),
+            ],
+            r#"{"error":"rate limit"}"#,
+        ),
+    ]);
+
+    let transport = HttpTransport::new(
+        1_000,
+        RetryPolicy {
+            max_attempts: 1,
+            initial_backoff_ms: 0,
+            max_backoff_ms: 0,
+            retryable_status_codes: vec![429],
+            max_retry_after_ms: 0,
+            max_total_retry_duration_ms: None,
+            jitter: JitterMode::None,
+        },
+    )
+    .expect("create transport");
+    let ctx = AdapterContext::default();
+    let url = format!("{}/limits", server.url());
+
+    let ok = transport
+        .post_json_response::<_, OkResponse>(
+            ProviderId::Openai,
+            None,
+            &url,
+            &serde_json::json!({}),
+            &ctx,
+        )
+        .await
+        .expect("request should succeed");
+    assert_eq!(ok.request_id.as_deref(), Some("req-ok"));
+    assert_eq!(
+        ok.rate_limit,
+        Some(RateLimitInfo {
+            requests_limit: Some(500),
+            requests_remaining: Some(499),
+            requests_reset: Some("120ms".to_string()),
+            tokens_remaining: Some(9_000),
+            ..Default::default()
+        })
+    );
+
+    let err = transport
+        .post_json_response::<_, OkResponse>(
+            ProviderId::Anthropic,
+            None,
+            &url,
+            &serde_json::json!({}),
+            &ctx,
+        )
+        .await
+        .expect_err("429 should fail");
+    match err {
+        ProviderError::Status { rate_limit, .. } => assert_eq!(
+            rate_limit.as_deref(),
+            Some(&RateLimitInfo {
+                tokens_remaining: Some(0),
+                tokens_reset: Some("2026-01-01T00:00:30Z".to_string()),
+                retry_after_ms: Some(1_500),
+                ..Default::default()
+            })
+        ),
+        other => panic!("expected ProviderError::Status, got {other:?}"),
+    }
+
+    server.shutdown();
+}
+
+#[tokio::test]
+async fn test_retry_after_overrides_backoff_and_is_capped() {
+    let mut server = MockServer::start(vec![
+        MockResponse::new(
+            503,
+            vec![(
+                "retry-after".to_string(),
+                "Wed, 21 Oct 2015 07:28:00 GMT".to_string(),
+            )],
+            r#"{"error":"unavailable"}"#,
+        ),
+        MockResponse::new(
+            429,
+            vec![("retry-after".to_string(), "3600".to_string())],
+            r#"{"error":"rate limit"}"#,
+        ),
+        MockResponse::new(200, vec![], r#"{"ok":true}"#),
+    ]);
+
+    let transport = HttpTransport::new(
+        1_000,
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff_ms: 60_000,
+            max_backoff_ms: 60_000,
+            retryable_status_codes: vec![429, 503],
+            max_retry_after_ms: 10,
+            max_total_retry_duration_ms: None,
+            jitter: JitterMode::None,
+        },
+    )
+    .expect("create transport");
+
+    let ctx = AdapterContext::default();
+    let started = std::time::Instant::now();
+    let result = transport
+        .get_json::<OkResponse>(
+            ProviderId::Openai,
+            None,
+            &format!("{}/retry-after", server.url()),
+            &ctx,
+        )
+        .await;
+
+    assert!(result.expect("third attempt should succeed").ok);
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    server.shutdown();
+    assert_eq!(server.request_count(), 3);
+}
+
+#[test]
+fn test_jitter_modes_stay_within_backoff_bounds() {
+    let policy = |jitter| RetryPolicy {
+        max_attempts: 5,
+        initial_backoff_ms: 400,
+        max_backoff_ms: 400,
+        retryable_status_codes: vec![429],
+        max_retry_after_ms: 0,
+        jitter,
+        max_total_retry_duration_ms: None,
+    };
+
+    let none = policy(JitterMode::None);
+    let full = policy(JitterMode::Full);
+    let equal = policy(JitterMode::Equal);
+    for _ in 0..50 {
+        assert_eq!(
+            none.backoff_duration_for_retry(0),
+            Duration::from_millis(400)
+        );
+        assert!(full.backoff_duration_for_retry(0) <= Duration::from_millis(400));
+        let equal_backoff = equal.backoff_duration_for_retry(0);
+        assert!(equal_backoff >= Duration::from_millis(200));
+        assert!(equal_backoff <= Duration::from_millis(400));
+    }
+}
+
+#[tokio::test]
+async fn test_retry_budget_stops_retries_that_would_overrun() {
+    let mut server = MockServer::start(vec![MockResponse::new(
+        503,
+        vec![],
+        r#"{"error":"unavailable"}"#,
+    )]);
+
+    let transport = HttpTransport::new(
+        1_000,
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff_ms: 60_000,
+            max_backoff_ms: 60_000,
+            retryable_status_codes: vec![503],
+            max_retry_after_ms: 0,
+            jitter: JitterMode::None,
+            max_total_retry_duration_ms: Some(500),
+        },
+    )
+    .expect("create transport");
+
+    let ctx = AdapterContext::default();
+    let started = std::time::Instant::now();
+    let result = transport
+        .get_json::<OkResponse>(
+            ProviderId::Openai,
+            None,
+            &format!("{}/budget", server.url()),
+            &ctx,
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(ProviderError::Status {
+            status_code: 503,
+            ..
+        })
+    ));
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    server.shutdown();
+    assert_eq!(server.request_count(), 1);
+}
+
+#[tokio::test]
+async fn test_post_sse_streams_parsed_events() {
+    use futures_util::StreamExt;
+
+    let mut server = MockServer::start(vec![MockResponse::new(
+        200,
+        vec![
+            ("content-type".to_string(), "text/event-stream".to_string()),
+            ("x-request-id".to_string(), "req-sse".to_string()),
+        ],
+        ": keep-alive\n\nevent: delta\ndata: {\"text\":\"a\"}\n\nid: 2\r\ndata: line one\r\ndata: line two\r\n\r\ndata: [DONE]",
+    )]);
+
+    let transport = HttpTransport::new(1_000, RetryPolicy::default()).expect("create transport");
+    let events = transport
+        .post_sse(
+            ProviderId::Openai,
+            None,
+            &format!("{}/stream", server.url()),
+            &serde_json::json!({ "stream": true }),
+            &AdapterContext::default(),
+        )
+        .await
+        .expect("stream should open")
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("events should parse");
+
+    assert_eq!(
+        events,
+        vec![
+            SseEvent {
+                event: Some("delta".to_string()),
+                data: r#"{"text":"a"}"#.to_string(),
+                id: None,
+            },
+            SseEvent {
+                event: None,
+                data: "line one\nline two".to_string(),
+                id: Some("2".to_string()),
+            },
+            SseEvent {
+                event: None,
+                data: "[DONE]".to_string(),
+                id: None,
+            },
+        ]
+    );
+
+    server.shutdown();
+    assert_eq!(
+        server.captured_headers()[0]
+            .get("accept")
+            .map(String::as_str),
+        Some("text/event-stream")
+    );
+}
+
+#[tokio::test]
+async fn test_builder_routes_requests_through_proxy() {
+    let mut server = MockServer::start(vec![MockResponse::new(200, vec![], r#"{"ok":true}"#)]);
+
+    let transport = HttpTransport::builder()
+        .with_timeout_ms(1_000)
+        .with_proxy(server.url())
+        .with_min_tls_version(TlsVersion::Tls12)
+        .build()
+        .expect("create transport");
+
+    let result = transport
+        .post_json::<serde_json::Value, OkResponse>(
+            ProviderId::Openai,
+            None,
+            "http://provider.example.invalid/v1/ping",
+            &serde_json::json!({ "ping": true }),
+            &AdapterContext::default(),
+        )
+        .await
+        .expect("proxied response");
+
+    assert_eq!(result, OkResponse { ok: true });
+    server.shutdown();
+    assert_eq!(server.request_count(), 1);
+}
+
+#[test]
+fn test_builder_rejects_invalid_proxy_and_certificates() {
+    let proxy_error = HttpTransport::builder()
+        .with_proxy("not a url")
+        .build()
+        .expect_err("invalid proxy should fail");
+    assert!(matches!(
+        proxy_error,
+        ConfigError::InvalidTransportConfig { ref reason } if reason.contains("proxy")
+    ));
+
+    let certificate_error = HttpTransport::builder()
+        .with_root_certificate_pem("not a certificate")
+        .build()
+        .expect_err("PEM without certificates should fail");
+    assert!(matches!(
+        certificate_error,
+        ConfigError::InvalidTransportConfig { ref reason } if reason.contains("certificate")
+    ));
+}
+
+#[tokio::test]
+async fn test_builder_applies_pool_and_keep_alive_tuning() {
+    let mut server = MockServer::start(vec![MockResponse::new(200, vec![], r#"{"ok":true}"#)]);
+
+    let transport = HttpTransport::builder()
+        .with_timeout_ms(1_000)
+        .with_pool_idle_timeout_ms(5_000)
+        .with_pool_max_idle_per_host(4)
+        .with_http2_keep_alive(10_000, Some(2_000))
+        .with_tcp_nodelay(true)
+        .build()
+        .expect("create transport");
+
+    let result = transport
+        .post_json::<serde_json::Value, OkResponse>(
+            ProviderId::Openai,
+            None,
+            &format!("{}/tuned", server.url()),
+            &serde_json::json!({ "ping": true }),
+            &AdapterContext::default(),
+        )
+        .await
+        .expect("successful response");
+    assert_eq!(result, OkResponse { ok: true });
+    server.shutdown();
+
+    let error = HttpTransport::builder()
+        .with_http2_keep_alive(0, None)
+        .build()
+        .expect_err("zero keep-alive interval should fail");
+    assert!(matches!(error, ConfigError::InvalidTransportConfig { .. }));
+}
+
+#[tokio::test]
+async fn test_rate_limiter_throttles_attempts() {
+    use crate::transport::ratelimit::{RateLimit, RateLimiter};
+
+    let mut server = MockServer::start(vec![
+        MockResponse::new(200, vec![], r#"{"ok":true}"#),
+        MockResponse::new(200, vec![], r#"{"ok":true}"#),
+    ]);
+    let limiter = Arc::new(
+        RateLimiter::new().with_limit(ProviderId::Openai, RateLimit::requests_per_second(5.0)),
+    );
+    let transport = HttpTransport::builder()
+        .with_timeout_ms(1_000)
+        .with_rate_limiter(limiter.clone())
+        .build()
+        .expect("create transport");
+
+    // Drain the burst allowance so the requests below have to wait for refills. Done after
+    // building the transport so client setup time does not refill the bucket.
+    for _ in 0..5 {
+        limiter.acquire(&ProviderId::Openai, 0).await;
+    }
+
+    let started = std::time::Instant::now();
+    for _ in 0..2 {
+        transport
+            .post_json::<serde_json::Value, OkResponse>(
+                ProviderId::Openai,
+                None,
+                &format!("{}/limited", server.url()),
+                &serde_json::json!({ "ping": true }),
+                &AdapterContext::default(),
+            )
+            .await
+            .expect("successful response");
+    }
+
+    assert!(started.elapsed() >= Duration::from_millis(350));
+    server.shutdown();
+    assert_eq!(server.request_count(), 2);
+}
+
+#[tokio::test]
+async fn test_post_json_binary_returns_raw_body_and_content_type() {
+    let mut server = MockServer::start(vec![MockResponse::new(
+        200,
+        vec![
+            ("content-type".to_string(), "audio/mpeg".to_string()),
+            ("x-request-id".to_string(), "req-audio".to_string()),
+        ],
+        "ID3-fake-mp3",
+    )]);
+
+    let transport = HttpTransport::new(1_000, RetryPolicy::default()).expect("create transport");
+    let response = transport
+        .post_json_binary(
+            ProviderId::Openai,
+            None,
+            &format!("{}/speech", server.url()),
+            &serde_json::json!({ "input": "hello" }),
+            &AdapterContext::default(),
+        )
+        .await
+        .expect("binary response should succeed");
+
+    assert_eq!(response.body, b"ID3-fake-mp3".to_vec());
+    assert_eq!(response.content_type.as_deref(), Some("audio/mpeg"));
+    assert_eq!(response.request_id.as_deref(), Some("req-audio"));
+    server.shutdown();
+}
+
+#[tokio::test]
+async fn test_post_multipart_sends_form_content_type() {
+    let mut server = MockServer::start(vec![MockResponse::new(200, vec![], r#"{"ok":true}"#)]);
+
+    let transport = HttpTransport::new(1_000, RetryPolicy::default()).expect("create transport");
+    let response: JsonResponse<OkResponse> = transport
+        .post_multipart_response(
+            ProviderId::Openai,
+            None,
+            &format!("{}/upload", server.url()),
+            &MultipartForm::new().file("file", "clip.wav", None, b"RIFF".to_vec()),
+            &AdapterContext::default(),
+        )
+        .await
+        .expect("multipart post should succeed");
+
+    assert_eq!(response.body, OkResponse { ok: true });
+    server.shutdown();
+    assert!(
+        server.captured_headers()[0]
+            .get("content-type")
+            .is_some_and(|value| value.starts_with("multipart/form-data; boundary="))
+    );
+}
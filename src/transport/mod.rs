@@ -1,4 +1,214 @@
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+
+use async_trait::async_trait;
+use futures_util::stream::BoxStream;
+use serde_json::Value;
+
+use crate::core::error::ProviderError;
+use crate::core::types::{AdapterContext, ProviderId};
+
 pub mod http;
+pub mod ratelimit;
+pub mod redaction;
+
+use http::{BinaryResponse, JsonResponse};
+
+/// One server-sent event. Multi-line `data:` fields are joined with `\n`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SseEvent {
+    pub event: Option<String>,
+    pub data: String,
+    pub id: Option<String>,
+}
+
+/// Events of a streaming response, in arrival order.
+pub type SseStream = BoxStream<'static, Result<SseEvent, ProviderError>>;
+
+/// A `multipart/form-data` request body, used for file uploads such as audio transcription.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MultipartForm {
+    parts: Vec<MultipartPart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MultipartPart {
+    name: String,
+    filename: Option<String>,
+    content_type: Option<String>,
+    data: Vec<u8>,
+}
+
+impl MultipartForm {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.parts.push(MultipartPart {
+            name: name.into(),
+            filename: None,
+            content_type: None,
+            data: value.into().into_bytes(),
+        });
+        self
+    }
+
+    pub fn file(
+        mut self,
+        name: impl Into<String>,
+        filename: impl Into<String>,
+        content_type: Option<String>,
+        data: Vec<u8>,
+    ) -> Self {
+        self.parts.push(MultipartPart {
+            name: name.into(),
+            filename: Some(filename.into()),
+            content_type,
+            data,
+        });
+        self
+    }
+
+    /// Text value of the field `name`, if the form has one.
+    pub fn text_field(&self, name: &str) -> Option<&str> {
+        self.parts
+            .iter()
+            .find(|part| part.name == name && part.filename.is_none())
+            .and_then(|part| std::str::from_utf8(&part.data).ok())
+    }
+
+    /// Filename and bytes of the file field `name`, if the form has one.
+    pub fn file_field(&self, name: &str) -> Option<(&str, &[u8])> {
+        self.parts.iter().find_map(|part| match &part.filename {
+            Some(filename) if part.name == name => Some((filename.as_str(), part.data.as_slice())),
+            _ => None,
+        })
+    }
+
+    /// Encodes the form, returning the `Content-Type` header value (with its boundary) and the
+    /// body bytes.
+    pub fn encode(&self) -> (String, Vec<u8>) {
+        let boundary = self.boundary();
+        let mut body = Vec::new();
+        for part in &self.parts {
+            body.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
+            let name = escape_quoted(&part.name);
+            match &part.filename {
+                Some(filename) => body.extend_from_slice(
+                    format!(
+                        "Content-Disposition: form-data; name=\"{name}\"; filename=\"{}\"\r\n",
+                        escape_quoted(filename)
+                    )
+                    .as_bytes(),
+                ),
+                None => body.extend_from_slice(
+                    format!("Content-Disposition: form-data; name=\"{name}\"\r\n").as_bytes(),
+                ),
+            }
+            if let Some(content_type) = &part.content_type {
+                body.extend_from_slice(format!("Content-Type: {content_type}\r\n").as_bytes());
+            }
+            body.extend_from_slice(b"\r\n");
+            body.extend_from_slice(&part.data);
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+        (format!("multipart/form-data; boundary={boundary}"), body)
+    }
+
+    /// A boundary that does not occur in any part's data.
+    fn boundary(&self) -> String {
+        let mut seed = RandomState::new().hash_one(self.parts.len());
+        loop {
+            let boundary = format!("provider-runtime-{seed:016x}");
+            if !self.parts.iter().any(|part| {
+                part.data
+                    .windows(boundary.len())
+                    .any(|window| window == boundary.as_bytes())
+            }) {
+                return boundary;
+            }
+            seed = seed.wrapping_add(1);
+        }
+    }
+}
+
+fn escape_quoted(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace(['\r', '\n'], " ")
+}
+
+/// The wire layer adapters send provider requests through.
+///
+/// `HttpTransport` is the reqwest-backed implementation. Custom implementations (test fakes,
+/// unix-socket clients, corporate gateways) receive the same `AdapterContext`, so they can honor
+/// the `transport.*` metadata keys adapters set for auth and custom headers.
+#[async_trait]
+pub trait Transport: Send + Sync {
+    async fn post_json(
+        &self,
+        provider: ProviderId,
+        model: Option<&str>,
+        url: &str,
+        body: &Value,
+        ctx: &AdapterContext,
+    ) -> Result<JsonResponse<Value>, ProviderError>;
+
+    async fn get_json(
+        &self,
+        provider: ProviderId,
+        model: Option<&str>,
+        url: &str,
+        ctx: &AdapterContext,
+    ) -> Result<JsonResponse<Value>, ProviderError>;
+
+    async fn post_sse(
+        &self,
+        provider: ProviderId,
+        model: Option<&str>,
+        url: &str,
+        body: &Value,
+        ctx: &AdapterContext,
+    ) -> Result<SseStream, ProviderError>;
+
+    /// Posts `body` and returns the raw response bytes. Transports that only speak JSON can keep
+    /// the default, which reports the call as unsupported.
+    async fn post_json_binary(
+        &self,
+        provider: ProviderId,
+        model: Option<&str>,
+        url: &str,
+        _body: &Value,
+        _ctx: &AdapterContext,
+    ) -> Result<BinaryResponse, ProviderError> {
+        Err(unsupported(provider, model, url, "binary responses"))
+    }
+
+    /// Posts a `multipart/form-data` body and decodes the JSON response. The default reports
+    /// the call as unsupported.
+    async fn post_multipart(
+        &self,
+        provider: ProviderId,
+        model: Option<&str>,
+        url: &str,
+        _form: &MultipartForm,
+        _ctx: &AdapterContext,
+    ) -> Result<JsonResponse<Value>, ProviderError> {
+        Err(unsupported(provider, model, url, "multipart uploads"))
+    }
+}
+
+fn unsupported(provider: ProviderId, model: Option<&str>, url: &str, what: &str) -> ProviderError {
+    ProviderError::Protocol {
+        provider,
+        model: model.map(str::to_string),
+        request_id: None,
+        message: format!("transport does not support {what} (POST {url})"),
+    }
+}
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,190 @@
+//! Client-side token-bucket rate limiting for provider calls.
+//!
+//! A `RateLimiter` holds independent buckets per provider so a burst of `ProviderRuntime::run`
+//! calls is spread out before it reaches the provider instead of coming back as 429s. Attach one
+//! to `HttpTransportBuilder::with_rate_limiter`; sharing the same `Arc` across adapters' transports
+//! makes them draw from the same budget. Implement `RateLimitBackend` to share the budget across
+//! processes instead, as the `redis` feature's `RedisRateLimiter` does.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::core::types::ProviderId;
+
+/// Budget `HttpTransport` draws from before every attempt.
+#[async_trait]
+pub trait RateLimitBackend: fmt::Debug + Send + Sync {
+    /// Waits until `provider` has room for one request of roughly `estimated_tokens` tokens,
+    /// then reserves it.
+    async fn acquire(&self, provider: &ProviderId, estimated_tokens: u64);
+}
+
+/// Limits applied to one provider. Unset limits are not enforced.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RateLimit {
+    /// Sustained request rate. Bursts of up to `max(1, requests_per_second)` requests pass
+    /// without waiting.
+    pub requests_per_second: Option<f64>,
+    /// Sustained token budget, counted from the caller's per-request estimate.
+    pub tokens_per_minute: Option<u64>,
+}
+
+impl RateLimit {
+    pub fn requests_per_second(requests_per_second: f64) -> Self {
+        Self {
+            requests_per_second: Some(requests_per_second),
+            tokens_per_minute: None,
+        }
+    }
+
+    pub fn with_tokens_per_minute(mut self, tokens_per_minute: u64) -> Self {
+        self.tokens_per_minute = Some(tokens_per_minute);
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    refill_per_second: f64,
+    available: f64,
+}
+
+impl Bucket {
+    fn new(capacity: f64, refill_per_second: f64) -> Self {
+        Self {
+            capacity,
+            refill_per_second,
+            available: capacity,
+        }
+    }
+
+    fn refill(&mut self, elapsed: Duration) {
+        self.available =
+            (self.available + elapsed.as_secs_f64() * self.refill_per_second).min(self.capacity);
+    }
+
+    /// Time until `amount` is available. Amounts above capacity are clamped so oversized
+    /// requests wait for a full bucket instead of forever.
+    fn wait_for(&self, amount: f64) -> Duration {
+        let missing = amount.min(self.capacity) - self.available;
+        if missing <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(missing / self.refill_per_second)
+        }
+    }
+
+    fn take(&mut self, amount: f64) {
+        self.available -= amount.min(self.capacity);
+    }
+}
+
+#[derive(Debug)]
+struct ProviderBuckets {
+    requests: Option<Bucket>,
+    tokens: Option<Bucket>,
+    refilled_at: Instant,
+}
+
+/// Per-provider token buckets for request rate and token throughput.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    buckets: Mutex<BTreeMap<ProviderId, ProviderBuckets>>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the limits for `provider`. Providers without limits are never delayed.
+    pub fn with_limit(self, provider: ProviderId, limit: RateLimit) -> Self {
+        let requests = limit
+            .requests_per_second
+            .filter(|rate| *rate > 0.0)
+            .map(|rate| Bucket::new(rate.max(1.0), rate));
+        let tokens = limit
+            .tokens_per_minute
+            .filter(|tokens| *tokens > 0)
+            .map(|tokens| Bucket::new(tokens as f64, tokens as f64 / 60.0));
+
+        self.lock().insert(
+            provider,
+            ProviderBuckets {
+                requests,
+                tokens,
+                refilled_at: Instant::now(),
+            },
+        );
+        self
+    }
+
+    /// Waits until `provider` has room for one request of roughly `estimated_tokens` tokens,
+    /// then reserves it.
+    pub async fn acquire(&self, provider: &ProviderId, estimated_tokens: u64) {
+        while let Some(wait) = self.try_acquire(provider, estimated_tokens) {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Reserves capacity if it is available now; otherwise returns how long to wait before
+    /// trying again.
+    fn try_acquire(&self, provider: &ProviderId, estimated_tokens: u64) -> Option<Duration> {
+        let mut buckets = self.lock();
+        let state = buckets.get_mut(provider)?;
+
+        let now = Instant::now();
+        let elapsed = now.saturating_duration_since(state.refilled_at);
+        state.refilled_at = now;
+        for bucket in [&mut state.requests, &mut state.tokens]
+            .into_iter()
+            .flatten()
+        {
+            bucket.refill(elapsed);
+        }
+
+        let tokens = estimated_tokens as f64;
+        let wait = state
+            .requests
+            .as_ref()
+            .map_or(Duration::ZERO, |bucket| bucket.wait_for(1.0))
+            .max(
+                state
+                    .tokens
+                    .as_ref()
+                    .map_or(Duration::ZERO, |bucket| bucket.wait_for(tokens)),
+            );
+        if !wait.is_zero() {
+            return Some(wait);
+        }
+
+        if let Some(bucket) = &mut state.requests {
+            bucket.take(1.0);
+        }
+        if let Some(bucket) = &mut state.tokens {
+            bucket.take(tokens);
+        }
+        None
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<ProviderId, ProviderBuckets>> {
+        self.buckets
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl RateLimitBackend for RateLimiter {
+    async fn acquire(&self, provider: &ProviderId, estimated_tokens: u64) {
+        RateLimiter::acquire(self, provider, estimated_tokens).await;
+    }
+}
+
+#[cfg(test)]
+mod tests;
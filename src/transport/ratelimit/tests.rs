@@ -0,0 +1,53 @@
+use std::time::{Duration, Instant};
+
+use super::{RateLimit, RateLimiter};
+use crate::core::types::ProviderId;
+
+#[tokio::test]
+async fn test_requests_per_second_allows_burst_then_waits() {
+    let limiter =
+        RateLimiter::new().with_limit(ProviderId::Openai, RateLimit::requests_per_second(10.0));
+
+    let started = Instant::now();
+    for _ in 0..10 {
+        limiter.acquire(&ProviderId::Openai, 0).await;
+    }
+    assert!(started.elapsed() < Duration::from_millis(50));
+
+    limiter.acquire(&ProviderId::Openai, 0).await;
+    assert!(started.elapsed() >= Duration::from_millis(80));
+}
+
+#[test]
+fn test_tokens_per_minute_reports_wait_and_clamps_oversized_requests() {
+    let limiter = RateLimiter::new().with_limit(
+        ProviderId::Anthropic,
+        RateLimit::default().with_tokens_per_minute(600),
+    );
+
+    assert_eq!(limiter.try_acquire(&ProviderId::Anthropic, 500), None);
+    let wait = limiter
+        .try_acquire(&ProviderId::Anthropic, 200)
+        .expect("budget exhausted");
+    assert!(wait > Duration::from_secs(9) && wait <= Duration::from_secs(10));
+
+    let wait = limiter
+        .try_acquire(&ProviderId::Anthropic, 10_000)
+        .expect("oversized request waits for a full bucket");
+    assert!(wait <= Duration::from_secs(50));
+}
+
+#[test]
+fn test_unlimited_providers_are_never_delayed() {
+    let limiter =
+        RateLimiter::new().with_limit(ProviderId::Openai, RateLimit::requests_per_second(1.0));
+
+    for _ in 0..100 {
+        assert_eq!(
+            limiter.try_acquire(&ProviderId::Openrouter, 1_000_000),
+            None
+        );
+    }
+    assert_eq!(limiter.try_acquire(&ProviderId::Openai, 0), None);
+    assert!(limiter.try_acquire(&ProviderId::Openai, 0).is_some());
+}
@@ -0,0 +1,110 @@
+use serde_json::Value;
+
+use crate::core::traits::Redactor;
+
+/// Replacement written over masked secrets and message content.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// JSON keys whose values carry prompt or completion text.
+const MESSAGE_CONTENT_KEYS: [&str; 7] = [
+    "content",
+    "text",
+    "prompt",
+    "input",
+    "instructions",
+    "system",
+    "arguments",
+];
+
+/// Shortest `sk-` prefixed word treated as an API key, so prose like "sk-learn" is kept.
+const MIN_API_KEY_LEN: usize = 20;
+
+/// The redactor transports and the runtime use unless configured otherwise.
+///
+/// Always masks the word after `Bearer` and `sk-` style API keys (OpenAI, Anthropic, and
+/// OpenRouter keys all share the prefix). With message content masking on, JSON bodies also
+/// have their `content`, `text`, `prompt`, `input`, `instructions`, `system`, and `arguments`
+/// values replaced, wherever they are nested.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DefaultRedactor {
+    mask_message_content: bool,
+}
+
+impl DefaultRedactor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Masks message content in JSON bodies, such as provider error bodies echoing the prompt.
+    /// Text that is not JSON only has its secrets masked.
+    pub fn with_message_content_masking(mut self, enabled: bool) -> Self {
+        self.mask_message_content = enabled;
+        self
+    }
+}
+
+impl Redactor for DefaultRedactor {
+    fn redact(&self, text: &str) -> String {
+        if self.mask_message_content
+            && let Ok(mut value) = serde_json::from_str::<Value>(text)
+            && (value.is_object() || value.is_array())
+        {
+            self.redact_json(&mut value);
+            return value.to_string();
+        }
+        mask_secrets(text)
+    }
+
+    fn redact_json(&self, value: &mut Value) {
+        match value {
+            Value::String(text) => *text = mask_secrets(text),
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_json(item)),
+            Value::Object(fields) => {
+                for (key, field) in fields.iter_mut() {
+                    if self.mask_message_content
+                        && !field.is_null()
+                        && MESSAGE_CONTENT_KEYS.contains(&key.as_str())
+                    {
+                        *field = Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact_json(field);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Replaces bearer tokens and API keys in `text`, keeping everything around them.
+fn mask_secrets(text: &str) -> String {
+    let mut masked = String::with_capacity(text.len());
+    let mut after_bearer = false;
+    let mut rest = text;
+    while let Some(start) = rest.find(is_token_char) {
+        let (gap, tail) = rest.split_at(start);
+        let end = tail.find(|c: char| !is_token_char(c)).unwrap_or(tail.len());
+        let (word, after) = tail.split_at(end);
+
+        let is_bearer_token = after_bearer && gap.chars().all(char::is_whitespace);
+        let is_api_key = word.starts_with("sk-") && word.len() >= MIN_API_KEY_LEN;
+        masked.push_str(gap);
+        masked.push_str(if is_bearer_token || is_api_key {
+            REDACTED
+        } else {
+            word
+        });
+        after_bearer = word.eq_ignore_ascii_case("bearer");
+        rest = after;
+    }
+    masked.push_str(rest);
+    masked
+}
+
+/// Characters of API keys, JWTs, and base64 tokens.
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~' | '+' | '/')
+}
+
+#[cfg(test)]
+mod tests;
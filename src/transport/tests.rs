@@ -1,3 +1,4 @@
+use crate::transport::MultipartForm;
 use crate::transport::http::{HttpTransport, RetryPolicy};
 
 #[test]
@@ -6,3 +7,34 @@ fn test_transport_exports_compile() {
     let transport = HttpTransport::new(1_000, policy);
     assert!(transport.is_ok());
 }
+
+#[test]
+fn test_multipart_form_encodes_text_and_file_parts() {
+    let form = MultipartForm::new().text("model", "whisper-1").file(
+        "file",
+        "clip \"1\".wav",
+        Some("audio/wav".to_string()),
+        b"RIFF".to_vec(),
+    );
+
+    let (content_type, body) = form.encode();
+    let boundary = content_type
+        .strip_prefix("multipart/form-data; boundary=")
+        .expect("multipart content type");
+    let body = String::from_utf8(body).expect("utf8 body");
+
+    assert_eq!(
+        body,
+        format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"model\"\r\n\r\nwhisper-1\r\n\
+             --{boundary}\r\nContent-Disposition: form-data; name=\"file\"; \
+             filename=\"clip \\\"1\\\".wav\"\r\nContent-Type: audio/wav\r\n\r\nRIFF\r\n\
+             --{boundary}--\r\n"
+        )
+    );
+    assert_eq!(form.text_field("model"), Some("whisper-1"));
+    assert_eq!(
+        form.file_field("file"),
+        Some(("clip \"1\".wav", b"RIFF".as_slice()))
+    );
+}
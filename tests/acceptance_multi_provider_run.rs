@@ -9,9 +9,9 @@ use std::time::Duration;
 
 use provider_runtime::ProviderRuntime;
 use provider_runtime::core::types::{
-    ContentPart, FinishReason, Message, MessageRole, ModelCatalog, ModelInfo, ModelRef, ProviderId,
-    ProviderRequest, ProviderResponse, ResponseFormat, ToolChoice, ToolDefinition, ToolResult,
-    ToolResultContent,
+    ContentPart, FinishReason, Message, MessageRole, ModelCatalog, ModelInfo, ModelRef,
+    PricingSource, ProviderId, ProviderRequest, ProviderResponse, ResponseFormat, ToolChoice,
+    ToolDefinition, ToolResult, ToolResultContent,
 };
 use provider_runtime::handoff::normalize_handoff_messages;
 use provider_runtime::pricing::{PriceRule, PricingTable};
@@ -168,6 +168,7 @@ fn request_for(
                 },
                 "required": ["expression"]
             }),
+            cache_hint: None,
         }]
     } else {
         Vec::new()
@@ -177,21 +178,28 @@ fn request_for(
         model: ModelRef {
             provider_hint,
             model_id: model_id.to_string(),
+            requirements: None,
         },
         messages: vec![Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "What is the weather today?".to_string(),
             }],
+            cache_hint: None,
         }],
         tools,
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::Text,
         temperature: None,
         top_p: None,
         max_output_tokens: None,
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     }
 }
 
@@ -209,6 +217,11 @@ fn runtime_multi_provider(
                 max_output_tokens: None,
                 supports_tools: true,
                 supports_structured_output: true,
+                pricing: None,
+                input_modalities: Vec::new(),
+                output_modalities: Vec::new(),
+                deprecated: false,
+                knowledge_cutoff: None,
             },
             ModelInfo {
                 provider: ProviderId::Anthropic,
@@ -218,6 +231,11 @@ fn runtime_multi_provider(
                 max_output_tokens: None,
                 supports_tools: true,
                 supports_structured_output: true,
+                pricing: None,
+                input_modalities: Vec::new(),
+                output_modalities: Vec::new(),
+                deprecated: false,
+                knowledge_cutoff: None,
             },
             ModelInfo {
                 provider: ProviderId::Openrouter,
@@ -227,6 +245,11 @@ fn runtime_multi_provider(
                 max_output_tokens: None,
                 supports_tools: true,
                 supports_structured_output: true,
+                pricing: None,
+                input_modalities: Vec::new(),
+                output_modalities: Vec::new(),
+                deprecated: false,
+                knowledge_cutoff: None,
             },
         ],
     };
@@ -256,6 +279,7 @@ fn project_response(response: &ProviderResponse) -> CanonicalProjection {
             ContentPart::Text { .. } => text_parts += 1,
             ContentPart::ToolCall { .. } => tool_call_parts += 1,
             ContentPart::ToolResult { .. } => tool_result_parts += 1,
+            _ => {}
         }
     }
 
@@ -452,14 +476,17 @@ async fn test_handoff_deterministic_behavior() {
             content: vec![ContentPart::Text {
                 text: "Find the weather then summarize".to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Assistant,
             content: openai_basic.output.content.clone(),
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Assistant,
             content: anthropic_tool.output.content.clone(),
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Tool,
@@ -470,8 +497,10 @@ async fn test_handoff_deterministic_behavior() {
                         text: "sunny and 68F".to_string(),
                     },
                     raw_provider_content: None,
+                    is_error: false,
                 },
             }],
+            cache_hint: None,
         },
     ];
     let original = history.clone();
@@ -510,6 +539,13 @@ async fn test_optional_cost_never_blocks_success() {
         model_pattern: "gpt-5.2*".to_string(),
         input_cost_per_token: 0.01,
         output_cost_per_token: 0.02,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: None,
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
     }]);
 
     let runtime = runtime_multi_provider(
@@ -571,17 +607,14 @@ async fn test_optional_cost_never_blocks_success() {
         "anthropic run should emit pricing.missing_rule"
     );
 
-    assert!(
-        openrouter.cost.is_none(),
-        "unpriced provider should not get cost"
-    );
-    assert!(
-        openrouter
-            .warnings
-            .iter()
-            .any(|warning| warning.code == "pricing.missing_rule"),
-        "openrouter run should emit pricing.missing_rule"
+    let openrouter_cost = openrouter
+        .cost
+        .expect("provider-reported cost should not need a pricing rule");
+    assert_eq!(
+        openrouter_cost.pricing_source,
+        PricingSource::ProviderReported
     );
+    assert_eq!(openrouter_cost.total_cost, 0.0002415);
 
     openai_server.shutdown();
     anthropic_server.shutdown();
@@ -279,6 +279,7 @@ fn assert_error_fingerprint(err: &ProviderError) -> String {
             status_code,
             request_id,
             message,
+            ..
         } => format!("status:{provider:?}:{model:?}:{status_code}:{request_id:?}:{message}"),
         ProviderError::CredentialsRejected {
             provider,
@@ -289,13 +290,16 @@ fn assert_error_fingerprint(err: &ProviderError) -> String {
             provider,
             request_id,
             message,
+            ..
         } => format!("transport:{provider:?}:{request_id:?}:{message}"),
         ProviderError::Serialization {
             provider,
             model,
             request_id,
             message,
+            ..
         } => format!("serialization:{provider:?}:{model:?}:{request_id:?}:{message}"),
+        other => format!("other:{other}"),
     }
 }
 
@@ -699,10 +703,7 @@ async fn test_anthropic_decode_fixture_contract() {
         if decode_fixture.ends_with("finish_reason_unknown.json") {
             assert_warning_codes(
                 &response,
-                &[
-                    "unknown_stop_reason",
-                    "unknown_content_block_mapped_to_text",
-                ],
+                &["unknown_stop_reason", "unknown_content_block_kept"],
             );
         }
 
@@ -889,7 +890,7 @@ async fn test_anthropic_contract_non_2xx_auth_maps_to_credentials_rejected() {
 async fn test_anthropic_contract_non_2xx_non_auth_maps_to_status() {
     let error_body = load_fixture_str("errors/status_non_auth_mapping.json");
     let response = MockResponse::with_status(
-        429,
+        503,
         vec![("request-id".to_string(), "req-contract-rate".to_string())],
         &error_body,
     );
@@ -909,10 +910,11 @@ async fn test_anthropic_contract_non_2xx_non_auth_maps_to_status() {
             status_code,
             request_id,
             message,
+            ..
         } => {
             assert_eq!(provider, ProviderId::Anthropic);
             assert_eq!(model, Some("claude-sonnet-4-5-20250929".to_string()));
-            assert_eq!(status_code, 429);
+            assert_eq!(status_code, 503);
             assert_eq!(request_id, Some("req-contract-rate".to_string()));
             assert!(message.contains("model: this-model-does-not-exist"));
             assert!(message.contains("not_found_error"));
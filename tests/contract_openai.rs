@@ -282,6 +282,7 @@ fn assert_error_fingerprint(err: &ProviderError) -> String {
             status_code,
             request_id,
             message,
+            ..
         } => format!("status:{provider:?}:{model:?}:{status_code}:{request_id:?}:{message}"),
         ProviderError::CredentialsRejected {
             provider,
@@ -292,13 +293,16 @@ fn assert_error_fingerprint(err: &ProviderError) -> String {
             provider,
             request_id,
             message,
+            ..
         } => format!("transport:{provider:?}:{request_id:?}:{message}"),
         ProviderError::Serialization {
             provider,
             model,
             request_id,
             message,
+            ..
         } => format!("serialization:{provider:?}:{model:?}:{request_id:?}:{message}"),
+        other => format!("other:{other}"),
     }
 }
 
@@ -868,7 +872,7 @@ async fn test_openai_contract_non_2xx_auth_maps_to_credentials_rejected() {
 async fn test_openai_contract_non_2xx_non_auth_maps_to_status() {
     let error_body = load_fixture_str("errors/status_non_auth_mapping.json");
     let response = MockResponse::with_status(
-        429,
+        503,
         vec![("x-request-id".to_string(), "req-contract-rate".to_string())],
         &error_body,
     );
@@ -888,10 +892,11 @@ async fn test_openai_contract_non_2xx_non_auth_maps_to_status() {
             status_code,
             request_id,
             message,
+            ..
         } => {
             assert_eq!(provider, ProviderId::Openai);
             assert_eq!(model, Some("gpt-5-mini".to_string()));
-            assert_eq!(status_code, 429);
+            assert_eq!(status_code, 503);
             assert_eq!(request_id, Some("req-contract-rate".to_string()));
             assert!(message.contains("requested model"));
             assert!(message.contains("invalid_request_error"));
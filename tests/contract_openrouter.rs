@@ -297,6 +297,7 @@ fn assert_error_fingerprint(err: &ProviderError) -> String {
             status_code,
             request_id,
             message,
+            ..
         } => format!("status:{provider:?}:{model:?}:{status_code}:{request_id:?}:{message}"),
         ProviderError::CredentialsRejected {
             provider,
@@ -307,13 +308,16 @@ fn assert_error_fingerprint(err: &ProviderError) -> String {
             provider,
             request_id,
             message,
+            ..
         } => format!("transport:{provider:?}:{request_id:?}:{message}"),
         ProviderError::Serialization {
             provider,
             model,
             request_id,
             message,
+            ..
         } => format!("serialization:{provider:?}:{model:?}:{request_id:?}:{message}"),
+        other => format!("other:{other}"),
     }
 }
 
@@ -700,8 +704,13 @@ async fn test_openrouter_decode_fixture_contract() {
             assert_usage_fields(&response, input, output, total, cached);
         }
         if let Some(text) = expected_text {
+            let first_text = response
+                .output
+                .content
+                .iter()
+                .find(|part| matches!(part, ContentPart::Text { .. }));
             assert!(matches!(
-                response.output.content.first(),
+                first_text,
                 Some(ContentPart::Text { text: actual }) if actual == text
             ));
         }
@@ -910,7 +919,7 @@ async fn test_openrouter_contract_non_2xx_auth_maps_to_credentials_rejected() {
 async fn test_openrouter_contract_non_2xx_non_auth_maps_to_status() {
     let error_body = load_fixture_str("errors/status_non_auth_mapping.json");
     let response = MockResponse::with_status(
-        429,
+        503,
         vec![("x-request-id".to_string(), "req-contract-rate".to_string())],
         &error_body,
     );
@@ -930,10 +939,11 @@ async fn test_openrouter_contract_non_2xx_non_auth_maps_to_status() {
             status_code,
             request_id,
             message,
+            ..
         } => {
             assert_eq!(provider, ProviderId::Openrouter);
             assert_eq!(model, Some("openai/gpt-5-mini".to_string()));
-            assert_eq!(status_code, 429);
+            assert_eq!(status_code, 503);
             assert_eq!(request_id, Some("req-contract-rate".to_string()));
             assert!(message.contains("openrouter error"));
             assert!(message.contains("not a valid model ID"));
@@ -20,6 +20,7 @@ impl ProviderAdapter for CompileAdapter {
             supports_structured_output: true,
             supports_thinking: false,
             supports_remote_discovery: false,
+            supports_parallel_tool_calls: false,
         }
     }
 
@@ -34,6 +35,7 @@ impl ProviderAdapter for CompileAdapter {
                     text: "ok".to_string(),
                 }],
                 structured_output: None,
+                annotations: Vec::new(),
             },
             usage: Usage::default(),
             cost: None,
@@ -42,6 +44,10 @@ impl ProviderAdapter for CompileAdapter {
             raw_provider_response: None,
             finish_reason: FinishReason::Stop,
             warnings: Vec::new(),
+            alternatives: Vec::new(),
+            response_id: None,
+            request_id: None,
+            rate_limit: None,
         })
     }
 
@@ -69,25 +75,35 @@ async fn test_core_exports_compile() {
         model: ModelRef {
             provider_hint: Some(ProviderId::Openai),
             model_id: "gpt-5-mini".to_string(),
+            requirements: None,
         },
         messages: vec![Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "hello".to_string(),
             }],
+            cache_hint: None,
         }],
         tools: vec![ToolDefinition {
             name: "lookup".to_string(),
             description: Some("tool".to_string()),
             parameters_schema: serde_json::json!({ "type": "object" }),
+            cache_hint: None,
+        }],
+        hosted_tools: vec![HostedTool::WebSearch {
+            allowed_domains: Vec::new(),
         }],
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::Text,
         temperature: None,
         top_p: None,
         max_output_tokens: None,
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     };
     let ctx = AdapterContext::default();
     let opts = DiscoveryOptions {
@@ -104,6 +120,7 @@ async fn test_core_exports_compile() {
         provider: ProviderId::Openai,
         request_id: Some("req_123".to_string()),
         message: "timeout".to_string(),
+        source: None,
     };
     let _runtime_error =
         RuntimeError::credential_missing(ProviderId::Openai, vec!["OPENAI_API_KEY".to_string()]);
@@ -15,7 +15,9 @@ use provider_runtime::core::types::{
 use provider_runtime::pricing::{PriceRule, PricingTable};
 use provider_runtime::providers::anthropic::AnthropicAdapter;
 use provider_runtime::providers::openai::OpenAiAdapter;
-use provider_runtime::providers::openrouter::OpenRouterAdapter;
+use provider_runtime::providers::openrouter::{
+    DebugOptions, OpenRouterAdapter, OpenRouterAdapterOptions,
+};
 use serde_json::json;
 
 const FIXTURE_ROOT: &str = "tests/fixtures/integration/runtime_mock_http";
@@ -153,6 +155,7 @@ fn request_for(
                 },
                 "required": ["expression"]
             }),
+            cache_hint: None,
         }]
     } else {
         Vec::new()
@@ -162,21 +165,28 @@ fn request_for(
         model: ModelRef {
             provider_hint,
             model_id: model_id.to_string(),
+            requirements: None,
         },
         messages: vec![Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "What is the weather today?".to_string(),
             }],
+            cache_hint: None,
         }],
         tools,
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::Text,
         temperature: None,
         top_p: None,
         max_output_tokens: None,
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     }
 }
 
@@ -278,6 +288,66 @@ async fn test_runtime_run_openai_mock() {
     );
 }
 
+#[tokio::test]
+async fn test_runtime_run_trace_capture_records_retries_and_payloads() {
+    let basic = load_fixture_str("openai/basic_chat_gpt-5.2.json");
+
+    let mut server = MockServer::start(vec![
+        MockResponse {
+            status_code: 503,
+            headers: vec![("x-request-id".to_string(), "req_retry".to_string())],
+            body: "{\"error\":{\"message\":\"busy\"}}".to_string(),
+        },
+        MockResponse {
+            status_code: 200,
+            headers: vec![("x-request-id".to_string(), "req_ok".to_string())],
+            body: basic,
+        },
+    ]);
+    let adapter = OpenAiAdapter::with_base_url(Some("test-key".to_string()), server.url())
+        .expect("create openai adapter");
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(adapter))
+        .with_run_trace_capture(4)
+        .build();
+
+    let response = runtime
+        .run(request_for(Some(ProviderId::Openai), "gpt-5-mini", false))
+        .await
+        .expect("openai run should succeed after retry");
+    assert_eq!(response.request_id.as_deref(), Some("req_ok"));
+
+    let trace = runtime
+        .run_trace("req_ok")
+        .expect("trace captured by request id");
+    assert_eq!(trace.provider, Some(ProviderId::Openai));
+    assert_eq!(trace.requested_model.model_id, "gpt-5-mini");
+    assert_eq!(
+        trace
+            .encoded_request
+            .as_ref()
+            .and_then(|body| body.get("model")),
+        Some(&json!("gpt-5-mini"))
+    );
+    assert_eq!(trace.attempts.len(), 2);
+    assert_eq!(trace.attempts[0].status_code, Some(503));
+    assert_eq!(trace.attempts[0].request_id.as_deref(), Some("req_retry"));
+    assert!(trace.attempts[0].error.is_some());
+    assert_eq!(trace.attempts[1].status_code, Some(200));
+    assert!(trace.attempts[1].error.is_none());
+    assert!(trace.raw_response.is_some());
+    assert_eq!(trace.response.as_ref(), Some(&response));
+    assert!(trace.error.is_none());
+    assert!(runtime.run_trace("req_retry").is_none());
+
+    let serialized = trace.to_json_pretty().expect("trace should serialize");
+    let round_trip: provider_runtime::runtime::trace::RunTrace =
+        serde_json::from_str(&serialized).expect("trace should deserialize");
+    assert_eq!(round_trip, trace);
+
+    server.shutdown();
+}
+
 #[tokio::test]
 async fn test_runtime_run_anthropic_mock() {
     let basic = load_fixture_str("anthropic/basic_chat_claude-opus-4-6.json");
@@ -324,6 +394,65 @@ async fn test_runtime_run_anthropic_mock() {
     );
 }
 
+#[tokio::test]
+async fn test_runtime_run_trace_captures_openrouter_debug_echo() {
+    let mut body: serde_json::Value = serde_json::from_str(&load_fixture_str(
+        "openrouter/basic_chat_openai.gpt-5.2.json",
+    ))
+    .expect("fixture should be json");
+    body["debug"] = json!({"echo_upstream_body": {"model": "gpt-5.2", "messages": []}});
+
+    let mut server = MockServer::start(vec![MockResponse::json(body.to_string())]);
+    let adapter = OpenRouterAdapter::with_base_url_and_options(
+        Some("test-key".to_string()),
+        server.url(),
+        OpenRouterAdapterOptions {
+            debug: Some(DebugOptions {
+                echo_upstream_body: true,
+            }),
+            ..Default::default()
+        },
+    )
+    .expect("create openrouter adapter");
+    let runtime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(adapter))
+        .with_run_trace_capture(1)
+        .build();
+
+    let response = runtime
+        .run(request_for(
+            Some(ProviderId::Openrouter),
+            "openai/gpt-5.2",
+            false,
+        ))
+        .await
+        .expect("openrouter run should succeed");
+    assert!(
+        response
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "debug_echo_enabled")
+    );
+
+    let trace = runtime
+        .recent_run_traces()
+        .pop()
+        .expect("trace should be captured");
+    assert_eq!(
+        trace
+            .encoded_request
+            .as_ref()
+            .and_then(|body| body.get("debug")),
+        Some(&json!({"echo_upstream_body": true}))
+    );
+    assert_eq!(
+        trace.provider_debug,
+        Some(json!({"echo_upstream_body": {"model": "gpt-5.2", "messages": []}}))
+    );
+
+    server.shutdown();
+}
+
 #[tokio::test]
 async fn test_runtime_run_openrouter_mock() {
     let basic = load_fixture_str("openrouter/basic_chat_openai.gpt-5.2.json");
@@ -403,6 +532,11 @@ async fn test_runtime_routing_and_warning_behavior() {
                 max_output_tokens: None,
                 supports_tools: true,
                 supports_structured_output: true,
+                pricing: None,
+                input_modalities: Vec::new(),
+                output_modalities: Vec::new(),
+                deprecated: false,
+                knowledge_cutoff: None,
             },
             ModelInfo {
                 provider: ProviderId::Openrouter,
@@ -412,6 +546,11 @@ async fn test_runtime_routing_and_warning_behavior() {
                 max_output_tokens: None,
                 supports_tools: true,
                 supports_structured_output: true,
+                pricing: None,
+                input_modalities: Vec::new(),
+                output_modalities: Vec::new(),
+                deprecated: false,
+                knowledge_cutoff: None,
             },
         ],
     };
@@ -421,6 +560,13 @@ async fn test_runtime_routing_and_warning_behavior() {
         model_pattern: "gpt-5.2*".to_string(),
         input_cost_per_token: 0.01,
         output_cost_per_token: 0.02,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: None,
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
     }]);
 
     let runtime = runtime_multi_provider(
@@ -459,10 +605,9 @@ async fn test_runtime_routing_and_warning_behavior() {
 
     assert_eq!(by_default.provider, ProviderId::Openrouter);
     assert!(
-        by_default.cost.is_none(),
-        "openrouter run should not get priced"
+        by_default.cost.is_some(),
+        "openrouter run should carry its provider-reported cost"
     );
-    assert_has_warning_code(&by_default, "pricing.missing_rule");
 
     openai_server.shutdown();
     anthropic_server.shutdown();
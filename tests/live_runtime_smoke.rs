@@ -128,6 +128,7 @@ fn weather_tool_definition() -> ToolDefinition {
             "required": ["city"],
             "additionalProperties": false
         }),
+        cache_hint: None,
     }
 }
 
@@ -150,6 +151,7 @@ fn response_debug_summary(response: &ProviderResponse) -> String {
             ContentPart::Text { .. } => text_parts += 1,
             ContentPart::ToolCall { .. } => tool_call_parts += 1,
             ContentPart::ToolResult { .. } => tool_result_parts += 1,
+            _ => {}
         }
     }
 
@@ -247,6 +249,7 @@ fn basic_request(provider: LiveProvider, model_id: &str) -> ProviderRequest {
         model: ModelRef {
             provider_hint: Some(provider.id()),
             model_id: model_id.to_string(),
+            requirements: None,
         },
         messages: vec![Message {
             role: MessageRole::User,
@@ -254,15 +257,21 @@ fn basic_request(provider: LiveProvider, model_id: &str) -> ProviderRequest {
                 text: "Reply with one short sentence confirming live smoke test success."
                     .to_string(),
             }],
+            cache_hint: None,
         }],
         tools: Vec::new(),
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::Text,
         temperature: None,
         top_p: None,
         max_output_tokens: Some(max_tokens_for(provider, LiveScenario::Basic)),
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     }
 }
 
@@ -271,21 +280,28 @@ fn tool_call_request(provider: LiveProvider, model_id: &str) -> ProviderRequest
         model: ModelRef {
             provider_hint: Some(provider.id()),
             model_id: model_id.to_string(),
+    requirements: None,
         },
         messages: vec![Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "Call exactly one tool named weather_lookup with city set to \"Boston\" before any text response. Do not answer with plain text before the tool call.".to_string(),
             }],
+            cache_hint: None,
         }],
         tools: vec![weather_tool_definition()],
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Required,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::Text,
         temperature: None,
         top_p: None,
         max_output_tokens: Some(max_tokens_for(provider, LiveScenario::ToolCall)),
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     }
 }
 
@@ -300,6 +316,7 @@ fn tool_result_roundtrip_request(
         model: ModelRef {
             provider_hint: Some(provider.id()),
             model_id: model_id.to_string(),
+    requirements: None,
         },
         messages: vec![
             Message {
@@ -308,10 +325,12 @@ fn tool_result_roundtrip_request(
                     text: "Use the provided tool result and return exactly one short weather summary sentence."
                         .to_string(),
                 }],
+                cache_hint: None,
             },
             Message {
                 role: MessageRole::Assistant,
                 content: vec![ContentPart::ToolCall { tool_call }],
+                cache_hint: None,
             },
             Message {
                 role: MessageRole::Tool,
@@ -326,18 +345,25 @@ fn tool_result_roundtrip_request(
                             }),
                         },
                         raw_provider_content: None,
+                        is_error: false,
                     },
                 }],
+                cache_hint: None,
             },
         ],
         tools: vec![weather_tool_definition()],
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Required,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::Text,
         temperature: None,
         top_p: None,
         max_output_tokens: Some(max_tokens_for(provider, LiveScenario::ToolRoundtrip)),
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     }
 }
 
@@ -346,15 +372,19 @@ fn structured_output_request(provider: LiveProvider, model_id: &str) -> Provider
         model: ModelRef {
             provider_hint: Some(provider.id()),
             model_id: model_id.to_string(),
+            requirements: None,
         },
         messages: vec![Message {
             role: MessageRole::User,
             content: vec![ContentPart::Text {
                 text: "Return JSON containing city and forecast fields for Boston.".to_string(),
             }],
+            cache_hint: None,
         }],
         tools: Vec::new(),
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::JsonSchema {
             name: "weather_schema".to_string(),
             schema: json!({
@@ -372,6 +402,9 @@ fn structured_output_request(provider: LiveProvider, model_id: &str) -> Provider
         max_output_tokens: Some(max_tokens_for(provider, LiveScenario::Structured)),
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     }
 }
 
@@ -412,6 +445,11 @@ fn provider_catalog_entry(provider: LiveProvider, model_id: &str) -> ModelInfo {
         max_output_tokens: None,
         supports_tools: true,
         supports_structured_output: true,
+        pricing: None,
+        input_modalities: Vec::new(),
+        output_modalities: Vec::new(),
+        deprecated: false,
+        knowledge_cutoff: None,
     }
 }
 
@@ -595,12 +633,14 @@ async fn run_cross_provider_handoff(source: LiveProvider, target: LiveProvider)
                 text: "Use the tool output and return exactly one short weather summary sentence in plain text."
                     .to_string(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Assistant,
             content: vec![ContentPart::ToolCall {
                 tool_call: source_tool_call.clone(),
             }],
+            cache_hint: None,
         },
         Message {
             role: MessageRole::Tool,
@@ -615,8 +655,10 @@ async fn run_cross_provider_handoff(source: LiveProvider, target: LiveProvider)
                         }),
                     },
                     raw_provider_content: None,
+                    is_error: false,
                 },
             }],
+            cache_hint: None,
         },
     ];
 
@@ -626,16 +668,22 @@ async fn run_cross_provider_handoff(source: LiveProvider, target: LiveProvider)
         model: ModelRef {
             provider_hint: Some(target.id()),
             model_id: target_model.to_string(),
+            requirements: None,
         },
         messages: normalized,
         tools: vec![weather_tool_definition()],
+        hosted_tools: Vec::new(),
         tool_choice: ToolChoice::Auto,
+        parallel_tool_calls: None,
         response_format: ResponseFormat::Text,
         temperature: None,
         top_p: None,
         max_output_tokens: Some(max_tokens_for(target, LiveScenario::Handoff)),
         stop: Vec::new(),
         metadata: BTreeMap::new(),
+        n: None,
+        seed: None,
+        provider_options: BTreeMap::new(),
     };
 
     let target_response = runtime
@@ -770,6 +818,13 @@ async fn live_partial_pricing_non_blocking() {
         model_pattern: format!("{openai_model}*"),
         input_cost_per_token: 0.00001,
         output_cost_per_token: 0.00002,
+        cached_input_cost_per_token: None,
+        cache_write_input_cost_per_token: None,
+        reasoning_cost_per_token: None,
+        currency: None,
+        tiers: Vec::new(),
+        per_request_cost: None,
+        per_web_search_cost: None,
     }]);
 
     let Some((runtime, models)) = runtime_for_providers(
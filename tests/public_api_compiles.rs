@@ -17,6 +17,7 @@ fn test_public_api_compiles() {
         content: vec![ContentPart::Text {
             text: "assistant text".to_string(),
         }],
+        cache_hint: None,
     }];
 
     let _normalized = provider_runtime::handoff::normalize_handoff_messages(
@@ -26,4 +27,31 @@ fn test_public_api_compiles() {
 
     let _runtime_path: provider_runtime::runtime::ProviderRuntime =
         ProviderRuntime::builder().build();
+
+    let _translator: Result<provider_runtime::providers::translate::Translator, _> =
+        provider_runtime::providers::translate::Translator::for_provider(
+            &provider_runtime::ProviderId::Anthropic,
+        );
+}
+
+#[test]
+fn test_prelude_covers_common_surface() {
+    use provider_runtime::prelude::*;
+    use std::sync::Arc;
+
+    let adapter =
+        MockAdapter::new(ProviderId::Openai).on(MockMatcher::any(), MockOutcome::text("ok"));
+    let _runtime: ProviderRuntime = ProviderRuntime::builder()
+        .with_adapter(Arc::new(adapter) as Arc<dyn ProviderAdapter>)
+        .with_pricing_table(PricingTable::default())
+        .build();
+
+    let _policy = RetryPolicy {
+        jitter: JitterMode::Full,
+        ..RetryPolicy::default()
+    };
+    let _context = RequestContext::new().with_tenant("acme");
+    let _error: Option<RuntimeError> = None;
+    let _report: Option<DiscoveryReport> = None;
+    let _registry_path: Option<provider_runtime::registry::ProviderRegistry> = None;
 }